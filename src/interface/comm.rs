@@ -4,6 +4,7 @@
 
 use crate::interface;
 use std::fs::read_to_string;
+use std::hash::{Hash, Hasher};
 use std::mem::size_of;
 use std::str::from_utf8;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
@@ -94,6 +95,18 @@ impl GenSockaddr {
             GenSockaddr::V6(_) => panic!("Invalid function called for this type of Sockaddr."),
         }
     }
+
+    //Returns the name of an abstract unix domain address, which starts with a null
+    // byte and has nothing to do with the filesystem, or None for a path. Abstract
+    // names are not null terminated but as long as the address given to us, so
+    // trailing null bytes are part of the name
+    pub fn abstract_name(&self) -> Option<&[u8]> {
+        match self {
+            GenSockaddr::Unix(unixaddr) => unixaddr.abstract_name(),
+            GenSockaddr::V4(_) => panic!("Invalid function called for this type of Sockaddr."),
+            GenSockaddr::V6(_) => panic!("Invalid function called for this type of Sockaddr."),
+        }
+    }
 }
 
 #[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
@@ -166,11 +179,57 @@ impl GenIpaddr {
     }
 }
 
+//The first SOCKADDR_UNIX_SIZE bytes are laid out as the C sockaddr_un and are
+// all that is ever copied in from or out to the user, while addrlen trails the
+// C structure and only tells where an abstract name ends
 #[repr(C)]
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Clone, Copy)]
 pub struct SockaddrUnix {
     pub sun_family: u16,
     pub sun_path: [u8; 108],
+    //how many bytes of the family and path were given
+    pub addrlen: usize,
+}
+
+//the size of the C structure, without addrlen
+pub const SOCKADDR_UNIX_SIZE: usize = size_of::<u16>() + 108;
+
+impl SockaddrUnix {
+    fn abstract_name(&self) -> Option<&[u8]> {
+        let pathlen = self.addrlen.checked_sub(size_of::<u16>())?;
+        if pathlen == 0 || self.sun_path[0] != 0 {
+            return None;
+        }
+        Some(&self.sun_path[1..pathlen])
+    }
+
+    //The bytes that name the address: an abstract name and its length, or a path
+    // up to its null terminator. addrlen itself is not part of the address, as
+    // the same path may be given with or without the bytes following it
+    fn name(&self) -> (bool, &[u8]) {
+        match self.abstract_name() {
+            Some(name) => (true, name),
+            None => {
+                let end = self.sun_path.iter().position(|&c| c == 0).unwrap_or(108);
+                (false, &self.sun_path[..end])
+            }
+        }
+    }
+}
+
+impl PartialEq for SockaddrUnix {
+    fn eq(&self, other: &Self) -> bool {
+        self.sun_family == other.sun_family && self.name() == other.name()
+    }
+}
+
+impl Eq for SockaddrUnix {}
+
+impl Hash for SockaddrUnix {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sun_family.hash(state);
+        self.name().hash(state);
+    }
 }
 
 pub fn new_sockaddr_unix(family: u16, path: &[u8]) -> SockaddrUnix {
@@ -183,6 +242,7 @@ pub fn new_sockaddr_unix(family: u16, path: &[u8]) -> SockaddrUnix {
     SockaddrUnix {
         sun_family: family,
         sun_path: array_path,
+        addrlen: size_of::<u16>() + pathlen,
     }
 }
 
@@ -605,6 +665,21 @@ impl FdSet {
     }
 }
 
+// Checks what a kernel socket is ready for without blocking, returning the
+// poll revents the host reports for it, or 0 if the host call fails
+pub fn kernel_poll(rawfd: i32, events: i16) -> i16 {
+    let mut pollfd = libc::pollfd {
+        fd: rawfd,
+        events,
+        revents: 0,
+    };
+    let result = unsafe { libc::poll(&mut pollfd, 1, 0) };
+    if result < 0 {
+        return 0;
+    }
+    pollfd.revents
+}
+
 // for unwrapping in kernel_select
 fn to_fdset_ptr(opt: Option<&mut FdSet>) -> *mut libc::fd_set {
     match opt {
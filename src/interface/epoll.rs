@@ -0,0 +1,391 @@
+#![allow(dead_code)]
+
+//! Readiness notification for epoll in the RustPOSIX interface
+//!
+//! ## Epoll Module
+//!
+//! This module provides the parts of epoll that don't depend on what the
+//! descriptors being watched are. An epoll instance keeps its interest list
+//! and a ready list of the descriptors that may have become ready since they
+//! were last looked at, so that epoll_wait only has to look at those rather
+//! than at the whole interest list.
+//!
+//! Whatever an in-memory descriptor is built on (pipes, message pipes, the
+//! pending connections of a Unix domain socket) holds an `EpollWaitQueue`, and
+//! wakes it whenever its state changes. Waking the queue puts the descriptor
+//! on the ready list of each epoll instance watching it. Descriptors backed by
+//! a kernel socket are instead added to a host epoll instance, which reports
+//! their changes the same way.
+//!
+//! A waiter blocks in the host epoll instance, which also watches an eventfd
+//! written whenever something is put on the empty ready list. This way both
+//! kinds of descriptors wake it up, and a signal interrupts the wait.
+//!
+//! To learn more about epoll
+//! [epoll(7)](https://man7.org/linux/man-pages/man7/epoll.7.html)
+use crate::interface;
+use crate::interface::errnos::{syscall_error, Errno};
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// the host epoll data of the eventfd, which no descriptor can have
+const EVENTFD_KEY: u64 = u64::MAX;
+// how many host events we take at once
+const HOST_EVENT_BATCH: usize = 64;
+
+/// # Description
+/// The registration of one descriptor with one epoll instance, handed to the
+/// wait queues of whatever the descriptor is built on. It only lives as long
+/// as the item in the interest list does, so wait queues hold on to it weakly
+/// and forget about it once the descriptor is removed from the instance.
+pub struct EpollWatch {
+    instance: interface::RustWeak<EpollInstance>,
+    fd: i32,
+}
+
+impl EpollWatch {
+    // Puts the watched descriptor on the ready list of its instance, if the
+    // instance is still around
+    fn notify(&self) {
+        if let Some(instance) = self.instance.upgrade() {
+            instance.mark_ready(self.fd);
+        }
+    }
+}
+
+/// # Description
+/// The epoll instances interested in an object whose readiness can change,
+/// which the object wakes whenever it does.
+#[derive(Default)]
+pub struct EpollWaitQueue {
+    watches: interface::Mutex<Vec<interface::RustWeak<EpollWatch>>>,
+}
+
+impl EpollWaitQueue {
+    pub fn new() -> EpollWaitQueue {
+        EpollWaitQueue {
+            watches: interface::Mutex::new(Vec::new()),
+        }
+    }
+
+    /// # Description
+    /// Adds a watch to the queue, unless it is already there.
+    pub fn register(&self, watch: &interface::RustRfc<EpollWatch>) {
+        let mut watches = self.watches.lock();
+        let weak = interface::RustRfc::downgrade(watch);
+        if !watches.iter().any(|w| w.ptr_eq(&weak)) {
+            watches.push(weak);
+        }
+    }
+
+    /// # Description
+    /// Notifies every epoll instance watching the queue that the object may
+    /// have changed, and forgets about the watches whose items are gone.
+    ///
+    /// # Returns
+    ///
+    /// True if any watch is left in the queue
+    pub fn wake(&self) -> bool {
+        let mut watches = self.watches.lock();
+        if watches.is_empty() {
+            return false;
+        }
+        watches.retain(|w| w.strong_count() > 0);
+        let live: Vec<interface::RustRfc<EpollWatch>> =
+            watches.iter().filter_map(|w| w.upgrade()).collect();
+        let remaining = !watches.is_empty();
+        drop(watches);
+
+        for watch in live {
+            watch.notify();
+        }
+        remaining
+    }
+}
+
+impl std::fmt::Debug for EpollWaitQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EpollWaitQueue")
+            .field("watches", &self.watches.lock().len())
+            .finish()
+    }
+}
+
+/// # Description
+/// A descriptor in the interest list of an epoll instance.
+///
+/// `source` tells apart what the descriptor referred to when it was added, so
+/// that the item can be dropped once the descriptor is closed and its number
+/// reused. An EPOLLONESHOT item is `disabled` once it has been reported, until
+/// it is modified again. `rawfd` is the kernel socket watched by the host epoll
+/// instance for the item, if any.
+pub struct EpollItem {
+    pub events: u32,
+    pub data: u64,
+    pub source: usize,
+    pub disabled: bool,
+    pub rawfd: i32,
+    pub watch: interface::RustRfc<EpollWatch>,
+}
+
+// The descriptors that may be ready, in the order they became so, along with
+// the set of them so that none is queued twice
+struct ReadyList {
+    order: VecDeque<i32>,
+    queued: HashSet<i32>,
+}
+
+/// # Description
+/// An epoll instance, shared by every descriptor referring to it. Along with
+/// the interest list and the ready list, it owns a host epoll instance and the
+/// eventfd used to wake up whoever waits on it.
+///
+/// Some descriptors can't be hooked up to a wait queue yet, like a socket that
+/// isn't connected. Those are kept in the `polled` set, and looked at on every
+/// pass of epoll_wait instead.
+pub struct EpollInstance {
+    pub interest: interface::Mutex<HashMap<i32, EpollItem>>,
+    ready: interface::Mutex<ReadyList>,
+    polled: interface::Mutex<HashSet<i32>>,
+    hostepfd: i32,
+    eventfd: i32,
+}
+
+impl EpollInstance {
+    /// # Description
+    /// Creates an epoll instance with an empty interest list.
+    ///
+    /// # Returns
+    ///
+    /// The instance upon success, or the error of the host when it could not
+    /// create its epoll instance or eventfd.
+    ///
+    /// # Errors
+    ///
+    /// * `EMFILE`, `ENFILE`, `ENOMEM` - as returned by the host
+    pub fn new() -> Result<interface::RustRfc<EpollInstance>, i32> {
+        let hostepfd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+        if hostepfd < 0 {
+            return Err(Self::host_error("epoll create"));
+        }
+        let eventfd = unsafe { libc::eventfd(0, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+        if eventfd < 0 {
+            let err = Self::host_error("epoll create");
+            unsafe { libc::close(hostepfd) };
+            return Err(err);
+        }
+        let mut event = libc::epoll_event {
+            events: libc::EPOLLIN as u32,
+            u64: EVENTFD_KEY,
+        };
+        unsafe { libc::epoll_ctl(hostepfd, libc::EPOLL_CTL_ADD, eventfd, &mut event) };
+
+        Ok(interface::RustRfc::new(EpollInstance {
+            interest: interface::Mutex::new(HashMap::new()),
+            ready: interface::Mutex::new(ReadyList {
+                order: VecDeque::new(),
+                queued: HashSet::new(),
+            }),
+            polled: interface::Mutex::new(HashSet::new()),
+            hostepfd,
+            eventfd,
+        }))
+    }
+
+    /// # Description
+    /// Creates the watch to hand to wait queues for the item of `fd`.
+    pub fn new_watch(self: &interface::RustRfc<Self>, fd: i32) -> interface::RustRfc<EpollWatch> {
+        interface::RustRfc::new(EpollWatch {
+            instance: interface::RustRfc::downgrade(self),
+            fd,
+        })
+    }
+
+    /// # Description
+    /// Puts `fd` on the ready list, waking up a waiter if the list was empty.
+    pub fn mark_ready(&self, fd: i32) {
+        let mut ready = self.ready.lock();
+        if !ready.queued.insert(fd) {
+            return;
+        }
+        ready.order.push_back(fd);
+        if ready.order.len() == 1 {
+            self.kick();
+        }
+    }
+
+    /// # Description
+    /// Puts descriptors back at the end of the ready list. This is how
+    /// level-triggered items stay ready, as they are looked at again by the
+    /// next epoll_wait, or by another thread already waiting.
+    pub fn requeue(&self, fds: &[i32]) {
+        let mut ready = self.ready.lock();
+        let was_empty = ready.order.is_empty();
+        for &fd in fds {
+            if ready.queued.insert(fd) {
+                ready.order.push_back(fd);
+            }
+        }
+        if was_empty && !ready.order.is_empty() {
+            self.kick();
+        }
+    }
+
+    /// # Description
+    /// Empties the ready list.
+    ///
+    /// # Returns
+    ///
+    /// The descriptors that were on it, in the order they were put there
+    pub fn take_ready(&self) -> Vec<i32> {
+        let mut ready = self.ready.lock();
+        ready.queued.clear();
+        ready.order.drain(..).collect()
+    }
+
+    /// # Description
+    /// Sets whether `fd` has to be looked at on every pass of epoll_wait, as
+    /// nothing will put it on the ready list.
+    pub fn set_polled(&self, fd: i32, polled: bool) {
+        let mut set = self.polled.lock();
+        if polled {
+            set.insert(fd);
+        } else {
+            set.remove(&fd);
+        }
+    }
+
+    /// # Description
+    /// Returns the descriptors looked at on every pass of epoll_wait.
+    pub fn polled(&self) -> Vec<i32> {
+        self.polled.lock().iter().copied().collect()
+    }
+
+    /// # Description
+    /// Checks whether `fd` is looked at on every pass of epoll_wait.
+    pub fn is_polled(&self, fd: i32) -> bool {
+        self.polled.lock().contains(&fd)
+    }
+
+    /// # Description
+    /// Watches the kernel socket `rawfd` for the item of `fd`. The socket is
+    /// watched edge-triggered for every event, as the item's events are only
+    /// applied once the socket is looked at.
+    ///
+    /// # Returns
+    ///
+    /// 0 upon success, or the error of the host.
+    pub fn host_add(&self, rawfd: i32, fd: i32) -> i32 {
+        let mut event = libc::epoll_event {
+            events: (libc::EPOLLIN
+                | libc::EPOLLOUT
+                | libc::EPOLLPRI
+                | libc::EPOLLRDHUP
+                | libc::EPOLLET) as u32,
+            u64: fd as u64,
+        };
+        if unsafe { libc::epoll_ctl(self.hostepfd, libc::EPOLL_CTL_ADD, rawfd, &mut event) } < 0 {
+            return Self::host_error("epoll ctl");
+        }
+        0
+    }
+
+    /// # Description
+    /// Stops watching the kernel socket `rawfd`. The host forgets about it by
+    /// itself once it is closed, so errors are ignored.
+    pub fn host_del(&self, rawfd: i32) {
+        let mut event = libc::epoll_event { events: 0, u64: 0 };
+        unsafe { libc::epoll_ctl(self.hostepfd, libc::EPOLL_CTL_DEL, rawfd, &mut event) };
+    }
+
+    /// # Description
+    /// Blocks until something is put on the ready list or a kernel socket
+    /// being watched changes, which puts it on the ready list, or until the
+    /// timeout expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout_ms` - how long to wait for in milliseconds, or -1 to wait
+    ///   until woken up
+    ///
+    /// # Returns
+    ///
+    /// 0 once woken up or timed out.
+    ///
+    /// # Errors
+    ///
+    /// * `EINTR` - the wait was interrupted by a signal
+    pub fn wait(&self, timeout_ms: i32) -> i32 {
+        let mut hostevents = [libc::epoll_event { events: 0, u64: 0 }; HOST_EVENT_BATCH];
+        let ret = unsafe {
+            libc::epoll_wait(
+                self.hostepfd,
+                hostevents.as_mut_ptr(),
+                HOST_EVENT_BATCH as i32,
+                timeout_ms,
+            )
+        };
+        if ret < 0 {
+            if interface::get_errno() == libc::EINTR {
+                return -(Errno::EINTR as i32);
+            }
+            return Self::host_error("epoll wait");
+        }
+
+        for hostevent in &hostevents[..ret as usize] {
+            let key = hostevent.u64;
+            if key == EVENTFD_KEY {
+                // reset the eventfd, so that the next wait blocks again
+                let mut count: u64 = 0;
+                unsafe {
+                    libc::read(
+                        self.eventfd,
+                        &mut count as *mut u64 as *mut libc::c_void,
+                        std::mem::size_of::<u64>(),
+                    )
+                };
+            } else {
+                self.mark_ready(key as i32);
+            }
+        }
+        0
+    }
+
+    // Wakes up whoever waits on the instance by making the eventfd readable
+    fn kick(&self) {
+        let one: u64 = 1;
+        unsafe {
+            libc::write(
+                self.eventfd,
+                &one as *const u64 as *const libc::c_void,
+                std::mem::size_of::<u64>(),
+            )
+        };
+    }
+
+    // Turns the errno of a failed host call into a syscall error
+    fn host_error(syscall: &str) -> i32 {
+        match Errno::from_discriminant(interface::get_errno()) {
+            Ok(i) => syscall_error(i, syscall, "The libc call failed"),
+            Err(()) => panic!("Unknown errno value from {} returned!", syscall),
+        }
+    }
+}
+
+impl Drop for EpollInstance {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.eventfd);
+            libc::close(self.hostepfd);
+        }
+    }
+}
+
+impl std::fmt::Debug for EpollInstance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EpollInstance")
+            .field("interest", &self.interest.lock().len())
+            .field("ready", &self.ready.lock().order.len())
+            .finish()
+    }
+}
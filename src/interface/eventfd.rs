@@ -0,0 +1,147 @@
+#![allow(dead_code)]
+
+//! In-Memory eventfd Implementation for the RustPOSIX interface
+//!
+//! ## Eventfd Module
+//!
+//! An eventfd is a 64 bit counter that is added to by writes and taken from
+//! by reads, which makes it a lightweight way for threads and cages to wake
+//! each other up. The counter is readable while it is nonzero and writable
+//! while adding one more to it would not overflow it.
+//!
+//! A blocked read or write waits on a condition variable, but only for a
+//! short while at a time, returning EAGAIN so that the calling syscall gets to
+//! check whether the cage has been cancelled, the same way pipes do. Every
+//! change to the counter wakes the wait queue of the eventfd, which is how
+//! epoll instances watching it learn about it.
+//!
+//! To learn more about eventfd
+//! [eventfd(2)](https://man7.org/linux/man-pages/man2/eventfd.2.html)
+use crate::interface;
+use crate::interface::errnos::{syscall_error, Errno};
+
+use parking_lot::{Condvar, Mutex};
+use std::fmt;
+
+// the largest value the counter can hold
+pub const EVENTFD_MAX: u64 = u64::MAX - 1;
+
+// how long a blocked read or write waits before giving the caller a chance to
+// check for cancellation
+const BLOCK_SLICE: interface::RustDuration = interface::RustDuration::from_millis(10);
+
+pub struct EmulatedEventFd {
+    counter: Mutex<u64>,
+    changed: Condvar,
+    semaphore: bool,
+    waiters: interface::EpollWaitQueue,
+}
+
+impl EmulatedEventFd {
+    /// # Description
+    /// Creates an eventfd counter starting out at `initval`. With `semaphore`
+    /// set, each read takes one from the counter instead of emptying it.
+    pub fn new(initval: u64, semaphore: bool) -> EmulatedEventFd {
+        EmulatedEventFd {
+            counter: Mutex::new(initval),
+            changed: Condvar::new(),
+            semaphore,
+            waiters: interface::EpollWaitQueue::new(),
+        }
+    }
+
+    /// # Description
+    /// Takes the value of the counter and resets it to zero, or takes one
+    /// from it for a semaphore eventfd.
+    ///
+    /// # Arguments
+    ///
+    /// * `nonblocking` - Whether to fail rather than wait while the counter is
+    ///   zero
+    ///
+    /// # Returns
+    ///
+    /// The value read, or EAGAIN if the counter is still zero, which for a
+    /// blocking read means it should be tried again after checking for
+    /// cancellation
+    pub fn read(&self, nonblocking: bool) -> Result<u64, i32> {
+        let mut counter = self.counter.lock();
+        if *counter == 0 && !nonblocking {
+            self.changed.wait_for(&mut counter, BLOCK_SLICE);
+        }
+        if *counter == 0 {
+            return Err(syscall_error(
+                Errno::EAGAIN,
+                "read",
+                "the eventfd counter is zero",
+            ));
+        }
+        let value = if self.semaphore { 1 } else { *counter };
+        *counter -= value;
+        drop(counter);
+        self.changed.notify_all();
+        self.waiters.wake();
+        Ok(value)
+    }
+
+    /// # Description
+    /// Adds `value` to the counter.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - What to add, which can't be 0xffffffffffffffff
+    /// * `nonblocking` - Whether to fail rather than wait while the counter has
+    ///   no room for the value
+    ///
+    /// # Returns
+    ///
+    /// 0 on success, or EAGAIN if there is still no room for the value, which
+    /// for a blocking write means it should be tried again after checking for
+    /// cancellation
+    pub fn write(&self, value: u64, nonblocking: bool) -> i32 {
+        if value == u64::MAX {
+            return syscall_error(
+                Errno::EINVAL,
+                "write",
+                "0xffffffffffffffff can't be added to an eventfd",
+            );
+        }
+        let mut counter = self.counter.lock();
+        if EVENTFD_MAX - *counter < value && !nonblocking {
+            self.changed.wait_for(&mut counter, BLOCK_SLICE);
+        }
+        if EVENTFD_MAX - *counter < value {
+            return syscall_error(Errno::EAGAIN, "write", "the eventfd counter would overflow");
+        }
+        *counter += value;
+        drop(counter);
+        if value > 0 {
+            self.changed.notify_all();
+            self.waiters.wake();
+        }
+        0
+    }
+
+    // the counter can be read while it is nonzero
+    pub fn is_readable(&self) -> bool {
+        *self.counter.lock() > 0
+    }
+
+    // at least one can be added to the counter without overflowing it
+    pub fn is_writable(&self) -> bool {
+        *self.counter.lock() < EVENTFD_MAX
+    }
+
+    pub fn waiters(&self) -> &interface::EpollWaitQueue {
+        &self.waiters
+    }
+}
+
+impl fmt::Debug for EmulatedEventFd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmulatedEventFd")
+            .field("counter", &*self.counter.lock())
+            .field("semaphore", &self.semaphore)
+            .finish()
+    }
+}
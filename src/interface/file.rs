@@ -8,13 +8,14 @@ use parking_lot::Mutex;
 use std::env;
 pub use std::ffi::CStr as RustCStr;
 use std::fs::{self, canonicalize, File, OpenOptions};
-use std::io::{IoSlice, Read, Seek, SeekFrom, Write};
+use std::io::{IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
 pub use std::path::{Component as RustPathComponent, Path as RustPath, PathBuf as RustPathBuf};
 use std::slice;
 use std::sync::Arc;
 pub use std::sync::LazyLock as RustLazyGlobal;
 
 use crate::interface::errnos::{syscall_error, Errno};
+use crate::interface::storage::{DataCopyOnWrite, DataWriteBack};
 use libc::{mmap, mremap, munmap, off64_t, MAP_SHARED, MREMAP_MAYMOVE, PROT_READ, PROT_WRITE};
 use std::convert::TryInto;
 use std::ffi::c_void;
@@ -44,6 +45,14 @@ pub struct EmulatedFile {
     filename: String,
     fobj: Option<Arc<Mutex<File>>>,
     filesize: usize,
+    // set when the host file only stages the data, which the storage backend
+    // keeps elsewhere, and has to be written back on sync and close
+    writeback: Option<Arc<dyn DataWriteBack>>,
+    // set when the storage backend may share the host file with other files,
+    // in which case `shared` says whether a copy has to be made before the
+    // first change
+    cow: Option<Arc<dyn DataCopyOnWrite>>,
+    shared: bool,
 }
 
 pub fn pathexists(filename: String) -> bool {
@@ -63,9 +72,66 @@ impl EmulatedFile {
             filename,
             fobj: Some(Arc::new(Mutex::new(f))),
             filesize,
+            writeback: None,
+            cow: None,
+            shared: false,
         })
     }
 
+    // Wraps a host file opened by a storage backend, which may hand in a
+    // write-back hook if the host file is only a staging copy of the data
+    pub fn from_file(
+        filename: String,
+        f: File,
+        filesize: usize,
+        writeback: Option<Arc<dyn DataWriteBack>>,
+    ) -> EmulatedFile {
+        EmulatedFile {
+            filename,
+            fobj: Some(Arc::new(Mutex::new(f))),
+            filesize,
+            writeback,
+            cow: None,
+            shared: false,
+        }
+    }
+
+    // Lets the storage backend give the file a copy of its own before it is
+    // changed, which it has to if `shared` is set
+    pub fn with_copy_on_write(
+        mut self,
+        cow: Arc<dyn DataCopyOnWrite>,
+        shared: bool,
+    ) -> EmulatedFile {
+        self.cow = Some(cow);
+        self.shared = shared;
+        self
+    }
+
+    // Notes that the storage backend now shares the host file with another
+    // file, so that the next change makes a copy first
+    pub fn mark_shared(&mut self) {
+        self.shared = self.cow.is_some();
+    }
+
+    // Makes the host file one the file does not share with any other file,
+    // copying the data if needed. Every change to the data goes through here
+    // first, and so does mapping the host file for writing
+    pub fn make_private(&mut self) -> std::io::Result<()> {
+        if !self.shared {
+            return Ok(());
+        }
+        if let (Some(cow), Some(f)) = (&self.cow, &self.fobj) {
+            let copy = {
+                let fobj = f.lock();
+                cow.copy_on_write(&self.filename, &fobj, self.filesize)?
+            };
+            self.fobj = Some(Arc::new(Mutex::new(copy)));
+        }
+        self.shared = false;
+        Ok(())
+    }
+
     fn new_metadata(filename: String) -> std::io::Result<EmulatedFile> {
         let f = OpenOptions::new()
             .read(true)
@@ -80,11 +146,26 @@ impl EmulatedFile {
             filename,
             fobj: Some(Arc::new(Mutex::new(f))),
             filesize: filesize as usize,
+            writeback: None,
+            cow: None,
+            shared: false,
         })
     }
 
     pub fn close(&self) -> std::io::Result<()> {
-        Ok(())
+        self.write_back()
+    }
+
+    // Hands the data back to the storage backend if it is only staged in the
+    // host file
+    pub fn write_back(&self) -> std::io::Result<()> {
+        match (&self.writeback, &self.fobj) {
+            (Some(writeback), Some(f)) => {
+                let fobj = f.lock();
+                writeback.write_back(&self.filename, &fobj, self.filesize)
+            }
+            _ => Ok(()),
+        }
     }
 
     pub fn shrink(&mut self, length: usize) -> std::io::Result<()> {
@@ -94,6 +175,7 @@ impl EmulatedFile {
                 self.filename
             );
         }
+        self.make_private()?;
         match &self.fobj {
             None => panic!("{} is already closed.", self.filename),
             Some(f) => {
@@ -111,9 +193,9 @@ impl EmulatedFile {
             Some(f) => {
                 let fobj = f.lock();
                 fobj.sync_data()?;
-                Ok(())
             }
         }
+        self.write_back()
     }
 
     pub fn fsync(&self) -> std::io::Result<()> {
@@ -122,9 +204,9 @@ impl EmulatedFile {
             Some(f) => {
                 let fobj = f.lock();
                 fobj.sync_all()?;
-                Ok(())
             }
         }
+        self.write_back()
     }
 
     pub fn sync_file_range(&self, offset: isize, nbytes: isize, flags: u32) -> i32 {
@@ -165,6 +247,27 @@ impl EmulatedFile {
         }
     }
 
+    // Wrapper around Rust's file object read_vectored_at function
+    // Reads from file at specified offset into the provided buffers, filling
+    // each one before moving on to the next
+    pub fn read_vectored_at(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        offset: usize,
+    ) -> std::io::Result<usize> {
+        match &self.fobj {
+            None => panic!("{} is already closed.", self.filename),
+            Some(f) => {
+                let fobj = f.lock();
+                if offset > self.filesize {
+                    panic!("Seek offset extends past the EOF!");
+                }
+                let bytes_read = fobj.read_vectored_at(bufs, offset as u64)?;
+                Ok(bytes_read)
+            }
+        }
+    }
+
     // Wrapper around Rust's file object write_at function
     // Writes from provided C-buffer into file at specified offset
     // We need to specify the offset for read/write operations because multiple
@@ -175,6 +278,7 @@ impl EmulatedFile {
         length: usize,
         offset: usize,
     ) -> std::io::Result<usize> {
+        self.make_private()?;
         let bytes_written;
 
         let buf = unsafe {
@@ -206,6 +310,7 @@ impl EmulatedFile {
         bufs: &[IoSlice<'_>],
         offset: usize,
     ) -> std::io::Result<usize> {
+        self.make_private()?;
         let mut total_bytes_written = 0; // To keep track of the total number of bytes written.
     
         if let Some(f) = &self.fobj {
@@ -236,6 +341,7 @@ impl EmulatedFile {
 
     // Write to entire file from provided bytes
     pub fn writefile_from_bytes(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.make_private()?;
         let length = buf.len();
         let offset = self.filesize;
 
@@ -247,7 +353,7 @@ impl EmulatedFile {
                     panic!("Seek offset extends past the EOF!");
                 }
                 fobj.seek(SeekFrom::Start(offset as u64))?;
-                fobj.write(buf)?;
+                fobj.write_all(buf)?;
             }
         }
 
@@ -258,19 +364,26 @@ impl EmulatedFile {
         Ok(())
     }
 
+    // Fills `count` bytes at `offset` with zeroes. The part of the range that
+    // lies past the end of the file is added by extending the file, which
+    // leaves a hole there instead of writing the zeroes out, so that sparse
+    // files stay sparse
     pub fn zerofill_at(&mut self, offset: usize, count: usize) -> std::io::Result<usize> {
-        let bytes_written;
-        let buf = vec![0; count];
-
+        self.make_private()?;
         match &self.fobj {
             None => panic!("{} is already closed.", self.filename),
             Some(f) => {
-                let mut fobj = f.lock();
+                let fobj = f.lock();
                 if offset > self.filesize {
                     panic!("Seek offset extends past the EOF!");
                 }
-                fobj.seek(SeekFrom::Start(offset as u64))?;
-                bytes_written = fobj.write(buf.as_slice())?;
+                let inside = std::cmp::min(count, self.filesize - offset);
+                if inside > 0 {
+                    fobj.write_all_at(&vec![0; inside], offset as u64)?;
+                }
+                if offset + count > self.filesize {
+                    fobj.set_len((offset + count) as u64)?;
+                }
             }
         }
 
@@ -278,7 +391,77 @@ impl EmulatedFile {
             self.filesize = offset + count;
         }
 
-        Ok(bytes_written)
+        Ok(count)
+    }
+
+    // Wrapper around the host's fallocate, which allocates (`mode` 0 or
+    // FALLOC_FL_KEEP_SIZE), punches a hole in (FALLOC_FL_PUNCH_HOLE) or zeroes
+    // (FALLOC_FL_ZERO_RANGE) the range of `len` bytes at `offset` of the file.
+    // When the host file system can't do this, the file is extended the same
+    // way `zerofill_at` does it and the range is filled with zero bytes, which
+    // reads back the same
+    pub fn fallocate(&mut self, mode: i32, offset: usize, len: usize) -> std::io::Result<()> {
+        self.make_private()?;
+        let end = offset + len;
+        let keep_size = mode & libc::FALLOC_FL_KEEP_SIZE != 0;
+
+        match &self.fobj {
+            None => panic!("{} is already closed.", self.filename),
+            Some(f) => {
+                let fobj = f.lock();
+                let ret = unsafe {
+                    libc::fallocate(fobj.as_raw_fd(), mode, offset as off64_t, len as off64_t)
+                };
+                if ret < 0 {
+                    let err = std::io::Error::last_os_error();
+                    if err.raw_os_error() != Some(libc::EOPNOTSUPP) {
+                        return Err(err);
+                    }
+                    let zeroing =
+                        mode & (libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_ZERO_RANGE) != 0;
+                    let zeroend = std::cmp::min(end, self.filesize);
+                    if zeroing && offset < zeroend {
+                        fobj.write_all_at(&vec![0; zeroend - offset], offset as u64)?;
+                    }
+                    if !keep_size && end > self.filesize {
+                        fobj.set_len(end as u64)?;
+                    }
+                }
+            }
+        }
+
+        if !keep_size && end > self.filesize {
+            self.filesize = end;
+        }
+
+        Ok(())
+    }
+
+    // Wrapper around the host's posix_fadvise, passing the advice about the
+    // range of `len` bytes at `offset` on to the file. Returns 0 or the
+    // error number
+    pub fn fadvise(&self, offset: usize, len: usize, advice: i32) -> i32 {
+        let fd = self.as_fd_handle_raw_int();
+        unsafe { libc::posix_fadvise(fd, offset as off64_t, len as off64_t, advice) }
+    }
+
+    // Wrapper around the host's lseek with SEEK_DATA or SEEK_HOLE as `whence`,
+    // finding the next data or hole in the file at or after `offset`. The
+    // host file has the same size as the emulated one, so the answer holds for
+    // both. Only lseek can find holes, which is why the position of the shared
+    // host file handle is moved; every other read or write gives its own offset
+    pub fn seek_data_or_hole(&self, offset: usize, whence: i32) -> std::io::Result<usize> {
+        match &self.fobj {
+            None => panic!("{} is already closed.", self.filename),
+            Some(f) => {
+                let fobj = f.lock();
+                let ret = unsafe { libc::lseek(fobj.as_raw_fd(), offset as off64_t, whence) };
+                if ret < 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(ret as usize)
+            }
+        }
     }
 
     //gets the raw fd handle (integer) from a rust fileobject
@@ -294,6 +477,39 @@ impl EmulatedFile {
 pub const COUNTMAPSIZE: usize = 8;
 pub const MAP_1MB: usize = usize::pow(2, 20);
 
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+// Builds the lookup table for the CRC-32 (IEEE 802.3) polynomial
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+// Computes the CRC-32 checksum of a byte slice, used to detect torn or
+// corrupted entries in the metadata log
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
 #[derive(Debug)]
 pub struct EmulatedFileMap {
     filename: String,
@@ -317,6 +533,11 @@ impl EmulatedFileMap {
             .open(filename.clone())
             .unwrap();
 
+        EmulatedFileMap::from_file(filename, f)
+    }
+
+    // Maps a host file opened by a storage backend as a log
+    pub fn from_file(filename: String, f: File) -> std::io::Result<EmulatedFileMap> {
         let mapsize = MAP_1MB - COUNTMAPSIZE;
         // set the file equal to where were mapping the count and the actual map
         let _newsize = f.set_len((COUNTMAPSIZE + mapsize) as u64).unwrap();
@@ -354,8 +575,9 @@ impl EmulatedFileMap {
     pub fn write_to_map(&mut self, bytes_to_write: &[u8]) -> std::io::Result<()> {
         let writelen = bytes_to_write.len();
 
-        // if we're writing past the current map, increase the map another 1MB
-        if writelen + self.count > self.mapsize {
+        // if we're writing past the current map, increase the map 1MB at a time
+        // until the write fits
+        while writelen + self.count > self.mapsize {
             self.extend_map();
         }
 
@@ -374,6 +596,11 @@ impl EmulatedFileMap {
         Ok(())
     }
 
+    // Returns how many bytes have been written to the map so far
+    pub fn bytes_written(&self) -> usize {
+        self.count
+    }
+
     fn extend_map(&mut self) {
         // open count and map to resize mmap, and file to increase file size
         let mut mapopt = self.map.lock();
@@ -567,4 +794,12 @@ mod tests {
             .unwrap();
         assert_eq!(buffer, new_content);
     }
+
+    #[test]
+    fn test_crc32() {
+        // the standard check value of CRC-32 (IEEE 802.3)
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+        assert_ne!(crc32(b"Hello, world!"), crc32(b"Hello, world?"));
+    }
 }
@@ -15,7 +15,7 @@ use std::cell::RefCell;
 pub use std::cmp::{max as rust_max, min as rust_min};
 pub use std::collections::VecDeque as RustDeque;
 use std::fs::File;
-pub use std::io::IoSlice as RustIOSlice;
+pub use std::io::{IoSlice as RustIOSlice, IoSliceMut as RustIOSliceMut};
 use std::io::{self, Read, Write};
 use std::slice;
 use std::str::{from_utf8, Utf8Error};
@@ -24,7 +24,7 @@ pub use std::sync::atomic::{
     AtomicU32 as RustAtomicU32, AtomicU64 as RustAtomicU64, AtomicUsize as RustAtomicUsize,
     Ordering as RustAtomicOrdering,
 };
-pub use std::sync::Arc as RustRfc;
+pub use std::sync::{Arc as RustRfc, Weak as RustWeak};
 pub use std::thread::spawn as helper_thread;
 
 use libc::{mmap, pthread_exit, pthread_kill, pthread_self, sched_yield};
@@ -37,7 +37,7 @@ pub use serde_cbor::{
 
 use crate::interface;
 use crate::interface::errnos::VERBOSE;
-use crate::interface::types::SigsetType;
+use crate::interface::types::{Rusage, SigsetType, TimeVal};
 use crate::safeposix::syscalls::fs_constants::SEM_VALUE_MAX;
 use std::sync::LazyLock;
 use std::time::Duration;
@@ -171,6 +171,43 @@ pub fn iovec_to_ioslice<'a>(
     }
 }
 
+// This function is the counterpart of `iovec_to_ioslice`, converting the
+// array of `IovecStruct` objects into mutable slices that can be read into
+pub fn iovec_to_ioslice_mut<'a>(
+    iovec: *const interface::IovecStruct,
+    iovcnt: i32,
+) -> Vec<RustIOSliceMut<'a>> {
+    unsafe {
+        std::slice::from_raw_parts(iovec, iovcnt as usize)
+            .iter()
+            .map(|current_iovec| {
+                let slice = std::slice::from_raw_parts_mut(
+                    current_iovec.iov_base as *mut u8,
+                    current_iovec.iov_len,
+                );
+                RustIOSliceMut::new(slice)
+            })
+            .collect()
+    }
+}
+
+// This function is the counterpart of `concat_iovec_to_slice`, copying `data`
+// into the buffers of the iovec array in order, filling each before moving on
+// to the next one. It returns the number of bytes copied, which is less than
+// the length of `data` if the buffers are too small to hold all of it
+pub fn scatter_to_iovec(iovec: *const interface::IovecStruct, iovcnt: i32, data: &[u8]) -> usize {
+    let mut copied = 0;
+    for mut buf in iovec_to_ioslice_mut(iovec, iovcnt) {
+        if copied == data.len() {
+            break;
+        }
+        let len = rust_min(buf.len(), data.len() - copied);
+        buf[..len].copy_from_slice(&data[copied..copied + len]);
+        copied += len;
+    }
+    copied
+}
+
 pub fn log_from_ptr(buf: *const u8, length: usize) {
     if let Ok(s) = from_utf8(unsafe { std::slice::from_raw_parts(buf, length) }) {
         log_to_stdout(s);
@@ -214,6 +251,35 @@ pub fn lind_threadkill(thread_id: u64, sig: i32) -> i32 {
     unsafe { pthread_kill(thread_id, sig) as i32 }
 }
 
+// returns the resource usage of the calling thread, which the cage it belongs
+// to adds up with the usage of its other threads
+pub fn lind_getrusage() -> Rusage {
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    unsafe { libc::getrusage(libc::RUSAGE_THREAD, &mut usage) };
+    let timeval = |tv: libc::timeval| TimeVal {
+        tv_sec: tv.tv_sec,
+        tv_usec: tv.tv_usec,
+    };
+    Rusage {
+        ru_utime: timeval(usage.ru_utime),
+        ru_stime: timeval(usage.ru_stime),
+        ru_maxrss: usage.ru_maxrss as i64,
+        ru_ixrss: usage.ru_ixrss as i64,
+        ru_idrss: usage.ru_idrss as i64,
+        ru_isrss: usage.ru_isrss as i64,
+        ru_minflt: usage.ru_minflt as i64,
+        ru_majflt: usage.ru_majflt as i64,
+        ru_nswap: usage.ru_nswap as i64,
+        ru_inblock: usage.ru_inblock as i64,
+        ru_oublock: usage.ru_oublock as i64,
+        ru_msgsnd: usage.ru_msgsnd as i64,
+        ru_msgrcv: usage.ru_msgrcv as i64,
+        ru_nsignals: usage.ru_nsignals as i64,
+        ru_nvcsw: usage.ru_nvcsw as i64,
+        ru_nivcsw: usage.ru_nivcsw as i64,
+    }
+}
+
 pub fn get_pthreadid() -> u64 {
     unsafe { pthread_self() as u64 }
 }
@@ -15,16 +15,22 @@
 //! paths.
 
 mod comm;
+mod epoll;
 pub mod errnos;
+mod eventfd;
 mod file;
 mod misc;
 mod pipe;
+mod storage;
 mod timer;
 pub mod types;
 pub use comm::*;
+pub use epoll::*;
 pub use errnos::*;
+pub use eventfd::*;
 pub use file::*;
 pub use misc::*;
 pub use pipe::*;
+pub use storage::*;
 pub use timer::*;
 pub use types::*;
@@ -14,27 +14,33 @@
 //!
 //! This implementation is also used internally by RustPOSIX to approximate Unix
 //! sockets by allocating two of these pipes bi-directionally.
+//! Unix datagram and seqpacket sockets instead use a variant of the pipe that
+//! queues whole messages, so that message boundaries are kept.
 //!
 //! We expose an API allowing to read and write to the pipe as well as check if
-//! pipe descriptors are reading for reading and writing via select/poll
+//! pipe descriptors are reading for reading and writing via select/poll.
+//! Every change to what a pipe is ready for wakes its wait queue, which is how
+//! epoll instances watching the pipe learn about it
 ///
 /// To learn more about pipes
 /// [pipe(7)](https://man7.org/linux/man-pages/man7/pipe.7.html)
 use crate::interface;
 use crate::interface::errnos::{syscall_error, Errno};
 
-use parking_lot::Mutex;
+use parking_lot::{Mutex, MutexGuard};
 use ringbuf::{Consumer, Producer, RingBuffer};
 use std::cmp::min;
+use std::collections::VecDeque;
 use std::fmt;
 use std::slice;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 
 // lets define a few constants for permission flags and the standard size of a
 // Linux page
 const O_RDONLY: i32 = 0o0;
 const O_WRONLY: i32 = 0o1;
+const O_RDWR: i32 = 0o2;
 const O_RDWRFLAGS: i32 = 0o3;
 const PAGE_SIZE: usize = 4096;
 
@@ -47,14 +53,17 @@ const CANCEL_CHECK_INTERVAL: usize = 1048576;
 /// # Description
 /// In-memory pipe struct of given size which contains references to read and
 /// write ends of a lock-free ringbuffer, as well as reference counters to each
-/// end
+/// end. It also counts the bytes that have ever gone in and out of the pipe, so
+/// that Unix sockets can tie ancillary data to a position in the byte stream
 #[derive(Clone)]
 pub struct EmulatedPipe {
     write_end: Arc<Mutex<Producer<u8>>>,
     read_end: Arc<Mutex<Consumer<u8>>>,
     refcount_write: Arc<AtomicU32>,
     refcount_read: Arc<AtomicU32>,
-    size: usize,
+    total_written: Arc<AtomicU64>,
+    total_read: Arc<AtomicU64>,
+    waiters: Arc<interface::EpollWaitQueue>,
 }
 
 impl EmulatedPipe {
@@ -79,10 +88,31 @@ impl EmulatedPipe {
             read_end: Arc::new(Mutex::new(cons)),
             refcount_write: Arc::new(AtomicU32::new(1)),
             refcount_read: Arc::new(AtomicU32::new(1)),
-            size: size,
+            total_written: Arc::new(AtomicU64::new(0)),
+            total_read: Arc::new(AtomicU64::new(0)),
+            waiters: Arc::new(interface::EpollWaitQueue::new()),
         }
     }
 
+    /// # Description
+    /// Creates an in-memory pipe object of specified size with neither of its
+    /// ends open yet. This is how the pipe of a FIFO starts out, its ends are
+    /// then opened as the FIFO is opened for reading and for writing.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Size of the pipe in bytes
+    ///
+    /// # Returns
+    ///
+    /// EmulatedPipe object
+    pub fn new_unopened_with_capacity(size: usize) -> EmulatedPipe {
+        let pipe = EmulatedPipe::new_with_capacity(size);
+        pipe.refcount_write.store(0, Ordering::Relaxed);
+        pipe.refcount_read.store(0, Ordering::Relaxed);
+        pipe
+    }
+
     /// # Description
     /// Checks the references to each end of the pipe to determine if its closed
     /// Necessary for determining if Unix sockets are closed for each direction
@@ -108,20 +138,110 @@ impl EmulatedPipe {
         self.refcount_read.load(Ordering::Relaxed)
     }
 
+    /// # Description
+    /// Checks whether every reference to the write end has been closed, in
+    /// which case nothing more can ever be written to the pipe.
+    pub fn is_write_closed(&self) -> bool {
+        self.get_write_ref() == 0
+    }
+
+    /// # Description
+    /// Checks whether every reference to the read end has been closed, in
+    /// which case writing to the pipe fails.
+    pub fn is_read_closed(&self) -> bool {
+        self.get_read_ref() == 0
+    }
+
+    /// # Description
+    /// Checks whether there is anything in the pipe to be read.
+    pub fn has_data(&self) -> bool {
+        !self.read_end.lock().is_empty()
+    }
+
+    /// # Description
+    /// The wait queue of the epoll instances watching either end of the pipe.
+    pub fn waiters(&self) -> &interface::EpollWaitQueue {
+        &self.waiters
+    }
+
+    /// # Description
+    /// Returns the number of bytes the pipe can hold.
+    pub fn capacity(&self) -> usize {
+        self.write_end.lock().capacity()
+    }
+
+    /// # Description
+    /// Checks whether both pipe objects refer to the same pipe.
+    pub fn same_pipe(&self, other: &EmulatedPipe) -> bool {
+        Arc::ptr_eq(&self.read_end, &other.read_end)
+    }
+
+    /// # Description
+    /// Changes the number of bytes the pipe can hold, keeping whatever is in
+    /// the pipe. Both ends of the pipe are locked while the contents are moved
+    /// over to the new buffer, so no reader or writer sees the pipe in between.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The new size of the pipe in bytes
+    ///
+    /// # Returns
+    ///
+    /// 0 on success, or an error if the contents of the pipe don't fit
+    ///
+    /// # Errors
+    ///
+    /// * `EBUSY` - There is more in the pipe than `size` bytes
+    pub fn resize(&self, size: usize) -> i32 {
+        // the read end is always locked before the write end
+        let mut read_end = self.read_end.lock();
+        let mut write_end = self.write_end.lock();
+        if read_end.len() > size {
+            return syscall_error(
+                Errno::EBUSY,
+                "fcntl",
+                "the pipe holds more data than the requested size",
+            );
+        }
+        let (mut prod, cons) = RingBuffer::<u8>::new(size).split();
+        read_end.move_to(&mut prod, None);
+        *write_end = prod;
+        *read_end = cons;
+        drop(write_end);
+        drop(read_end);
+        // a smaller or larger pipe may have become ready for something else
+        self.waiters.wake();
+        0
+    }
+
+    /// # Description
+    /// Returns the number of bytes written to the pipe since it was created.
+    /// A byte is only counted once it can be read from the pipe.
+    pub fn total_written(&self) -> u64 {
+        self.total_written.load(Ordering::Acquire)
+    }
+
+    /// # Description
+    /// Returns the number of bytes read from the pipe since it was created.
+    pub fn total_read(&self) -> u64 {
+        self.total_read.load(Ordering::Acquire)
+    }
+
     /// # Description
     /// Increase references to write or read end.
     /// This is called when a reference to the pipe end is duplicated in cases
-    /// such as fork() and dup/dup2().
+    /// such as fork() and dup/dup2(). A FIFO opened for both reading and
+    /// writing refers to both ends.
     ///
     /// # Arguments
     ///
     /// * `flags` - Flags set on pipe descriptor, used to determine if its the
     ///   read or write end
     pub fn incr_ref(&self, flags: i32) {
-        if (flags & O_RDWRFLAGS) == O_RDONLY {
+        if (flags & O_RDWRFLAGS) == O_RDONLY || (flags & O_RDWRFLAGS) == O_RDWR {
             self.refcount_read.fetch_add(1, Ordering::Relaxed);
         }
-        if (flags & O_RDWRFLAGS) == O_WRONLY {
+        if (flags & O_RDWRFLAGS) == O_WRONLY || (flags & O_RDWRFLAGS) == O_RDWR {
             self.refcount_write.fetch_add(1, Ordering::Relaxed);
         }
     }
@@ -136,12 +256,14 @@ impl EmulatedPipe {
     /// * `flags` - Flags set on pipe descriptor, used to determine if its the
     ///   read or write end
     pub fn decr_ref(&self, flags: i32) {
-        if (flags & O_RDWRFLAGS) == O_RDONLY {
+        if (flags & O_RDWRFLAGS) == O_RDONLY || (flags & O_RDWRFLAGS) == O_RDWR {
             self.refcount_read.fetch_sub(1, Ordering::Relaxed);
         }
-        if (flags & O_RDWRFLAGS) == O_WRONLY {
+        if (flags & O_RDWRFLAGS) == O_WRONLY || (flags & O_RDWRFLAGS) == O_RDWR {
             self.refcount_write.fetch_sub(1, Ordering::Relaxed);
         }
+        // the other end may now see a hang up
+        self.waiters.wake();
     }
 
     /// # Description
@@ -161,7 +283,7 @@ impl EmulatedPipe {
 
     /// # Description
     /// Checks if pipe is currently ready for writing, used by select/poll etc.
-    /// A pipe descriptor is ready for writing if there is more than a page
+    /// A pipe descriptor is ready for writing if there is at least a page
     /// (4096 bytes) of room in the pipe or if there are 0 remaining read
     /// references.
     ///
@@ -174,7 +296,7 @@ impl EmulatedPipe {
 
         // Linux considers a pipe writeable if there is at least PAGE_SIZE (PIPE_BUF)
         // remaining space (4096 bytes)
-        return pipe_space > PAGE_SIZE || self.get_read_ref() == 0;
+        return pipe_space >= PAGE_SIZE || self.get_read_ref() == 0;
     }
 
     /// ### Description
@@ -272,8 +394,8 @@ impl EmulatedPipe {
                     }
                 } else {
                     // we yield here on a non-writable pipe to let other threads continue more
-                    // quickly
-                    interface::lind_yield();
+                    // quickly, letting go of the write end meanwhile so that it can be resized
+                    MutexGuard::unlocked(&mut write_end, interface::lind_yield);
                     continue;
                 }
             };
@@ -281,7 +403,10 @@ impl EmulatedPipe {
             // lets read the minimum of the specified amount or whatever space we have
             let bytes_to_write = min(length, bytes_written as usize + remaining);
             write_end.push_slice(&buf[bytes_written..bytes_to_write]);
+            self.total_written
+                .fetch_add((bytes_to_write - bytes_written) as u64, Ordering::Release);
             bytes_written = bytes_to_write;
+            self.waiters.wake();
         }
 
         // lets return the amount we've written to the pipe
@@ -431,9 +556,14 @@ impl EmulatedPipe {
         // wait for something to be in the pipe, but break on eof
         let mut count = 0;
         while pipe_space == 0 {
-            // If write references are 0, we've reached EOF so return 0
+            // If write references are 0, we've reached EOF so return 0, unless the
+            // last writer put something in the pipe since we last looked
             if self.get_write_ref() == 0 {
-                return 0;
+                pipe_space = read_end.len();
+                if pipe_space == 0 {
+                    return 0;
+                }
+                break;
             }
 
             // we return EAGAIN here so we can go back to check if this cage has been sent a
@@ -448,8 +578,10 @@ impl EmulatedPipe {
             count = count + 1;
 
             if pipe_space == 0 {
-                // we yield here on an empty pipe to let other threads continue more quickly
-                interface::lind_yield();
+                // we yield here on an empty pipe to let other threads continue more quickly,
+                // letting go of the read end meanwhile so that it can be resized
+                MutexGuard::unlocked(&mut read_end, interface::lind_yield);
+                pipe_space = read_end.len();
             }
         }
 
@@ -457,10 +589,248 @@ impl EmulatedPipe {
         // lets read the minimum of the specified amount or whatever is in the pipe
         let bytes_to_read = min(length, pipe_space);
         read_end.pop_slice(&mut buf[0..bytes_to_read]);
+        self.total_read
+            .fetch_add(bytes_to_read as u64, Ordering::Release);
+        drop(read_end);
+        self.waiters.wake();
 
         // return the amount we read
         bytes_to_read as i32
     }
+
+    /// ### Description
+    ///
+    /// `read_vectored_from_pipe` reads from the pipe into a set of iovec
+    /// buffers, filling each buffer before moving on to the next one. Only
+    /// the first buffer may wait for data; once something has been read, the
+    /// read stops as soon as the pipe runs empty instead of blocking.
+    ///
+    /// ### Arguments
+    ///
+    /// * `ptr` - A pointer to an array of `IovecStruct` which describes the
+    ///   buffers (iovecs) that will be read into.
+    /// * `iovcnt` - The number of `IovecStruct` buffers to be read into.
+    /// * `nonblocking` - A boolean flag indicating whether the read operation
+    ///   should be non-blocking.
+    ///
+    /// ### Returns
+    ///
+    /// Upon success, the total number of bytes read, which is 0 once EOF is
+    /// reached. Otherwise the error of `read_from_pipe`.
+    ///
+    /// ### Errors
+    ///
+    /// * `EAGAIN` - Nothing could be read as the pipe is empty, and either the
+    ///   read is non-blocking or the caller should check for cancellation
+    ///   before trying again.
+    ///
+    /// ### Panics
+    ///
+    /// A panic occurs if the provided `ptr` is null when dereferencing the
+    /// iovecs.
+    ///
+    /// To learn more about pipes and the readv syscall
+    /// [pipe(7)](https://man7.org/linux/man-pages/man7/pipe.7.html)
+    /// [readv(2)](https://man7.org/linux/man-pages/man2/readv.2.html)
+    pub fn read_vectored_from_pipe(
+        &self,
+        ptr: *const interface::IovecStruct,
+        iovcnt: i32,
+        nonblocking: bool,
+    ) -> i32 {
+        let mut total_bytes_read = 0;
+
+        for mut buf in interface::iovec_to_ioslice_mut(ptr, iovcnt) {
+            if buf.is_empty() {
+                continue;
+            }
+            // after the first bytes have been read we only take what is already in the pipe
+            let current_read = self.read_from_pipe(
+                buf.as_mut_ptr(),
+                buf.len(),
+                nonblocking || total_bytes_read > 0,
+            );
+            if current_read < 0 {
+                if total_bytes_read == 0 {
+                    return current_read;
+                }
+                break;
+            }
+            total_bytes_read += current_read;
+            // a short read means the pipe is empty or at EOF
+            if (current_read as usize) < buf.len() {
+                break;
+            }
+        }
+        total_bytes_read
+    }
+
+    /// ### Description
+    ///
+    /// `transfer_to` moves bytes from this pipe straight into another pipe
+    /// without them passing through a buffer of the caller, or copies them
+    /// over while leaving them in this pipe. This is what splice and tee do
+    /// between two pipes.
+    ///
+    /// ### Arguments
+    ///
+    /// * `dst` - The pipe the bytes are put into, which must not be this pipe
+    /// * `length` - The most bytes to move or copy
+    /// * `consume` - Whether the bytes are taken out of this pipe (splice) or
+    ///   left in it (tee)
+    ///
+    /// ### Returns
+    ///
+    /// The number of bytes moved or copied, which is 0 once this pipe is empty
+    /// and has no writers left.
+    ///
+    /// ### Errors
+    ///
+    /// * `EAGAIN` - This pipe is empty or `dst` is full, so the caller has to
+    ///   either give up or try again later.
+    /// * `EPIPE` - Every read reference to `dst` has been closed.
+    pub fn transfer_to(&self, dst: &EmulatedPipe, length: usize, consume: bool) -> i32 {
+        if length == 0 {
+            return 0;
+        }
+
+        // the read end of the source is always locked before the write end of the
+        // destination
+        let mut read_end = self.read_end.lock();
+        if read_end.is_empty() {
+            if self.get_write_ref() == 0 {
+                return 0;
+            }
+            return syscall_error(
+                Errno::EAGAIN,
+                "splice",
+                "there is no data available right now, try again later",
+            );
+        }
+        if dst.get_read_ref() == 0 {
+            return syscall_error(Errno::EPIPE, "splice", "broken pipe");
+        }
+        let mut write_end = dst.write_end.lock();
+        let bytes_to_move = min(length, min(read_end.len(), write_end.remaining()));
+        if bytes_to_move == 0 {
+            return syscall_error(
+                Errno::EAGAIN,
+                "splice",
+                "there is no space available right now, try again later",
+            );
+        }
+
+        if consume {
+            read_end.move_to(&mut write_end, Some(bytes_to_move));
+            self.total_read
+                .fetch_add(bytes_to_move as u64, Ordering::Release);
+        } else {
+            let (head, tail) = read_end.as_slices();
+            let from_head = min(bytes_to_move, head.len());
+            write_end.push_slice(&head[..from_head]);
+            write_end.push_slice(&tail[..bytes_to_move - from_head]);
+        }
+        dst.total_written
+            .fetch_add(bytes_to_move as u64, Ordering::Release);
+        drop(write_end);
+        drop(read_end);
+        if consume {
+            self.waiters.wake();
+        }
+        dst.waiters.wake();
+
+        bytes_to_move as i32
+    }
+
+    /// ### Description
+    ///
+    /// `consume_with` hands what is at the front of the pipe to `f`, and
+    /// takes out of the pipe however many bytes `f` says it used. The pipe
+    /// stays locked meanwhile, so the bytes `f` doesn't use are still at the
+    /// front of the pipe afterwards. This is how splice moves bytes from a pipe
+    /// into a file or socket.
+    ///
+    /// ### Arguments
+    ///
+    /// * `length` - The most bytes to hand to `f`
+    /// * `f` - Gets the bytes, and returns how many of them it used or an error
+    ///
+    /// ### Returns
+    ///
+    /// None if the pipe is empty but may still be written to. Otherwise what
+    /// `f` returned, or 0 if the pipe is empty and has no writers left.
+    pub fn consume_with<F: FnOnce(&[u8]) -> i32>(&self, length: usize, f: F) -> Option<i32> {
+        let mut read_end = self.read_end.lock();
+        if read_end.is_empty() {
+            if self.get_write_ref() == 0 {
+                return Some(0);
+            }
+            return None;
+        }
+        let bytes_to_read = min(length, read_end.len());
+
+        // the bytes only need to be gathered into one buffer if they wrap around the
+        // end of the ring buffer
+        let ret = {
+            let (head, tail) = read_end.as_slices();
+            if head.len() >= bytes_to_read {
+                f(&head[..bytes_to_read])
+            } else {
+                let mut buf = head.to_vec();
+                buf.extend_from_slice(&tail[..bytes_to_read - head.len()]);
+                f(&buf)
+            }
+        };
+
+        if ret > 0 {
+            read_end.discard(ret as usize);
+            self.total_read.fetch_add(ret as u64, Ordering::Release);
+            drop(read_end);
+            self.waiters.wake();
+        }
+        Some(ret)
+    }
+
+    /// ### Description
+    ///
+    /// `produce_with` gives `f` a buffer of as much room as there is in the
+    /// pipe, and puts into the pipe however many bytes `f` says it filled in.
+    /// The pipe stays locked meanwhile, so no other writer takes the room.
+    /// This is how splice moves bytes from a file or socket into a pipe.
+    ///
+    /// ### Arguments
+    ///
+    /// * `length` - The most bytes to let `f` fill in
+    /// * `f` - Fills in the buffer, and returns how many bytes it filled in or
+    ///   an error
+    ///
+    /// ### Returns
+    ///
+    /// None if the pipe is full. Otherwise what `f` returned, or EPIPE.
+    ///
+    /// ### Errors
+    ///
+    /// * `EPIPE` - Every read reference to the pipe has been closed.
+    pub fn produce_with<F: FnOnce(&mut [u8]) -> i32>(&self, length: usize, f: F) -> Option<i32> {
+        if self.get_read_ref() == 0 {
+            return Some(syscall_error(Errno::EPIPE, "splice", "broken pipe"));
+        }
+        let mut write_end = self.write_end.lock();
+        let remaining = write_end.remaining();
+        if remaining == 0 {
+            return None;
+        }
+
+        let mut buf = vec![0u8; min(length, remaining)];
+        let ret = f(&mut buf);
+        if ret > 0 {
+            write_end.push_slice(&buf[..ret as usize]);
+            self.total_written.fetch_add(ret as u64, Ordering::Release);
+            drop(write_end);
+            self.waiters.wake();
+        }
+        Some(ret)
+    }
 }
 
 impl fmt::Debug for EmulatedPipe {
@@ -471,3 +841,333 @@ impl fmt::Debug for EmulatedPipe {
             .finish()
     }
 }
+
+/// # Description
+/// A message held by an `EmulatedMsgPipe`: the bytes written by one call along
+/// with whatever the writer wants to pass with them
+#[derive(Debug)]
+pub struct PipeMsg<T> {
+    pub data: Vec<u8>,
+    pub meta: T,
+}
+
+// The messages queued in an EmulatedMsgPipe, along with the number of bytes in
+// them, which is what counts against the capacity of the pipe
+struct MsgQueue<T> {
+    msgs: VecDeque<PipeMsg<T>>,
+    bytes: usize,
+}
+
+/// # Description
+/// In-memory pipe struct which preserves message boundaries, used to emulate
+/// Unix datagram and seqpacket sockets. Each write queues a single message
+/// which a read takes off whole, so a message is never split up or merged
+/// with another one. Like EmulatedPipe it keeps reference counters to each
+/// end, but it is always shared behind a single reference counted pointer.
+pub struct EmulatedMsgPipe<T> {
+    queue: Mutex<MsgQueue<T>>,
+    refcount_write: AtomicU32,
+    refcount_read: AtomicU32,
+    waiters: interface::EpollWaitQueue,
+    size: usize,
+}
+
+impl<T> EmulatedMsgPipe<T> {
+    /// # Description
+    /// Creates an in-memory message pipe object which holds up to `size` bytes
+    /// of messages, with one reference to each of its ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Number of bytes the queued messages may add up to, which is
+    ///   also the largest message that can be written
+    ///
+    /// # Returns
+    ///
+    /// EmulatedMsgPipe object
+    pub fn new_with_capacity(size: usize) -> EmulatedMsgPipe<T> {
+        EmulatedMsgPipe {
+            queue: Mutex::new(MsgQueue {
+                msgs: VecDeque::new(),
+                bytes: 0,
+            }),
+            refcount_write: AtomicU32::new(1),
+            refcount_read: AtomicU32::new(1),
+            waiters: interface::EpollWaitQueue::new(),
+            size,
+        }
+    }
+
+    /// # Description
+    /// Checks the references to each end of the pipe to determine if its closed
+    ///
+    /// # Returns
+    ///
+    /// True if all references are closed, false if there are open references
+    pub fn is_pipe_closed(&self) -> bool {
+        self.is_write_closed() && self.is_read_closed()
+    }
+
+    /// # Description
+    /// Checks whether every reference to the write end has been closed, in
+    /// which case no more messages can ever be written to the pipe.
+    pub fn is_write_closed(&self) -> bool {
+        self.refcount_write.load(Ordering::Relaxed) == 0
+    }
+
+    /// # Description
+    /// Checks whether every reference to the read end has been closed, in
+    /// which case the messages in the pipe can never be read.
+    pub fn is_read_closed(&self) -> bool {
+        self.refcount_read.load(Ordering::Relaxed) == 0
+    }
+
+    /// # Description
+    /// Checks whether a message is queued.
+    pub fn has_data(&self) -> bool {
+        !self.queue.lock().msgs.is_empty()
+    }
+
+    /// # Description
+    /// The wait queue of the epoll instances watching either end of the pipe.
+    pub fn waiters(&self) -> &interface::EpollWaitQueue {
+        &self.waiters
+    }
+
+    /// # Description
+    /// Increase references to write or read end, as EmulatedPipe::incr_ref
+    /// does.
+    ///
+    /// # Arguments
+    ///
+    /// * `flags` - O_RDONLY for the read end or O_WRONLY for the write end
+    pub fn incr_ref(&self, flags: i32) {
+        if (flags & O_RDWRFLAGS) == O_RDONLY {
+            self.refcount_read.fetch_add(1, Ordering::Relaxed);
+        }
+        if (flags & O_RDWRFLAGS) == O_WRONLY {
+            self.refcount_write.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// # Description
+    /// Decrease references to write or read end, as EmulatedPipe::decr_ref
+    /// does.
+    ///
+    /// # Arguments
+    ///
+    /// * `flags` - O_RDONLY for the read end or O_WRONLY for the write end
+    pub fn decr_ref(&self, flags: i32) {
+        if (flags & O_RDWRFLAGS) == O_RDONLY {
+            self.refcount_read.fetch_sub(1, Ordering::Relaxed);
+        }
+        if (flags & O_RDWRFLAGS) == O_WRONLY {
+            self.refcount_write.fetch_sub(1, Ordering::Relaxed);
+        }
+        // the other end may now see a hang up
+        self.waiters.wake();
+    }
+
+    /// # Description
+    /// Checks if pipe is currently ready for reading, used by select/poll etc.
+    /// It is ready if a message is queued or there are 0 remaining write
+    /// references.
+    ///
+    /// # Returns
+    ///
+    /// True if descriptor is ready for reading, false if it will block
+    pub fn check_select_read(&self) -> bool {
+        !self.queue.lock().msgs.is_empty() || self.is_write_closed()
+    }
+
+    /// # Description
+    /// Checks if pipe is currently ready for writing, used by select/poll etc.
+    /// It is ready if the queued messages leave room in the pipe or there are
+    /// 0 remaining read references.
+    ///
+    /// # Returns
+    ///
+    /// True if descriptor is ready for writing, false if it will block
+    pub fn check_select_write(&self) -> bool {
+        self.queue.lock().bytes < self.size || self.is_read_closed()
+    }
+
+    /// ### Description
+    ///
+    /// `write_msg` queues `data` as a single message, along with `meta`. The
+    /// message is queued whole once there is room for it in the pipe, and
+    /// always fits in an empty pipe.
+    ///
+    /// ### Arguments
+    ///
+    /// * `data` - the bytes of the message.
+    /// * `meta` - whatever is passed along with the bytes.
+    /// * `nonblocking` - if this attempt to write is nonblocking
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion, the length of the message. In case of a
+    /// failure, the error is returned to the calling syscall along with
+    /// `meta`, which was not passed on.
+    ///
+    /// ### Errors
+    ///
+    /// * `EAGAIN` - Non-blocking is enabled and there is no room for the
+    ///   message.
+    /// * `EMSGSIZE` - The message is larger than the pipe.
+    /// * `EPIPE` - All read references have been closed.
+    pub fn write_msg(&self, data: &[u8], meta: T, nonblocking: bool) -> Result<usize, (i32, T)> {
+        if data.len() > self.size {
+            return Err((
+                syscall_error(
+                    Errno::EMSGSIZE,
+                    "write",
+                    "message too long to be sent atomically",
+                ),
+                meta,
+            ));
+        }
+
+        loop {
+            let mut queue = self.queue.lock();
+            // we check for readers with the queue locked, so that whoever closes the read
+            // end and then empties the queue can't miss our message
+            if self.is_read_closed() {
+                return Err((syscall_error(Errno::EPIPE, "write", "broken pipe"), meta));
+            }
+            if queue.msgs.is_empty() || queue.bytes + data.len() <= self.size {
+                queue.bytes += data.len();
+                queue.msgs.push_back(PipeMsg {
+                    data: data.to_vec(),
+                    meta,
+                });
+                drop(queue);
+                self.waiters.wake();
+                return Ok(data.len());
+            }
+            drop(queue);
+
+            if nonblocking {
+                return Err((
+                    syscall_error(
+                        Errno::EAGAIN,
+                        "write",
+                        "there is no space available right now, try again later",
+                    ),
+                    meta,
+                ));
+            }
+            // we yield here on a full pipe to let other threads continue more quickly
+            interface::lind_yield();
+        }
+    }
+
+    /// ### Description
+    ///
+    /// `read_msg` takes the first message off the pipe, waiting for one to be
+    /// written if the pipe is empty.
+    ///
+    /// ### Arguments
+    ///
+    /// * `nonblocking` - if this attempt to read is nonblocking
+    ///
+    /// ### Returns
+    ///
+    /// The message upon success, or None once the pipe is empty and all write
+    /// references have been closed. In case of a failure, an error is returned
+    /// to the calling syscall.
+    ///
+    /// ### Errors
+    ///
+    /// * `EAGAIN` - The pipe is empty, and either the read is non-blocking or
+    ///   the caller should check for cancellation before trying again, as with
+    ///   read_from_pipe.
+    pub fn read_msg(&self, nonblocking: bool) -> Result<Option<PipeMsg<T>>, i32> {
+        let mut queue = match self.wait_for_msg(nonblocking)? {
+            Some(queue) => queue,
+            None => return Ok(None),
+        };
+        let msg = queue.msgs.pop_front().unwrap();
+        queue.bytes -= msg.data.len();
+        drop(queue);
+        self.waiters.wake();
+        Ok(Some(msg))
+    }
+
+    /// ### Description
+    ///
+    /// `peek_msg` waits for a message just like read_msg, but leaves it in the
+    /// pipe, handing it to `f` instead.
+    ///
+    /// ### Returns
+    ///
+    /// What `f` returned for the first message, or None at the end of file.
+    ///
+    /// ### Errors
+    ///
+    /// The errors of read_msg.
+    pub fn peek_msg<R>(
+        &self,
+        nonblocking: bool,
+        f: impl FnOnce(&PipeMsg<T>) -> R,
+    ) -> Result<Option<R>, i32> {
+        let queue = match self.wait_for_msg(nonblocking)? {
+            Some(queue) => queue,
+            None => return Ok(None),
+        };
+        Ok(Some(f(queue.msgs.front().unwrap())))
+    }
+
+    /// # Description
+    /// Takes every message off the pipe, so that whatever was passed along
+    /// with them can be released once they can no longer be read.
+    pub fn drain(&self) -> Vec<PipeMsg<T>> {
+        let mut queue = self.queue.lock();
+        queue.bytes = 0;
+        let msgs = queue.msgs.drain(..).collect();
+        drop(queue);
+        self.waiters.wake();
+        msgs
+    }
+
+    // Waits until a message is queued and returns the locked queue, or None once
+    // the pipe is empty with no writers left. Blocking waits give up with EAGAIN
+    // every so often, so that the calling syscall can check for cancellation
+    fn wait_for_msg(&self, nonblocking: bool) -> Result<Option<MutexGuard<'_, MsgQueue<T>>>, i32> {
+        let mut count = 0;
+        loop {
+            let queue = self.queue.lock();
+            if !queue.msgs.is_empty() {
+                return Ok(Some(queue));
+            }
+            drop(queue);
+
+            // If write references are 0, we've reached EOF
+            if self.is_write_closed() {
+                return Ok(None);
+            }
+            if nonblocking {
+                return Err(syscall_error(
+                    Errno::EAGAIN,
+                    "read",
+                    "there is no data available right now, try again later",
+                ));
+            }
+            if count == CANCEL_CHECK_INTERVAL {
+                return Err(-(Errno::EAGAIN as i32));
+            }
+            count += 1;
+            // we yield here on an empty pipe to let other threads continue more quickly
+            interface::lind_yield();
+        }
+    }
+}
+
+impl<T> fmt::Debug for EmulatedMsgPipe<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmulatedMsgPipe")
+            .field("refcount read", &self.refcount_read)
+            .field("refcount write", &self.refcount_write)
+            .finish()
+    }
+}
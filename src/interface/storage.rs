@@ -0,0 +1,1629 @@
+// Storage backends for file data and file system metadata
+//! ## Storage Backends
+//!
+//! The data of regular files and the saved file system metadata are kept by a
+//! `StorageBackend`. The file system asks the backend for the data of a file by
+//! name (`linddata.<inode>`), for the saved metadata, and for the metadata log,
+//! and never touches the host file system for them directly. Three backends
+//! are provided:
+//!
+//! - `HostDirBackend`: one host file per file, next to `lind.metadata` and
+//!   `lind.md.log`, all in one host directory. This is the default, rooted at
+//!   `$LIND_FS_ROOT` if it is set and at the current directory otherwise.
+//! - `MemoryBackend`: everything in memory, gone when the process exits. Meant
+//!   for tests and ephemeral cages.
+//! - `ImageBackend`: everything in a single host file, laid out as described
+//!   below.
+//!
+//! Files can share storage. `clone_data()` gives a file the data of another
+//! without copying it where the backend can, and `dedup_data()` lets a file
+//! share the storage of another file holding the same bytes. A file changing
+//! its data never changes the data of the files it shares storage with:
+//!
+//! - `HostDirBackend` keeps one host file per content in `lindobjects/`, named
+//!   by the hash of the content, and shares it by hard linking the data of
+//!   files to it. Cloned files are hard links to each other. The link count of
+//!   the host file is the reference count of the data, and a file whose data
+//!   has more than one link gets a copy of its own before it is first changed.
+//!   Content nothing but `lindobjects/` links to any longer is removed. Only
+//!   whole files are shared this way: files that differ in a single block keep
+//!   a host file each, and a file that shares its host file gets a copy of all
+//!   of it on its first change.
+//! - `ImageBackend` shares blocks: every block it stores is indexed by the hash
+//!   of its bytes, and a block with the same bytes as one already stored refers
+//!   to that one instead. Blocks are reference counted and never written in
+//!   place anyway, so sharing them costs nothing on later writes.
+//! - `MemoryBackend` copies the data, so `shares_data()` is false for it.
+//!
+//! The backend in use is chosen with `set_storage_backend()`, which has to be
+//! called before `lindrustinit()`. Without it, an image is mounted if
+//! `$LIND_FS_IMAGE` names one, and a host directory is used otherwise. If that
+//! backend cannot be opened, the error is logged and everything is kept in
+//! memory instead, so that the image or directory is left as it was.
+//!
+//! ## Image Layout
+//!
+//! An image is a sequence of `IMAGE_BLOCK_SIZE` blocks. Blocks 0 and 1 hold
+//! two copies of the superblock, each made of the magic `IMAGE_MAGIC`, the
+//! format version, the length and CRC-32 of its payload, and the CBOR encoded
+//! `ImageSuperblock` itself. The superblock points at the saved metadata
+//! (which holds the inode table) and at the data index, which maps every file
+//! name to its size and the extents holding its data. Blocks of a file that
+//! are all zeroes are not stored, so sparse files stay sparse.
+//!
+//! Changes are never written over blocks the current superblock refers to.
+//! New data, metadata and index are written to free blocks first, and then
+//! the superblock with the next generation number is written to the slot the
+//! older copy was in, so a crash at any point leaves the previous generation
+//! intact. The newest copy that checks out is used when opening an image.
+//!
+//! Since version 2 of the format, a block may be referred to by the extents
+//! of more than one file, and the superblock also points at the hashes of the
+//! stored blocks. Version 1 images are read as ones with no block shared.
+//!
+//! File data is staged in memory while a file is open, and written back to
+//! the image when it is synced or closed. The metadata log is kept beside
+//! the image in `<image>.log`, and is folded into the image whenever the
+//! metadata is saved.
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::ffi::CString;
+use std::fmt::Debug;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::{FileExt, MetadataExt};
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::path::PathBuf;
+
+use crate::interface;
+use crate::interface::file::{crc32, EmulatedFile, EmulatedFileMap, MAP_1MB};
+
+pub const METADATAFILENAME: &str = "lind.metadata";
+
+pub const LOGFILENAME: &str = "lind.md.log";
+
+// the metadata is written here first and then renamed over lind.metadata, so
+// that a crash while saving never leaves a partially written metadata file
+pub const METADATATMPFILENAME: &str = "lind.metadata.tmp";
+
+// a metadata file that could not be decoded is moved here instead of being lost
+pub const METADATACORRUPTFILENAME: &str = "lind.metadata.corrupt";
+
+pub const IMAGE_MAGIC: &[u8; 8] = b"LINDIMG\0";
+pub const IMAGE_VERSION: u32 = 2;
+pub const IMAGE_BLOCK_SIZE: u64 = 4096;
+
+// host files holding data that files share, named by its hash
+pub const OBJECTSDIRNAME: &str = "lindobjects";
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+// magic, version, payload length and payload checksum
+const IMAGE_SUPERBLOCK_HEADER: usize = 20;
+
+/// Keeps the data of regular files, the saved file system metadata, and the
+/// log of metadata changes made since it was saved
+pub trait StorageBackend: Send + Sync + Debug {
+    /// Opens the data of a file, creating it empty if it does not exist yet
+    fn open_data(&self, name: &str, filesize: usize) -> io::Result<EmulatedFile>;
+    /// Removes the data of a file
+    fn remove_data(&self, name: &str) -> io::Result<()>;
+    /// Returns the saved metadata, or `None` if none was ever saved
+    fn read_metadata(&self) -> io::Result<Option<Vec<u8>>>;
+    /// Replaces the saved metadata in one step, so that a crash leaves either
+    /// the old or the new metadata
+    fn write_metadata(&self, bytes: &[u8]) -> io::Result<()>;
+    /// Moves saved metadata that could not be decoded out of the way, keeping
+    /// it as `lind.metadata.corrupt`
+    fn set_aside_metadata(&self) -> io::Result<()>;
+    /// Returns the contents of the metadata log, or `None` if there is none
+    fn read_log(&self) -> io::Result<Option<Vec<u8>>>;
+    /// Starts a new, empty metadata log in place of any existing one
+    fn create_log(&self) -> io::Result<EmulatedFileMap>;
+    /// Removes the metadata log, if there is one
+    fn remove_log(&self) -> io::Result<()>;
+    /// Gives `to` the data of `from`, replacing whatever data `to` had. The
+    /// data is shared instead of copied where the backend can do that, and
+    /// changing either file later leaves the other one as it was. `from` must
+    /// not be staged with changes that were not written back. A file object
+    /// already open on `to` keeps the old data, and is to be dropped without
+    /// being written back.
+    fn clone_data(&self, from: &str, to: &str, filesize: usize) -> io::Result<()> {
+        let _ = self.remove_data(to);
+        copy_data(self, from, self, to, filesize)
+    }
+    /// Whether `clone_data()` shares the data instead of copying it. A backend
+    /// may only share whole files, which are then copied in full on the first
+    /// change to either one
+    fn shares_data(&self) -> bool {
+        false
+    }
+    /// Lets the data of a file that is not open share storage with any other
+    /// file holding the same bytes. Returns whether the data now shares
+    /// storage with another file.
+    fn dedup_data(&self, _name: &str, _filesize: usize) -> io::Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Receives the data of a file that a backend only staged in a host file
+/// while it was open
+pub trait DataWriteBack: Send + Sync + Debug {
+    fn write_back(&self, name: &str, staged: &File, filesize: usize) -> io::Result<()>;
+}
+
+/// Gives a file whose host file is shared with other files a host file of its
+/// own holding the same data, before the file is changed
+pub trait DataCopyOnWrite: Send + Sync + Debug {
+    fn copy_on_write(&self, name: &str, shared: &File, filesize: usize) -> io::Result<File>;
+}
+
+// the backend in use, which is only opened once it is first needed so that one
+// set beforehand means the default is never opened at all
+pub static STORAGE: interface::RustLazyGlobal<
+    interface::RustLock<Option<interface::RustRfc<dyn StorageBackend>>>,
+> = interface::RustLazyGlobal::new(|| interface::RustLock::new(None));
+
+/// ### Description
+///
+/// Opens the backend used when none was set: the image named by
+/// `$LIND_FS_IMAGE` if it is set, and otherwise the host directory backend,
+/// rooted at `$LIND_FS_ROOT` or the current directory.
+///
+/// ### Errors
+///
+/// Fails with the error opening the image or the directory ran into, naming
+/// which one it was.
+pub fn default_storage_backend() -> io::Result<interface::RustRfc<dyn StorageBackend>> {
+    if let Ok(image) = env::var("LIND_FS_IMAGE") {
+        return match ImageBackend::open(&image) {
+            Ok(backend) => Ok(interface::RustRfc::new(backend)),
+            Err(e) => Err(io::Error::new(
+                e.kind(),
+                format!("cannot mount image {}: {}", image, e),
+            )),
+        };
+    }
+    let root = env::var("LIND_FS_ROOT").unwrap_or_else(|_| ".".to_string());
+    match HostDirBackend::new(&root) {
+        Ok(backend) => Ok(interface::RustRfc::new(backend)),
+        Err(e) => Err(io::Error::new(
+            e.kind(),
+            format!("cannot use directory {}: {}", root, e),
+        )),
+    }
+}
+
+/// Returns the storage backend in use, opening the default one if none was
+/// set. If the default cannot be opened the error is logged and a
+/// `MemoryBackend` is used instead, so nothing is saved.
+pub fn storage_backend() -> interface::RustRfc<dyn StorageBackend> {
+    if let Some(backend) = &*STORAGE.read() {
+        return backend.clone();
+    }
+    STORAGE
+        .write()
+        .get_or_insert_with(|| match default_storage_backend() {
+            Ok(backend) => backend,
+            Err(e) => {
+                crate::interface::log_to_stderr(&format!(
+                    "lind storage: {}, keeping the file system in memory instead",
+                    e
+                ));
+                interface::RustRfc::new(MemoryBackend::new())
+            }
+        })
+        .clone()
+}
+
+/// Switches to another storage backend. This must happen before
+/// `lindrustinit()`, or after `lindrustfinalize()`, since open files and the
+/// log belong to the backend that was in use when they were opened.
+pub fn set_storage_backend(backend: interface::RustRfc<dyn StorageBackend>) {
+    STORAGE.write().replace(backend);
+}
+
+/// ### Description
+///
+/// Copies the data of a file from one backend to another, or to another name
+/// in the same backend. Only the parts of the file that hold data are read and
+/// written, so holes stay holes.
+///
+/// ### Errors
+///
+/// Fails with any error either backend runs into.
+pub fn copy_data<F, T>(
+    from: &F,
+    fromname: &str,
+    to: &T,
+    toname: &str,
+    filesize: usize,
+) -> io::Result<()>
+where
+    F: StorageBackend + ?Sized,
+    T: StorageBackend + ?Sized,
+{
+    let source = from.open_data(fromname, filesize)?;
+    let _ = to.remove_data(toname);
+    let mut dest = to.open_data(toname, 0)?;
+    dest.zerofill_at(0, filesize)?;
+
+    let mut buf = vec![0u8; MAP_1MB];
+    let mut offset = 0;
+    while offset < filesize {
+        // if holes cannot be found, the rest of the file is copied as data
+        let start = match source.seek_data_or_hole(offset, libc::SEEK_DATA) {
+            Ok(start) => start,
+            Err(e) if e.raw_os_error() == Some(libc::ENXIO) => break,
+            Err(_) => offset,
+        };
+        let end = source
+            .seek_data_or_hole(start, libc::SEEK_HOLE)
+            .unwrap_or(filesize)
+            .min(filesize);
+        let mut pos = start;
+        while pos < end {
+            let len = std::cmp::min(buf.len(), end - pos);
+            let read = source.readat(buf.as_mut_ptr(), len, pos)?;
+            if read == 0 {
+                break;
+            }
+            dest.writeat(buf.as_ptr(), read, pos)?;
+            pos += read;
+        }
+        offset = end;
+    }
+
+    source.close()?;
+    dest.close()
+}
+
+// Reads a whole host file, which is None if the file does not exist
+fn read_host_file(path: &PathBuf) -> io::Result<Option<Vec<u8>>> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// Removes a host file, which is fine if it is already gone
+fn remove_host_file(path: &PathBuf) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+// Creates an anonymous file that lives in memory
+fn memfd(name: &str) -> io::Result<File> {
+    let cname = CString::new(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let fd = unsafe { libc::memfd_create(cname.as_ptr(), libc::MFD_CLOEXEC) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+// Reads all of a file from its start, whatever its offset
+fn read_whole(f: &File) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut fobj = f.try_clone()?;
+    fobj.seek(SeekFrom::Start(0))?;
+    fobj.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+// Continues a 64-bit FNV-1a hash over more bytes. Shared content is found by
+// this hash, but always compared before it is shared, so a collision only
+// means a missed chance to share
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |hash, &b| (hash ^ b as u64).wrapping_mul(FNV_PRIME))
+}
+
+// Hashes all of a host file, with holes read as zeroes
+fn hash_host_file(f: &File) -> io::Result<u64> {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut buf = vec![0u8; MAP_1MB];
+    let mut offset = 0;
+    loop {
+        let read = f.read_at(&mut buf, offset)?;
+        if read == 0 {
+            return Ok(hash);
+        }
+        hash = fnv1a(hash, &buf[..read]);
+        offset += read as u64;
+    }
+}
+
+// Whether two host files hold the same bytes
+fn same_contents(a: &File, b: &File) -> io::Result<bool> {
+    if a.metadata()?.len() != b.metadata()?.len() {
+        return Ok(false);
+    }
+    let mut abuf = vec![0u8; MAP_1MB];
+    let mut bbuf = vec![0u8; MAP_1MB];
+    let mut offset = 0;
+    loop {
+        let read = a.read_at(&mut abuf, offset)?;
+        if read == 0 {
+            return Ok(true);
+        }
+        b.read_exact_at(&mut bbuf[..read], offset)?;
+        if abuf[..read] != bbuf[..read] {
+            return Ok(false);
+        }
+        offset += read as u64;
+    }
+}
+
+// Copies a host file into another, keeping its holes
+fn copy_host_file(from: &File, to: &File) -> io::Result<()> {
+    let len = from.metadata()?.len();
+    to.set_len(len)?;
+    let mut buf = vec![0u8; MAP_1MB];
+    for (start, end) in data_ranges(from, len) {
+        let mut pos = start;
+        while pos < end {
+            let chunk = std::cmp::min(buf.len() as u64, end - pos) as usize;
+            from.read_exact_at(&mut buf[..chunk], pos)?;
+            to.write_all_at(&buf[..chunk], pos)?;
+            pos += chunk as u64;
+        }
+    }
+    Ok(())
+}
+
+/// Keeps each file in its own host file in a directory of the host
+#[derive(Debug)]
+pub struct HostDirBackend {
+    root: PathBuf,
+    objects: interface::RustRfc<HostDirObjects>,
+}
+
+// The shared content of a host directory backend, which the files it opens
+// also hold on to so they can get copies of their own
+#[derive(Debug)]
+struct HostDirObjects {
+    root: PathBuf,
+}
+
+impl HostDirObjects {
+    fn object_path(&self, hash: u64) -> PathBuf {
+        self.root
+            .join(OBJECTSDIRNAME)
+            .join(format!("{:016x}", hash))
+    }
+
+    // Removes the shared content with this hash once no file links to it
+    fn collect(&self, hash: u64) -> io::Result<()> {
+        let path = self.object_path(hash);
+        match fs::metadata(&path) {
+            Ok(m) if m.nlink() == 1 => remove_host_file(&path),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl DataCopyOnWrite for HostDirObjects {
+    fn copy_on_write(&self, name: &str, shared: &File, _filesize: usize) -> io::Result<File> {
+        let hash = hash_host_file(shared)?;
+        let tmppath = self.root.join(format!("{}.tmp", name));
+        let copy = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmppath)?;
+        copy_host_file(shared, &copy)?;
+        fs::rename(&tmppath, self.root.join(name))?;
+        self.collect(hash)?;
+        Ok(copy)
+    }
+}
+
+impl HostDirBackend {
+    /// Uses `root` as the data directory, creating it if needed
+    pub fn new<P: Into<PathBuf>>(root: P) -> io::Result<HostDirBackend> {
+        let root = root.into();
+        fs::create_dir_all(&root)?;
+        Ok(HostDirBackend {
+            objects: interface::RustRfc::new(HostDirObjects { root: root.clone() }),
+            root,
+        })
+    }
+
+    pub fn root(&self) -> &PathBuf {
+        &self.root
+    }
+}
+
+impl StorageBackend for HostDirBackend {
+    fn open_data(&self, name: &str, filesize: usize) -> io::Result<EmulatedFile> {
+        let f = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.root.join(name))?;
+        let shared = f.metadata()?.nlink() > 1;
+        let cow: interface::RustRfc<dyn DataCopyOnWrite> = self.objects.clone();
+        Ok(EmulatedFile::from_file(name.to_string(), f, filesize, None)
+            .with_copy_on_write(cow, shared))
+    }
+
+    fn remove_data(&self, name: &str) -> io::Result<()> {
+        let path = self.root.join(name);
+        let f = File::open(&path)?;
+        // shared content may be left with nothing but its link in lindobjects
+        let hash = if f.metadata()?.nlink() > 1 {
+            Some(hash_host_file(&f)?)
+        } else {
+            None
+        };
+        fs::remove_file(path)?;
+        match hash {
+            Some(hash) => self.objects.collect(hash),
+            None => Ok(()),
+        }
+    }
+
+    fn read_metadata(&self) -> io::Result<Option<Vec<u8>>> {
+        read_host_file(&self.root.join(METADATAFILENAME))
+    }
+
+    fn write_metadata(&self, bytes: &[u8]) -> io::Result<()> {
+        // write to a temporary file, and then swap it in for the old metadata
+        let tmppath = self.root.join(METADATATMPFILENAME);
+        let mut f = File::create(&tmppath)?;
+        f.write_all(bytes)?;
+        f.sync_all()?;
+        fs::rename(tmppath, self.root.join(METADATAFILENAME))
+    }
+
+    fn set_aside_metadata(&self) -> io::Result<()> {
+        fs::rename(
+            self.root.join(METADATAFILENAME),
+            self.root.join(METADATACORRUPTFILENAME),
+        )
+    }
+
+    fn read_log(&self) -> io::Result<Option<Vec<u8>>> {
+        read_host_file(&self.root.join(LOGFILENAME))
+    }
+
+    fn create_log(&self) -> io::Result<EmulatedFileMap> {
+        let logpath = self.root.join(LOGFILENAME);
+        remove_host_file(&logpath)?;
+        let f = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&logpath)?;
+        EmulatedFileMap::from_file(logpath.to_string_lossy().into_owned(), f)
+    }
+
+    fn remove_log(&self) -> io::Result<()> {
+        remove_host_file(&self.root.join(LOGFILENAME))
+    }
+
+    fn clone_data(&self, from: &str, to: &str, _filesize: usize) -> io::Result<()> {
+        // the link is made beside the old data and renamed over it, so the old
+        // data stays until the new data is in its place
+        let topath = self.root.join(to);
+        let tmppath = self.root.join(format!("{}.tmp", to));
+        remove_host_file(&tmppath)?;
+        fs::hard_link(self.root.join(from), &tmppath)?;
+        // shared content may be left with nothing but its link in lindobjects
+        let hash = match File::open(&topath) {
+            Ok(f) if f.metadata()?.nlink() > 1 => Some(hash_host_file(&f)?),
+            _ => None,
+        };
+        fs::rename(&tmppath, &topath)?;
+        // renaming onto a link of the same host file leaves both in place
+        remove_host_file(&tmppath)?;
+        match hash {
+            Some(hash) => self.objects.collect(hash),
+            None => Ok(()),
+        }
+    }
+
+    // Whole files are shared, so the first change to either file copies all
+    // of its data
+    fn shares_data(&self) -> bool {
+        true
+    }
+
+    fn dedup_data(&self, name: &str, _filesize: usize) -> io::Result<bool> {
+        let path = self.root.join(name);
+        let f = File::open(&path)?;
+        let hash = hash_host_file(&f)?;
+        fs::create_dir_all(self.root.join(OBJECTSDIRNAME))?;
+        let objpath = self.objects.object_path(hash);
+        let object = match File::open(&objpath) {
+            Ok(object) => object,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                // the first file with this content becomes the one others share
+                fs::hard_link(&path, &objpath)?;
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        };
+        let (fstat, objstat) = (f.metadata()?, object.metadata()?);
+        if fstat.dev() == objstat.dev() && fstat.ino() == objstat.ino() {
+            return Ok(objstat.nlink() > 2);
+        }
+        if !same_contents(&f, &object)? {
+            return Ok(false);
+        }
+        // swap the data for a link to the shared content in one step
+        let tmppath = self.root.join(format!("{}.tmp", name));
+        remove_host_file(&tmppath)?;
+        fs::hard_link(&objpath, &tmppath)?;
+        fs::rename(&tmppath, &path)?;
+        Ok(true)
+    }
+}
+
+/// Keeps everything in memory. The data of each file lives in an anonymous
+/// memory file, so it can still be mapped and have holes like a host file
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    data: interface::Mutex<HashMap<String, File>>,
+    metadata: interface::Mutex<Option<Vec<u8>>>,
+    log: interface::Mutex<Option<File>>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> MemoryBackend {
+        MemoryBackend::default()
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn open_data(&self, name: &str, filesize: usize) -> io::Result<EmulatedFile> {
+        let mut data = self.data.lock();
+        let f = match data.get(name) {
+            Some(f) => f.try_clone()?,
+            None => {
+                let f = memfd(name)?;
+                data.insert(name.to_string(), f.try_clone()?);
+                f
+            }
+        };
+        Ok(EmulatedFile::from_file(name.to_string(), f, filesize, None))
+    }
+
+    fn remove_data(&self, name: &str) -> io::Result<()> {
+        match self.data.lock().remove(name) {
+            Some(_) => Ok(()),
+            None => Err(io::Error::from(io::ErrorKind::NotFound)),
+        }
+    }
+
+    fn read_metadata(&self) -> io::Result<Option<Vec<u8>>> {
+        Ok(self.metadata.lock().clone())
+    }
+
+    fn write_metadata(&self, bytes: &[u8]) -> io::Result<()> {
+        self.metadata.lock().replace(bytes.to_vec());
+        Ok(())
+    }
+
+    fn set_aside_metadata(&self) -> io::Result<()> {
+        let bytes = match self.metadata.lock().take() {
+            Some(bytes) => bytes,
+            None => return Err(io::Error::from(io::ErrorKind::NotFound)),
+        };
+        let mut f = memfd(METADATACORRUPTFILENAME)?;
+        f.write_all(&bytes)?;
+        self.data
+            .lock()
+            .insert(METADATACORRUPTFILENAME.to_string(), f);
+        Ok(())
+    }
+
+    fn read_log(&self) -> io::Result<Option<Vec<u8>>> {
+        match &*self.log.lock() {
+            Some(f) => read_whole(f).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn create_log(&self) -> io::Result<EmulatedFileMap> {
+        let f = memfd(LOGFILENAME)?;
+        self.log.lock().replace(f.try_clone()?);
+        EmulatedFileMap::from_file(LOGFILENAME.to_string(), f)
+    }
+
+    fn remove_log(&self) -> io::Result<()> {
+        self.log.lock().take();
+        Ok(())
+    }
+}
+
+/// A run of bytes stored in consecutive blocks of an image
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageRegion {
+    pub start: u64,
+    pub len: u64,
+}
+
+/// Blocks of a file stored in consecutive blocks of an image, starting at
+/// block `fileblock` of the file
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageExtent {
+    pub fileblock: u64,
+    pub start: u64,
+    pub blocks: u64,
+}
+
+/// Where the data of one file is kept in an image
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImageFileEntry {
+    pub size: u64,
+    pub extents: Vec<ImageExtent>,
+}
+
+/// The root of an image, pointing at everything else in it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ImageSuperblock {
+    pub generation: u64,
+    pub block_count: u64,
+    pub metadata: Option<ImageRegion>,
+    pub index: ImageRegion,
+    // the hashes of the stored blocks, as pairs of block and hash
+    #[serde(default)]
+    pub hashes: Option<ImageRegion>,
+}
+
+fn blocks_for(len: u64) -> u64 {
+    len.div_ceil(IMAGE_BLOCK_SIZE)
+}
+
+// Decodes one superblock slot, which is None unless it checks out
+fn decode_superblock(slot: &[u8]) -> io::Result<Option<ImageSuperblock>> {
+    if slot.len() < IMAGE_SUPERBLOCK_HEADER || &slot[0..8] != IMAGE_MAGIC {
+        return Ok(None);
+    }
+    let version = u32::from_be_bytes([slot[8], slot[9], slot[10], slot[11]]);
+    if !(1..=IMAGE_VERSION).contains(&version) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported image version {}", version),
+        ));
+    }
+    let len = u32::from_be_bytes([slot[12], slot[13], slot[14], slot[15]]) as usize;
+    let checksum = u32::from_be_bytes([slot[16], slot[17], slot[18], slot[19]]);
+    if slot.len() - IMAGE_SUPERBLOCK_HEADER < len {
+        return Ok(None);
+    }
+    let payload = &slot[IMAGE_SUPERBLOCK_HEADER..IMAGE_SUPERBLOCK_HEADER + len];
+    if crc32(payload) != checksum {
+        return Ok(None);
+    }
+    Ok(serde_cbor::from_slice(payload).ok())
+}
+
+// Returns the ranges of a staged file that hold data, so that holes are not
+// copied into the image
+fn data_ranges(f: &File, size: u64) -> Vec<(u64, u64)> {
+    let fd = f.as_raw_fd();
+    let mut ranges = Vec::new();
+    let mut pos = 0;
+    while pos < size {
+        let start = unsafe { libc::lseek(fd, pos as libc::off_t, libc::SEEK_DATA) };
+        if start < 0 {
+            if io::Error::last_os_error().raw_os_error() == Some(libc::ENXIO) {
+                break;
+            }
+            // holes cannot be found, so treat the whole file as data
+            return vec![(0, size)];
+        }
+        let end = unsafe { libc::lseek(fd, start, libc::SEEK_HOLE) };
+        if end < 0 {
+            return vec![(0, size)];
+        }
+        let end = std::cmp::min(end as u64, size);
+        if start as u64 >= end {
+            break;
+        }
+        ranges.push((start as u64, end));
+        pos = end;
+    }
+    ranges
+}
+
+#[derive(Debug)]
+struct ImageState {
+    file: File,
+    generation: u64,
+    block_count: u64,
+    metadata: Option<ImageRegion>,
+    index_region: Option<ImageRegion>,
+    index: BTreeMap<String, ImageFileEntry>,
+    hashes_region: Option<ImageRegion>,
+    // how many extents refer to each block that more than one extent refers to
+    refs: HashMap<u64, u64>,
+    // the stored blocks by the hash of their bytes, and the other way round
+    hashes: HashMap<u64, u64>,
+    blockhashes: HashMap<u64, u64>,
+    // runs of blocks the committed superblock does not refer to
+    free: Vec<(u64, u64)>,
+    // runs of blocks released since the last commit, which the committed
+    // superblock may still refer to and so cannot be reused yet
+    pending_free: Vec<(u64, u64)>,
+}
+
+impl ImageState {
+    // Finds room for a run of blocks, first in the free runs and then at the
+    // end of the image
+    fn allocate(&mut self, blocks: u64) -> u64 {
+        if let Some(i) = self.free.iter().position(|&(_, len)| len >= blocks) {
+            let (start, len) = self.free[i];
+            if len == blocks {
+                self.free.remove(i);
+            } else {
+                self.free[i] = (start + blocks, len - blocks);
+            }
+            return start;
+        }
+        let start = self.block_count;
+        self.block_count += blocks;
+        start
+    }
+
+    fn release(&mut self, start: u64, blocks: u64) {
+        if blocks > 0 {
+            self.pending_free.push((start, blocks));
+        }
+    }
+
+    fn release_region(&mut self, region: ImageRegion) {
+        self.release(region.start, blocks_for(region.len));
+    }
+
+    // Drops a reference to each block of an extent, releasing the blocks that
+    // nothing refers to any longer
+    fn release_extent(&mut self, extent: ImageExtent) {
+        for block in extent.start..extent.start + extent.blocks {
+            match self.refs.get_mut(&block) {
+                Some(refs) => {
+                    *refs -= 1;
+                    if *refs == 1 {
+                        self.refs.remove(&block);
+                    }
+                }
+                None => {
+                    if let Some(hash) = self.blockhashes.remove(&block) {
+                        self.hashes.remove(&hash);
+                    }
+                    self.release(block, 1);
+                }
+            }
+        }
+    }
+
+    // Adds a reference to each block of an extent
+    fn share_extent(&mut self, extent: ImageExtent) {
+        for block in extent.start..extent.start + extent.blocks {
+            *self.refs.entry(block).or_insert(1) += 1;
+        }
+    }
+
+    fn write_region(&mut self, bytes: &[u8]) -> io::Result<ImageRegion> {
+        let blocks = blocks_for(bytes.len() as u64);
+        let start = if blocks == 0 {
+            0
+        } else {
+            self.allocate(blocks)
+        };
+        self.file.write_all_at(bytes, start * IMAGE_BLOCK_SIZE)?;
+        Ok(ImageRegion {
+            start,
+            len: bytes.len() as u64,
+        })
+    }
+
+    fn read_region(&self, region: ImageRegion) -> io::Result<Vec<u8>> {
+        let mut bytes = vec![0u8; region.len as usize];
+        self.file
+            .read_exact_at(&mut bytes, region.start * IMAGE_BLOCK_SIZE)?;
+        Ok(bytes)
+    }
+
+    // Stores the blocks of a file that are not all zeroes. A block with the
+    // same bytes as one already stored refers to that one, and the others are
+    // written to newly allocated blocks. The blocks the file had before are
+    // only released afterwards, so the ones that did not change are kept
+    fn store_file(&mut self, name: &str, staged: &File, size: u64) -> io::Result<()> {
+        let mut extents: Vec<ImageExtent> = Vec::new();
+        let mut block = vec![0u8; IMAGE_BLOCK_SIZE as usize];
+        let mut stored = vec![0u8; IMAGE_BLOCK_SIZE as usize];
+        for (start, end) in data_ranges(staged, size) {
+            for fileblock in start / IMAGE_BLOCK_SIZE..blocks_for(end) {
+                let offset = fileblock * IMAGE_BLOCK_SIZE;
+                let len = std::cmp::min(IMAGE_BLOCK_SIZE, size - offset) as usize;
+                block.iter_mut().for_each(|b| *b = 0);
+                staged.read_exact_at(&mut block[..len], offset)?;
+                if block.iter().all(|&b| b == 0) {
+                    continue;
+                }
+
+                let hash = fnv1a(FNV_OFFSET_BASIS, &block);
+                let mut found = None;
+                if let Some(&existing) = self.hashes.get(&hash) {
+                    self.file
+                        .read_exact_at(&mut stored, existing * IMAGE_BLOCK_SIZE)?;
+                    if stored == block {
+                        found = Some(existing);
+                    }
+                }
+                let imageblock = match found {
+                    Some(existing) => {
+                        *self.refs.entry(existing).or_insert(1) += 1;
+                        existing
+                    }
+                    None => {
+                        let new = self.allocate(1);
+                        self.file.write_all_at(&block, new * IMAGE_BLOCK_SIZE)?;
+                        if let std::collections::hash_map::Entry::Vacant(vac) =
+                            self.hashes.entry(hash)
+                        {
+                            vac.insert(new);
+                            self.blockhashes.insert(new, hash);
+                        }
+                        new
+                    }
+                };
+
+                // blocks that follow each other in both the file and the image
+                // make up one extent
+                match extents.last_mut() {
+                    Some(last)
+                        if last.fileblock + last.blocks == fileblock
+                            && last.start + last.blocks == imageblock =>
+                    {
+                        last.blocks += 1;
+                    }
+                    _ => extents.push(ImageExtent {
+                        fileblock,
+                        start: imageblock,
+                        blocks: 1,
+                    }),
+                }
+            }
+        }
+
+        if let Some(old) = self
+            .index
+            .insert(name.to_string(), ImageFileEntry { size, extents })
+        {
+            for extent in old.extents {
+                self.release_extent(extent);
+            }
+        }
+        Ok(())
+    }
+
+    // Copies the data of a file out of the image into a staging file
+    fn load_file(&self, entry: &ImageFileEntry, staged: &File) -> io::Result<()> {
+        staged.set_len(entry.size)?;
+        for extent in &entry.extents {
+            let offset = extent.fileblock * IMAGE_BLOCK_SIZE;
+            let len = std::cmp::min(extent.blocks * IMAGE_BLOCK_SIZE, entry.size - offset);
+            let mut bytes = vec![0u8; len as usize];
+            self.file
+                .read_exact_at(&mut bytes, extent.start * IMAGE_BLOCK_SIZE)?;
+            staged.write_all_at(&bytes, offset)?;
+        }
+        Ok(())
+    }
+
+    // Writes the index, and the metadata if given, then makes them current by
+    // writing the next superblock over the older of the two copies
+    fn commit(&mut self, metadata: Option<&[u8]>) -> io::Result<()> {
+        if let Some(bytes) = metadata {
+            if let Some(old) = self.metadata.take() {
+                self.release_region(old);
+            }
+            self.metadata = Some(self.write_region(bytes)?);
+        }
+        let indexbytes = serde_cbor::to_vec(&self.index)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if let Some(old) = self.index_region.take() {
+            self.release_region(old);
+        }
+        let index = self.write_region(&indexbytes)?;
+        self.index_region = Some(index);
+        let mut pairs: Vec<(u64, u64)> = self.blockhashes.iter().map(|(&b, &h)| (b, h)).collect();
+        pairs.sort_unstable();
+        let hashbytes = serde_cbor::to_vec(&pairs)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if let Some(old) = self.hashes_region.take() {
+            self.release_region(old);
+        }
+        let hashes = self.write_region(&hashbytes)?;
+        self.hashes_region = Some(hashes);
+        self.file.set_len(self.block_count * IMAGE_BLOCK_SIZE)?;
+        self.file.sync_data()?;
+
+        let superblock = ImageSuperblock {
+            generation: self.generation + 1,
+            block_count: self.block_count,
+            metadata: self.metadata,
+            index,
+            hashes: Some(hashes),
+        };
+        let payload = serde_cbor::to_vec(&superblock)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut slot = Vec::with_capacity(IMAGE_SUPERBLOCK_HEADER + payload.len());
+        slot.extend_from_slice(IMAGE_MAGIC);
+        slot.extend_from_slice(&IMAGE_VERSION.to_be_bytes());
+        slot.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        slot.extend_from_slice(&crc32(&payload).to_be_bytes());
+        slot.extend_from_slice(&payload);
+        if slot.len() > IMAGE_BLOCK_SIZE as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "superblock does not fit in a block",
+            ));
+        }
+        self.file
+            .write_all_at(&slot, (superblock.generation % 2) * IMAGE_BLOCK_SIZE)?;
+        self.file.sync_data()?;
+        self.generation = superblock.generation;
+
+        // nothing refers to the released blocks any longer, and the ones next
+        // to each other make up a single run
+        let mut released = std::mem::take(&mut self.pending_free);
+        self.free.append(&mut released);
+        self.free.sort_unstable();
+        let mut runs: Vec<(u64, u64)> = Vec::with_capacity(self.free.len());
+        for (start, len) in self.free.drain(..) {
+            match runs.last_mut() {
+                Some(last) if last.0 + last.1 == start => last.1 += len,
+                _ => runs.push((start, len)),
+            }
+        }
+        self.free = runs;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct ImageInner {
+    path: PathBuf,
+    state: interface::Mutex<ImageState>,
+}
+
+impl DataWriteBack for ImageInner {
+    fn write_back(&self, name: &str, staged: &File, filesize: usize) -> io::Result<()> {
+        let mut state = self.state.lock();
+        state.store_file(name, staged, filesize as u64)?;
+        state.commit(None)
+    }
+}
+
+/// Keeps everything in one host file, as described in the module
+/// documentation
+#[derive(Debug)]
+pub struct ImageBackend {
+    inner: interface::RustRfc<ImageInner>,
+}
+
+impl ImageBackend {
+    /// ### Description
+    ///
+    /// Opens the image at `path`, making a new empty image there if the file
+    /// does not exist or is empty. The newest superblock that checks out is
+    /// used, and every block it does not refer to is free.
+    ///
+    /// ### Errors
+    ///
+    /// Fails if the file cannot be opened, if it is of an unknown version, if
+    /// neither superblock checks out, or if the file is shorter than the
+    /// superblock says, as when it was cut short.
+    pub fn open<P: Into<PathBuf>>(path: P) -> io::Result<ImageBackend> {
+        let path = path.into();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        let mut state = ImageState {
+            file,
+            generation: 0,
+            block_count: 2,
+            metadata: None,
+            index_region: None,
+            index: BTreeMap::new(),
+            hashes_region: None,
+            refs: HashMap::new(),
+            hashes: HashMap::new(),
+            blockhashes: HashMap::new(),
+            free: Vec::new(),
+            pending_free: Vec::new(),
+        };
+
+        if state.file.metadata()?.len() == 0 {
+            state.commit(None)?;
+        } else {
+            let mut slots = vec![0u8; 2 * IMAGE_BLOCK_SIZE as usize];
+            let read = state.file.read_at(&mut slots, 0)?;
+            slots.truncate(read);
+            let (first, second) = slots.split_at(std::cmp::min(read, IMAGE_BLOCK_SIZE as usize));
+            let superblock = match (decode_superblock(first)?, decode_superblock(second)?) {
+                (Some(a), Some(b)) => {
+                    if a.generation > b.generation {
+                        a
+                    } else {
+                        b
+                    }
+                }
+                (Some(a), None) => a,
+                (None, Some(b)) => b,
+                (None, None) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "no valid superblock in image",
+                    ))
+                }
+            };
+            // the image is never shorter than its blocks once a superblock
+            // refers to them, so one that is has lost data
+            if state.file.metadata()?.len() < superblock.block_count * IMAGE_BLOCK_SIZE {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "image is shorter than its superblock says",
+                ));
+            }
+            state.generation = superblock.generation;
+            state.block_count = superblock.block_count;
+            state.metadata = superblock.metadata;
+            state.index_region = Some(superblock.index);
+            let indexbytes = state.read_region(superblock.index)?;
+            state.index = serde_cbor::from_slice(&indexbytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            state.hashes_region = superblock.hashes;
+            if let Some(region) = superblock.hashes {
+                let hashbytes = state.read_region(region)?;
+                let pairs: Vec<(u64, u64)> = serde_cbor::from_slice(&hashbytes)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                for (block, hash) in pairs {
+                    state.hashes.insert(hash, block);
+                    state.blockhashes.insert(block, hash);
+                }
+            }
+
+            // every block between the superblocks and the end that nothing refers
+            // to, and the blocks referred to more than once are counted
+            let mut used: Vec<(u64, u64)> = vec![(0, 2)];
+            let regions = [state.metadata, state.index_region, state.hashes_region];
+            for region in regions.iter().flatten() {
+                used.push((region.start, blocks_for(region.len)));
+            }
+            let mut counts: HashMap<u64, u64> = HashMap::new();
+            for entry in state.index.values() {
+                for extent in &entry.extents {
+                    used.push((extent.start, extent.blocks));
+                    for block in extent.start..extent.start + extent.blocks {
+                        *counts.entry(block).or_insert(0) += 1;
+                    }
+                }
+            }
+            counts.retain(|_, refs| *refs > 1);
+            state.refs = counts;
+            used.sort_unstable();
+            let mut next = 0;
+            for (start, blocks) in used {
+                if start > next {
+                    state.free.push((next, start - next));
+                }
+                next = std::cmp::max(next, start + blocks);
+            }
+            if state.block_count > next {
+                state.free.push((next, state.block_count - next));
+            }
+        }
+
+        Ok(ImageBackend {
+            inner: interface::RustRfc::new(ImageInner {
+                path,
+                state: interface::Mutex::new(state),
+            }),
+        })
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.inner.path
+    }
+
+    fn log_path(&self) -> PathBuf {
+        let mut logpath = self.inner.path.clone().into_os_string();
+        logpath.push(".log");
+        PathBuf::from(logpath)
+    }
+}
+
+impl StorageBackend for ImageBackend {
+    fn open_data(&self, name: &str, filesize: usize) -> io::Result<EmulatedFile> {
+        let staged = memfd(name)?;
+        let mut state = self.inner.state.lock();
+        match state.index.get(name) {
+            Some(entry) => state.load_file(entry, &staged)?,
+            None => {
+                state
+                    .index
+                    .insert(name.to_string(), ImageFileEntry::default());
+            }
+        }
+        let writeback: interface::RustRfc<dyn DataWriteBack> = self.inner.clone();
+        Ok(EmulatedFile::from_file(
+            name.to_string(),
+            staged,
+            filesize,
+            Some(writeback),
+        ))
+    }
+
+    fn remove_data(&self, name: &str) -> io::Result<()> {
+        let mut state = self.inner.state.lock();
+        match state.index.remove(name) {
+            Some(entry) => {
+                for extent in entry.extents {
+                    state.release_extent(extent);
+                }
+                Ok(())
+            }
+            None => Err(io::Error::from(io::ErrorKind::NotFound)),
+        }
+    }
+
+    fn read_metadata(&self) -> io::Result<Option<Vec<u8>>> {
+        let state = self.inner.state.lock();
+        match state.metadata {
+            Some(region) => state.read_region(region).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn write_metadata(&self, bytes: &[u8]) -> io::Result<()> {
+        self.inner.state.lock().commit(Some(bytes))
+    }
+
+    fn set_aside_metadata(&self) -> io::Result<()> {
+        let mut state = self.inner.state.lock();
+        let region = match state.metadata.take() {
+            Some(region) => region,
+            None => return Err(io::Error::from(io::ErrorKind::NotFound)),
+        };
+        // the corrupt metadata simply becomes the data of a file of that name
+        state.index.insert(
+            METADATACORRUPTFILENAME.to_string(),
+            ImageFileEntry {
+                size: region.len,
+                extents: vec![ImageExtent {
+                    fileblock: 0,
+                    start: region.start,
+                    blocks: blocks_for(region.len),
+                }],
+            },
+        );
+        state.commit(None)
+    }
+
+    fn read_log(&self) -> io::Result<Option<Vec<u8>>> {
+        read_host_file(&self.log_path())
+    }
+
+    fn create_log(&self) -> io::Result<EmulatedFileMap> {
+        let logpath = self.log_path();
+        remove_host_file(&logpath)?;
+        let f = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&logpath)?;
+        EmulatedFileMap::from_file(logpath.to_string_lossy().into_owned(), f)
+    }
+
+    fn remove_log(&self) -> io::Result<()> {
+        remove_host_file(&self.log_path())
+    }
+
+    fn clone_data(&self, from: &str, to: &str, _filesize: usize) -> io::Result<()> {
+        let mut state = self.inner.state.lock();
+        let entry = match state.index.get(from) {
+            Some(entry) => entry.clone(),
+            None => return Err(io::Error::from(io::ErrorKind::NotFound)),
+        };
+        for extent in &entry.extents {
+            state.share_extent(*extent);
+        }
+        if let Some(old) = state.index.insert(to.to_string(), entry) {
+            for extent in old.extents {
+                state.release_extent(extent);
+            }
+        }
+        state.commit(None)
+    }
+
+    // Blocks are shared, so a change only copies the blocks it touches
+    fn shares_data(&self) -> bool {
+        true
+    }
+
+    // Blocks are shared whenever they are stored, so this only tells whether
+    // any of them is
+    fn dedup_data(&self, name: &str, _filesize: usize) -> io::Result<bool> {
+        let state = self.inner.state.lock();
+        match state.index.get(name) {
+            Some(entry) => Ok(entry.extents.iter().any(|extent| {
+                (extent.start..extent.start + extent.blocks).any(|b| state.refs.contains_key(&b))
+            })),
+            None => Err(io::Error::from(io::ErrorKind::NotFound)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    // Writes a file with data at both ends and a hole in between, saves
+    // metadata, and checks both can be read back
+    fn exercise_backend(backend: &dyn StorageBackend) {
+        let far = 3 * IMAGE_BLOCK_SIZE as usize + 5;
+        let mut file = backend.open_data("linddata.9", 0).unwrap();
+        file.writeat(b"head".as_ptr(), 4, 0).unwrap();
+        file.zerofill_at(4, far - 4).unwrap();
+        file.writeat(b"tail".as_ptr(), 4, far).unwrap();
+        file.close().unwrap();
+
+        let file = backend.open_data("linddata.9", far + 4).unwrap();
+        let mut buf = [0u8; 4];
+        file.readat(buf.as_mut_ptr(), 4, 0).unwrap();
+        assert_eq!(&buf, b"head");
+        file.readat(buf.as_mut_ptr(), 4, far).unwrap();
+        assert_eq!(&buf, b"tail");
+        file.readat(buf.as_mut_ptr(), 4, IMAGE_BLOCK_SIZE as usize)
+            .unwrap();
+        assert_eq!(buf, [0u8; 4]);
+        file.close().unwrap();
+
+        assert_eq!(backend.read_metadata().unwrap(), None);
+        backend.write_metadata(b"first").unwrap();
+        backend.write_metadata(b"second").unwrap();
+        assert_eq!(backend.read_metadata().unwrap().unwrap(), b"second");
+
+        let mut log = backend.create_log().unwrap();
+        log.write_to_map(b"entry").unwrap();
+        let logread = backend.read_log().unwrap().unwrap();
+        assert_eq!(&logread[8..13], b"entry");
+        log.close().unwrap();
+        backend.remove_log().unwrap();
+        assert_eq!(backend.read_log().unwrap(), None);
+
+        backend.remove_data("linddata.9").unwrap();
+        assert!(backend.remove_data("linddata.9").is_err());
+    }
+
+    // Writes a file with data in its first and last blocks only, and checks
+    // through `reopen`, which gives a backend on the same storage, that closing
+    // the file left its data there with the blocks in between still a hole
+    fn exercise_write_back(
+        backend: &dyn StorageBackend,
+        reopen: &dyn Fn() -> interface::RustRfc<dyn StorageBackend>,
+    ) {
+        let far = 5 * IMAGE_BLOCK_SIZE as usize;
+        let mut file = backend.open_data("linddata.50", 0).unwrap();
+        file.writeat(b"head".as_ptr(), 4, 0).unwrap();
+        file.zerofill_at(4, far - 4).unwrap();
+        file.writeat(b"tail".as_ptr(), 4, far).unwrap();
+        file.writeat(b"HEAD".as_ptr(), 4, 0).unwrap();
+        file.close().unwrap();
+
+        let reopened = reopen();
+        let file = reopened.open_data("linddata.50", far + 4).unwrap();
+        let mut buf = [0u8; 4];
+        file.readat(buf.as_mut_ptr(), 4, 0).unwrap();
+        assert_eq!(&buf, b"HEAD");
+        file.readat(buf.as_mut_ptr(), 4, far).unwrap();
+        assert_eq!(&buf, b"tail");
+        file.readat(buf.as_mut_ptr(), 4, 2 * IMAGE_BLOCK_SIZE as usize)
+            .unwrap();
+        assert_eq!(buf, [0u8; 4]);
+        assert_eq!(
+            file.seek_data_or_hole(0, libc::SEEK_HOLE).unwrap(),
+            IMAGE_BLOCK_SIZE as usize
+        );
+        assert_eq!(
+            file.seek_data_or_hole(IMAGE_BLOCK_SIZE as usize, libc::SEEK_DATA)
+                .unwrap(),
+            far
+        );
+        file.close().unwrap();
+        reopened.remove_data("linddata.50").unwrap();
+    }
+
+    fn read_data(backend: &dyn StorageBackend, name: &str, len: usize) -> Vec<u8> {
+        let file = backend.open_data(name, len).unwrap();
+        let mut buf = vec![0u8; len];
+        file.readat(buf.as_mut_ptr(), len, 0).unwrap();
+        file.close().unwrap();
+        buf
+    }
+
+    // Stores two files with the same data and one with other data, and clones
+    // the first one, then checks that changing any of them leaves the others
+    // as they were
+    fn exercise_sharing(backend: &dyn StorageBackend) {
+        let len = 2 * IMAGE_BLOCK_SIZE as usize;
+        let same = vec![7u8; len];
+        let other = vec![8u8; len];
+        for (name, data) in [
+            ("linddata.20", &same),
+            ("linddata.21", &same),
+            ("linddata.22", &other),
+        ] {
+            let mut file = backend.open_data(name, 0).unwrap();
+            file.writeat(data.as_ptr(), len, 0).unwrap();
+            file.close().unwrap();
+            backend.dedup_data(name, len).unwrap();
+        }
+        backend
+            .clone_data("linddata.20", "linddata.23", len)
+            .unwrap();
+
+        for name in ["linddata.21", "linddata.23"] {
+            let mut file = backend.open_data(name, len).unwrap();
+            file.writeat(name.as_ptr(), name.len(), 0).unwrap();
+            file.close().unwrap();
+        }
+        assert_eq!(read_data(backend, "linddata.20", len), same);
+        assert_eq!(read_data(backend, "linddata.22", len), other);
+        for name in ["linddata.21", "linddata.23"] {
+            let data = read_data(backend, name, len);
+            assert_eq!(&data[..name.len()], name.as_bytes());
+            assert_eq!(data[name.len()..], same[name.len()..]);
+        }
+
+        for name in ["linddata.20", "linddata.21", "linddata.22", "linddata.23"] {
+            backend.remove_data(name).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_host_dir_backend() {
+        let dir = tempdir().unwrap();
+        let backend = HostDirBackend::new(dir.path().join("data")).unwrap();
+        exercise_backend(&backend);
+        assert!(!dir.path().join("data").join(METADATATMPFILENAME).exists());
+    }
+
+    #[test]
+    fn test_host_dir_write_back() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("data");
+        let backend = HostDirBackend::new(&root).unwrap();
+        exercise_write_back(&backend, &|| {
+            interface::RustRfc::new(HostDirBackend::new(&root).unwrap())
+        });
+    }
+
+    #[test]
+    fn test_host_dir_sharing() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("data");
+        let backend = HostDirBackend::new(&root).unwrap();
+        exercise_sharing(&backend);
+        assert_eq!(fs::read_dir(root.join(OBJECTSDIRNAME)).unwrap().count(), 0);
+
+        // identical files are links to one host file, and a clone is another
+        let nlink = |name: &str| fs::metadata(root.join(name)).unwrap().nlink();
+        for name in ["linddata.30", "linddata.31"] {
+            let mut file = backend.open_data(name, 0).unwrap();
+            file.writeat(b"shared".as_ptr(), 6, 0).unwrap();
+            file.close().unwrap();
+        }
+        assert!(!backend.dedup_data("linddata.30", 6).unwrap());
+        assert!(backend.dedup_data("linddata.31", 6).unwrap());
+        backend.clone_data("linddata.31", "linddata.32", 6).unwrap();
+        assert_eq!(nlink("linddata.30"), 4);
+
+        // a file that is open when it gets cloned copies its data once it is
+        // told that it is shared
+        let mut file = backend.open_data("linddata.33", 0).unwrap();
+        file.writeat(b"before".as_ptr(), 6, 0).unwrap();
+        backend.clone_data("linddata.33", "linddata.34", 6).unwrap();
+        file.mark_shared();
+        file.writeat(b"after!".as_ptr(), 6, 0).unwrap();
+        file.close().unwrap();
+        assert_eq!(read_data(&backend, "linddata.34", 6), b"before");
+        assert_eq!(nlink("linddata.33"), 1);
+
+        // the shared content goes away with the last file that links to it
+        for name in ["linddata.30", "linddata.31", "linddata.32"] {
+            backend.remove_data(name).unwrap();
+        }
+        assert_eq!(fs::read_dir(root.join(OBJECTSDIRNAME)).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_memory_backend() {
+        let backend = MemoryBackend::new();
+        exercise_backend(&backend);
+        exercise_sharing(&backend);
+        backend.write_metadata(b"corrupt").unwrap();
+        backend.set_aside_metadata().unwrap();
+        assert_eq!(backend.read_metadata().unwrap(), None);
+    }
+
+    #[test]
+    fn test_memory_write_back() {
+        let backend = interface::RustRfc::new(MemoryBackend::new());
+        exercise_write_back(&*backend, &|| backend.clone());
+    }
+
+    #[test]
+    fn test_image_backend() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fs.img");
+        exercise_backend(&ImageBackend::open(&path).unwrap());
+
+        // data and metadata survive reopening the image, and holes are not stored
+        let backend = ImageBackend::open(&path).unwrap();
+        let far = 64 * IMAGE_BLOCK_SIZE as usize;
+        let mut file = backend.open_data("linddata.10", 0).unwrap();
+        file.zerofill_at(0, far).unwrap();
+        file.writeat(b"sparse".as_ptr(), 6, far).unwrap();
+        file.close().unwrap();
+        backend.write_metadata(b"metadata").unwrap();
+        drop(backend);
+        assert!(fs::metadata(&path).unwrap().len() < far as u64);
+
+        let backend = ImageBackend::open(&path).unwrap();
+        assert_eq!(backend.read_metadata().unwrap().unwrap(), b"metadata");
+        let file = backend.open_data("linddata.10", far + 6).unwrap();
+        let mut buf = [0u8; 6];
+        file.readat(buf.as_mut_ptr(), 6, far).unwrap();
+        assert_eq!(&buf, b"sparse");
+        backend.write_metadata(b"newer").unwrap();
+        drop(backend);
+
+        // a torn write of the newest superblock falls back to the one before
+        let image = OpenOptions::new().write(true).open(&path).unwrap();
+        let backend = ImageBackend::open(&path).unwrap();
+        let generation = backend.inner.state.lock().generation;
+        drop(backend);
+        image
+            .write_all_at(&[0xFF; 16], (generation % 2) * IMAGE_BLOCK_SIZE + 20)
+            .unwrap();
+        let backend = ImageBackend::open(&path).unwrap();
+        assert_eq!(backend.inner.state.lock().generation, generation - 1);
+        assert_eq!(backend.read_metadata().unwrap().unwrap(), b"metadata");
+    }
+
+    #[test]
+    fn test_image_sharing() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fs.img");
+        let backend = ImageBackend::open(&path).unwrap();
+        exercise_sharing(&backend);
+        assert!(backend.inner.state.lock().refs.is_empty());
+
+        // identical blocks are stored once, also when the image was reopened
+        // in between
+        let len = 8 * IMAGE_BLOCK_SIZE as usize;
+        let data: Vec<u8> = (0..len).map(|i| (i / 4096) as u8 + 1).collect();
+        let mut file = backend.open_data("linddata.40", 0).unwrap();
+        file.writeat(data.as_ptr(), len, 0).unwrap();
+        file.close().unwrap();
+        drop(backend);
+        let backend = ImageBackend::open(&path).unwrap();
+        let before = backend.inner.state.lock().block_count;
+        let mut file = backend.open_data("linddata.41", 0).unwrap();
+        file.writeat(data.as_ptr(), len, 0).unwrap();
+        file.close().unwrap();
+        assert!(backend.inner.state.lock().block_count < before + 8);
+        assert!(backend.dedup_data("linddata.41", len).unwrap());
+        backend
+            .clone_data("linddata.41", "linddata.42", len)
+            .unwrap();
+        drop(backend);
+
+        // and their reference counts are taken from the index on opening
+        let backend = ImageBackend::open(&path).unwrap();
+        assert_eq!(backend.inner.state.lock().refs.len(), 8);
+        backend.remove_data("linddata.40").unwrap();
+        backend.remove_data("linddata.41").unwrap();
+        assert_eq!(read_data(&backend, "linddata.42", len), data);
+        assert!(!backend.dedup_data("linddata.42", len).unwrap());
+    }
+
+    #[test]
+    fn test_image_write_back() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fs.img");
+        let backend = ImageBackend::open(&path).unwrap();
+        exercise_write_back(&backend, &|| {
+            interface::RustRfc::new(ImageBackend::open(&path).unwrap())
+        });
+
+        // data is only staged until the file is closed
+        let mut file = backend.open_data("linddata.51", 0).unwrap();
+        file.writeat(b"staged".as_ptr(), 6, 0).unwrap();
+        let reopened = ImageBackend::open(&path).unwrap();
+        assert!(!reopened
+            .inner
+            .state
+            .lock()
+            .index
+            .contains_key("linddata.51"));
+        file.close().unwrap();
+        assert_eq!(
+            read_data(&ImageBackend::open(&path).unwrap(), "linddata.51", 6),
+            b"staged"
+        );
+
+        // and only the blocks holding data are given extents
+        let far = 5 * IMAGE_BLOCK_SIZE as usize;
+        let mut file = backend.open_data("linddata.52", 0).unwrap();
+        file.writeat(b"head".as_ptr(), 4, 0).unwrap();
+        file.zerofill_at(4, far - 4).unwrap();
+        file.writeat(b"tail".as_ptr(), 4, far).unwrap();
+        file.close().unwrap();
+        let reopened = ImageBackend::open(&path).unwrap();
+        let entry = reopened.inner.state.lock().index["linddata.52"].clone();
+        assert_eq!(entry.size, far as u64 + 4);
+        let blocks: Vec<(u64, u64)> = entry
+            .extents
+            .iter()
+            .map(|extent| (extent.fileblock, extent.blocks))
+            .collect();
+        assert_eq!(blocks, vec![(0, 1), (5, 1)]);
+    }
+
+    #[test]
+    fn test_image_backend_rejects_truncated() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fs.img");
+        let backend = ImageBackend::open(&path).unwrap();
+        let data = vec![3u8; 4 * IMAGE_BLOCK_SIZE as usize];
+        let mut file = backend.open_data("linddata.60", 0).unwrap();
+        file.writeat(data.as_ptr(), data.len(), 0).unwrap();
+        file.close().unwrap();
+        backend.write_metadata(b"metadata").unwrap();
+        drop(backend);
+
+        // an image cut short anywhere, into the superblocks too, is refused
+        // rather than read as if the lost blocks held zeroes
+        let len = fs::metadata(&path).unwrap().len();
+        let image = OpenOptions::new().write(true).open(&path).unwrap();
+        for cut in [len - IMAGE_BLOCK_SIZE, IMAGE_BLOCK_SIZE + 10, 100] {
+            image.set_len(cut).unwrap();
+            let err = ImageBackend::open(&path).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn test_image_backend_rejects_garbage() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("garbage.img");
+        fs::write(&path, vec![7u8; 3 * IMAGE_BLOCK_SIZE as usize]).unwrap();
+        assert!(ImageBackend::open(&path).is_err());
+    }
+}
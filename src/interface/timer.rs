@@ -18,6 +18,20 @@ pub fn timestamp() -> u64 {
         .as_secs()
 }
 
+// Nanoseconds since the epoch, the resolution inode timestamps are kept in
+pub fn timestamp_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64
+}
+
+// Split nanoseconds since the epoch into whole seconds and the remaining
+// nanoseconds, the way a timespec holds them
+pub fn nanos_to_timespec(nanos: u64) -> (u64, u64) {
+    (nanos / 1_000_000_000, nanos % 1_000_000_000)
+}
+
 // Create a new timer
 pub fn starttimer() -> RustInstant {
     RustInstant::now()
@@ -140,3 +154,143 @@ impl IntervalTimer {
         self.clone()
     }
 }
+
+// Reads the clock a timerfd measures time with, which is either the time
+// since the epoch or the time since some arbitrary point for the monotonic
+// clock
+pub fn clock_now(monotonic: bool) -> RustDuration {
+    if !monotonic {
+        return SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap();
+    }
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) };
+    RustDuration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+}
+
+#[derive(Debug)]
+struct _ExpirationTimer {
+    pub armed_instant: RustInstant, // When the timer was last set
+    pub value: RustDuration,        // How long after that it first expires
+    pub interval: RustDuration,     // How often it expires from then on
+    pub consumed: u64,              // The expirations that were already read
+
+    pub is_armed: bool,
+}
+
+/// A timer that keeps count of how many times it has expired, the way a
+/// timerfd does. Rather than having a thread tick it, the expirations are
+/// worked out from the time that has passed since it was set.
+#[derive(Debug)]
+pub struct ExpirationTimer {
+    _ac: Mutex<_ExpirationTimer>,
+}
+
+impl _ExpirationTimer {
+    // The number of times the timer has expired since it was set
+    fn total_expirations(&self) -> u64 {
+        if !self.is_armed {
+            return 0;
+        }
+        let elapsed = self.armed_instant.elapsed();
+        if elapsed < self.value {
+            0
+        } else if self.interval.is_zero() {
+            1
+        } else {
+            1 + ((elapsed - self.value).as_nanos() / self.interval.as_nanos()) as u64
+        }
+    }
+
+    // The time left until the timer next expires, which is zero once a timer
+    // without an interval has expired
+    fn remaining(&self) -> RustDuration {
+        if !self.is_armed {
+            return RustDuration::ZERO;
+        }
+        let elapsed = self.armed_instant.elapsed();
+        if elapsed < self.value {
+            self.value - elapsed
+        } else if self.interval.is_zero() {
+            RustDuration::ZERO
+        } else {
+            let since_last = (elapsed - self.value).as_nanos() % self.interval.as_nanos();
+            self.interval - RustDuration::from_nanos(since_last as u64)
+        }
+    }
+}
+
+impl ExpirationTimer {
+    pub fn new() -> Self {
+        Self {
+            _ac: Mutex::new(_ExpirationTimer {
+                armed_instant: RustInstant::now(),
+                value: RustDuration::ZERO,
+                interval: RustDuration::ZERO,
+                consumed: 0,
+                is_armed: false,
+            }),
+        }
+    }
+
+    // Similar to timerfd_gettime. Returns (time until the next expiration,
+    // interval)
+    pub fn get_time(&self) -> (RustDuration, RustDuration) {
+        let guard = self._ac.lock().unwrap();
+        (guard.remaining(), guard.interval)
+    }
+
+    // Similar to timerfd_settime, with the first expiration relative to now.
+    // A zero value disarms the timer. Either way the expirations that haven't
+    // been read are dropped. Returns the previous setting
+    pub fn set_time(
+        &self,
+        value: RustDuration,
+        interval: RustDuration,
+    ) -> (RustDuration, RustDuration) {
+        let mut guard = self._ac.lock().unwrap();
+        let old = (guard.remaining(), guard.interval);
+
+        guard.armed_instant = RustInstant::now();
+        guard.value = value;
+        guard.interval = interval;
+        guard.consumed = 0;
+        guard.is_armed = !value.is_zero();
+        old
+    }
+
+    // The expirations that haven't been read yet
+    pub fn expirations(&self) -> u64 {
+        let guard = self._ac.lock().unwrap();
+        guard.total_expirations() - guard.consumed
+    }
+
+    // Reads the expirations, so that they aren't counted again
+    pub fn take_expirations(&self) -> u64 {
+        let mut guard = self._ac.lock().unwrap();
+        let total = guard.total_expirations();
+        let count = total - guard.consumed;
+        guard.consumed = total;
+        count
+    }
+
+    // The time left until the timer next expires, if it is armed and will
+    // expire again
+    pub fn until_next_expiration(&self) -> Option<RustDuration> {
+        let guard = self._ac.lock().unwrap();
+        if !guard.is_armed || (guard.interval.is_zero() && guard.total_expirations() > 0) {
+            return None;
+        }
+        Some(guard.remaining())
+    }
+}
+
+impl Default for ExpirationTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -2,8 +2,6 @@
 use crate::interface;
 use crate::interface::errnos::{syscall_error, Errno};
 
-const SIZEOF_SOCKADDR: u32 = 16;
-
 //redefining the FSData struct in this file so that we maintain flow of program
 //derive eq attributes for testing whether the structs equal other fsdata
 // structs from stat/fstat
@@ -15,9 +13,9 @@ pub struct FSData {
     pub f_blocks: u64,
     pub f_bfree: u64,
     pub f_bavail: u64,
-    //total files in the file system -- should be infinite
+    //total files (inodes) the file system can hold
     pub f_files: u64,
-    //free files in the file system -- should be infinite
+    //free files (inodes) left in the file system
     pub f_ffiles: u64,
     pub f_fsid: u64,
     //not really a limit for naming, but 254 works
@@ -43,13 +41,14 @@ pub struct StatData {
     pub st_size: usize,
     pub st_blksize: i32,
     pub st_blocks: u32,
-    //currently we don't populate or care about the time bits here
+    //seconds and nanoseconds since the epoch
     pub st_atim: (u64, u64),
     pub st_mtim: (u64, u64),
     pub st_ctim: (u64, u64),
 }
 
 //R Limit for getrlimit system call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[repr(C)]
 pub struct Rlimit {
     pub rlim_cur: u64,
@@ -71,12 +70,35 @@ pub struct SockPair {
 }
 
 //EPOLL
-#[derive(Copy, Clone, Debug)]
+//The user data of an epoll event, which epoll hands back untouched. As in
+// native, it is a union of whatever the program wants to keep there
+#[derive(Copy, Clone)]
 #[repr(C)]
+pub union EpollData {
+    pub ptr: *mut std::ffi::c_void,
+    pub fd: i32,
+    pub u32: u32,
+    pub u64: u64,
+}
+
+//This is packed like the native struct epoll_event on x86_64, so that arrays
+// of events line up with the ones programs pass in
+#[derive(Copy, Clone)]
+#[repr(C, packed)]
 pub struct EpollEvent {
     pub events: u32,
-    pub fd: i32, /*in native this is a union which could be one of a number of things
-                  *however, we only support EPOLL_CTL subcommands which take the fd */
+    pub data: EpollData,
+}
+
+impl std::fmt::Debug for EpollEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let events = self.events;
+        let data = unsafe { self.data.u64 };
+        f.debug_struct("EpollEvent")
+            .field("events", &events)
+            .field("data", &data)
+            .finish()
+    }
 }
 
 #[derive(Debug, Default)]
@@ -93,24 +115,114 @@ pub struct SockaddrDummy {
     pub _sa_data: [u16; 14],
 }
 
+#[derive(Debug, Clone, Copy, Default)]
 #[repr(C)]
 pub struct TimeVal {
     pub tv_sec: i64,
     pub tv_usec: i64,
 }
 
+// Resource usage of a cage, as reported by wait4 for a reaped child
+#[derive(Debug, Clone, Copy, Default)]
+#[repr(C)]
+pub struct Rusage {
+    pub ru_utime: TimeVal,
+    pub ru_stime: TimeVal,
+    pub ru_maxrss: i64,
+    pub ru_ixrss: i64,
+    pub ru_idrss: i64,
+    pub ru_isrss: i64,
+    pub ru_minflt: i64,
+    pub ru_majflt: i64,
+    pub ru_nswap: i64,
+    pub ru_inblock: i64,
+    pub ru_oublock: i64,
+    pub ru_msgsnd: i64,
+    pub ru_msgrcv: i64,
+    pub ru_nsignals: i64,
+    pub ru_nvcsw: i64,
+    pub ru_nivcsw: i64,
+}
+
+impl Rusage {
+    // adds the usage in `other` to ours, keeping the larger of the two resident
+    // set sizes since that one is a peak rather than a total
+    pub fn accumulate(&mut self, other: &Rusage) {
+        let add = |ours: &mut TimeVal, theirs: &TimeVal| {
+            let usec = ours.tv_usec + theirs.tv_usec;
+            ours.tv_sec += theirs.tv_sec + usec / 1_000_000;
+            ours.tv_usec = usec % 1_000_000;
+        };
+        add(&mut self.ru_utime, &other.ru_utime);
+        add(&mut self.ru_stime, &other.ru_stime);
+        self.ru_maxrss = self.ru_maxrss.max(other.ru_maxrss);
+        self.ru_ixrss += other.ru_ixrss;
+        self.ru_idrss += other.ru_idrss;
+        self.ru_isrss += other.ru_isrss;
+        self.ru_minflt += other.ru_minflt;
+        self.ru_majflt += other.ru_majflt;
+        self.ru_nswap += other.ru_nswap;
+        self.ru_inblock += other.ru_inblock;
+        self.ru_oublock += other.ru_oublock;
+        self.ru_msgsnd += other.ru_msgsnd;
+        self.ru_msgrcv += other.ru_msgrcv;
+        self.ru_nsignals += other.ru_nsignals;
+        self.ru_nvcsw += other.ru_nvcsw;
+        self.ru_nivcsw += other.ru_nivcsw;
+    }
+}
+
 #[repr(C)]
 pub struct ITimerVal {
     pub it_interval: TimeVal,
     pub it_value: TimeVal,
 }
 
+#[derive(Debug, Clone, Copy, Default)]
 #[repr(C)]
 pub struct TimeSpec {
     pub tv_sec: i64,
     pub tv_nsec: i64,
 }
 
+// The setting of a timerfd, as passed to timerfd_settime and returned by
+// timerfd_gettime
+#[derive(Debug, Clone, Copy, Default)]
+#[repr(C)]
+pub struct ITimerSpec {
+    pub it_interval: TimeSpec,
+    pub it_value: TimeSpec,
+}
+
+// What a read from a signalfd returns for each signal, laid out the way Linux
+// lays out struct signalfd_siginfo
+#[derive(Debug, Clone, Copy, Default)]
+#[repr(C)]
+pub struct SignalfdSiginfo {
+    pub ssi_signo: u32,
+    pub ssi_errno: i32,
+    pub ssi_code: i32,
+    pub ssi_pid: u32,
+    pub ssi_uid: u32,
+    pub ssi_fd: i32,
+    pub ssi_tid: u32,
+    pub ssi_band: u32,
+    pub ssi_overrun: u32,
+    pub ssi_trapno: u32,
+    pub ssi_status: i32,
+    pub ssi_int: i32,
+    pub ssi_ptr: u64,
+    pub ssi_utime: u64,
+    pub ssi_stime: u64,
+    pub ssi_addr: u64,
+    pub ssi_addr_lsb: u16,
+    pub __pad2: u16,
+    pub ssi_syscall: i32,
+    pub ssi_call_addr: u64,
+    pub ssi_arch: u32,
+    pub __pad: [u8; 28],
+}
+
 #[derive(Copy, Clone)]
 #[repr(C)]
 pub union IoctlPtrUnion {
@@ -159,6 +271,9 @@ pub struct SigactionStruct {
 }
 
 pub type IovecStruct = libc::iovec;
+// message header and ancillary data header used by sendmsg/recvmsg
+pub type MsgHdr = libc::msghdr;
+pub type CmsgHdr = libc::cmsghdr;
 
 //redefining the Arg union to maintain the flow of the program
 #[derive(Copy, Clone)]
@@ -180,6 +295,8 @@ pub union Arg {
     pub dispatch_cstrarr: *const *const i8, /* Typically corresponds to a passed in string array
                                              * of type char* const[] as in execve */
     pub dispatch_rlimitstruct: *mut Rlimit,
+    pub dispatch_constrlimitstruct: *const Rlimit,
+    pub dispatch_rusagestruct: *mut Rusage,
     pub dispatch_statdatastruct: *mut StatData,
     pub dispatch_fsdatastruct: *mut FSData,
     pub dispatch_shmidstruct: *mut ShmidsStruct,
@@ -187,10 +304,12 @@ pub union Arg {
     pub dispatch_sockaddrstruct: *mut SockaddrDummy,
     pub dispatch_socklen_t_ptr: *mut u32,
     pub dispatch_intptr: *mut i32,
+    pub dispatch_longptr: *mut i64,
     pub dispatch_pollstructarray: *mut PollStruct,
     pub dispatch_epollevent: *mut EpollEvent,
     pub dispatch_structtimeval: *mut TimeVal,
     pub dispatch_structtimespec: *mut TimeSpec,
+    pub dispatch_consttimespecpair: *const [TimeSpec; 2],
     pub dispatch_pipearray: *mut PipeArray,
     pub dispatch_sockpair: *mut SockPair,
     pub dispatch_ioctlptrunion: IoctlPtrUnion,
@@ -200,8 +319,12 @@ pub union Arg {
     pub dispatch_constsigsett: *const SigsetType,
     pub dispatch_structitimerval: *mut ITimerVal,
     pub dispatch_conststructitimerval: *const ITimerVal,
+    pub dispatch_structitimerspec: *mut ITimerSpec,
+    pub dispatch_conststructitimerspec: *const ITimerSpec,
     pub dispatch_fdset: *mut libc::fd_set,
     pub dispatch_constiovecstruct: *const interface::IovecStruct,
+    pub dispatch_msghdr: *mut MsgHdr,
+    pub dispatch_constmsghdr: *const MsgHdr,
 }
 
 use std::mem::size_of;
@@ -377,6 +500,30 @@ pub fn get_iovecstruct(union_argument: Arg) -> Result<*const interface::IovecStr
     ));
 }
 
+pub fn get_msghdr<'a>(union_argument: Arg) -> Result<&'a mut MsgHdr, i32> {
+    let pointer = unsafe { union_argument.dispatch_msghdr };
+    if !pointer.is_null() {
+        return Ok(unsafe { &mut *pointer });
+    }
+    return Err(syscall_error(
+        Errno::EFAULT,
+        "dispatcher",
+        "input data not valid",
+    ));
+}
+
+pub fn get_constmsghdr<'a>(union_argument: Arg) -> Result<&'a MsgHdr, i32> {
+    let pointer = unsafe { union_argument.dispatch_constmsghdr };
+    if !pointer.is_null() {
+        return Ok(unsafe { &*pointer });
+    }
+    return Err(syscall_error(
+        Errno::EFAULT,
+        "dispatcher",
+        "input data not valid",
+    ));
+}
+
 pub fn get_statdatastruct<'a>(union_argument: Arg) -> Result<&'a mut StatData, i32> {
     let pointer = unsafe { union_argument.dispatch_statdatastruct };
     if !pointer.is_null() {
@@ -389,6 +536,51 @@ pub fn get_statdatastruct<'a>(union_argument: Arg) -> Result<&'a mut StatData, i
     ));
 }
 
+pub fn get_rlimitstruct<'a>(union_argument: Arg) -> Result<&'a mut Rlimit, i32> {
+    let pointer = unsafe { union_argument.dispatch_rlimitstruct };
+    if !pointer.is_null() {
+        Ok(unsafe { &mut *pointer })
+    } else {
+        Err(syscall_error(
+            Errno::EFAULT,
+            "dispatcher",
+            "input data not valid",
+        ))
+    }
+}
+
+pub fn get_constrlimitstruct<'a>(union_argument: Arg) -> Result<&'a Rlimit, i32> {
+    let pointer = unsafe { union_argument.dispatch_constrlimitstruct };
+    if !pointer.is_null() {
+        Ok(unsafe { &*pointer })
+    } else {
+        Err(syscall_error(
+            Errno::EFAULT,
+            "dispatcher",
+            "input data not valid",
+        ))
+    }
+}
+
+// for the case where the pointer being Null is normal
+pub fn get_rlimitstruct_null<'a>(union_argument: Arg) -> Result<Option<&'a mut Rlimit>, i32> {
+    let pointer = unsafe { union_argument.dispatch_rlimitstruct };
+    if !pointer.is_null() {
+        Ok(Some(unsafe { &mut *pointer }))
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn get_constrlimitstruct_null<'a>(union_argument: Arg) -> Result<Option<&'a Rlimit>, i32> {
+    let pointer = unsafe { union_argument.dispatch_constrlimitstruct };
+    if !pointer.is_null() {
+        Ok(Some(unsafe { &*pointer }))
+    } else {
+        Ok(None)
+    }
+}
+
 pub fn get_fsdatastruct<'a>(union_argument: Arg) -> Result<&'a mut FSData, i32> {
     let pointer = unsafe { union_argument.dispatch_fsdatastruct };
     if !pointer.is_null() {
@@ -425,6 +617,12 @@ pub fn get_ioctl_int<'a>(ptrunion: IoctlPtrUnion) -> Result<i32, i32> {
     return Err(syscall_error(Errno::EFAULT, "ioctl", "argp is not valid"));
 }
 
+// For requests that take their argument by value, like FICLONE, the argument
+// is passed in place of the pointer
+pub fn get_ioctl_value(ptrunion: IoctlPtrUnion) -> i32 {
+    unsafe { ptrunion.int_ptr as usize as i32 }
+}
+
 pub fn get_ioctl_char<'a>(ptrunion: IoctlPtrUnion) -> Result<u8, i32> {
     let pointer = unsafe { ptrunion.c_char_ptr };
     if !pointer.is_null() {
@@ -517,8 +715,10 @@ pub fn get_sockaddr(union_argument: Arg, addrlen: u32) -> Result<interface::GenS
         match tmpsock.sa_family {
             /* AF_UNIX */
             1 => {
-                if addrlen < SIZEOF_SOCKADDR
-                    || addrlen > size_of::<interface::SockaddrUnix>() as u32
+                //a unix domain address may be as short as its family, as the path or
+                //abstract name that follows it can be cut off at any length
+                if addrlen < size_of::<u16>() as u32
+                    || addrlen > interface::SOCKADDR_UNIX_SIZE as u32
                 {
                     return Err(syscall_error(
                         Errno::EINVAL,
@@ -526,8 +726,18 @@ pub fn get_sockaddr(union_argument: Arg, addrlen: u32) -> Result<interface::GenS
                         "input length incorrect for family of sockaddr",
                     ));
                 }
-                let unix_ptr = pointer as *const interface::SockaddrUnix;
-                return Ok(interface::GenSockaddr::Unix(unsafe { *unix_ptr }));
+                //Only addrlen bytes are copied and the rest of the path is left zeroed, as
+                //abstract addresses are not null terminated
+                let mut unixaddr = interface::new_sockaddr_unix(tmpsock.sa_family, &[]);
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        pointer as *const u8,
+                        &mut unixaddr as *mut interface::SockaddrUnix as *mut u8,
+                        addrlen as usize,
+                    );
+                }
+                unixaddr.addrlen = addrlen as usize;
+                return Ok(interface::GenSockaddr::Unix(unixaddr));
             }
             /* AF_INET */
             2 => {
@@ -578,7 +788,7 @@ pub fn copy_out_sockaddr(union_argument: Arg, len_argument: Arg, gensock: interf
     let mut mutgensock = gensock;
     match mutgensock {
         interface::GenSockaddr::Unix(ref mut unixa) => {
-            let unixlen = size_of::<interface::SockaddrUnix>() as u32;
+            let unixlen = interface::SOCKADDR_UNIX_SIZE as u32;
 
             let fullcopylen = interface::rust_min(initaddrlen, unixlen);
             unsafe {
@@ -698,6 +908,33 @@ pub fn get_int_from_intptr(union_argument: Arg) -> i32 {
     return unsafe { *union_argument.dispatch_intptr };
 }
 
+pub fn get_intptr<'a>(union_argument: Arg) -> Result<Option<&'a mut i32>, i32> {
+    let pointer = unsafe { union_argument.dispatch_intptr };
+    if !pointer.is_null() {
+        Ok(Some(unsafe { &mut *pointer }))
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn get_longptr<'a>(union_argument: Arg) -> Result<Option<&'a mut i64>, i32> {
+    let pointer = unsafe { union_argument.dispatch_longptr };
+    if !pointer.is_null() {
+        Ok(Some(unsafe { &mut *pointer }))
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn get_rusagestruct<'a>(union_argument: Arg) -> Result<Option<&'a mut Rusage>, i32> {
+    let pointer = unsafe { union_argument.dispatch_rusagestruct };
+    if !pointer.is_null() {
+        Ok(Some(unsafe { &mut *pointer }))
+    } else {
+        Ok(None)
+    }
+}
+
 pub fn copy_out_intptr(union_argument: Arg, intval: i32) {
     unsafe {
         *union_argument.dispatch_intptr = intval;
@@ -735,6 +972,33 @@ pub fn get_constitimerval<'a>(union_argument: Arg) -> Result<Option<&'a ITimerVa
     }
 }
 
+pub fn get_itimerspec<'a>(union_argument: Arg) -> Result<Option<&'a mut ITimerSpec>, i32> {
+    let pointer = unsafe { union_argument.dispatch_structitimerspec };
+    if !pointer.is_null() {
+        Ok(Some(unsafe { &mut *pointer }))
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn get_constitimerspec<'a>(union_argument: Arg) -> Result<Option<&'a ITimerSpec>, i32> {
+    let pointer = unsafe { union_argument.dispatch_conststructitimerspec };
+    if !pointer.is_null() {
+        Ok(Some(unsafe { &*pointer }))
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn get_consttimespecpair<'a>(union_argument: Arg) -> Result<Option<&'a [TimeSpec; 2]>, i32> {
+    let pointer = unsafe { union_argument.dispatch_consttimespecpair };
+    if !pointer.is_null() {
+        Ok(Some(unsafe { &*pointer }))
+    } else {
+        Ok(None)
+    }
+}
+
 pub fn duration_fromtimespec(union_argument: Arg) -> Result<interface::RustDuration, i32> {
     let pointer = unsafe { union_argument.dispatch_structtimespec };
     if !pointer.is_null() {
@@ -11,6 +11,9 @@
 //! - Current Working Directory: A string representing the current working
 //!   directory of the cage.
 //! - Parent ID: An integer representing the ID of the parent cage.
+//! - Process Group ID: An integer representing the process group the cage
+//!   belongs to.
+//! - Session ID: An integer representing the session the cage belongs to.
 //! - File Descriptor Table: A locked hash map mapping integers to descriptor
 //!   enums.
 //!
@@ -23,9 +26,19 @@
 //! - Socket
 //! - Pipe
 //! - Epoll
+//! - EventFd
+//! - TimerFd
+//! - SignalFd
 //!
 //! Each descriptor type is a struct with specific fields, detailed in cage.rs.
 //!
+//! Child Events:
+//! When a child cage exits or is stopped, a `ChildEvent` recording its wait
+//! status is queued in its parent, where it stays until the parent collects
+//! it with one of the wait calls or exits itself. The record of an exited
+//! child is what Linux calls a zombie. Orphans are adopted by the init cage,
+//! which reaps them as soon as they exit.
+//!
 //! System Calls:
 //! - Cage objects provide public methods for various system calls, categorized
 //!   into filesystem-related, system-related, or network-related calls. Each
@@ -37,11 +50,12 @@ use crate::interface;
 //going to get the datatypes and errnos from the cage file from now on
 pub use crate::interface::errnos::{syscall_error, Errno};
 pub use crate::interface::types::{
-    Arg, EpollEvent, FSData, IoctlPtrUnion, PipeArray, PollStruct, Rlimit, ShmidsStruct, StatData,
+    Arg, EpollData, EpollEvent, FSData, IoctlPtrUnion, PipeArray, PollStruct, Rlimit, ShmidsStruct,
+    StatData,
 };
 
 use super::filesystem::normpath;
-use super::net::SocketHandle;
+use super::net::{SocketHandle, UnixMsgPipe};
 pub use super::syscalls::fs_constants::*;
 pub use super::syscalls::net_constants::*;
 pub use super::syscalls::sys_constants::*;
@@ -55,6 +69,9 @@ pub enum FileDescriptor {
     Socket(SocketDesc),
     Pipe(PipeDesc),
     Epoll(EpollDesc),
+    EventFd(EventFdDesc),
+    TimerFd(TimerFdDesc),
+    SignalFd(SignalFdDesc),
 }
 
 #[derive(Debug, Clone)]
@@ -82,29 +99,77 @@ pub struct SocketDesc {
     pub advlock: interface::RustRfc<interface::AdvisoryLock>,
 }
 
+//A descriptor opened on a named pipe also keeps the inode of the FIFO, which
+// holds the pipe for as long as any such descriptor is open
 #[derive(Debug, Clone)]
 pub struct PipeDesc {
     pub pipe: interface::RustRfc<interface::EmulatedPipe>,
     pub flags: i32,
     pub advlock: interface::RustRfc<interface::AdvisoryLock>,
+    pub fifoinode: Option<usize>,
 }
 
+//The epoll instance is shared by every descriptor referring to it, be it
+// through dup or fork
 #[derive(Debug, Clone)]
 pub struct EpollDesc {
     pub mode: i32,
-    pub registered_fds: interface::RustHashMap<i32, EpollEvent>,
+    pub instance: interface::RustRfc<interface::EpollInstance>,
     pub advlock: interface::RustRfc<interface::AdvisoryLock>,
     pub errno: i32,
     pub flags: i32,
 }
 
+//The eventfd counter is shared by every descriptor referring to it
+#[derive(Debug, Clone)]
+pub struct EventFdDesc {
+    pub eventfd: interface::RustRfc<interface::EmulatedEventFd>,
+    pub flags: i32,
+    pub advlock: interface::RustRfc<interface::AdvisoryLock>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TimerFdDesc {
+    pub timer: interface::RustRfc<interface::ExpirationTimer>,
+    pub clockid: i32,
+    pub flags: i32,
+    pub advlock: interface::RustRfc<interface::AdvisoryLock>,
+}
+
+//The signals a signalfd accepts can be changed through any descriptor
+// referring to it, so the mask is shared between them
+#[derive(Debug, Clone)]
+pub struct SignalFdDesc {
+    pub mask: interface::RustRfc<interface::RustAtomicU64>,
+    pub flags: i32,
+    pub advlock: interface::RustRfc<interface::AdvisoryLock>,
+}
+
+// A state change of a child cage that its parent has not collected yet, which
+// is either the zombie record of an exited child or a stop notification
+#[derive(Debug, Clone)]
+pub struct ChildEvent {
+    pub cageid: u64,
+    pub pgid: u64,
+    pub status: i32, // wait status, encoded the way WIFEXITED and friends expect
+    pub rusage: interface::Rusage,
+}
+
+impl ChildEvent {
+    pub fn is_stop(&self) -> bool {
+        self.status & 0xff == 0x7f
+    }
+}
+
 pub type FdTable = Vec<interface::RustRfc<interface::RustLock<Option<FileDescriptor>>>>;
 
 #[derive(Debug)]
 pub struct Cage {
     pub cageid: u64,
     pub cwd: interface::RustLock<interface::RustRfc<interface::RustPathBuf>>,
-    pub parent: u64,
+    pub parent: interface::RustAtomicU64,
+    pub pgid: interface::RustAtomicU64,
+    pub sid: interface::RustAtomicU64,
     pub filedescriptortable: FdTable,
     pub cancelstatus: interface::RustAtomicBool,
     pub getgid: interface::RustAtomicI32,
@@ -121,6 +186,15 @@ pub struct Cage {
     pub pendingsigset: interface::RustHashMap<u64, interface::RustAtomicU64>,
     pub main_threadid: interface::RustAtomicU64,
     pub interval_timer: interface::IntervalTimer,
+    pub child_events: interface::Mutex<Vec<ChildEvent>>,
+    pub child_event_cv: interface::Condvar,
+    // resource usage of the threads of the cage that have finished and of the
+    // children it has reaped, which the thread that exits adds its own usage to
+    pub rusage: interface::Mutex<interface::Rusage>,
+    // set once the cage has been adopted by the init cage, which reaps it as soon
+    // as it exits
+    pub adopted: interface::RustAtomicBool,
+    pub rlimits: interface::RustLock<[Rlimit; RLIM_NLIMITS]>,
 }
 
 impl Cage {
@@ -136,9 +210,13 @@ impl Cage {
             None => STARTINGFD,
         };
 
+        // fd numbers at or above the soft RLIMIT_NOFILE may not be allocated
+        let limit = self.get_rlimit(RLIMIT_NOFILE).rlim_cur;
+        let end = interface::rust_min(limit, MAXFD as u64) as i32;
+
         // let's get the next available fd number. The standard says we need to return
         // the lowest open fd number.
-        for fd in start..MAXFD {
+        for fd in start..end {
             let fdguard = self.filedescriptortable[fd as usize].try_write();
             if let Some(ref fdopt) = fdguard {
                 // we grab the lock here and if there is no occupied cage, we return the fdno
@@ -148,6 +226,16 @@ impl Cage {
                 }
             }
         }
+        if end < MAXFD {
+            return (
+                syscall_error(
+                    Errno::EMFILE,
+                    "get_next_fd",
+                    "the per-cage limit on the number of open files has been reached",
+                ),
+                None,
+            );
+        }
         return (
             syscall_error(
                 Errno::ENFILE,
@@ -158,6 +246,11 @@ impl Cage {
         );
     }
 
+    // returns the soft and hard limit the cage has on the given resource
+    pub fn get_rlimit(&self, resource: u64) -> Rlimit {
+        self.rlimits.read()[resource as usize]
+    }
+
     pub fn changedir(&self, newdir: interface::RustPathBuf) {
         let newwd = interface::RustRfc::new(normpath(newdir, self));
         let mut cwdbox = self.cwd.write();
@@ -184,6 +277,80 @@ impl Cage {
         }
     }
 
+    // Sends sig to the main thread of the cage. If that thread has the signal
+    // blocked it is left pending instead, until it is unblocked or read from a
+    // signalfd
+    pub fn send_signal(&self, sig: i32) {
+        let threadid = self
+            .main_threadid
+            .load(interface::RustAtomicOrdering::Relaxed);
+        if let Some(mask) = self.sigset.get(&threadid) {
+            if interface::lind_sigismember(mask.load(interface::RustAtomicOrdering::Relaxed), sig) {
+                if let Some(pending) = self.pendingsigset.get(&threadid) {
+                    pending.fetch_or(
+                        interface::lind_sigaddset(0, sig),
+                        interface::RustAtomicOrdering::Relaxed,
+                    );
+                    return;
+                }
+            }
+        }
+        interface::lind_threadkill(threadid, sig);
+    }
+
+    // returns the signals pending for any thread of the cage
+    pub fn pending_signals(&self) -> interface::SigsetType {
+        self.pendingsigset.iter().fold(0, |set, pending| {
+            set | pending.load(interface::RustAtomicOrdering::Relaxed)
+        })
+    }
+
+    // Takes the lowest numbered signal in mask that is pending for any thread of
+    // the cage, so that it is no longer pending
+    pub fn take_pending_signal(&self, mask: interface::SigsetType) -> Option<i32> {
+        for pending in self.pendingsigset.iter() {
+            loop {
+                let matching = pending.load(interface::RustAtomicOrdering::Relaxed) & mask;
+                if matching == 0 {
+                    break;
+                }
+                let signo = matching.trailing_zeros() as i32 + 1;
+                let bit = interface::lind_sigaddset(0, signo);
+                // someone else may have taken the signal in the meantime
+                if pending.fetch_and(!bit, interface::RustAtomicOrdering::Relaxed) & bit != 0 {
+                    return Some(signo);
+                }
+            }
+        }
+        None
+    }
+
+    // The ids below start out as -1 until the loader's first get*id call, which
+    // stands for the default user and group Lind runs as
+    fn load_id(id: &interface::RustAtomicI32, default: u32) -> u32 {
+        match id.load(interface::RustAtomicOrdering::Relaxed) {
+            -1 => default,
+            id => id as u32,
+        }
+    }
+
+    // returns the effective user and group ids, which filesystem permission
+    // checks are made against
+    pub fn get_effective_ids(&self) -> (u32, u32) {
+        (
+            Self::load_id(&self.geteuid, DEFAULT_UID),
+            Self::load_id(&self.getegid, DEFAULT_GID),
+        )
+    }
+
+    // returns the real user and group ids, which access() checks against
+    pub fn get_real_ids(&self) -> (u32, u32) {
+        (
+            Self::load_id(&self.getuid, DEFAULT_UID),
+            Self::load_id(&self.getgid, DEFAULT_GID),
+        )
+    }
+
     pub fn get_filedescriptor(
         &self,
         fd: i32,
@@ -233,6 +400,28 @@ pub fn init_fdtable() -> FdTable {
     fdtable
 }
 
+// the resource limits a cage starts out with, which its children inherit
+pub fn init_rlimits() -> [Rlimit; RLIM_NLIMITS] {
+    let mut rlimits = [Rlimit {
+        rlim_cur: RLIM_INFINITY,
+        rlim_max: RLIM_INFINITY,
+    }; RLIM_NLIMITS];
+    rlimits[RLIMIT_NOFILE as usize] = Rlimit {
+        rlim_cur: NOFILE_CUR,
+        rlim_max: NOFILE_MAX,
+    };
+    rlimits[RLIMIT_STACK as usize] = Rlimit {
+        rlim_cur: STACK_CUR,
+        rlim_max: STACK_MAX,
+    };
+    rlimits[RLIMIT_CORE as usize].rlim_cur = CORE_CUR;
+    rlimits[RLIMIT_NPROC as usize] = Rlimit {
+        rlim_cur: interface::MAXCAGEID as u64,
+        rlim_max: interface::MAXCAGEID as u64,
+    };
+    rlimits
+}
+
 pub fn create_unix_sockpipes() -> (
     interface::RustRfc<interface::EmulatedPipe>,
     interface::RustRfc<interface::EmulatedPipe>,
@@ -244,3 +433,14 @@ pub fn create_unix_sockpipes() -> (
 
     (pipe1, pipe2)
 }
+
+pub fn create_unix_msgpipes() -> (UnixMsgPipe, UnixMsgPipe) {
+    let pipe1 = interface::RustRfc::new(interface::EmulatedMsgPipe::new_with_capacity(
+        UDSOCK_CAPACITY,
+    ));
+    let pipe2 = interface::RustRfc::new(interface::EmulatedMsgPipe::new_with_capacity(
+        UDSOCK_CAPACITY,
+    ));
+
+    (pipe1, pipe2)
+}
@@ -121,11 +121,64 @@ const FDATASYNC_SYSCALL: i32 = 163;
 const SYNC_FILE_RANGE: i32 = 164;
 
 const WRITEV_SYSCALL: i32 = 170;
+const SYMLINK_SYSCALL: i32 = 171;
+const READLINK_SYSCALL: i32 = 172;
+const LSTAT_SYSCALL: i32 = 173;
+const CHOWN_SYSCALL: i32 = 174;
+const FCHOWN_SYSCALL: i32 = 175;
+const LCHOWN_SYSCALL: i32 = 176;
+const SETUID_SYSCALL: i32 = 177;
+const SETGID_SYSCALL: i32 = 178;
+const SETEUID_SYSCALL: i32 = 179;
+const SETEGID_SYSCALL: i32 = 180;
+const UTIMENSAT_SYSCALL: i32 = 181;
+const FUTIMENS_SYSCALL: i32 = 182;
+const WAIT_SYSCALL: i32 = 183;
+const WAITPID_SYSCALL: i32 = 184;
+const WAIT4_SYSCALL: i32 = 185;
+const SETPGID_SYSCALL: i32 = 186;
+const GETPGID_SYSCALL: i32 = 187;
+const SETSID_SYSCALL: i32 = 188;
+const GETSID_SYSCALL: i32 = 189;
+const GETPGRP_SYSCALL: i32 = 190;
+const GETRLIMIT_SYSCALL: i32 = 191;
+const SETRLIMIT_SYSCALL: i32 = 192;
+const PRLIMIT64_SYSCALL: i32 = 193;
+const READV_SYSCALL: i32 = 194;
+const PREADV_SYSCALL: i32 = 195;
+const PWRITEV_SYSCALL: i32 = 196;
+const PREADV2_SYSCALL: i32 = 197;
+const PWRITEV2_SYSCALL: i32 = 198;
+const SENDMSG_SYSCALL: i32 = 199;
+const RECVMSG_SYSCALL: i32 = 200;
+const EVENTFD_SYSCALL: i32 = 201;
+const TIMERFD_CREATE_SYSCALL: i32 = 202;
+const TIMERFD_SETTIME_SYSCALL: i32 = 203;
+const TIMERFD_GETTIME_SYSCALL: i32 = 204;
+const SIGNALFD_SYSCALL: i32 = 205;
+const MKNOD_SYSCALL: i32 = 206;
+const MKFIFO_SYSCALL: i32 = 207;
+const SPLICE_SYSCALL: i32 = 208;
+const TEE_SYSCALL: i32 = 209;
+const VMSPLICE_SYSCALL: i32 = 210;
+const SENDFILE_SYSCALL: i32 = 211;
+const COPY_FILE_RANGE_SYSCALL: i32 = 212;
+const FALLOCATE_SYSCALL: i32 = 213;
+const FADVISE_SYSCALL: i32 = 214;
+const OPENAT_SYSCALL: i32 = 215;
+const MKDIRAT_SYSCALL: i32 = 216;
+const UNLINKAT_SYSCALL: i32 = 217;
+const RENAMEAT2_SYSCALL: i32 = 218;
+const LINKAT_SYSCALL: i32 = 219;
+const FSTATAT_SYSCALL: i32 = 220;
+const FACCESSAT_SYSCALL: i32 = 221;
+const FCHMODAT_SYSCALL: i32 = 222;
+const READLINKAT_SYSCALL: i32 = 223;
 
 use super::cage::*;
 use super::filesystem::{
     incref_root, load_fs, persist_metadata, remove_domain_sock, FilesystemMetadata, FS_METADATA,
-    LOGFILENAME, LOGMAP,
+    LOGMAP,
 };
 use super::net::NET_METADATA;
 use super::shm::SHM_METADATA;
@@ -371,9 +424,71 @@ pub extern "C" fn dispatcher(
                 interface::get_ioctlptrunion(arg3)
             )
         }
+        WAIT_SYSCALL => {
+            check_and_dispatch!(cage.wait_syscall, interface::get_intptr(arg1))
+        }
+        WAITPID_SYSCALL => {
+            check_and_dispatch!(
+                cage.waitpid_syscall,
+                interface::get_int(arg1),
+                interface::get_intptr(arg2),
+                interface::get_int(arg3)
+            )
+        }
+        WAIT4_SYSCALL => {
+            check_and_dispatch!(
+                cage.wait4_syscall,
+                interface::get_int(arg1),
+                interface::get_intptr(arg2),
+                interface::get_int(arg3),
+                interface::get_rusagestruct(arg4)
+            )
+        }
         GETPPID_SYSCALL => {
             check_and_dispatch!(cage.getppid_syscall,)
         }
+        SETPGID_SYSCALL => {
+            check_and_dispatch!(
+                cage.setpgid_syscall,
+                interface::get_int(arg1),
+                interface::get_int(arg2)
+            )
+        }
+        GETPGID_SYSCALL => {
+            check_and_dispatch!(cage.getpgid_syscall, interface::get_int(arg1))
+        }
+        SETSID_SYSCALL => {
+            check_and_dispatch!(cage.setsid_syscall,)
+        }
+        GETSID_SYSCALL => {
+            check_and_dispatch!(cage.getsid_syscall, interface::get_int(arg1))
+        }
+        GETPGRP_SYSCALL => {
+            check_and_dispatch!(cage.getpgrp_syscall,)
+        }
+        GETRLIMIT_SYSCALL => {
+            check_and_dispatch!(
+                cage.getrlimit_syscall,
+                interface::get_ulong(arg1),
+                interface::get_rlimitstruct(arg2)
+            )
+        }
+        SETRLIMIT_SYSCALL => {
+            check_and_dispatch!(
+                cage.setrlimit_syscall,
+                interface::get_ulong(arg1),
+                interface::get_constrlimitstruct(arg2)
+            )
+        }
+        PRLIMIT64_SYSCALL => {
+            check_and_dispatch!(
+                cage.prlimit64_syscall,
+                interface::get_int(arg1),
+                interface::get_ulong(arg2),
+                interface::get_constrlimitstruct_null(arg3),
+                interface::get_rlimitstruct_null(arg4)
+            )
+        }
         GETPID_SYSCALL => {
             check_and_dispatch!(cage.getpid_syscall,)
         }
@@ -463,6 +578,22 @@ pub extern "C" fn dispatcher(
                 )
             }
         }
+        SENDMSG_SYSCALL => {
+            check_and_dispatch!(
+                cage.sendmsg_syscall,
+                interface::get_int(arg1),
+                interface::get_constmsghdr(arg2),
+                interface::get_int(arg3)
+            )
+        }
+        RECVMSG_SYSCALL => {
+            check_and_dispatch!(
+                cage.recvmsg_syscall,
+                interface::get_int(arg1),
+                interface::get_msghdr(arg2),
+                interface::get_int(arg3)
+            )
+        }
         CONNECT_SYSCALL => {
             let addrlen = get_onearg!(interface::get_uint(arg3));
             let addr = get_onearg!(interface::get_sockaddr(arg2, addrlen));
@@ -943,6 +1074,329 @@ pub extern "C" fn dispatcher(
                 interface::get_int(arg3)
             )
         }
+        READV_SYSCALL => {
+            check_and_dispatch!(
+                cage.readv_syscall,
+                interface::get_int(arg1),
+                interface::get_iovecstruct(arg2),
+                interface::get_int(arg3)
+            )
+        }
+        PREADV_SYSCALL => {
+            check_and_dispatch!(
+                cage.preadv_syscall,
+                interface::get_int(arg1),
+                interface::get_iovecstruct(arg2),
+                interface::get_int(arg3),
+                interface::get_isize(arg4)
+            )
+        }
+        PWRITEV_SYSCALL => {
+            check_and_dispatch!(
+                cage.pwritev_syscall,
+                interface::get_int(arg1),
+                interface::get_iovecstruct(arg2),
+                interface::get_int(arg3),
+                interface::get_isize(arg4)
+            )
+        }
+        PREADV2_SYSCALL => {
+            check_and_dispatch!(
+                cage.preadv2_syscall,
+                interface::get_int(arg1),
+                interface::get_iovecstruct(arg2),
+                interface::get_int(arg3),
+                interface::get_isize(arg4),
+                interface::get_int(arg5)
+            )
+        }
+        PWRITEV2_SYSCALL => {
+            check_and_dispatch!(
+                cage.pwritev2_syscall,
+                interface::get_int(arg1),
+                interface::get_iovecstruct(arg2),
+                interface::get_int(arg3),
+                interface::get_isize(arg4),
+                interface::get_int(arg5)
+            )
+        }
+        EVENTFD_SYSCALL => {
+            check_and_dispatch!(
+                cage.eventfd_syscall,
+                interface::get_uint(arg1),
+                interface::get_int(arg2)
+            )
+        }
+        TIMERFD_CREATE_SYSCALL => {
+            check_and_dispatch!(
+                cage.timerfd_create_syscall,
+                interface::get_int(arg1),
+                interface::get_int(arg2)
+            )
+        }
+        TIMERFD_SETTIME_SYSCALL => {
+            check_and_dispatch!(
+                cage.timerfd_settime_syscall,
+                interface::get_int(arg1),
+                interface::get_int(arg2),
+                interface::get_constitimerspec(arg3),
+                interface::get_itimerspec(arg4)
+            )
+        }
+        TIMERFD_GETTIME_SYSCALL => {
+            let curr_value = match get_onearg!(interface::get_itimerspec(arg2)) {
+                Some(curr_value) => curr_value,
+                None => {
+                    return syscall_error(Errno::EFAULT, "timerfd_gettime", "curr_value is null")
+                }
+            };
+            cage.timerfd_gettime_syscall(get_onearg!(interface::get_int(arg1)), curr_value)
+        }
+        SIGNALFD_SYSCALL => {
+            check_and_dispatch!(
+                cage.signalfd_syscall,
+                interface::get_int(arg1),
+                interface::get_constsigsett(arg2),
+                interface::get_int(arg3)
+            )
+        }
+        MKNOD_SYSCALL => {
+            check_and_dispatch!(
+                cage.mknod_syscall,
+                interface::get_cstr(arg1),
+                interface::get_uint(arg2),
+                interface::get_ulong(arg3)
+            )
+        }
+        MKFIFO_SYSCALL => {
+            check_and_dispatch!(
+                cage.mkfifo_syscall,
+                interface::get_cstr(arg1),
+                interface::get_uint(arg2)
+            )
+        }
+        SPLICE_SYSCALL => {
+            check_and_dispatch!(
+                cage.splice_syscall,
+                interface::get_int(arg1),
+                interface::get_longptr(arg2),
+                interface::get_int(arg3),
+                interface::get_longptr(arg4),
+                interface::get_usize(arg5),
+                interface::get_uint(arg6)
+            )
+        }
+        TEE_SYSCALL => {
+            check_and_dispatch!(
+                cage.tee_syscall,
+                interface::get_int(arg1),
+                interface::get_int(arg2),
+                interface::get_usize(arg3),
+                interface::get_uint(arg4)
+            )
+        }
+        VMSPLICE_SYSCALL => {
+            check_and_dispatch!(
+                cage.vmsplice_syscall,
+                interface::get_int(arg1),
+                interface::get_iovecstruct(arg2),
+                interface::get_int(arg3),
+                interface::get_uint(arg4)
+            )
+        }
+        SENDFILE_SYSCALL => {
+            check_and_dispatch!(
+                cage.sendfile_syscall,
+                interface::get_int(arg1),
+                interface::get_int(arg2),
+                interface::get_longptr(arg3),
+                interface::get_usize(arg4)
+            )
+        }
+        COPY_FILE_RANGE_SYSCALL => {
+            check_and_dispatch!(
+                cage.copy_file_range_syscall,
+                interface::get_int(arg1),
+                interface::get_longptr(arg2),
+                interface::get_int(arg3),
+                interface::get_longptr(arg4),
+                interface::get_usize(arg5),
+                interface::get_uint(arg6)
+            )
+        }
+        FALLOCATE_SYSCALL => {
+            check_and_dispatch!(
+                cage.fallocate_syscall,
+                interface::get_int(arg1),
+                interface::get_int(arg2),
+                interface::get_isize(arg3),
+                interface::get_isize(arg4)
+            )
+        }
+        FADVISE_SYSCALL => {
+            check_and_dispatch!(
+                cage.posix_fadvise_syscall,
+                interface::get_int(arg1),
+                interface::get_isize(arg2),
+                interface::get_isize(arg3),
+                interface::get_int(arg4)
+            )
+        }
+        OPENAT_SYSCALL => {
+            check_and_dispatch!(
+                cage.openat_syscall,
+                interface::get_int(arg1),
+                interface::get_cstr(arg2),
+                interface::get_int(arg3),
+                interface::get_uint(arg4)
+            )
+        }
+        MKDIRAT_SYSCALL => {
+            check_and_dispatch!(
+                cage.mkdirat_syscall,
+                interface::get_int(arg1),
+                interface::get_cstr(arg2),
+                interface::get_uint(arg3)
+            )
+        }
+        UNLINKAT_SYSCALL => {
+            check_and_dispatch!(
+                cage.unlinkat_syscall,
+                interface::get_int(arg1),
+                interface::get_cstr(arg2),
+                interface::get_int(arg3)
+            )
+        }
+        RENAMEAT2_SYSCALL => {
+            check_and_dispatch!(
+                cage.renameat2_syscall,
+                interface::get_int(arg1),
+                interface::get_cstr(arg2),
+                interface::get_int(arg3),
+                interface::get_cstr(arg4),
+                interface::get_uint(arg5)
+            )
+        }
+        LINKAT_SYSCALL => {
+            check_and_dispatch!(
+                cage.linkat_syscall,
+                interface::get_int(arg1),
+                interface::get_cstr(arg2),
+                interface::get_int(arg3),
+                interface::get_cstr(arg4),
+                interface::get_int(arg5)
+            )
+        }
+        FSTATAT_SYSCALL => {
+            check_and_dispatch!(
+                cage.fstatat_syscall,
+                interface::get_int(arg1),
+                interface::get_cstr(arg2),
+                interface::get_statdatastruct(arg3),
+                interface::get_int(arg4)
+            )
+        }
+        FACCESSAT_SYSCALL => {
+            check_and_dispatch!(
+                cage.faccessat_syscall,
+                interface::get_int(arg1),
+                interface::get_cstr(arg2),
+                interface::get_uint(arg3),
+                interface::get_int(arg4)
+            )
+        }
+        FCHMODAT_SYSCALL => {
+            check_and_dispatch!(
+                cage.fchmodat_syscall,
+                interface::get_int(arg1),
+                interface::get_cstr(arg2),
+                interface::get_uint(arg3),
+                interface::get_int(arg4)
+            )
+        }
+        READLINKAT_SYSCALL => {
+            check_and_dispatch!(
+                cage.readlinkat_syscall,
+                interface::get_int(arg1),
+                interface::get_cstr(arg2),
+                interface::get_mutcbuf(arg3),
+                interface::get_usize(arg4)
+            )
+        }
+        SYMLINK_SYSCALL => {
+            check_and_dispatch!(
+                cage.symlink_syscall,
+                interface::get_cstr(arg1),
+                interface::get_cstr(arg2)
+            )
+        }
+        READLINK_SYSCALL => {
+            check_and_dispatch!(
+                cage.readlink_syscall,
+                interface::get_cstr(arg1),
+                interface::get_mutcbuf(arg2),
+                interface::get_usize(arg3)
+            )
+        }
+        LSTAT_SYSCALL => {
+            check_and_dispatch!(
+                cage.lstat_syscall,
+                interface::get_cstr(arg1),
+                interface::get_statdatastruct(arg2)
+            )
+        }
+        CHOWN_SYSCALL => {
+            check_and_dispatch!(
+                cage.chown_syscall,
+                interface::get_cstr(arg1),
+                interface::get_uint(arg2),
+                interface::get_uint(arg3)
+            )
+        }
+        FCHOWN_SYSCALL => {
+            check_and_dispatch!(
+                cage.fchown_syscall,
+                interface::get_int(arg1),
+                interface::get_uint(arg2),
+                interface::get_uint(arg3)
+            )
+        }
+        LCHOWN_SYSCALL => {
+            check_and_dispatch!(
+                cage.lchown_syscall,
+                interface::get_cstr(arg1),
+                interface::get_uint(arg2),
+                interface::get_uint(arg3)
+            )
+        }
+        SETUID_SYSCALL => {
+            check_and_dispatch!(cage.setuid_syscall, interface::get_uint(arg1))
+        }
+        SETGID_SYSCALL => {
+            check_and_dispatch!(cage.setgid_syscall, interface::get_uint(arg1))
+        }
+        SETEUID_SYSCALL => {
+            check_and_dispatch!(cage.seteuid_syscall, interface::get_uint(arg1))
+        }
+        SETEGID_SYSCALL => {
+            check_and_dispatch!(cage.setegid_syscall, interface::get_uint(arg1))
+        }
+        UTIMENSAT_SYSCALL => {
+            check_and_dispatch!(
+                cage.utimensat_syscall,
+                interface::get_int(arg1),
+                interface::get_cstr(arg2),
+                interface::get_consttimespecpair(arg3),
+                interface::get_int(arg4)
+            )
+        }
+        FUTIMENS_SYSCALL => {
+            check_and_dispatch!(
+                cage.futimens_syscall,
+                interface::get_int(arg1),
+                interface::get_consttimespecpair(arg2)
+            )
+        }
         _ => {
             //unknown syscall
             -1
@@ -958,6 +1412,18 @@ pub extern "C" fn lindcancelinit(cageid: u64) {
     cage.signalcvs();
 }
 
+#[no_mangle]
+pub extern "C" fn lindsignalexit(cageid: u64, sig: i32) {
+    let cage = interface::cagetable_getref(cageid);
+    cage.terminate_by_signal(sig);
+}
+
+#[no_mangle]
+pub extern "C" fn lindcagestopped(cageid: u64, sig: i32) {
+    let cage = interface::cagetable_getref(cageid);
+    cage.notify_stopped(sig);
+}
+
 #[no_mangle]
 pub extern "C" fn lindsetthreadkill(cageid: u64, pthreadid: u64, kill: bool) {
     let cage = interface::cagetable_getref(cageid);
@@ -983,6 +1449,11 @@ pub extern "C" fn lindcheckthread(cageid: u64, pthreadid: u64) -> bool {
 pub extern "C" fn lindthreadremove(cageid: u64, pthreadid: u64) {
     let cage = interface::cagetable_getref(cageid);
     cage.thread_table.remove(&pthreadid);
+    // a thread removing itself as it finishes hands its resource usage over to
+    // the cage
+    if pthreadid == interface::get_pthreadid() {
+        cage.rusage.lock().accumulate(&interface::lind_getrusage());
+    }
 }
 
 fn cleartmp(init: bool) {
@@ -1001,7 +1472,7 @@ fn cleartmp(init: bool) {
         });
     } else {
         if init == true {
-            cage.mkdir_syscall(path, S_IRWXA);
+            cage.mkdir_syscall(path, S_IRWXA | S_ISVTX);
         }
     }
 }
@@ -1011,7 +1482,7 @@ pub extern "C" fn lindgetsighandler(cageid: u64, signo: i32) -> u32 {
     let cage = interface::cagetable_getref(cageid);
     let pthreadid = interface::get_pthreadid();
     let sigset = cage.sigset.get(&pthreadid).unwrap(); // these lock sigset dashmaps for concurrency
-    let pendingset = cage.sigset.get(&pthreadid).unwrap();
+    let pendingset = cage.pendingsigset.get(&pthreadid).unwrap();
 
     if !interface::lind_sigismember(sigset.load(interface::RustAtomicOrdering::Relaxed), signo) {
         return match cage.signalhandler.get(&signo) {
@@ -1022,8 +1493,8 @@ pub extern "C" fn lindgetsighandler(cageid: u64, signo: i32) -> u32 {
             None => 0, // if we dont have a handler return 0
         };
     } else {
-        let mutpendingset = sigset.load(interface::RustAtomicOrdering::Relaxed);
-        sigset.store(
+        let mutpendingset = pendingset.load(interface::RustAtomicOrdering::Relaxed);
+        pendingset.store(
             interface::lind_sigaddset(mutpendingset, signo),
             interface::RustAtomicOrdering::Relaxed,
         );
@@ -1037,20 +1508,28 @@ pub extern "C" fn lindgetsighandler(cageid: u64, signo: i32) -> u32 {
 pub extern "C" fn lindrustinit(verbosity: isize) {
     let _ = interface::VERBOSE.set(verbosity); //assigned to suppress unused result warning
     interface::cagetable_init();
-    load_fs();
+    // a damaged metadata log is always worth knowing about, whatever the verbosity
+    let replayreport = load_fs();
+    if !replayreport.is_clean() {
+        interface::log_to_stderr(&replayreport.to_string());
+    }
     incref_root();
     incref_root();
 
     let utilcage = Cage {
         cageid: 0,
         cwd: interface::RustLock::new(interface::RustRfc::new(interface::RustPathBuf::from("/"))),
-        parent: 0,
+        parent: interface::RustAtomicU64::new(0),
+        pgid: interface::RustAtomicU64::new(0),
+        sid: interface::RustAtomicU64::new(0),
         filedescriptortable: init_fdtable(),
         cancelstatus: interface::RustAtomicBool::new(false),
         getgid: interface::RustAtomicI32::new(-1),
-        getuid: interface::RustAtomicI32::new(-1),
+        // the utility cage does housekeeping such as clearing out /tmp, which
+        // has to succeed whoever owns the files in there
+        getuid: interface::RustAtomicI32::new(ROOT_UID as i32),
         getegid: interface::RustAtomicI32::new(-1),
-        geteuid: interface::RustAtomicI32::new(-1),
+        geteuid: interface::RustAtomicI32::new(ROOT_UID as i32),
         rev_shm: interface::Mutex::new(vec![]),
         mutex_table: interface::RustLock::new(vec![]),
         cv_table: interface::RustLock::new(vec![]),
@@ -1061,6 +1540,11 @@ pub extern "C" fn lindrustinit(verbosity: isize) {
         pendingsigset: interface::RustHashMap::new(),
         main_threadid: interface::RustAtomicU64::new(0),
         interval_timer: interface::IntervalTimer::new(0),
+        child_events: interface::Mutex::new(vec![]),
+        child_event_cv: interface::Condvar::new(),
+        rusage: interface::Mutex::new(interface::Rusage::default()),
+        adopted: interface::RustAtomicBool::new(false),
+        rlimits: interface::RustLock::new(init_rlimits()),
     };
 
     interface::cagetable_insert(0, utilcage);
@@ -1069,7 +1553,9 @@ pub extern "C" fn lindrustinit(verbosity: isize) {
     let initcage = Cage {
         cageid: 1,
         cwd: interface::RustLock::new(interface::RustRfc::new(interface::RustPathBuf::from("/"))),
-        parent: 1,
+        parent: interface::RustAtomicU64::new(1),
+        pgid: interface::RustAtomicU64::new(1),
+        sid: interface::RustAtomicU64::new(1),
         filedescriptortable: init_fdtable(),
         cancelstatus: interface::RustAtomicBool::new(false),
         getgid: interface::RustAtomicI32::new(-1),
@@ -1086,6 +1572,11 @@ pub extern "C" fn lindrustinit(verbosity: isize) {
         pendingsigset: interface::RustHashMap::new(),
         main_threadid: interface::RustAtomicU64::new(0),
         interval_timer: interface::IntervalTimer::new(1),
+        child_events: interface::Mutex::new(vec![]),
+        child_event_cv: interface::Condvar::new(),
+        rusage: interface::Mutex::new(interface::Rusage::default()),
+        adopted: interface::RustAtomicBool::new(false),
+        rlimits: interface::RustLock::new(init_rlimits()),
     };
     interface::cagetable_insert(1, initcage);
     // make sure /tmp is clean
@@ -1104,12 +1595,11 @@ pub extern "C" fn lindrustfinalize() {
     interface::cagetable_clear();
     // if we get here, persist and delete log
     persist_metadata(&FS_METADATA);
-    if interface::pathexists(LOGFILENAME.to_string()) {
-        // remove file if it exists, assigning it to nothing to avoid the compiler
-        // yelling about unused result
-        let mut logobj = LOGMAP.write();
-        let log = logobj.take().unwrap();
+    let mut logobj = LOGMAP.write();
+    if let Some(log) = logobj.take() {
+        // remove the log if it exists, assigning it to nothing to avoid the
+        // compiler yelling about unused result
         let _close = log.close().unwrap();
-        let _logremove = interface::removefile(LOGFILENAME.to_string());
+        let _logremove = interface::storage_backend().remove_log();
     }
 }
@@ -7,12 +7,36 @@
 //! - `nextinode`: Next inode number
 //! - `dev_id`: Device ID
 //! - `inodetable`: Hash map of inode numbers to `InodeEnum`
+//! - `capacity_bytes`, `capacity_inodes`: How much the file system can hold
+//! - `quotas`: Per-user limits on bytes and inodes
+//! - `snapshots`: The snapshots taken of the file system, by name
+//! - `usage`: Bytes and inodes in use, recounted from the inodes on load
 //!
 //! `InodeEnum` represents inode structures like `File`, `CharDev`, `Socket`,
-//! and `Directory`.
+//! `Directory`, `Symlink`, and `Fifo`.
 //!
-//! Metadata is stored in `lind.metadata` and managed by `init_fs_metadata()`
-//! and `blank_fs_init()`.
+//! Metadata is saved as `lind.metadata` through the storage backend in use
+//! (see `interface::StorageBackend`), which also keeps the data of every
+//! regular file, and is managed by `init_fs_metadata()` and `blank_fs_init()`.
+//!
+//! ## Metadata Log
+//!
+//! Changes made between two saves of `lind.metadata` are appended to the
+//! mmapped `lind.md.log`. Each entry is a transaction holding the new state of
+//! every inode touched by one operation, framed by its length and a CRC-32 of
+//! its payload. On load the entries are replayed in order up to the first one
+//! that is truncated or damaged, and a `ReplayReport` describes what was
+//! applied and what was dropped. Once the log grows past
+//! `LOG_CHECKPOINT_BYTES` it is folded into `lind.metadata` and started over.
+//!
+//! ## Snapshots
+//!
+//! A snapshot is a named, read-only copy of the whole file system, taken and
+//! restored while lind is not running. The data of every regular file is
+//! cloned to `lindsnap.<name>.<inode>` and the metadata is saved as
+//! `lindsnap.<name>.metadata`, so backends that share data between files do
+//! not copy any of it. Restoring clones the data back and takes on the saved
+//! metadata, keeping the list of snapshots as it is.
 
 // Filesystem metadata struct
 #![allow(dead_code)]
@@ -21,12 +45,23 @@ use super::net::NET_METADATA;
 use super::syscalls::fs_constants::*;
 use super::syscalls::sys_constants::*;
 use crate::interface;
+use std::convert::TryInto;
+
+use super::cage::{Cage, Errno};
 
-use super::cage::Cage;
+// every log entry starts with the length of its payload and the CRC-32 of the
+// payload, both as big endian u32s
+pub const LOGENTRYHEADERSIZE: usize = 8;
 
-pub const METADATAFILENAME: &str = "lind.metadata";
+// how many bytes of entries the log may hold before it is checkpointed into
+// lind.metadata
+pub static LOG_CHECKPOINT_BYTES: interface::RustAtomicUsize =
+    interface::RustAtomicUsize::new(4 * interface::MAP_1MB);
 
-pub const LOGFILENAME: &str = "lind.md.log";
+// serializes writers of lind.metadata, which may save it both when
+// checkpointing the log and when the capacity or a quota changes
+static PERSISTLOCK: interface::RustLazyGlobal<interface::Mutex<()>> =
+    interface::RustLazyGlobal::new(|| interface::Mutex::new(()));
 
 pub static LOGMAP: interface::RustLazyGlobal<
     interface::RustRfc<interface::RustLock<Option<interface::EmulatedFileMap>>>,
@@ -41,12 +76,16 @@ type FileObjectTable = interface::RustHashMap<usize, interface::EmulatedFile>;
 pub static FILEOBJECTTABLE: interface::RustLazyGlobal<FileObjectTable> =
     interface::RustLazyGlobal::new(|| interface::RustHashMap::new());
 
+// The atime, ctime and mtime of every kind of inode are kept in nanoseconds
+// since the epoch
 #[derive(interface::SerdeSerialize, interface::SerdeDeserialize, Debug)]
 pub enum Inode {
     File(GenericInode),
     CharDev(DeviceInode),
     Socket(SocketInode),
     Dir(DirectoryInode),
+    Symlink(SymlinkInode),
+    Fifo(FifoInode),
 }
 
 #[derive(interface::SerdeSerialize, interface::SerdeDeserialize, Debug)]
@@ -121,11 +160,144 @@ pub struct DirectoryInode {
     pub filename_to_inode_dict: interface::RustHashMap<String, usize>,
 }
 
+#[derive(interface::SerdeSerialize, interface::SerdeDeserialize, Debug)]
+/// Refer [here](https://man7.org/linux/man-pages/man7/symlink.7.html)
+/// for more information on the below fields. The size of a symbolic link is
+/// the length of the path it points to.
+pub struct SymlinkInode {
+    pub size: usize,
+    pub uid: u32,
+    pub gid: u32,
+    pub mode: u32,
+    pub linkcount: u32,
+    #[serde(skip)]
+    //skips serializing and deserializing field, will populate with u32 default of 0 (refcount
+    // should not be persisted)
+    pub refcount: u32,
+    pub atime: u64,
+    pub ctime: u64,
+    pub mtime: u64,
+    pub target: String,
+}
+
+#[derive(interface::SerdeSerialize, interface::SerdeDeserialize, Debug)]
+/// Refer [here](https://man7.org/linux/man-pages/man7/fifo.7.html)
+/// for more information on named pipes. Only the inode itself is persisted,
+/// the pipe behind it exists while the FIFO is open and is shared by every
+/// descriptor opened on it. The open counters count every open of each end
+/// ever made, so that an open waiting for the other end notices one even if
+/// it was closed again right away.
+pub struct FifoInode {
+    pub size: usize,
+    pub uid: u32,
+    pub gid: u32,
+    pub mode: u32,
+    pub linkcount: u32,
+    #[serde(skip)]
+    //skips serializing and deserializing field, will populate with u32 default of 0 (refcount
+    // should not be persisted)
+    pub refcount: u32,
+    pub atime: u64,
+    pub ctime: u64,
+    pub mtime: u64,
+    #[serde(skip)]
+    pub pipe: Option<interface::RustRfc<interface::EmulatedPipe>>,
+    #[serde(skip)]
+    pub readers_opened: u64,
+    #[serde(skip)]
+    pub writers_opened: u64,
+}
+
 #[derive(interface::SerdeSerialize, interface::SerdeDeserialize, Debug)]
 pub struct FilesystemMetadata {
     pub nextinode: interface::RustAtomicUsize,
     pub dev_id: u64,
     pub inodetable: interface::RustHashMap<usize, Inode>,
+    // How many bytes of file data and how many inodes the file system can hold.
+    // Metadata written before the capacity was tracked gets the default one
+    #[serde(default = "default_capacity_bytes")]
+    pub capacity_bytes: interface::RustAtomicU64,
+    #[serde(default = "default_capacity_inodes")]
+    pub capacity_inodes: interface::RustAtomicU64,
+    // Per-user limits on the space charged to the files a user owns
+    #[serde(default)]
+    pub quotas: interface::RustHashMap<u32, FsSpace>,
+    // The snapshots taken of the file system, which outlive restoring any of
+    // them and formatting the file system
+    #[serde(default)]
+    pub snapshots: interface::RustHashMap<String, SnapshotInfo>,
+    // The space currently in use, which is not persisted but recounted from
+    // the inode table whenever the file system is loaded
+    #[serde(skip)]
+    pub usage: interface::Mutex<FsUsage>,
+}
+
+/// An amount of file system space, counted as bytes of file data and as
+/// inodes. When used as a quota, a limit of 0 means that the respective
+/// resource is not limited, as with Linux disk quotas.
+#[derive(
+    interface::SerdeSerialize,
+    interface::SerdeDeserialize,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+)]
+pub struct FsSpace {
+    pub bytes: u64,
+    pub inodes: u64,
+}
+
+/// The space in use in the file system, in total and for each user owning
+/// files. The bytes of a regular file are charged to its owner, and every
+/// inode counts as one inode of its owner.
+///
+/// A regular file is charged for its whole size, holes included, rather than
+/// for the extents the storage backend has actually allocated. A sparse file
+/// thus takes up as much of the capacity and of its owner's quota as if it
+/// had been written out in full, which keeps the charge independent of the
+/// backend and of whether a hole gets filled in later.
+#[derive(Debug, Default)]
+pub struct FsUsage {
+    pub total: FsSpace,
+    pub peruid: std::collections::HashMap<u32, FsSpace>,
+}
+
+/// Describes a snapshot of the file system, as listed by `snapshot_list`
+#[derive(interface::SerdeSerialize, interface::SerdeDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    pub name: String,
+    /// When the snapshot was taken, in nanoseconds since the epoch
+    pub created: u64,
+    /// How many regular files the snapshot holds
+    pub files: usize,
+    /// How many bytes of file data the snapshot holds
+    pub bytes: u64,
+    // the size of the saved metadata of the snapshot
+    metadatasize: usize,
+}
+
+fn default_capacity_bytes() -> interface::RustAtomicU64 {
+    interface::RustAtomicU64::new(DEFAULT_CAPACITY_BYTES)
+}
+
+fn default_capacity_inodes() -> interface::RustAtomicU64 {
+    interface::RustAtomicU64::new(DEFAULT_CAPACITY_INODES)
+}
+
+//returns the owner of an inode and the bytes of file data charged to it, which
+//is the logical size of a regular file including any holes in it
+pub fn inode_charge(inode: &Inode) -> (u32, u64) {
+    match inode {
+        Inode::File(f) => (f.uid, f.size as u64),
+        Inode::CharDev(f) => (f.uid, 0),
+        Inode::Socket(f) => (f.uid, 0),
+        Inode::Dir(f) => (f.uid, 0),
+        Inode::Symlink(f) => (f.uid, 0),
+        Inode::Fifo(f) => (f.uid, 0),
+    }
 }
 
 pub fn init_filename_to_inode_dict(
@@ -145,8 +317,13 @@ impl FilesystemMetadata {
             nextinode: interface::RustAtomicUsize::new(STREAMINODE + 1),
             dev_id: 20,
             inodetable: interface::RustHashMap::new(),
+            capacity_bytes: default_capacity_bytes(),
+            capacity_inodes: default_capacity_inodes(),
+            quotas: interface::RustHashMap::new(),
+            snapshots: interface::RustHashMap::new(),
+            usage: interface::Mutex::new(FsUsage::default()),
         };
-        let time = interface::timestamp(); //We do a real timestamp now
+        let time = interface::timestamp_nanos(); //We do a real timestamp now
         let dirinode = DirectoryInode {
             size: 0,
             uid: DEFAULT_UID,
@@ -173,20 +350,245 @@ impl FilesystemMetadata {
         retval
     }
 
-    // Read file, and deserialize CBOR to FS METADATA
+    // Read the saved metadata from the storage backend, and deserialize CBOR to
+    // FS METADATA
     pub fn init_fs_metadata() -> FilesystemMetadata {
-        // Read CBOR from file
-        if interface::pathexists(METADATAFILENAME.to_string()) {
-            let metadata_fileobj = interface::openmetadata(METADATAFILENAME.to_string()).unwrap();
-            let metadatabytes = metadata_fileobj.readfile_to_new_bytes().unwrap();
-            metadata_fileobj.close().unwrap();
-
-            // Restore metadata
-            interface::serde_deserialize_from_bytes(&metadatabytes).unwrap()
+        // Read CBOR from the backend
+        if let Ok(Some(metadatabytes)) = interface::storage_backend().read_metadata() {
+            // Restore metadata, load_fs will report and set aside metadata that
+            // cannot be decoded
+            interface::serde_deserialize_from_bytes(&metadatabytes)
+                .unwrap_or_else(|_| FilesystemMetadata::blank_fs_init())
         } else {
             FilesystemMetadata::blank_fs_init()
         }
     }
+
+    /// ### Description
+    ///
+    /// Takes on the inodes, inode counter, capacity, quotas and snapshots of
+    /// another metadata in place. `FS_METADATA` is created once per
+    /// process, so this is how a file system loaded from a storage backend
+    /// becomes the one in use. The space in use is not carried over and has
+    /// to be recounted.
+    pub fn replace_with(&self, other: FilesystemMetadata) {
+        self.inodetable.clear();
+        for (inodenum, inode) in other.inodetable {
+            self.inodetable.insert(inodenum, inode);
+        }
+        self.nextinode.store(
+            other.nextinode.into_inner(),
+            interface::RustAtomicOrdering::Relaxed,
+        );
+        self.capacity_bytes.store(
+            other.capacity_bytes.into_inner(),
+            interface::RustAtomicOrdering::Relaxed,
+        );
+        self.capacity_inodes.store(
+            other.capacity_inodes.into_inner(),
+            interface::RustAtomicOrdering::Relaxed,
+        );
+        self.quotas.clear();
+        for (uid, quota) in other.quotas {
+            self.quotas.insert(uid, quota);
+        }
+        self.snapshots.clear();
+        for (name, info) in other.snapshots {
+            self.snapshots.insert(name, info);
+        }
+    }
+
+    /// ### Description
+    ///
+    /// Recounts the bytes and inodes in use, in total and per user, from the
+    /// inode table. This is done whenever the file system is loaded, since the
+    /// usage is not persisted and the replayed log may have changed any inode.
+    pub fn recount_usage(&self) {
+        let mut usage = FsUsage::default();
+        for inode in self.inodetable.iter() {
+            let (uid, bytes) = inode_charge(&inode);
+            let user = usage.peruid.entry(uid).or_default();
+            user.bytes += bytes;
+            user.inodes += 1;
+            usage.total.bytes += bytes;
+            usage.total.inodes += 1;
+        }
+        *self.usage.lock() = usage;
+    }
+
+    /// ### Description
+    ///
+    /// Reserves space for the file data of a regular file owned by `uid` that
+    /// is about to grow. As much of `wanted` as fits is reserved, but never
+    /// less than `minimum`, so that a write can be cut short at the point
+    /// where the file system or the user's quota fills up.
+    ///
+    /// ### Arguments
+    ///
+    /// * `uid` - the owner of the file, whose quota the bytes are charged to
+    /// * `wanted` - the number of bytes the file would like to grow by
+    /// * `minimum` - the fewest bytes that are of any use to the caller
+    /// * `enforce_quota` - whether the quota of `uid` applies, which it does
+    ///   unless the caller is the superuser
+    ///
+    /// ### Returns
+    ///
+    /// The number of bytes reserved, between `minimum` and `wanted`.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOSPC` - not even `minimum` bytes are left in the file system
+    /// * `EDQUOT` - not even `minimum` bytes are left in the quota of `uid`
+    pub fn reserve_bytes(
+        &self,
+        uid: u32,
+        wanted: u64,
+        minimum: u64,
+        enforce_quota: bool,
+    ) -> Result<u64, Errno> {
+        let mut usage = self.usage.lock();
+        let capacity = self
+            .capacity_bytes
+            .load(interface::RustAtomicOrdering::Relaxed);
+        let free = capacity.saturating_sub(usage.total.bytes);
+        if free < minimum {
+            return Err(Errno::ENOSPC);
+        }
+        let mut granted = interface::rust_min(wanted, free);
+        if enforce_quota {
+            let limit = self.quotas.get(&uid).map_or(0, |quota| quota.bytes);
+            if limit != 0 {
+                let used = usage.peruid.get(&uid).map_or(0, |user| user.bytes);
+                let left = limit.saturating_sub(used);
+                if left < minimum {
+                    return Err(Errno::EDQUOT);
+                }
+                granted = interface::rust_min(granted, left);
+            }
+        }
+        usage.total.bytes += granted;
+        usage.peruid.entry(uid).or_default().bytes += granted;
+        Ok(granted)
+    }
+
+    /// ### Description
+    ///
+    /// Reserves an inode for a new file, directory, device, socket or symbolic
+    /// link owned by `uid`. If the inode ends up not being created, it has to
+    /// be given back with `release_space()`.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOSPC` - every inode of the file system is in use
+    /// * `EDQUOT` - `uid` already owns as many inodes as its quota allows, and
+    ///   `enforce_quota` is set
+    pub fn reserve_inode(&self, uid: u32, enforce_quota: bool) -> Result<(), Errno> {
+        let mut usage = self.usage.lock();
+        let capacity = self
+            .capacity_inodes
+            .load(interface::RustAtomicOrdering::Relaxed);
+        if usage.total.inodes >= capacity {
+            return Err(Errno::ENOSPC);
+        }
+        if enforce_quota {
+            let limit = self.quotas.get(&uid).map_or(0, |quota| quota.inodes);
+            let used = usage.peruid.get(&uid).map_or(0, |user| user.inodes);
+            if limit != 0 && used >= limit {
+                return Err(Errno::EDQUOT);
+            }
+        }
+        usage.total.inodes += 1;
+        usage.peruid.entry(uid).or_default().inodes += 1;
+        Ok(())
+    }
+
+    /// Gives back bytes and inodes charged to `uid`, e.g. when a file shrinks
+    /// or an inode is removed
+    pub fn release_space(&self, uid: u32, bytes: u64, inodes: u64) {
+        let mut usage = self.usage.lock();
+        usage.total.bytes = usage.total.bytes.saturating_sub(bytes);
+        usage.total.inodes = usage.total.inodes.saturating_sub(inodes);
+        let user = usage.peruid.entry(uid).or_default();
+        user.bytes = user.bytes.saturating_sub(bytes);
+        user.inodes = user.inodes.saturating_sub(inodes);
+    }
+
+    /// Moves the charge for an inode from its old owner to its new one when it
+    /// changes hands. Like the superuser who does this, it ignores quotas.
+    pub fn transfer_space(&self, olduid: u32, newuid: u32, bytes: u64) {
+        if olduid == newuid {
+            return;
+        }
+        let mut usage = self.usage.lock();
+        let old = usage.peruid.entry(olduid).or_default();
+        old.bytes = old.bytes.saturating_sub(bytes);
+        old.inodes = old.inodes.saturating_sub(1);
+        let new = usage.peruid.entry(newuid).or_default();
+        new.bytes += bytes;
+        new.inodes += 1;
+    }
+
+    /// Removes an inode from the inode table and gives back the space that was
+    /// charged for it, returning the removed inode if there was one
+    pub fn remove_inode(&self, inodenum: usize) -> Option<Inode> {
+        let (_, inode) = self.inodetable.remove(&inodenum)?;
+        let (uid, bytes) = inode_charge(&inode);
+        self.release_space(uid, bytes, 1);
+        Some(inode)
+    }
+
+    /// Returns the capacity of the file system and the space in use in it
+    pub fn space_in_use(&self) -> (FsSpace, FsSpace) {
+        let capacity = FsSpace {
+            bytes: self
+                .capacity_bytes
+                .load(interface::RustAtomicOrdering::Relaxed),
+            inodes: self
+                .capacity_inodes
+                .load(interface::RustAtomicOrdering::Relaxed),
+        };
+        (capacity, self.usage.lock().total)
+    }
+
+    /// Returns the quota of `uid` together with the space charged to it
+    pub fn quota_of(&self, uid: u32) -> (FsSpace, FsSpace) {
+        let quota = self.quotas.get(&uid).map_or(FsSpace::default(), |q| *q);
+        let used = self
+            .usage
+            .lock()
+            .peruid
+            .get(&uid)
+            .copied()
+            .unwrap_or_default();
+        (quota, used)
+    }
+
+    /// ### Description
+    ///
+    /// Changes the capacity of the file system. Shrinking it below what is
+    /// already in use is allowed; nothing is removed, but the file system will
+    /// not grow until enough space has been freed. The new capacity is
+    /// persisted right away.
+    pub fn set_capacity(&self, capacity: FsSpace) {
+        self.capacity_bytes
+            .store(capacity.bytes, interface::RustAtomicOrdering::Relaxed);
+        self.capacity_inodes
+            .store(capacity.inodes, interface::RustAtomicOrdering::Relaxed);
+        persist_metadata(self);
+    }
+
+    /// ### Description
+    ///
+    /// Sets the quota of `uid`, where a limit of 0 means unlimited, and
+    /// persists it right away. Setting both limits to 0 removes the quota.
+    pub fn set_quota(&self, uid: u32, quota: FsSpace) {
+        if quota == FsSpace::default() {
+            self.quotas.remove(&uid);
+        } else {
+            self.quotas.insert(uid, quota);
+        }
+        persist_metadata(self);
+    }
 }
 
 pub fn format_fs() {
@@ -219,7 +621,7 @@ pub fn format_fs() {
     tmpchildren.insert("..".to_string(), 1);
     tmpchildren.insert(".".to_string(), 2);
 
-    let time = interface::timestamp(); //We do a real timestamp now
+    let time = interface::timestamp_nanos(); //We do a real timestamp now
     let devdirinode = Inode::Dir(DirectoryInode {
         size: 0,
         uid: DEFAULT_UID,
@@ -302,80 +704,271 @@ pub fn format_fs() {
     newmetadata.inodetable.insert(6, randominode);
     newmetadata.inodetable.insert(7, tmpdirinode);
 
-    let _logremove = interface::removefile(LOGFILENAME.to_string());
+    // snapshots are kept, so that one can still be restored over the new file
+    // system
+    for entry in FS_METADATA.snapshots.iter() {
+        newmetadata
+            .snapshots
+            .insert(entry.key().clone(), entry.value().clone());
+    }
+
+    let _logremove = interface::storage_backend().remove_log();
 
     persist_metadata(&newmetadata);
 }
 
-pub fn load_fs() {
-    // If the metadata file exists, just close the file for later restore
-    // If it doesn't, lets create a new one, load special files, and persist it.
-    if interface::pathexists(METADATAFILENAME.to_string()) {
-        let metadata_fileobj = interface::openmetadata(METADATAFILENAME.to_string()).unwrap();
-        metadata_fileobj.close().unwrap();
+/// Why replaying the metadata log stopped before the end of the log. The
+/// offset is where the offending entry starts within the log entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogDamage {
+    /// The log ends partway through an entry, as after a torn write
+    TruncatedEntry { offset: usize },
+    /// An entry's payload does not match its checksum
+    ChecksumMismatch { offset: usize },
+    /// An entry's checksum is intact but its payload cannot be decoded
+    UndecodableEntry { offset: usize },
+}
 
-        // if we have a log file at this point, we need to sync it with the existing
-        // metadata
-        if interface::pathexists(LOGFILENAME.to_string()) {
-            let log_fileobj = interface::openmetadata(LOGFILENAME.to_string()).unwrap();
-            // read log file and parse count
-            let mut logread = log_fileobj.readfile_to_new_bytes().unwrap();
-            let logsize = interface::convert_bytes_to_size(&logread[0..interface::COUNTMAPSIZE]);
-
-            // create vec of log file bounded by indefinite encoding bytes (0x9F, 0xFF)
-            let mut logbytes: Vec<u8> = Vec::new();
-            logbytes.push(0x9F);
-            logbytes.extend_from_slice(
-                &mut logread[interface::COUNTMAPSIZE..(interface::COUNTMAPSIZE + logsize)],
-            );
-            logbytes.push(0xFF);
-            let mut logvec: Vec<(usize, Option<Inode>)> =
-                interface::serde_deserialize_from_bytes(&logbytes).unwrap();
-
-            // drain the vector and deserialize into pairs of inodenum + inodes,
-            // if the inode exists, add it, if not, remove it
-            // keep track of the largest inodenum we see so we can update the nextinode
-            // counter
-            let mut max_inodenum = FS_METADATA
-                .nextinode
-                .load(interface::RustAtomicOrdering::Relaxed);
-            for serialpair in logvec.drain(..) {
-                let (inodenum, inode) = serialpair;
-                match inode {
-                    Some(inode) => {
-                        max_inodenum = interface::rust_max(max_inodenum, inodenum);
-                        FS_METADATA.inodetable.insert(inodenum, inode);
-                    }
-                    None => {
-                        FS_METADATA.inodetable.remove(&inodenum);
-                    }
+/// Describes what `load_fs` found when restoring the file system
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// A new file system was made because there was no usable metadata
+    pub formatted: bool,
+    /// `lind.metadata` could not be decoded and was moved aside
+    pub metadata_corrupt: bool,
+    /// A log was found without any metadata to apply it to, and was dropped
+    pub orphaned_log: bool,
+    /// Log transactions that were applied
+    pub transactions_applied: usize,
+    /// Inode updates that were applied, across all transactions
+    pub inodes_applied: usize,
+    /// Bytes at the end of the log that were dropped
+    pub bytes_discarded: usize,
+    /// What stopped the replay early, if anything
+    pub damage: Option<LogDamage>,
+}
+
+impl ReplayReport {
+    /// Returns true if nothing had to be dropped or set aside
+    pub fn is_clean(&self) -> bool {
+        !self.metadata_corrupt
+            && !self.orphaned_log
+            && self.bytes_discarded == 0
+            && self.damage.is_none()
+    }
+}
+
+impl std::fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "metadata log replay: {} transactions ({} inodes) applied, {} bytes discarded",
+            self.transactions_applied, self.inodes_applied, self.bytes_discarded
+        )?;
+        if let Some(damage) = self.damage {
+            write!(f, ", stopped at {:?}", damage)?;
+        }
+        if self.metadata_corrupt {
+            write!(f, ", {} was corrupt", interface::METADATAFILENAME)?;
+        }
+        if self.orphaned_log {
+            write!(f, ", log existed but metadata did not")?;
+        }
+        if self.formatted {
+            write!(f, ", file system formatted")?;
+        }
+        Ok(())
+    }
+}
+
+/// ### Description
+///
+/// Splits the contents of a metadata log into its transactions. The log starts
+/// with the count of bytes of entries written, followed by the entries. Entries
+/// are read in order until the count is reached, and reading stops at the
+/// first entry that is cut short, fails its checksum, or cannot be decoded, so
+/// that a damaged tail never takes the intact entries before it down with it.
+///
+/// ### Arguments
+///
+/// * `logread` - The full contents of the log file
+/// * `report` - Updated with the bytes discarded and the damage found
+///
+/// ### Returns
+///
+/// The decoded transactions, each a list of inode numbers paired with the new
+/// state of the inode, or `None` if it was removed.
+pub fn parse_log(logread: &[u8], report: &mut ReplayReport) -> Vec<Vec<(usize, Option<Inode>)>> {
+    let mut transactions = Vec::new();
+
+    if logread.len() < interface::COUNTMAPSIZE {
+        report.bytes_discarded += logread.len();
+        report.damage = Some(LogDamage::TruncatedEntry { offset: 0 });
+        return transactions;
+    }
+
+    // the count may claim more than the file holds if the file was cut short
+    let available = logread.len() - interface::COUNTMAPSIZE;
+    let logsize = interface::convert_bytes_to_size(&logread[0..interface::COUNTMAPSIZE]);
+    let entries = &logread[interface::COUNTMAPSIZE..][..interface::rust_min(logsize, available)];
+    if logsize > available {
+        report.damage = Some(LogDamage::TruncatedEntry { offset: available });
+    }
+
+    let mut offset = 0;
+    while offset < entries.len() {
+        let remaining = &entries[offset..];
+        if remaining.len() < LOGENTRYHEADERSIZE {
+            report.damage = Some(LogDamage::TruncatedEntry { offset });
+            break;
+        }
+        let payloadlen = u32::from_be_bytes(remaining[0..4].try_into().unwrap()) as usize;
+        let checksum = u32::from_be_bytes(remaining[4..8].try_into().unwrap());
+        if remaining.len() - LOGENTRYHEADERSIZE < payloadlen {
+            report.damage = Some(LogDamage::TruncatedEntry { offset });
+            break;
+        }
+        let payload = &remaining[LOGENTRYHEADERSIZE..LOGENTRYHEADERSIZE + payloadlen];
+        if interface::crc32(payload) != checksum {
+            report.damage = Some(LogDamage::ChecksumMismatch { offset });
+            break;
+        }
+
+        // the payload is a run of (inodenum, inode) pairs, bound it by indefinite
+        // encoding bytes (0x9F, 0xFF) to read it as one vector
+        let mut entrybytes: Vec<u8> = Vec::with_capacity(payloadlen + 2);
+        entrybytes.push(0x9F);
+        entrybytes.extend_from_slice(payload);
+        entrybytes.push(0xFF);
+        match interface::serde_deserialize_from_bytes(&entrybytes) {
+            Ok(transaction) => transactions.push(transaction),
+            Err(_) => {
+                report.damage = Some(LogDamage::UndecodableEntry { offset });
+                break;
+            }
+        }
+        offset += LOGENTRYHEADERSIZE + payloadlen;
+    }
+
+    report.bytes_discarded += entries.len() - interface::rust_min(offset, entries.len());
+    transactions
+}
+
+// Applies the transactions of a metadata log to a metadata, each as a whole.
+// An inode logged with its state is added or replaced, one logged as removed is
+// dropped, and inodes the log doesn't mention are left alone. A transaction
+// whose entry was cut short or damaged is skipped along with everything after
+// it, since parse_log stops there. The inode counter is moved past every inode
+// seen to avoid collisions
+fn replay_log(metadata: &FilesystemMetadata, logread: &[u8], report: &mut ReplayReport) {
+    let transactions = parse_log(logread, report);
+
+    let mut max_inodenum = metadata
+        .nextinode
+        .load(interface::RustAtomicOrdering::Relaxed);
+    for transaction in transactions {
+        report.transactions_applied += 1;
+        for (inodenum, inode) in transaction {
+            report.inodes_applied += 1;
+            match inode {
+                Some(inode) => {
+                    max_inodenum = interface::rust_max(max_inodenum, inodenum);
+                    metadata.inodetable.insert(inodenum, inode);
+                }
+                None => {
+                    metadata.inodetable.remove(&inodenum);
                 }
             }
+        }
+    }
+
+    // update the nextinode counter to avoid collisions
+    metadata
+        .nextinode
+        .store(max_inodenum + 1, interface::RustAtomicOrdering::Relaxed);
+}
+
+/// ### Description
+///
+/// Restores the file system from the storage backend when lind starts. If
+/// there is no usable saved metadata a new file system is formatted, and
+/// metadata that cannot be decoded is set aside as `lind.metadata.corrupt`
+/// first. Otherwise any
+/// log left behind by a previous run is replayed onto the metadata one whole
+/// transaction at a time, and the result is checkpointed back into
+/// `lind.metadata` before a fresh log is started. Nothing found on disk makes
+/// this panic, what was applied and dropped is returned instead.
+///
+/// ### Returns
+///
+/// A `ReplayReport` describing what was restored.
+pub fn load_fs() -> ReplayReport {
+    let mut report = ReplayReport::default();
+    let storage = interface::storage_backend();
+
+    // metadata that cannot be read is treated as missing
+    let mut loaded: Option<FilesystemMetadata> = None;
+    if let Some(bytes) = storage.read_metadata().unwrap_or(None) {
+        match interface::serde_deserialize_from_bytes(&bytes) {
+            Ok(metadata) => loaded = Some(metadata),
+            Err(_) => {
+                report.metadata_corrupt = true;
+                let _ = storage.set_aside_metadata();
+            }
+        }
+    }
+    let logread = storage.read_log().unwrap_or(None);
 
-            // update the nextinode counter to avoid collisions
-            FS_METADATA
-                .nextinode
-                .store(max_inodenum + 1, interface::RustAtomicOrdering::Relaxed);
+    if let Some(metadata) = loaded {
+        FS_METADATA.replace_with(metadata);
 
-            let _logclose = log_fileobj.close();
-            let _logremove = interface::removefile(LOGFILENAME.to_string());
+        // if we have a log at this point, we need to sync it with the existing
+        // metadata
+        if let Some(logread) = logread {
+            replay_log(&FS_METADATA, &logread, &mut report);
 
             // clean up broken links
             fsck();
+
+            // fold what was replayed into the metadata before the log goes away,
+            // so a crash before the next save does not lose it
+            persist_metadata(&FS_METADATA);
+            let _logremove = storage.remove_log();
         }
     } else {
-        if interface::pathexists(LOGFILENAME.to_string()) {
-            println!("Filesystem in very corrupted state: log existed but metadata did not!");
+        if logread.is_some() {
+            report.orphaned_log = true;
         }
+        // nothing loaded before, from this or another backend, belongs to the
+        // new file system
+        FS_METADATA.replace_with(FilesystemMetadata::blank_fs_init());
         format_fs();
+        report.formatted = true;
+
+        // and take on the file system that was just made
+        if let Ok(Some(bytes)) = storage.read_metadata() {
+            if let Ok(metadata) = interface::serde_deserialize_from_bytes(&bytes) {
+                FS_METADATA.replace_with(metadata);
+            }
+        }
     }
 
+    // the space in use is not persisted, so count it from what was loaded
+    FS_METADATA.recount_usage();
+
     // then recreate the log
     create_log();
+
+    report
 }
 
 pub fn fsck() {
-    FS_METADATA.inodetable.retain(|_inodenum, inode_obj| {
+    fsck_metadata(&FS_METADATA);
+}
+
+// Drops the inodes of a metadata that nothing links to any longer
+fn fsck_metadata(metadata: &FilesystemMetadata) {
+    metadata.inodetable.retain(|_inodenum, inode_obj| {
         match inode_obj {
             Inode::File(ref mut normalfile_inode) => normalfile_inode.linkcount != 0,
             Inode::Dir(ref mut dir_inode) => {
@@ -383,53 +976,461 @@ pub fn fsck() {
                 dir_inode.linkcount > 2
             }
             Inode::CharDev(ref mut char_inodej) => char_inodej.linkcount != 0,
+            Inode::Symlink(ref mut symlink_inode) => symlink_inode.linkcount != 0,
+            Inode::Fifo(ref mut fifo_inode) => fifo_inode.linkcount != 0,
             Inode::Socket(_) => false,
         }
     });
 }
 
 pub fn create_log() {
-    // reinstantiate the log file and assign it to the metadata struct
-    let log_mapobj = interface::mapfilenew(LOGFILENAME.to_string()).unwrap();
+    // reinstantiate the log and assign it to the metadata struct
+    let log_mapobj = interface::storage_backend().create_log().unwrap();
     let mut logobj = LOGMAP.write();
     logobj.replace(log_mapobj);
 }
 
 /// Serialize New Metadata to CBOR, write to logfile
 pub fn log_metadata(metadata: &FilesystemMetadata, inodenum: usize) {
-    let serialpair: (usize, Option<&Inode>);
-    let entrybytes;
+    log_metadata_group(metadata, &[inodenum]);
+}
 
-    // pack and serialize log entry
-    if let Some(inode) = metadata.inodetable.get(&inodenum) {
-        serialpair = (inodenum, Some(&*inode));
-        entrybytes = interface::serde_serialize_to_bytes(&serialpair).unwrap();
-    } else {
-        serialpair = (inodenum, None);
-        entrybytes = interface::serde_serialize_to_bytes(&serialpair).unwrap();
+/// ### Description
+///
+/// Writes the current state of several inodes to the metadata log as a single
+/// transaction. Operations that change more than one inode, such as link,
+/// rename and rmdir, log all of them through here so that replaying the log
+/// applies either every change the operation made or none of them. An inode
+/// that is no longer in the inode table is logged as removed.
+///
+/// Once the log holds more than `LOG_CHECKPOINT_BYTES` of entries it is
+/// checkpointed: the metadata is saved to `lind.metadata` and the log is
+/// started over. As with `log_metadata`, the caller must not hold a reference
+/// into the inode table.
+///
+/// ### Arguments
+///
+/// * `metadata` - The file system metadata the inodes belong to
+/// * `inodenums` - The inodes changed by the operation
+pub fn log_metadata_group(metadata: &FilesystemMetadata, inodenums: &[usize]) {
+    // pack and serialize each (inodenum, inode) pair, one inode at a time so that
+    // we never hold more than one reference into the inode table
+    let mut payload = Vec::new();
+    for &inodenum in inodenums {
+        let entrybytes = if let Some(inode) = metadata.inodetable.get(&inodenum) {
+            let serialpair: (usize, Option<&Inode>) = (inodenum, Some(&*inode));
+            interface::serde_serialize_to_bytes(&serialpair).unwrap()
+        } else {
+            let serialpair: (usize, Option<&Inode>) = (inodenum, None);
+            interface::serde_serialize_to_bytes(&serialpair).unwrap()
+        };
+        payload.extend_from_slice(&entrybytes);
     }
 
+    // frame the transaction with its length and checksum
+    let mut entry = Vec::with_capacity(LOGENTRYHEADERSIZE + payload.len());
+    entry.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    entry.extend_from_slice(&interface::crc32(&payload).to_be_bytes());
+    entry.extend_from_slice(&payload);
+
     // write to file
     let mut mapopt = LOGMAP.write();
     let map = mapopt.as_mut().unwrap();
-    map.write_to_map(&entrybytes).unwrap();
+    map.write_to_map(&entry).unwrap();
+
+    if map.bytes_written() >= LOG_CHECKPOINT_BYTES.load(interface::RustAtomicOrdering::Relaxed) {
+        checkpoint_log(metadata, &mut mapopt);
+    }
+}
+
+// Saves the metadata and starts the log over. The log lock is held throughout
+// so that no entry can be written between the save and the new log, and every
+// entry already in the log describes a change the saved metadata includes
+fn checkpoint_log(metadata: &FilesystemMetadata, logobj: &mut Option<interface::EmulatedFileMap>) {
+    persist_metadata(metadata);
+    if let Some(log) = logobj.take() {
+        let _logclose = log.close();
+    }
+    logobj.replace(interface::storage_backend().create_log().unwrap());
+}
+
+/// ### Description
+///
+/// Folds the metadata log into `lind.metadata` now, rather than waiting for
+/// the log to reach `LOG_CHECKPOINT_BYTES`.
+pub fn checkpoint_metadata(metadata: &FilesystemMetadata) {
+    let mut mapopt = LOGMAP.write();
+    checkpoint_log(metadata, &mut mapopt);
 }
 
-// Serialize Metadata Struct to CBOR, write to file
+// Serialize Metadata Struct to CBOR, and hand it to the storage backend, which
+// replaces the saved metadata in one step
 pub fn persist_metadata(metadata: &FilesystemMetadata) {
+    let _persistguard = PERSISTLOCK.lock();
+
     // Serialize metadata to string
     let metadatabytes = interface::serde_serialize_to_bytes(&metadata).unwrap();
 
-    // remove file if it exists, assigning it to nothing to avoid the compiler
-    // yelling about unused result
-    let _ = interface::removefile(METADATAFILENAME.to_string());
-
-    // write to file
-    let mut metadata_fileobj = interface::openmetadata(METADATAFILENAME.to_string()).unwrap();
-    metadata_fileobj
-        .writefile_from_bytes(&metadatabytes)
+    interface::storage_backend()
+        .write_metadata(&metadatabytes)
         .unwrap();
-    metadata_fileobj.close().unwrap();
+}
+
+/// ### Description
+///
+/// Copies a whole file system from one storage backend to another: the
+/// metadata, with any log left in the source replayed into it, the data of
+/// every regular file, keeping the holes in files, and every snapshot. This
+/// works on the backends directly and not on the file system lind is running,
+/// so it is how a directory-based file system is exported to an image, and how
+/// an image is imported back into a directory.
+///
+/// ### Arguments
+///
+/// * `from` - The backend to copy from, which is only read
+/// * `to` - The backend to copy to. Its metadata and the data of files with the
+///   same names are replaced, and its log is removed
+///
+/// ### Returns
+///
+/// The report of replaying the log of the source.
+///
+/// ### Errors
+///
+/// Fails with `InvalidData` if the source has no metadata or its metadata
+/// cannot be decoded, and with any error either backend runs into.
+pub fn copy_fs(
+    from: &dyn interface::StorageBackend,
+    to: &dyn interface::StorageBackend,
+) -> std::io::Result<ReplayReport> {
+    let mut report = ReplayReport::default();
+    let metadata = read_stored_metadata(from, &mut report)?;
+
+    for (inodenum, size) in regular_files(&metadata) {
+        let sysfilename = format!("{}{}", FILEDATAPREFIX, inodenum);
+        interface::copy_data(from, &sysfilename, to, &sysfilename, size)?;
+    }
+    let snapshots: Vec<SnapshotInfo> = metadata
+        .snapshots
+        .iter()
+        .map(|entry| entry.value().clone())
+        .collect();
+    for info in snapshots {
+        let snapshot = read_snapshot_metadata(from, &info)?;
+        for (inodenum, size) in regular_files(&snapshot) {
+            let dataname = snapshot_data_name(&info.name, inodenum);
+            interface::copy_data(from, &dataname, to, &dataname, size)?;
+        }
+        let metadataname = snapshot_metadata_name(&info.name);
+        interface::copy_data(from, &metadataname, to, &metadataname, info.metadatasize)?;
+    }
+
+    write_stored_metadata(to, &metadata)?;
+    Ok(report)
+}
+
+// Reads the metadata a storage backend holds, with any log left in it
+// replayed, failing with InvalidData if there is none or it cannot be decoded
+fn read_stored_metadata(
+    storage: &dyn interface::StorageBackend,
+    report: &mut ReplayReport,
+) -> std::io::Result<FilesystemMetadata> {
+    let bytes = storage.read_metadata()?.ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, "no file system metadata")
+    })?;
+    let metadata: FilesystemMetadata = interface::serde_deserialize_from_bytes(&bytes)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    if let Some(logread) = storage.read_log()? {
+        replay_log(&metadata, &logread, report);
+        fsck_metadata(&metadata);
+    }
+    Ok(metadata)
+}
+
+// Saves metadata as the whole of what a storage backend holds, so any log it
+// had is removed after
+fn write_stored_metadata(
+    storage: &dyn interface::StorageBackend,
+    metadata: &FilesystemMetadata,
+) -> std::io::Result<()> {
+    let metadatabytes = interface::serde_serialize_to_bytes(&metadata)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    storage.write_metadata(&metadatabytes)?;
+    storage.remove_log()
+}
+
+// The inode number and size of every regular file in a metadata
+fn regular_files(metadata: &FilesystemMetadata) -> Vec<(usize, usize)> {
+    metadata
+        .inodetable
+        .iter()
+        .filter_map(|entry| match entry.value() {
+            Inode::File(f) => Some((*entry.key(), f.size)),
+            _ => None,
+        })
+        .collect()
+}
+
+// The names under which a snapshot keeps the data of a file and its metadata
+fn snapshot_data_name(snapshot: &str, inodenum: usize) -> String {
+    format!("{}{}.{}", SNAPSHOTPREFIX, snapshot, inodenum)
+}
+
+fn snapshot_metadata_name(snapshot: &str) -> String {
+    format!("{}{}.metadata", SNAPSHOTPREFIX, snapshot)
+}
+
+// Reads the metadata saved with a snapshot
+fn read_snapshot_metadata(
+    storage: &dyn interface::StorageBackend,
+    info: &SnapshotInfo,
+) -> std::io::Result<FilesystemMetadata> {
+    let metadataobj = storage.open_data(&snapshot_metadata_name(&info.name), info.metadatasize)?;
+    let mut metadatabytes = vec![0u8; info.metadatasize];
+    let readlen = metadataobj.readat(metadatabytes.as_mut_ptr(), info.metadatasize, 0)?;
+    metadataobj.close()?;
+    if readlen != info.metadatasize {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "the metadata of the snapshot is incomplete",
+        ));
+    }
+    interface::serde_deserialize_from_bytes(&metadatabytes)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+// Snapshots work on what is in storage, so they are only taken and restored
+// while lind is not running and nothing else changes it. Returns the backend
+// in use, after checking the name of the snapshot if there is one
+fn snapshot_storage(
+    name: Option<&str>,
+) -> std::io::Result<interface::RustRfc<dyn interface::StorageBackend>> {
+    if LOGMAP.read().is_some() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::ResourceBusy,
+            "snapshots cannot be used while lind is running",
+        ));
+    }
+    if let Some(name) = name {
+        let valid = name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if name.is_empty() || name.len() > MAXSNAPSHOTNAME || !valid {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "snapshot names are letters, digits, '-' and '_'",
+            ));
+        }
+    }
+    Ok(interface::storage_backend())
+}
+
+// Clones the data of a file, where an empty file may never have had any data
+// stored
+fn clone_file_data(
+    storage: &dyn interface::StorageBackend,
+    from: &str,
+    to: &str,
+    size: usize,
+) -> std::io::Result<()> {
+    match storage.clone_data(from, to, size) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && size == 0 => {
+            let _ = storage.remove_data(to);
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+// Removes the data of a file, which may already be gone
+fn remove_file_data(storage: &dyn interface::StorageBackend, name: &str) -> std::io::Result<()> {
+    match storage.remove_data(name) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// ### Description
+///
+/// Takes a snapshot of the file system in the storage backend in use. The
+/// data of every regular file is cloned, which shares it instead of copying
+/// it where the backend can, and the metadata is saved alongside, after which
+/// the snapshot is added to the list kept in the file system's metadata. Like
+/// the other snapshot functions this is called while lind is not running,
+/// before `lindrustinit()` or after `lindrustfinalize()`.
+///
+/// ### Arguments
+///
+/// * `name` - The name of the snapshot, made of letters, digits, '-' and '_'
+///
+/// ### Returns
+///
+/// The description of the new snapshot.
+///
+/// ### Errors
+///
+/// * `ResourceBusy` - lind is running
+/// * `InvalidInput` - the name is not a valid snapshot name
+/// * `AlreadyExists` - there already is a snapshot with this name
+/// * `InvalidData` - there is no file system, or its metadata cannot be decoded
+///
+/// and any error the storage backend runs into.
+pub fn snapshot_create(name: &str) -> std::io::Result<SnapshotInfo> {
+    let storage = snapshot_storage(Some(name))?;
+    let mut report = ReplayReport::default();
+    let mut metadata = read_stored_metadata(&*storage, &mut report)?;
+    if metadata.snapshots.contains_key(name) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            "a snapshot with this name already exists",
+        ));
+    }
+
+    let files = regular_files(&metadata);
+    for &(inodenum, size) in &files {
+        let sysfilename = format!("{}{}", FILEDATAPREFIX, inodenum);
+        clone_file_data(
+            &*storage,
+            &sysfilename,
+            &snapshot_data_name(name, inodenum),
+            size,
+        )?;
+    }
+
+    // the snapshot holds the file system without the list of snapshots, which
+    // restoring it leaves as it is
+    let snapshots = std::mem::take(&mut metadata.snapshots);
+    let metadatabytes = interface::serde_serialize_to_bytes(&metadata)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let metadataname = snapshot_metadata_name(name);
+    let _ = storage.remove_data(&metadataname);
+    let mut metadataobj = storage.open_data(&metadataname, 0)?;
+    metadataobj.writefile_from_bytes(&metadatabytes)?;
+    metadataobj.fsync()?;
+    metadataobj.close()?;
+
+    // only once it is all stored does the snapshot become part of the file system
+    let info = SnapshotInfo {
+        name: name.to_string(),
+        created: interface::timestamp_nanos(),
+        files: files.len(),
+        bytes: files.iter().map(|&(_, size)| size as u64).sum(),
+        metadatasize: metadatabytes.len(),
+    };
+    metadata.snapshots = snapshots;
+    metadata.snapshots.insert(name.to_string(), info.clone());
+    write_stored_metadata(&*storage, &metadata)?;
+    Ok(info)
+}
+
+/// ### Description
+///
+/// Lists the snapshots of the file system in the storage backend in use,
+/// oldest first.
+///
+/// ### Errors
+///
+/// * `ResourceBusy` - lind is running
+/// * `InvalidData` - there is no file system, or its metadata cannot be decoded
+///
+/// and any error the storage backend runs into.
+pub fn snapshot_list() -> std::io::Result<Vec<SnapshotInfo>> {
+    let storage = snapshot_storage(None)?;
+    let mut report = ReplayReport::default();
+    let metadata = read_stored_metadata(&*storage, &mut report)?;
+    let mut snapshots: Vec<SnapshotInfo> = metadata
+        .snapshots
+        .iter()
+        .map(|entry| entry.value().clone())
+        .collect();
+    snapshots.sort_by(|a, b| (a.created, &a.name).cmp(&(b.created, &b.name)));
+    Ok(snapshots)
+}
+
+/// ### Description
+///
+/// Puts the file system in the storage backend in use back the way it was
+/// when a snapshot was taken. The data of the files there are now is removed,
+/// the data of the snapshot is cloned back in their place and the metadata of
+/// the snapshot is taken on, while the list of snapshots stays as it is, so the
+/// snapshot can be restored again. If restoring is interrupted, restoring the
+/// same snapshot again completes it.
+///
+/// ### Arguments
+///
+/// * `name` - The name of the snapshot to restore
+///
+/// ### Errors
+///
+/// * `ResourceBusy` - lind is running
+/// * `InvalidInput` - the name is not a valid snapshot name
+/// * `NotFound` - there is no snapshot with this name
+/// * `InvalidData` - the metadata of the file system or of the snapshot cannot
+///   be decoded
+///
+/// and any error the storage backend runs into.
+pub fn snapshot_restore(name: &str) -> std::io::Result<()> {
+    let storage = snapshot_storage(Some(name))?;
+    let mut report = ReplayReport::default();
+    let mut current = read_stored_metadata(&*storage, &mut report)?;
+    let info = match current.snapshots.get(name) {
+        Some(info) => info.clone(),
+        None => return Err(std::io::Error::from(std::io::ErrorKind::NotFound)),
+    };
+
+    let mut restored = read_snapshot_metadata(&*storage, &info)?;
+
+    for (inodenum, _) in regular_files(&current) {
+        remove_file_data(&*storage, &format!("{}{}", FILEDATAPREFIX, inodenum))?;
+    }
+    for (inodenum, size) in regular_files(&restored) {
+        let sysfilename = format!("{}{}", FILEDATAPREFIX, inodenum);
+        clone_file_data(
+            &*storage,
+            &snapshot_data_name(name, inodenum),
+            &sysfilename,
+            size,
+        )?;
+    }
+
+    restored.snapshots = std::mem::take(&mut current.snapshots);
+    write_stored_metadata(&*storage, &restored)
+}
+
+/// ### Description
+///
+/// Deletes a snapshot of the file system in the storage backend in use. It is
+/// taken off the list of snapshots first, and its data removed after, so an
+/// interrupted delete at worst leaves data nothing refers to.
+///
+/// ### Arguments
+///
+/// * `name` - The name of the snapshot to delete
+///
+/// ### Errors
+///
+/// * `ResourceBusy` - lind is running
+/// * `InvalidInput` - the name is not a valid snapshot name
+/// * `NotFound` - there is no snapshot with this name
+/// * `InvalidData` - the metadata of the file system or of the snapshot cannot
+///   be decoded
+///
+/// and any error the storage backend runs into.
+pub fn snapshot_delete(name: &str) -> std::io::Result<()> {
+    let storage = snapshot_storage(Some(name))?;
+    let mut report = ReplayReport::default();
+    let current = read_stored_metadata(&*storage, &mut report)?;
+    let info = match current.snapshots.remove(name) {
+        Some((_, info)) => info,
+        None => return Err(std::io::Error::from(std::io::ErrorKind::NotFound)),
+    };
+    write_stored_metadata(&*storage, &current)?;
+
+    let snapshot = read_snapshot_metadata(&*storage, &info)?;
+    for (inodenum, _) in regular_files(&snapshot) {
+        remove_file_data(&*storage, &snapshot_data_name(name, inodenum))?;
+    }
+    remove_file_data(&*storage, &snapshot_metadata_name(name))
 }
 
 pub fn convpath(cpath: &str) -> interface::RustPathBuf {
@@ -466,46 +1467,162 @@ pub fn pathnamefrominodenum(inodenum: usize) -> Option<String> {
     let mut current_inodenum = inodenum;
 
     loop {
-        let mut thisinode = match FS_METADATA.inodetable.get_mut(&current_inodenum) {
+        let thisinode = match FS_METADATA.inodetable.get(&current_inodenum) {
             Some(inode) => inode,
             None => {
                 return None;
             }
         };
 
-        match *thisinode {
-            Inode::Dir(ref mut dir_inode) => {
-                // We try to get the parent directory inode.
-                if let Some(parent_dir_inode) = dir_inode.filename_to_inode_dict.get("..") {
-                    // If the parent node is 1 (indicating the root directory) and this is not the
-                    // first iteration, this indicates that we have arrived at the root directory.
-                    // Here we add a '/' to the beginning of the path string and return it.
-                    if *parent_dir_inode == (1 as usize) {
-                        if !first_iteration {
-                            path_string.insert(0, '/');
-                            return Some(path_string);
-                        }
-                        first_iteration = false;
-                    }
-
-                    match filenamefrominode(*parent_dir_inode, current_inodenum) {
-                        Some(filename) => {
-                            path_string = filename + "/" + &path_string;
-                            current_inodenum = *parent_dir_inode;
-                        }
-                        None => return None,
-                    };
-                } else {
-                    return None;
-                }
-            }
+        // We try to get the parent directory inode. The reference into the inode table
+        // is dropped right after, as filenamefrominode will need to look up the parent
+        // in the same table and the DashMap deadlocks if we hold onto it.
+        let parent_dir_inode = match &*thisinode {
+            Inode::Dir(dir_inode) => dir_inode
+                .filename_to_inode_dict
+                .get("..")
+                .map(|parent| *parent),
             _ => {
                 return None;
             }
+        };
+        drop(thisinode);
+
+        let parent_dir_inode = match parent_dir_inode {
+            Some(parent_dir_inode) => parent_dir_inode,
+            None => {
+                return None;
+            }
+        };
+
+        // If the parent node is 1 (indicating the root directory) and this is not the
+        // first iteration, this indicates that we have arrived at the root directory.
+        // Here we add a '/' to the beginning of the path string and return it.
+        if parent_dir_inode == (1 as usize) {
+            if !first_iteration {
+                path_string.insert(0, '/');
+                return Some(path_string);
+            }
+            first_iteration = false;
         }
+
+        match filenamefrominode(parent_dir_inode, current_inodenum) {
+            Some(filename) => {
+                path_string = filename + "/" + &path_string;
+                current_inodenum = parent_dir_inode;
+            }
+            None => return None,
+        };
     }
 }
 
+/// ### Description
+///
+/// Tells whether the directory `ancestor` is the directory `dirinodenum` or
+/// one of the directories above it, by following the `..` entries from
+/// `dirinodenum` up to the root. Unlike comparing paths this sees through
+/// symbolic links, which can give a directory any number of paths.
+///
+/// ### Arguments
+///
+/// * `ancestor` - The inode number of the directory that may be above
+/// * `dirinodenum` - The inode number of the directory to start from
+///
+/// ### Returns
+///
+/// True if `ancestor` is reached before the root, or is the root.
+pub fn dir_is_ancestor(ancestor: usize, dirinodenum: usize) -> bool {
+    let mut current = dirinodenum;
+    // every step goes one directory up, so a walk longer than the inode table
+    // could only come from a broken tree
+    for _ in 0..=FS_METADATA.inodetable.len() {
+        if current == ancestor {
+            return true;
+        }
+        if current == ROOTDIRECTORYINODE {
+            return false;
+        }
+        match dirparent(current) {
+            Some(parent) => current = parent,
+            None => return false,
+        }
+    }
+    false
+}
+
+//returns the directory the `..` entry of a directory leads to
+fn dirparent(dirinodenum: usize) -> Option<usize> {
+    match FS_METADATA.inodetable.get(&dirinodenum).as_deref() {
+        Some(Inode::Dir(dir)) => dir.filename_to_inode_dict.get("..").map(|p| *p),
+        _ => None,
+    }
+}
+
+//returns whether a directory still has an entry in its parent, which it loses
+// when it is removed while it is open
+pub fn dir_is_linked(dirinodenum: usize) -> bool {
+    if dirinodenum == ROOTDIRECTORYINODE {
+        return true;
+    }
+    let parent = match dirparent(dirinodenum) {
+        Some(parent) => parent,
+        None => return false,
+    };
+    match FS_METADATA.inodetable.get(&parent).as_deref() {
+        Some(Inode::Dir(dir)) => dir
+            .filename_to_inode_dict
+            .iter()
+            .any(|entry| entry.key() != ".." && *entry.value() == dirinodenum),
+        _ => false,
+    }
+}
+
+/// Returns a path that names the directory `dirinodenum` by its inode number
+/// instead of by where it is in the tree. Paths built onto it are walked from
+/// that directory, so they keep leading to the same files when the directory
+/// is renamed, and even after it is removed. The component naming the inode
+/// starts with a NUL byte, which no file name can contain.
+pub fn anchorpath(dirinodenum: usize) -> interface::RustPathBuf {
+    interface::RustPathBuf::from(format!("/\0{}", dirinodenum))
+}
+
+//returns the directory a path made by `anchorpath` starts from, if it is one
+pub fn path_anchor(path: &interface::RustPath) -> Option<usize> {
+    match path.components().nth(1)? {
+        interface::RustPathComponent::Normal(f) => anchorinode(f),
+        _ => None,
+    }
+}
+
+//returns the directory a path component made by `anchorpath` stands for
+fn anchorinode(comp: &std::ffi::OsStr) -> Option<usize> {
+    comp.to_str()?.strip_prefix('\0')?.parse().ok()
+}
+
+/// Turns a path made by `anchorpath` into the absolute path it currently
+/// leads to, for the places that keep track of files by their path. A path
+/// that isn't anchored, or whose directory has been removed, is returned as
+/// it is.
+pub fn unanchoredpath(path: &interface::RustPath) -> interface::RustPathBuf {
+    if let Some(dirpath) = path_anchor(path).and_then(dirpathfrominodenum) {
+        return dirpath.join(
+            path.components()
+                .skip(2)
+                .collect::<interface::RustPathBuf>(),
+        );
+    }
+    path.to_path_buf()
+}
+
+/// Returns the absolute path of a directory, built from the `..` entries
+/// leading up from it, or `None` if it is not connected to the root.
+pub fn dirpathfrominodenum(dirinodenum: usize) -> Option<interface::RustPathBuf> {
+    if dirinodenum == ROOTDIRECTORYINODE {
+        return Some(interface::RustPathBuf::from("/"));
+    }
+    pathnamefrominodenum(dirinodenum).map(interface::RustPathBuf::from)
+}
+
 // Find the file by the given inode number in the given directory
 pub fn filenamefrominode(dir_inode_no: usize, target_inode: usize) -> Option<String> {
     let cur_node = Some(FS_METADATA.inodetable.get(&dir_inode_no).unwrap());
@@ -526,75 +1643,308 @@ pub fn filenamefrominode(dir_inode_no: usize, target_inode: usize) -> Option<Str
     }
 }
 
-//returns tuple consisting of inode number of file (if it exists), and inode
-// number of parent (if it exists)
-pub fn metawalkandparent(path: &interface::RustPath) -> (Option<usize>, Option<usize>) {
-    let mut curnode = Some(FS_METADATA.inodetable.get(&ROOTDIRECTORYINODE).unwrap());
-    let mut inodeno = Some(ROOTDIRECTORYINODE);
-    let mut previnodeno = None;
+//returns the mode bits, owning user and owning group of an inode
+pub fn inode_ownership(inode: &Inode) -> (u32, u32, u32) {
+    match inode {
+        Inode::File(f) => (f.mode, f.uid, f.gid),
+        Inode::CharDev(f) => (f.mode, f.uid, f.gid),
+        Inode::Socket(f) => (f.mode, f.uid, f.gid),
+        Inode::Dir(f) => (f.mode, f.uid, f.gid),
+        Inode::Symlink(f) => (f.mode, f.uid, f.gid),
+        Inode::Fifo(f) => (f.mode, f.uid, f.gid),
+    }
+}
 
-    //Iterate over the components of the pathbuf in order to walk the file tree
-    for comp in path.components() {
-        match comp {
-            //We've already done what initialization needs to be done
-            interface::RustPathComponent::RootDir => {}
+//returns mutable references to the access, modification and status change
+//timestamps of an inode, which are kept in nanoseconds since the epoch
+pub fn inode_times_mut(inode: &mut Inode) -> (&mut u64, &mut u64, &mut u64) {
+    match inode {
+        Inode::File(f) => (&mut f.atime, &mut f.mtime, &mut f.ctime),
+        Inode::CharDev(f) => (&mut f.atime, &mut f.mtime, &mut f.ctime),
+        Inode::Socket(f) => (&mut f.atime, &mut f.mtime, &mut f.ctime),
+        Inode::Dir(f) => (&mut f.atime, &mut f.mtime, &mut f.ctime),
+        Inode::Symlink(f) => (&mut f.atime, &mut f.mtime, &mut f.ctime),
+        Inode::Fifo(f) => (&mut f.atime, &mut f.mtime, &mut f.ctime),
+    }
+}
+
+//records that the contents of an inode (the data of a file or the entries of
+//a directory) were modified at `time`, which is also a change of its status
+pub fn mark_modified(inode: &mut Inode, time: u64) {
+    let (_, mtime, ctime) = inode_times_mut(inode);
+    *mtime = time;
+    *ctime = time;
+}
+
+//records that the status of an inode (its mode, owners or link count) was
+//changed at `time`
+pub fn mark_changed(inode: &mut Inode, time: u64) {
+    *inode_times_mut(inode).2 = time;
+}
+
+/// This function decides whether a caller with user id `uid` and group id
+/// `gid` is granted every permission in `amode`, which is a combination of
+/// `R_OK`, `W_OK` and `X_OK`, on a file with the given mode and owners.
+///
+/// - Exactly one class of permission bits applies: the owner bits if the caller
+///   owns the file, otherwise the group bits if the caller is in the file's
+///   group, otherwise the bits for other users.
+///
+/// - The superuser is granted read and write access to everything, and execute
+///   access to directories and to files with at least one execute bit set.
+pub fn permission_granted(
+    mode: u32,
+    owner: u32,
+    group: u32,
+    uid: u32,
+    gid: u32,
+    amode: u32,
+) -> bool {
+    if uid == ROOT_UID {
+        return amode & X_OK == 0 || is_dir(mode) || mode & (S_IXUSR | S_IXGRP | S_IXOTH) != 0;
+    }
+
+    let classbits = if uid == owner {
+        (mode & S_IRWXU) >> 6
+    } else if gid == group {
+        (mode & S_IRWXG) >> 3
+    } else {
+        mode & S_IRWXO
+    };
+    classbits & amode == amode
+}
+
+//checks permission_granted against the inode numbered inodenum, which is
+// treated as inaccessible if it no longer exists
+pub fn inode_permission_granted(inodenum: usize, uid: u32, gid: u32, amode: u32) -> bool {
+    match FS_METADATA.inodetable.get(&inodenum) {
+        Some(inode) => {
+            let (mode, owner, group) = inode_ownership(&inode);
+            permission_granted(mode, owner, group, uid, gid, amode)
+        }
+        None => false,
+    }
+}
 
-            interface::RustPathComponent::Normal(f) => {
-                //If we're trying to get the child of a nonexistent directory, exit out
-                if inodeno.is_none() {
-                    return (None, None);
+//returns whether the caller may search (execute) every directory that has to
+// be looked into to reach path, i.e. every directory above its final component.
+// The path must already have its symbolic links resolved. A walk that runs into
+// a missing directory is allowed here so that the caller reports ENOENT instead
+pub fn search_permitted(path: &interface::RustPath, uid: u32, gid: u32) -> bool {
+    let mut inodeno = ROOTDIRECTORYINODE;
+    for (index, comp) in path.components().enumerate() {
+        if let interface::RustPathComponent::Normal(f) = comp {
+            //an anchored path is only searched from its directory on
+            if index == 1 {
+                if let Some(anchor) = anchorinode(f) {
+                    inodeno = anchor;
+                    continue;
                 }
-                match &*curnode.unwrap() {
-                    Inode::Dir(d) => {
-                        previnodeno = inodeno;
+            }
+            let inode = match FS_METADATA.inodetable.get(&inodeno) {
+                Some(inode) => inode,
+                None => return true,
+            };
+            let child = match &*inode {
+                Inode::Dir(d) => {
+                    if !permission_granted(d.mode, d.uid, d.gid, uid, gid, X_OK) {
+                        return false;
+                    }
+                    d.filename_to_inode_dict
+                        .get(f.to_str().unwrap())
+                        .map(|num| *num)
+                }
+                _ => return true,
+            };
+            drop(inode);
+            match child {
+                Some(child) => inodeno = child,
+                None => return true,
+            }
+        }
+    }
+    true
+}
 
-                        //populate child inode number from parent directory's inode dict
-                        inodeno = match d
-                            .filename_to_inode_dict
-                            .get(&f.to_str().unwrap().to_string())
-                        {
-                            Some(num) => {
-                                curnode = FS_METADATA.inodetable.get(&num);
-                                Some(*num)
-                            }
+/// This function walks the file tree for an absolute, normalized path,
+/// following any symbolic links it encounters along the way. Here's how it
+/// operates:
+///
+/// - Starts at the root directory and looks up each path component in the
+///   `filename_to_inode_dict` of the directory walked so far.
+///
+/// - When a component names a symbolic link, the link's target is spliced into
+///   the path in place of the component (relative targets are interpreted with
+///   respect to the directory containing the link), the resulting path is
+///   normalized, and the walk restarts from the root. The final component is
+///   only followed if `follow_final` is set, which lets callers such as
+///   `lstat_syscall` and `unlink_syscall` operate on the link itself.
+///
+/// - After `MAXSYMLINKS` links have been followed, the walk gives up and
+///   returns `ELOOP`.
+///
+/// On success a tuple consisting of the inode number of the file (if it
+/// exists), the inode number of its parent (if it exists), and the path with
+/// all followed links resolved is returned.
+pub fn metawalkandparent_resolve(
+    path: &interface::RustPath,
+    follow_final: bool,
+) -> Result<(Option<usize>, Option<usize>, interface::RustPathBuf), Errno> {
+    let mut curpath = path.to_path_buf();
+    let mut linkhops = 0;
 
-                            //if no such child exists, update curnode, inodeno accordingly so that
-                            //we can check against none as we do at the beginning of the Normal
-                            // match arm
-                            None => {
-                                curnode = None;
-                                None
+    'restart: loop {
+        let components: Vec<interface::RustPathComponent> = curpath.components().collect();
+        let mut inodeno = Some(ROOTDIRECTORYINODE);
+        let mut previnodeno = None;
+        //the path of the directory we have walked to so far, used to anchor relative
+        // link targets
+        let mut walkedpath = interface::RustPathBuf::from("/");
+
+        //Iterate over the components of the pathbuf in order to walk the file tree
+        for (index, comp) in components.iter().enumerate() {
+            match comp {
+                //We've already done what initialization needs to be done
+                interface::RustPathComponent::RootDir => {}
+
+                interface::RustPathComponent::Normal(f) => {
+                    //A path made by `anchorpath` starts from its directory instead
+                    if index == 1 {
+                        if let Some(anchor) = anchorinode(f) {
+                            if !matches!(
+                                FS_METADATA.inodetable.get(&anchor).as_deref(),
+                                Some(Inode::Dir(_))
+                            ) {
+                                return Ok((None, None, curpath));
+                            }
+                            if dir_is_linked(anchor) {
+                                previnodeno = dirparent(anchor);
+                            } else if index < components.len() - 1 {
+                                //a removed directory is empty, and nothing can be
+                                // created in it either
+                                return Ok((None, None, curpath));
                             }
+                            inodeno = Some(anchor);
+                            walkedpath.push(f);
+                            continue;
                         }
                     }
-                    //if we're trying to get a child of a non-directory inode, exit out
-                    _ => {
-                        return (None, None);
+
+                    //If we're trying to get the child of a nonexistent directory, exit out
+                    let curinodeno = match inodeno {
+                        Some(curinodeno) => curinodeno,
+                        None => return Ok((None, None, curpath)),
+                    };
+
+                    //populate child inode number from parent directory's inode dict
+                    let childinodeno = match &*FS_METADATA.inodetable.get(&curinodeno).unwrap() {
+                        Inode::Dir(d) => d
+                            .filename_to_inode_dict
+                            .get(f.to_str().unwrap())
+                            .map(|num| *num),
+                        //if we're trying to get a child of a non-directory inode, exit out
+                        _ => return Ok((None, None, curpath)),
+                    };
+                    previnodeno = inodeno;
+                    inodeno = childinodeno;
+
+                    //Follow the child if it is a symbolic link, unless it is the last component
+                    // and the caller asked for the link itself
+                    let is_final = index == components.len() - 1;
+                    if let Some(childinodeno) = childinodeno {
+                        if !is_final || follow_final {
+                            if let Inode::Symlink(ref link) =
+                                *FS_METADATA.inodetable.get(&childinodeno).unwrap()
+                            {
+                                linkhops += 1;
+                                if linkhops > MAXSYMLINKS {
+                                    return Err(Errno::ELOOP);
+                                }
+
+                                //joining an absolute target replaces the walked path
+                                // entirely, while a relative one is appended to it
+                                let mut linkedpath = walkedpath.join(&link.target);
+                                for rest in &components[index + 1..] {
+                                    linkedpath.push(rest);
+                                }
+                                curpath =
+                                    normpath_onto(interface::RustPathBuf::from("/"), linkedpath);
+                                continue 'restart;
+                            }
+                        }
                     }
+                    walkedpath.push(f);
                 }
-            }
 
-            //If it's a component of the pathbuf that we don't expect given a normed path, exit out
-            _ => {
-                return (None, None);
+                //If it's a component of the pathbuf that we don't expect given a normed path,
+                // exit out
+                _ => {
+                    return Ok((None, None, curpath));
+                }
             }
         }
+        //return inode number and it's parent's number
+        return Ok((inodeno, previnodeno, curpath));
+    }
+}
+
+//returns tuple consisting of inode number of file (if it exists), and inode
+// number of parent (if it exists). Symbolic links are followed for every
+// component except the last one, so the returned inode may be a link itself
+pub fn metawalkandparent(path: &interface::RustPath) -> (Option<usize>, Option<usize>) {
+    match metawalkandparent_resolve(path, false) {
+        Ok((inodeno, previnodeno, _)) => (inodeno, previnodeno),
+        Err(_) => (None, None),
     }
-    //return inode number and it's parent's number
-    (inodeno, previnodeno)
 }
+
+//returns the inode number of the file (if it exists), following symbolic links
+// for every component including the last one
 pub fn metawalk(path: &interface::RustPath) -> Option<usize> {
-    metawalkandparent(path).0
+    match metawalkandparent_resolve(path, true) {
+        Ok((inodeno, ..)) => inodeno,
+        Err(_) => None,
+    }
 }
+
+//returns the path with any symbolic links in it resolved, or ELOOP if too many
+// links had to be followed to do so
+pub fn resolvepath(
+    path: &interface::RustPath,
+    follow_final: bool,
+) -> Result<interface::RustPathBuf, Errno> {
+    metawalkandparent_resolve(path, follow_final).map(|(_, _, resolved)| resolved)
+}
+
 pub fn normpath(origp: interface::RustPathBuf, cage: &Cage) -> interface::RustPathBuf {
     //If path is relative, prefix it with the current working directory, otherwise
     // populate it with rootdir
-    let mut newp = if origp.is_relative() {
+    let newp = if origp.is_relative() {
         (**cage.cwd.read()).clone()
     } else {
         interface::RustPathBuf::from("/")
     };
 
+    normpath_onto(newp, origp)
+}
+
+//normalizes origp relative to the directory `dirinodenum` rather than the
+// current working directory, giving a path made by `anchorpath` unless origp
+// is absolute
+pub fn normpath_at(dirinodenum: usize, origp: interface::RustPathBuf) -> interface::RustPathBuf {
+    if origp.is_absolute() || dirinodenum == ROOTDIRECTORYINODE {
+        return normpath_onto(interface::RustPathBuf::from("/"), origp);
+    }
+    normpath_onto(anchorpath(dirinodenum), origp)
+}
+
+//lexically normalizes origp onto the absolute path newp, resolving . and ..
+// components without consulting the file tree, except to go up from the
+// directory of an anchored path
+fn normpath_onto(
+    mut newp: interface::RustPathBuf,
+    origp: interface::RustPathBuf,
+) -> interface::RustPathBuf {
     for comp in origp.components() {
         match comp {
             //if we have a normal path component, push it on to our normed path
@@ -604,7 +1954,19 @@ pub fn normpath(origp: interface::RustPathBuf, cage: &Cage) -> interface::RustPa
 
             //if we have a .. path component, pop the last component off our normed path
             interface::RustPathComponent::ParentDir => {
-                newp.pop();
+                //going up from the directory of an anchored path leads to the
+                // directory above it, which is anchored in turn unless it is the root
+                match path_anchor(&newp) {
+                    Some(anchor) if newp.components().count() == 2 => {
+                        newp = match dirparent(anchor) {
+                            Some(parent) if parent != ROOTDIRECTORYINODE => anchorpath(parent),
+                            _ => interface::RustPathBuf::from("/"),
+                        };
+                    }
+                    _ => {
+                        newp.pop();
+                    }
+                }
             }
 
             //if we have a . path component (Or a root dir or a prefix(?)) do nothing
@@ -629,7 +1991,7 @@ pub fn remove_domain_sock(truepath: interface::RustPathBuf) {
         (Some(inodenum), Some(parentinodenum)) => {
             Cage::remove_from_parent_dir(parentinodenum, &truepath);
 
-            FS_METADATA.inodetable.remove(&inodenum);
+            FS_METADATA.remove_inode(inodenum);
             NET_METADATA.domsock_paths.remove(&truepath);
         }
     }
@@ -42,6 +42,12 @@ pub static NET_METADATA: interface::RustLazyGlobal<interface::RustRfc<NetMetadat
                                                                   * connection process */
             domsock_paths: interface::RustHashSet::new(), /* set of all currently bound domain
                                                            * sockets */
+            domsock_dgram_table: interface::RustHashMap::new(), /* receive queues of bound
+                                                                 * datagram sockets */
+            domsock_abstract_table: interface::RustHashMap::new(), /* abstract addresses
+                                                                    * in use */
+            domsock_accept_waiters: interface::RustHashMap::new(), /* epoll instances
+                                                                    * watching listeners */
         })
     }); //we want to check if fs exists before doing a blank init, but not for now
 
@@ -108,14 +114,72 @@ pub fn mux_port(
 }
 
 //A substructure for information only populated in a unix domain socket
+//Each pipe has a control queue next to it holding the ancillary data sent
+// through it with sendmsg. Datagram and seqpacket sockets use message pipes
+// instead, where the ancillary data travels inside each message. A datagram
+// socket's receive message pipe is its own queue, and its send message pipe the
+// queue of the socket it is connected to. A socket bound to an abstract address
+// has no inode, and instead holds on to its name
 #[derive(Debug)]
 pub struct UnixSocketInfo {
     pub mode: i32,
     pub sendpipe: Option<interface::RustRfc<interface::EmulatedPipe>>,
     pub receivepipe: Option<interface::RustRfc<interface::EmulatedPipe>>,
-    pub inode: usize,
+    pub sendcontrol: Option<ControlQueue>,
+    pub receivecontrol: Option<ControlQueue>,
+    pub sendmsgpipe: Option<UnixMsgPipe>,
+    pub receivemsgpipe: Option<UnixMsgPipe>,
+    pub inode: Option<usize>,
+    pub _abstract_name: Option<UnixAbstractName>,
 }
 
+//An abstract address in use by the unix domain socket that bound it. Abstract
+// addresses don't exist in the filesystem, so rather than being unlinked the
+// name is given back when the socket is gone, that is when the last cage has
+// closed it
+#[derive(Debug)]
+pub struct UnixAbstractName {
+    pub name: Vec<u8>,
+}
+
+impl Drop for UnixAbstractName {
+    fn drop(&mut self) {
+        NET_METADATA.domsock_abstract_table.remove(&self.name);
+    }
+}
+
+//Ancillary data sent with sendmsg over a unix domain socket. It belongs to the
+// bytes sent in the same call, and is handed to whichever recvmsg reads the
+// first of them. File descriptors in flight hold a reference to what they
+// describe just like a dup'd descriptor would, until they are either installed
+// in the receiving cage or discarded
+#[derive(Debug)]
+pub struct UnixControlMsg {
+    pub offset: u64, // position in the stream of the first byte sent with it
+    pub rights: Vec<FileDescriptor>,
+    pub sendcreds: bool, // whether the sender asked for SCM_CREDENTIALS
+    pub pid: i32,
+    pub uid: u32,
+    pub gid: u32,
+}
+
+pub type ControlQueue = interface::RustRfc<interface::Mutex<interface::RustDeque<UnixControlMsg>>>;
+
+pub fn new_control_queue() -> ControlQueue {
+    interface::RustRfc::new(interface::Mutex::new(interface::RustDeque::new()))
+}
+
+//What goes along with the bytes of a message over a unix datagram or seqpacket
+// socket: the address of the datagram socket that sent it and any ancillary
+// data sent with sendmsg
+#[derive(Debug)]
+pub struct UnixDatagram {
+    pub source: Option<interface::GenSockaddr>,
+    pub control: Option<UnixControlMsg>,
+}
+
+pub type UnixMsgPipe = interface::RustRfc<interface::EmulatedMsgPipe<UnixDatagram>>;
+
 //This structure contains all socket-associated data that is not held in the fd
 #[derive(Debug)]
 pub struct SocketHandle {
@@ -144,6 +208,14 @@ pub struct SocketHandle {
 // shut down the inner socket, which is what we could have done manually in
 // close instead. This should be both cleaner and faster, because we don't have
 // to wait for the recv timeout like we do in shutdown
+impl SocketHandle {
+    //Whether this is a unix datagram or seqpacket socket, which keeps message
+    // boundaries and so is built on message pipes rather than on pipes
+    pub fn is_unix_msg(&self) -> bool {
+        self.domain == AF_UNIX && self.socktype & 0x7 != SOCK_STREAM
+    }
+}
+
 impl Drop for SocketHandle {
     fn drop(&mut self) {
         Cage::_cleanup_socket_inner_helper(self, -1, false);
@@ -181,10 +253,16 @@ impl ConnCondVar {
     }
 }
 
+//A pending connection to a unix domain socket. Stream sockets pass pipes and
+// control queues, seqpacket sockets message pipes
 pub struct DomsockTableEntry {
     pub sockaddr: interface::GenSockaddr,
-    pub receive_pipe: interface::RustRfc<interface::EmulatedPipe>,
-    pub send_pipe: interface::RustRfc<interface::EmulatedPipe>,
+    pub receive_pipe: Option<interface::RustRfc<interface::EmulatedPipe>>,
+    pub send_pipe: Option<interface::RustRfc<interface::EmulatedPipe>>,
+    pub receive_control: Option<ControlQueue>,
+    pub send_control: Option<ControlQueue>,
+    pub receive_msgpipe: Option<UnixMsgPipe>,
+    pub send_msgpipe: Option<UnixMsgPipe>,
     pub cond_var: Option<interface::RustRfc<ConnCondVar>>,
 }
 
@@ -195,11 +273,23 @@ impl DomsockTableEntry {
     pub fn get_sockaddr(&self) -> &interface::GenSockaddr {
         &self.sockaddr
     }
-    pub fn get_send_pipe(&self) -> &interface::RustRfc<interface::EmulatedPipe> {
-        &self.send_pipe
+    pub fn get_send_pipe(&self) -> Option<&interface::RustRfc<interface::EmulatedPipe>> {
+        self.send_pipe.as_ref()
+    }
+    pub fn get_receive_pipe(&self) -> Option<&interface::RustRfc<interface::EmulatedPipe>> {
+        self.receive_pipe.as_ref()
+    }
+    pub fn get_send_control(&self) -> Option<&ControlQueue> {
+        self.send_control.as_ref()
     }
-    pub fn get_receive_pipe(&self) -> &interface::RustRfc<interface::EmulatedPipe> {
-        &self.receive_pipe
+    pub fn get_receive_control(&self) -> Option<&ControlQueue> {
+        self.receive_control.as_ref()
+    }
+    pub fn get_send_msgpipe(&self) -> Option<&UnixMsgPipe> {
+        self.send_msgpipe.as_ref()
+    }
+    pub fn get_receive_msgpipe(&self) -> Option<&UnixMsgPipe> {
+        self.receive_msgpipe.as_ref()
     }
 }
 
@@ -216,6 +306,19 @@ pub struct NetMetadata {
     >,
     pub domsock_accept_table: interface::RustHashMap<interface::RustPathBuf, DomsockTableEntry>,
     pub domsock_paths: interface::RustHashSet<interface::RustPathBuf>,
+    pub domsock_dgram_table: interface::RustHashMap<usize, UnixMsgPipe>, /* maps the inode of a
+                                                                          * bound datagram
+                                                                          * socket to its
+                                                                          * receive queue */
+    //maps an abstract name in use to the receive queue of the socket bound to it,
+    // if that is a datagram socket
+    pub domsock_abstract_table: interface::RustHashMap<Vec<u8>, Option<UnixMsgPipe>>,
+    //maps the path of a listening domain socket to the wait queue of the epoll
+    // instances watching it, which are woken once a connection is pending
+    pub domsock_accept_waiters: interface::RustHashMap<
+        interface::RustPathBuf,
+        interface::RustRfc<interface::EpollWaitQueue>,
+    >,
 }
 
 impl NetMetadata {
@@ -23,7 +23,10 @@
 //! - [link_syscall](crate::safeposix::cage::Cage::link_syscall)
 //! - [unlink_syscall](crate::safeposix::cage::Cage::unlink_syscall)
 //! - [creat_syscall](crate::safeposix::cage::Cage::creat_syscall)
+//! - [symlink_syscall](crate::safeposix::cage::Cage::symlink_syscall)
+//! - [readlink_syscall](crate::safeposix::cage::Cage::readlink_syscall)
 //! - [stat_syscall](crate::safeposix::cage::Cage::stat_syscall)
+//! - [lstat_syscall](crate::safeposix::cage::Cage::lstat_syscall)
 //! - [fstat_syscall](crate::safeposix::cage::Cage::fstat_syscall)
 //! - [statfs_syscall](crate::safeposix::cage::Cage::statfs_syscall)
 //! - [fstatfs_syscall](crate::safeposix::cage::Cage::fstatfs_syscall)
@@ -48,6 +51,12 @@
 //! - [_chmod_helper](crate::safeposix::cage::Cage::_chmod_helper)
 //! - [chmod_syscall](crate::safeposix::cage::Cage::chmod_syscall)
 //! - [fchmod_syscall](crate::safeposix::cage::Cage::fchmod_syscall)
+//! - [_chown_helper](crate::safeposix::cage::Cage::_chown_helper)
+//! - [chown_syscall](crate::safeposix::cage::Cage::chown_syscall)
+//! - [lchown_syscall](crate::safeposix::cage::Cage::lchown_syscall)
+//! - [fchown_syscall](crate::safeposix::cage::Cage::fchown_syscall)
+//! - [utimensat_syscall](crate::safeposix::cage::Cage::utimensat_syscall)
+//! - [futimens_syscall](crate::safeposix::cage::Cage::futimens_syscall)
 //! - [mmap_syscall](crate::safeposix::cage::Cage::mmap_syscall)
 //! - [munmap_syscall](crate::safeposix::cage::Cage::munmap_syscall)
 //! - [flock_syscall](crate::safeposix::cage::Cage::flock_syscall)
@@ -101,6 +110,15 @@ use crate::safeposix::filesystem::*;
 use crate::safeposix::net::NET_METADATA;
 use crate::safeposix::shm::*;
 
+// What kind of descriptor splice or tee was given, along with the pipe and the
+// flags of a pipe descriptor
+enum SpliceEnd {
+    Pipe(interface::RustRfc<interface::EmulatedPipe>, i32),
+    File,
+    Stream,
+    Socket,
+}
+
 impl Cage {
     /// ## ------------------OPEN SYSCALL------------------
     /// ### Description
@@ -139,6 +157,7 @@ impl Cage {
     /// ### Errors
     ///
     /// * ENFILE - no available file descriptor number could be found
+    /// * EMFILE - the soft RLIMIT_NOFILE of the cage has been reached
     /// * ENOENT - tried to open a file that did not exist
     /// * EINVAL - the input flags contain S_IFCHR flag representing a special
     ///   character file
@@ -147,7 +166,15 @@ impl Cage {
     ///   directory
     /// * EEXIST - the file already exists and O_CREAT and O_EXCL flags were
     ///   passed
-    /// * ENXIO - the file is of type UNIX domain socket
+    /// * ENXIO - the file is of type UNIX domain socket, or it is a FIFO
+    ///   opened with O_WRONLY | O_NONBLOCK which nobody has open for reading
+    /// * EINTR - a signal arrived while waiting for the other end of a FIFO to
+    ///   be opened
+    /// * ELOOP - too many symbolic links were encountered in resolving the
+    ///   path, or O_NOFOLLOW was passed and the file is a symbolic link
+    /// * EACCES - a directory in the path may not be searched, the file may not
+    ///   be accessed as requested by flags, or the file does not exist and the
+    ///   parent directory may not be written to
     ///
     /// ### Panics
     ///
@@ -172,7 +199,9 @@ impl Cage {
         // that the file descriptor should be automatically closed during an exec family
         // function. It’s needed for managing file descriptors across different
         // processes, ensuring that they do not unintentionally remain open.
-        let allowmask = O_RDWRFLAGS | O_CLOEXEC;
+        // O_APPEND is kept as well, for the calls that can't append to refuse
+        // the descriptor.
+        let allowmask = O_RDWRFLAGS | O_CLOEXEC | O_APPEND;
         FileDesc {
             position: position,
             inode: inodenum,
@@ -181,6 +210,60 @@ impl Cage {
         }
     }
 
+    // Waits until the given end of a FIFO has been opened more than `opened`
+    // times, which is how an open of the other end learns that it has company.
+    // Counting opens rather than looking at the ends that are open means that an
+    // end being opened and closed again right away is not missed.
+    fn _fifo_wait(&self, inodenum: usize, end: i32, opened: u64) -> i32 {
+        loop {
+            if let Inode::Fifo(ref f) = *FS_METADATA.inodetable.get(&inodenum).unwrap() {
+                let nowopened = if end == O_RDONLY {
+                    f.readers_opened
+                } else {
+                    f.writers_opened
+                };
+                if nowopened != opened {
+                    return 0;
+                }
+            }
+            if self
+                .cancelstatus
+                .load(interface::RustAtomicOrdering::Relaxed)
+            {
+                loop {
+                    interface::cancelpoint(self.cageid);
+                }
+            }
+            if interface::sigcheck() {
+                return syscall_error(
+                    Errno::EINTR,
+                    "open",
+                    "interrupted while waiting for the other end of the FIFO to be opened",
+                );
+            }
+            interface::sleep(interface::RustDuration::from_millis(1));
+        }
+    }
+
+    // Lets go of an end of the pipe of a FIFO, as opened with the given flags.
+    // Once neither end is open the pipe is dropped along with anything left in
+    // it, and a FIFO which has been unlinked is removed once nothing refers to it.
+    fn _release_fifo(inodenum: usize, pipe: &interface::EmulatedPipe, flags: i32) {
+        let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+        if let Inode::Fifo(ref mut fifo_inode_obj) = *inodeobj {
+            pipe.decr_ref(flags);
+            fifo_inode_obj.refcount -= 1;
+            if pipe.is_pipe_closed() {
+                fifo_inode_obj.pipe = None;
+            }
+            if fifo_inode_obj.refcount == 0 && fifo_inode_obj.linkcount == 0 {
+                drop(inodeobj);
+                FS_METADATA.remove_inode(inodenum);
+                log_metadata(&FS_METADATA, inodenum);
+            }
+        }
+    }
+
     pub fn open_syscall(&self, path: &str, flags: i32, mode: u32) -> i32 {
         // Check that the given input path is not empty
         if path.len() == 0 {
@@ -192,17 +275,36 @@ impl Cage {
         // subdirectories and creating a new file or open existing file at the given
         // location.
         let truepath = normpath(convpath(path), self);
+
+        // Resolve any symbolic links in the path. The final component is followed
+        // unless O_NOFOLLOW was passed, or O_CREAT and O_EXCL were passed together, in
+        // which case a symbolic link there must be reported rather than opened
+        let follow_final =
+            0 == (flags & O_NOFOLLOW) && (O_CREAT | O_EXCL) != (flags & (O_CREAT | O_EXCL));
+        let truepath = match resolvepath(truepath.as_path(), follow_final) {
+            Ok(resolved) => resolved,
+            Err(_) => {
+                return syscall_error(
+                    Errno::ELOOP,
+                    "open",
+                    "too many symbolic links were encountered in resolving pathname",
+                );
+            }
+        };
+
+        // Every directory leading to the file has to be searchable by the caller
+        let searchcheck = self._search_check("open", &truepath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
     
         // Fetch the next file descriptor and its lock write guard to ensure the file
         // can be associated with the file descriptor
         let (fd, guardopt) = self.get_next_fd(None);
         if fd < 0 {
-            // Handle case where no valid file descriptor could be found
-            return syscall_error(
-                Errno::ENFILE,
-                "open",
-                "no available file descriptor number could be found",
-            );
+            // Handle case where no valid file descriptor could be found, which is
+            // either ENFILE or EMFILE
+            return fd;
         }
     
         // When the file descriptor is valid, we proceed with performing the remaining
@@ -239,20 +341,33 @@ impl Cage {
                 if mode & (S_IRWXA | S_FILETYPEFLAGS as u32) != mode {
                     return syscall_error(Errno::EPERM, "open", "Mode bits were not sane");
                 }
+
+                // Creating a file adds an entry to the parent directory, which requires
+                // write and search permission on it
+                let parentcheck = self._access_check("open", pardirinode, W_OK | X_OK);
+                if parentcheck != 0 {
+                    return parentcheck;
+                }
     
                 let filename = truepath.file_name().unwrap().to_str().unwrap().to_string(); //for now we assume this is sane, but maybe this should be checked later
-                let time = interface::timestamp(); //We do a real timestamp now
+                let time = interface::timestamp_nanos(); //We do a real timestamp now
     
                 // S_IFREG is the flag for a regular file, so it's added to the mode to
                 // indicate that the new file being created is a regular file.
                 let effective_mode = S_IFREG as u32 | mode;
     
                 // Create a new inode of type "File" representing a file and set the
-                // required attributes
+                // required attributes. The file is owned by the caller's effective user
+                // and group, which is charged for the new inode.
+                let (euid, egid) = self.get_effective_ids();
+                let reserved = self._reserve_inode(euid, "open");
+                if reserved != 0 {
+                    return reserved;
+                }
                 let newinode = Inode::File(GenericInode {
                     size: 0,
-                    uid: DEFAULT_UID,
-                    gid: DEFAULT_GID,
+                    uid: euid,
+                    gid: egid,
                     mode: effective_mode,
                     linkcount: 1, /* because when a new file is created, it has a single
                                    * hard link, which is the directory entry that points
@@ -280,6 +395,7 @@ impl Cage {
                     ind.ctime = time; // Here, update the ctime and mtime for the parent directory as well
                     ind.mtime = time;
                 } else {
+                    FS_METADATA.release_space(euid, 0, 1);
                     return syscall_error(
                         Errno::ENOTDIR,
                         "open",
@@ -289,8 +405,7 @@ impl Cage {
                 // Update the inode table by inserting the newly formed inode mapped with
                 // its inode number.
                 FS_METADATA.inodetable.insert(newinodenum, newinode);
-                log_metadata(&FS_METADATA, pardirinode);
-                log_metadata(&FS_METADATA, newinodenum);
+                log_metadata_group(&FS_METADATA, &[pardirinode, newinodenum]);
     
                 // FileObjectTable stores the entries of the currently opened files in the
                 // system Since, a new file is being opened here, an
@@ -300,7 +415,11 @@ impl Cage {
                 // Value - Opened file with its size as 0
                 if let interface::RustHashEntry::Vacant(vac) = FILEOBJECTTABLE.entry(newinodenum) {
                     let sysfilename = format!("{}{}", FILEDATAPREFIX, newinodenum);
-                    vac.insert(interface::openfile(sysfilename, 0).unwrap());
+                    vac.insert(
+                        interface::storage_backend()
+                            .open_data(&sysfilename, 0)
+                            .unwrap(),
+                    );
                     // new file of size 0
                 }
     
@@ -326,6 +445,21 @@ impl Cage {
                     );
                 }
                 let size;
+
+                // The caller needs read permission to open for reading and write
+                // permission to open for writing or to truncate the file. Sockets and
+                // symbolic links are refused below regardless of their mode.
+                let mut amode = 0;
+                if !is_wronly(flags) {
+                    amode |= R_OK;
+                }
+                if !is_rdonly(flags) || (flags & O_TRUNC) != 0 {
+                    amode |= W_OK;
+                }
+                let accesscheck = self._access_check("open", inodenum, amode);
+                if accesscheck != 0 {
+                    return accesscheck;
+                }
     
                 // Fetch the Inode Object associated with the inode number of the existing
                 // file. There are different Inode types supported
@@ -345,10 +479,12 @@ impl Cage {
                                 occ.get().close().unwrap();
                             }
     
+                            // The data is dropped, so the space it took up is freed
+                            FS_METADATA.release_space(f.uid, f.size as u64, 0);
                             f.size = 0;
     
                             // Update the timestamps as well
-                            let latest_time = interface::timestamp();
+                            let latest_time = interface::timestamp_nanos();
                             f.ctime = latest_time;
                             f.mtime = latest_time;
     
@@ -359,7 +495,9 @@ impl Cage {
     
                             // The current file is removed from the filesystem
                             let sysfilename = format!("{}{}", FILEDATAPREFIX, inodenum);
-                            interface::removefile(sysfilename.clone()).unwrap();
+                            interface::storage_backend()
+                                .remove_data(&sysfilename)
+                                .unwrap();
                         }
     
                         // Once the metadata for the file is reset, a new file is inserted
@@ -369,7 +507,11 @@ impl Cage {
                         // state.
                         if let interface::RustHashEntry::Vacant(vac) = FILEOBJECTTABLE.entry(inodenum) {
                             let sysfilename = format!("{}{}", FILEDATAPREFIX, inodenum);
-                            vac.insert(interface::openfile(sysfilename, f.size).unwrap());
+                            vac.insert(
+                                interface::storage_backend()
+                                    .open_data(&sysfilename, f.size)
+                                    .unwrap(),
+                            );
                         }
     
                         // Update the final size and reference count for the file
@@ -401,6 +543,80 @@ impl Cage {
                     Inode::Socket(_) => {
                         return syscall_error(Errno::ENXIO, "open", "file is a UNIX domain socket");
                     }
+
+                    // A symbolic link is only left unresolved here when O_NOFOLLOW was
+                    // passed, in which case opening it fails
+                    Inode::Symlink(_) => {
+                        return syscall_error(
+                            Errno::ELOOP,
+                            "open",
+                            "O_NOFOLLOW was specified and the file is a symbolic link",
+                        );
+                    }
+
+                    // A FIFO is opened as an end of the pipe it holds, which is created
+                    // by whichever open comes first. Unless O_NONBLOCK was passed,
+                    // opening only one of its ends waits for the other end to be opened
+                    // as well, which is done once the inode has been let go of.
+                    Inode::Fifo(ref mut f) => {
+                        let nonblocking = 0 != (flags & O_NONBLOCK);
+                        if is_wronly(flags)
+                            && nonblocking
+                            && f.pipe.as_ref().is_none_or(|pipe| pipe.is_read_closed())
+                        {
+                            return syscall_error(
+                                Errno::ENXIO,
+                                "open",
+                                "O_NONBLOCK was specified for writing and the FIFO has no readers",
+                            );
+                        }
+                        let pipe = f
+                            .pipe
+                            .get_or_insert_with(|| {
+                                interface::RustRfc::new(
+                                    interface::EmulatedPipe::new_unopened_with_capacity(
+                                        PIPE_CAPACITY,
+                                    ),
+                                )
+                            })
+                            .clone();
+                        pipe.incr_ref(flags);
+                        f.refcount += 1;
+                        if !is_wronly(flags) {
+                            f.readers_opened += 1;
+                        }
+                        if !is_rdonly(flags) {
+                            f.writers_opened += 1;
+                        }
+
+                        // The end to wait for, along with how often it has been opened
+                        // so far. A FIFO opened for reading and writing never waits.
+                        let waitfor = if is_rdonly(flags) && !nonblocking && pipe.is_write_closed()
+                        {
+                            Some((O_WRONLY, f.writers_opened))
+                        } else if is_wronly(flags) && pipe.is_read_closed() {
+                            Some((O_RDONLY, f.readers_opened))
+                        } else {
+                            None
+                        };
+                        drop(inodeobj);
+
+                        if let Some((end, opened)) = waitfor {
+                            let waitresult = self._fifo_wait(inodenum, end, opened);
+                            if waitresult != 0 {
+                                Self::_release_fifo(inodenum, &pipe, flags);
+                                return waitresult;
+                            }
+                        }
+
+                        let _insertval = fdoption.insert(Pipe(PipeDesc {
+                            pipe,
+                            flags: flags & (O_RDWRFLAGS | O_NONBLOCK | O_CLOEXEC),
+                            advlock: interface::RustRfc::new(interface::AdvisoryLock::new()),
+                            fifoinode: Some(inodenum),
+                        }));
+                        return fd;
+                    }
                 }
     
                 // The file object of size 0, associated with the existing inode number is
@@ -433,6 +649,40 @@ impl Cage {
         // Return the valid file descriptor
         fd
     }
+
+    /// ## ------------------OPENAT SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `openat_syscall()` is like `open_syscall()`, except that a relative
+    /// `path` is interpreted relative to the directory referred to by `dirfd`
+    /// instead of the current working directory, see `_at_path_helper()`.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `openat_syscall()` receives four arguments:
+    /// * `dirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `path`, `flags`, `mode` - as for `open_syscall()`.
+    ///
+    /// ### Returns
+    ///
+    /// The new file descriptor, as for `open_syscall()`.
+    ///
+    /// ### Errors
+    ///
+    /// * `EBADF` - `path` is relative and `dirfd` is not a valid file
+    ///   descriptor.
+    /// * `ENOTDIR` - `path` is relative and `dirfd` does not refer to a
+    ///   directory.
+    /// * Any error of `open_syscall()`.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [openat(2)](https://man7.org/linux/man-pages/man2/openat.2.html)
+    pub fn openat_syscall(&self, dirfd: i32, path: &str, flags: i32, mode: u32) -> i32 {
+        match self._at_path_helper("openat", dirfd, path) {
+            Ok(truepath) => self.open_syscall(truepath.to_str().unwrap(), flags, mode),
+            Err(e) => e,
+        }
+    }
     
 
     /// ### Description
@@ -465,6 +715,8 @@ impl Cage {
     /// * EPERM - if mode bits were not set.
     /// * EEXIST - if a directory with the same name already exists at the given
     ///   path.
+    /// * EACCES - search permission is denied on a component of the path
+    ///   prefix, or write permission is denied on the parent directory.
     ///
     /// ### Panics
     ///
@@ -492,6 +744,12 @@ impl Cage {
         // subdirectories and establishing new directory at the given location.
         let truepath = normpath(convpath(path), self);
 
+        // Every directory leading to the new one has to be searchable by the caller
+        let searchcheck = self._search_check("mkdir", &truepath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
+
         // Walk through the absolute path which returns a tuple consisting of inode
         // number of file (if it exists), and inode number of parent (if it exists)
         match metawalkandparent(truepath.as_path()) {
@@ -508,22 +766,34 @@ impl Cage {
 
                 let effective_mode = S_IFDIR as u32 | mode;
                 // Check for the condition if the mode bits are correct and have the required
-                // permissions to create a directory
-                if mode & (S_IRWXA | S_FILETYPEFLAGS as u32) != mode {
+                // permissions to create a directory. The sticky bit may be given as well.
+                if mode & (S_IRWXA | S_ISVTX | S_FILETYPEFLAGS as u32) != mode {
                     return syscall_error(Errno::EPERM, "mkdir", "Mode bits were not sane");
                 }
 
+                // Creating a directory adds an entry to the parent directory, which
+                // requires write and search permission on it
+                let parentcheck = self._access_check("mkdir", pardirinode, W_OK | X_OK);
+                if parentcheck != 0 {
+                    return parentcheck;
+                }
+
                 // Fetch the next available inode number using the FileSystem MetaData table
                 // Create a new inode of type "Dir" representing a directory and set the
-                // required attributes
+                // required attributes. It is owned by, and charged to, the caller
+                let (euid, egid) = self.get_effective_ids();
+                let reserved = self._reserve_inode(euid, "mkdir");
+                if reserved != 0 {
+                    return reserved;
+                }
                 let newinodenum = FS_METADATA
                     .nextinode
                     .fetch_add(1, interface::RustAtomicOrdering::Relaxed); //fetch_add returns the previous value, which is the inode number we want
-                let time = interface::timestamp(); //We do a real timestamp now
+                let time = interface::timestamp_nanos(); //We do a real timestamp now
                 let newinode = Inode::Dir(DirectoryInode {
                     size: 0, //initial size of a directory is 0 as it is empty
-                    uid: DEFAULT_UID,
-                    gid: DEFAULT_GID,
+                    uid: euid,
+                    gid: egid,
                     mode: effective_mode,
                     linkcount: 3, /* because of the directory name(.), itself, and reference to
                                    * the parent directory(..) */
@@ -553,8 +823,7 @@ impl Cage {
                 // Update the inode table by inserting the newly formed inode mapped with its
                 // inode number.
                 metadata.inodetable.insert(newinodenum, newinode);
-                log_metadata(&metadata, pardirinode);
-                log_metadata(&metadata, newinodenum);
+                log_metadata_group(&metadata, &[pardirinode, newinodenum]);
 
                 // Return 0 when mkdir has succeeded
                 0
@@ -569,6 +838,37 @@ impl Cage {
         }
     }
 
+    /// ## ------------------MKDIRAT SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `mkdirat_syscall()` is like `mkdir_syscall()`, except that a
+    /// relative `path` is interpreted relative to the directory referred to by
+    /// `dirfd`, see `_at_path_helper()`.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `mkdirat_syscall()` receives three arguments:
+    /// * `dirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `path`, `mode` - as for `mkdir_syscall()`.
+    ///
+    /// ### Returns
+    ///
+    /// 0 on success, otherwise the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `EBADF`, `ENOTDIR` - see `_at_path_helper()`.
+    /// * Any error of `mkdir_syscall()`.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [mkdirat(2)](https://man7.org/linux/man-pages/man2/mkdirat.2.html)
+    pub fn mkdirat_syscall(&self, dirfd: i32, path: &str, mode: u32) -> i32 {
+        match self._at_path_helper("mkdirat", dirfd, path) {
+            Ok(truepath) => self.mkdir_syscall(truepath.to_str().unwrap(), mode),
+            Err(e) => e,
+        }
+    }
+
     /// ## ------------------MKNOD SYSCALL------------------
     /// ### Description
     ///
@@ -594,8 +894,8 @@ impl Cage {
     ///   the
     /// type of node to be created. It is a combination (using bitwise OR) of
     /// one of the file types and the permissions for the new node.
-    /// FileType - In LIND, we have only implemented the file type of "Character
-    /// Device" represented by S_IFCHR flag.
+    /// FileType - In LIND, we have only implemented the "Character Device" and
+    /// "FIFO" file types, represented by the S_IFCHR and S_IFIFO flags.
     /// FilePermission - The general permission mode used is "S_IRWXA": which
     /// represents the read, write, and search permissions on the new file.
     /// The final file mode is represented by the bitwise-OR of FileType and
@@ -605,7 +905,7 @@ impl Cage {
     ///   or
     /// block I/O device. If mode does not indicate a block special or character
     /// special device, dev is ignored.
-    /// Since "CharDev" is the only supported type, 'dev' is represented using
+    /// For a "CharDev", 'dev' is represented using
     /// makedev() function; that returns a formatted device number   
     /// For example: "makedev(&DevNo { major: majorId, minor: minorId })"
     /// accepts a Device Number that consists of a MajorID, identifying the
@@ -622,10 +922,11 @@ impl Cage {
     /// * `ENOENT` - occurs when a directory component in the absolute path does
     /// not exist
     /// * `EPERM` - the mode bits for the new file are not sane
-    /// * `EINVAL` - when any other file type (regular, socket, block, fifo)
-    ///   instead
-    /// of character file type is passed
+    /// * `EINVAL` - when any other file type (regular, socket, block) instead
+    /// of character file or FIFO type is passed
     /// * `EEXIST` - when the file to be created already exists
+    /// * `EACCES` - search permission is denied on a component of the path
+    ///   prefix, or write permission is denied on the parent directory
     ///
     /// ### Panics
     ///
@@ -647,6 +948,12 @@ impl Cage {
         // the metadata of a given inode from the Inode Table.
         let metadata = &FS_METADATA;
 
+        // Every directory leading to the new node has to be searchable by the caller
+        let searchcheck = self._search_check("mknod", &truepath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
+
         // Walk through the absolute path which returns a tuple consisting of inode
         // number of file (if it exists), and inode number of parent (if it exists)
         match metawalkandparent(truepath.as_path()) {
@@ -665,32 +972,61 @@ impl Cage {
                     return syscall_error(Errno::EPERM, "mknod", "Mode bits were not sane");
                 }
 
-                // As of now, the only file type in LIND supported by mknod_syscall
-                // is "Char Device" represented by S_IFCHR flag.
-                // In order to check for Char file type, a bitwise-AND operation for
-                // S_IFCHR flag is performed with the "mode" bits and an error is returned
-                // when the result is 0 denoting the support for only character files.
-                if mode as i32 & S_IFCHR == 0 {
+                // As of now, the only file types in LIND supported by mknod_syscall
+                // are "Char Device" and "FIFO" represented by the S_IFCHR and S_IFIFO
+                // flags, and an error is returned for any other file type.
+                if !is_chr(mode) && !is_fifo(mode) {
                     return syscall_error(
                         Errno::EINVAL,
                         "mknod",
-                        "only character files are supported",
+                        "only character files and FIFOs are supported",
                     );
                 }
-                // New Inode of type CharDev is created with file size 0
-                let time = interface::timestamp(); // We do a real timestamp now
-                let newinode = Inode::CharDev(DeviceInode {
-                    size: 0,
-                    uid: DEFAULT_UID,
-                    gid: DEFAULT_GID,
-                    mode: mode,
-                    linkcount: 1,
-                    refcount: 0,
-                    atime: time,
-                    ctime: time,
-                    mtime: time,
-                    dev: devtuple(dev),
-                });
+
+                // Creating a node adds an entry to the parent directory, which requires
+                // write and search permission on it
+                let parentcheck = self._access_check("mknod", pardirinode, W_OK | X_OK);
+                if parentcheck != 0 {
+                    return parentcheck;
+                }
+
+                // New Inode of type CharDev or Fifo is created with file size 0, owned
+                // by the caller. A FIFO only gets a pipe once it is opened.
+                let time = interface::timestamp_nanos(); // We do a real timestamp now
+                let (euid, egid) = self.get_effective_ids();
+                let reserved = self._reserve_inode(euid, "mknod");
+                if reserved != 0 {
+                    return reserved;
+                }
+                let newinode = if is_fifo(mode) {
+                    Inode::Fifo(FifoInode {
+                        size: 0,
+                        uid: euid,
+                        gid: egid,
+                        mode,
+                        linkcount: 1,
+                        refcount: 0,
+                        atime: time,
+                        ctime: time,
+                        mtime: time,
+                        pipe: None,
+                        readers_opened: 0,
+                        writers_opened: 0,
+                    })
+                } else {
+                    Inode::CharDev(DeviceInode {
+                        size: 0,
+                        uid: euid,
+                        gid: egid,
+                        mode: mode,
+                        linkcount: 1,
+                        refcount: 0,
+                        atime: time,
+                        ctime: time,
+                        mtime: time,
+                        dev: devtuple(dev),
+                    })
+                };
 
                 // fetch_add returns the previous value, which is the inode number we want
                 let newinodenum = FS_METADATA
@@ -717,8 +1053,7 @@ impl Cage {
                 // Update the inode table by inserting the newly formed inode mapped
                 // with its inode number.
                 metadata.inodetable.insert(newinodenum, newinode);
-                log_metadata(metadata, pardirinode);
-                log_metadata(metadata, newinodenum);
+                log_metadata_group(metadata, &[pardirinode, newinodenum]);
                 0 // mknod has succeeded
             }
 
@@ -738,6 +1073,38 @@ impl Cage {
         }
     }
 
+    /// ## ------------------MKFIFO SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `mkfifo_syscall()` creates a FIFO (named pipe) named by the path
+    /// given as the input parameter, in the same way as `mknod_syscall()` does
+    /// for the S_IFIFO file type. Opening the FIFO for reading and for writing
+    /// connects the two ends through a pipe, which is shared between cages.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `mkfifo_syscall()` receives two arguments:
+    /// * `path` - This argument points to a pathname naming the FIFO.
+    /// * `mode` - The permissions of the new FIFO.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful creation of the FIFO, 0 is returned.
+    ///
+    /// ### Errors
+    ///
+    /// The same as those of `mknod_syscall()`.
+    ///
+    /// ### Panics
+    ///
+    /// We don't have panics for mkfifo_syscall() as of now.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [mkfifo(3)](https://man7.org/linux/man-pages/man3/mkfifo.3.html)
+    pub fn mkfifo_syscall(&self, path: &str, mode: u32) -> i32 {
+        self.mknod_syscall(path, S_IFIFO as u32 | mode, 0)
+    }
+
     /// ## ------------------LINK SYSCALL------------------
     /// ### Description
     ///
@@ -774,6 +1141,8 @@ impl Cage {
     /// * `EPERM` - The file named by oldpath is a directory; current
     /// implementation probibits links to directories.
     /// * `EEXIST` - The link named by newpath already exists
+    /// * `EACCES` - A directory in either path prefix may not be searched, or
+    ///   the directory that would hold newpath may not be written to.
     ///
     /// ### Panics
     ///
@@ -798,20 +1167,28 @@ impl Cage {
         // while navigating through subdirectories.
         let trueoldpath = normpath(convpath(oldpath), self);
         let truenewpath = normpath(convpath(newpath), self);
-        //for now we assume this is sane, but maybe this should be checked later
-        let filename = truenewpath
-            .file_name()
-            .unwrap()
-            .to_str()
-            .unwrap()
-            .to_string();
 
-        // TODO BUG: Man-page contains a check for the directories in the path
-        // to have search/read permissions, which is not implemented in this syscall.
+        // Every directory leading to either path has to be searchable by the caller
+        let searchcheck = self._search_check("link", &trueoldpath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
+        let searchcheck = self._search_check("link", &truenewpath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
+        // and the directory that will hold the new link has to be writable
+        if let (_, Some(newparent)) = metawalkandparent(truenewpath.as_path()) {
+            let writecheck = self._access_check("link", newparent, W_OK | X_OK);
+            if writecheck != 0 {
+                return writecheck;
+            }
+        }
 
         // Walk through the absolute path for the oldpath file which returns the inode
-        // number of file (if it exists).
-        match metawalk(trueoldpath.as_path()) {
+        // number of file (if it exists). As on Linux, a symbolic link named by oldpath
+        // is not followed, so the new name refers to the link itself.
+        match metawalkandparent(trueoldpath.as_path()).0 {
             // Case: If the directory component doesn't exist, return an error.
             None => syscall_error(
                 Errno::ENOENT,
@@ -822,185 +1199,389 @@ impl Cage {
             // Case: Get the inode number and increment the link count of the existing
             // directory component i.e., (File, CharDev, and Socket).
             // "Directory" type is not supported for this implementation.
-            Some(inodenum) => {
-                // Get the mutable instance of the inode object from the FileMetaData table.
-                let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
-
-                // Match the inode object with the correct inode type and increment link count
-                match *inodeobj {
-                    // Directory type inode is not supported for linking, so return an error.
-                    Inode::Dir(_) => {
-                        return syscall_error(Errno::EPERM, "link", "oldpath is a directory")
-                    }
-
-                    Inode::File(ref mut normalfile_inode_obj) => {
-                        normalfile_inode_obj.linkcount += 1; //add link to
-                                                             // inode
-                    }
-
-                    Inode::CharDev(ref mut chardev_inode_obj) => {
-                        chardev_inode_obj.linkcount += 1; //add link to inode
-                    }
-
-                    // The Sockets only have an inode if they are a unix type
-                    // socket which has a corresponding inode. Regular sockets
-                    // do not have inodes.
-                    Inode::Socket(ref mut socket_inode_obj) => {
-                        socket_inode_obj.linkcount += 1; //add link to inode
-                    }
-                }
-
-                // the mutable reference to the inode has to be dropped because
-                //`log_metadata` will need to acquire an immutable reference to
-                // the same inode
-                drop(inodeobj);
-
-                // Walk the newpath and once the parent directory inode is found, insert a
-                // reference of this oldpath inode in the inode table
-                let retval = match metawalkandparent(truenewpath.as_path()) {
-                    // If both the file and the parent doesn't exist, newpath can't be created
-                    (None, None) => {
-                        syscall_error(Errno::ENOENT, "link", "newpath cannot be created")
-                    }
-
-                    // If the newpath exists, linking can't be perfomed and an error is returned.
-                    (Some(_), ..) => syscall_error(Errno::EEXIST, "link", "newpath already exists"),
-
-                    // If the parent directory inode exists, make a reference of the oldpath inode
-                    // in the parent directory to make a link between the two directory paths.
-                    (None, Some(pardirinode)) => {
-                        // Get the mutable instance of the parent inode object
-                        let mut parentinodeobj =
-                            FS_METADATA.inodetable.get_mut(&pardirinode).unwrap();
-                        //insert a reference to the inode in the parent directory
-                        if let Inode::Dir(ref mut parentdirinodeobj) = *parentinodeobj {
-                            parentdirinodeobj
-                                .filename_to_inode_dict
-                                .insert(filename, inodenum);
-                            // Increment the link count of the parent inode as well because
-                            // when a link is created, a new directory entry is added to
-                            // the parent directory of the new link.
-                            parentdirinodeobj.linkcount += 1;
-                            //drop the mutable instance of the parent inode object
-                            drop(parentinodeobj);
-                            log_metadata(&FS_METADATA, pardirinode);
-                            log_metadata(&FS_METADATA, inodenum);
-                        } else {
-                            // If the parent inode is not of type "Directory", panic occurs.
-                            panic!("Parent directory was not a directory!");
-                        }
-                        // If the linking is successful, 0 is returned.
-                        0
-                    }
-                };
-
-                // If the linking fails, an error with a value < 0 is returned from above.
-                // The following cases lead to the failing of the linking of files:
-                // 1. When both the file and the parent doesn't exist, newpath can't be created
-                // 2. When the the parent inode is not of type "Directory".
-                // 3. When the newpath already exists.
-                // So, we revert the link count updates made to the oldpath inode.
-                if retval != 0 {
-                    // Fetch the inode object from the FileMetadata Table
-                    let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
-
-                    // Match the relevant inode object type and decrement link count
-                    match *inodeobj {
-                        Inode::File(ref mut normalfile_inode_obj) => {
-                            normalfile_inode_obj.linkcount -= 1;
-                        }
-
-                        Inode::CharDev(ref mut chardev_inode_obj) => {
-                            chardev_inode_obj.linkcount -= 1;
-                        }
-
-                        Inode::Socket(ref mut socket_inode_obj) => {
-                            socket_inode_obj.linkcount -= 1;
-                        }
-
-                        Inode::Dir(_) => {
-                            panic!("Known non-directory file has been replaced with a directory!");
-                        }
-                    }
-                }
-
-                return retval;
-            }
+            Some(inodenum) => self._link_inode("link", inodenum, &truenewpath),
         }
     }
 
-    /// ## ------------------UNLINK SYSCALL------------------
+    /// ## ------------------LINKAT SYSCALL------------------
     /// ### Description
     ///
-    /// The `unlink_syscall()` removes a link to a file. It removes the link
-    /// named by the pathname pointed to by path and decrements the link
-    /// count of the file referenced by the link.
-    /// If that name was the last link to a file and no processes have the file
-    /// open, the file is deleted and the space it was using is made
-    /// available for reuse. If the name was the last link to a file but any
-    /// processes still have the file open, the file will remain in
-    /// existence until the last file descriptor referring to it is closed.
-    /// On successful completion, the timestamp for the parent directory is
-    /// updated along with its linkcounts.
+    /// The `linkat_syscall()` is like `link_syscall()`, except that relative
+    /// paths are interpreted relative to the directories referred to by
+    /// `olddirfd` and `newdirfd`, see `_at_path_helper()`.
     ///
     /// ### Function Arguments
     ///
-    /// The `unlink_syscall()` receives one argument:
-    /// * `path` - This argument points to a pathname which needs to be unlinked
+    /// The `linkat_syscall()` receives five arguments:
+    /// * `olddirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `oldpath` - the existing file.
+    /// * `newdirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `newpath` - the new link.
+    /// * `flags` - a combination of:
+    ///   * `AT_SYMLINK_FOLLOW` - a symbolic link named by `oldpath` is
+    ///     followed, linking the file it refers to instead of the link itself.
+    ///   * `AT_EMPTY_PATH` - an empty `oldpath` links the file `olddirfd`
+    ///     refers to, which is reserved to the superuser.
     ///
     /// ### Returns
     ///
-    /// Upon successful unlinking of the file, 0 is returned.
-    /// Otherwise, −1 is returned, and errno is set to indicate the error.
+    /// 0 on success, otherwise the error.
     ///
     /// ### Errors
     ///
-    /// * `ENOENT` - The path argument is a null pathname;
-    /// a component of path prefix does not exist; or the file
-    /// named by oldpath does not exist.
-    /// * `EISDIR` - When the unlinking is done on a directory
-    ///
-    /// ### Panics
-    ///
-    /// * If the parent inode does not exist in the inode table, causing
-    ///   unwrap() to panic.
+    /// * `EINVAL` - `flags` contains an unknown flag.
+    /// * `ENOENT` - `oldpath` is empty without `AT_EMPTY_PATH`, or with it but
+    ///   the caller is not the superuser, or the file `olddirfd` refers to has
+    ///   no links left.
+    /// * `EBADF`, `ENOTDIR` - see `_at_path_helper()`.
+    /// * `ELOOP` - too many symbolic links were encountered in resolving
+    ///   `oldpath`.
+    /// * Any error of `link_syscall()`.
     ///
     /// For more detailed description of all the commands and return values, see
-    /// [unlink(2)](https://man7.org/linux/man-pages/man2/unlink.2.html)
-    pub fn unlink_syscall(&self, path: &str) -> i32 {
-        // Return an error if the provided path is empty
-        if path.len() == 0 {
-            return syscall_error(Errno::ENOENT, "unlink", "given path was null");
+    /// [linkat(2)](https://man7.org/linux/man-pages/man2/linkat.2.html)
+    pub fn linkat_syscall(
+        &self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: i32,
+    ) -> i32 {
+        if flags & !(AT_SYMLINK_FOLLOW | AT_EMPTY_PATH) != 0 {
+            return syscall_error(Errno::EINVAL, "linkat", "invalid flags");
         }
-        // Retrieve the absolute path from the root directory for the given path.
-        // The absolute path is then used to validate directory paths while navigating
-        // through subdirectories.
-        let truepath = normpath(convpath(path), self);
-
-        // Walk through the absolute path which returns a tuple consisting of inode
-        // number of file (if it exists), and inode number of parent (if it exists)
-        match metawalkandparent(truepath.as_path()) {
-            // Return an error if the given file does not exist
-            (None, ..) => syscall_error(Errno::ENOENT, "unlink", "path does not exist"),
+        let truenewpath = match self._at_path_helper("linkat", newdirfd, newpath) {
+            Ok(truenewpath) => truenewpath,
+            Err(e) => return e,
+        };
 
-            // If the file exists but has no parent, it's the root directory
-            // No unlinking is done on the root, and an error is returned
-            (Some(_), None) => {
-                syscall_error(Errno::EISDIR, "unlink", "cannot unlink root directory")
+        if oldpath.is_empty() && flags & AT_EMPTY_PATH != 0 {
+            // as on Linux, linking a file by its descriptor takes privileges, as
+            // it could give a new name to a file that was opened through a path
+            // the caller may no longer search
+            if self.get_effective_ids().0 != ROOT_UID {
+                return syscall_error(
+                    Errno::ENOENT,
+                    "linkat",
+                    "only the superuser may link a file by its descriptor",
+                );
             }
-
-            // If both the file and the parent directory exists
-            (Some(inodenum), Some(parentinodenum)) => {
-                // Get the mutable instance of the file from the Inode table
-                let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
-
-                // For the inode object, we update 4 parameters:
-                // reference count: refers to the active file descriptors pointing to the
-                // file.
-                // link count: refers to the number of hard links pointing to the file.
-                // linkcount is decremented by 1 for all inode types except "Dir" type.
-                // file object: indicates whether the inode being unlinked has an associated
-                // file object. This is relevant for managing the physical deletion of the file
+            let inodenum = if olddirfd == AT_FDCWD {
+                metawalk(normpath(convpath("."), self).as_path())
+            } else {
+                let checkedfd = match self.get_filedescriptor(olddirfd) {
+                    Ok(checkedfd) => checkedfd,
+                    Err(_) => {
+                        return syscall_error(Errno::EBADF, "linkat", "invalid file descriptor")
+                    }
+                };
+                let unlocked_fd = checkedfd.read();
+                match &*unlocked_fd {
+                    Some(File(normalfile_filedesc_obj)) => Some(normalfile_filedesc_obj.inode),
+                    _ => {
+                        return syscall_error(
+                            Errno::EBADF,
+                            "linkat",
+                            "olddirfd does not refer to a file",
+                        )
+                    }
+                }
+            };
+            let inodenum = match inodenum {
+                Some(inodenum) => inodenum,
+                None => {
+                    return syscall_error(
+                        Errno::ENOENT,
+                        "linkat",
+                        "the current working directory does not exist",
+                    )
+                }
+            };
+            // a file that was unlinked while open can't be brought back
+            let unlinked = match *FS_METADATA.inodetable.get(&inodenum).unwrap() {
+                Inode::File(ref f) => f.linkcount == 0,
+                Inode::CharDev(ref f) => f.linkcount == 0,
+                _ => false,
+            };
+            if unlinked {
+                return syscall_error(Errno::ENOENT, "linkat", "the file has no links left");
+            }
+            let searchcheck = self._search_check("linkat", &truenewpath);
+            if searchcheck != 0 {
+                return searchcheck;
+            }
+            if let (_, Some(newparent)) = metawalkandparent(truenewpath.as_path()) {
+                let writecheck = self._access_check("linkat", newparent, W_OK | X_OK);
+                if writecheck != 0 {
+                    return writecheck;
+                }
+            }
+            return self._link_inode("linkat", inodenum, &truenewpath);
+        }
+
+        let mut trueoldpath = match self._at_path_helper("linkat", olddirfd, oldpath) {
+            Ok(trueoldpath) => trueoldpath,
+            Err(e) => return e,
+        };
+        if flags & AT_SYMLINK_FOLLOW != 0 {
+            trueoldpath = match resolvepath(trueoldpath.as_path(), true) {
+                Ok(resolved) => resolved,
+                Err(_) => {
+                    return syscall_error(
+                        Errno::ELOOP,
+                        "linkat",
+                        "too many symbolic links were encountered in resolving oldpath",
+                    )
+                }
+            };
+        }
+        self.link_syscall(trueoldpath.to_str().unwrap(), truenewpath.to_str().unwrap())
+    }
+
+    /// ### Description
+    ///
+    /// The `_link_inode()` is a helper function used by `link_syscall()` and
+    /// `linkat_syscall()` that adds the entry `truenewpath` for the existing
+    /// inode `inodenum`, incrementing its link count. The caller has already
+    /// checked that it may write to the directory that will hold the entry.
+    ///
+    /// ### Returns
+    ///
+    /// Zero if the link was created, otherwise the error below, in which case
+    /// the link count is left as it was.
+    ///
+    /// ### Errors
+    ///
+    /// * `EPERM` - `inodenum` is a directory.
+    /// * `ENOENT` - the directory that would hold the entry does not exist.
+    /// * `EEXIST` - `truenewpath` already exists.
+    fn _link_inode(
+        &self,
+        syscallname: &str,
+        inodenum: usize,
+        truenewpath: &interface::RustPathBuf,
+    ) -> i32 {
+        //for now we assume this is sane, but maybe this should be checked later
+        let filename = truenewpath
+            .file_name()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        // Get the mutable instance of the inode object from the FileMetaData table.
+        let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+
+        // Match the inode object with the correct inode type and increment link count
+        match *inodeobj {
+            // Directory type inode is not supported for linking, so return an error.
+            Inode::Dir(_) => {
+                return syscall_error(Errno::EPERM, syscallname, "oldpath is a directory")
+            }
+
+            Inode::File(ref mut normalfile_inode_obj) => {
+                normalfile_inode_obj.linkcount += 1; //add link to inode
+            }
+
+            Inode::CharDev(ref mut chardev_inode_obj) => {
+                chardev_inode_obj.linkcount += 1; //add link to inode
+            }
+
+            // The Sockets only have an inode if they are a unix type
+            // socket which has a corresponding inode. Regular sockets
+            // do not have inodes.
+            Inode::Socket(ref mut socket_inode_obj) => {
+                socket_inode_obj.linkcount += 1; //add link to inode
+            }
+
+            Inode::Symlink(ref mut symlink_inode_obj) => {
+                symlink_inode_obj.linkcount += 1; //add link to inode
+            }
+
+            Inode::Fifo(ref mut fifo_inode_obj) => {
+                fifo_inode_obj.linkcount += 1; //add link to inode
+            }
+        }
+
+        // the mutable reference to the inode has to be dropped because
+        //`log_metadata` will need to acquire an immutable reference to
+        // the same inode
+        drop(inodeobj);
+
+        // Walk the newpath and once the parent directory inode is found, insert a
+        // reference of this oldpath inode in the inode table
+        let retval = match metawalkandparent(truenewpath.as_path()) {
+            // If both the file and the parent doesn't exist, newpath can't be created
+            (None, None) => syscall_error(Errno::ENOENT, syscallname, "newpath cannot be created"),
+
+            // If the newpath exists, linking can't be perfomed and an error is returned.
+            (Some(_), ..) => syscall_error(Errno::EEXIST, syscallname, "newpath already exists"),
+
+            // If the parent directory inode exists, make a reference of the oldpath inode
+            // in the parent directory to make a link between the two directory paths.
+            (None, Some(pardirinode)) => {
+                // Get the mutable instance of the parent inode object
+                let mut parentinodeobj = FS_METADATA.inodetable.get_mut(&pardirinode).unwrap();
+                //insert a reference to the inode in the parent directory
+                if let Inode::Dir(ref mut parentdirinodeobj) = *parentinodeobj {
+                    parentdirinodeobj
+                        .filename_to_inode_dict
+                        .insert(filename, inodenum);
+                    // Increment the link count of the parent inode as well because
+                    // when a link is created, a new directory entry is added to
+                    // the parent directory of the new link.
+                    parentdirinodeobj.linkcount += 1;
+                    // Adding an entry modifies the directory, and the new link
+                    // changes the status of the linked file
+                    let time = interface::timestamp_nanos();
+                    parentdirinodeobj.mtime = time;
+                    parentdirinodeobj.ctime = time;
+                    //drop the mutable instance of the parent inode object
+                    drop(parentinodeobj);
+                    mark_changed(
+                        &mut FS_METADATA.inodetable.get_mut(&inodenum).unwrap(),
+                        time,
+                    );
+                    log_metadata_group(&FS_METADATA, &[pardirinode, inodenum]);
+                } else {
+                    // If the parent inode is not of type "Directory", panic occurs.
+                    panic!("Parent directory was not a directory!");
+                }
+                // If the linking is successful, 0 is returned.
+                0
+            }
+        };
+
+        // If the linking fails, an error with a value < 0 is returned from above.
+        // The following cases lead to the failing of the linking of files:
+        // 1. When both the file and the parent doesn't exist, newpath can't be created
+        // 2. When the the parent inode is not of type "Directory".
+        // 3. When the newpath already exists.
+        // So, we revert the link count updates made to the oldpath inode.
+        if retval != 0 {
+            // Fetch the inode object from the FileMetadata Table
+            let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+
+            // Match the relevant inode object type and decrement link count
+            match *inodeobj {
+                Inode::File(ref mut normalfile_inode_obj) => {
+                    normalfile_inode_obj.linkcount -= 1;
+                }
+
+                Inode::CharDev(ref mut chardev_inode_obj) => {
+                    chardev_inode_obj.linkcount -= 1;
+                }
+
+                Inode::Socket(ref mut socket_inode_obj) => {
+                    socket_inode_obj.linkcount -= 1;
+                }
+
+                Inode::Symlink(ref mut symlink_inode_obj) => {
+                    symlink_inode_obj.linkcount -= 1;
+                }
+
+                Inode::Fifo(ref mut fifo_inode_obj) => {
+                    fifo_inode_obj.linkcount -= 1;
+                }
+
+                Inode::Dir(_) => {
+                    panic!("Known non-directory file has been replaced with a directory!");
+                }
+            }
+        }
+
+        retval
+    }
+
+    /// ## ------------------UNLINK SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `unlink_syscall()` removes a link to a file. It removes the link
+    /// named by the pathname pointed to by path and decrements the link
+    /// count of the file referenced by the link.
+    /// If that name was the last link to a file and no processes have the file
+    /// open, the file is deleted and the space it was using is made
+    /// available for reuse. If the name was the last link to a file but any
+    /// processes still have the file open, the file will remain in
+    /// existence until the last file descriptor referring to it is closed.
+    /// On successful completion, the timestamp for the parent directory is
+    /// updated along with its linkcounts.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `unlink_syscall()` receives one argument:
+    /// * `path` - This argument points to a pathname which needs to be unlinked
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful unlinking of the file, 0 is returned.
+    /// Otherwise, −1 is returned, and errno is set to indicate the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOENT` - The path argument is a null pathname;
+    /// a component of path prefix does not exist; or the file
+    /// named by oldpath does not exist.
+    /// * `EISDIR` - When the unlinking is done on a directory
+    /// * `EACCES` - search permission is denied on a component of the path
+    ///   prefix, or write permission is denied on the parent directory
+    /// * `EPERM` - the parent directory has the sticky bit set and the caller
+    ///   owns neither the file nor the directory
+    ///
+    /// ### Panics
+    ///
+    /// * If the parent inode does not exist in the inode table, causing
+    ///   unwrap() to panic.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [unlink(2)](https://man7.org/linux/man-pages/man2/unlink.2.html)
+    pub fn unlink_syscall(&self, path: &str) -> i32 {
+        // Return an error if the provided path is empty
+        if path.len() == 0 {
+            return syscall_error(Errno::ENOENT, "unlink", "given path was null");
+        }
+        // Retrieve the absolute path from the root directory for the given path.
+        // The absolute path is then used to validate directory paths while navigating
+        // through subdirectories.
+        let truepath = normpath(convpath(path), self);
+
+        // Every directory leading to the file has to be searchable by the caller
+        let searchcheck = self._search_check("unlink", &truepath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
+
+        // Walk through the absolute path which returns a tuple consisting of inode
+        // number of file (if it exists), and inode number of parent (if it exists)
+        match metawalkandparent(truepath.as_path()) {
+            // Return an error if the given file does not exist
+            (None, ..) => syscall_error(Errno::ENOENT, "unlink", "path does not exist"),
+
+            // If the file exists but has no parent, it's the root directory
+            // No unlinking is done on the root, and an error is returned
+            (Some(_), None) => {
+                syscall_error(Errno::EISDIR, "unlink", "cannot unlink root directory")
+            }
+
+            // If both the file and the parent directory exists
+            (Some(inodenum), Some(parentinodenum)) => {
+                // The caller has to be allowed to remove entries from the parent directory
+                let removecheck = self._remove_check("unlink", parentinodenum, inodenum);
+                if removecheck != 0 {
+                    return removecheck;
+                }
+
+                // Get the mutable instance of the file from the Inode table
+                let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+
+                // For the inode object, we update 4 parameters:
+                // reference count: refers to the active file descriptors pointing to the
+                // file.
+                // link count: refers to the number of hard links pointing to the file.
+                // linkcount is decremented by 1 for all inode types except "Dir" type.
+                // file object: indicates whether the inode being unlinked has an associated
+                // file object. This is relevant for managing the physical deletion of the file
                 // data from the filesystem. It is only "true" for "File" type inode.
                 // log: indicates whether the FileMetaData will be updated for the inode.
                 let (currefcount, curlinkcount, has_fobj, log) = match *inodeobj {
@@ -1024,7 +1605,20 @@ impl Cage {
                         // need to be persisted, thus using log is unnecessary and is set to "false"
                         (f.refcount, f.linkcount, false, false)
                     }
+                    Inode::Symlink(ref mut f) => {
+                        // The link itself is removed, never the file it points to
+                        f.linkcount -= 1;
+                        (f.refcount, f.linkcount, false, true)
+                    }
+                    Inode::Fifo(ref mut f) => {
+                        // Whatever is still in the pipe stays there for the descriptors
+                        // open on it, but the FIFO can no longer be opened by name
+                        f.linkcount -= 1;
+                        (f.refcount, f.linkcount, false, true)
+                    }
                 }; //count current number of links and references
+                   // Losing a link is a change of the file's status
+                mark_changed(&mut inodeobj, interface::timestamp_nanos());
 
                 drop(inodeobj);
 
@@ -1047,7 +1641,7 @@ impl Cage {
                     // exists.
                     if currefcount == 0 {
                         // remove the reference of the inode from the inodetable
-                        FS_METADATA.inodetable.remove(&inodenum);
+                        FS_METADATA.remove_inode(inodenum);
                         // only "File" type inode has this flag set to "true",
                         // so, the file is removed from the FileSystem
                         if has_fobj {
@@ -1057,148 +1651,545 @@ impl Cage {
                             // Since the file is of no use, we are removing its entry
                             // from the system.
                             let sysfilename = format!("{}{}", FILEDATAPREFIX, inodenum);
-                            interface::removefile(sysfilename).unwrap();
+                            interface::storage_backend()
+                                .remove_data(&sysfilename)
+                                .unwrap();
                         }
                     }
                 }
                 // Remove any domain socket paths associated with the file
-                NET_METADATA.domsock_paths.remove(&truepath);
+                NET_METADATA
+                    .domsock_paths
+                    .remove(&unanchoredpath(&truepath));
 
                 // the log boolean will be false if we are working on a domain socket
                 if log {
-                    log_metadata(&FS_METADATA, parentinodenum);
-                    log_metadata(&FS_METADATA, inodenum);
+                    log_metadata_group(&FS_METADATA, &[parentinodenum, inodenum]);
                 }
                 0 //unlink has succeeded
             }
         }
     }
 
-    /// ## ------------------CREAT SYSCALL------------------
+    /// ## ------------------UNLINKAT SYSCALL------------------
     /// ### Description
     ///
-    /// The `creat_syscall()` is similar to `open_syscall()` with the "flags"
-    /// parameter for open_syscall set to representing create, truncate or write
-    /// only for the file. It simplifies the process of creating a new file or
-    /// truncating an existing one by combining the O_CREAT, O_TRUNC, and
-    /// O_WRONLY flags.
-    /// There are generally two cases which occur when this syscall happens:
-    /// Case 1: If the file to be opened doesn't exist, then due to O_CREAT
-    /// flag, a new file is created at the given location and a new file
-    /// descriptor is created and returned.
-    /// Case 2: If the file already exists, then due to O_TRUNC flag, the file
-    /// size gets reduced to 0, and the existing file descriptor is returned.
+    /// The `unlinkat_syscall()` is like `unlink_syscall()`, or like
+    /// `rmdir_syscall()` if `flags` is `AT_REMOVEDIR`, except that a relative
+    /// `path` is interpreted relative to the directory referred to by `dirfd`,
+    /// see `_at_path_helper()`.
     ///
     /// ### Function Arguments
     ///
-    /// The `creat_syscall()` receives two arguments:
-    /// * `path` - This argument points to a pathname naming the file. For
-    ///   example: "/parentdir/file1" represents a file which will be either
-    ///   opened if exists or will be created at the given path.
-    /// * `mode` - This represents the permission of the newly created file. The
-    ///   general mode used is "S_IRWXA": which represents the read, write, and
-    ///   search permissions on the new file.
+    /// The `unlinkat_syscall()` receives three arguments:
+    /// * `dirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `path` - the file or directory to remove.
+    /// * `flags` - either 0 or `AT_REMOVEDIR`, which removes a directory.
     ///
     /// ### Returns
     ///
-    /// Upon successful completion of this call, a file descriptor is returned
-    /// which points the file which is opened. Otherwise, errors or panics
-    /// are returned for different scenarios.
+    /// 0 on success, otherwise the error.
     ///
     /// ### Errors
     ///
-    /// * ENFILE - no available file descriptor number could be found
-    /// * ENOENT - tried to open a file that did not exist
-    /// * EPERM - the mode bits for a file are not sane
-    /// * ENOTDIR - tried to create a file as a child of something that isn't a
-    ///   directory
-    /// * EEXIST - the given file already exists
-    /// * ENXIO - the file is of type UNIX domain socket
-    ///
-    /// ### Panics
-    ///
-    /// * If truepath.file_name() returns None or if to_str() fails, causing
-    ///   unwrap() to panic.
-    /// * If the parent inode does not exist in the inode table, causing
-    ///   unwrap() to panic.
-    /// * When there is some other issue fetching the file descriptor.
+    /// * `EINVAL` - `flags` contains an unknown flag.
+    /// * `EBADF`, `ENOTDIR` - see `_at_path_helper()`.
+    /// * Any error of `unlink_syscall()` or `rmdir_syscall()`.
     ///
     /// For more detailed description of all the commands and return values, see
-    /// [creat(3p)](https://man7.org/linux/man-pages/man3/creat.3p.html)
-    pub fn creat_syscall(&self, path: &str, mode: u32) -> i32 {
-        // These flags represent that the given file is either newly created
-        // (if it doesn't exist) or truncated to zero length (if it does exist),
-        // and it is opened for write-only access.
-        self.open_syscall(path, O_CREAT | O_TRUNC | O_WRONLY, mode)
+    /// [unlinkat(2)](https://man7.org/linux/man-pages/man2/unlinkat.2.html)
+    pub fn unlinkat_syscall(&self, dirfd: i32, path: &str, flags: i32) -> i32 {
+        if flags & !AT_REMOVEDIR != 0 {
+            return syscall_error(Errno::EINVAL, "unlinkat", "invalid flags");
+        }
+        let truepath = match self._at_path_helper("unlinkat", dirfd, path) {
+            Ok(truepath) => truepath,
+            Err(e) => return e,
+        };
+        if flags & AT_REMOVEDIR != 0 {
+            self.rmdir_syscall(truepath.to_str().unwrap())
+        } else {
+            self.unlink_syscall(truepath.to_str().unwrap())
+        }
     }
 
-    //------------------------------------STAT SYSCALL------------------------------------
+    /// ## ------------------SYMLINK SYSCALL------------------
     /// ### Description
     ///
-    /// `stat_syscall` retrieves file information for the file specified by
-    /// `path` and populates the provided `statbuf` with this information.
-    /// Although no file permissions are required to perform the call, execute
-    /// (search) permission is required on all of the directories in
-    /// pathname that lead to the file.
+    /// The `symlink_syscall()` creates a symbolic link named `linkpath` which
+    /// contains the string `target`. Symbolic links are interpreted at run
+    /// time as if the contents of the link had been substituted into the path
+    /// being followed to find a file or directory. The target is stored
+    /// verbatim, so it may be relative or absolute and need not exist; a
+    /// relative target is interpreted with respect to the directory
+    /// containing the link.
     ///
-    /// ### Arguments
+    /// ### Function Arguments
     ///
-    /// It accepts two parameters:
-    /// * `path` - A string slice that specifies the file path for which status
-    ///   information is to be retrieved.
-    /// * `statbuf` - A mutable reference to a `StatData` struct where the file
-    ///   status will be stored.
+    /// The `symlink_syscall()` receives two arguments:
+    /// * `target` - The string the new link will contain
+    /// * `linkpath` - The pathname at which the link is created
     ///
     /// ### Returns
     ///
-    /// For a successful call, the return value will be 0. On error, a negative
-    /// errno is returned to indicate the error.
+    /// Upon successful creation of the link, 0 is returned.
+    /// Otherwise, −1 is returned, no link is created, and errno is set to
+    /// indicate the error.
     ///
     /// ### Errors
     ///
-    /// * `ENOENT` - The file specified by `path` does not exist or the path is
-    ///   invalid.
+    /// * `ENOENT` - The target or linkpath argument is a null pathname, or a
+    ///   directory component of linkpath does not exist.
+    /// * `EEXIST` - linkpath already exists (whether or not it is itself a
+    ///   symbolic link).
+    /// * `EACCES` - search permission is denied on a component of linkpath, or
+    ///   write permission is denied on the directory that would hold it.
     ///
     /// ### Panics
     ///
-    /// * This function does not have any known panics.
+    /// * If the parent inode does not exist in the inode table, causing
+    ///   unwrap() to panic.
     ///
-    /// For more detailed description of all the commands and return values,
-    /// refer to the stat man page [here](https://man7.org/linux/man-pages/man2/stat.2.html).
+    /// For more detailed description of all the commands and return values, see
+    /// [symlink(2)](https://man7.org/linux/man-pages/man2/symlink.2.html)
+    pub fn symlink_syscall(&self, target: &str, linkpath: &str) -> i32 {
+        // Return an error if either of the provided paths is empty
+        if target.is_empty() {
+            return syscall_error(Errno::ENOENT, "symlink", "given target was null");
+        }
+        if linkpath.is_empty() {
+            return syscall_error(Errno::ENOENT, "symlink", "given linkpath was null");
+        }
+        // Retrieve the absolute path from the root directory for the link. The
+        // target is stored as it was given and only resolved when the link is
+        // followed.
+        let truepath = normpath(convpath(linkpath), self);
+
+        // Every directory leading to the link has to be searchable by the caller
+        let searchcheck = self._search_check("symlink", &truepath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
 
-    pub fn stat_syscall(&self, path: &str, statbuf: &mut StatData) -> i32 {
-        //convert the path to an absolute path of type `PathBuf`
-        let truepath = normpath(convpath(path), self);
+        // Walk the path of the link without following it, so that an existing link
+        // at linkpath is reported rather than the file it points to
+        match metawalkandparent(truepath.as_path()) {
+            // If the parent directory doesn't exist, the link can't be created
+            (None, None) => syscall_error(
+                Errno::ENOENT,
+                "symlink",
+                "a directory component in linkpath does not exist",
+            ),
 
-        //Walk the file tree to get inode from path
-        if let Some(inodenum) = metawalk(truepath.as_path()) {
-            // won't panic since check for inode number in table is already happening in
-            // above 'metawalk' function
-            let inodeobj = FS_METADATA.inodetable.get(&inodenum).unwrap();
+            // If linkpath exists, no link is created and an error is returned.
+            (Some(_), ..) => syscall_error(Errno::EEXIST, "symlink", "linkpath already exists"),
 
-            //populate those fields in statbuf which depend on things other than the inode
-            // object
-            statbuf.st_dev = FS_METADATA.dev_id;
-            statbuf.st_ino = inodenum;
+            // If the parent directory exists, create the link inode and insert it there
+            (None, Some(pardirinode)) => {
+                // Creating a link adds an entry to the parent directory, which requires
+                // write and search permission on it
+                let parentcheck = self._access_check("symlink", pardirinode, W_OK | X_OK);
+                if parentcheck != 0 {
+                    return parentcheck;
+                }
 
-            //delegate the rest of populating statbuf to the relevant helper
-            match &*inodeobj {
-                Inode::File(f) => {
-                    Self::_istat_helper(&f, statbuf);
+                //for now we assume this is sane, but maybe this should be checked later
+                let filename = truepath.file_name().unwrap().to_str().unwrap().to_string();
+                let time = interface::timestamp_nanos();
+                let (euid, egid) = self.get_effective_ids();
+                let reserved = self._reserve_inode(euid, "symlink");
+                if reserved != 0 {
+                    return reserved;
                 }
-                Inode::CharDev(f) => {
-                    Self::_istat_helper_chr_file(&f, statbuf);
+
+                // The permission bits of a symbolic link are always 0777 and are never
+                // consulted when the link is followed
+                let newinode = Inode::Symlink(SymlinkInode {
+                    size: target.len(),
+                    uid: euid,
+                    gid: egid,
+                    mode: S_IFLNK as u32 | S_IRWXA,
+                    linkcount: 1,
+                    refcount: 0,
+                    atime: time,
+                    ctime: time,
+                    mtime: time,
+                    target: target.to_string(),
+                });
+
+                // Fetch the next available inode number using the FileSystem MetaData table
+                let newinodenum = FS_METADATA
+                    .nextinode
+                    .fetch_add(1, interface::RustAtomicOrdering::Relaxed);
+
+                // Insert a reference to the new link into its parent directory
+                if let Inode::Dir(ref mut parentdir) =
+                    *(FS_METADATA.inodetable.get_mut(&pardirinode).unwrap())
+                {
+                    parentdir
+                        .filename_to_inode_dict
+                        .insert(filename, newinodenum);
+                    parentdir.linkcount += 1;
+                    parentdir.ctime = time;
+                    parentdir.mtime = time;
+                } else {
+                    FS_METADATA.release_space(euid, 0, 1);
+                    return syscall_error(
+                        Errno::ENOTDIR,
+                        "symlink",
+                        "tried to create a link as a child of something that isn't a directory",
+                    );
                 }
-                Inode::Socket(f) => {
-                    Self::_istat_helper_sock(&f, statbuf);
+                FS_METADATA.inodetable.insert(newinodenum, newinode);
+                log_metadata_group(&FS_METADATA, &[pardirinode, newinodenum]);
+                0 //symlink has succeeded
+            }
+        }
+    }
+
+    /// ## ------------------READLINK SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `readlink_syscall()` places the contents of the symbolic link
+    /// `path` in the buffer `buf`, which has size `bufsize`. A null byte is
+    /// not appended to `buf`, and the contents are silently truncated if the
+    /// buffer is too small to hold all of them.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `readlink_syscall()` receives three arguments:
+    /// * `path` - The pathname of the symbolic link to be read
+    /// * `buf` - A pointer to the buffer the contents are copied into
+    /// * `bufsize` - The size of `buf` in bytes
+    ///
+    /// ### Returns
+    ///
+    /// Upon success, the number of bytes placed in `buf` is returned.
+    /// Otherwise, −1 is returned and errno is set to indicate the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOENT` - The path argument is a null pathname, or the named file
+    ///   does not exist.
+    /// * `EINVAL` - The named file is not a symbolic link, or `bufsize` is 0.
+    ///
+    /// ### Panics
+    ///
+    /// * This function does not have any known panics.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [readlink(2)](https://man7.org/linux/man-pages/man2/readlink.2.html)
+    pub fn readlink_syscall(&self, path: &str, buf: *mut u8, bufsize: usize) -> i32 {
+        // Return an error if the provided path is empty
+        if path.is_empty() {
+            return syscall_error(Errno::ENOENT, "readlink", "given path was null");
+        }
+        if bufsize == 0 {
+            return syscall_error(Errno::EINVAL, "readlink", "bufsize is not positive");
+        }
+        let truepath = normpath(convpath(path), self);
+
+        // Walk the path without following the final component, which is the link
+        // whose contents we are after
+        match metawalkandparent(truepath.as_path()).0 {
+            None => syscall_error(Errno::ENOENT, "readlink", "path does not exist"),
+            Some(inodenum) => {
+                let inodeobj = FS_METADATA.inodetable.get(&inodenum).unwrap();
+                if let Inode::Symlink(ref link) = *inodeobj {
+                    // Only the first `bufsize` bytes of the target fit into the buffer
+                    let count = link.target.len().min(bufsize);
+                    interface::fill(buf, count, &link.target.as_bytes().to_vec());
+                    count as i32
+                } else {
+                    syscall_error(Errno::EINVAL, "readlink", "path is not a symbolic link")
                 }
-                Inode::Dir(f) => {
-                    Self::_istat_helper_dir(&f, statbuf);
+            }
+        }
+    }
+
+    /// ## ------------------READLINKAT SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `readlinkat_syscall()` is like `readlink_syscall()`, except that a
+    /// relative `path` is interpreted relative to the directory referred to by
+    /// `dirfd`, see `_at_path_helper()`.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `readlinkat_syscall()` receives four arguments:
+    /// * `dirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `path`, `buf`, `bufsize` - as for `readlink_syscall()`.
+    ///
+    /// ### Returns
+    ///
+    /// The number of bytes placed in `buf`, as for `readlink_syscall()`.
+    ///
+    /// ### Errors
+    ///
+    /// * `EBADF`, `ENOTDIR` - see `_at_path_helper()`.
+    /// * Any error of `readlink_syscall()`.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [readlinkat(2)](https://man7.org/linux/man-pages/man2/readlinkat.2.html)
+    pub fn readlinkat_syscall(&self, dirfd: i32, path: &str, buf: *mut u8, bufsize: usize) -> i32 {
+        match self._at_path_helper("readlinkat", dirfd, path) {
+            Ok(truepath) => self.readlink_syscall(truepath.to_str().unwrap(), buf, bufsize),
+            Err(e) => e,
+        }
+    }
+
+    /// ## ------------------CREAT SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `creat_syscall()` is similar to `open_syscall()` with the "flags"
+    /// parameter for open_syscall set to representing create, truncate or write
+    /// only for the file. It simplifies the process of creating a new file or
+    /// truncating an existing one by combining the O_CREAT, O_TRUNC, and
+    /// O_WRONLY flags.
+    /// There are generally two cases which occur when this syscall happens:
+    /// Case 1: If the file to be opened doesn't exist, then due to O_CREAT
+    /// flag, a new file is created at the given location and a new file
+    /// descriptor is created and returned.
+    /// Case 2: If the file already exists, then due to O_TRUNC flag, the file
+    /// size gets reduced to 0, and the existing file descriptor is returned.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `creat_syscall()` receives two arguments:
+    /// * `path` - This argument points to a pathname naming the file. For
+    ///   example: "/parentdir/file1" represents a file which will be either
+    ///   opened if exists or will be created at the given path.
+    /// * `mode` - This represents the permission of the newly created file. The
+    ///   general mode used is "S_IRWXA": which represents the read, write, and
+    ///   search permissions on the new file.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion of this call, a file descriptor is returned
+    /// which points the file which is opened. Otherwise, errors or panics
+    /// are returned for different scenarios.
+    ///
+    /// ### Errors
+    ///
+    /// * ENFILE - no available file descriptor number could be found
+    /// * EMFILE - the soft RLIMIT_NOFILE of the cage has been reached
+    /// * ENOENT - tried to open a file that did not exist
+    /// * EPERM - the mode bits for a file are not sane
+    /// * ENOTDIR - tried to create a file as a child of something that isn't a
+    ///   directory
+    /// * EEXIST - the given file already exists
+    /// * ENXIO - the file is of type UNIX domain socket
+    ///
+    /// ### Panics
+    ///
+    /// * If truepath.file_name() returns None or if to_str() fails, causing
+    ///   unwrap() to panic.
+    /// * If the parent inode does not exist in the inode table, causing
+    ///   unwrap() to panic.
+    /// * When there is some other issue fetching the file descriptor.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [creat(3p)](https://man7.org/linux/man-pages/man3/creat.3p.html)
+    pub fn creat_syscall(&self, path: &str, mode: u32) -> i32 {
+        // These flags represent that the given file is either newly created
+        // (if it doesn't exist) or truncated to zero length (if it does exist),
+        // and it is opened for write-only access.
+        self.open_syscall(path, O_CREAT | O_TRUNC | O_WRONLY, mode)
+    }
+
+    //------------------------------------STAT SYSCALL------------------------------------
+    /// ### Description
+    ///
+    /// `stat_syscall` retrieves file information for the file specified by
+    /// `path` and populates the provided `statbuf` with this information.
+    /// Although no file permissions are required to perform the call, execute
+    /// (search) permission is required on all of the directories in
+    /// pathname that lead to the file.
+    ///
+    /// ### Arguments
+    ///
+    /// It accepts two parameters:
+    /// * `path` - A string slice that specifies the file path for which status
+    ///   information is to be retrieved.
+    /// * `statbuf` - A mutable reference to a `StatData` struct where the file
+    ///   status will be stored.
+    ///
+    /// ### Returns
+    ///
+    /// For a successful call, the return value will be 0. On error, a negative
+    /// errno is returned to indicate the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOENT` - The file specified by `path` does not exist or the path is
+    ///   invalid.
+    /// * `ELOOP` - Too many symbolic links were encountered in resolving
+    ///   `path`.
+    ///
+    /// ### Panics
+    ///
+    /// * This function does not have any known panics.
+    ///
+    /// For more detailed description of all the commands and return values,
+    /// refer to the stat man page [here](https://man7.org/linux/man-pages/man2/stat.2.html).
+
+    pub fn stat_syscall(&self, path: &str, statbuf: &mut StatData) -> i32 {
+        self._stat_path_helper("stat", path, statbuf, true)
+    }
+
+    //------------------------------------LSTAT SYSCALL------------------------------------
+    /// ### Description
+    ///
+    /// `lstat_syscall` is identical to `stat_syscall`, except that if `path`
+    /// names a symbolic link, the information returned describes the link
+    /// itself rather than the file it refers to. Symbolic links in the
+    /// directory components of `path` are still followed.
+    ///
+    /// ### Arguments
+    ///
+    /// It accepts two parameters:
+    /// * `path` - A string slice that specifies the file path for which status
+    ///   information is to be retrieved.
+    /// * `statbuf` - A mutable reference to a `StatData` struct where the file
+    ///   status will be stored.
+    ///
+    /// ### Returns
+    ///
+    /// For a successful call, the return value will be 0. On error, a negative
+    /// errno is returned to indicate the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOENT` - The file specified by `path` does not exist or the path is
+    ///   invalid.
+    /// * `ELOOP` - Too many symbolic links were encountered in resolving the
+    ///   directory components of `path`.
+    ///
+    /// ### Panics
+    ///
+    /// * This function does not have any known panics.
+    ///
+    /// For more detailed description of all the commands and return values,
+    /// refer to the stat man page [here](https://man7.org/linux/man-pages/man2/stat.2.html).
+    pub fn lstat_syscall(&self, path: &str, statbuf: &mut StatData) -> i32 {
+        self._stat_path_helper("lstat", path, statbuf, false)
+    }
+
+    //------------------------------------FSTATAT SYSCALL------------------------------------
+    /// ### Description
+    ///
+    /// `fstatat_syscall` is like `stat_syscall`, except that a relative `path`
+    /// is interpreted relative to the directory referred to by `dirfd`, see
+    /// `_at_path_helper()`. It serves both fstatat and newfstatat.
+    ///
+    /// ### Arguments
+    ///
+    /// It accepts four parameters:
+    /// * `dirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `path` - the file to get the status of.
+    /// * `statbuf` - the buffer the status is written to.
+    /// * `flags` - a combination of:
+    ///   * `AT_SYMLINK_NOFOLLOW` - a symbolic link named by `path` is not
+    ///     followed, as for `lstat_syscall`.
+    ///   * `AT_EMPTY_PATH` - an empty `path` gets the status of the file
+    ///     `dirfd` refers to, or of the current working directory if `dirfd` is
+    ///     `AT_FDCWD`.
+    ///   * `AT_NO_AUTOMOUNT` - accepted and ignored, as nothing is automounted.
+    ///
+    /// ### Returns
+    ///
+    /// 0 on success, otherwise the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `EINVAL` - `flags` contains an unknown flag.
+    /// * `EBADF` - `AT_EMPTY_PATH` is given with an empty path and `dirfd` is
+    ///   not a valid file descriptor, or see `_at_path_helper()`.
+    /// * `ENOTDIR` - see `_at_path_helper()`.
+    /// * Any error of `stat_syscall` or `fstat_syscall`.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [fstatat(2)](https://man7.org/linux/man-pages/man2/fstatat.2.html)
+    pub fn fstatat_syscall(
+        &self,
+        dirfd: i32,
+        path: &str,
+        statbuf: &mut StatData,
+        flags: i32,
+    ) -> i32 {
+        if flags & !(AT_SYMLINK_NOFOLLOW | AT_EMPTY_PATH | AT_NO_AUTOMOUNT) != 0 {
+            return syscall_error(Errno::EINVAL, "fstatat", "invalid flags");
+        }
+        if path.is_empty() && flags & AT_EMPTY_PATH != 0 {
+            if dirfd == AT_FDCWD {
+                return self._stat_path_helper("fstatat", ".", statbuf, true);
+            }
+            if self.get_filedescriptor(dirfd).is_err() {
+                return syscall_error(Errno::EBADF, "fstatat", "invalid file descriptor");
+            }
+            return self.fstat_syscall(dirfd, statbuf);
+        }
+        let truepath = match self._at_path_helper("fstatat", dirfd, path) {
+            Ok(truepath) => truepath,
+            Err(e) => return e,
+        };
+        let follow_final = flags & AT_SYMLINK_NOFOLLOW == 0;
+        self._stat_path_helper("fstatat", truepath.to_str().unwrap(), statbuf, follow_final)
+    }
+
+    // shared implementation of stat and lstat, which only differ in whether a
+    // symbolic link named by the final component of the path is followed
+    fn _stat_path_helper(
+        &self,
+        syscallname: &str,
+        path: &str,
+        statbuf: &mut StatData,
+        follow_final: bool,
+    ) -> i32 {
+        //convert the path to an absolute path of type `PathBuf`
+        let truepath = normpath(convpath(path), self);
+
+        //Walk the file tree to get inode from path
+        match metawalkandparent_resolve(truepath.as_path(), follow_final) {
+            Ok((Some(inodenum), ..)) => {
+                // won't panic since check for inode number in table is already happening in
+                // above walk
+                let inodeobj = FS_METADATA.inodetable.get(&inodenum).unwrap();
+
+                //populate those fields in statbuf which depend on things other than the
+                // inode object
+                statbuf.st_dev = FS_METADATA.dev_id;
+                statbuf.st_ino = inodenum;
+
+                //delegate the rest of populating statbuf to the relevant helper
+                match &*inodeobj {
+                    Inode::File(f) => {
+                        Self::_istat_helper(&f, statbuf);
+                    }
+                    Inode::CharDev(f) => {
+                        Self::_istat_helper_chr_file(&f, statbuf);
+                    }
+                    Inode::Socket(f) => {
+                        Self::_istat_helper_sock(&f, statbuf);
+                    }
+                    Inode::Dir(f) => {
+                        Self::_istat_helper_dir(&f, statbuf);
+                    }
+                    Inode::Symlink(f) => {
+                        Self::_istat_helper_symlink(f, statbuf);
+                    }
+                    Inode::Fifo(f) => {
+                        Self::_istat_helper_fifo(f, statbuf);
+                    }
                 }
+                0 //stat has succeeded!
             }
-            0 //stat has succeeded!
-        } else {
-            syscall_error(Errno::ENOENT, "stat", "path refers to an invalid file")
+            Ok(_) => syscall_error(Errno::ENOENT, syscallname, "path refers to an invalid file"),
+            Err(_) => syscall_error(
+                Errno::ELOOP,
+                syscallname,
+                "too many symbolic links were encountered in resolving path",
+            ),
         }
     }
 
@@ -1214,6 +2205,9 @@ impl Cage {
         statbuf.st_size = inodeobj.size;
         statbuf.st_blksize = 0;
         statbuf.st_blocks = 0;
+        statbuf.st_atim = interface::nanos_to_timespec(inodeobj.atime);
+        statbuf.st_mtim = interface::nanos_to_timespec(inodeobj.mtime);
+        statbuf.st_ctim = interface::nanos_to_timespec(inodeobj.ctime);
     }
 
     // helper function to populate information of socket inode object into the
@@ -1228,27 +2222,65 @@ impl Cage {
         statbuf.st_size = inodeobj.size;
         statbuf.st_blksize = 0;
         statbuf.st_blocks = 0;
+        statbuf.st_atim = interface::nanos_to_timespec(inodeobj.atime);
+        statbuf.st_mtim = interface::nanos_to_timespec(inodeobj.mtime);
+        statbuf.st_ctim = interface::nanos_to_timespec(inodeobj.ctime);
     }
 
-    // helper function to populate information of directory inode object into the
-    // statbuf. Refer [here](https://man7.org/linux/man-pages/man7/inode.7.html)
-    // for more information on the fields being populated below.
-    fn _istat_helper_dir(inodeobj: &DirectoryInode, statbuf: &mut StatData) {
+    // helper function to populate information of FIFO inode object into the
+    // statbuf. Whatever is in the pipe of a FIFO doesn't count towards its size.
+    fn _istat_helper_fifo(inodeobj: &FifoInode, statbuf: &mut StatData) {
         statbuf.st_mode = inodeobj.mode;
         statbuf.st_nlink = inodeobj.linkcount;
         statbuf.st_uid = inodeobj.uid;
         statbuf.st_gid = inodeobj.gid;
         statbuf.st_rdev = 0;
-        statbuf.st_size = inodeobj.size;
+        statbuf.st_size = 0;
         statbuf.st_blksize = 0;
         statbuf.st_blocks = 0;
+        statbuf.st_atim = interface::nanos_to_timespec(inodeobj.atime);
+        statbuf.st_mtim = interface::nanos_to_timespec(inodeobj.mtime);
+        statbuf.st_ctim = interface::nanos_to_timespec(inodeobj.ctime);
     }
 
-    // helper function to populate information of device inode object into the
+    // helper function to populate information of directory inode object into the
     // statbuf. Refer [here](https://man7.org/linux/man-pages/man7/inode.7.html)
     // for more information on the fields being populated below.
-    fn _istat_helper_chr_file(inodeobj: &DeviceInode, statbuf: &mut StatData) {
-        statbuf.st_dev = 5;
+    fn _istat_helper_dir(inodeobj: &DirectoryInode, statbuf: &mut StatData) {
+        statbuf.st_mode = inodeobj.mode;
+        statbuf.st_nlink = inodeobj.linkcount;
+        statbuf.st_uid = inodeobj.uid;
+        statbuf.st_gid = inodeobj.gid;
+        statbuf.st_rdev = 0;
+        statbuf.st_size = inodeobj.size;
+        statbuf.st_blksize = 0;
+        statbuf.st_blocks = 0;
+        statbuf.st_atim = interface::nanos_to_timespec(inodeobj.atime);
+        statbuf.st_mtim = interface::nanos_to_timespec(inodeobj.mtime);
+        statbuf.st_ctim = interface::nanos_to_timespec(inodeobj.ctime);
+    }
+
+    // helper function to populate information of symbolic link inode object into
+    // the statbuf. The size of a link is the length of the path it points to.
+    fn _istat_helper_symlink(inodeobj: &SymlinkInode, statbuf: &mut StatData) {
+        statbuf.st_mode = inodeobj.mode;
+        statbuf.st_nlink = inodeobj.linkcount;
+        statbuf.st_uid = inodeobj.uid;
+        statbuf.st_gid = inodeobj.gid;
+        statbuf.st_rdev = 0;
+        statbuf.st_size = inodeobj.size;
+        statbuf.st_blksize = 0;
+        statbuf.st_blocks = 0;
+        statbuf.st_atim = interface::nanos_to_timespec(inodeobj.atime);
+        statbuf.st_mtim = interface::nanos_to_timespec(inodeobj.mtime);
+        statbuf.st_ctim = interface::nanos_to_timespec(inodeobj.ctime);
+    }
+
+    // helper function to populate information of device inode object into the
+    // statbuf. Refer [here](https://man7.org/linux/man-pages/man7/inode.7.html)
+    // for more information on the fields being populated below.
+    fn _istat_helper_chr_file(inodeobj: &DeviceInode, statbuf: &mut StatData) {
+        statbuf.st_dev = 5;
         statbuf.st_mode = inodeobj.mode;
         statbuf.st_nlink = inodeobj.linkcount;
         statbuf.st_uid = inodeobj.uid;
@@ -1256,6 +2288,9 @@ impl Cage {
         //compose device number into u64
         statbuf.st_rdev = makedev(&inodeobj.dev);
         statbuf.st_size = inodeobj.size;
+        statbuf.st_atim = interface::nanos_to_timespec(inodeobj.atime);
+        statbuf.st_mtim = interface::nanos_to_timespec(inodeobj.mtime);
+        statbuf.st_ctim = interface::nanos_to_timespec(inodeobj.ctime);
     }
 
     // Streams and pipes don't have associated inodes so we populate them from
@@ -1359,20 +2394,47 @@ impl Cage {
                         Inode::Dir(f) => {
                             Self::_istat_helper_dir(&f, statbuf);
                         }
+                        Inode::Symlink(f) => {
+                            Self::_istat_helper_symlink(f, statbuf);
+                        }
+                        Inode::Fifo(f) => {
+                            Self::_istat_helper_fifo(f, statbuf);
+                        }
                     }
                 }
                 // Streams don't have inodes, so we'll populate statbuf with dummy info
                 Stream(_) => {
                     self._stat_alt_helper(statbuf, STREAMINODE);
                 }
-                // Pipes don't have inodes, so we'll populate statbuf with dummy info
-                Pipe(_) => {
-                    self._stat_alt_helper(statbuf, PIPEINODE);
-                }
+                // Pipes don't have inodes, so we'll populate statbuf with dummy info,
+                // unless the pipe belongs to a FIFO
+                Pipe(pipe_filedesc_obj) => match pipe_filedesc_obj.fifoinode {
+                    Some(inodenum) => {
+                        if let Inode::Fifo(ref f) = *FS_METADATA.inodetable.get(&inodenum).unwrap()
+                        {
+                            statbuf.st_ino = inodenum;
+                            statbuf.st_dev = FS_METADATA.dev_id;
+                            Self::_istat_helper_fifo(f, statbuf);
+                        }
+                    }
+                    None => {
+                        self._stat_alt_helper(statbuf, PIPEINODE);
+                    }
+                },
                 // Epolls don't have inodes, so we'll populate statbuf with dummy info
                 Epoll(_) => {
                     self._stat_alt_helper(statbuf, EPOLLINODE);
                 }
+                // Neither do eventfds, timerfds and signalfds
+                EventFd(_) => {
+                    self._stat_alt_helper(statbuf, EVENTFDINODE);
+                }
+                TimerFd(_) => {
+                    self._stat_alt_helper(statbuf, TIMERFDINODE);
+                }
+                SignalFd(_) => {
+                    self._stat_alt_helper(statbuf, SIGNALFDINODE);
+                }
             }
             0 //fstat has succeeded!
         } else {
@@ -1490,12 +2552,14 @@ impl Cage {
                     return Self::_istatfs_helper(self, databuf);
                 }
 
-                // if the fd points to a socket, pipe, stream, or epoll file descriptor
-                Socket(_) | Pipe(_) | Stream(_) | Epoll(_) => {
+                // if the fd points to a socket, pipe, stream, epoll, eventfd, timerfd or
+                // signalfd file descriptor
+                Socket(_) | Pipe(_) | Stream(_) | Epoll(_) | EventFd(_) | TimerFd(_)
+                | SignalFd(_) => {
                     return syscall_error(
                         Errno::EBADF,
                         "fstatfs",
-                        "can't fstatfs on sockets, streams, pipes, or epoll fds",
+                        "can't fstatfs on sockets, streams, pipes, epoll, eventfd, timerfd or signalfd fds",
                     );
                 }
             }
@@ -1504,19 +2568,25 @@ impl Cage {
         }
     }
 
-    // These values have (probably) been picked up from the previously used
-    // environment, and have been working fine till now for our purposes
-    // TODO: Figure out how to populate the databuf values properly
+    /// ### Description
+    ///
+    /// The `_istatfs_helper()` fills in the parts of `databuf` that describe
+    /// the file system as a whole for `statfs_syscall()` and
+    /// `fstatfs_syscall()`. The block counts are derived from the capacity of
+    /// the file system and the bytes of file data in use, in units of
+    /// `FS_BLOCKSIZE`, and the file counts from its inode capacity and the
+    /// inodes in use. Quotas are not reflected in the numbers.
     pub fn _istatfs_helper(&self, databuf: &mut FSData) -> i32 {
+        let (capacity, used) = FS_METADATA.space_in_use();
         databuf.f_type = 0xBEEFC0DE; //unassigned
-        databuf.f_bsize = 4096;
-        databuf.f_blocks = 0; //int(limits['diskused']) / 4096
-        databuf.f_bfree = 1024 * 1024 * 1024; //(int(limits['diskused']-usage['diskused'])) / 4096
-        databuf.f_bavail = 1024 * 1024 * 1024; //(int(limits['diskused']-usage['diskused'])) / 4096
-        databuf.f_files = 1024 * 1024 * 1024;
-        databuf.f_ffiles = 1024 * 1024 * 515;
+        databuf.f_bsize = FS_BLOCKSIZE;
+        databuf.f_blocks = capacity.bytes / FS_BLOCKSIZE;
+        databuf.f_bfree = capacity.bytes.saturating_sub(used.bytes) / FS_BLOCKSIZE;
+        databuf.f_bavail = databuf.f_bfree;
+        databuf.f_files = capacity.inodes;
+        databuf.f_ffiles = capacity.inodes.saturating_sub(used.inodes);
         databuf.f_namelen = 254;
-        databuf.f_frsize = 4096;
+        databuf.f_frsize = FS_BLOCKSIZE;
         databuf.f_spare = [0; 32];
 
         0 //success!
@@ -1631,6 +2701,10 @@ impl Cage {
                                 fileobject.readat(buf, count, position as usize).unwrap();
                             //move position forward by the number of bytes we've read
                             normalfile_filedesc_obj.position += bytesread;
+                            // The inode has to be released before its access time can be
+                            // updated
+                            drop(inodeobj);
+                            Self::_update_atime(normalfile_filedesc_obj.inode);
                             // Return the number of bytes read.
                             bytesread as i32
                         }
@@ -1649,6 +2723,16 @@ impl Cage {
                         Inode::Socket(_) => {
                             panic!("read(): Socket inode found on a filedesc fd.")
                         }
+                        // Symbolic links are always resolved or rejected by open, so a "File"
+                        // type fd can never refer to one.
+                        Inode::Symlink(_) => {
+                            panic!("read(): symlink inode found on a filedesc fd")
+                        }
+                        // FIFOs are always opened as pipes, so a "File" type fd can never
+                        // refer to one.
+                        Inode::Fifo(_) => {
+                            panic!("read(): FIFO inode found on a filedesc fd")
+                        }
 
                         // For `Dir` type inode, an error is returned as reading from a directory is
                         // not allowed
@@ -1678,6 +2762,29 @@ impl Cage {
                     "read",
                     "fd is attached to an object which is unsuitable for reading",
                 ),
+                // Reading from an eventfd, timerfd or signalfd may block until another
+                // thread writes to the same descriptor or signals the cage, so the file
+                // descriptor is released before reading.
+                EventFd(eventfd_filedesc_obj) => {
+                    let eventfd = eventfd_filedesc_obj.eventfd.clone();
+                    let nonblocking = eventfd_filedesc_obj.flags & O_NONBLOCK != 0;
+                    drop(unlocked_fd);
+                    self._read_eventfd(&eventfd, nonblocking, buf, count)
+                }
+                TimerFd(timerfd_filedesc_obj) => {
+                    let timer = timerfd_filedesc_obj.timer.clone();
+                    let nonblocking = timerfd_filedesc_obj.flags & O_NONBLOCK != 0;
+                    drop(unlocked_fd);
+                    self._read_timerfd(&timer, nonblocking, buf, count)
+                }
+                SignalFd(signalfd_filedesc_obj) => {
+                    let mask = signalfd_filedesc_obj
+                        .mask
+                        .load(interface::RustAtomicOrdering::Relaxed);
+                    let nonblocking = signalfd_filedesc_obj.flags & O_NONBLOCK != 0;
+                    drop(unlocked_fd);
+                    self._read_signalfd(mask, nonblocking, buf, count)
+                }
                 // The `Pipe` type file descriptor handles read through blocking and non-blocking
                 // modes differently to ensure appropriate behavior based on the flags set on the
                 // pipe. In blocking mode, the read_from_pipe function will wait until data is
@@ -1830,6 +2937,10 @@ impl Cage {
                             // `readat` function reads from file at specified offset into provided
                             // C-buffer.
                             let bytesread = fileobject.readat(buf, count, offset as usize).unwrap();
+                            // The inode has to be released before its access time can be
+                            // updated
+                            drop(inodeobj);
+                            Self::_update_atime(normalfile_filedesc_obj.inode);
                             // Return the number of bytes read.
                             bytesread as i32
                         }
@@ -1848,6 +2959,16 @@ impl Cage {
                         Inode::Socket(_) => {
                             panic!("pread(): Socket inode found on a filedesc fd")
                         }
+                        // Symbolic links are always resolved or rejected by open, so a "File"
+                        // type fd can never refer to one.
+                        Inode::Symlink(_) => {
+                            panic!("pread(): symlink inode found on a filedesc fd")
+                        }
+                        // FIFOs are always opened as pipes, so a "File" type fd can never
+                        // refer to one.
+                        Inode::Fifo(_) => {
+                            panic!("pread(): FIFO inode found on a filedesc fd")
+                        }
                         // For `Dir` type inode, an error is returned as reading from a directory is
                         // not allowed
                         Inode::Dir(_) => syscall_error(
@@ -1886,12 +3007,360 @@ impl Cage {
                     "pread",
                     "file descriptor is associated with an epollfd, cannot seek",
                 ),
+                // Eventfds, timerfds and signalfds don't hold any data to seek in either.
+                EventFd(_) | TimerFd(_) | SignalFd(_) => syscall_error(
+                    Errno::ESPIPE,
+                    "pread",
+                    "file descriptor is associated with an eventfd, timerfd or signalfd, cannot seek",
+                ),
             }
         } else {
             syscall_error(Errno::EBADF, "pread", "invalid file descriptor")
         }
     }
 
+    /// ## ------------------READV SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `readv_syscall()` reads from the file descriptor `fd` into the
+    /// `iovcnt` buffers described by `iovec`, filling each buffer completely
+    /// before moving on to the next one. It behaves like `read_syscall()`
+    /// into a single buffer as large as all of them together, and supports
+    /// regular files, character devices, pipes and sockets. On regular files
+    /// the read starts at the file offset, which is advanced by the number of
+    /// bytes read.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `readv_syscall()` receives three arguments:
+    /// * `fd` - the file descriptor to read from.
+    /// * `iovec` - the array of buffers to read into.
+    /// * `iovcnt` - the number of buffers in `iovec`.
+    ///
+    /// ### Returns
+    ///
+    /// The number of bytes read, which is 0 at the end of the file.
+    ///
+    /// ### Errors
+    ///
+    /// * EBADF - `fd` is not a valid file descriptor or is not open for
+    ///   reading.
+    /// * EINVAL - `iovcnt` is negative or greater than `IOV_MAX`, or `fd` is an
+    ///   epoll descriptor.
+    /// * EISDIR - `fd` refers to a directory.
+    /// * EAGAIN - `fd` is non-blocking and no data is available.
+    /// * EOPNOTSUPP - reading from streams is not supported.
+    /// * Any error of `recv_syscall()` when `fd` is a socket.
+    ///
+    /// ### Panics
+    ///
+    /// * If the inode number does not exist in the file system metadata table.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [readv(2)](https://man7.org/linux/man-pages/man2/readv.2.html)
+    pub fn readv_syscall(&self, fd: i32, iovec: *const interface::IovecStruct, iovcnt: i32) -> i32 {
+        self._readv_helper(fd, iovec, iovcnt, None, 0, "readv")
+    }
+
+    /// ## ------------------PREADV SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `preadv_syscall()` works like `readv_syscall()`, except that a
+    /// regular file is read starting at `offset` and its file offset is left
+    /// unchanged, the same way `pread_syscall()` relates to `read_syscall()`.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `preadv_syscall()` receives four arguments:
+    /// * `fd` - the file descriptor to read from.
+    /// * `iovec` - the array of buffers to read into.
+    /// * `iovcnt` - the number of buffers in `iovec`.
+    /// * `offset` - the position in the file to read from.
+    ///
+    /// ### Returns
+    ///
+    /// The number of bytes read, which is 0 at or past the end of the file.
+    ///
+    /// ### Errors
+    ///
+    /// Those of `readv_syscall()`, and:
+    /// * EINVAL - `offset` is negative.
+    /// * ESPIPE - `fd` is a socket, pipe, stream or epoll descriptor.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [preadv(2)](https://man7.org/linux/man-pages/man2/preadv.2.html)
+    pub fn preadv_syscall(
+        &self,
+        fd: i32,
+        iovec: *const interface::IovecStruct,
+        iovcnt: i32,
+        offset: isize,
+    ) -> i32 {
+        if offset < 0 {
+            return syscall_error(Errno::EINVAL, "preadv", "offset is negative");
+        }
+        self._readv_helper(fd, iovec, iovcnt, Some(offset as usize), 0, "preadv")
+    }
+
+    /// ## ------------------PREADV2 SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `preadv2_syscall()` extends `preadv_syscall()` with `flags`. An
+    /// `offset` of -1 reads from the file offset, like `readv_syscall()`.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `preadv2_syscall()` receives five arguments:
+    /// * `fd` - the file descriptor to read from.
+    /// * `iovec` - the array of buffers to read into.
+    /// * `iovcnt` - the number of buffers in `iovec`.
+    /// * `offset` - the position in the file to read from, or -1.
+    /// * `flags` - a combination of the following:
+    ///   * `RWF_NOWAIT` - don't wait for data that is not available, failing
+    ///     with EAGAIN instead. Regular files never have to wait.
+    ///   * `RWF_HIPRI`, `RWF_DSYNC`, `RWF_SYNC`, `RWF_APPEND` - accepted, but
+    ///     have no effect on reads.
+    ///
+    /// ### Returns
+    ///
+    /// The number of bytes read.
+    ///
+    /// ### Errors
+    ///
+    /// Those of `preadv_syscall()`, and:
+    /// * EINVAL - `offset` is less than -1.
+    /// * EOPNOTSUPP - `flags` contains an unknown flag, or `RWF_NOWAIT` is used
+    ///   on a socket.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [preadv2(2)](https://man7.org/linux/man-pages/man2/preadv2.2.html)
+    pub fn preadv2_syscall(
+        &self,
+        fd: i32,
+        iovec: *const interface::IovecStruct,
+        iovcnt: i32,
+        offset: isize,
+        flags: i32,
+    ) -> i32 {
+        if flags & !RWF_SUPPORTED != 0 {
+            return syscall_error(Errno::EOPNOTSUPP, "preadv2", "unknown flags");
+        }
+        let offset = match offset {
+            -1 => None,
+            _ if offset < 0 => {
+                return syscall_error(Errno::EINVAL, "preadv2", "offset is negative");
+            }
+            _ => Some(offset as usize),
+        };
+        self._readv_helper(fd, iovec, iovcnt, offset, flags, "preadv2")
+    }
+
+    /// ### Description
+    ///
+    /// The `_readv_helper()` implements `readv_syscall()`, `preadv_syscall()`
+    /// and `preadv2_syscall()`. Regular files are read at `offset`, or at the
+    /// file offset of `fd` which is then advanced when `offset` is `None`.
+    /// Other kinds of descriptors can't be read at an offset. Data received
+    /// from a socket is copied out to the buffers after a single receive.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fd` - the file descriptor to read from.
+    /// * `iovec` - the array of buffers to read into.
+    /// * `iovcnt` - the number of buffers in `iovec`.
+    /// * `offset` - where in a regular file to read, if not at its file offset.
+    /// * `flags` - the `RWF_*` flags of `preadv2_syscall()`, or 0.
+    /// * `syscallname` - the name of the calling syscall, used in errors.
+    fn _readv_helper(
+        &self,
+        fd: i32,
+        iovec: *const interface::IovecStruct,
+        iovcnt: i32,
+        offset: Option<usize>,
+        flags: i32,
+        syscallname: &str,
+    ) -> i32 {
+        if !(0..=IOV_MAX).contains(&iovcnt) {
+            return syscall_error(Errno::EINVAL, syscallname, "iovcnt is out of range");
+        }
+        let checkedfd = match self.get_filedescriptor(fd) {
+            Ok(checkedfd) => checkedfd,
+            Err(_) => return syscall_error(Errno::EBADF, syscallname, "invalid file descriptor"),
+        };
+        let mut unlocked_fd = checkedfd.write();
+        if let Some(filedesc_enum) = &mut *unlocked_fd {
+            // only regular files can be read at an offset
+            if offset.is_some() && !matches!(filedesc_enum, File(_)) {
+                return syscall_error(
+                    Errno::ESPIPE,
+                    syscallname,
+                    "file descriptor can't be read at an offset",
+                );
+            }
+            match filedesc_enum {
+                File(ref mut normalfile_filedesc_obj) => {
+                    if is_wronly(normalfile_filedesc_obj.flags) {
+                        return syscall_error(
+                            Errno::EBADF,
+                            syscallname,
+                            "specified file not open for reading",
+                        );
+                    }
+
+                    let inodeobj = FS_METADATA
+                        .inodetable
+                        .get(&normalfile_filedesc_obj.inode)
+                        .unwrap();
+                    match &*inodeobj {
+                        Inode::File(normalfile_inode_obj) => {
+                            let position = offset.unwrap_or(normalfile_filedesc_obj.position);
+                            // Nothing is left to read at or past the end of the file
+                            let bytesread = if position >= normalfile_inode_obj.size {
+                                0
+                            } else {
+                                let fileobject =
+                                    FILEOBJECTTABLE.get(&normalfile_filedesc_obj.inode).unwrap();
+                                let mut bufs = interface::iovec_to_ioslice_mut(iovec, iovcnt);
+                                fileobject.read_vectored_at(&mut bufs, position).unwrap()
+                            };
+                            // Move position forward by the number of bytes we've read, unless
+                            // we read at an explicit offset
+                            if offset.is_none() {
+                                normalfile_filedesc_obj.position = position + bytesread;
+                            }
+                            // The inode has to be released before its access time can be
+                            // updated
+                            drop(inodeobj);
+                            Self::_update_atime(normalfile_filedesc_obj.inode);
+                            bytesread as i32
+                        }
+                        // Character devices are read one buffer at a time
+                        Inode::CharDev(char_inode_obj) => {
+                            let mut bytesread = 0;
+                            for mut iov in interface::iovec_to_ioslice_mut(iovec, iovcnt) {
+                                let ret = self._read_chr_file(
+                                    char_inode_obj,
+                                    iov.as_mut_ptr(),
+                                    iov.len(),
+                                );
+                                if ret < 0 {
+                                    return ret;
+                                }
+                                bytesread += ret;
+                            }
+                            bytesread
+                        }
+                        Inode::Socket(_) => {
+                            panic!("{}(): Socket inode found on a filedesc fd", syscallname)
+                        }
+                        Inode::Symlink(_) => {
+                            panic!("{}(): symlink inode found on a filedesc fd", syscallname)
+                        }
+                        // FIFOs are always opened as pipes, so a "File" type fd can never
+                        // refer to one.
+                        Inode::Fifo(_) => {
+                            panic!("{}(): FIFO inode found on a filedesc fd", syscallname)
+                        }
+                        Inode::Dir(_) => syscall_error(
+                            Errno::EISDIR,
+                            syscallname,
+                            "attempted to read from a directory",
+                        ),
+                    }
+                }
+                // Like `read_syscall()`, this is a receive on the socket, which is done into
+                // one buffer that is then copied out to the iovecs
+                Socket(_) => {
+                    if flags & RWF_NOWAIT != 0 {
+                        return syscall_error(
+                            Errno::EOPNOTSUPP,
+                            syscallname,
+                            "RWF_NOWAIT is not supported for sockets",
+                        );
+                    }
+                    drop(unlocked_fd);
+                    let total = interface::iovec_to_ioslice_mut(iovec, iovcnt)
+                        .iter()
+                        .map(|iov| iov.len())
+                        .sum();
+                    let mut data = vec![0u8; total];
+                    let ret = self.recv_common(fd, data.as_mut_ptr(), total, 0, &mut None);
+                    if ret > 0 {
+                        interface::scatter_to_iovec(iovec, iovcnt, &data[..ret as usize]);
+                    }
+                    ret
+                }
+                Stream(_) => syscall_error(
+                    Errno::EOPNOTSUPP,
+                    syscallname,
+                    "reading from stdin not implemented yet",
+                ),
+                Epoll(_) => syscall_error(
+                    Errno::EINVAL,
+                    syscallname,
+                    "fd is attached to an object which is unsuitable for reading",
+                ),
+                // These are read the same way as in `read_syscall()`, into one buffer that is
+                // then copied out to the iovecs
+                EventFd(_) | TimerFd(_) | SignalFd(_) => {
+                    if flags & RWF_NOWAIT != 0 {
+                        return syscall_error(
+                            Errno::EOPNOTSUPP,
+                            syscallname,
+                            "RWF_NOWAIT is not supported for this fd type",
+                        );
+                    }
+                    drop(unlocked_fd);
+                    let total = interface::iovec_to_ioslice_mut(iovec, iovcnt)
+                        .iter()
+                        .map(|iov| iov.len())
+                        .sum();
+                    let mut data = vec![0u8; total];
+                    let ret = self.read_syscall(fd, data.as_mut_ptr(), total);
+                    if ret > 0 {
+                        interface::scatter_to_iovec(iovec, iovcnt, &data[..ret as usize]);
+                    }
+                    ret
+                }
+                // Pipes are read the same way as in `read_syscall()`, where RWF_NOWAIT
+                // makes a blocking pipe behave like a non-blocking one for this read
+                Pipe(pipe_filedesc_obj) => {
+                    if is_wronly(pipe_filedesc_obj.flags) {
+                        return syscall_error(
+                            Errno::EBADF,
+                            syscallname,
+                            "specified file not open for reading",
+                        );
+                    }
+                    let nonblocking =
+                        pipe_filedesc_obj.flags & O_NONBLOCK != 0 || flags & RWF_NOWAIT != 0;
+                    loop {
+                        let ret = pipe_filedesc_obj.pipe.read_vectored_from_pipe(
+                            iovec,
+                            iovcnt,
+                            nonblocking,
+                        );
+                        // keep waiting for data, checking for cancellation in between
+                        if !nonblocking && ret == -(Errno::EAGAIN as i32) {
+                            if self
+                                .cancelstatus
+                                .load(interface::RustAtomicOrdering::Relaxed)
+                            {
+                                loop {
+                                    interface::cancelpoint(self.cageid);
+                                }
+                            }
+                            continue;
+                        }
+                        return ret;
+                    }
+                }
+            }
+        } else {
+            syscall_error(Errno::EBADF, syscallname, "invalid file descriptor")
+        }
+    }
+
     /// ### Description
     ///
     /// The `_read_chr_file()` helper function is used by `read_syscall()` and
@@ -1984,9 +3453,10 @@ impl Cage {
     /// Upon successful completion of this call, we return the number of bytes
     /// written. This number will never be greater than `count`. The value
     /// returned may be less than `count` if the write_syscall() was
-    /// interrupted by a signal, or if the file is a pipe or FIFO or special
+    /// interrupted by a signal, if the file is a pipe or FIFO or special
     /// file and has fewer than `count` bytes immediately available for
-    /// writing.
+    /// writing, or if the write would grow the file beyond the soft
+    /// RLIMIT_FSIZE of the cage.
     ///
     /// ### Errors
     ///
@@ -1995,6 +3465,8 @@ impl Cage {
     /// * EISDIR - The file descriptor opened for writing is a directory.
     /// * EINVAL - File descriptor is attached to an object which is unsuitable
     ///   for writing.
+    /// * EFBIG - The current position is at or beyond the soft RLIMIT_FSIZE of
+    ///   the cage. SIGXFSZ is raised as well.
     ///
     /// ### Panics
     ///
@@ -2027,6 +3499,21 @@ impl Cage {
                     "write",
                     "fd is attached to an object which is unsuitable for writing",
                 ),
+                // Writing to an eventfd adds to its counter, which may block until another
+                // thread reads from the same descriptor, so the file descriptor is released
+                // before writing.
+                EventFd(eventfd_filedesc_obj) => {
+                    let eventfd = eventfd_filedesc_obj.eventfd.clone();
+                    let nonblocking = eventfd_filedesc_obj.flags & O_NONBLOCK != 0;
+                    drop(unlocked_fd);
+                    self._write_eventfd(&eventfd, nonblocking, buf, count)
+                }
+                // Timerfds and signalfds can only be read from.
+                TimerFd(_) | SignalFd(_) => syscall_error(
+                    Errno::EINVAL,
+                    "write",
+                    "fd is attached to an object which is unsuitable for writing",
+                ),
 
                 // We must borrow the filedesc object as a mutable reference to update the position
                 File(ref mut normalfile_filedesc_obj) => {
@@ -2066,6 +3553,18 @@ impl Cage {
                             // Get the current position of the File Descriptor Object.
                             let position = normalfile_filedesc_obj.position;
 
+                            // The file may not grow beyond RLIMIT_FSIZE, so only the part of
+                            // the data below the limit is written. If nothing can be written,
+                            // the write fails instead.
+                            let limit = self.get_rlimit(RLIMIT_FSIZE).rlim_cur;
+                            if count > 0 && position as u64 >= limit {
+                                return self._file_too_large("write");
+                            }
+                            let count = interface::rust_min(
+                                count as u64,
+                                limit.saturating_sub(position as u64),
+                            ) as usize;
+
                             // Calculate the number of blank bytes needed to pad the file
                             // if the current position is past the end of the file, because
                             // the space between the end of the file and the new write position
@@ -2073,6 +3572,19 @@ impl Cage {
                             let filesize = normalfile_inode_obj.size;
                             let blankbytecount = position as isize - filesize as isize;
 
+                            // Growing the file takes up space in the file system and the
+                            // quota of its owner, and the write is cut short where either
+                            // runs out
+                            let count = match self._grow_for_write(
+                                normalfile_inode_obj,
+                                position,
+                                count,
+                                "write",
+                            ) {
+                                Ok(count) => count,
+                                Err(e) => return e,
+                            };
+
                             // Get the mutable file object associated with the file descriptor
                             // object
                             let mut fileobject = FILEOBJECTTABLE
@@ -2101,6 +3613,13 @@ impl Cage {
                             let byteswritten = fileobject.writeat(buf, count, position).unwrap();
                             // Move position forward by the number of bytes we've written
                             normalfile_filedesc_obj.position = position + byteswritten;
+                            // Writing data modifies the file. Like the "lazytime" mount
+                            // option, the new times are only logged along with the next
+                            // metadata change rather than after every write; they are
+                            // persisted with the rest of the metadata on shutdown.
+                            let time = interface::timestamp_nanos();
+                            normalfile_inode_obj.mtime = time;
+                            normalfile_inode_obj.ctime = time;
                             // Update the file size if necessary
                             if normalfile_filedesc_obj.position > normalfile_inode_obj.size {
                                 normalfile_inode_obj.size = normalfile_filedesc_obj.position;
@@ -2127,6 +3646,16 @@ impl Cage {
                         Inode::Socket(_) => {
                             panic!("write(): Socket inode found on a filedesc fd")
                         }
+                        // Symbolic links are always resolved or rejected by open, so a "File"
+                        // type fd can never refer to one.
+                        Inode::Symlink(_) => {
+                            panic!("write(): symlink inode found on a filedesc fd")
+                        }
+                        // FIFOs are always opened as pipes, so a "File" type fd can never
+                        // refer to one.
+                        Inode::Fifo(_) => {
+                            panic!("write(): FIFO inode found on a filedesc fd")
+                        }
                     }
                 }
 
@@ -2222,7 +3751,8 @@ impl Cage {
     /// Upon successful completion of this call, we return the number of bytes
     /// written. This number will never be greater than `count`. The value
     /// returned may be less than `count` if there is insufficient space in
-    /// the file system or if the pwrite is interrupted by a signal.
+    /// the file system, if the pwrite is interrupted by a signal, or if the
+    /// write would grow the file beyond the soft RLIMIT_FSIZE of the cage.
     ///
     /// ### Errors
     ///
@@ -2231,6 +3761,8 @@ impl Cage {
     /// * `EISDIR` - The file descriptor opened for writing is a directory.
     /// * `ESPIPE` - The file descriptor opened for writing is either of type
     ///   Socket, Stream, Pipe, or Epoll.
+    /// * `EFBIG` - `offset` is at or beyond the soft RLIMIT_FSIZE of the cage.
+    ///   SIGXFSZ is raised as well.
     ///
     /// ### Panics
     ///
@@ -2283,6 +3815,12 @@ impl Cage {
                     "pwrite",
                     "file descriptor is associated with an epollfd, cannot seek",
                 ),
+                // Eventfds, timerfds and signalfds don't hold any data to seek in either.
+                EventFd(_) | TimerFd(_) | SignalFd(_) => syscall_error(
+                    Errno::ESPIPE,
+                    "pwrite",
+                    "file descriptor is associated with an eventfd, timerfd or signalfd, cannot seek",
+                ),
                 // We must borrow the filedesc object as a mutable reference to update the position
                 File(ref mut normalfile_filedesc_obj) => {
                     // Return an error if the file cannot be not opened for writing.
@@ -2320,6 +3858,27 @@ impl Cage {
                             let filesize = normalfile_inode_obj.size;
                             let blankbytecount = offset - filesize as isize;
 
+                            // RLIMIT_FSIZE is applied the same way as in `write_syscall()`
+                            let limit = self.get_rlimit(RLIMIT_FSIZE).rlim_cur;
+                            if count > 0 && position as u64 >= limit {
+                                return self._file_too_large("pwrite");
+                            }
+                            let count = interface::rust_min(
+                                count as u64,
+                                limit.saturating_sub(position as u64),
+                            ) as usize;
+
+                            // Space is reserved the same way as in `write_syscall()`
+                            let count = match self._grow_for_write(
+                                normalfile_inode_obj,
+                                position,
+                                count,
+                                "pwrite",
+                            ) {
+                                Ok(count) => count,
+                                Err(e) => return e,
+                            };
+
                             let mut fileobject = FILEOBJECTTABLE
                                 .get_mut(&normalfile_filedesc_obj.inode)
                                 .unwrap();
@@ -2344,6 +3903,11 @@ impl Cage {
                             // `writeat` function, which returns the number of bytes written.
                             let retval = fileobject.writeat(buf, count, position).unwrap();
                             let newposition = position + retval;
+                            // Writing data modifies the file, see `write_syscall()` for when
+                            // the new times are logged
+                            let time = interface::timestamp_nanos();
+                            normalfile_inode_obj.mtime = time;
+                            normalfile_inode_obj.ctime = time;
 
                             // Update the file size once data is written to the file
                             if newposition > filesize {
@@ -2374,6 +3938,16 @@ impl Cage {
                         Inode::Socket(_) => {
                             panic!("pwrite: socket fd and inode don't match types")
                         }
+                        // Symbolic links are always resolved or rejected by open, so a "File"
+                        // type fd can never refer to one.
+                        Inode::Symlink(_) => {
+                            panic!("pwrite: symlink inode found on a filedesc fd")
+                        }
+                        // FIFOs are always opened as pipes, so a "File" type fd can never
+                        // refer to one.
+                        Inode::Fifo(_) => {
+                            panic!("pwrite: FIFO inode found on a filedesc fd")
+                        }
                     }
                 }
             }
@@ -2516,25 +4090,82 @@ impl Cage {
         iovec: *const interface::IovecStruct,
         iovcnt: i32,
     ) -> i32 {
-        let checkedfd = self.get_filedescriptor(fd).unwrap();
-        let mut unlocked_fd = checkedfd.write();
-        if let Some(filedesc_enum) = &mut *unlocked_fd {
-            // we're only implementing this for INET/tcp sockets right now
-            match filedesc_enum {
-                Socket(socket_filedesc_obj) => {
-                    let sock_tmp = socket_filedesc_obj.handle.clone();
-                    let sockhandle = sock_tmp.write();
-                    // Check the domain of the socket (IPv4 or IPv6)
-                    match sockhandle.domain {
-                        AF_INET | AF_INET6 => match sockhandle.protocol {
-                            IPPROTO_TCP => {
-                                // We need to be either fully connected or connected for write-only.
-                                if (sockhandle.state != ConnState::CONNECTED)
-                                    && (sockhandle.state != ConnState::CONNWRONLY)
+        self._writev_helper(fd, iovec, iovcnt, None, 0, "writev")
+    }
+
+    /// ### Description
+    ///
+    /// The `_writev_helper()` implements `writev_syscall()`,
+    /// `pwritev_syscall()` and `pwritev2_syscall()`. Regular files are written
+    /// at `offset`, or at the file offset of `fd` which is then advanced when
+    /// `offset` is `None`. Other kinds of descriptors can't be written at an
+    /// offset.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fd` - the file descriptor to write to.
+    /// * `iovec` - the array of buffers holding the data.
+    /// * `iovcnt` - the number of buffers in `iovec`.
+    /// * `offset` - where in a regular file to write, if not at its file
+    ///   offset.
+    /// * `flags` - the `RWF_*` flags of `pwritev2_syscall()`, or 0.
+    /// * `syscallname` - the name of the calling syscall, used in errors.
+    ///
+    /// ### Errors
+    ///
+    /// Besides the errors of `writev_syscall()`:
+    /// * `EINVAL` - `iovcnt` is negative or greater than `IOV_MAX`.
+    /// * `ESPIPE` - an offset was given for a socket, pipe or stream.
+    /// * `EOPNOTSUPP` - `RWF_NOWAIT` was given for a socket.
+    fn _writev_helper(
+        &self,
+        fd: i32,
+        iovec: *const interface::IovecStruct,
+        iovcnt: i32,
+        offset: Option<usize>,
+        flags: i32,
+        syscallname: &str,
+    ) -> i32 {
+        if !(0..=IOV_MAX).contains(&iovcnt) {
+            return syscall_error(Errno::EINVAL, syscallname, "iovcnt is out of range");
+        }
+        let checkedfd = match self.get_filedescriptor(fd) {
+            Ok(checkedfd) => checkedfd,
+            Err(_) => return syscall_error(Errno::EBADF, syscallname, "invalid file descriptor"),
+        };
+        let mut unlocked_fd = checkedfd.write();
+        if let Some(filedesc_enum) = &mut *unlocked_fd {
+            // only regular files can be written at an offset
+            if offset.is_some() && !matches!(filedesc_enum, File(_)) {
+                return syscall_error(
+                    Errno::ESPIPE,
+                    syscallname,
+                    "file descriptor can't be written at an offset",
+                );
+            }
+            // we're only implementing this for INET/tcp sockets right now
+            match filedesc_enum {
+                Socket(socket_filedesc_obj) => {
+                    if flags & RWF_NOWAIT != 0 {
+                        return syscall_error(
+                            Errno::EOPNOTSUPP,
+                            syscallname,
+                            "RWF_NOWAIT is not supported for sockets",
+                        );
+                    }
+                    let sock_tmp = socket_filedesc_obj.handle.clone();
+                    let sockhandle = sock_tmp.write();
+                    // Check the domain of the socket (IPv4 or IPv6)
+                    match sockhandle.domain {
+                        AF_INET | AF_INET6 => match sockhandle.protocol {
+                            IPPROTO_TCP => {
+                                // We need to be either fully connected or connected for write-only.
+                                if (sockhandle.state != ConnState::CONNECTED)
+                                    && (sockhandle.state != ConnState::CONNWRONLY)
                                 {
                                     return syscall_error(
                                         Errno::ENOTCONN,
-                                        "writev",
+                                        syscallname,
                                         "The descriptor is not connected",
                                     );
                                 }
@@ -2552,7 +4183,7 @@ impl Cage {
                                         Ok(i) => {
                                             return syscall_error(
                                                 i,
-                                                "writev",
+                                                syscallname,
                                                 "The libc call to writev failed!",
                                             );
                                         }
@@ -2567,11 +4198,22 @@ impl Cage {
                             _ => {
                                 return syscall_error(
                                     Errno::EOPNOTSUPP,
-                                    "writev",
+                                    syscallname,
                                     "System call not implemented for this socket protocol",
                                 );
                             }
                         },
+                        // Datagram and seqpacket sockets send everything as one message
+                        AF_UNIX if sockhandle.is_unix_msg() => {
+                            drop(sockhandle);
+                            drop(unlocked_fd);
+                            let data = if iovcnt == 0 {
+                                vec![]
+                            } else {
+                                interface::concat_iovec_to_slice(iovec, iovcnt)
+                            };
+                            self.send_syscall(fd, data.as_ptr(), data.len(), 0)
+                        }
                         AF_UNIX => {
                             match sockhandle.protocol {
                                 IPPROTO_TCP => {
@@ -2582,7 +4224,7 @@ impl Cage {
                                     {
                                         return syscall_error(
                                             Errno::ENOTCONN,
-                                            "writev",
+                                            syscallname,
                                             "The descriptor is not connected",
                                         );
                                     }
@@ -2601,7 +4243,7 @@ impl Cage {
                                         )
                                             as i32,
                                         None => {
-                                            return syscall_error(Errno::EAGAIN, "writev", "there is no data available right now, try again later");
+                                            return syscall_error(Errno::EAGAIN, syscallname, "there is no data available right now, try again later");
                                         }
                                     };
                                     if retval == -(Errno::EPIPE as i32) {
@@ -2621,7 +4263,7 @@ impl Cage {
                         _ => {
                             return syscall_error(
                                 Errno::EOPNOTSUPP,
-                                "writev",
+                                syscallname,
                                 "System call not implemented for this socket domain",
                             );
                         }
@@ -2632,12 +4274,12 @@ impl Cage {
                     if is_rdonly(pipe_filedesc_obj.flags) {
                         return syscall_error(
                             Errno::EBADF,
-                            "write",
+                            syscallname,
                             "specified pipe not open for writing",
                         );
                     }
 
-                    let mut nonblocking = false;
+                    let mut nonblocking = flags & RWF_NOWAIT != 0;
                     // Check if the O_NONBLOCK flag is set in the pipe's flags.
                     // If it is, enable non-blocking mode for the write operation.
                     //Non-blocking I/O allows a process to continue doing other tasks while waiting
@@ -2656,6 +4298,24 @@ impl Cage {
                     } // Trigger SIGPIPE
                     retval
                 }
+                // An eventfd is written the same way as in `write_syscall()`, from the
+                // iovecs gathered into one buffer
+                EventFd(_) => {
+                    if flags & RWF_NOWAIT != 0 {
+                        return syscall_error(
+                            Errno::EOPNOTSUPP,
+                            syscallname,
+                            "RWF_NOWAIT is not supported for this fd type",
+                        );
+                    }
+                    drop(unlocked_fd);
+                    let data = if iovcnt == 0 {
+                        vec![]
+                    } else {
+                        interface::concat_iovec_to_slice(iovec, iovcnt)
+                    };
+                    self.write_syscall(fd, data.as_ptr(), data.len())
+                }
                 Stream(_stream_filedesc_obj) => {
                     // Convert the iovec array to a single contiguous slice of bytes
                     let iovecslice = interface::concat_iovec_to_slice(iovec, iovcnt);
@@ -2663,7 +4323,7 @@ impl Cage {
                     // Log the data from the slice. Handle the Result from log_from_slice
                     match interface::log_from_slice(fd, &iovecslice) {
                         Ok(bytes_written) => bytes_written,
-                        Err(err_msg) => syscall_error(Errno::EIO, "writev", &err_msg),
+                        Err(err_msg) => syscall_error(Errno::EIO, syscallname, &err_msg),
                     }
                 }
                 File(ref mut normalfile_filedesc_obj) => {
@@ -2672,7 +4332,7 @@ impl Cage {
                         // Return error if the file is read-only
                         return syscall_error(
                             Errno::EBADF,
-                            "writev",
+                            syscallname,
                             "specified file not open for writing",
                         );
                     }
@@ -2687,12 +4347,33 @@ impl Cage {
                         Inode::File(ref mut normalfile_inode_obj) => {
                             // The inode object retrieved is of type File. We get a mutable
                             // reference to the actual inode data.
-                            let position = normalfile_filedesc_obj.position; 
-                            // Get the current write position from the file descriptor object
+                            // Get the write position, which is the end of the file for
+                            // RWF_APPEND, otherwise the given offset or the current position
+                            // of the file descriptor object
+                            let position = if flags & RWF_APPEND != 0 {
+                                normalfile_inode_obj.size
+                            } else {
+                                offset.unwrap_or(normalfile_filedesc_obj.position)
+                            };
                             let filesize = normalfile_inode_obj.size; // Get the file size
                             let blankbytecount = position as isize - filesize as isize; 
                             // Calculate the difference between the required and desired file position
 
+                            // Space for the data is reserved the same way as in
+                            // `write_syscall()`, so only part of it may be written
+                            // Create IoSlice objects from the raw iovec pointer
+                            let iovs = interface::iovec_to_ioslice(iovec, iovcnt);
+                            let total: usize = iovs.iter().map(|iov| iov.len()).sum();
+                            let count = match self._grow_for_write(
+                                normalfile_inode_obj,
+                                position,
+                                total,
+                                syscallname,
+                            ) {
+                                Ok(count) => count,
+                                Err(e) => return e,
+                            };
+
                             // Retrieve the file object from the file object table
                             let mut fileobject = FILEOBJECTTABLE
                                 .get_mut(&normalfile_filedesc_obj.inode)
@@ -2712,15 +4393,33 @@ impl Cage {
                                 }
                             }
 
-                            // Create IoSlice objects from the raw iovec pointer
-                            let iovs = interface::iovec_to_ioslice(iovec, iovcnt);
-
-                            // Write to the file using the vectored IO method
-                            if let Ok(byteswritten) = fileobject.write_vectored_at(&iovs, position)
-                            {
-                                // Move position forward by the number of bytes we've written
-                                normalfile_filedesc_obj.position = position + byteswritten as usize;
-                                let newposition = normalfile_filedesc_obj.position;
+                            // Write to the file using the vectored IO method, unless the
+                            // data has to be cut short, in which case the part of it that
+                            // fits is written in one piece
+                            let written = if count < total {
+                                let data = interface::concat_iovec_to_slice(iovec, iovcnt);
+                                fileobject.writeat(data.as_ptr(), count, position)
+                            } else {
+                                fileobject.write_vectored_at(&iovs, position)
+                            };
+                            if let Ok(byteswritten) = written {
+                                // Move position forward by the number of bytes we've written,
+                                // unless we wrote at an explicit offset
+                                let newposition = position + byteswritten;
+                                if offset.is_none() {
+                                    normalfile_filedesc_obj.position = newposition;
+                                }
+                                // RWF_DSYNC and RWF_SYNC make the data durable before returning
+                                if flags & RWF_SYNC != 0 {
+                                    fileobject.fsync().unwrap();
+                                } else if flags & RWF_DSYNC != 0 {
+                                    fileobject.fdatasync().unwrap();
+                                }
+                                // Writing data modifies the file, see `write_syscall()` for
+                                // when the new times are logged
+                                let time = interface::timestamp_nanos();
+                                normalfile_inode_obj.mtime = time;
+                                normalfile_inode_obj.ctime = time;
 
                                 // Update file size if necessary
                                 if newposition > normalfile_inode_obj.size {
@@ -2732,7 +4431,11 @@ impl Cage {
 
                                 byteswritten as i32
                             } else {
-                                syscall_error(Errno::EIO, "writev", "Failed to write data to file")
+                                syscall_error(
+                                    Errno::EIO,
+                                    syscallname,
+                                    "Failed to write data to file",
+                                )
                             }
                         }
                         // Handle character device file
@@ -2757,10 +4460,20 @@ impl Cage {
                         Inode::Socket(_) => {
                             panic!("writev(): Socket inode found on a filedesc fd")
                         }
+                        // Symbolic links are always resolved or rejected by open, so a "File"
+                        // type fd can never refer to one.
+                        Inode::Symlink(_) => {
+                            panic!("writev(): symlink inode found on a filedesc fd")
+                        }
+                        // FIFOs are always opened as pipes, so a "File" type fd can never
+                        // refer to one.
+                        Inode::Fifo(_) => {
+                            panic!("writev(): FIFO inode found on a filedesc fd")
+                        }
 
                         Inode::Dir(_) => syscall_error(
                             Errno::EISDIR,
-                            "writev",
+                            syscallname,
                             "attempted to write to a directory",
                         ),
                     }
@@ -2768,1874 +4481,4323 @@ impl Cage {
                 _ => {
                     return syscall_error(
                         Errno::EOPNOTSUPP,
-                        "writev",
+                        syscallname,
                         "System call not implemented for this fd type",
                     );
                 }
             }
         } else {
-            syscall_error(Errno::EBADF, "write", "invalid file descriptor")
+            syscall_error(Errno::EBADF, syscallname, "invalid file descriptor")
         }
     }
 
-    /// ## -------------------------------- LSEEK SYSCALL -------------------------
+    /// ## ------------------PWRITEV SYSCALL------------------
     /// ### Description
     ///
-    /// The `lseek_syscall()` repositions the offset of the file associated
-    /// with the file descriptor `fd` to a new location as specified by
-    /// `offset` and `whence`. The new offset is calculated relative to the
-    /// position specified by whence. This syscall allows the file offset to
-    /// be set beyond the end of the file (but this does not change the size
-    /// of the file). If data is later written at this point, subsequent reads
-    /// of the data in the gap (a "hole") return null bytes ('\0') until data
-    /// is actually written into the gap.
+    /// The `pwritev_syscall()` works like `writev_syscall()`, except that a
+    /// regular file is written starting at `offset` and its file offset is
+    /// left unchanged, the same way `pwrite_syscall()` relates to
+    /// `write_syscall()`.
     ///
     /// ### Function Arguments
     ///
-    /// The `lseek_syscall()` receives three arguments:
-    /// * `fd` - It is the file descriptor, which identifies the file being
-    ///   accessed
-    /// * `offset` - It determines the position in the file where the file
-    ///   offset should be set. It specifies the number of bytes from a
-    ///   particular reference point.
-    /// * `whence` - It determines how the seek operation is performed. There
-    ///   are three possible values for whence: `SEEK_SET`: The file offset is
-    ///   set to 0 + offset bytes. `SEEK_CUR`: The file offset is set to its
-    ///   current location plus offset bytes. `SEEK_END`: The file offset is set
-    ///   to the size of the file plus offset bytes.
+    /// The `pwritev_syscall()` receives four arguments:
+    /// * `fd` - the file descriptor to write to.
+    /// * `iovec` - the array of buffers holding the data.
+    /// * `iovcnt` - the number of buffers in `iovec`.
+    /// * `offset` - the position in the file to write at.
     ///
     /// ### Returns
     ///
-    /// Upon successful completion of this call, we return the resulting offset
-    /// location as measured in bytes from the beginning of the file.
-    /// Otherwise, errors or panics are returned for different scenarios.
+    /// The number of bytes written.
     ///
     /// ### Errors
     ///
-    /// * `EINVAL` - The `whence` type is different from the available values;
-    ///   seek to a position before the start of the file; seek to after last
-    ///   position in directory
-    /// * `ESPIPE` - Seek is not possible when file descriptor is a
-    ///   socket/pipe/stream/epoll.
+    /// Those of `writev_syscall()`, and:
+    /// * EINVAL - `offset` is negative, or `iovcnt` is negative or greater than
+    ///   `IOV_MAX`.
+    /// * ESPIPE - `fd` is a socket, pipe or stream.
     ///
+    /// For more detailed description of all the commands and return values, see
+    /// [pwritev(2)](https://man7.org/linux/man-pages/man2/pwritev.2.html)
+    pub fn pwritev_syscall(
+        &self,
+        fd: i32,
+        iovec: *const interface::IovecStruct,
+        iovcnt: i32,
+        offset: isize,
+    ) -> i32 {
+        if offset < 0 {
+            return syscall_error(Errno::EINVAL, "pwritev", "offset is negative");
+        }
+        self._writev_helper(fd, iovec, iovcnt, Some(offset as usize), 0, "pwritev")
+    }
+
+    /// ## ------------------PWRITEV2 SYSCALL------------------
+    /// ### Description
     ///
-    /// ### Panics
+    /// The `pwritev2_syscall()` extends `pwritev_syscall()` with `flags`. An
+    /// `offset` of -1 writes at the file offset, like `writev_syscall()`.
     ///
-    /// * When there is some issue fetching the file descriptor.
-    /// * When the file type file descriptor contains a Socket as an inode.
+    /// ### Function Arguments
+    ///
+    /// The `pwritev2_syscall()` receives five arguments:
+    /// * `fd` - the file descriptor to write to.
+    /// * `iovec` - the array of buffers holding the data.
+    /// * `iovcnt` - the number of buffers in `iovec`.
+    /// * `offset` - the position in the file to write at, or -1.
+    /// * `flags` - a combination of the following:
+    ///   * `RWF_APPEND` - write at the end of a regular file, whatever the
+    ///     offset. The file offset is only advanced if `offset` is -1.
+    ///   * `RWF_NOWAIT` - don't wait for room in a pipe, failing with EAGAIN
+    ///     instead. Regular files never have to wait.
+    ///   * `RWF_DSYNC`, `RWF_SYNC` - flush the data, or the data and metadata,
+    ///     of a regular file to disk before returning.
+    ///   * `RWF_HIPRI` - accepted, but has no effect.
+    ///
+    /// ### Returns
+    ///
+    /// The number of bytes written.
+    ///
+    /// ### Errors
+    ///
+    /// Those of `pwritev_syscall()`, and:
+    /// * EINVAL - `offset` is less than -1.
+    /// * EOPNOTSUPP - `flags` contains an unknown flag, or `RWF_NOWAIT` is used
+    ///   on a socket.
     ///
     /// For more detailed description of all the commands and return values, see
-    /// [lseek(2)](https://man7.org/linux/man-pages/man2/lseek.2.html)
-    pub fn lseek_syscall(&self, fd: i32, offset: isize, whence: i32) -> i32 {
-        //BUG
-        //If the provided file descriptor is out of bounds, get_filedescriptor returns
-        //Err(), unwrapping on which  produces a 'panic!'
-        //otherwise, file descriptor table entry is stored in 'checkedfd'
-        let checkedfd = self.get_filedescriptor(fd).unwrap();
-        // Acquire a write lock on the file descriptor to ensure exclusive access.
-        let mut unlocked_fd = checkedfd.write();
-
-        // Check if the file descriptor object is valid
-        if let Some(filedesc_enum) = &mut *unlocked_fd {
-            //confirm fd type is seekable
-            match filedesc_enum {
-                // Return an error for Sockets, as they do not support the concept of seeking to a
-                // specific offset because data arrives in a continuous stream from the network.
-                Socket(_) => syscall_error(
-                    Errno::ESPIPE,
-                    "lseek",
-                    "file descriptor is associated with a socket, cannot seek",
-                ),
-                // Return an error for Streams, as like sockets, streams are sequential and do not
-                // support seeking to an offset.
-                Stream(_) => syscall_error(
-                    Errno::ESPIPE,
-                    "lseek",
-                    "file descriptor is associated with a stream, cannot seek",
-                ),
-                // Return an error for Pipes, as they are designed for sequential reads and writes
-                // between processes. Seeking within a pipe would not be possible.
-                Pipe(_) => syscall_error(
-                    Errno::ESPIPE,
-                    "lseek",
-                    "file descriptor is associated with a pipe, cannot seek",
-                ),
-                // Return an error for Epoll, as Epoll file descriptors are for event notification
-                // and do not hold any data themselves, so seeking is not possible.
-                Epoll(_) => syscall_error(
-                    Errno::ESPIPE,
-                    "lseek",
-                    "file descriptor is associated with an epollfd, cannot seek",
-                ),
-                File(ref mut normalfile_filedesc_obj) => {
-                    // Get the inode object from the inode table associated with the file
-                    // descriptor.
-                    let inodeobj = FS_METADATA
-                        .inodetable
-                        .get(&normalfile_filedesc_obj.inode)
-                        .unwrap();
+    /// [pwritev2(2)](https://man7.org/linux/man-pages/man2/pwritev2.2.html)
+    pub fn pwritev2_syscall(
+        &self,
+        fd: i32,
+        iovec: *const interface::IovecStruct,
+        iovcnt: i32,
+        offset: isize,
+        flags: i32,
+    ) -> i32 {
+        if flags & !RWF_SUPPORTED != 0 {
+            return syscall_error(Errno::EOPNOTSUPP, "pwritev2", "unknown flags");
+        }
+        let offset = match offset {
+            -1 => None,
+            _ if offset < 0 => {
+                return syscall_error(Errno::EINVAL, "pwritev2", "offset is negative");
+            }
+            _ => Some(offset as usize),
+        };
+        self._writev_helper(fd, iovec, iovcnt, offset, flags, "pwritev2")
+    }
 
-                    // Match the type of inode object with the type (File, Socket, CharDev, Dir)
-                    // Handle files/directories differently
-                    match &*inodeobj {
-                        Inode::File(normalfile_inode_obj) => {
-                            // For file type inode, match the type of whence first, and based
-                            // on that, update the final position of the offset of the file.
-                            let eventualpos = match whence {
-                                // Simply update the offset of the file to the given value.
-                                SEEK_SET => offset,
-                                // Get the current offset position of the file and add `offset`.
-                                SEEK_CUR => normalfile_filedesc_obj.position as isize + offset,
-                                // Get the file size and add `offset` position.
-                                SEEK_END => normalfile_inode_obj.size as isize + offset,
-                                _ => {
-                                    return syscall_error(Errno::EINVAL, "lseek", "unknown whence");
-                                }
-                            };
+    /// ## ------------------SPLICE SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `splice_syscall()` moves bytes between two file descriptors, at
+    /// least one of which is a pipe, without them passing through the cage.
+    /// Bytes moved out of a pipe are taken out of it. The other descriptor may
+    /// be a regular file, a socket, a standard stream, or another pipe.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `splice_syscall()` receives six arguments:
+    /// * `fd_in` - the file descriptor to move bytes from.
+    /// * `off_in` - for a regular file, the position in the file to read at,
+    ///   which is advanced by the number of bytes read instead of the file
+    ///   offset. None reads at the file offset. Must be None for a pipe or a
+    ///   socket.
+    /// * `fd_out` - the file descriptor to move bytes to.
+    /// * `off_out` - like `off_in`, for `fd_out`.
+    /// * `len` - the most bytes to move.
+    /// * `flags` - a combination of the following:
+    ///   * `SPLICE_F_NONBLOCK` - don't wait on the pipes, failing with EAGAIN
+    ///     instead, and don't wait for data on a socket being read.
+    ///   * `SPLICE_F_MOVE`, `SPLICE_F_MORE`, `SPLICE_F_GIFT` - accepted, but
+    ///     have no effect.
+    ///
+    /// ### Returns
+    ///
+    /// The number of bytes moved, which is 0 once there is nothing more to
+    /// read from `fd_in`.
+    ///
+    /// ### Errors
+    ///
+    /// * EAGAIN - the move would have to wait and `SPLICE_F_NONBLOCK` is set,
+    ///   or one of the pipes is non-blocking.
+    /// * EBADF - either fd is not a valid file descriptor, `fd_in` is not open
+    ///   for reading, or `fd_out` is not open for writing.
+    /// * EINVAL - neither fd is a pipe, both refer to the same pipe, one of
+    ///   them is of a type that can't be spliced, an offset is negative, or
+    ///   `flags` contains an unknown flag.
+    /// * EPIPE - `fd_out` is a pipe whose read end has been closed.
+    /// * ESPIPE - an offset is given for a pipe or a socket.
+    ///
+    /// Errors from reading `fd_in` or writing `fd_out` are passed on.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [splice(2)](https://man7.org/linux/man-pages/man2/splice.2.html)
+    pub fn splice_syscall(
+        &self,
+        fd_in: i32,
+        off_in: Option<&mut i64>,
+        fd_out: i32,
+        off_out: Option<&mut i64>,
+        len: usize,
+        flags: u32,
+    ) -> i32 {
+        if flags & !SPLICE_F_ALL != 0 {
+            return syscall_error(Errno::EINVAL, "splice", "unknown flags");
+        }
+        let end_in = match self._splice_end(fd_in, off_in.is_some(), false, "splice") {
+            Ok(end) => end,
+            Err(e) => return e,
+        };
+        let end_out = match self._splice_end(fd_out, off_out.is_some(), true, "splice") {
+            Ok(end) => end,
+            Err(e) => return e,
+        };
+        for off in [&off_in, &off_out] {
+            if off.as_ref().is_some_and(|off| **off < 0) {
+                return syscall_error(Errno::EINVAL, "splice", "offset is negative");
+            }
+        }
+        let nonblocking = flags & SPLICE_F_NONBLOCK != 0;
 
-                            // Sanity check to make sure that the final position of seeking
-                            // doesn't go before position 0 in the file.
-                            if eventualpos < 0 {
-                                return syscall_error(
-                                    Errno::EINVAL,
-                                    "lseek",
-                                    "seek to before position 0 in file",
-                                );
+        match (end_in, end_out) {
+            (SpliceEnd::Pipe(pipe_in, flags_in), SpliceEnd::Pipe(pipe_out, flags_out)) => {
+                if pipe_in.same_pipe(&pipe_out) {
+                    return syscall_error(Errno::EINVAL, "splice", "can't splice a pipe to itself");
+                }
+                let nonblocking = nonblocking || (flags_in | flags_out) & O_NONBLOCK != 0;
+                let ret = self._splice_wait(nonblocking, || {
+                    Some(pipe_in.transfer_to(&pipe_out, len, true))
+                        .filter(|&ret| ret != -(Errno::EAGAIN as i32))
+                });
+                if ret == -(Errno::EPIPE as i32) {
+                    interface::lind_kill_from_id(self.cageid, SIGPIPE);
+                }
+                ret
+            }
+            (SpliceEnd::Pipe(pipe_in, flags_in), end_out) => {
+                let nonblocking = nonblocking || flags_in & O_NONBLOCK != 0;
+                let mut off_out = off_out;
+                self._splice_wait(nonblocking, || {
+                    pipe_in.consume_with(len, |data| match (&end_out, off_out.as_deref_mut()) {
+                        (SpliceEnd::File, Some(off)) => {
+                            let ret = self.pwrite_syscall(
+                                fd_out,
+                                data.as_ptr(),
+                                data.len(),
+                                *off as isize,
+                            );
+                            if ret > 0 {
+                                *off += ret as i64;
                             }
-                            // subsequent writes to the end of the file must zero pad up (filling
-                            // the gap between the current end of the file and the new position with
-                            // zero bytes ('\0')) until this point if we overran the end of our file
-                            // when seeking. This is currently supported in write and pwrite
-                            // syscalls.
-
-                            // Update the final position of the file descriptor object
-                            normalfile_filedesc_obj.position = eventualpos as usize;
-                            // Return the location that we sought to
-                            eventualpos as i32
+                            ret
                         }
-
-                        Inode::CharDev(_) => {
-                            0 // for character files, rather than seeking, we
-                              // transparently do nothing
+                        _ => self.write_syscall(fd_out, data.as_ptr(), data.len()),
+                    })
+                })
+            }
+            (end_in, SpliceEnd::Pipe(pipe_out, flags_out)) => {
+                let nonblocking = nonblocking || flags_out & O_NONBLOCK != 0;
+                let mut off_in = off_in;
+                let ret = self._splice_wait(nonblocking, || {
+                    pipe_out.produce_with(len, |buf| match (&end_in, off_in.as_deref_mut()) {
+                        (SpliceEnd::File, Some(off)) => {
+                            let ret = self.pread_syscall(
+                                fd_in,
+                                buf.as_mut_ptr(),
+                                buf.len(),
+                                *off as isize,
+                            );
+                            if ret > 0 {
+                                *off += ret as i64;
+                            }
+                            ret
                         }
-
-                        // A Sanity check is added to make sure that there is no such case when the
-                        // fd type is "File" and the inode type is "Socket". This state is ideally
-                        // not possible, so we panic in such cases.
-                        Inode::Socket(_) => {
-                            panic!("lseek: socket fd and inode don't match types")
-                        }
-
-                        Inode::Dir(dir_inode_obj) => {
-                            // For directory type inode, we seek between directory entries,
-                            // and based on the whence check, we update the final position.
-                            let eventualpos = match whence {
-                                // Simply update the offset of the directory to the given value.
-                                SEEK_SET => offset,
-                                // Get the current position of the directory pointing to a directory
-                                // entry and add the offset to it.
-                                SEEK_CUR => normalfile_filedesc_obj.position as isize + offset,
-                                // The Inode dictionary contains the directory entries mapped with
-                                // the inode object, and final position is updated by adding the
-                                // total count of the entries with the given offset.
-                                SEEK_END => {
-                                    dir_inode_obj.filename_to_inode_dict.len() as isize + offset
-                                }
-                                _ => {
-                                    return syscall_error(Errno::EINVAL, "lseek", "unknown whence");
-                                }
-                            };
-
-                            // Sanity check to make sure that the final position of seeking
-                            // doesn't go before position 0 in the directory.
-                            if eventualpos < 0 {
-                                return syscall_error(
-                                    Errno::EINVAL,
-                                    "lseek",
-                                    "seek to before position 0 in directory",
-                                );
-                            }
-                            // Return an error if the position we are seeking is after the
-                            // last possible position in the directory.
-                            if eventualpos > dir_inode_obj.filename_to_inode_dict.len() as isize {
-                                return syscall_error(
-                                    Errno::EINVAL,
-                                    "lseek",
-                                    "seek to after last position in directory",
-                                );
-                            }
-
-                            // Update the final position of the file descriptor object
-                            normalfile_filedesc_obj.position = eventualpos as usize;
-                            // Return the location that we sought to
-                            eventualpos as i32
-                        }
-                    }
+                        // SPLICE_F_NONBLOCK also keeps a socket from waiting for data
+                        (SpliceEnd::Socket, _) => self.recv_syscall(
+                            fd_in,
+                            buf.as_mut_ptr(),
+                            buf.len(),
+                            if flags & SPLICE_F_NONBLOCK != 0 {
+                                MSG_DONTWAIT
+                            } else {
+                                0
+                            },
+                        ),
+                        _ => self.read_syscall(fd_in, buf.as_mut_ptr(), buf.len()),
+                    })
+                });
+                if ret == -(Errno::EPIPE as i32) {
+                    interface::lind_kill_from_id(self.cageid, SIGPIPE);
                 }
+                ret
             }
-        } else {
-            syscall_error(Errno::EBADF, "lseek", "invalid file descriptor")
+            _ => syscall_error(Errno::EINVAL, "splice", "neither fd refers to a pipe"),
         }
     }
 
-    //------------------------------------ACCESS SYSCALL------------------------------------
+    /// ## ------------------TEE SYSCALL------------------
     /// ### Description
     ///
-    /// `access_syscall` checks the accessibility of the file specified by
-    /// `path` according to the given `amode`. The assumption while running this
-    /// command is that the current user owns the file.
+    /// The `tee_syscall()` copies bytes from one pipe into another, leaving
+    /// them in the first pipe so that they can still be read or spliced from
+    /// it.
     ///
-    /// ### Arguments
+    /// ### Function Arguments
     ///
-    /// It accepts two parameters:
-    /// * `path` - A string slice that specifies the file path for which
-    ///   accessibility is to be checked.
-    /// * `amode` - A bitmask value specifying the accessibility check to be
-    ///   performed. It can be a combination of the following constants:
-    ///     * `R_OK` - Check for read permission.
-    ///     * `W_OK` - Check for write permission.
-    ///     * `X_OK` - Check for execute (search) permission.
+    /// The `tee_syscall()` receives four arguments:
+    /// * `fd_in` - the read end of the pipe to copy from.
+    /// * `fd_out` - the write end of the pipe to copy to.
+    /// * `len` - the most bytes to copy.
+    /// * `flags` - as for `splice_syscall()`, where only `SPLICE_F_NONBLOCK`
+    ///   has an effect.
     ///
     /// ### Returns
     ///
-    /// For a successful call, the return value will be 0, indicating that the
-    /// requested access is allowed. On error, a negative errno is returned to
-    /// indicate the error.
+    /// The number of bytes copied, which is 0 once `fd_in` is empty and has no
+    /// writers left.
     ///
     /// ### Errors
     ///
-    /// * `ENOENT` - The file specified by `path` does not exist or the path is
-    ///   invalid.
-    /// * `EACCES` - The requested access would be denied to the file due to
-    ///   insufficient permissions.
+    /// * EAGAIN - the copy would have to wait and `SPLICE_F_NONBLOCK` is set,
+    ///   or one of the pipes is non-blocking.
+    /// * EBADF - either fd is not a valid file descriptor, `fd_in` is not open
+    ///   for reading, or `fd_out` is not open for writing.
+    /// * EINVAL - either fd is not a pipe, both refer to the same pipe, or
+    ///   `flags` contains an unknown flag.
+    /// * EPIPE - the read end of `fd_out` has been closed.
     ///
-    /// ### Panics
+    /// For more detailed description of all the commands and return values, see
+    /// [tee(2)](https://man7.org/linux/man-pages/man2/tee.2.html)
+    pub fn tee_syscall(&self, fd_in: i32, fd_out: i32, len: usize, flags: u32) -> i32 {
+        if flags & !SPLICE_F_ALL != 0 {
+            return syscall_error(Errno::EINVAL, "tee", "unknown flags");
+        }
+        let (pipe_in, pipe_out, nonblocking) = match (
+            self._splice_end(fd_in, false, false, "tee"),
+            self._splice_end(fd_out, false, true, "tee"),
+        ) {
+            (Err(e), _) | (_, Err(e)) => return e,
+            (Ok(SpliceEnd::Pipe(pipe_in, flags_in)), Ok(SpliceEnd::Pipe(pipe_out, flags_out))) => {
+                let nonblocking =
+                    flags & SPLICE_F_NONBLOCK != 0 || (flags_in | flags_out) & O_NONBLOCK != 0;
+                (pipe_in, pipe_out, nonblocking)
+            }
+            _ => return syscall_error(Errno::EINVAL, "tee", "fd does not refer to a pipe"),
+        };
+        if pipe_in.same_pipe(&pipe_out) {
+            return syscall_error(Errno::EINVAL, "tee", "can't tee a pipe to itself");
+        }
+        let ret = self._splice_wait(nonblocking, || {
+            Some(pipe_in.transfer_to(&pipe_out, len, false))
+                .filter(|&ret| ret != -(Errno::EAGAIN as i32))
+        });
+        if ret == -(Errno::EPIPE as i32) {
+            interface::lind_kill_from_id(self.cageid, SIGPIPE);
+        }
+        ret
+    }
+
+    /// ## ------------------VMSPLICE SYSCALL------------------
+    /// ### Description
     ///
-    /// * There are no known panics in this system call.
+    /// The `vmsplice_syscall()` moves bytes between the buffers of the cage
+    /// and a pipe: into the pipe for its write end, and out of it for its read
+    /// end, the same as `writev_syscall()` and `readv_syscall()` would.
     ///
-    /// For more detailed description of all the commands and return values,
-    /// refer to the access syscall man page [here](https://man7.org/linux/man-pages/man2/access.2.html).
-
-    pub fn access_syscall(&self, path: &str, amode: u32) -> i32 {
-        let truepath = normpath(convpath(path), self);
-
-        //Walk the file tree to get inode from path
-        if let Some(inodenum) = metawalk(truepath.as_path()) {
-            // BUG: We don't support F_OK as a valid amode flag, which when passed we need
-            // to check only whether the file exists or not
-
-            // will not panic since check for existence in table already happened in
-            // metawalk
-            let inodeobj = FS_METADATA.inodetable.get(&inodenum).unwrap();
-
-            //Get the mode bits if the type of the inode is sane
-            let mode = match &*inodeobj {
-                Inode::File(f) => f.mode,
-                Inode::CharDev(f) => f.mode,
-                Inode::Socket(f) => f.mode,
-                Inode::Dir(f) => f.mode,
-            };
-
-            //We assume that the current user owns the file
-
-            //Construct desired access bits (i.e. 0777) based on the amode parameter
-            let mut newmode: u32 = 0;
-            // if we are checking for execute rights
-            if amode & X_OK == X_OK {
-                // update newmode with S_IXUSR flags
-                newmode |= S_IXUSR;
-            }
-            // if we are checking for write rights
-            if amode & W_OK == W_OK {
-                // update newmode with S_IWUSR flags
-                newmode |= S_IWUSR;
-            }
-            // if we are checking for read rights
-            if amode & R_OK == R_OK {
-                // update newmode with S_IRUSR flags
-                newmode |= S_IRUSR;
-            }
+    /// ### Function Arguments
+    ///
+    /// The `vmsplice_syscall()` receives four arguments:
+    /// * `fd` - a pipe descriptor. If it is open for writing, the buffers are
+    ///   written to the pipe, otherwise they are read into.
+    /// * `iov` - the array of buffers.
+    /// * `nr_segs` - the number of buffers in `iov`.
+    /// * `flags` - as for `splice_syscall()`, where only `SPLICE_F_NONBLOCK`
+    ///   has an effect.
+    ///
+    /// ### Returns
+    ///
+    /// The number of bytes moved.
+    ///
+    /// ### Errors
+    ///
+    /// * EAGAIN - the move would have to wait and `SPLICE_F_NONBLOCK` is set,
+    ///   or the pipe is non-blocking.
+    /// * EBADF - fd is not a valid file descriptor, or not a pipe.
+    /// * EINVAL - `nr_segs` is negative or greater than IOV_MAX, or `flags`
+    ///   contains an unknown flag.
+    /// * EPIPE - the read end of the pipe has been closed.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [vmsplice(2)](https://man7.org/linux/man-pages/man2/vmsplice.2.html)
+    pub fn vmsplice_syscall(
+        &self,
+        fd: i32,
+        iov: *const interface::IovecStruct,
+        nr_segs: i32,
+        flags: u32,
+    ) -> i32 {
+        if flags & !SPLICE_F_ALL != 0 {
+            return syscall_error(Errno::EINVAL, "vmsplice", "unknown flags");
+        }
+        if !(0..=IOV_MAX).contains(&nr_segs) {
+            return syscall_error(Errno::EINVAL, "vmsplice", "nr_segs is out of range");
+        }
+        let (pipe, pipeflags) = match self.get_filedescriptor(fd) {
+            Ok(checkedfd) => match &*checkedfd.read() {
+                Some(Pipe(pipe_filedesc_obj)) => {
+                    (pipe_filedesc_obj.pipe.clone(), pipe_filedesc_obj.flags)
+                }
+                _ => return syscall_error(Errno::EBADF, "vmsplice", "fd does not refer to a pipe"),
+            },
+            Err(_) => return syscall_error(Errno::EBADF, "vmsplice", "invalid file descriptor"),
+        };
+        let nonblocking = flags & SPLICE_F_NONBLOCK != 0 || pipeflags & O_NONBLOCK != 0;
 
-            //if the desired access bits are compatible with the actual access bits
-            //of the file, return a success result, else return a failure result
-            if mode & newmode == newmode {
-                0
-            } else {
-                syscall_error(
-                    Errno::EACCES,
-                    "access",
-                    "the requested access would be denied to the file",
-                )
+        if !is_rdonly(pipeflags) {
+            let ret = pipe.write_vectored_to_pipe(iov, nr_segs, nonblocking);
+            if ret == -(Errno::EPIPE as i32) {
+                interface::lind_kill_from_id(self.cageid, SIGPIPE);
             }
+            ret
         } else {
-            // if incase the file isn't found
-            syscall_error(
-                Errno::ENOENT,
-                "access",
-                "path does not refer to an existing file",
-            )
+            self._splice_wait(nonblocking, || {
+                Some(pipe.read_vectored_from_pipe(iov, nr_segs, nonblocking))
+                    .filter(|&ret| ret != -(Errno::EAGAIN as i32))
+            })
         }
     }
 
+    /// ## ------------------SENDFILE SYSCALL------------------
     /// ### Description
     ///
-    /// The `fchdir_syscall()` function changes the current working
-    /// directory of the calling process to the directory specified
-    /// by an open file descriptor `fd`.
+    /// The `sendfile_syscall()` copies bytes from a regular file to another
+    /// descriptor, reading them straight from the file object of the file
+    /// instead of passing them through the cage. The bytes can go to a
+    /// socket, a pipe, or another file.
     ///
-    /// ### Arguments
+    /// ### Function Arguments
     ///
-    /// The `fchdir_syscall()` accepts one argument:
-    /// * `fd` - an open file descriptor that specifies the directory
-    /// to which we want to change the current working directory.
+    /// The `sendfile_syscall()` receives four arguments:
+    /// * `out_fd` - the file descriptor to write to.
+    /// * `in_fd` - the regular file to read from.
+    /// * `offset` - the position in `in_fd` to read at, which is advanced by
+    ///   the number of bytes copied instead of the file offset of `in_fd`. None
+    ///   reads at the file offset, which is then advanced.
+    /// * `count` - the most bytes to copy.
     ///
     /// ### Returns
     ///
-    /// Upon successful completion, zero is returned.
-    /// In case of a failure, an error is returned, and `errno` is set depending
-    /// on the error, e.g. `ENOTDIR`, `EBADF`, etc.
+    /// The number of bytes copied, which is 0 at the end of `in_fd`. Fewer
+    /// than `count` bytes are copied if `out_fd` doesn't take them all.
     ///
     /// ### Errors
     ///
-    /// * `EBADF` - fd is not a valid file descriptor.
-    /// * `ENOTDIR` - the open file descriptor fildes does not refer to a
-    ///   directory.
-    /// Other errors, like `EACCES`, `ENOMEM`, etc. are not supported.
-    ///
-    /// ### Panics
+    /// * EBADF - either fd is not a valid file descriptor, `in_fd` is not open
+    ///   for reading, or `out_fd` is not open for writing.
+    /// * EINVAL - `in_fd` is not a regular file, `out_fd` is a regular file
+    ///   opened with O_APPEND, or `offset` is negative.
+    /// * EISDIR - `in_fd` is a directory.
     ///
-    /// * invalid or out-of-bounds file descriptor, calling unwrap() on which
-    /// causes a panic.
+    /// Errors from writing `out_fd` are passed on if nothing could be copied.
     ///
-    /// To learn more about the syscall and possible error values, see
-    /// [fchdir(2)](https://linux.die.net/man/2/fchdir)
+    /// For more detailed description of all the commands and return values, see
+    /// [sendfile(2)](https://man7.org/linux/man-pages/man2/sendfile.2.html)
+    pub fn sendfile_syscall(
+        &self,
+        out_fd: i32,
+        in_fd: i32,
+        offset: Option<&mut i64>,
+        count: usize,
+    ) -> i32 {
+        let (inodenum_in, flags_in) = match self._regular_file_of(in_fd, "sendfile") {
+            Ok(Some(file)) => file,
+            Ok(None) => {
+                return syscall_error(
+                    Errno::EINVAL,
+                    "sendfile",
+                    "in_fd does not refer to a regular file",
+                )
+            }
+            Err(e) => return e,
+        };
+        if is_wronly(flags_in) {
+            return syscall_error(Errno::EBADF, "sendfile", "in_fd is not open for reading");
+        }
+        // a regular file is written through its file object as well, anything else
+        // through its descriptor
+        let file_out = match self._regular_file_of(out_fd, "sendfile") {
+            Ok(Some((inodenum, flags))) => {
+                if is_rdonly(flags) {
+                    return syscall_error(
+                        Errno::EBADF,
+                        "sendfile",
+                        "out_fd is not open for writing",
+                    );
+                }
+                // Linux doesn't support appending with sendfile
+                if flags & O_APPEND != 0 {
+                    return syscall_error(Errno::EINVAL, "sendfile", "out_fd is in append mode");
+                }
+                Some(inodenum)
+            }
+            Ok(None) | Err(_) => None,
+        };
+        let start = match offset.as_deref() {
+            Some(&off) if off < 0 => {
+                return syscall_error(Errno::EINVAL, "sendfile", "offset is negative");
+            }
+            Some(&off) => off as usize,
+            None => self._file_position(in_fd),
+        };
 
-    pub fn fchdir_syscall(&self, fd: i32) -> i32 {
-        //BUG
-        //if the provided file descriptor is out of bounds, get_filedescriptor returns
-        //Err(), unwrapping on which  produces a 'panic!'
-        //otherwise, file descriptor table entry is stored in 'checkedfd'
-        let checkedfd = self.get_filedescriptor(fd).unwrap();
-        let unlocked_fd = checkedfd.read();
-        //If a table descriptor entry corresponds to a file, we check if it is
-        //a directory file type. If it is not, we return `A component of path is
-        //not a directory` error.
-        //If it is one of the special file types, we return `Cannot change working
-        //directory on this file descriptor` error.
-        //Finally, if it does not correspond to any file type, we return `Invalid file
-        //descriptor` error.
-        let path_string = match &*unlocked_fd {
-            None => return syscall_error(Errno::EBADF, "fchdir", "invalid file descriptor"),
-            Some(File(normalfile_filedesc_obj)) => {
-                let inodenum = normalfile_filedesc_obj.inode;
-                //`pathnamefrominodenum` resolves the absolute path of a directory
-                //from its inode and returns None in case any of the path components
-                //is not a directory
-                match pathnamefrominodenum(inodenum) {
-                    Some(name) => name,
-                    None => {
-                        return syscall_error(
-                            Errno::ENOTDIR,
-                            "fchdir",
-                            "the file descriptor does not refer to a directory",
-                        )
+        let count = interface::rust_min(count, i32::MAX as usize);
+        let mut buf = vec![0u8; interface::rust_min(count, COPY_CHUNK_SIZE)];
+        let mut copied = 0;
+        while copied < count {
+            let chunk = interface::rust_min(buf.len(), count - copied);
+            let bytesread = Self::_read_file_at(inodenum_in, &mut buf[..chunk], start + copied);
+            if bytesread == 0 {
+                break;
+            }
+            let ret = match file_out {
+                Some(inodenum_out) => {
+                    let checkedfd = self.get_filedescriptor(out_fd).unwrap();
+                    let mut unlocked_fd = checkedfd.write();
+                    match &mut *unlocked_fd {
+                        Some(File(normalfile_filedesc_obj)) => {
+                            let ret = self._write_file_at(
+                                inodenum_out,
+                                &buf[..bytesread],
+                                normalfile_filedesc_obj.position,
+                                "sendfile",
+                            );
+                            if ret > 0 {
+                                normalfile_filedesc_obj.position += ret as usize;
+                            }
+                            ret
+                        }
+                        _ => syscall_error(Errno::EBADF, "sendfile", "out_fd was closed"),
                     }
                 }
+                None => self.write_syscall(out_fd, buf.as_ptr(), bytesread),
+            };
+            if ret < 0 {
+                if copied == 0 {
+                    return ret;
+                }
+                break;
             }
-            Some(_) => {
-                return syscall_error(
-                    Errno::ENOTDIR,
-                    "fchdir",
-                    "the file descriptor does not refer to a directory",
-                )
+            copied += ret as usize;
+            if (ret as usize) < bytesread {
+                break;
             }
-        };
-        //Obtain the write lock on the current working directory of the cage
-        //and change it to the new directory
-        let mut cwd_container = self.cwd.write();
-        *cwd_container = interface::RustRfc::new(normpath(convpath(path_string.as_str()), self));
+        }
 
-        0 // fchdir success
+        match offset {
+            Some(off) => *off += copied as i64,
+            None => self._set_file_position(in_fd, start + copied),
+        }
+        if copied > 0 {
+            Self::_update_atime(inodenum_in);
+        }
+        copied as i32
     }
 
+    /// ## ------------------COPY_FILE_RANGE SYSCALL------------------
     /// ### Description
     ///
-    /// The `chdir_syscall()` function changes the current working
-    /// directory of the calling process to the directory specified
-    /// in `path`, which can be an absolute or a relative pathname.
+    /// The `copy_file_range_syscall()` copies bytes from one regular file to
+    /// another, or within the same file, going straight from one file object
+    /// to the other instead of passing them through the cage.
     ///
-    /// ### Arguments
+    /// ### Function Arguments
     ///
-    /// The `chdir_syscall()` accepts one argument:
-    /// * `path` - the pathname, to which the current working
-    /// directory shall be changed.
+    /// The `copy_file_range_syscall()` receives six arguments:
+    /// * `fd_in` - the regular file to read from.
+    /// * `off_in` - the position in `fd_in` to read at, which is advanced by
+    ///   the number of bytes copied instead of the file offset of `fd_in`. None
+    ///   reads at the file offset, which is then advanced.
+    /// * `fd_out` - the regular file to write to.
+    /// * `off_out` - like `off_in`, for `fd_out`.
+    /// * `len` - the most bytes to copy.
+    /// * `flags` - must be 0.
     ///
     /// ### Returns
     ///
-    /// Upon successful completion, zero is returned.
-    /// In case of a failure, an error is returned, and `errno` is set depending
-    /// on the error, e.g. EACCES, ENOENT, etc.
+    /// The number of bytes copied, which is 0 at the end of `fd_in`.
     ///
     /// ### Errors
     ///
-    /// * `ENOTDIR` - a component of `path` is not a directory.
-    /// * `ENOENT` - the directory specified in path does not exist.
-    /// Other errors, like `EACCES`, `ENOMEM`, etc. are not supported.
-    ///
-    /// ### Panics
-    ///
-    /// * when the previous working directory does not exist or does not
-    /// have the directory file type flag, the function panics
+    /// * EBADF - either fd is not a valid file descriptor, `fd_in` is not open
+    ///   for reading, or `fd_out` is not open for writing or was opened with
+    ///   O_APPEND.
+    /// * EINVAL - either fd is not a regular file, an offset is negative,
+    ///   `flags` is not 0, or the ranges overlap within the same file.
+    /// * EISDIR - either fd is a directory.
+    /// * EFBIG, ENOSPC, EDQUOT - nothing could be written to `fd_out`, see
+    ///   `pwrite_syscall()`.
     ///
-    /// To learn more about the syscall and possible error values, see
-    /// [chdir(2)](https://man7.org/linux/man-pages/man2/chdir.2.html)
-
-    pub fn chdir_syscall(&self, path: &str) -> i32 {
-        //Convert the provided pathname into an absolute path without `.` or `..`
-        //components.
-        let truepath = normpath(convpath(path), self);
-        //Perfrom a walk down the file tree starting from the root directory to
-        //obtain an inode number of the file whose pathname was specified.
-        //`None` is returned if one of the following occurs while moving down
-        //the tree:
-        // 1. Accessing a child of a non-directory inode
-        // 2. Accessing a child of a nonexistent parent directory
-        // 3. Accessing a nonexistent child
-        // 4. Accessing an unexpected component, like `.` or `..` directory reference.
-        //In this case, `The file does not exist` error is returned.
-        //Otherwise, a `Some()` option containing the inode number is returned.
-        if let Some(inodenum) = metawalk(&truepath) {
-            //A sanity check to make sure that the last component of the
-            //specified path is indeed a directory
-            if let Inode::Dir(ref mut _dir) = *(FS_METADATA.inodetable.get_mut(&inodenum).unwrap())
-            {
-                //Obtain the write lock on the current working directory of the cage
-                //and change it to the new directory
-                let mut cwd_container = self.cwd.write();
-                *cwd_container = interface::RustRfc::new(truepath);
-                0 //chdir has succeeded!;
-            } else {
+    /// For more detailed description of all the commands and return values, see
+    /// [copy_file_range(2)](https://man7.org/linux/man-pages/man2/copy_file_range.2.html)
+    pub fn copy_file_range_syscall(
+        &self,
+        fd_in: i32,
+        off_in: Option<&mut i64>,
+        fd_out: i32,
+        off_out: Option<&mut i64>,
+        len: usize,
+        flags: u32,
+    ) -> i32 {
+        if flags != 0 {
+            return syscall_error(Errno::EINVAL, "copy_file_range", "flags must be 0");
+        }
+        let mut files = [(0, 0); 2];
+        for (file, fd) in files.iter_mut().zip([fd_in, fd_out]) {
+            *file = match self._regular_file_of(fd, "copy_file_range") {
+                Ok(Some(file)) => file,
+                Ok(None) => {
+                    return syscall_error(
+                        Errno::EINVAL,
+                        "copy_file_range",
+                        "fd does not refer to a regular file",
+                    )
+                }
+                Err(e) => return e,
+            };
+        }
+        let [(inodenum_in, flags_in), (inodenum_out, flags_out)] = files;
+        if is_wronly(flags_in) || is_rdonly(flags_out) {
+            return syscall_error(
+                Errno::EBADF,
+                "copy_file_range",
+                "fd is not open for the direction of the copy",
+            );
+        }
+        if flags_out & O_APPEND != 0 {
+            return syscall_error(Errno::EBADF, "copy_file_range", "fd_out is in append mode");
+        }
+        let mut positions = [0; 2];
+        for (position, (off, fd)) in positions
+            .iter_mut()
+            .zip([(&off_in, fd_in), (&off_out, fd_out)])
+        {
+            *position = match off.as_deref() {
+                Some(&off) if off < 0 => {
+                    return syscall_error(Errno::EINVAL, "copy_file_range", "offset is negative");
+                }
+                Some(&off) => off as usize,
+                None => self._file_position(fd),
+            };
+        }
+        let [start_in, start_out] = positions;
+
+        // nothing past the end of fd_in can be copied, and what is copied can't
+        // overlap with where it is copied to
+        let size_in = match *FS_METADATA.inodetable.get(&inodenum_in).unwrap() {
+            Inode::File(ref normalfile_inode_obj) => normalfile_inode_obj.size,
+            _ => 0,
+        };
+        let len = interface::rust_min(
+            interface::rust_min(len, i32::MAX as usize),
+            size_in.saturating_sub(start_in),
+        );
+        if inodenum_in == inodenum_out && start_in < start_out + len && start_out < start_in + len {
+            return syscall_error(
+                Errno::EINVAL,
+                "copy_file_range",
+                "the ranges overlap within the same file",
+            );
+        }
+
+        let mut buf = vec![0u8; interface::rust_min(len, COPY_CHUNK_SIZE)];
+        let mut copied = 0;
+        while copied < len {
+            let chunk = interface::rust_min(buf.len(), len - copied);
+            let bytesread = Self::_read_file_at(inodenum_in, &mut buf[..chunk], start_in + copied);
+            if bytesread == 0 {
+                break;
+            }
+            let ret = self._write_file_at(
+                inodenum_out,
+                &buf[..bytesread],
+                start_out + copied,
+                "copy_file_range",
+            );
+            if ret < 0 {
+                if copied == 0 {
+                    return ret;
+                }
+                break;
+            }
+            copied += ret as usize;
+            if (ret as usize) < bytesread {
+                break;
+            }
+        }
+
+        match off_in {
+            Some(off) => *off += copied as i64,
+            None => self._set_file_position(fd_in, start_in + copied),
+        }
+        match off_out {
+            Some(off) => *off += copied as i64,
+            None => self._set_file_position(fd_out, start_out + copied),
+        }
+        if copied > 0 {
+            Self::_update_atime(inodenum_in);
+        }
+        copied as i32
+    }
+
+    // Returns the file offset of a regular file descriptor
+    fn _file_position(&self, fd: i32) -> usize {
+        match &*self.get_filedescriptor(fd).unwrap().read() {
+            Some(File(normalfile_filedesc_obj)) => normalfile_filedesc_obj.position,
+            _ => 0,
+        }
+    }
+
+    // Moves the file offset of a regular file descriptor, if it is still open
+    fn _set_file_position(&self, fd: i32, position: usize) {
+        if let Some(File(normalfile_filedesc_obj)) =
+            &mut *self.get_filedescriptor(fd).unwrap().write()
+        {
+            normalfile_filedesc_obj.position = position;
+        }
+    }
+
+    // Looks up a descriptor given to splice or tee, checking that it is open
+    // for reading, or for writing if it is the one being written to. The pipe
+    // of a pipe descriptor is cloned so that the descriptor isn't kept locked
+    // while the call waits on the pipe.
+    fn _splice_end(
+        &self,
+        fd: i32,
+        has_offset: bool,
+        writing: bool,
+        syscallname: &str,
+    ) -> Result<SpliceEnd, i32> {
+        let checkedfd = match self.get_filedescriptor(fd) {
+            Ok(checkedfd) => checkedfd,
+            Err(_) => {
+                return Err(syscall_error(
+                    Errno::EBADF,
+                    syscallname,
+                    "invalid file descriptor",
+                ))
+            }
+        };
+        let unlocked_fd = checkedfd.read();
+        let (end, flags) = match &*unlocked_fd {
+            Some(Pipe(pipe_filedesc_obj)) => (
+                SpliceEnd::Pipe(pipe_filedesc_obj.pipe.clone(), pipe_filedesc_obj.flags),
+                pipe_filedesc_obj.flags,
+            ),
+            Some(File(normalfile_filedesc_obj)) => (SpliceEnd::File, normalfile_filedesc_obj.flags),
+            Some(Stream(stream_filedesc_obj)) => (SpliceEnd::Stream, stream_filedesc_obj.flags),
+            Some(Socket(socket_filedesc_obj)) => (SpliceEnd::Socket, socket_filedesc_obj.flags),
+            Some(_) => {
+                return Err(syscall_error(
+                    Errno::EINVAL,
+                    syscallname,
+                    "fd can't be spliced",
+                ))
+            }
+            None => {
+                return Err(syscall_error(
+                    Errno::EBADF,
+                    syscallname,
+                    "invalid file descriptor",
+                ))
+            }
+        };
+        // sockets don't keep an access mode in their flags
+        let socket = matches!(end, SpliceEnd::Socket);
+        if !socket && ((writing && is_rdonly(flags)) || (!writing && is_wronly(flags))) {
+            return Err(syscall_error(
+                Errno::EBADF,
+                syscallname,
+                "fd is not open for the direction of the splice",
+            ));
+        }
+        if has_offset && !matches!(end, SpliceEnd::File) {
+            return Err(syscall_error(
+                Errno::ESPIPE,
+                syscallname,
+                "an offset can only be given for a regular file",
+            ));
+        }
+        Ok(end)
+    }
+
+    // Runs `attempt` until it doesn't have to wait on a pipe, which it says by
+    // returning None, checking for cancellation in between. A non-blocking
+    // call instead fails with EAGAIN.
+    fn _splice_wait<F: FnMut() -> Option<i32>>(&self, nonblocking: bool, mut attempt: F) -> i32 {
+        loop {
+            if let Some(ret) = attempt() {
+                return ret;
+            }
+            if nonblocking {
                 return syscall_error(
-                    Errno::ENOTDIR,
-                    "chdir",
-                    "the last component in path is not a directory",
+                    Errno::EAGAIN,
+                    "splice",
+                    "the pipe isn't ready right now, try again later",
                 );
             }
+            if self
+                .cancelstatus
+                .load(interface::RustAtomicOrdering::Relaxed)
+            {
+                loop {
+                    interface::cancelpoint(self.cageid);
+                }
+            }
+            interface::lind_yield();
+        }
+    }
+
+    /// ## -------------------------------- LSEEK SYSCALL -------------------------
+    /// ### Description
+    ///
+    /// The `lseek_syscall()` repositions the offset of the file associated
+    /// with the file descriptor `fd` to a new location as specified by
+    /// `offset` and `whence`. The new offset is calculated relative to the
+    /// position specified by whence. This syscall allows the file offset to
+    /// be set beyond the end of the file (but this does not change the size
+    /// of the file). If data is later written at this point, subsequent reads
+    /// of the data in the gap (a "hole") return null bytes ('\0') until data
+    /// is actually written into the gap.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `lseek_syscall()` receives three arguments:
+    /// * `fd` - It is the file descriptor, which identifies the file being
+    ///   accessed
+    /// * `offset` - It determines the position in the file where the file
+    ///   offset should be set. It specifies the number of bytes from a
+    ///   particular reference point.
+    /// * `whence` - It determines how the seek operation is performed. There
+    ///   are three possible values for whence: `SEEK_SET`: The file offset is
+    ///   set to 0 + offset bytes. `SEEK_CUR`: The file offset is set to its
+    ///   current location plus offset bytes. `SEEK_END`: The file offset is set
+    ///   to the size of the file plus offset bytes. Regular files also accept
+    ///   `SEEK_DATA`: The file offset is set to the start of the next data at
+    ///   or after offset, and `SEEK_HOLE`: The file offset is set to the start
+    ///   of the next hole at or after offset, the end of the file being one.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion of this call, we return the resulting offset
+    /// location as measured in bytes from the beginning of the file.
+    /// Otherwise, errors or panics are returned for different scenarios.
+    ///
+    /// ### Errors
+    ///
+    /// * `EINVAL` - The `whence` type is different from the available values;
+    ///   seek to a position before the start of the file; seek to after last
+    ///   position in directory
+    /// * `ENXIO` - `SEEK_DATA` or `SEEK_HOLE` with an offset at or past the end
+    ///   of the file, or `SEEK_DATA` with no data after the offset.
+    /// * `ESPIPE` - Seek is not possible when file descriptor is a
+    ///   socket/pipe/stream/epoll.
+    ///
+    ///
+    /// ### Panics
+    ///
+    /// * When there is some issue fetching the file descriptor.
+    /// * When the file type file descriptor contains a Socket as an inode.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [lseek(2)](https://man7.org/linux/man-pages/man2/lseek.2.html)
+    pub fn lseek_syscall(&self, fd: i32, offset: isize, whence: i32) -> i32 {
+        //BUG
+        //If the provided file descriptor is out of bounds, get_filedescriptor returns
+        //Err(), unwrapping on which  produces a 'panic!'
+        //otherwise, file descriptor table entry is stored in 'checkedfd'
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        // Acquire a write lock on the file descriptor to ensure exclusive access.
+        let mut unlocked_fd = checkedfd.write();
+
+        // Check if the file descriptor object is valid
+        if let Some(filedesc_enum) = &mut *unlocked_fd {
+            //confirm fd type is seekable
+            match filedesc_enum {
+                // Return an error for Sockets, as they do not support the concept of seeking to a
+                // specific offset because data arrives in a continuous stream from the network.
+                Socket(_) => syscall_error(
+                    Errno::ESPIPE,
+                    "lseek",
+                    "file descriptor is associated with a socket, cannot seek",
+                ),
+                // Return an error for Streams, as like sockets, streams are sequential and do not
+                // support seeking to an offset.
+                Stream(_) => syscall_error(
+                    Errno::ESPIPE,
+                    "lseek",
+                    "file descriptor is associated with a stream, cannot seek",
+                ),
+                // Return an error for Pipes, as they are designed for sequential reads and writes
+                // between processes. Seeking within a pipe would not be possible.
+                Pipe(_) => syscall_error(
+                    Errno::ESPIPE,
+                    "lseek",
+                    "file descriptor is associated with a pipe, cannot seek",
+                ),
+                // Return an error for Epoll, as Epoll file descriptors are for event notification
+                // and do not hold any data themselves, so seeking is not possible.
+                Epoll(_) => syscall_error(
+                    Errno::ESPIPE,
+                    "lseek",
+                    "file descriptor is associated with an epollfd, cannot seek",
+                ),
+                // Eventfds, timerfds and signalfds don't hold any data to seek in either.
+                EventFd(_) | TimerFd(_) | SignalFd(_) => syscall_error(
+                    Errno::ESPIPE,
+                    "lseek",
+                    "file descriptor is associated with an eventfd, timerfd or signalfd, cannot seek",
+                ),
+                File(ref mut normalfile_filedesc_obj) => {
+                    // Get the inode object from the inode table associated with the file
+                    // descriptor.
+                    let inodeobj = FS_METADATA
+                        .inodetable
+                        .get(&normalfile_filedesc_obj.inode)
+                        .unwrap();
+
+                    // Match the type of inode object with the type (File, Socket, CharDev, Dir)
+                    // Handle files/directories differently
+                    match &*inodeobj {
+                        Inode::File(normalfile_inode_obj) => {
+                            // For file type inode, match the type of whence first, and based
+                            // on that, update the final position of the offset of the file.
+                            let eventualpos = match whence {
+                                // Simply update the offset of the file to the given value.
+                                SEEK_SET => offset,
+                                // Get the current offset position of the file and add `offset`.
+                                SEEK_CUR => normalfile_filedesc_obj.position as isize + offset,
+                                // Get the file size and add `offset` position.
+                                SEEK_END => normalfile_inode_obj.size as isize + offset,
+                                // Find the next data or hole at or after `offset`, which the
+                                // backing file on the host keeps track of. There is neither
+                                // at or past the end of the file, though there is always the
+                                // implicit hole at the end of it.
+                                SEEK_DATA | SEEK_HOLE => {
+                                    if offset < 0 || offset as usize >= normalfile_inode_obj.size {
+                                        return syscall_error(
+                                            Errno::ENXIO,
+                                            "lseek",
+                                            "offset is at or past the end of the file",
+                                        );
+                                    }
+                                    let fileobject =
+                                        FILEOBJECTTABLE.get(&normalfile_filedesc_obj.inode).unwrap();
+                                    match fileobject.seek_data_or_hole(offset as usize, whence) {
+                                        Ok(pos) => {
+                                            interface::rust_min(pos, normalfile_inode_obj.size)
+                                                as isize
+                                        }
+                                        // the only data left is past the end of the file
+                                        Err(_) => {
+                                            return syscall_error(
+                                                Errno::ENXIO,
+                                                "lseek",
+                                                "no more data after offset",
+                                            );
+                                        }
+                                    }
+                                }
+                                _ => {
+                                    return syscall_error(Errno::EINVAL, "lseek", "unknown whence");
+                                }
+                            };
+
+                            // Sanity check to make sure that the final position of seeking
+                            // doesn't go before position 0 in the file.
+                            if eventualpos < 0 {
+                                return syscall_error(
+                                    Errno::EINVAL,
+                                    "lseek",
+                                    "seek to before position 0 in file",
+                                );
+                            }
+                            // subsequent writes to the end of the file must zero pad up (filling
+                            // the gap between the current end of the file and the new position with
+                            // zero bytes ('\0')) until this point if we overran the end of our file
+                            // when seeking. This is currently supported in write and pwrite
+                            // syscalls.
+
+                            // Update the final position of the file descriptor object
+                            normalfile_filedesc_obj.position = eventualpos as usize;
+                            // Return the location that we sought to
+                            eventualpos as i32
+                        }
+
+                        Inode::CharDev(_) => {
+                            0 // for character files, rather than seeking, we
+                              // transparently do nothing
+                        }
+
+                        // A Sanity check is added to make sure that there is no such case when the
+                        // fd type is "File" and the inode type is "Socket". This state is ideally
+                        // not possible, so we panic in such cases.
+                        Inode::Socket(_) => {
+                            panic!("lseek: socket fd and inode don't match types")
+                        }
+                        // Symbolic links are always resolved or rejected by open, so a "File"
+                        // type fd can never refer to one.
+                        Inode::Symlink(_) => {
+                            panic!("lseek: symlink inode found on a filedesc fd")
+                        }
+                        // FIFOs are always opened as pipes, so a "File" type fd can never
+                        // refer to one.
+                        Inode::Fifo(_) => {
+                            panic!("lseek: FIFO inode found on a filedesc fd")
+                        }
+
+                        Inode::Dir(dir_inode_obj) => {
+                            // For directory type inode, we seek between directory entries,
+                            // and based on the whence check, we update the final position.
+                            let eventualpos = match whence {
+                                // Simply update the offset of the directory to the given value.
+                                SEEK_SET => offset,
+                                // Get the current position of the directory pointing to a directory
+                                // entry and add the offset to it.
+                                SEEK_CUR => normalfile_filedesc_obj.position as isize + offset,
+                                // The Inode dictionary contains the directory entries mapped with
+                                // the inode object, and final position is updated by adding the
+                                // total count of the entries with the given offset.
+                                SEEK_END => {
+                                    dir_inode_obj.filename_to_inode_dict.len() as isize + offset
+                                }
+                                _ => {
+                                    return syscall_error(Errno::EINVAL, "lseek", "unknown whence");
+                                }
+                            };
+
+                            // Sanity check to make sure that the final position of seeking
+                            // doesn't go before position 0 in the directory.
+                            if eventualpos < 0 {
+                                return syscall_error(
+                                    Errno::EINVAL,
+                                    "lseek",
+                                    "seek to before position 0 in directory",
+                                );
+                            }
+                            // Return an error if the position we are seeking is after the
+                            // last possible position in the directory.
+                            if eventualpos > dir_inode_obj.filename_to_inode_dict.len() as isize {
+                                return syscall_error(
+                                    Errno::EINVAL,
+                                    "lseek",
+                                    "seek to after last position in directory",
+                                );
+                            }
+
+                            // Update the final position of the file descriptor object
+                            normalfile_filedesc_obj.position = eventualpos as usize;
+                            // Return the location that we sought to
+                            eventualpos as i32
+                        }
+                    }
+                }
+            }
+        } else {
+            syscall_error(Errno::EBADF, "lseek", "invalid file descriptor")
+        }
+    }
+
+    //------------------------------------ACCESS SYSCALL------------------------------------
+    /// ### Description
+    ///
+    /// `access_syscall` checks the accessibility of the file specified by
+    /// `path` according to the given `amode`. The check is made against the
+    /// owner, group or other permission bits of the file, whichever class the
+    /// real user and group ids of the caller fall into.
+    ///
+    /// ### Arguments
+    ///
+    /// It accepts two parameters:
+    /// * `path` - A string slice that specifies the file path for which
+    ///   accessibility is to be checked.
+    /// * `amode` - A bitmask value specifying the accessibility check to be
+    ///   performed. It can be a combination of the following constants:
+    ///     * `R_OK` - Check for read permission.
+    ///     * `W_OK` - Check for write permission.
+    ///     * `X_OK` - Check for execute (search) permission.
+    ///
+    /// ### Returns
+    ///
+    /// For a successful call, the return value will be 0, indicating that the
+    /// requested access is allowed. On error, a negative errno is returned to
+    /// indicate the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOENT` - The file specified by `path` does not exist or the path is
+    ///   invalid.
+    /// * `EACCES` - The requested access would be denied to the file due to
+    ///   insufficient permissions, or a directory in the path prefix may not be
+    ///   searched.
+    ///
+    /// ### Panics
+    ///
+    /// * There are no known panics in this system call.
+    ///
+    /// For more detailed description of all the commands and return values,
+    /// refer to the access syscall man page [here](https://man7.org/linux/man-pages/man2/access.2.html).
+
+    pub fn access_syscall(&self, path: &str, amode: u32) -> i32 {
+        let truepath = normpath(convpath(path), self);
+
+        //Unlike every other check, access is answered for the real user and group
+        //of the caller rather than the effective ones
+        self._access_path_helper("access", &truepath, amode, self.get_real_ids(), true)
+    }
+
+    // shared implementation of access and faccessat, checking `amode` for the
+    // given user and group. A symbolic link named by the final component of the
+    // path is only followed if `follow_final` is set
+    fn _access_path_helper(
+        &self,
+        syscallname: &str,
+        truepath: &interface::RustPath,
+        amode: u32,
+        (uid, gid): (u32, u32),
+        follow_final: bool,
+    ) -> i32 {
+        //Every directory leading to the file has to be searchable
+        if let Ok(resolved) = resolvepath(truepath, follow_final) {
+            if !search_permitted(&resolved, uid, gid) {
+                return syscall_error(
+                    Errno::EACCES,
+                    syscallname,
+                    "search permission is denied for a directory in the path prefix",
+                );
+            }
+        }
+
+        //Walk the file tree to get inode from path
+        let inodenum = if follow_final {
+            metawalk(truepath)
+        } else {
+            metawalkandparent(truepath).0
+        };
+        if let Some(inodenum) = inodenum {
+            // BUG: We don't support F_OK as a valid amode flag, which when passed we need
+            // to check only whether the file exists or not
+
+            //Check the requested access against the class of permission bits (owner,
+            //group or other) that applies to the caller
+            if inode_permission_granted(inodenum, uid, gid, amode & (R_OK | W_OK | X_OK)) {
+                0
+            } else {
+                syscall_error(
+                    Errno::EACCES,
+                    syscallname,
+                    "the requested access would be denied to the file",
+                )
+            }
+        } else {
+            // if incase the file isn't found
+            syscall_error(
+                Errno::ENOENT,
+                syscallname,
+                "path does not refer to an existing file",
+            )
+        }
+    }
+
+    //------------------------------------FACCESSAT SYSCALL------------------------------------
+    /// ### Description
+    ///
+    /// `faccessat_syscall` is like `access_syscall`, except that a relative
+    /// `path` is interpreted relative to the directory referred to by `dirfd`,
+    /// see `_at_path_helper()`.
+    ///
+    /// ### Arguments
+    ///
+    /// It accepts four parameters:
+    /// * `dirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `path`, `amode` - as for `access_syscall`.
+    /// * `flags` - a combination of:
+    ///   * `AT_EACCESS` - the check is made for the effective user and group
+    ///     ids of the caller instead of the real ones.
+    ///   * `AT_SYMLINK_NOFOLLOW` - a symbolic link named by `path` is checked
+    ///     itself instead of the file it refers to.
+    ///
+    /// ### Returns
+    ///
+    /// 0 if the requested access is allowed, otherwise the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `EINVAL` - `flags` contains an unknown flag.
+    /// * `EBADF`, `ENOTDIR` - see `_at_path_helper()`.
+    /// * Any error of `access_syscall`.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [faccessat(2)](https://man7.org/linux/man-pages/man2/faccessat.2.html)
+    pub fn faccessat_syscall(&self, dirfd: i32, path: &str, amode: u32, flags: i32) -> i32 {
+        if flags & !(AT_EACCESS | AT_SYMLINK_NOFOLLOW) != 0 {
+            return syscall_error(Errno::EINVAL, "faccessat", "invalid flags");
+        }
+        let truepath = match self._at_path_helper("faccessat", dirfd, path) {
+            Ok(truepath) => truepath,
+            Err(e) => return e,
+        };
+        let ids = if flags & AT_EACCESS != 0 {
+            self.get_effective_ids()
+        } else {
+            self.get_real_ids()
+        };
+        let follow_final = flags & AT_SYMLINK_NOFOLLOW == 0;
+        self._access_path_helper("faccessat", &truepath, amode, ids, follow_final)
+    }
+
+    /// ### Description
+    ///
+    /// The `fchdir_syscall()` function changes the current working
+    /// directory of the calling process to the directory specified
+    /// by an open file descriptor `fd`.
+    ///
+    /// ### Arguments
+    ///
+    /// The `fchdir_syscall()` accepts one argument:
+    /// * `fd` - an open file descriptor that specifies the directory
+    /// to which we want to change the current working directory.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion, zero is returned.
+    /// In case of a failure, an error is returned, and `errno` is set depending
+    /// on the error, e.g. `ENOTDIR`, `EBADF`, etc.
+    ///
+    /// ### Errors
+    ///
+    /// * `EBADF` - fd is not a valid file descriptor.
+    /// * `ENOTDIR` - the open file descriptor fildes does not refer to a
+    ///   directory.
+    /// Other errors, like `EACCES`, `ENOMEM`, etc. are not supported.
+    ///
+    /// ### Panics
+    ///
+    /// * invalid or out-of-bounds file descriptor, calling unwrap() on which
+    /// causes a panic.
+    ///
+    /// To learn more about the syscall and possible error values, see
+    /// [fchdir(2)](https://linux.die.net/man/2/fchdir)
+
+    pub fn fchdir_syscall(&self, fd: i32) -> i32 {
+        //BUG
+        //if the provided file descriptor is out of bounds, get_filedescriptor returns
+        //Err(), unwrapping on which  produces a 'panic!'
+        //otherwise, file descriptor table entry is stored in 'checkedfd'
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let unlocked_fd = checkedfd.read();
+        //If a table descriptor entry corresponds to a file, we check if it is
+        //a directory file type. If it is not, we return `A component of path is
+        //not a directory` error.
+        //If it is one of the special file types, we return `Cannot change working
+        //directory on this file descriptor` error.
+        //Finally, if it does not correspond to any file type, we return `Invalid file
+        //descriptor` error.
+        let path_string = match &*unlocked_fd {
+            None => return syscall_error(Errno::EBADF, "fchdir", "invalid file descriptor"),
+            Some(File(normalfile_filedesc_obj)) => {
+                let inodenum = normalfile_filedesc_obj.inode;
+                //`pathnamefrominodenum` resolves the absolute path of a directory
+                //from its inode and returns None in case any of the path components
+                //is not a directory
+                match pathnamefrominodenum(inodenum) {
+                    Some(name) => name,
+                    None => {
+                        return syscall_error(
+                            Errno::ENOTDIR,
+                            "fchdir",
+                            "the file descriptor does not refer to a directory",
+                        )
+                    }
+                }
+            }
+            Some(_) => {
+                return syscall_error(
+                    Errno::ENOTDIR,
+                    "fchdir",
+                    "the file descriptor does not refer to a directory",
+                )
+            }
+        };
+        //Obtain the write lock on the current working directory of the cage
+        //and change it to the new directory
+        let mut cwd_container = self.cwd.write();
+        *cwd_container = interface::RustRfc::new(normpath(convpath(path_string.as_str()), self));
+
+        0 // fchdir success
+    }
+
+    /// ### Description
+    ///
+    /// The `chdir_syscall()` function changes the current working
+    /// directory of the calling process to the directory specified
+    /// in `path`, which can be an absolute or a relative pathname.
+    ///
+    /// ### Arguments
+    ///
+    /// The `chdir_syscall()` accepts one argument:
+    /// * `path` - the pathname, to which the current working
+    /// directory shall be changed.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion, zero is returned.
+    /// In case of a failure, an error is returned, and `errno` is set depending
+    /// on the error, e.g. EACCES, ENOENT, etc.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOTDIR` - a component of `path` is not a directory.
+    /// * `ENOENT` - the directory specified in path does not exist.
+    /// * `ELOOP` - too many symbolic links were encountered in resolving
+    ///   `path`.
+    /// Other errors, like `EACCES`, `ENOMEM`, etc. are not supported.
+    ///
+    /// ### Panics
+    ///
+    /// * when the previous working directory does not exist or does not
+    /// have the directory file type flag, the function panics
+    ///
+    /// To learn more about the syscall and possible error values, see
+    /// [chdir(2)](https://man7.org/linux/man-pages/man2/chdir.2.html)
+
+    pub fn chdir_syscall(&self, path: &str) -> i32 {
+        //Convert the provided pathname into an absolute path without `.` or `..`
+        //components.
+        let truepath = normpath(convpath(path), self);
+        //Resolve any symbolic links in the path, so that the working directory
+        //stored for the cage never contains one and `..` keeps referring to the
+        //real parent directory.
+        let truepath = match resolvepath(&truepath, true) {
+            Ok(resolved) => resolved,
+            Err(_) => {
+                return syscall_error(
+                    Errno::ELOOP,
+                    "chdir",
+                    "too many symbolic links were encountered in resolving path",
+                );
+            }
+        };
+        //Perfrom a walk down the file tree starting from the root directory to
+        //obtain an inode number of the file whose pathname was specified.
+        //`None` is returned if one of the following occurs while moving down
+        //the tree:
+        // 1. Accessing a child of a non-directory inode
+        // 2. Accessing a child of a nonexistent parent directory
+        // 3. Accessing a nonexistent child
+        // 4. Accessing an unexpected component, like `.` or `..` directory reference.
+        //In this case, `The file does not exist` error is returned.
+        //Otherwise, a `Some()` option containing the inode number is returned.
+        if let Some(inodenum) = metawalk(&truepath) {
+            //A sanity check to make sure that the last component of the
+            //specified path is indeed a directory
+            if let Inode::Dir(ref mut _dir) = *(FS_METADATA.inodetable.get_mut(&inodenum).unwrap())
+            {
+                //Obtain the write lock on the current working directory of the cage
+                //and change it to the new directory
+                let mut cwd_container = self.cwd.write();
+                *cwd_container = interface::RustRfc::new(truepath);
+                0 //chdir has succeeded!;
+            } else {
+                return syscall_error(
+                    Errno::ENOTDIR,
+                    "chdir",
+                    "the last component in path is not a directory",
+                );
+            }
+        } else {
+            return syscall_error(
+                Errno::ENOENT,
+                "chdir",
+                "the directory referred to in path does not exist",
+            );
+        }
+    }
+
+    ///##------------------------------------DUP & DUP2 SYSCALLS------------------------------------
+    /// ## `dup_syscall`
+    ///
+    /// ### Description
+    /// This function duplicates a file descriptor. It creates a new file
+    /// descriptor that refers to the same open file description as the original
+    /// file descriptor.
+    /// * Finding the Next Available File Descriptor: If `start_desc` is
+    ///   provided and it is already in use, the function will continue
+    ///   searching for the next available file descriptor starting from
+    ///   `start_desc`. If no file descriptors are available, it will return an
+    ///   error (`ENFILE`).
+    /// * If `fd` is equal to `start_fd`, the function returns `start_fd` as the
+    ///   new file descriptor. This is because in this scenario, the original
+    ///   and new file descriptors would point to the same file description.
+    /// * The `_dup2_helper` function is called to perform the actual file
+    ///   descriptor duplication, handling the allocation of a new file
+    ///   descriptor, updating the file descriptor table, and incrementing the
+    ///   reference count of the file object.
+    /// * The function modifies the global `filedescriptortable` array, adding a
+    ///   new entry for the duplicated file descriptor. It also increments the
+    ///   reference count of the file object associated with the original file
+    ///   descriptor.
+    /// * The `false` argument passed to `_dup2_helper` indicates that this call
+    ///   is from the `dup_syscall` function, not the `dup2_syscall` function.
+    ///
+    /// ### Function Arguments
+    /// * `fd`: The original file descriptor to duplicate.
+    /// * `start_desc`:  An optional starting file descriptor number. If
+    ///   provided, the new file descriptor will be
+    ///  assigned the first available file descriptor number starting from this
+    /// value. If not provided, it defaults to  `STARTINGFD`,which is the
+    /// minimum designated file descriptor value for new file descriptors.
+    ///
+    /// ### Returns
+    /// * The new file descriptor on success.
+    /// * `EBADF`: If the original file descriptor is invalid.
+    /// * `ENFILE`: If there are no available file descriptors.
+    ///
+    /// ### Errors
+    /// * `EBADF(9)`: If the original file descriptor is invalid.
+    /// * `ENFILE(23)`: If there are no available file descriptors.
+    /// * `EMFILE(24)`: If the soft RLIMIT_NOFILE of the cage has been reached.
+    ///  ###Panics
+    /// * There are no panics for this syscall
+    ///[dup(2)](https://man7.org/linux/man-pages/man2/dup.2.html)
+
+    pub fn dup_syscall(&self, fd: i32, start_desc: Option<i32>) -> i32 {
+        //if a starting fd was passed, then use that as the starting point, but
+        // otherwise, use the designated minimum of STARTINGFD
+        let start_fd = match start_desc {
+            Some(start_desc) => start_desc,
+            None => STARTINGFD,
+        };
+
+        if start_fd == fd {
+            return start_fd;
+        } //if the file descriptors are equal, return the new one
+
+        // get the filedesc_enum
+        // Attempt to get the file descriptor; handle error if it does not exist
+        let checkedfd = match self.get_filedescriptor(fd) {
+            Ok(fd) => fd,
+            Err(_) => return syscall_error(Errno::EBADF, "dup", "Invalid old file descriptor."),
+        };
+        let filedesc_enum = checkedfd.write();
+        let filedesc_enum = if let Some(f) = &*filedesc_enum {
+            f
+        } else {
+            return syscall_error(Errno::EBADF, "dup", "Invalid old file descriptor.");
+        };
+
+        //checking whether the fd exists in the file table
+        return Self::_dup2_helper(&self, filedesc_enum, start_fd, false);
+    }
+
+    /// ## `dup2_syscall`
+    ///
+    /// ### Description
+    /// This function implements the `dup2` system call, which duplicates a file
+    /// descriptor and assigns it to a new file descriptor number. If the
+    /// new file descriptor already exists, it is closed before the duplication
+    /// takes place.
+    /// * File Descriptor Reuse:  If the new file descriptor (`newfd`) is
+    ///   already open, the function will first close the existing file
+    ///   descriptor silently (without returning an error) before allocating a
+    ///   new file descriptor and updating the file descriptor table.
+    /// * If `oldfd` and `newfd` are the same, the function returns `newfd`
+    ///   without closing it. This is because in this scenario, the original and
+    ///   new file descriptors would already point to the same file description.
+    /// * the global `filedescriptortable` array, replacing the entry for the
+    ///   new file descriptor with a new entry for the duplicated file
+    ///   descriptor. It also increments the reference count of the file object
+    ///   associated with the original file descriptor.
+    ///
+    /// ### Function Arguments
+    /// * `oldfd`: The original file descriptor to duplicate.
+    /// * `newfd`: The new file descriptor number to assign to the duplicated
+    ///   file descriptor.
+    ///
+    /// ### Returns
+    /// * The new file descriptor on success.
+    ///
+    /// ### Errors
+    /// * `EBADF(9)`: If the original file descriptor (`oldfd`) is invalid or
+    ///   the new file descriptor (`newfd`) number is out of range, or not below
+    ///   the soft RLIMIT_NOFILE of the cage.
+    ///  ###Panics
+    /// * There are no panics for this syscall
+    ///[dup2(2)](https://linux.die.net/man/2/dup2)
+
+    pub fn dup2_syscall(&self, oldfd: i32, newfd: i32) -> i32 {
+        //checking if the new fd is out of range, which includes fd numbers at or above
+        // RLIMIT_NOFILE
+        if newfd >= MAXFD
+            || newfd < 0
+            || newfd as u64 >= self.get_rlimit(RLIMIT_NOFILE).rlim_cur
+        {
+            return syscall_error(
+                Errno::EBADF,
+                "dup2",
+                "provided file descriptor is out of range",
+            );
+        }
+
+        if newfd == oldfd {
+            return newfd;
+        } //if the file descriptors are equal, return the new one
+
+        // get the filedesc_enum
+        let checkedfd = match self.get_filedescriptor(oldfd) {
+            Ok(fd) => fd,
+            Err(_) => return syscall_error(Errno::EBADF, "dup2", "Invalid old file descriptor."),
+        };
+        let filedesc_enum = checkedfd.write();
+        let filedesc_enum = if let Some(f) = &*filedesc_enum {
+            f
+        } else {
+            return syscall_error(Errno::EBADF, "dup2", "Invalid old file descriptor.");
+        };
+
+        //if the old fd exists, execute the helper, else return error
+        return Self::_dup2_helper(&self, filedesc_enum, newfd, true);
+    }
+
+    /// ## `_dup2_helper`
+    ///
+    /// ### Description
+    /// This helper function performs the actual file descriptor duplication
+    /// process for both `dup` and `dup2` system calls. It handles the
+    /// allocation of a new file descriptor, updates the file descriptor table,
+    /// and increments the reference count of the associated file object.
+    /// * Duplication from `dup2_syscall`: If `fromdup2` is true, the function
+    ///   first closes the existing file descriptor at `newfd` (if any) before
+    ///   allocating a new file descriptor and updating the file descriptor
+    ///   table.
+    /// * Duplication from `dup_syscall`: If `fromdup2` is false, the function
+    ///   allocates a new file descriptor, finds the first available file
+    ///   descriptor number starting from `newfd`, and updates the file
+    ///   descriptor table.
+    /// * Reference Counting: The function increments the reference count of the
+    ///   file object associated with the original file descriptor. This ensures
+    ///   that the file object is not deleted until all its associated file
+    ///   descriptors are closed.
+    /// * Socket Handling: For domain sockets, the function increments the
+    ///   reference count of both the send and receive pipes associated with the
+    ///   socket.
+    /// * Stream Handling: Streams are not currently supported for duplication
+    /// * Unhandled File Types: If the file descriptor is associated with a file
+    ///   type that is not handled by the function (i.e., not a File, Pipe,
+    ///   Socket, or Stream), the function returns an error (`EACCES`).
+    /// * The function does not handle streams.
+    /// * Socket Handling: If the file descriptor is associated with a socket,
+    ///   the function handles domain sockets differently by incrementing the
+    ///   reference count of both the send and receive pipes.
+    /// ### Function Arguments
+    /// * `self`:  A reference to the `FsCalls` struct, which contains the file
+    ///   descriptor table and other system-related data.
+    /// * `filedesc_enum`: A reference to the `FileDescriptor` object
+    ///   representing the file descriptor to be duplicated.
+    /// * `newfd`: The new file descriptor number to assign to the duplicated
+    ///   file descriptor.
+    /// * `fromdup2`: A boolean flag indicating whether the call is from
+    ///   `dup2_syscall` (true) or `dup_syscall` (false).
+    ///
+    /// ### Returns
+    /// * The new file descriptor on success.
+    ///
+    /// ### Errors
+    /// * `ENFILE(23)`: If there are no available file descriptors.
+    /// * `EACCES(13)`: If the file descriptor cannot be duplicated.
+    /// ###Panics
+    /// * If the file descriptor is associated with a socket, and the inode does
+    ///   not match the file descriptor.
+
+    pub fn _dup2_helper(&self, filedesc_enum: &FileDescriptor, newfd: i32, fromdup2: bool) -> i32 {
+        let (dupfd, mut dupfdguard) = if fromdup2 {
+            let mut fdguard = self.filedescriptortable[newfd as usize].write();
+            let closebool = fdguard.is_some();
+            drop(fdguard);
+            // close the fd in the way of the new fd. mirror the implementation of linux,
+            // ignore the potential error of the close here
+            if closebool {
+                let _close_result = Self::_close_helper_inner(&self, newfd);
+            }
+
+            // re-grab clean fd
+            fdguard = self.filedescriptortable[newfd as usize].write();
+            (newfd, fdguard)
+        } else {
+            let (newdupfd, guardopt) = self.get_next_fd(Some(newfd));
+            if newdupfd < 0 {
+                // The function allocates a new file descriptor and updates the file descriptor
+                // table, handling the potential for file descriptor table
+                // overflow (resulting in an `ENFILE` error), or for reaching RLIMIT_NOFILE
+                // (resulting in an `EMFILE` error).
+                return newdupfd;
+            }
+            (newdupfd, guardopt.unwrap())
+        };
+
+        let dupfdoption = &mut *dupfdguard;
+
+        let dupd_fd_enum = match self._dup_filedesc(filedesc_enum) {
+            Ok(dupd_fd_enum) => dupd_fd_enum,
+            Err(e) => return e,
+        };
+
+        let _insertval = dupfdoption.insert(dupd_fd_enum);
+
+        return dupfd;
+    }
+
+    /// ### Description
+    ///
+    /// `_dup_filedesc` makes a copy of a file descriptor that holds its own
+    /// reference to the file, pipe or socket it describes, without the
+    /// `O_CLOEXEC` flag. Besides dup and dup2, it is used for descriptors
+    /// passed over a Unix domain socket with SCM_RIGHTS, which need to keep
+    /// what they describe alive while they are in flight.
+    ///
+    /// ### Arguments
+    ///
+    /// * `filedesc_enum` - The file descriptor to copy.
+    ///
+    /// ### Returns
+    ///
+    /// The copy of the file descriptor, or an error code.
+    ///
+    /// ### Errors
+    ///
+    /// * EACCES - The file descriptor cannot be duplicated.
+    ///
+    /// ### Panics
+    ///
+    /// * If the file descriptor is a file, and its inode is a socket or a
+    ///   symlink.
+    pub fn _dup_filedesc(&self, filedesc_enum: &FileDescriptor) -> Result<FileDescriptor, i32> {
+        match filedesc_enum {
+            File(normalfile_filedesc_obj) => {
+                let inodenum = normalfile_filedesc_obj.inode;
+                let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+                //incrementing the ref count so that when close is executed on the dup'd file
+                //the original file does not get a negative ref count
+                match *inodeobj {
+                    // increments the reference count of the file object associated with the
+                    // original file descriptor to ensure that the file object
+                    // is not deleted until all its associated file descriptors are closed.
+                    Inode::File(ref mut normalfile_inode_obj) => {
+                        normalfile_inode_obj.refcount += 1;
+                    }
+                    Inode::Dir(ref mut dir_inode_obj) => {
+                        dir_inode_obj.refcount += 1;
+                    }
+                    Inode::CharDev(ref mut chardev_inode_obj) => {
+                        chardev_inode_obj.refcount += 1;
+                    }
+                    Inode::Socket(_) => panic!("dup: fd and inode do not match."),
+                    Inode::Symlink(_) => panic!("dup: fd and inode do not match."),
+                    Inode::Fifo(_) => panic!("dup: fd and inode do not match."),
+                }
+            }
+            Pipe(pipe_filedesc_obj) => {
+                pipe_filedesc_obj.pipe.incr_ref(pipe_filedesc_obj.flags);
+                // a descriptor of a FIFO also refers to its inode
+                if let Some(inodenum) = pipe_filedesc_obj.fifoinode {
+                    if let Inode::Fifo(ref mut fifo_inode_obj) =
+                        *FS_METADATA.inodetable.get_mut(&inodenum).unwrap()
+                    {
+                        fifo_inode_obj.refcount += 1;
+                    }
+                }
+            }
+            Socket(ref socket_filedesc_obj) => {
+                //we handle the closing of sockets on drop
+                // checking whether this is a domain socket
+
+                let sock_tmp = socket_filedesc_obj.handle.clone();
+                let sockhandle = sock_tmp.write();
+                let socket_type = sockhandle.domain;
+                if socket_type == AF_UNIX {
+                    if let Some(sockinfo) = &sockhandle.unix_info {
+                        if let Some(sendpipe) = sockinfo.sendpipe.as_ref() {
+                            sendpipe.incr_ref(O_WRONLY);
+                        }
+                        if let Some(receivepipe) = sockinfo.receivepipe.as_ref() {
+                            receivepipe.incr_ref(O_RDONLY);
+                        }
+                        if let Some(receivemsgpipe) = sockinfo.receivemsgpipe.as_ref() {
+                            receivemsgpipe.incr_ref(O_RDONLY);
+                        }
+                        // a datagram socket doesn't hold the queue it sends to open
+                        if sockhandle.socktype & 0x7 == SOCK_SEQPACKET {
+                            if let Some(sendmsgpipe) = sockinfo.sendmsgpipe.as_ref() {
+                                sendmsgpipe.incr_ref(O_WRONLY);
+                            }
+                        }
+                    }
+                }
+            }
+            Stream(_normalfile_filedesc_obj) => {
+                // no stream refs
+            }
+            _ => {
+                return Err(syscall_error(
+                    Errno::EACCES,
+                    "dup or dup2",
+                    "can't dup the provided file",
+                ));
+            }
+        }
+
+        let mut dupd_fd_enum = filedesc_enum.clone(); //clones the arc for sockethandle
+
+        // get and clone fd, wrap and insert into table.
+        match dupd_fd_enum {
+            // we don't want to pass on the CLOEXEC flag
+            File(ref mut normalfile_filedesc_obj) => {
+                normalfile_filedesc_obj.flags = normalfile_filedesc_obj.flags & !O_CLOEXEC;
+            }
+            Pipe(ref mut pipe_filedesc_obj) => {
+                pipe_filedesc_obj.flags = pipe_filedesc_obj.flags & !O_CLOEXEC;
+            }
+            Socket(ref mut socket_filedesc_obj) => {
+                // can do this for domainsockets and sockets
+                socket_filedesc_obj.flags = socket_filedesc_obj.flags & !O_CLOEXEC;
+            }
+            Stream(ref mut stream_filedesc_obj) => {
+                stream_filedesc_obj.flags = stream_filedesc_obj.flags & !O_CLOEXEC;
+            }
+            _ => {
+                return Err(syscall_error(
+                    Errno::EACCES,
+                    "dup or dup2",
+                    "can't dup the provided file",
+                ));
+            }
+        }
+
+        Ok(dupd_fd_enum)
+    }
+
+    /// ## ------------------CLOSE SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `close_syscall()` is used to close a file descriptor, so that it no
+    /// longer refers to any file and may be reused. This is an essential
+    /// operation for managing resources, as file descriptors are a limited
+    /// resource in any operating system.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `close_syscall()` receives one argument:
+    /// * `fd` - This argument refers to the file descriptor to be closed.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion of this call, it returns 0. Otherwise, it
+    /// returns error number set with the appropriate error code.
+    ///
+    /// ### Errors
+    ///
+    /// * The syscall returns errors which occur in its helper functions.
+    ///
+    /// ### Panics
+    ///
+    /// * This syscall indirectly panics when the helper function panics.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [close(2)](https://man7.org/linux/man-pages/man2/close.2.html)
+    pub fn close_syscall(&self, fd: i32) -> i32 {
+        // Call the helper function which validates the result and deletes
+        // reference of the file descriptor.
+        return Self::_close_helper(self, fd);
+    }
+
+    /// ## ----------------- CLOSE HELPER INNER ------------------
+    /// ### Description
+    ///
+    /// The `_close_helper_inner` function manages the cleanup and closing of
+    /// various types of file descriptors (files, directories, pipes, sockets)
+    /// by decrementing reference counts and removing inodes if necessary.
+    /// ### Function Arguments
+    ///
+    /// The function receives one argument:
+    /// * `fd` - This argument refers to the file descriptor to be closed.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion of this call, it returns 0. Otherwise, it
+    /// returns errors set with the appropriate error code.
+    ///
+    /// ### Errors
+    ///
+    /// * EBADF - The given file descriptor is invalid
+    /// * ENOEXEC - The Non-regular type file (dir/chardev) in file object table
+    ///
+    /// ### Panics
+    ///
+    /// * If the provided file descriptor is out of bounds, causing unwrap() to
+    ///   panic
+    /// * When the file type filedescriptor contains a Socket as an inode.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [close(2)](https://man7.org/linux/man-pages/man2/close.2.html)
+    pub fn _close_helper_inner(&self, fd: i32) -> i32 {
+        //BUG
+        //if the provided file descriptor is out of bounds, get_filedescriptor returns
+        // Err(), unwrapping on which  produces a 'panic!'
+        //otherwise, file descriptor table entry is stored in 'checkedfd'
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let mut unlocked_fd = checkedfd.write();
+        if let Some(filedesc_enum) = &mut *unlocked_fd {
+            self._release_filedesc(filedesc_enum)
+        } else {
+            return syscall_error(Errno::EBADF, "close", "invalid file descriptor");
+        }
+    }
+
+    /// ### Description
+    ///
+    /// `_release_filedesc` drops the references a file descriptor holds on
+    /// the file, pipe or socket it describes, removing inodes that are no
+    /// longer linked or used. It does the work of `_close_helper_inner`, and
+    /// is also used for descriptors that were passed over a Unix domain
+    /// socket with SCM_RIGHTS but never received, as these are not in any
+    /// file descriptor table.
+    ///
+    /// ### Arguments
+    ///
+    /// * `filedesc_enum` - The file descriptor to release.
+    ///
+    /// ### Returns
+    ///
+    /// 0 on success, or an error code as described below.
+    ///
+    /// ### Errors
+    ///
+    /// * ENOEXEC - The Non-regular type file (dir/chardev) in file object table
+    ///
+    /// ### Panics
+    ///
+    /// * When the file type filedescriptor contains a Socket as an inode.
+    pub fn _release_filedesc(&self, filedesc_enum: &mut FileDescriptor) -> i32 {
+        // We decide, how to proceed depending on the fd type.
+        // First we check in the file descriptor to handle stream / epoll (no-op),
+        // sockets (clean the socket), and pipes (clean the pipe), and if it is a
+        // normal file descriptor we decrement the reference count to reflect
+        // one less reference to the file.
+        match filedesc_enum {
+            //if we are a socket, we dont change disk metadata
+            Stream(_) => {} // Streams don't require any additional cleanup
+            Epoll(_) => {}  // TODO: Epoll closing not implemented yet
+            // Eventfds, timerfds and signalfds go away with their last descriptor
+            EventFd(_) | TimerFd(_) | SignalFd(_) => {}
+            Socket(ref mut socket_filedesc_obj) => {
+                // Retrieve the socket file descriptor object and get the write
+                // lock on the socket handle.
+                let sock_tmp = socket_filedesc_obj.handle.clone();
+                let mut sockhandle = sock_tmp.write();
+
+                // ancillary data that can no longer be received once a pipe is gone
+                let mut unreceived = vec![];
+
+                // we need to do the following if UDS (Unix Domain Socket)
+                // AF_UNIX represents the Unix domain sockets.
+                let socket_type = sockhandle.domain;
+                let is_seqpacket = sockhandle.socktype & 0x7 == SOCK_SEQPACKET;
+                if socket_type == AF_UNIX {
+                    if let Some(ref mut ui) = sockhandle.unix_info {
+                        let inodenum = ui.inode;
+                        // Decrement the reference count for the send pipe if it exists. If the
+                        // reference count drops to zero, remove the send pipe.
+                        if let Some(sendpipe) = ui.sendpipe.as_ref() {
+                            sendpipe.decr_ref(O_WRONLY);
+                            //last reference, lets remove it along with its control queue
+                            if sendpipe.is_pipe_closed() {
+                                ui.sendpipe = None;
+                                if let Some(control) = ui.sendcontrol.take() {
+                                    unreceived.extend(control.lock().drain(..));
+                                }
+                            }
+                        }
+                        // Decrement the reference count for the receive pipe if it exists. If
+                        // the reference count drops to zero, remove
+                        // the receive pipe.
+                        if let Some(receivepipe) = ui.receivepipe.as_ref() {
+                            receivepipe.decr_ref(O_RDONLY);
+                            //last reference, lets remove it
+                            if receivepipe.is_pipe_closed() {
+                                ui.receivepipe = None;
+                                if let Some(control) = ui.receivecontrol.take() {
+                                    unreceived.extend(control.lock().drain(..));
+                                }
+                            }
+                        }
+                        // Message pipes are handled the same way, except that the messages
+                        // in a receive queue are dropped as soon as nothing can read them.
+                        // A datagram socket's receive queue can then no longer be sent to,
+                        // and the queue it sends to is not its own to close.
+                        if let Some(receivemsgpipe) = ui.receivemsgpipe.as_ref() {
+                            receivemsgpipe.decr_ref(O_RDONLY);
+                            if receivemsgpipe.is_read_closed() {
+                                if let Some(inodenum) = inodenum {
+                                    NET_METADATA.domsock_dgram_table.remove_if(
+                                        &inodenum,
+                                        |_, queue| {
+                                            interface::RustRfc::ptr_eq(queue, receivemsgpipe)
+                                        },
+                                    );
+                                }
+                                for msg in receivemsgpipe.drain() {
+                                    unreceived.extend(msg.meta.control);
+                                }
+                                ui.receivemsgpipe = None;
+                            }
+                        }
+                        if is_seqpacket {
+                            if let Some(sendmsgpipe) = ui.sendmsgpipe.as_ref() {
+                                sendmsgpipe.decr_ref(O_WRONLY);
+                                if sendmsgpipe.is_pipe_closed() {
+                                    for msg in sendmsgpipe.drain() {
+                                        unreceived.extend(msg.meta.control);
+                                    }
+                                    ui.sendmsgpipe = None;
+                                }
+                            }
+                        }
+                        // Retrieve the inode object for the socket and decrement its reference
+                        // count. If both the reference count and
+                        // link count are zero, the socket is no longer needed.
+                        // A socket bound to an abstract address has no inode, its name is
+                        // given back once the socket handle is dropped.
+                        if let Some(inodenum) = inodenum {
+                            let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+                            if let Inode::Socket(ref mut sock) = *inodeobj {
+                                sock.refcount -= 1;
+                                if sock.refcount == 0 && sock.linkcount == 0 {
+                                    // Remove the socket from the inode table and the domain
+                                    // socket paths if it is no longer needed.
+                                    drop(inodeobj);
+                                    // Get the entire path of the socket which is used for
+                                    // removing it from the metadata file.
+                                    let path = normpath(
+                                        convpath(sockhandle.localaddr.unwrap().path()),
+                                        self,
+                                    );
+                                    // Remove the reference of the inode from the inodetable
+                                    FS_METADATA.remove_inode(inodenum);
+                                    // Remove any domain socket paths associated with the socket
+                                    NET_METADATA.domsock_paths.remove(&path);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Descriptors still in flight are closed the same way as ones in a table,
+                // after letting go of our socket in case one of them refers to it
+                drop(sockhandle);
+                for msg in unreceived {
+                    for mut inflight in msg.rights {
+                        self._release_filedesc(&mut inflight);
+                    }
+                }
+            }
+            Pipe(ref pipe_filedesc_obj) => {
+                // Decrease the pipe objects internal ref count for the corresponding end
+                // depending on what flags are set (O_RDONLY or O_WRONLY). The pipe of a
+                // FIFO is let go of through its inode, which holds on to it.
+                match pipe_filedesc_obj.fifoinode {
+                    Some(inodenum) => Self::_release_fifo(
+                        inodenum,
+                        &pipe_filedesc_obj.pipe,
+                        pipe_filedesc_obj.flags,
+                    ),
+                    None => pipe_filedesc_obj.pipe.decr_ref(pipe_filedesc_obj.flags),
+                }
+            }
+            File(ref normalfile_filedesc_obj) => {
+                // Retrieve the inode object for the file.
+                let inodenum = normalfile_filedesc_obj.inode;
+                let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+
+                match *inodeobj {
+                    Inode::File(ref mut normalfile_inode_obj) => {
+                        normalfile_inode_obj.refcount -= 1;
+
+                        // Check if it's not a regular file, then we have nothing to close
+                        // Inode::File is a regular file by default
+                        // Reference count represents the number of active file descriptors
+                        // pointing to the file.
+                        if normalfile_inode_obj.refcount == 0 {
+                            // FileObjectTable stores the entries of the currently opened files
+                            // in the system, so if there are no
+                            // active file descriptors pointing to the
+                            // file, we delete them from the table.
+                            FILEOBJECTTABLE
+                                .remove(&inodenum)
+                                .unwrap()
+                                .1
+                                .close()
+                                .unwrap();
+                            // Link count as 0 represents that there are no hard links present
+                            // for the file, so we need to remove it from the filesystem.
+                            if normalfile_inode_obj.linkcount == 0 {
+                                drop(inodeobj);
+                                // removing the file from the entire filesystem (interface,
+                                // metadata, and object table)
+                                FS_METADATA.remove_inode(inodenum);
+                                // FILEDATAPREFIX represents the common prefix of the name
+                                // of the file which combined with the inode number represents
+                                // a unique entity. It stores the data of the inode object.
+                                let sysfilename = format!("{}{}", FILEDATAPREFIX, inodenum);
+                                interface::storage_backend()
+                                    .remove_data(&sysfilename)
+                                    .unwrap();
+                                log_metadata(&FS_METADATA, inodenum);
+                            } else {
+                                drop(inodeobj);
+                            }
+                        }
+                    }
+                    Inode::Dir(ref mut dir_inode_obj) => {
+                        dir_inode_obj.refcount -= 1;
+
+                        // File object table only contains references for the regular
+                        // type files, and since "Directory" is not a regular file type,
+                        // it should not exist in the table. Return an error if the
+                        // directory exists in the file object table.
+                        match FILEOBJECTTABLE.get(&inodenum) {
+                            Some(_) => {
+                                return syscall_error(
+                                    Errno::ENOEXEC,
+                                    "close or dup",
+                                    "Non-regular file (dir) in file object table",
+                                );
+                            }
+                            // Continue if the object table doesn't contain the inode.
+                            None => {}
+                        }
+                        // When the link count is 2 and the reference count is 0, it means
+                        // the directory is empty and no processes are using it. This allows
+                        // the directory to be safely removed from the file system.
+                        if dir_inode_obj.linkcount == 2 && dir_inode_obj.refcount == 0 {
+                            //The reference to the inode has to be dropped to avoid
+                            //deadlocking because the remove() method will need to
+                            //acquire a reference to the same inode from the
+                            //filesystem's inodetable.
+                            //The inodetable represents a Rust DashMap that deadlocks
+                            //when trying to get a reference to its entry while holding any
+                            // sort of reference into it.
+                            drop(inodeobj);
+                            FS_METADATA.remove_inode(inodenum);
+                            log_metadata(&FS_METADATA, inodenum);
+                        }
+                    }
+                    Inode::CharDev(ref mut char_inode_obj) => {
+                        char_inode_obj.refcount -= 1;
+                        // Since "CharDev" is not a regular file type, it should not
+                        // exist in the file object table. Return an error if the
+                        // chardev inode exists in the file object table.
+                        match FILEOBJECTTABLE.get(&inodenum) {
+                            Some(_) => {
+                                return syscall_error(
+                                    Errno::ENOEXEC,
+                                    "close or dup",
+                                    "Non-regular file (chardev) in file object table",
+                                );
+                            }
+                            // Continue if the object table doesn't contain the inode.
+                            None => {}
+                        }
+                        if char_inode_obj.linkcount == 0 && char_inode_obj.refcount == 0 {
+                            //removing the file from the metadata
+                            drop(inodeobj);
+                            FS_METADATA.remove_inode(inodenum);
+                        } else {
+                            drop(inodeobj);
+                        }
+                        log_metadata(&FS_METADATA, inodenum);
+                    }
+                    // A Sanity check is added to make sure that there is no such case when the
+                    // fd type is "File" and the inode type is "Socket". This state is ideally
+                    // not possible, so we panic in such cases.
+                    Inode::Socket(_) => {
+                        panic!("close(): Socket inode found on a filedesc fd.")
+                    }
+                    // Symbolic links are always resolved or rejected by open, so a "File"
+                    // type fd can never refer to one.
+                    Inode::Symlink(_) => {
+                        panic!("close(): symlink inode found on a filedesc fd")
+                    }
+                    // FIFOs are always opened as pipes, so a "File" type fd can never
+                    // refer to one.
+                    Inode::Fifo(_) => {
+                        panic!("close(): FIFO inode found on a filedesc fd")
+                    }
+                }
+            }
+        }
+        // If everything is successful, we return 0
+        0
+    }
+
+    /// ## ------------------CLOSE HELPER ------------------
+    /// ### Description
+    ///
+    /// The `_close_helper` function calls `_close_helper_inner` to handle
+    /// the specifics of closing the file descriptor (like decrementing
+    /// reference counts, handling special cases for sockets, pipes, etc.).
+    /// Once `_close_helper_inner` has successfully done its job (indicated
+    /// by returning a non-negative value), `_close_helper` removes the file
+    /// descriptor from the file descriptor table. This ensures that the file
+    /// descriptor is fully closed and no longer tracked by the system.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `_close_helper()` receives one argument:
+    /// * `fd` - This argument refers to the file descriptor to be closed.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion of this call, it returns 0. Otherwise, it
+    /// returns an error number with the appropriate error code.
+    ///
+    /// ### Errors
+    ///
+    /// * There are no explicit errors returned by this function. The errors
+    ///   thrown by `_close_helper_inner` function are returned.
+    ///
+    /// ### Panics
+    ///
+    /// * If the provided file descriptor is out of bounds, causing unwrap() to
+    ///   panic.
+    pub fn _close_helper(&self, fd: i32) -> i32 {
+        let inner_result = self._close_helper_inner(fd);
+        // Return value of less than 0 indicates that there was some error
+        // closing the fd, so we return the error.
+        if inner_result < 0 {
+            return inner_result;
+        }
+
+        // Once we know that the closing of the fd was successful,
+        // we remove the file descriptor from the fd table and free the space.
+        //
+        // Bug:
+        // if the provided file descriptor is out of bounds, get_filedescriptor returns
+        // Err(), unwrapping on which  produces a 'panic!'
+        //otherwise, file descriptor table entry is stored in 'checkedfd'
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let mut unlocked_fd = checkedfd.write();
+        // This operation effectively removes the file descriptor from the fd table
+        // by removing its reference from the variable and then not using it further,
+        // allowing it to be dropped
+        if unlocked_fd.is_some() {
+            let _discarded_fd = unlocked_fd.take();
+        }
+        0 //_close_helper has succeeded!
+    }
+
+    /// ### Description
+    ///
+    /// `fcntl_syscall` performs operations, like returning or setting file
+    /// status flags, duplicating a file descriptor, etc., on an open file
+    /// descriptor
+    ///
+    /// ### Arguments
+    ///
+    /// it accepts three parameters:
+    /// * `fd` - an open file descriptor
+    /// * `cmd` - an operation to be performed on fd
+    /// * `arg` - an optional argument (whether or not arg is required is
+    ///   determined by cmd)
+    ///
+    /// ### Returns
+    ///
+    /// for a successful call, the return value depends on the operation and can
+    /// be one of: zero, the new file descriptor, value of file descriptor
+    /// flags, value of status flags, etc.
+    ///
+    /// ### Errors
+    ///
+    /// * EBADF - fd is not a valid file descriptor, or F_GETPIPE_SZ or
+    ///   F_SETPIPE_SZ was given an fd that isn't a pipe
+    /// * EBUSY - F_SETPIPE_SZ would make a pipe smaller than what is in it
+    /// * EINVAL - doesnt match implementation parameters, or F_SETPIPE_SZ was
+    ///   asked for a pipe larger than PIPE_HARD_MAX_SIZE
+    /// * EPERM - F_SETPIPE_SZ would make a pipe larger than PIPE_MAX_SIZE,
+    ///   which only root may do
+    ///
+    /// ### Panics
+    ///
+    /// * invalid or out-of-bounds file descriptor), calling unwrap() on it will
+    ///   cause a panic.
+    /// * Unknown errno value from fcntl returned, will cause panic.
+    ///
+    /// for more detailed description of all the commands and return values, see
+    /// [fcntl(2)](https://linux.die.net/man/2/fcntl)
+
+    pub fn fcntl_syscall(&self, fd: i32, cmd: i32, arg: i32) -> i32 {
+        //BUG
+        //if the provided file descriptor is out of bounds, get_filedescriptor returns
+        // Err(), unwrapping on which  produces a 'panic!'
+        //otherwise, file descriptor table entry is stored in 'checkedfd'
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let mut unlocked_fd = checkedfd.write();
+        if let Some(filedesc_enum) = &mut *unlocked_fd {
+            //'flags' consists of bitwise-or'd access mode, file creation, and file status flags
+            //to retrieve a particular flag, it can bitwise-and'd with 'flags'
+            let flags = match filedesc_enum {
+                Epoll(obj) => &mut obj.flags,
+                EventFd(obj) => &mut obj.flags,
+                TimerFd(obj) => &mut obj.flags,
+                SignalFd(obj) => &mut obj.flags,
+                Pipe(obj) => &mut obj.flags,
+                Stream(obj) => &mut obj.flags,
+                File(obj) => &mut obj.flags,
+                //not clear why running F_SETFL on Socket type requires special treatment
+                Socket(ref mut sockfdobj) => {
+                    if cmd == F_SETFL && arg >= 0 {
+                        let sock_tmp = sockfdobj.handle.clone();
+                        let mut sockhandle = sock_tmp.write();
+
+                        if let Some(ins) = &mut sockhandle.innersocket {
+                            let fcntlret;
+                            if arg & O_NONBLOCK == O_NONBLOCK {
+                                //set non-blocking I/O
+                                fcntlret = ins.set_nonblocking();
+                            } else {
+                                //set blocking I/O
+                                fcntlret = ins.set_blocking();
+                            }
+                            if fcntlret < 0 {
+                                match Errno::from_discriminant(interface::get_errno()) {
+                                    Ok(i) => {
+                                        return syscall_error(
+                                            i,
+                                            "fcntl",
+                                            "The libc call to fcntl failed!",
+                                        );
+                                    }
+                                    Err(()) => panic!("Unknown errno value from fcntl returned!"),
+                                };
+                            }
+                        }
+                    }
+
+                    &mut sockfdobj.flags
+                }
+            };
+
+            //matching the tuple
+            match (cmd, arg) {
+                //because the arg parameter is not used in certain commands, it can be anything (..)
+                //F_GETFD returns file descriptor flags only, meaning that access mode flags
+                //and file status flags are excluded
+                //F_SETFD is used to set file descriptor flags only, meaning that any changes to access mode flags
+                //or file status flags should be ignored
+                //currently, O_CLOEXEC is the only defined file descriptor flag, thus only this flag is
+                //masked when using F_GETFD or F_SETFD
+                (F_GETFD, ..) => *flags & O_CLOEXEC,
+                (F_SETFD, arg) if arg >= 0 => {
+                    if arg & O_CLOEXEC != 0 {
+                        //if O_CLOEXEC flag is set to 1 in 'arg', 'flags' is updated by setting its O_CLOEXEC bit to 1
+                        *flags |= O_CLOEXEC;
+                    } else {
+                        //if O_CLOEXEC flag is set to 0 in 'arg', 'flags' is updated by setting its O_CLOEXEC bit to 0
+                        *flags &= !O_CLOEXEC;
+                    }
+                    0
+                }
+                //F_GETFL should return file access mode and file status flags, which means that
+                //file creation flags should be masked out
+                (F_GETFL, ..) => *flags & !(O_CREAT | O_EXCL | O_NOCTTY | O_TRUNC),
+                //F_SETFL is used to set file status flags, thus any changes to file access mode and file
+                //creation flags should be ignored (see F_SETFL command in the man page for fcntl for the reference)
+                (F_SETFL, arg) if arg >= 0 => {
+                    //valid changes are extracted by ignoring changes to file access mode and file creation flags
+                    let valid_changes =
+                        arg & !(O_RDWRFLAGS | O_CREAT | O_EXCL | O_NOCTTY | O_TRUNC);
+                    //access mode and creation flags are extracted and other flags are set to 0 to update them
+                    let acc_and_creation_flags =
+                        *flags & (O_RDWRFLAGS | O_CREAT | O_EXCL | O_NOCTTY | O_TRUNC);
+                    //valid changes are combined with the old file access mode and file creation flags
+                    *flags = valid_changes | acc_and_creation_flags;
+                    0
+                }
+                (F_DUPFD, arg) if arg >= 0 => {
+                    if arg as u64 >= self.get_rlimit(RLIMIT_NOFILE).rlim_cur {
+                        return syscall_error(
+                            Errno::EINVAL,
+                            "fcntl",
+                            "arg is not below the limit on open files",
+                        );
+                    }
+                    self._dup2_helper(&filedesc_enum, arg, false)
+                }
+                //F_GETPIPE_SZ and F_SETPIPE_SZ only apply to pipes, which includes FIFOs
+                (F_GETPIPE_SZ, ..) => match filedesc_enum {
+                    Pipe(pipe_filedesc_obj) => pipe_filedesc_obj.pipe.capacity() as i32,
+                    _ => syscall_error(Errno::EBADF, "fcntl", "fd does not refer to a pipe"),
+                },
+                (F_SETPIPE_SZ, arg) if arg >= 0 => match filedesc_enum {
+                    Pipe(pipe_filedesc_obj) => {
+                        //the limits are powers of two, so checking them before rounding
+                        //the size up keeps the rounding from overflowing
+                        if arg as usize > PIPE_HARD_MAX_SIZE {
+                            return syscall_error(
+                                Errno::EINVAL,
+                                "fcntl",
+                                "the requested pipe size is too large",
+                            );
+                        }
+                        if arg as usize > PIPE_MAX_SIZE && self.get_effective_ids().0 != 0 {
+                            return syscall_error(
+                                Errno::EPERM,
+                                "fcntl",
+                                "only root may make a pipe larger than the pipe size limit",
+                            );
+                        }
+                        //the size is rounded up to a power of two number of pages, same as Linux
+                        let size = (arg as usize).max(PAGE_SIZE).next_power_of_two();
+                        let ret = pipe_filedesc_obj.pipe.resize(size);
+                        if ret < 0 {
+                            return ret;
+                        }
+                        size as i32
+                    }
+                    _ => syscall_error(Errno::EBADF, "fcntl", "fd does not refer to a pipe"),
+                },
+                //TO DO: F_GETOWN and F_SETOWN commands are not implemented yet
+                (F_GETOWN, ..) => 0,
+                (F_SETOWN, arg) if arg >= 0 => 0,
+                _ => {
+                    let err_msg = format!(
+                        "Arguments pair ({}, {}) does not match implemented parameters",
+                        cmd, arg
+                    );
+                    syscall_error(Errno::EINVAL, "fcntl", &err_msg)
+                }
+            }
+        } else {
+            syscall_error(Errno::EBADF, "fcntl", "File descriptor is out of range")
+        }
+    }
+
+    /// ### Description
+    ///
+    /// The `ioctl_syscall()` manipulates the underlying device parameters of
+    /// special files. In particular, it is used as a way for user-space
+    /// applications to interface with device drivers.
+    ///
+    /// ### Arguments
+    ///
+    /// The `ioctl_syscall()` accepts three arguments:
+    /// * `fd` - an open file descriptor that refers to a device.
+    /// * `request` - the control function to be performed. The set of valid
+    ///   request values depends entirely on the device being addressed.
+    ///   MEDIA_IOC_DEVICE_INFO is an example of an ioctl control function to
+    ///   query device information that all media devices must support.
+    /// * `ptrunion` - additional information needed by the addressed device to
+    ///   perform the selected control function. In the example of
+    ///   MEDIA_IOC_DEVICE_INFO request, a valid ptrunion value is a pointer to
+    ///   a struct media_device_info, from which the device information is
+    ///   obtained.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion, a value other than -1 that depends on the
+    /// selected control function is returned. In case of a failure, -1 is
+    /// returned with errno set to a particular value, like EBADF, EINVAL, etc.
+    ///
+    /// ### Errors and Panics
+    ///
+    /// * `EBADF` - fd is not a valid file descriptor
+    /// * `EFAULT` - ptrunion references an inaccessible memory area
+    /// * `EINVAL` - request or ptrunion is not valid
+    /// * `ENOTTY` - fd is not associated with a character special device
+    /// When `ioctl_syscall() is called on a Socket with `FIONBIO` control
+    /// function, an underlying call to `libc::fcntl()` is made,
+    /// which can return with an error. For a complete list of possible erorrs,
+    /// see [fcntl(2)](https://linux.die.net/man/2/fcntl)
+    ///
+    /// A panic occurs either when a provided file descriptor is out of bounds
+    /// or when an underlying call to `libc::fcntl()` for Socket type is
+    /// returned with an unknown error.
+    ///
+    /// The `FICLONE` control function is handled by `_clone_file()`, with the
+    /// descriptor of the file to clone passed by value in `ptrunion`.
+    ///
+    /// To learn more about the syscall, control functions applicable to all the
+    /// devices, and possible error values, see [ioctl(2)](https://man.openbsd.org/ioctl)
+
+    pub fn ioctl_syscall(&self, fd: i32, request: u32, ptrunion: IoctlPtrUnion) -> i32 {
+        // cloning a file looks up both of the file descriptors itself
+        if request == FICLONE {
+            return self._clone_file(interface::get_ioctl_value(ptrunion), fd);
+        }
+        //BUG
+        //if the provided file descriptor is out of bounds, 'get_filedescriptor'
+        // returns Err(), unwrapping on which  produces a 'panic!'
+        //otherwise, file descriptor table entry is stored in 'checkedfd'
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let mut unlocked_fd = checkedfd.write();
+        //if a table descriptor entry is non-empty, a valid request is performed
+        if let Some(filedesc_enum) = &mut *unlocked_fd {
+            //For now, the only implemented control function is FIONBIO command used with
+            // sockets
+            match request {
+                //for FIONBIO, 'ptrunion' stores a pointer to an integer. If the integer is 0, the
+                // socket's nonblocking I/O is cleared. Otherwise, the socket is set
+                // for nonblocking I/O
+                FIONBIO => {
+                    //if 'ptrunion' stores a Null pointer, a 'Bad address' error is returned
+                    //otheriwse, the integer value stored in that address is returned and saved
+                    // into 'arg_result'
+                    let arg_result = interface::get_ioctl_int(ptrunion);
+                    match (arg_result, filedesc_enum) {
+                        (Err(arg_result), ..)=> {
+                            return arg_result;
+                        }
+                        //since FIONBIO command is used with sockets, we need to make sure that the provided
+                        //file descriptor addresses a socket
+                        //otherwise, a 'Not a typewriter' error designating that the specified command
+                        //is only applicable to sockets is returned 
+                        (Ok(arg_result), Socket(ref mut sockfdobj)) => {
+                            let sock_tmp = sockfdobj.handle.clone();
+                            let mut sockhandle = sock_tmp.write();
+                            let flags = &mut sockfdobj.flags;
+                            let arg: i32 = arg_result;
+                            let mut ioctlret = 0;
+                            //clearing nonblocking I/O on the socket if the integer is 0
+                            if arg == 0 {
+                                *flags &= !O_NONBLOCK;
+                                //libc::fcntl is called under the hood with F_SETFL command and 0 as an argument
+                                //to set blocking I/O, and the result of the call is stored in ioctlret
+                                if let Some(ins) = &mut sockhandle.innersocket {
+
+                                    ioctlret = ins.set_blocking();
+                                }
+                            } else {
+                                *flags |= O_NONBLOCK;
+                                //libc::fcntl is called under the hood with F_SETFL command ans O_NONBLOCK as an argument
+                                //to set nonblocking I/O, and the result of the call is stored in ioctlret
+                                if let Some(ins) = &mut sockhandle.innersocket {
+                                    ioctlret = ins.set_nonblocking();
+                                }
+                            }
+                            //if ioctlret is negative, it means that the call to fcntl returned with an error
+                            if ioctlret < 0 {
+                                match Errno::from_discriminant(interface::get_errno()) {
+                                    Ok(i) => {return syscall_error(i, "ioctl", "The libc call to ioctl failed!");},
+                                    Err(()) => panic!("Unknown errno value from ioctl returned!"),
+                                };
+                            }
+
+                            0
+                        }
+                        _ => {syscall_error(Errno::ENOTTY, "ioctl", "The specified request does not apply to the kind of object that the file descriptor fd references.")}
+                    }
+                }
+                FIOASYNC => {
+                    //not implemented
+                    interface::log_verbose(
+                        "ioctl(FIOASYNC) is not implemented, and just returns 0.",
+                    );
+                    0
+                }
+                _ => syscall_error(
+                    Errno::EINVAL,
+                    "ioctl",
+                    "Arguments provided do not match implemented parameters",
+                ),
+            }
         } else {
-            return syscall_error(
-                Errno::ENOENT,
-                "chdir",
-                "the directory referred to in path does not exist",
-            );
+            syscall_error(Errno::EBADF, "ioctl", "Invalid file descriptor")
         }
     }
 
-    ///##------------------------------------DUP & DUP2 SYSCALLS------------------------------------
-    /// ## `dup_syscall`
-    ///
     /// ### Description
-    /// This function duplicates a file descriptor. It creates a new file
-    /// descriptor that refers to the same open file description as the original
-    /// file descriptor.
-    /// * Finding the Next Available File Descriptor: If `start_desc` is
-    ///   provided and it is already in use, the function will continue
-    ///   searching for the next available file descriptor starting from
-    ///   `start_desc`. If no file descriptors are available, it will return an
-    ///   error (`ENFILE`).
-    /// * If `fd` is equal to `start_fd`, the function returns `start_fd` as the
-    ///   new file descriptor. This is because in this scenario, the original
-    ///   and new file descriptors would point to the same file description.
-    /// * The `_dup2_helper` function is called to perform the actual file
-    ///   descriptor duplication, handling the allocation of a new file
-    ///   descriptor, updating the file descriptor table, and incrementing the
-    ///   reference count of the file object.
-    /// * The function modifies the global `filedescriptortable` array, adding a
-    ///   new entry for the duplicated file descriptor. It also increments the
-    ///   reference count of the file object associated with the original file
-    ///   descriptor.
-    /// * The `false` argument passed to `_dup2_helper` indicates that this call
-    ///   is from the `dup_syscall` function, not the `dup2_syscall` function.
     ///
-    /// ### Function Arguments
-    /// * `fd`: The original file descriptor to duplicate.
-    /// * `start_desc`:  An optional starting file descriptor number. If
-    ///   provided, the new file descriptor will be
-    ///  assigned the first available file descriptor number starting from this
-    /// value. If not provided, it defaults to  `STARTINGFD`,which is the
-    /// minimum designated file descriptor value for new file descriptors.
+    /// The `_clone_file()` is a helper function for `ioctl_syscall()` with the
+    /// `FICLONE` control function. It replaces all of the data of `destfd` with
+    /// the data of `srcfd`, which the storage backend shares between the two
+    /// files instead of copying. Writing to either file afterwards leaves the
+    /// other one as it was. The image backend shares the data block by block,
+    /// while the host directory backend shares the whole file until the first
+    /// write to either one, which copies all of it.
     ///
     /// ### Returns
-    /// * The new file descriptor on success.
-    /// * `EBADF`: If the original file descriptor is invalid.
-    /// * `ENFILE`: If there are no available file descriptors.
+    ///
+    /// 0 on success, or the error.
     ///
     /// ### Errors
-    /// * `EBADF(9)`: If the original file descriptor is invalid.
-    /// * `ENFILE(23)`: If there are no available file descriptors.
-    ///  ###Panics
-    /// * There are no panics for this syscall
-    ///[dup(2)](https://man7.org/linux/man-pages/man2/dup.2.html)
+    ///
+    /// * `EBADF` - either fd is not a valid file descriptor, `srcfd` is not
+    ///   open for reading, or `destfd` is not open for writing or is open for
+    ///   appending.
+    /// * `EXDEV` - either fd is not on the lind file system, such as a device,
+    ///   a pipe or a socket.
+    /// * `EINVAL` - either fd is not a regular file, or both refer to the same
+    ///   file.
+    /// * `EISDIR` - either fd is a directory.
+    /// * `EOPNOTSUPP` - the storage backend can't share data, and would have to
+    ///   copy it instead.
+    /// * `EFBIG`, `ENOSPC`, `EDQUOT` - the destination can't grow to the size
+    ///   of the source, see `truncate_syscall()`.
+    /// * `EIO` - the storage backend failed to share or copy the data.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [ioctl_ficlone(2)](https://man7.org/linux/man-pages/man2/ioctl_ficlone.2.html)
+    fn _clone_file(&self, srcfd: i32, destfd: i32) -> i32 {
+        let mut files = [(0, 0); 2];
+        for (file, fd) in files.iter_mut().zip([srcfd, destfd]) {
+            *file = match self._regular_file_of(fd, "ioctl") {
+                Ok(Some(file)) => file,
+                Ok(None) if !self._on_file_system(fd) => {
+                    return syscall_error(
+                        Errno::EXDEV,
+                        "ioctl",
+                        "fd is not on the same file system",
+                    )
+                }
+                Ok(None) => {
+                    return syscall_error(
+                        Errno::EINVAL,
+                        "ioctl",
+                        "fd does not refer to a regular file",
+                    )
+                }
+                Err(e) => return e,
+            };
+        }
+        let [(srcinode, srcflags), (destinode, destflags)] = files;
+        if is_wronly(srcflags) || is_rdonly(destflags) || destflags & O_APPEND != 0 {
+            return syscall_error(
+                Errno::EBADF,
+                "ioctl",
+                "fd is not open for the direction of the clone",
+            );
+        }
+        if srcinode == destinode {
+            return syscall_error(
+                Errno::EINVAL,
+                "ioctl",
+                "the source and destination are the same file",
+            );
+        }
+        let storage = interface::storage_backend();
+        if !storage.shares_data() {
+            return syscall_error(
+                Errno::EOPNOTSUPP,
+                "ioctl",
+                "the storage backend cannot share data between files",
+            );
+        }
 
-    pub fn dup_syscall(&self, fd: i32, start_desc: Option<i32>) -> i32 {
-        //if a starting fd was passed, then use that as the starting point, but
-        // otherwise, use the designated minimum of STARTINGFD
-        let start_fd = match start_desc {
-            Some(start_desc) => start_desc,
-            None => STARTINGFD,
+        let srcsize = match *FS_METADATA.inodetable.get(&srcinode).unwrap() {
+            Inode::File(ref normalfile_inode_obj) => normalfile_inode_obj.size,
+            _ => 0,
         };
 
-        if start_fd == fd {
-            return start_fd;
-        } //if the file descriptors are equal, return the new one
+        // the destination takes on the size of the source, which is charged
+        // or given back like a truncate to that size. Growth is charged up
+        // front and shrinking only given back once the data is cloned, with
+        // the inode locked until then
+        let mut inodeobj = FS_METADATA.inodetable.get_mut(&destinode).unwrap();
+        let normalfile_inode_obj = match *inodeobj {
+            Inode::File(ref mut normalfile_inode_obj) => normalfile_inode_obj,
+            _ => unreachable!(),
+        };
+        let destsize = normalfile_inode_obj.size;
+        let owner = normalfile_inode_obj.uid;
+        if srcsize > destsize {
+            if srcsize as u64 > self.get_rlimit(RLIMIT_FSIZE).rlim_cur {
+                return self._file_too_large("ioctl");
+            }
+            let growth = srcsize - destsize;
+            if let Err(e) = self._reserve_bytes(owner, growth, growth, "ioctl") {
+                return e;
+            }
+        }
 
-        // get the filedesc_enum
-        // Attempt to get the file descriptor; handle error if it does not exist
-        let checkedfd = match self.get_filedescriptor(fd) {
-            Ok(fd) => fd,
-            Err(_) => return syscall_error(Errno::EBADF, "dup", "Invalid old file descriptor."),
+        if let Err(e) = Self::_clone_data(&*storage, srcinode, destinode, srcsize) {
+            // the destination keeps its data and size, so the growth is given back
+            if srcsize > destsize {
+                FS_METADATA.release_space(owner, (srcsize - destsize) as u64, 0);
+            }
+            return e;
+        }
+        if srcsize < destsize {
+            FS_METADATA.release_space(owner, (destsize - srcsize) as u64, 0);
+        }
+
+        normalfile_inode_obj.size = srcsize;
+        let time = interface::timestamp_nanos();
+        normalfile_inode_obj.mtime = time;
+        normalfile_inode_obj.ctime = time;
+        drop(inodeobj);
+        log_metadata(&FS_METADATA, destinode);
+        0
+    }
+
+    /// ### Description
+    ///
+    /// The `_clone_data()` is a helper function for `_clone_file()` that has
+    /// the storage backend give the regular file `destinode` the data of
+    /// `srcinode`. An open file object of the destination is only swapped for
+    /// one opened on the cloned data once that has worked, and is otherwise
+    /// left as it was.
+    ///
+    /// ### Returns
+    ///
+    /// Nothing on success, or `EIO` for the caller to return.
+    fn _clone_data(
+        storage: &dyn interface::StorageBackend,
+        srcinode: usize,
+        destinode: usize,
+        srcsize: usize,
+    ) -> Result<(), i32> {
+        let srcname = format!("{}{}", FILEDATAPREFIX, srcinode);
+        let destname = format!("{}{}", FILEDATAPREFIX, destinode);
+
+        // the backend shares what it has stored of the source, so changes only
+        // staged in its file object go there first
+        if let Some(srcobj) = FILEOBJECTTABLE.get(&srcinode) {
+            if srcobj.write_back().is_err() {
+                return Err(syscall_error(
+                    Errno::EIO,
+                    "ioctl",
+                    "the source could not be written out",
+                ));
+            }
+        }
+
+        // the old file object of the destination is dropped without being
+        // written back, as its data is replaced
+        let cloned = match FILEOBJECTTABLE.get_mut(&destinode) {
+            Some(mut destobj) => storage
+                .clone_data(&srcname, &destname, srcsize)
+                .and_then(|_| storage.open_data(&destname, srcsize))
+                .map(|newobj| *destobj = newobj),
+            None => storage.clone_data(&srcname, &destname, srcsize),
         };
-        let filedesc_enum = checkedfd.write();
-        let filedesc_enum = if let Some(f) = &*filedesc_enum {
-            f
+        if cloned.is_err() {
+            return Err(syscall_error(
+                Errno::EIO,
+                "ioctl",
+                "the data could not be cloned",
+            ));
+        }
+
+        // and the source may now share its storage, so it has to be copied
+        // before it changes
+        if let Some(mut srcobj) = FILEOBJECTTABLE.get_mut(&srcinode) {
+            srcobj.mark_shared();
+        }
+        Ok(())
+    }
+
+    /// ### Description
+    ///
+    /// The `_chmod_helper()` is a helper function used by both
+    /// `chmod_syscall()` and `fchmod_syscall()` to change mode bits that
+    /// consist of read, write, and execute file permission bits of a file
+    /// specified by an inode obtained from the corresponding caller
+    /// syscall.
+    ///
+    /// ### Arguments
+    ///
+    /// The `_chmod_helper()` accepts two arguments:
+    /// * `inodenum` - an inode of a file whose mode bits we are willing to
+    /// change obtained from the caller syscall.
+    /// * `mode` - the new file mode, which is a bit mask created by
+    /// bitwise-or'ing zero or more valid mode bits. Some of the examples of
+    /// such bits are `S_IRUSR` (read by owner), `S_IWUSR` (write by owner),
+    /// etc.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion, zero is returned.
+    /// In case of a failure, an error is returned, and `errno` is set depending
+    /// on the error, e.g. EACCES, ENOENT, etc.
+    ///
+    /// ### Errors
+    ///
+    /// Currently, only one error is supported:
+    /// * `EINVAL` - the value of the mode argument is invalid.
+    /// Other errors, like `EFAULT`, `ENOTDIR`, etc. are not supported.
+    ///
+    /// ### Panics
+    ///
+    /// There are no cases where this helper function panics.
+
+    pub fn _chmod_helper(inodenum: usize, mode: u32) -> i32 {
+        //S_IRWXA is a result of bitwise-or'ing read, write, and execute or search
+        //permissions for the file owner, group owners,
+        //and other users. It encompasses all the mode bits that can be changed
+        //via `chmod_syscall()` and is used as a bitmask to make sure that no
+        //other invalid bit change is being made.
+        if (mode & S_IRWXA) == mode {
+            //getting a mutable reference to an inode struct that corresponds to
+            //the file whose mode bits we want to change
+            let mut thisinode = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+            //log is used to store all the changes made to the filesystem. After
+            //the cage is closed, all the collected changes are serialized and
+            //the state of the underlying filsystem is persisted. This allows us
+            //to avoid serializing and persisting filesystem state after every
+            //`chmod_syscall()`.
+            let mut log = true;
+            //We obtain the mode bits that should remain intact by bitwise-and'ing
+            //the inode's mode bits with the set of bits that can be changed via
+            //`chmod_syscall`. The changes are applied by bitwise-or'ing
+            //the intact mode bits with the changed mode bits.
+            match *thisinode {
+                Inode::File(ref mut general_inode) => {
+                    general_inode.mode = (general_inode.mode & !S_IRWXA) | mode;
+                }
+                Inode::CharDev(ref mut dev_inode) => {
+                    dev_inode.mode = (dev_inode.mode & !S_IRWXA) | mode;
+                }
+                Inode::Socket(ref mut sock_inode) => {
+                    sock_inode.mode = (sock_inode.mode & !S_IRWXA) | mode;
+                    //Sockets only exist as long as the cages using them are running.
+                    //After these cages are closed, no changes to sockets' inodes
+                    //need to be persisted, thus using log is unnecessary.
+                    log = false;
+                }
+                Inode::Dir(ref mut dir_inode) => {
+                    dir_inode.mode = (dir_inode.mode & !S_IRWXA) | mode;
+                }
+                Inode::Symlink(ref mut symlink_inode) => {
+                    symlink_inode.mode = (symlink_inode.mode & !S_IRWXA) | mode;
+                }
+                Inode::Fifo(ref mut fifo_inode) => {
+                    fifo_inode.mode = (fifo_inode.mode & !S_IRWXA) | mode;
+                }
+            }
+            //changing the mode is a change of the inode's status
+            mark_changed(&mut thisinode, interface::timestamp_nanos());
+            //the mutable reference to the inode has to be dropped because
+            //`log_metadata` will need to acquire an immutable reference to
+            //the same inode
+            drop(thisinode);
+            //changes to an inode are saved into the log for all file types
+            //except for Sockets
+            if log {
+                log_metadata(&FS_METADATA, inodenum);
+            };
+            //return 0 on success
+            0
         } else {
-            return syscall_error(Errno::EBADF, "dup", "Invalid old file descriptor.");
-        };
+            return syscall_error(
+                Errno::EINVAL,
+                "chmod",
+                "The value of the mode argument is invalid",
+            );
+        }
+    }
+
+    /// ### Description
+    ///
+    /// The `chmod_syscall()` changes a file's mode bits that consist of read,
+    /// write, and execute file permission bits.
+    /// Changing `set-user-ID`, `set-group-ID`, and sticky bits is currently
+    /// not supported.
+    ///
+    /// ### Arguments
+    ///
+    /// The `chmod_syscall()` accepts two arguments:
+    /// * `path` - pathname of the file whose mode bits we are willing to
+    /// change (symbolic links are currently not supported). If the
+    /// pathname is relative, then it is interpreted relative to the
+    /// current working directory of the calling process.
+    /// * `mode` - the new file mode, which is a bit mask created by
+    /// bitwise-or'ing zero or more valid mode bits. Some of the examples
+    /// of such bits are `S_IRUSR` (read by owner), `S_IWUSR` (write by owner),
+    /// etc.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion, zero is returned.
+    /// In case of a failure, an error is returned, and `errno` is set depending
+    /// on the error, e.g. `EACCES`, `ENOENT`, etc.
+    ///
+    /// ### Errors
+    ///
+    /// Currently, only three errors are supposrted:
+    /// * `EINVAL` - the value of the mode argument is invalid
+    /// * `ENOENT` - a component of path does not name an existing file
+    /// * `EPERM` - the caller is neither the owner of the file nor the
+    ///   superuser
+    /// Other errors, like `EFAULT`, `ENOTDIR`, etc. are not supported.
+    ///
+    /// ### Panics
+    ///
+    /// There are no cases where this syscall panics.
+    ///
+    /// To learn more about the syscall, valid mode bits, and error values, see
+    /// [chmod(2)](https://man7.org/linux/man-pages/man2/chmod.2.html)
 
-        //checking whether the fd exists in the file table
-        return Self::_dup2_helper(&self, filedesc_enum, start_fd, false);
+    pub fn chmod_syscall(&self, path: &str, mode: u32) -> i32 {
+        //Convert the provided pathname into an absolute path without `.` or `..`
+        //components.
+        let truepath = normpath(convpath(path), self);
+        //Perfrom a walk down the file tree starting from the root directory to
+        //obtain an inode number of the file whose pathname was specified.
+        //`None` is returned if one of the following occurs while moving down
+        //the tree: accessing a child of a non-directory inode, accessing a
+        //child of a nonexistent parent directory, accessing a nonexistent child,
+        //accessing an unexpected component, like `.` or `..` directory reference.
+        //In this case, `The file does not exist` error is returned.
+        //Otherwise, a `Some()` option containing the inode number is returned.
+        if let Some(inodenum) = metawalk(truepath.as_path()) {
+            //Only the owner of a file (or the superuser) may change its mode
+            let ownercheck = self._owner_check("chmod", inodenum);
+            if ownercheck != 0 {
+                return ownercheck;
+            }
+            Self::_chmod_helper(inodenum, mode)
+        } else {
+            return syscall_error(
+                Errno::ENOENT,
+                "chmod",
+                "A component of path does not name an existing file",
+            );
+        }
     }
 
-    /// ## `dup2_syscall`
-    ///
     /// ### Description
-    /// This function implements the `dup2` system call, which duplicates a file
-    /// descriptor and assigns it to a new file descriptor number. If the
-    /// new file descriptor already exists, it is closed before the duplication
-    /// takes place.
-    /// * File Descriptor Reuse:  If the new file descriptor (`newfd`) is
-    ///   already open, the function will first close the existing file
-    ///   descriptor silently (without returning an error) before allocating a
-    ///   new file descriptor and updating the file descriptor table.
-    /// * If `oldfd` and `newfd` are the same, the function returns `newfd`
-    ///   without closing it. This is because in this scenario, the original and
-    ///   new file descriptors would already point to the same file description.
-    /// * the global `filedescriptortable` array, replacing the entry for the
-    ///   new file descriptor with a new entry for the duplicated file
-    ///   descriptor. It also increments the reference count of the file object
-    ///   associated with the original file descriptor.
     ///
-    /// ### Function Arguments
-    /// * `oldfd`: The original file descriptor to duplicate.
-    /// * `newfd`: The new file descriptor number to assign to the duplicated
-    ///   file descriptor.
+    /// The `fchmodat_syscall()` is like `chmod_syscall()`, except that a
+    /// relative `path` is interpreted relative to the directory referred to by
+    /// `dirfd`, see `_at_path_helper()`.
+    ///
+    /// ### Arguments
+    ///
+    /// The `fchmodat_syscall()` accepts four arguments:
+    /// * `dirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `path`, `mode` - as for `chmod_syscall()`.
+    /// * `flags` - either 0 or `AT_SYMLINK_NOFOLLOW`, which asks for the mode
+    ///   of a symbolic link itself to be changed. The mode of a symbolic link
+    ///   can't be changed, so this always fails.
     ///
     /// ### Returns
-    /// * The new file descriptor on success.
+    ///
+    /// Upon successful completion, zero is returned.
     ///
     /// ### Errors
-    /// * `EBADF(9)`: If the original file descriptor (`oldfd`) is invalid or
-    ///   the new file descriptor (`newfd`) number is out of range.
-    ///  ###Panics
-    /// * There are no panics for this syscall
-    ///[dup2(2)](https://linux.die.net/man/2/dup2)
-
-    pub fn dup2_syscall(&self, oldfd: i32, newfd: i32) -> i32 {
-        //checking if the new fd is out of range
-        if newfd >= MAXFD || newfd < 0 {
+    ///
+    /// * `EINVAL` - `flags` contains an unknown flag.
+    /// * `EOPNOTSUPP` - `flags` is `AT_SYMLINK_NOFOLLOW`.
+    /// * `EBADF`, `ENOTDIR` - see `_at_path_helper()`.
+    /// * Any error of `chmod_syscall()`.
+    ///
+    /// To learn more about the syscall and possible error values, see
+    /// [fchmodat(2)](https://man7.org/linux/man-pages/man2/fchmodat.2.html)
+    pub fn fchmodat_syscall(&self, dirfd: i32, path: &str, mode: u32, flags: i32) -> i32 {
+        if flags & !AT_SYMLINK_NOFOLLOW != 0 {
+            return syscall_error(Errno::EINVAL, "fchmodat", "invalid flags");
+        }
+        if flags & AT_SYMLINK_NOFOLLOW != 0 {
             return syscall_error(
-                Errno::EBADF,
-                "dup2",
-                "provided file descriptor is out of range",
+                Errno::EOPNOTSUPP,
+                "fchmodat",
+                "the mode of a symbolic link can't be changed",
             );
         }
-
-        if newfd == oldfd {
-            return newfd;
-        } //if the file descriptors are equal, return the new one
-
-        // get the filedesc_enum
-        let checkedfd = match self.get_filedescriptor(oldfd) {
-            Ok(fd) => fd,
-            Err(_) => return syscall_error(Errno::EBADF, "dup2", "Invalid old file descriptor."),
-        };
-        let filedesc_enum = checkedfd.write();
-        let filedesc_enum = if let Some(f) = &*filedesc_enum {
-            f
-        } else {
-            return syscall_error(Errno::EBADF, "dup2", "Invalid old file descriptor.");
-        };
-
-        //if the old fd exists, execute the helper, else return error
-        return Self::_dup2_helper(&self, filedesc_enum, newfd, true);
+        match self._at_path_helper("fchmodat", dirfd, path) {
+            Ok(truepath) => self.chmod_syscall(truepath.to_str().unwrap(), mode),
+            Err(e) => e,
+        }
     }
 
-    /// ## `_dup2_helper`
-    ///
     /// ### Description
-    /// This helper function performs the actual file descriptor duplication
-    /// process for both `dup` and `dup2` system calls. It handles the
-    /// allocation of a new file descriptor, updates the file descriptor table,
-    /// and increments the reference count of the associated file object.
-    /// * Duplication from `dup2_syscall`: If `fromdup2` is true, the function
-    ///   first closes the existing file descriptor at `newfd` (if any) before
-    ///   allocating a new file descriptor and updating the file descriptor
-    ///   table.
-    /// * Duplication from `dup_syscall`: If `fromdup2` is false, the function
-    ///   allocates a new file descriptor, finds the first available file
-    ///   descriptor number starting from `newfd`, and updates the file
-    ///   descriptor table.
-    /// * Reference Counting: The function increments the reference count of the
-    ///   file object associated with the original file descriptor. This ensures
-    ///   that the file object is not deleted until all its associated file
-    ///   descriptors are closed.
-    /// * Socket Handling: For domain sockets, the function increments the
-    ///   reference count of both the send and receive pipes associated with the
-    ///   socket.
-    /// * Stream Handling: Streams are not currently supported for duplication
-    /// * Unhandled File Types: If the file descriptor is associated with a file
-    ///   type that is not handled by the function (i.e., not a File, Pipe,
-    ///   Socket, or Stream), the function returns an error (`EACCES`).
-    /// * The function does not handle streams.
-    /// * Socket Handling: If the file descriptor is associated with a socket,
-    ///   the function handles domain sockets differently by incrementing the
-    ///   reference count of both the send and receive pipes.
-    /// ### Function Arguments
-    /// * `self`:  A reference to the `FsCalls` struct, which contains the file
-    ///   descriptor table and other system-related data.
-    /// * `filedesc_enum`: A reference to the `FileDescriptor` object
-    ///   representing the file descriptor to be duplicated.
-    /// * `newfd`: The new file descriptor number to assign to the duplicated
-    ///   file descriptor.
-    /// * `fromdup2`: A boolean flag indicating whether the call is from
-    ///   `dup2_syscall` (true) or `dup_syscall` (false).
+    ///
+    /// The `fchmod_syscall()` is equivalent to `chmod_syscall()` in that
+    /// it is used to change a file's mode bits that consist of read,
+    /// write, and execute file permission bits except that the file
+    /// is specified by the file descriptor. Changing `set-user-ID`,
+    /// `set-group-ID`, and sticky bits is currently not supported.
+    ///
+    /// ### Arguments
+    ///
+    /// The `fchmod_syscall()` accepts two arguments:
+    /// * `fd` - an open file descriptor.
+    /// * `mode` - the new file mode, which is a bit mask created by
+    /// bitwise-or'ing zero or more valid mode bits. Some of the examples
+    /// of such bits are `S_IRUSR` (read by owner), `S_IWUSR`
+    /// (write by owner), etc.
     ///
     /// ### Returns
-    /// * The new file descriptor on success.
+    ///
+    /// Upon successful completion, zero is returned.
+    /// In case of a failure, an error is returned, and `errno` is set
+    /// depending on the error, e.g. `EACCES`, `ENOENT`, etc.
     ///
     /// ### Errors
-    /// * `ENFILE(23)`: If there are no available file descriptors.
-    /// * `EACCES(13)`: If the file descriptor cannot be duplicated.
-    /// ###Panics
-    /// * If the file descriptor is associated with a socket, and the inode does
-    ///   not match the file descriptor.
-
-    pub fn _dup2_helper(&self, filedesc_enum: &FileDescriptor, newfd: i32, fromdup2: bool) -> i32 {
-        let (dupfd, mut dupfdguard) = if fromdup2 {
-            let mut fdguard = self.filedescriptortable[newfd as usize].write();
-            let closebool = fdguard.is_some();
-            drop(fdguard);
-            // close the fd in the way of the new fd. mirror the implementation of linux,
-            // ignore the potential error of the close here
-            if closebool {
-                let _close_result = Self::_close_helper_inner(&self, newfd);
-            }
-
-            // re-grab clean fd
-            fdguard = self.filedescriptortable[newfd as usize].write();
-            (newfd, fdguard)
-        } else {
-            let (newdupfd, guardopt) = self.get_next_fd(Some(newfd));
-            if newdupfd < 0 {
-                // The function allocates a new file descriptor and updates the file descriptor
-                // table, handling the potential for file descriptor table
-                // overflow (resulting in an `ENFILE` error).
-                return syscall_error(
-                    Errno::ENFILE,
-                    "dup2_helper",
-                    "no available file descriptor number could be found",
-                );
-            }
-            (newdupfd, guardopt.unwrap())
-        };
-
-        let dupfdoption = &mut *dupfdguard;
+    ///
+    /// * `EBADF` - the file descriptor `fd` is not valid.
+    /// * `EINVAL` - the value of the `mode` argument is invalid or
+    /// mode bits cannot be changed on this file type
+    /// * `EPERM` - the caller is neither the owner of the file nor the
+    ///   superuser
+    /// Other errors, like `EFAULT`, `ENOTDIR`, etc. are not supported.
+    ///
+    /// ### Panics
+    ///
+    /// A panic occurs when a provided file descriptor is out of bounds
+    ///
+    /// To learn more about the syscall, valid mode bits, and error values, see
+    /// [fchmod(2)](https://linux.die.net/man/2/fchmod)
 
-        match filedesc_enum {
-            File(normalfile_filedesc_obj) => {
-                let inodenum = normalfile_filedesc_obj.inode;
-                let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
-                //incrementing the ref count so that when close is executed on the dup'd file
-                //the original file does not get a negative ref count
-                match *inodeobj {
-                    // increments the reference count of the file object associated with the
-                    // original file descriptor to ensure that the file object
-                    // is not deleted until all its associated file descriptors are closed.
-                    Inode::File(ref mut normalfile_inode_obj) => {
-                        normalfile_inode_obj.refcount += 1;
-                    }
-                    Inode::Dir(ref mut dir_inode_obj) => {
-                        dir_inode_obj.refcount += 1;
-                    }
-                    Inode::CharDev(ref mut chardev_inode_obj) => {
-                        chardev_inode_obj.refcount += 1;
+    pub fn fchmod_syscall(&self, fd: i32, mode: u32) -> i32 {
+        //BUG
+        //if the provided file descriptor is out of bounds, 'get_filedescriptor'
+        //returns `Err()`, unwrapping on which  produces a `panic!`
+        //otherwise, file descriptor table entry is stored in `checkedfd`
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let unlocked_fd = checkedfd.read();
+        //if a table descriptor entry is non-empty, a valid request is performed
+        if let Some(filedesc_enum) = &*unlocked_fd {
+            //Regular file type is the only type that supports `fchmod_syscall()`
+            match filedesc_enum {
+                File(normalfile_filedesc_obj) => {
+                    let inodenum = normalfile_filedesc_obj.inode;
+                    //Only the owner of a file (or the superuser) may change its mode
+                    let ownercheck = self._owner_check("fchmod", inodenum);
+                    if ownercheck != 0 {
+                        return ownercheck;
                     }
-                    Inode::Socket(_) => panic!("dup: fd and inode do not match."),
+                    Self::_chmod_helper(inodenum, mode)
                 }
-            }
-            Pipe(pipe_filedesc_obj) => {
-                pipe_filedesc_obj.pipe.incr_ref(pipe_filedesc_obj.flags);
-            }
-            Socket(ref socket_filedesc_obj) => {
-                //we handle the closing of sockets on drop
-                // checking whether this is a domain socket
-
-                let sock_tmp = socket_filedesc_obj.handle.clone();
-                let sockhandle = sock_tmp.write();
-                let socket_type = sockhandle.domain;
-                if socket_type == AF_UNIX {
-                    if let Some(sockinfo) = &sockhandle.unix_info {
-                        if let Some(sendpipe) = sockinfo.sendpipe.as_ref() {
-                            sendpipe.incr_ref(O_WRONLY);
-                        }
-                        if let Some(receivepipe) = sockinfo.receivepipe.as_ref() {
-                            receivepipe.incr_ref(O_RDONLY);
-                        }
-                    }
+                Socket(_) => {
+                    return syscall_error(
+                        Errno::EINVAL,
+                        "fchmod",
+                        "Mode bits cannot be changed on this file type",
+                    );
+                }
+                Stream(_) => {
+                    return syscall_error(
+                        Errno::EINVAL,
+                        "fchmod",
+                        "Mode bits cannot be changed on this file type",
+                    );
+                }
+                Pipe(_) => {
+                    return syscall_error(
+                        Errno::EINVAL,
+                        "fchmod",
+                        "Mode bits cannot be changed on this file type",
+                    );
+                }
+                Epoll(_) | EventFd(_) | TimerFd(_) | SignalFd(_) => {
+                    return syscall_error(
+                        Errno::EINVAL,
+                        "fchmod",
+                        "Mode bits cannot be changed on this file type",
+                    );
                 }
             }
-            Stream(_normalfile_filedesc_obj) => {
-                // no stream refs
+        } else {
+            return syscall_error(Errno::EBADF, "ioctl", "Invalid file descriptor");
+        }
+    }
+
+    /// ### Description
+    ///
+    /// The `_chown_helper()` is a helper function used by `chown_syscall()`,
+    /// `fchown_syscall()` and `lchown_syscall()` to change the owning user
+    /// and group of the file specified by an inode obtained from the
+    /// corresponding caller syscall.
+    ///
+    /// Only the superuser may change the owner of a file. The owner of a file
+    /// may change its group to their own effective group. When an
+    /// unprivileged caller changes the ownership of a non-directory, its
+    /// `set-user-ID` and `set-group-ID` bits are cleared, so that ownership
+    /// changes can't be used to gain privileges.
+    ///
+    /// ### Arguments
+    ///
+    /// The `_chown_helper()` accepts four arguments:
+    /// * `syscallname` - the name of the calling syscall, used in errors.
+    /// * `inodenum` - an inode of the file whose ownership is changed.
+    /// * `owner` - the new owning user, or `NOCHANGE_ID` to leave it as is.
+    /// * `group` - the new owning group, or `NOCHANGE_ID` to leave it as is.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion, zero is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * `EPERM` - the caller is not the superuser and either does not own the
+    ///   file, tried to change the owner, or tried to change the group to one
+    ///   other than its effective group.
+    ///
+    /// ### Panics
+    ///
+    /// A panic occurs if the inode does not exist in the inode table.
+    pub fn _chown_helper(&self, syscallname: &str, inodenum: usize, owner: u32, group: u32) -> i32 {
+        let (euid, egid) = self.get_effective_ids();
+        let mut thisinode = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+        let (_, curowner, curgroup) = inode_ownership(&thisinode);
+        let newowner = if owner == NOCHANGE_ID {
+            curowner
+        } else {
+            owner
+        };
+        let newgroup = if group == NOCHANGE_ID {
+            curgroup
+        } else {
+            group
+        };
+
+        if euid != ROOT_UID {
+            if euid != curowner {
+                return syscall_error(
+                    Errno::EPERM,
+                    syscallname,
+                    "the caller does not own the file",
+                );
             }
-            _ => {
-                return syscall_error(Errno::EACCES, "dup or dup2", "can't dup the provided file");
+            if newowner != curowner {
+                return syscall_error(
+                    Errno::EPERM,
+                    syscallname,
+                    "only the superuser may change the owner of a file",
+                );
+            }
+            if newgroup != curgroup && newgroup != egid {
+                return syscall_error(
+                    Errno::EPERM,
+                    syscallname,
+                    "the caller is not a member of the requested group",
+                );
             }
         }
 
-        let mut dupd_fd_enum = filedesc_enum.clone(); //clones the arc for sockethandle
-
-        // get and clone fd, wrap and insert into table.
-        match dupd_fd_enum {
-            // we don't want to pass on the CLOEXEC flag
-            File(ref mut normalfile_filedesc_obj) => {
-                normalfile_filedesc_obj.flags = normalfile_filedesc_obj.flags & !O_CLOEXEC;
+        //set-user-ID and set-group-ID bits don't survive an unprivileged ownership
+        //change of a non-directory
+        let clearbits = if euid != ROOT_UID {
+            S_ISUID | S_ISGID
+        } else {
+            0
+        };
+        //the space the inode takes up is charged to its new owner from now on
+        let (_, bytes) = inode_charge(&thisinode);
+        FS_METADATA.transfer_space(curowner, newowner, bytes);
+        let time = interface::timestamp_nanos();
+        //Sockets only exist as long as the cages using them are running, so
+        //changes to their inodes are not logged
+        let mut log = true;
+        match *thisinode {
+            Inode::File(ref mut f) => {
+                f.uid = newowner;
+                f.gid = newgroup;
+                f.mode &= !clearbits;
+                f.ctime = time;
             }
-            Pipe(ref mut pipe_filedesc_obj) => {
-                pipe_filedesc_obj.flags = pipe_filedesc_obj.flags & !O_CLOEXEC;
+            Inode::CharDev(ref mut f) => {
+                f.uid = newowner;
+                f.gid = newgroup;
+                f.mode &= !clearbits;
+                f.ctime = time;
             }
-            Socket(ref mut socket_filedesc_obj) => {
-                // can do this for domainsockets and sockets
-                socket_filedesc_obj.flags = socket_filedesc_obj.flags & !O_CLOEXEC;
+            Inode::Socket(ref mut f) => {
+                f.uid = newowner;
+                f.gid = newgroup;
+                f.ctime = time;
+                log = false;
             }
-            Stream(ref mut stream_filedesc_obj) => {
-                stream_filedesc_obj.flags = stream_filedesc_obj.flags & !O_CLOEXEC;
+            Inode::Dir(ref mut f) => {
+                f.uid = newowner;
+                f.gid = newgroup;
+                f.ctime = time;
             }
-            _ => {
-                return syscall_error(Errno::EACCES, "dup or dup2", "can't dup the provided file");
+            Inode::Symlink(ref mut f) => {
+                f.uid = newowner;
+                f.gid = newgroup;
+                f.ctime = time;
+            }
+            Inode::Fifo(ref mut f) => {
+                f.uid = newowner;
+                f.gid = newgroup;
+                f.mode &= !clearbits;
+                f.ctime = time;
             }
         }
+        //the mutable reference to the inode has to be dropped because
+        //`log_metadata` will need to acquire an immutable reference to
+        //the same inode
+        drop(thisinode);
+        if log {
+            log_metadata(&FS_METADATA, inodenum);
+        }
+        0
+    }
 
-        let _insertval = dupfdoption.insert(dupd_fd_enum);
-
-        return dupfd;
+    /// ### Description
+    ///
+    /// The `chown_syscall()` changes the owning user and group of the file
+    /// named by `path`. If `path` names a symbolic link, the file it refers
+    /// to is changed.
+    ///
+    /// ### Arguments
+    ///
+    /// The `chown_syscall()` accepts three arguments:
+    /// * `path` - pathname of the file whose ownership is changed.
+    /// * `owner` - the new owning user, or -1 (`NOCHANGE_ID`) to leave it
+    ///   unchanged.
+    /// * `group` - the new owning group, or -1 (`NOCHANGE_ID`) to leave it
+    ///   unchanged.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion, zero is returned.
+    /// In case of a failure, an error is returned, and `errno` is set depending
+    /// on the error, e.g. `EPERM`, `ENOENT`, etc.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOENT` - a component of path does not name an existing file
+    /// * `EACCES` - search permission is denied on a component of the path
+    ///   prefix
+    /// * `EPERM` - see `_chown_helper()`
+    ///
+    /// ### Panics
+    ///
+    /// There are no cases where this syscall panics.
+    ///
+    /// To learn more about the syscall and error values, see
+    /// [chown(2)](https://man7.org/linux/man-pages/man2/chown.2.html)
+    pub fn chown_syscall(&self, path: &str, owner: u32, group: u32) -> i32 {
+        self._chown_path_helper("chown", path, owner, group, true)
     }
 
-    /// ## ------------------CLOSE SYSCALL------------------
     /// ### Description
     ///
-    /// The `close_syscall()` is used to close a file descriptor, so that it no
-    /// longer refers to any file and may be reused. This is an essential
-    /// operation for managing resources, as file descriptors are a limited
-    /// resource in any operating system.
+    /// The `lchown_syscall()` is like `chown_syscall()`, but does not follow
+    /// a symbolic link named by `path`, changing the ownership of the link
+    /// itself instead.
     ///
-    /// ### Function Arguments
+    /// ### Arguments
     ///
-    /// The `close_syscall()` receives one argument:
-    /// * `fd` - This argument refers to the file descriptor to be closed.
+    /// The `lchown_syscall()` accepts three arguments:
+    /// * `path` - pathname of the file whose ownership is changed.
+    /// * `owner` - the new owning user, or -1 (`NOCHANGE_ID`) to leave it
+    ///   unchanged.
+    /// * `group` - the new owning group, or -1 (`NOCHANGE_ID`) to leave it
+    ///   unchanged.
     ///
     /// ### Returns
     ///
-    /// Upon successful completion of this call, it returns 0. Otherwise, it
-    /// returns error number set with the appropriate error code.
+    /// Upon successful completion, zero is returned.
     ///
     /// ### Errors
     ///
-    /// * The syscall returns errors which occur in its helper functions.
+    /// The same as for `chown_syscall()`.
     ///
     /// ### Panics
     ///
-    /// * This syscall indirectly panics when the helper function panics.
+    /// There are no cases where this syscall panics.
     ///
-    /// For more detailed description of all the commands and return values, see
-    /// [close(2)](https://man7.org/linux/man-pages/man2/close.2.html)
-    pub fn close_syscall(&self, fd: i32) -> i32 {
-        // Call the helper function which validates the result and deletes
-        // reference of the file descriptor.
-        return Self::_close_helper(self, fd);
+    /// To learn more about the syscall and error values, see
+    /// [lchown(2)](https://man7.org/linux/man-pages/man2/lchown.2.html)
+    pub fn lchown_syscall(&self, path: &str, owner: u32, group: u32) -> i32 {
+        self._chown_path_helper("lchown", path, owner, group, false)
+    }
+
+    // shared implementation of chown and lchown, which only differ in whether a
+    // symbolic link named by the final component of the path is followed
+    fn _chown_path_helper(
+        &self,
+        syscallname: &str,
+        path: &str,
+        owner: u32,
+        group: u32,
+        follow_final: bool,
+    ) -> i32 {
+        let truepath = normpath(convpath(path), self);
+        let searchcheck = self._search_check(syscallname, &truepath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
+        match metawalkandparent_resolve(truepath.as_path(), follow_final) {
+            Ok((Some(inodenum), ..)) => self._chown_helper(syscallname, inodenum, owner, group),
+            Ok(_) => syscall_error(
+                Errno::ENOENT,
+                syscallname,
+                "A component of path does not name an existing file",
+            ),
+            Err(_) => syscall_error(
+                Errno::ELOOP,
+                syscallname,
+                "too many symbolic links were encountered in resolving path",
+            ),
+        }
     }
 
-    /// ## ----------------- CLOSE HELPER INNER ------------------
     /// ### Description
     ///
-    /// The `_close_helper_inner` function manages the cleanup and closing of
-    /// various types of file descriptors (files, directories, pipes, sockets)
-    /// by decrementing reference counts and removing inodes if necessary.
-    /// ### Function Arguments
+    /// The `fchown_syscall()` is equivalent to `chown_syscall()` except that
+    /// the file is specified by the open file descriptor `fd`.
     ///
-    /// The function receives one argument:
-    /// * `fd` - This argument refers to the file descriptor to be closed.
+    /// ### Arguments
+    ///
+    /// The `fchown_syscall()` accepts three arguments:
+    /// * `fd` - an open file descriptor.
+    /// * `owner` - the new owning user, or -1 (`NOCHANGE_ID`) to leave it
+    ///   unchanged.
+    /// * `group` - the new owning group, or -1 (`NOCHANGE_ID`) to leave it
+    ///   unchanged.
     ///
     /// ### Returns
     ///
-    /// Upon successful completion of this call, it returns 0. Otherwise, it
-    /// returns errors set with the appropriate error code.
+    /// Upon successful completion, zero is returned.
     ///
     /// ### Errors
     ///
-    /// * EBADF - The given file descriptor is invalid
-    /// * ENOEXEC - The Non-regular type file (dir/chardev) in file object table
+    /// * `EBADF` - the file descriptor `fd` is not valid.
+    /// * `EINVAL` - ownership cannot be changed on this file type
+    /// * `EPERM` - see `_chown_helper()`
     ///
     /// ### Panics
     ///
-    /// * If the provided file descriptor is out of bounds, causing unwrap() to
-    ///   panic
-    /// * When the file type filedescriptor contains a Socket as an inode.
+    /// There are no cases where this syscall panics.
     ///
-    /// For more detailed description of all the commands and return values, see
-    /// [close(2)](https://man7.org/linux/man-pages/man2/close.2.html)
-    pub fn _close_helper_inner(&self, fd: i32) -> i32 {
-        //BUG
-        //if the provided file descriptor is out of bounds, get_filedescriptor returns
-        // Err(), unwrapping on which  produces a 'panic!'
-        //otherwise, file descriptor table entry is stored in 'checkedfd'
-        let checkedfd = self.get_filedescriptor(fd).unwrap();
-        let mut unlocked_fd = checkedfd.write();
-        if let Some(filedesc_enum) = &mut *unlocked_fd {
-            // We decide, how to proceed depending on the fd type.
-            // First we check in the file descriptor to handle stream / epoll (no-op),
-            // sockets (clean the socket), and pipes (clean the pipe), and if it is a
-            // normal file descriptor we decrement the reference count to reflect
-            // one less reference to the file.
-            match filedesc_enum {
-                //if we are a socket, we dont change disk metadata
-                Stream(_) => {} // Streams don't require any additional cleanup
-                Epoll(_) => {}  // TODO: Epoll closing not implemented yet
-                Socket(ref mut socket_filedesc_obj) => {
-                    // Retrieve the socket file descriptor object and get the write
-                    // lock on the socket handle.
-                    let sock_tmp = socket_filedesc_obj.handle.clone();
-                    let mut sockhandle = sock_tmp.write();
-
-                    // we need to do the following if UDS (Unix Domain Socket)
-                    // AF_UNIX represents the Unix domain sockets.
-                    let socket_type = sockhandle.domain;
-                    if socket_type == AF_UNIX {
-                        if let Some(ref mut ui) = sockhandle.unix_info {
-                            let inodenum = ui.inode;
-                            // Decrement the reference count for the send pipe if it exists. If the
-                            // reference count drops to zero, remove the send pipe.
-                            if let Some(sendpipe) = ui.sendpipe.as_ref() {
-                                sendpipe.decr_ref(O_WRONLY);
-                                //last reference, lets remove it
-                                if sendpipe.is_pipe_closed() {
-                                    ui.sendpipe = None;
-                                }
-                            }
-                            // Decrement the reference count for the receive pipe if it exists. If
-                            // the reference count drops to zero, remove
-                            // the receive pipe.
-                            if let Some(receivepipe) = ui.receivepipe.as_ref() {
-                                receivepipe.decr_ref(O_RDONLY);
-                                //last reference, lets remove it
-                                if receivepipe.is_pipe_closed() {
-                                    ui.receivepipe = None;
-                                }
-                            }
-                            // Retrieve the inode object for the socket and decrement its reference
-                            // count. If both the reference count and
-                            // link count are zero, the socket is no longer needed.
-                            let mut inodeobj = FS_METADATA.inodetable.get_mut(&ui.inode).unwrap();
-                            if let Inode::Socket(ref mut sock) = *inodeobj {
-                                sock.refcount -= 1;
-                                if sock.refcount == 0 && sock.linkcount == 0 {
-                                    // Remove the socket from the inode table and the domain
-                                    // socket paths if it is no longer needed.
-                                    drop(inodeobj);
-                                    // Get the entire path of the socket which is used for removing
-                                    // it from the metadata file.
-                                    let path = normpath(
-                                        convpath(sockhandle.localaddr.unwrap().path()),
-                                        self,
-                                    );
-                                    // Remove the reference of the inode from the inodetable
-                                    FS_METADATA.inodetable.remove(&inodenum);
-                                    // Remove any domain socket paths associated with the socket
-                                    NET_METADATA.domsock_paths.remove(&path);
-                                }
-                            }
-                        }
-                    }
-                }
-                Pipe(ref pipe_filedesc_obj) => {
-                    // Decrease the pipe objects internal ref count for the corresponding end
-                    // depending on what flags are set (O_RDONLY or O_WRONLY).
-                    pipe_filedesc_obj.pipe.decr_ref(pipe_filedesc_obj.flags);
-                }
-                File(ref normalfile_filedesc_obj) => {
-                    // Retrieve the inode object for the file.
-                    let inodenum = normalfile_filedesc_obj.inode;
-                    let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+    /// To learn more about the syscall and error values, see
+    /// [fchown(2)](https://man7.org/linux/man-pages/man2/fchown.2.html)
+    pub fn fchown_syscall(&self, fd: i32, owner: u32, group: u32) -> i32 {
+        let checkedfd = match self.get_filedescriptor(fd) {
+            Ok(checkedfd) => checkedfd,
+            Err(_) => return syscall_error(Errno::EBADF, "fchown", "Invalid file descriptor"),
+        };
+        let unlocked_fd = checkedfd.read();
+        match &*unlocked_fd {
+            //Only descriptors backed by an inode have an owner that can be changed
+            Some(File(normalfile_filedesc_obj)) => {
+                self._chown_helper("fchown", normalfile_filedesc_obj.inode, owner, group)
+            }
+            Some(_) => syscall_error(
+                Errno::EINVAL,
+                "fchown",
+                "Ownership cannot be changed on this file type",
+            ),
+            None => syscall_error(Errno::EBADF, "fchown", "Invalid file descriptor"),
+        }
+    }
 
-                    match *inodeobj {
-                        Inode::File(ref mut normalfile_inode_obj) => {
-                            normalfile_inode_obj.refcount -= 1;
-
-                            // Check if it's not a regular file, then we have nothing to close
-                            // Inode::File is a regular file by default
-                            // Reference count represents the number of active file descriptors
-                            // pointing to the file.
-                            if normalfile_inode_obj.refcount == 0 {
-                                // FileObjectTable stores the entries of the currently opened files
-                                // in the system, so if there are no
-                                // active file descriptors pointing to the
-                                // file, we delete them from the table.
-                                FILEOBJECTTABLE
-                                    .remove(&inodenum)
-                                    .unwrap()
-                                    .1
-                                    .close()
-                                    .unwrap();
-                                // Link count as 0 represents that there are no hard links present
-                                // for the file, so we need to remove it from the filesystem.
-                                if normalfile_inode_obj.linkcount == 0 {
-                                    drop(inodeobj);
-                                    // removing the file from the entire filesystem (interface,
-                                    // metadata, and object table)
-                                    FS_METADATA.inodetable.remove(&inodenum);
-                                    // FILEDATAPREFIX represents the common prefix of the name
-                                    // of the file which combined with the inode number represents
-                                    // a unique entity. It stores the data of the inode object.
-                                    let sysfilename = format!("{}{}", FILEDATAPREFIX, inodenum);
-                                    interface::removefile(sysfilename).unwrap();
-                                    log_metadata(&FS_METADATA, inodenum);
-                                } else {
-                                    drop(inodeobj);
-                                }
-                            }
-                        }
-                        Inode::Dir(ref mut dir_inode_obj) => {
-                            dir_inode_obj.refcount -= 1;
-
-                            // File object table only contains references for the regular
-                            // type files, and since "Directory" is not a regular file type,
-                            // it should not exist in the table. Return an error if the
-                            // directory exists in the file object table.
-                            match FILEOBJECTTABLE.get(&inodenum) {
-                                Some(_) => {
-                                    return syscall_error(
-                                        Errno::ENOEXEC,
-                                        "close or dup",
-                                        "Non-regular file (dir) in file object table",
-                                    );
-                                }
-                                // Continue if the object table doesn't contain the inode.
-                                None => {}
-                            }
-                            // When the link count is 2 and the reference count is 0, it means
-                            // the directory is empty and no processes are using it. This allows
-                            // the directory to be safely removed from the file system.
-                            if dir_inode_obj.linkcount == 2 && dir_inode_obj.refcount == 0 {
-                                //The reference to the inode has to be dropped to avoid
-                                //deadlocking because the remove() method will need to
-                                //acquire a reference to the same inode from the
-                                //filesystem's inodetable.
-                                //The inodetable represents a Rust DashMap that deadlocks
-                                //when trying to get a reference to its entry while holding any
-                                // sort of reference into it.
-                                drop(inodeobj);
-                                FS_METADATA.inodetable.remove(&inodenum);
-                                log_metadata(&FS_METADATA, inodenum);
-                            }
-                        }
-                        Inode::CharDev(ref mut char_inode_obj) => {
-                            char_inode_obj.refcount -= 1;
-                            // Since "CharDev" is not a regular file type, it should not
-                            // exist in the file object table. Return an error if the
-                            // chardev inode exists in the file object table.
-                            match FILEOBJECTTABLE.get(&inodenum) {
-                                Some(_) => {
-                                    return syscall_error(
-                                        Errno::ENOEXEC,
-                                        "close or dup",
-                                        "Non-regular file (chardev) in file object table",
-                                    );
-                                }
-                                // Continue if the object table doesn't contain the inode.
-                                None => {}
-                            }
-                            if char_inode_obj.linkcount == 0 && char_inode_obj.refcount == 0 {
-                                //removing the file from the metadata
-                                drop(inodeobj);
-                                FS_METADATA.inodetable.remove(&inodenum);
-                            } else {
-                                drop(inodeobj);
-                            }
-                            log_metadata(&FS_METADATA, inodenum);
-                        }
-                        // A Sanity check is added to make sure that there is no such case when the
-                        // fd type is "File" and the inode type is "Socket". This state is ideally
-                        // not possible, so we panic in such cases.
-                        Inode::Socket(_) => {
-                            panic!("close(): Socket inode found on a filedesc fd.")
-                        }
-                    }
-                }
+    /// ### Description
+    ///
+    /// The `_at_path_helper()` is a helper function for the syscalls that
+    /// take a directory file descriptor alongside a path, such as
+    /// `utimensat_syscall()`. It returns the absolute path named by `path`:
+    /// an absolute `path` is used as is, a relative one is interpreted
+    /// relative to the current working directory if `dirfd` is `AT_FDCWD`,
+    /// and relative to the directory referred to by `dirfd` otherwise.
+    /// In the last case the path is anchored at the inode of the directory,
+    /// see `anchorpath()`, so the lookup starts from the directory itself
+    /// however it is renamed meanwhile, and even after it has been removed.
+    ///
+    /// ### Arguments
+    ///
+    /// The `_at_path_helper()` accepts three arguments:
+    /// * `syscallname` - the name of the calling syscall, used in errors.
+    /// * `dirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `path` - the path to resolve.
+    ///
+    /// ### Returns
+    ///
+    /// The normalized absolute or anchored path, or the error below.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOENT` - `path` is an empty string
+    /// * `EBADF` - `path` is relative and `dirfd` is neither `AT_FDCWD` nor a
+    ///   valid file descriptor
+    /// * `ENOTDIR` - `path` is relative and `dirfd` does not refer to a
+    ///   directory
+    fn _at_path_helper(
+        &self,
+        syscallname: &str,
+        dirfd: i32,
+        path: &str,
+    ) -> Result<interface::RustPathBuf, i32> {
+        if path.is_empty() {
+            return Err(syscall_error(
+                Errno::ENOENT,
+                syscallname,
+                "given path was null",
+            ));
+        }
+        let relpath = convpath(path);
+        if relpath.is_absolute() || dirfd == AT_FDCWD {
+            return Ok(normpath(relpath, self));
+        }
+
+        let checkedfd = match self.get_filedescriptor(dirfd) {
+            Ok(checkedfd) => checkedfd,
+            Err(_) => {
+                return Err(syscall_error(
+                    Errno::EBADF,
+                    syscallname,
+                    "invalid file descriptor",
+                ))
+            }
+        };
+        let unlocked_fd = checkedfd.read();
+        let dirinodenum = match &*unlocked_fd {
+            None => {
+                return Err(syscall_error(
+                    Errno::EBADF,
+                    syscallname,
+                    "invalid file descriptor",
+                ))
+            }
+            Some(File(normalfile_filedesc_obj)) => Some(normalfile_filedesc_obj.inode),
+            Some(_) => None,
+        };
+        drop(unlocked_fd);
+        let dirinodenum = match dirinodenum.filter(|inodenum| {
+            matches!(
+                FS_METADATA.inodetable.get(inodenum).as_deref(),
+                Some(Inode::Dir(_))
+            )
+        }) {
+            Some(dirinodenum) => dirinodenum,
+            None => {
+                return Err(syscall_error(
+                    Errno::ENOTDIR,
+                    syscallname,
+                    "the file descriptor does not refer to a directory",
+                ))
+            }
+        };
+        let truepath = normpath_at(dirinodenum, relpath);
+        // a path leading back to the directory itself is named by where the
+        // directory is, so that it is handled like any other path to it
+        if path_anchor(&truepath).is_some() && truepath.components().count() == 2 {
+            return Ok(unanchoredpath(&truepath));
+        }
+        Ok(truepath)
+    }
+
+    /// ### Description
+    ///
+    /// The `_utimens_helper()` is a helper function used by
+    /// `utimensat_syscall()` and `futimens_syscall()` to set the last access
+    /// and modification times of the file specified by an inode obtained
+    /// from the corresponding caller syscall. The status change time of the
+    /// file is set to the current time, unless both times are left
+    /// unchanged.
+    ///
+    /// ### Arguments
+    ///
+    /// The `_utimens_helper()` accepts three arguments:
+    /// * `syscallname` - the name of the calling syscall, used in errors.
+    /// * `inodenum` - an inode of the file whose times are changed.
+    /// * `times` - the new access time (`times[0]`) and modification time
+    ///   (`times[1]`). A `tv_nsec` of `UTIME_NOW` sets the time to the current
+    ///   time and one of `UTIME_OMIT` leaves it unchanged. If `times` is
+    ///   `None`, both times are set to the current time.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion, zero is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * `EINVAL` - a `tv_nsec` is neither a valid nanosecond count nor one of
+    ///   `UTIME_NOW` and `UTIME_OMIT`, or a `tv_sec` is negative or too large
+    ///   for the time to be kept in nanoseconds
+    /// * `EACCES` - both times are set to the current time, and the caller is
+    ///   neither the owner of the file nor has write permission to it
+    /// * `EPERM` - a time is set to an explicit value, and the caller is
+    ///   neither the owner of the file nor the superuser
+    ///
+    /// ### Panics
+    ///
+    /// A panic occurs if the inode does not exist in the inode table.
+    fn _utimens_helper(
+        &self,
+        syscallname: &str,
+        inodenum: usize,
+        times: Option<&[interface::TimeSpec; 2]>,
+    ) -> i32 {
+        const NOW: interface::TimeSpec = interface::TimeSpec {
+            tv_sec: 0,
+            tv_nsec: UTIME_NOW,
+        };
+        let [atime, mtime] = times.unwrap_or(&[NOW, NOW]);
+        //inode times are nanoseconds since the epoch, which a time before the epoch
+        //or too far after it cannot be turned into
+        let explicit_nanos = |time: &interface::TimeSpec| -> Option<u64> {
+            if time.tv_sec < 0 || time.tv_nsec < 0 || time.tv_nsec >= 1_000_000_000 {
+                return None;
             }
-            // If everything is successful, we return 0
-            0
-        } else {
-            return syscall_error(Errno::EBADF, "close", "invalid file descriptor");
+            (time.tv_sec as u64)
+                .checked_mul(1_000_000_000)?
+                .checked_add(time.tv_nsec as u64)
+        };
+        for time in [atime, mtime] {
+            let special = time.tv_nsec == UTIME_NOW || time.tv_nsec == UTIME_OMIT;
+            if !special && explicit_nanos(time).is_none() {
+                return syscall_error(Errno::EINVAL, syscallname, "invalid time value");
+            }
+        }
+        if atime.tv_nsec == UTIME_OMIT && mtime.tv_nsec == UTIME_OMIT {
+            return 0;
+        }
+
+        //Anyone who may write to a file may "touch" it, but only its owner may set
+        //its times to arbitrary values
+        let settonow = atime.tv_nsec == UTIME_NOW && mtime.tv_nsec == UTIME_NOW;
+        let (euid, egid) = self.get_effective_ids();
+        let (_, owner, _) = inode_ownership(&FS_METADATA.inodetable.get(&inodenum).unwrap());
+        if euid != ROOT_UID && euid != owner {
+            if !settonow {
+                return syscall_error(
+                    Errno::EPERM,
+                    syscallname,
+                    "only the owner of a file may set its times to arbitrary values",
+                );
+            }
+            if !inode_permission_granted(inodenum, euid, egid, W_OK) {
+                return syscall_error(Errno::EACCES, syscallname, "write permission denied");
+            }
+        }
+
+        let now = interface::timestamp_nanos();
+        let resolve = |time: &interface::TimeSpec, current: u64| match time.tv_nsec {
+            UTIME_NOW => now,
+            UTIME_OMIT => current,
+            //explicit times were checked above
+            _ => explicit_nanos(time).unwrap(),
+        };
+        let mut thisinode = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+        //Sockets only exist as long as the cages using them are running, so
+        //changes to their inodes are not logged
+        let log = !matches!(*thisinode, Inode::Socket(_));
+        let (inode_atime, inode_mtime, inode_ctime) = inode_times_mut(&mut thisinode);
+        *inode_atime = resolve(atime, *inode_atime);
+        *inode_mtime = resolve(mtime, *inode_mtime);
+        *inode_ctime = now;
+        drop(thisinode);
+        if log {
+            log_metadata(&FS_METADATA, inodenum);
         }
+        0
     }
 
-    /// ## ------------------CLOSE HELPER ------------------
     /// ### Description
     ///
-    /// The `_close_helper` function calls `_close_helper_inner` to handle
-    /// the specifics of closing the file descriptor (like decrementing
-    /// reference counts, handling special cases for sockets, pipes, etc.).
-    /// Once `_close_helper_inner` has successfully done its job (indicated
-    /// by returning a non-negative value), `_close_helper` removes the file
-    /// descriptor from the file descriptor table. This ensures that the file
-    /// descriptor is fully closed and no longer tracked by the system.
+    /// The `utimensat_syscall()` sets the last access and modification times
+    /// of the file named by `path` with nanosecond precision. A relative
+    /// `path` is interpreted relative to the directory referred to by
+    /// `dirfd`, or relative to the current working directory if `dirfd` is
+    /// `AT_FDCWD`.
     ///
-    /// ### Function Arguments
+    /// ### Arguments
     ///
-    /// The `_close_helper()` receives one argument:
-    /// * `fd` - This argument refers to the file descriptor to be closed.
+    /// The `utimensat_syscall()` accepts four arguments:
+    /// * `dirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `path` - pathname of the file whose times are changed.
+    /// * `times` - the new access and modification times, see
+    ///   `_utimens_helper()`. `None` sets both to the current time.
+    /// * `flags` - either 0 or `AT_SYMLINK_NOFOLLOW`, in which case a symbolic
+    ///   link named by `path` is changed rather than the file it refers to.
     ///
     /// ### Returns
     ///
-    /// Upon successful completion of this call, it returns 0. Otherwise, it
-    /// returns an error number with the appropriate error code.
+    /// Upon successful completion, zero is returned.
+    /// In case of a failure, an error is returned, and `errno` is set depending
+    /// on the error, e.g. `EPERM`, `ENOENT`, etc.
     ///
     /// ### Errors
     ///
-    /// * There are no explicit errors returned by this function. The errors
-    ///   thrown by `_close_helper_inner` function are returned.
+    /// * `EINVAL` - `flags` contains an unknown flag, or see
+    ///   `_utimens_helper()`
+    /// * `ENOENT` - a component of path does not name an existing file, or path
+    ///   is an empty string
+    /// * `EBADF` - `path` is relative and `dirfd` is not a valid file
+    ///   descriptor
+    /// * `ENOTDIR` - `path` is relative and `dirfd` does not refer to a
+    ///   directory
+    /// * `ELOOP` - too many symbolic links were encountered in resolving `path`
+    /// * `EACCES` - search permission is denied on a component of the path
+    ///   prefix, or see `_utimens_helper()`
+    /// * `EPERM` - see `_utimens_helper()`
     ///
     /// ### Panics
     ///
-    /// * If the provided file descriptor is out of bounds, causing unwrap() to
-    ///   panic.
-    pub fn _close_helper(&self, fd: i32) -> i32 {
-        let inner_result = self._close_helper_inner(fd);
-        // Return value of less than 0 indicates that there was some error
-        // closing the fd, so we return the error.
-        if inner_result < 0 {
-            return inner_result;
+    /// There are no cases where this syscall panics.
+    ///
+    /// To learn more about the syscall and error values, see
+    /// [utimensat(2)](https://man7.org/linux/man-pages/man2/utimensat.2.html)
+    pub fn utimensat_syscall(
+        &self,
+        dirfd: i32,
+        path: &str,
+        times: Option<&[interface::TimeSpec; 2]>,
+        flags: i32,
+    ) -> i32 {
+        if flags & !AT_SYMLINK_NOFOLLOW != 0 {
+            return syscall_error(Errno::EINVAL, "utimensat", "invalid flags");
+        }
+        let truepath = match self._at_path_helper("utimensat", dirfd, path) {
+            Ok(truepath) => truepath,
+            Err(e) => return e,
+        };
+        let searchcheck = self._search_check("utimensat", &truepath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
+        let follow_final = flags & AT_SYMLINK_NOFOLLOW == 0;
+        match metawalkandparent_resolve(truepath.as_path(), follow_final) {
+            Ok((Some(inodenum), ..)) => self._utimens_helper("utimensat", inodenum, times),
+            Ok(_) => syscall_error(
+                Errno::ENOENT,
+                "utimensat",
+                "A component of path does not name an existing file",
+            ),
+            Err(_) => syscall_error(
+                Errno::ELOOP,
+                "utimensat",
+                "too many symbolic links were encountered in resolving path",
+            ),
         }
+    }
 
-        // Once we know that the closing of the fd was successful,
-        // we remove the file descriptor from the fd table and free the space.
-        //
-        // Bug:
-        // if the provided file descriptor is out of bounds, get_filedescriptor returns
-        // Err(), unwrapping on which  produces a 'panic!'
-        //otherwise, file descriptor table entry is stored in 'checkedfd'
-        let checkedfd = self.get_filedescriptor(fd).unwrap();
-        let mut unlocked_fd = checkedfd.write();
-        // This operation effectively removes the file descriptor from the fd table
-        // by removing its reference from the variable and then not using it further,
-        // allowing it to be dropped
-        if unlocked_fd.is_some() {
-            let _discarded_fd = unlocked_fd.take();
+    /// ### Description
+    ///
+    /// The `futimens_syscall()` is equivalent to `utimensat_syscall()` except
+    /// that the file is specified by the open file descriptor `fd`.
+    ///
+    /// ### Arguments
+    ///
+    /// The `futimens_syscall()` accepts two arguments:
+    /// * `fd` - an open file descriptor.
+    /// * `times` - the new access and modification times, see
+    ///   `_utimens_helper()`. `None` sets both to the current time.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion, zero is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * `EBADF` - the file descriptor `fd` is not valid.
+    /// * `EINVAL` - the times of this file type cannot be changed, or see
+    ///   `_utimens_helper()`
+    /// * `EACCES`, `EPERM` - see `_utimens_helper()`
+    ///
+    /// ### Panics
+    ///
+    /// There are no cases where this syscall panics.
+    ///
+    /// To learn more about the syscall and error values, see
+    /// [futimens(3)](https://man7.org/linux/man-pages/man3/futimens.3.html)
+    pub fn futimens_syscall(&self, fd: i32, times: Option<&[interface::TimeSpec; 2]>) -> i32 {
+        let checkedfd = match self.get_filedescriptor(fd) {
+            Ok(checkedfd) => checkedfd,
+            Err(_) => return syscall_error(Errno::EBADF, "futimens", "Invalid file descriptor"),
+        };
+        let unlocked_fd = checkedfd.read();
+        match &*unlocked_fd {
+            //Only descriptors backed by an inode have times that can be changed
+            Some(File(normalfile_filedesc_obj)) => {
+                self._utimens_helper("futimens", normalfile_filedesc_obj.inode, times)
+            }
+            Some(_) => syscall_error(
+                Errno::EINVAL,
+                "futimens",
+                "Times cannot be changed on this file type",
+            ),
+            None => syscall_error(Errno::EBADF, "futimens", "Invalid file descriptor"),
         }
-        0 //_close_helper has succeeded!
     }
 
     /// ### Description
     ///
-    /// `fcntl_syscall` performs operations, like returning or setting file
-    /// status flags, duplicating a file descriptor, etc., on an open file
-    /// descriptor
+    /// The `mmap_syscall()` function creates a new mapping in the
+    /// virtual address space of the calling process.
     ///
     /// ### Arguments
     ///
-    /// it accepts three parameters:
-    /// * `fd` - an open file descriptor
-    /// * `cmd` - an operation to be performed on fd
-    /// * `arg` - an optional argument (whether or not arg is required is
-    ///   determined by cmd)
+    /// The `mmap_syscall()` accepts six arguments:
+    /// * `addr` - the starting address for the new mapping. If `addr`
+    /// is NULL, then the kernel chooses the (page-aligned) address at
+    /// which to create the mapping. If addr is not NULL, then the
+    /// kernel takes it as a hint about where to place the mapping.
+    /// * `len` - specifies the length of the mapping (which must be
+    /// greater than 0).
+    /// * `prot` - describes the desired memory protection of the
+    /// mapping, which must not conflict with the open mode of the file.
+    /// It is either `PROT_NONE` or the bitwise OR of one or more of the
+    /// following flags: `PROT_EXEC` (Pages may be executed), `PROT_READ`
+    /// (Pages may be read), `PROT_WRITE` (Pages may be written), `PROT_NONE`
+    /// (Pages may not be accessed).
+    /// * `flags` - determines whether updates to the mapping are visible
+    /// to other processes mapping the same region, and whether updates are
+    /// carried through to the underlying file. This behavior is determined
+    /// by including exactly one of the following values in flags: `MAP_SHARED`
+    /// (Share this mapping. Updates to the mapping are visible to other
+    /// processes mapping the same region, and in the case of file-backed
+    /// mappings are carried through to the underlying file) or `MAP_PRIVATE`
+    /// (Create a private copy-on-write mapping. Updates to the mapping are
+    /// not visible to other processes mapping the same file, and are not
+    /// carried through to the underlying file). `MAP_SHARED_VALIDATE` and
+    /// other flags are not validated in the current implementation but
+    /// are supported by the underlying `libc_mmap()` syscall.
+    /// * `filedes` - a file descriptor specifying the file that shall be
+    /// mapped.
+    /// * `off` - designates the offset in the file from which the mapping
+    /// should start.
     ///
     /// ### Returns
     ///
-    /// for a successful call, the return value depends on the operation and can
-    /// be one of: zero, the new file descriptor, value of file descriptor
-    /// flags, value of status flags, etc.
+    /// On success, `mmap_syscall()` returns a pointer to the mapped area.
+    /// In case of a failure, an error is returned, and `errno` is set depending
+    /// on the error, e.g. EINVAL, ERANGE, etc.
     ///
     /// ### Errors
     ///
-    /// * EBADF - fd is not a valid file descriptor
-    /// * EINVAL - doesnt match implementation parameters
+    /// * `EINVAL` - the value of len is 0 or `flags` contained neither
+    /// `MAP_PRIVATE` nor `MAP_SHARED` or `flags` contained both
+    /// `MAP_PRIVATE` and `MAP_SHARED`.
+    /// * `EACCES` - `fildes` is not open for reading or `MAP_SHARED`
+    /// was requested and PROT_WRITE is set, but fd is not open in
+    /// read/write (`O_RDWR`) mode or `fildes` refers to a non-regular file.
+    /// * `ENXIO` - addresses in the range [`off`, `off`+`len`) are invalid
+    /// for the object specified by `fildes`.
+    /// * `EOPNOTSUPP` - Lind currently does not support mapping character
+    ///   files.
+    /// * `EBADF` - invalid file descriptor.
+    /// Other errors, like `ENOMEM`, `EOVERFLOW`, etc. are not supported.
     ///
     /// ### Panics
     ///
-    /// * invalid or out-of-bounds file descriptor), calling unwrap() on it will
-    ///   cause a panic.
-    /// * Unknown errno value from fcntl returned, will cause panic.
+    /// A panic occurs when a provided file descriptor is out of bounds.
     ///
-    /// for more detailed description of all the commands and return values, see
-    /// [fcntl(2)](https://linux.die.net/man/2/fcntl)
+    /// To learn more about the syscall, flags, possible error values, etc., see
+    /// [mmap(2)](https://man7.org/linux/man-pages/man2/mmap.2.html)
 
-    pub fn fcntl_syscall(&self, fd: i32, cmd: i32, arg: i32) -> i32 {
+    pub fn mmap_syscall(
+        &self,
+        addr: *mut u8,
+        len: usize,
+        prot: i32,
+        flags: i32,
+        fildes: i32,
+        off: i64,
+    ) -> i32 {
+        if len == 0 {
+            return syscall_error(Errno::EINVAL, "mmap", "the value of len is 0");
+        }
+        //Exactly one of the two flags (either `MAP_PRIVATE` or `MAP_SHARED`) must be
+        // set
+        if 0 == (flags & (MAP_PRIVATE | MAP_SHARED)) {
+            return syscall_error(
+                Errno::EINVAL,
+                "mmap",
+                "The value of flags is invalid (neither MAP_PRIVATE nor MAP_SHARED is set)",
+            );
+        }
+        if ((flags & MAP_PRIVATE) != 0) && ((flags & MAP_SHARED) != 0) {
+            return syscall_error(
+                Errno::EINVAL,
+                "mmap",
+                "The value of flags is invalid (MAP_PRIVATE and MAP_SHARED cannot be both set)",
+            );
+        }
+        //The `MAP_ANONYMOUS` flag specifies that the mapping
+        //is not backed by any file, so the `fildes` and `off`
+        //arguments should be ignored; however, some implementations
+        //require `fildes` to be -1, which we follow for the
+        //sake of portability.
+        if 0 != (flags & MAP_ANONYMOUS) {
+            return interface::libc_mmap(addr, len, prot, flags, -1, 0);
+        }
         //BUG
-        //if the provided file descriptor is out of bounds, get_filedescriptor returns
-        // Err(), unwrapping on which  produces a 'panic!'
+        //If the provided file descriptor is out of bounds, get_filedescriptor returns
+        //Err(), unwrapping on which  produces a 'panic!'
         //otherwise, file descriptor table entry is stored in 'checkedfd'
-        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let checkedfd = self.get_filedescriptor(fildes).unwrap();
         let mut unlocked_fd = checkedfd.write();
         if let Some(filedesc_enum) = &mut *unlocked_fd {
-            //'flags' consists of bitwise-or'd access mode, file creation, and file status flags
-            //to retrieve a particular flag, it can bitwise-and'd with 'flags'
-            let flags = match filedesc_enum {
-                Epoll(obj) => &mut obj.flags,
-                Pipe(obj) => &mut obj.flags,
-                Stream(obj) => &mut obj.flags,
-                File(obj) => &mut obj.flags,
-                //not clear why running F_SETFL on Socket type requires special treatment
-                Socket(ref mut sockfdobj) => {
-                    if cmd == F_SETFL && arg >= 0 {
-                        let sock_tmp = sockfdobj.handle.clone();
-                        let mut sockhandle = sock_tmp.write();
-
-                        if let Some(ins) = &mut sockhandle.innersocket {
-                            let fcntlret;
-                            if arg & O_NONBLOCK == O_NONBLOCK {
-                                //set non-blocking I/O
-                                fcntlret = ins.set_nonblocking();
-                            } else {
-                                //set blocking I/O
-                                fcntlret = ins.set_blocking();
+            //The current implementation supports only regular files
+            match filedesc_enum {
+                File(ref mut normalfile_filedesc_obj) => {
+                    let inodeobj = FS_METADATA
+                        .inodetable
+                        .get(&normalfile_filedesc_obj.inode)
+                        .unwrap();
+                    //Confirm inode type is mappable
+                    match &*inodeobj {
+                        Inode::CharDev(_chardev_inode_obj) => {
+                            syscall_error(Errno::EOPNOTSUPP, "mmap", "lind currently does not support mapping character files")
+                        }
+                        Inode::File(normalfile_inode_obj) => {
+                            //For any kind of memory mapping, the file should be
+                            //opened for reading, so if it was opened for write
+                            //only, the mapping should be denied
+                            if (normalfile_filedesc_obj.flags & O_WRONLY) != 0 {
+                                return syscall_error(Errno::EACCES, "mmap", "file descriptor is not open for reading");
                             }
-                            if fcntlret < 0 {
-                                match Errno::from_discriminant(interface::get_errno()) {
-                                    Ok(i) => {
-                                        return syscall_error(
-                                            i,
-                                            "fcntl",
-                                            "The libc call to fcntl failed!",
-                                        );
-                                    }
-                                    Err(()) => panic!("Unknown errno value from fcntl returned!"),
-                                };
+                            //If we want to write our changes back to the file the file needs to be open for reading and writing
+                            if (flags & MAP_SHARED) != 0 && (prot & PROT_WRITE) != 0 && (normalfile_filedesc_obj.flags & O_RDWR) != O_RDWR {
+                                return syscall_error(Errno::EACCES, "mmap", "file descriptor is not open RDWR, but MAP_SHARED and PROT_WRITE are set");
+                            }
+                            let filesize = normalfile_inode_obj.size;
+                            //The offset cannot be negative, and we cannot read past the end of the file
+                            if off < 0 || off > filesize as i64 {
+                                return syscall_error(Errno::ENXIO, "mmap", "Addresses in the range [off,off+len) are invalid for the object specified by fildes.");
                             }
+                            //Because of NaCl's internal workings we must allow mappings to extend past the end of the file
+                            let mut fobj = FILEOBJECTTABLE.get_mut(&normalfile_filedesc_obj.inode).unwrap();
+                            //Writes through a shared mapping go straight to the host file, so it
+                            //can't be one the file shares with other files
+                            if (flags & MAP_SHARED) != 0 && (prot & PROT_WRITE) != 0 && fobj.make_private().is_err() {
+                                return syscall_error(Errno::EIO, "mmap", "the file could not be copied before mapping it");
+                            }
+                            //The actual memory mapping is not emulated inside Lind, so the call to the kernel
+                            //is required. To perform this call, the file descriptor of the actual file
+                            //stored on the host machine is needed. Since Lind's emulated filesystem
+                            //does not match the underlying host's filesystem, the file descriptor
+                            //provided to the `mmap_syscall()` cannot be used and must be converted
+                            //to the actual file descriptor stored in the host's filesystem.
+                            let fobjfdno = fobj.as_fd_handle_raw_int();
+                            interface::libc_mmap(addr, len, prot, flags, fobjfdno, off)
                         }
+                        _ => {syscall_error(Errno::EACCES, "mmap", "the fildes argument refers to a file whose type is not supported by mmap")}
                     }
-
-                    &mut sockfdobj.flags
-                }
-            };
-
-            //matching the tuple
-            match (cmd, arg) {
-                //because the arg parameter is not used in certain commands, it can be anything (..)
-                //F_GETFD returns file descriptor flags only, meaning that access mode flags
-                //and file status flags are excluded
-                //F_SETFD is used to set file descriptor flags only, meaning that any changes to access mode flags
-                //or file status flags should be ignored
-                //currently, O_CLOEXEC is the only defined file descriptor flag, thus only this flag is
-                //masked when using F_GETFD or F_SETFD
-                (F_GETFD, ..) => *flags & O_CLOEXEC,
-                (F_SETFD, arg) if arg >= 0 => {
-                    if arg & O_CLOEXEC != 0 {
-                        //if O_CLOEXEC flag is set to 1 in 'arg', 'flags' is updated by setting its O_CLOEXEC bit to 1
-                        *flags |= O_CLOEXEC;
-                    } else {
-                        //if O_CLOEXEC flag is set to 0 in 'arg', 'flags' is updated by setting its O_CLOEXEC bit to 0
-                        *flags &= !O_CLOEXEC;
-                    }
-                    0
-                }
-                //F_GETFL should return file access mode and file status flags, which means that
-                //file creation flags should be masked out
-                (F_GETFL, ..) => *flags & !(O_CREAT | O_EXCL | O_NOCTTY | O_TRUNC),
-                //F_SETFL is used to set file status flags, thus any changes to file access mode and file
-                //creation flags should be ignored (see F_SETFL command in the man page for fcntl for the reference)
-                (F_SETFL, arg) if arg >= 0 => {
-                    //valid changes are extracted by ignoring changes to file access mode and file creation flags
-                    let valid_changes =
-                        arg & !(O_RDWRFLAGS | O_CREAT | O_EXCL | O_NOCTTY | O_TRUNC);
-                    //access mode and creation flags are extracted and other flags are set to 0 to update them
-                    let acc_and_creation_flags =
-                        *flags & (O_RDWRFLAGS | O_CREAT | O_EXCL | O_NOCTTY | O_TRUNC);
-                    //valid changes are combined with the old file access mode and file creation flags
-                    *flags = valid_changes | acc_and_creation_flags;
-                    0
-                }
-                (F_DUPFD, arg) if arg >= 0 => self._dup2_helper(&filedesc_enum, arg, false),
-                //TO DO: F_GETOWN and F_SETOWN commands are not implemented yet
-                (F_GETOWN, ..) => 0,
-                (F_SETOWN, arg) if arg >= 0 => 0,
-                _ => {
-                    let err_msg = format!(
-                        "Arguments pair ({}, {}) does not match implemented parameters",
-                        cmd, arg
-                    );
-                    syscall_error(Errno::EINVAL, "fcntl", &err_msg)
                 }
+                _ => syscall_error(
+                    Errno::EACCES,
+                    "mmap",
+                    "the fildes argument refers to a file whose type is not supported by mmap",
+                ),
             }
         } else {
-            syscall_error(Errno::EBADF, "fcntl", "File descriptor is out of range")
+            syscall_error(Errno::EBADF, "mmap", "invalid file descriptor")
         }
     }
 
     /// ### Description
     ///
-    /// The `ioctl_syscall()` manipulates the underlying device parameters of
-    /// special files. In particular, it is used as a way for user-space
-    /// applications to interface with device drivers.
+    /// The `munmap_syscall()` function shall remove any mappings
+    /// containing any part of the address space of the process
+    /// starting at `addr` and continuing for `len` bytes.
+    /// Further references to these pages shall result in the
+    /// generation of a `SIGSEGV` signal to the process. If there
+    /// are no mappings in the specified address range, then
+    /// `munmap_syscall()` has no effect.
+    /// The current implementation of the syscall solely relies
+    /// on the inner implementation of NaCl (except for a simple
+    /// `len` argument check) by creating a new mapping in the
+    /// specified memory region by using `MAP_FIXED` with
+    /// `PROT_NONE` flag to deny any access to the unmapped
+    /// memory region.
     ///
     /// ### Arguments
     ///
-    /// The `ioctl_syscall()` accepts three arguments:
-    /// * `fd` - an open file descriptor that refers to a device.
-    /// * `request` - the control function to be performed. The set of valid
-    ///   request values depends entirely on the device being addressed.
-    ///   MEDIA_IOC_DEVICE_INFO is an example of an ioctl control function to
-    ///   query device information that all media devices must support.
-    /// * `ptrunion` - additional information needed by the addressed device to
-    ///   perform the selected control function. In the example of
-    ///   MEDIA_IOC_DEVICE_INFO request, a valid ptrunion value is a pointer to
-    ///   a struct media_device_info, from which the device information is
-    ///   obtained.
+    /// The `munmap_syscall()` accepts two arguments:
+    /// * `addr` - the address starting from which the mapping
+    /// shall be removed
+    /// * `len` - specifies the length of the mapping that
+    /// shall be removed.
     ///
     /// ### Returns
     ///
-    /// Upon successful completion, a value other than -1 that depends on the
-    /// selected control function is returned. In case of a failure, -1 is
-    /// returned with errno set to a particular value, like EBADF, EINVAL, etc.
+    /// On success, `munmap_syscall()` returns 0.
+    /// In case of a failure, an error is returned, and `errno`
+    /// is set to `EINVAL`.
     ///
-    /// ### Errors and Panics
+    /// ### Errors
     ///
-    /// * `EBADF` - fd is not a valid file descriptor
-    /// * `EFAULT` - ptrunion references an inaccessible memory area
-    /// * `EINVAL` - request or ptrunion is not valid
-    /// * `ENOTTY` - fd is not associated with a character special device
-    /// When `ioctl_syscall() is called on a Socket with `FIONBIO` control
-    /// function, an underlying call to `libc::fcntl()` is made,
-    /// which can return with an error. For a complete list of possible erorrs,
-    /// see [fcntl(2)](https://linux.die.net/man/2/fcntl)
+    /// * `EINVAL` - the value of len is 0
+    /// Other `EINVAL` errors are returned directly from the  call to
+    /// `libc_mmap`
     ///
-    /// A panic occurs either when a provided file descriptor is out of bounds
-    /// or when an underlying call to `libc::fcntl()` for Socket type is
-    /// returned with an unknown error.
+    /// ### Panics
     ///
-    /// To learn more about the syscall, control functions applicable to all the
-    /// devices, and possible error values, see [ioctl(2)](https://man.openbsd.org/ioctl)
+    /// There are no cases where this function panics.
+    ///
+    /// To learn more about the syscall, flags, possible error values, etc., see
+    /// [munmap(2)](https://linux.die.net/man/2/munmap)
 
-    pub fn ioctl_syscall(&self, fd: i32, request: u32, ptrunion: IoctlPtrUnion) -> i32 {
-        //BUG
-        //if the provided file descriptor is out of bounds, 'get_filedescriptor'
-        // returns Err(), unwrapping on which  produces a 'panic!'
-        //otherwise, file descriptor table entry is stored in 'checkedfd'
-        let checkedfd = self.get_filedescriptor(fd).unwrap();
-        let mut unlocked_fd = checkedfd.write();
-        //if a table descriptor entry is non-empty, a valid request is performed
-        if let Some(filedesc_enum) = &mut *unlocked_fd {
-            //For now, the only implemented control function is FIONBIO command used with
-            // sockets
-            match request {
-                //for FIONBIO, 'ptrunion' stores a pointer to an integer. If the integer is 0, the
-                // socket's nonblocking I/O is cleared. Otherwise, the socket is set
-                // for nonblocking I/O
-                FIONBIO => {
-                    //if 'ptrunion' stores a Null pointer, a 'Bad address' error is returned
-                    //otheriwse, the integer value stored in that address is returned and saved
-                    // into 'arg_result'
-                    let arg_result = interface::get_ioctl_int(ptrunion);
-                    match (arg_result, filedesc_enum) {
-                        (Err(arg_result), ..)=> {
-                            return arg_result;
-                        }
-                        //since FIONBIO command is used with sockets, we need to make sure that the provided
-                        //file descriptor addresses a socket
-                        //otherwise, a 'Not a typewriter' error designating that the specified command
-                        //is only applicable to sockets is returned 
-                        (Ok(arg_result), Socket(ref mut sockfdobj)) => {
-                            let sock_tmp = sockfdobj.handle.clone();
-                            let mut sockhandle = sock_tmp.write();
-                            let flags = &mut sockfdobj.flags;
-                            let arg: i32 = arg_result;
-                            let mut ioctlret = 0;
-                            //clearing nonblocking I/O on the socket if the integer is 0
-                            if arg == 0 {
-                                *flags &= !O_NONBLOCK;
-                                //libc::fcntl is called under the hood with F_SETFL command and 0 as an argument
-                                //to set blocking I/O, and the result of the call is stored in ioctlret
-                                if let Some(ins) = &mut sockhandle.innersocket {
+    pub fn munmap_syscall(&self, addr: *mut u8, len: usize) -> i32 {
+        if len == 0 {
+            return syscall_error(Errno::EINVAL, "mmap", "the value of len is 0");
+        }
+        //NaCl's munmap implementation actually just writes
+        //over the previously mapped data with PROT_NONE.
+        //This frees all of the resources except page table
+        //space, and is put inside safeposix for consistency.
+        //`MAP_FIXED` is used to precisely unmap the specified
+        //memory region, and `MAP_ANONYMOUS` is used to deny
+        //any further access to the unmapped memory region
+        //thereby emulating the unmapping process.
+        interface::libc_mmap(
+            addr,
+            len,
+            PROT_NONE,
+            MAP_PRIVATE | MAP_ANONYMOUS | MAP_FIXED,
+            -1,
+            0,
+        )
+    }
 
-                                    ioctlret = ins.set_blocking();
-                                }
-                            } else {
-                                *flags |= O_NONBLOCK;
-                                //libc::fcntl is called under the hood with F_SETFL command ans O_NONBLOCK as an argument
-                                //to set nonblocking I/O, and the result of the call is stored in ioctlret
-                                if let Some(ins) = &mut sockhandle.innersocket {
-                                    ioctlret = ins.set_nonblocking();
-                                }
-                            }
-                            //if ioctlret is negative, it means that the call to fcntl returned with an error
-                            if ioctlret < 0 {
-                                match Errno::from_discriminant(interface::get_errno()) {
-                                    Ok(i) => {return syscall_error(i, "ioctl", "The libc call to ioctl failed!");},
-                                    Err(()) => panic!("Unknown errno value from ioctl returned!"),
-                                };
-                            }
+    //------------------------------------FLOCK SYSCALL------------------------------------
 
-                            0
-                        }
-                        _ => {syscall_error(Errno::ENOTTY, "ioctl", "The specified request does not apply to the kind of object that the file descriptor fd references.")}
+    pub fn flock_syscall(&self, fd: i32, operation: i32) -> i32 {
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let unlocked_fd = checkedfd.read();
+        if let Some(filedesc_enum) = &*unlocked_fd {
+            let lock = match filedesc_enum {
+                File(normalfile_filedesc_obj) => &normalfile_filedesc_obj.advlock,
+                Socket(socket_filedesc_obj) => &socket_filedesc_obj.advlock,
+                Stream(stream_filedesc_obj) => &stream_filedesc_obj.advlock,
+                Pipe(pipe_filedesc_obj) => &pipe_filedesc_obj.advlock,
+                Epoll(epoll_filedesc_obj) => &epoll_filedesc_obj.advlock,
+                EventFd(eventfd_filedesc_obj) => &eventfd_filedesc_obj.advlock,
+                TimerFd(timerfd_filedesc_obj) => &timerfd_filedesc_obj.advlock,
+                SignalFd(signalfd_filedesc_obj) => &signalfd_filedesc_obj.advlock,
+            };
+            match operation & (LOCK_SH | LOCK_EX | LOCK_UN) {
+                LOCK_SH => {
+                    if operation & LOCK_NB == LOCK_NB {
+                        //EAGAIN and EWOULDBLOCK are the same
+                        if !lock.try_lock_sh() {
+                            return syscall_error(
+                                Errno::EAGAIN,
+                                "flock",
+                                "shared lock would block",
+                            );
+                        };
+                    } else {
+                        lock.lock_sh();
                     }
                 }
-                FIOASYNC => {
-                    //not implemented
-                    interface::log_verbose(
-                        "ioctl(FIOASYNC) is not implemented, and just returns 0.",
-                    );
-                    0
+                LOCK_EX => {
+                    if operation & LOCK_NB == LOCK_NB {
+                        if !lock.try_lock_ex() {
+                            return syscall_error(
+                                Errno::EAGAIN,
+                                "flock",
+                                "exclusive lock would block",
+                            );
+                        };
+                    } else {
+                        lock.lock_ex();
+                    }
+                }
+                LOCK_UN => {
+                    if operation & LOCK_NB == LOCK_NB {
+                        lock.unlock();
+                    } else {
+                        lock.unlock();
+                    }
+                }
+                _ => {
+                    return syscall_error(Errno::EINVAL, "flock", "unknown operation");
                 }
-                _ => syscall_error(
-                    Errno::EINVAL,
-                    "ioctl",
-                    "Arguments provided do not match implemented parameters",
-                ),
             }
+            0 //flock has  succeeded!
         } else {
-            syscall_error(Errno::EBADF, "ioctl", "Invalid file descriptor")
+            syscall_error(Errno::EBADF, "flock", "invalid file descriptor")
         }
     }
 
     /// ### Description
     ///
-    /// The `_chmod_helper()` is a helper function used by both
-    /// `chmod_syscall()` and `fchmod_syscall()` to change mode bits that
-    /// consist of read, write, and execute file permission bits of a file
-    /// specified by an inode obtained from the corresponding caller
-    /// syscall.
+    /// The `_search_check()` is a helper function used by the path-based
+    /// syscalls to make sure the caller is allowed to search (execute) every
+    /// directory that has to be looked into to reach `truepath`.
     ///
     /// ### Arguments
     ///
-    /// The `_chmod_helper()` accepts two arguments:
-    /// * `inodenum` - an inode of a file whose mode bits we are willing to
-    /// change obtained from the caller syscall.
-    /// * `mode` - the new file mode, which is a bit mask created by
-    /// bitwise-or'ing zero or more valid mode bits. Some of the examples of
-    /// such bits are `S_IRUSR` (read by owner), `S_IWUSR` (write by owner),
-    /// etc.
+    /// The `_search_check()` accepts two arguments:
+    /// * `syscallname` - the name of the calling syscall, used in the error.
+    /// * `truepath` - the absolute, normalized path being looked up.
     ///
     /// ### Returns
     ///
-    /// Upon successful completion, zero is returned.
-    /// In case of a failure, an error is returned, and `errno` is set depending
-    /// on the error, e.g. EACCES, ENOENT, etc.
-    ///
-    /// ### Errors
-    ///
-    /// Currently, only one error is supported:
-    /// * `EINVAL` - the value of the mode argument is invalid.
-    /// Other errors, like `EFAULT`, `ENOTDIR`, etc. are not supported.
-    ///
-    /// ### Panics
-    ///
-    /// There are no cases where this helper function panics.
-
-    pub fn _chmod_helper(inodenum: usize, mode: u32) -> i32 {
-        //S_IRWXA is a result of bitwise-or'ing read, write, and execute or search
-        //permissions for the file owner, group owners,
-        //and other users. It encompasses all the mode bits that can be changed
-        //via `chmod_syscall()` and is used as a bitmask to make sure that no
-        //other invalid bit change is being made.
-        if (mode & S_IRWXA) == mode {
-            //getting a mutable reference to an inode struct that corresponds to
-            //the file whose mode bits we want to change
-            let mut thisinode = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
-            //log is used to store all the changes made to the filesystem. After
-            //the cage is closed, all the collected changes are serialized and
-            //the state of the underlying filsystem is persisted. This allows us
-            //to avoid serializing and persisting filesystem state after every
-            //`chmod_syscall()`.
-            let mut log = true;
-            //We obtain the mode bits that should remain intact by bitwise-and'ing
-            //the inode's mode bits with the set of bits that can be changed via
-            //`chmod_syscall`. The changes are applied by bitwise-or'ing
-            //the intact mode bits with the changed mode bits.
-            match *thisinode {
-                Inode::File(ref mut general_inode) => {
-                    general_inode.mode = (general_inode.mode & !S_IRWXA) | mode;
-                }
-                Inode::CharDev(ref mut dev_inode) => {
-                    dev_inode.mode = (dev_inode.mode & !S_IRWXA) | mode;
-                }
-                Inode::Socket(ref mut sock_inode) => {
-                    sock_inode.mode = (sock_inode.mode & !S_IRWXA) | mode;
-                    //Sockets only exist as long as the cages using them are running.
-                    //After these cages are closed, no changes to sockets' inodes
-                    //need to be persisted, thus using log is unnecessary.
-                    log = false;
-                }
-                Inode::Dir(ref mut dir_inode) => {
-                    dir_inode.mode = (dir_inode.mode & !S_IRWXA) | mode;
-                }
-            }
-            //the mutable reference to the inode has to be dropped because
-            //`log_metadata` will need to acquire an immutable reference to
-            //the same inode
-            drop(thisinode);
-            //changes to an inode are saved into the log for all file types
-            //except for Sockets
-            if log {
-                log_metadata(&FS_METADATA, inodenum);
-            };
-            //return 0 on success
+    /// Zero if every directory on the way may be searched, otherwise `EACCES`.
+    pub(crate) fn _search_check(&self, syscallname: &str, truepath: &interface::RustPath) -> i32 {
+        let (euid, egid) = self.get_effective_ids();
+        // The directories walked through are the ones with any symbolic links in
+        // the directory components resolved. A path that can't be resolved is
+        // left for the caller's own lookup to report.
+        let resolved = match resolvepath(truepath, false) {
+            Ok(resolved) => resolved,
+            Err(_) => return 0,
+        };
+        if search_permitted(&resolved, euid, egid) {
             0
         } else {
-            return syscall_error(
-                Errno::EINVAL,
-                "chmod",
-                "The value of the mode argument is invalid",
-            );
+            syscall_error(
+                Errno::EACCES,
+                syscallname,
+                "search permission is denied for a directory in the path prefix",
+            )
         }
     }
 
     /// ### Description
     ///
-    /// The `chmod_syscall()` changes a file's mode bits that consist of read,
-    /// write, and execute file permission bits.
-    /// Changing `set-user-ID`, `set-group-ID`, and sticky bits is currently
-    /// not supported.
+    /// The `_access_check()` is a helper function which checks that the caller
+    /// has every permission in `amode` on an inode, using the caller's
+    /// effective user and group ids.
     ///
     /// ### Arguments
     ///
-    /// The `chmod_syscall()` accepts two arguments:
-    /// * `path` - pathname of the file whose mode bits we are willing to
-    /// change (symbolic links are currently not supported). If the
-    /// pathname is relative, then it is interpreted relative to the
-    /// current working directory of the calling process.
-    /// * `mode` - the new file mode, which is a bit mask created by
-    /// bitwise-or'ing zero or more valid mode bits. Some of the examples
-    /// of such bits are `S_IRUSR` (read by owner), `S_IWUSR` (write by owner),
-    /// etc.
+    /// The `_access_check()` accepts three arguments:
+    /// * `syscallname` - the name of the calling syscall, used in the error.
+    /// * `inodenum` - the inode number of the file being accessed.
+    /// * `amode` - a combination of `R_OK`, `W_OK` and `X_OK`.
     ///
     /// ### Returns
     ///
-    /// Upon successful completion, zero is returned.
-    /// In case of a failure, an error is returned, and `errno` is set depending
-    /// on the error, e.g. `EACCES`, `ENOENT`, etc.
-    ///
-    /// ### Errors
-    ///
-    /// Currently, only two errors are supposrted:
-    /// * `EINVAL` - the value of the mode argument is invalid
-    /// * `ENOENT` - a component of path does not name an existing file
-    /// Other errors, like `EFAULT`, `ENOTDIR`, etc. are not supported.
-    ///
-    /// ### Panics
-    ///
-    /// There are no cases where this syscall panics.
-    ///
-    /// To learn more about the syscall, valid mode bits, and error values, see
-    /// [chmod(2)](https://man7.org/linux/man-pages/man2/chmod.2.html)
-
-    pub fn chmod_syscall(&self, path: &str, mode: u32) -> i32 {
-        //Convert the provided pathname into an absolute path without `.` or `..`
-        //components.
-        let truepath = normpath(convpath(path), self);
-        //Perfrom a walk down the file tree starting from the root directory to
-        //obtain an inode number of the file whose pathname was specified.
-        //`None` is returned if one of the following occurs while moving down
-        //the tree: accessing a child of a non-directory inode, accessing a
-        //child of a nonexistent parent directory, accessing a nonexistent child,
-        //accessing an unexpected component, like `.` or `..` directory reference.
-        //In this case, `The file does not exist` error is returned.
-        //Otherwise, a `Some()` option containing the inode number is returned.
-        if let Some(inodenum) = metawalk(truepath.as_path()) {
-            Self::_chmod_helper(inodenum, mode)
+    /// Zero if access is granted, otherwise `EACCES`.
+    pub(crate) fn _access_check(&self, syscallname: &str, inodenum: usize, amode: u32) -> i32 {
+        let (euid, egid) = self.get_effective_ids();
+        if inode_permission_granted(inodenum, euid, egid, amode) {
+            0
         } else {
-            return syscall_error(
-                Errno::ENOENT,
-                "chmod",
-                "A component of path does not name an existing file",
-            );
+            syscall_error(
+                Errno::EACCES,
+                syscallname,
+                "the requested access to the file is not allowed",
+            )
         }
     }
 
     /// ### Description
     ///
-    /// The `fchmod_syscall()` is equivalent to `chmod_syscall()` in that
-    /// it is used to change a file's mode bits that consist of read,
-    /// write, and execute file permission bits except that the file
-    /// is specified by the file descriptor. Changing `set-user-ID`,
-    /// `set-group-ID`, and sticky bits is currently not supported.
+    /// The `_remove_check()` is a helper function used by the syscalls that
+    /// remove a directory entry (unlink, rmdir and rename). Removing an entry
+    /// requires write and search permission on the parent directory, and if
+    /// the parent directory has the sticky bit set (as /tmp does), only the
+    /// owner of the file, the owner of the directory, or the superuser may
+    /// remove it.
     ///
     /// ### Arguments
     ///
-    /// The `fchmod_syscall()` accepts two arguments:
-    /// * `fd` - an open file descriptor.
-    /// * `mode` - the new file mode, which is a bit mask created by
-    /// bitwise-or'ing zero or more valid mode bits. Some of the examples
-    /// of such bits are `S_IRUSR` (read by owner), `S_IWUSR`
-    /// (write by owner), etc.
+    /// The `_remove_check()` accepts three arguments:
+    /// * `syscallname` - the name of the calling syscall, used in the error.
+    /// * `parent_inodenum` - the inode number of the directory holding the
+    ///   entry.
+    /// * `inodenum` - the inode number of the file the entry refers to.
     ///
     /// ### Returns
     ///
-    /// Upon successful completion, zero is returned.
-    /// In case of a failure, an error is returned, and `errno` is set
-    /// depending on the error, e.g. `EACCES`, `ENOENT`, etc.
+    /// Zero if the entry may be removed, otherwise the error below.
     ///
     /// ### Errors
     ///
-    /// * `EBADF` - the file descriptor `fd` is not valid.
-    /// * `EINVAL` - the value of the `mode` argument is invalid or
-    /// mode bits cannot be changed on this file type
-    /// Other errors, like `EFAULT`, `ENOTDIR`, etc. are not supported.
-    ///
-    /// ### Panics
-    ///
-    /// A panic occurs when a provided file descriptor is out of bounds
-    ///
-    /// To learn more about the syscall, valid mode bits, and error values, see
-    /// [fchmod(2)](https://linux.die.net/man/2/fchmod)
+    /// * `EACCES` - the parent directory may not be written to or searched.
+    /// * `EPERM` - the parent directory is sticky and the caller owns neither
+    ///   it nor the file.
+    fn _remove_check(&self, syscallname: &str, parent_inodenum: usize, inodenum: usize) -> i32 {
+        let writecheck = self._access_check(syscallname, parent_inodenum, W_OK | X_OK);
+        if writecheck != 0 {
+            return writecheck;
+        }
 
-    pub fn fchmod_syscall(&self, fd: i32, mode: u32) -> i32 {
-        //BUG
-        //if the provided file descriptor is out of bounds, 'get_filedescriptor'
-        //returns `Err()`, unwrapping on which  produces a `panic!`
-        //otherwise, file descriptor table entry is stored in `checkedfd`
-        let checkedfd = self.get_filedescriptor(fd).unwrap();
-        let unlocked_fd = checkedfd.read();
-        //if a table descriptor entry is non-empty, a valid request is performed
-        if let Some(filedesc_enum) = &*unlocked_fd {
-            //Regular file type is the only type that supports `fchmod_syscall()`
-            match filedesc_enum {
-                File(normalfile_filedesc_obj) => {
-                    let inodenum = normalfile_filedesc_obj.inode;
-                    Self::_chmod_helper(inodenum, mode)
-                }
-                Socket(_) => {
-                    return syscall_error(
-                        Errno::EINVAL,
-                        "fchmod",
-                        "Mode bits cannot be changed on this file type",
-                    );
-                }
-                Stream(_) => {
-                    return syscall_error(
-                        Errno::EINVAL,
-                        "fchmod",
-                        "Mode bits cannot be changed on this file type",
-                    );
-                }
-                Pipe(_) => {
-                    return syscall_error(
-                        Errno::EINVAL,
-                        "fchmod",
-                        "Mode bits cannot be changed on this file type",
-                    );
-                }
-                Epoll(_) => {
-                    return syscall_error(
-                        Errno::EINVAL,
-                        "fchmod",
-                        "Mode bits cannot be changed on this file type",
-                    );
-                }
-            }
+        let (euid, _) = self.get_effective_ids();
+        let (parentmode, parentowner, _) =
+            inode_ownership(&FS_METADATA.inodetable.get(&parent_inodenum).unwrap());
+        let (_, fileowner, _) = inode_ownership(&FS_METADATA.inodetable.get(&inodenum).unwrap());
+        if parentmode & S_ISVTX != 0 && euid != ROOT_UID && euid != parentowner && euid != fileowner
+        {
+            return syscall_error(
+                Errno::EPERM,
+                syscallname,
+                "the directory is sticky and the file is owned by another user",
+            );
+        }
+        0
+    }
+
+    /// ### Description
+    ///
+    /// The `_update_atime()` is a helper function that records an access to
+    /// the contents of a file or directory, e.g. by `read_syscall()`.
+    /// Updating the access time after every read would turn each read into a
+    /// metadata write, so like the "relatime" mount option on Linux, the
+    /// access time is only updated when it is not newer than the modification
+    /// or status change time, or is more than a day old. This is still enough
+    /// for programs that compare it against the modification time to learn
+    /// whether a file was read since it was last changed.
+    ///
+    /// ### Arguments
+    ///
+    /// The `_update_atime()` accepts one argument:
+    /// * `inodenum` - the inode number of the file that was accessed. No
+    ///   reference to the inode may be held by the caller.
+    fn _update_atime(inodenum: usize) {
+        let mut inodeobj = match FS_METADATA.inodetable.get_mut(&inodenum) {
+            Some(inodeobj) => inodeobj,
+            None => return,
+        };
+        let now = interface::timestamp_nanos();
+        let (atime, mtime, ctime) = inode_times_mut(&mut inodeobj);
+        if *atime > *mtime && *atime > *ctime && now.saturating_sub(*atime) < RELATIME_INTERVAL {
+            return;
+        }
+        *atime = now;
+        drop(inodeobj);
+        log_metadata(&FS_METADATA, inodenum);
+    }
+
+    /// ### Description
+    ///
+    /// The `_owner_check()` is a helper function for the syscalls that only
+    /// the owner of a file or the superuser may perform, such as changing its
+    /// mode.
+    ///
+    /// ### Arguments
+    ///
+    /// The `_owner_check()` accepts two arguments:
+    /// * `syscallname` - the name of the calling syscall, used in the error.
+    /// * `inodenum` - the inode number of the file being changed.
+    ///
+    /// ### Returns
+    ///
+    /// Zero if the caller owns the file or is the superuser, otherwise `EPERM`.
+    fn _owner_check(&self, syscallname: &str, inodenum: usize) -> i32 {
+        let (euid, _) = self.get_effective_ids();
+        let (_, owner, _) = inode_ownership(&FS_METADATA.inodetable.get(&inodenum).unwrap());
+        if euid == ROOT_UID || euid == owner {
+            0
         } else {
-            return syscall_error(Errno::EBADF, "ioctl", "Invalid file descriptor");
+            syscall_error(
+                Errno::EPERM,
+                syscallname,
+                "the caller does not own the file",
+            )
         }
     }
 
     /// ### Description
     ///
-    /// The `mmap_syscall()` function creates a new mapping in the
-    /// virtual address space of the calling process.
+    /// The `_file_too_large()` is a helper function for the syscalls that
+    /// would grow a file beyond the soft RLIMIT_FSIZE of the calling cage. Like
+    /// Linux, it raises SIGXFSZ for the calling thread, whose default action
+    /// terminates the cage.
     ///
     /// ### Arguments
     ///
-    /// The `mmap_syscall()` accepts six arguments:
-    /// * `addr` - the starting address for the new mapping. If `addr`
-    /// is NULL, then the kernel chooses the (page-aligned) address at
-    /// which to create the mapping. If addr is not NULL, then the
-    /// kernel takes it as a hint about where to place the mapping.
-    /// * `len` - specifies the length of the mapping (which must be
-    /// greater than 0).
-    /// * `prot` - describes the desired memory protection of the
-    /// mapping, which must not conflict with the open mode of the file.
-    /// It is either `PROT_NONE` or the bitwise OR of one or more of the
-    /// following flags: `PROT_EXEC` (Pages may be executed), `PROT_READ`
-    /// (Pages may be read), `PROT_WRITE` (Pages may be written), `PROT_NONE`
-    /// (Pages may not be accessed).
-    /// * `flags` - determines whether updates to the mapping are visible
-    /// to other processes mapping the same region, and whether updates are
-    /// carried through to the underlying file. This behavior is determined
-    /// by including exactly one of the following values in flags: `MAP_SHARED`
-    /// (Share this mapping. Updates to the mapping are visible to other
-    /// processes mapping the same region, and in the case of file-backed
-    /// mappings are carried through to the underlying file) or `MAP_PRIVATE`
-    /// (Create a private copy-on-write mapping. Updates to the mapping are
-    /// not visible to other processes mapping the same file, and are not
-    /// carried through to the underlying file). `MAP_SHARED_VALIDATE` and
-    /// other flags are not validated in the current implementation but
-    /// are supported by the underlying `libc_mmap()` syscall.
-    /// * `filedes` - a file descriptor specifying the file that shall be
-    /// mapped.
-    /// * `off` - designates the offset in the file from which the mapping
-    /// should start.
+    /// The `_file_too_large()` accepts one argument:
+    /// * `syscallname` - the name of the calling syscall, used in the error.
     ///
     /// ### Returns
     ///
-    /// On success, `mmap_syscall()` returns a pointer to the mapped area.
-    /// In case of a failure, an error is returned, and `errno` is set depending
-    /// on the error, e.g. EINVAL, ERANGE, etc.
+    /// Always `EFBIG`, for the caller to return.
+    fn _file_too_large(&self, syscallname: &str) -> i32 {
+        // signals can't be delivered when rustposix runs on its own, as it does
+        // for the test suite
+        if !interface::RUSTPOSIX_TESTSUITE.load(interface::RustAtomicOrdering::Relaxed) {
+            interface::lind_threadkill(interface::get_pthreadid(), SIGXFSZ);
+        }
+        syscall_error(
+            Errno::EFBIG,
+            syscallname,
+            "the file would exceed the maximum file size of the cage",
+        )
+    }
+
+    /// ### Description
     ///
-    /// ### Errors
+    /// The `_reserve_inode()` is a helper function for the syscalls that
+    /// create a new inode. It charges the inode to `uid`, the owner of the new
+    /// inode, unless the file system or the quota of `uid` has run out of
+    /// inodes. Quotas are not enforced for a caller whose effective user is
+    /// the superuser.
     ///
-    /// * `EINVAL` - the value of len is 0 or `flags` contained neither
-    /// `MAP_PRIVATE` nor `MAP_SHARED` or `flags` contained both
-    /// `MAP_PRIVATE` and `MAP_SHARED`.
-    /// * `EACCES` - `fildes` is not open for reading or `MAP_SHARED`
-    /// was requested and PROT_WRITE is set, but fd is not open in
-    /// read/write (`O_RDWR`) mode or `fildes` refers to a non-regular file.
-    /// * `ENXIO` - addresses in the range [`off`, `off`+`len`) are invalid
-    /// for the object specified by `fildes`.
-    /// * `EOPNOTSUPP` - Lind currently does not support mapping character
-    ///   files.
-    /// * `EBADF` - invalid file descriptor.
-    /// Other errors, like `ENOMEM`, `EOVERFLOW`, etc. are not supported.
+    /// ### Arguments
     ///
-    /// ### Panics
+    /// The `_reserve_inode()` accepts two arguments:
+    /// * `uid` - the user that will own the new inode.
+    /// * `syscallname` - the name of the calling syscall, used in the error.
     ///
-    /// A panic occurs when a provided file descriptor is out of bounds.
+    /// ### Returns
     ///
-    /// To learn more about the syscall, flags, possible error values, etc., see
-    /// [mmap(2)](https://man7.org/linux/man-pages/man2/mmap.2.html)
+    /// 0 if the inode was reserved, otherwise the error for the caller to
+    /// return. A reserved inode that ends up not being created has to be given
+    /// back with `FS_METADATA.release_space()`.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOSPC` - every inode of the file system is in use.
+    /// * `EDQUOT` - `uid` has used up its inode quota.
+    pub fn _reserve_inode(&self, uid: u32, syscallname: &str) -> i32 {
+        let enforce_quota = self.get_effective_ids().0 != ROOT_UID;
+        match FS_METADATA.reserve_inode(uid, enforce_quota) {
+            Ok(()) => 0,
+            Err(errno) => syscall_error(
+                errno,
+                syscallname,
+                "no inode is left for the file in the file system or the quota",
+            ),
+        }
+    }
 
-    pub fn mmap_syscall(
+    /// ### Description
+    ///
+    /// The `_grow_for_write()` is a helper function for the syscalls that
+    /// write `count` bytes at `position` in a regular file. Any growth of the
+    /// file, including the zeroes that fill a gap before `position`, is
+    /// reserved with `_reserve_bytes()`. When not all of it fits, the write is
+    /// shortened to what does, as long as at least one byte can be written.
+    ///
+    /// ### Returns
+    ///
+    /// The number of bytes that may be written, or the error for the caller
+    /// to return.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOSPC` - no byte can be written as the file system is full.
+    /// * `EDQUOT` - no byte can be written as the quota of the owner is used
+    ///   up.
+    fn _grow_for_write(
         &self,
-        addr: *mut u8,
-        len: usize,
-        prot: i32,
-        flags: i32,
-        fildes: i32,
-        off: i64,
-    ) -> i32 {
-        if len == 0 {
-            return syscall_error(Errno::EINVAL, "mmap", "the value of len is 0");
-        }
-        //Exactly one of the two flags (either `MAP_PRIVATE` or `MAP_SHARED`) must be
-        // set
-        if 0 == (flags & (MAP_PRIVATE | MAP_SHARED)) {
-            return syscall_error(
-                Errno::EINVAL,
-                "mmap",
-                "The value of flags is invalid (neither MAP_PRIVATE nor MAP_SHARED is set)",
-            );
-        }
-        if ((flags & MAP_PRIVATE) != 0) && ((flags & MAP_SHARED) != 0) {
-            return syscall_error(
-                Errno::EINVAL,
-                "mmap",
-                "The value of flags is invalid (MAP_PRIVATE and MAP_SHARED cannot be both set)",
-            );
+        inode: &GenericInode,
+        position: usize,
+        count: usize,
+        syscallname: &str,
+    ) -> Result<usize, i32> {
+        let growth = (position + count).saturating_sub(inode.size);
+        if growth == 0 {
+            return Ok(count);
         }
-        //The `MAP_ANONYMOUS` flag specifies that the mapping
-        //is not backed by any file, so the `fildes` and `off`
-        //arguments should be ignored; however, some implementations
-        //require `fildes` to be -1, which we follow for the
-        //sake of portability.
-        if 0 != (flags & MAP_ANONYMOUS) {
-            return interface::libc_mmap(addr, len, prot, flags, -1, 0);
+        // the gap before position has to fit along with at least one new byte
+        let minimum = interface::rust_min(growth, growth.saturating_sub(count) + 1);
+        let granted = self._reserve_bytes(inode.uid, growth, minimum, syscallname)?;
+        Ok(count - (growth - granted))
+    }
+
+    /// ### Description
+    ///
+    /// The `_reserve_bytes()` is a helper function for the syscalls that grow
+    /// a regular file owned by `uid`. It reserves as many of the `wanted` bytes
+    /// as are left in the file system and the quota of `uid`, but no fewer than
+    /// `minimum`. Quotas are not enforced for a caller whose effective user is
+    /// the superuser.
+    ///
+    /// ### Returns
+    ///
+    /// The number of bytes reserved, or the error for the caller to return.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOSPC` - fewer than `minimum` bytes are left in the file system.
+    /// * `EDQUOT` - fewer than `minimum` bytes are left in the quota of `uid`.
+    fn _reserve_bytes(
+        &self,
+        uid: u32,
+        wanted: usize,
+        minimum: usize,
+        syscallname: &str,
+    ) -> Result<usize, i32> {
+        let enforce_quota = self.get_effective_ids().0 != ROOT_UID;
+        match FS_METADATA.reserve_bytes(uid, wanted as u64, minimum as u64, enforce_quota) {
+            Ok(granted) => Ok(granted as usize),
+            Err(errno) => Err(syscall_error(
+                errno,
+                syscallname,
+                "no space is left for the data in the file system or the quota",
+            )),
         }
-        //BUG
-        //If the provided file descriptor is out of bounds, get_filedescriptor returns
-        //Err(), unwrapping on which  produces a 'panic!'
-        //otherwise, file descriptor table entry is stored in 'checkedfd'
-        let checkedfd = self.get_filedescriptor(fildes).unwrap();
-        let mut unlocked_fd = checkedfd.write();
-        if let Some(filedesc_enum) = &mut *unlocked_fd {
-            //The current implementation supports only regular files
-            match filedesc_enum {
-                File(ref mut normalfile_filedesc_obj) => {
-                    let inodeobj = FS_METADATA
-                        .inodetable
-                        .get(&normalfile_filedesc_obj.inode)
-                        .unwrap();
-                    //Confirm inode type is mappable
-                    match &*inodeobj {
-                        Inode::CharDev(_chardev_inode_obj) => {
-                            syscall_error(Errno::EOPNOTSUPP, "mmap", "lind currently does not support mapping character files")
-                        }
-                        Inode::File(normalfile_inode_obj) => {
-                            //For any kind of memory mapping, the file should be
-                            //opened for reading, so if it was opened for write
-                            //only, the mapping should be denied
-                            if (normalfile_filedesc_obj.flags & O_WRONLY) != 0 {
-                                return syscall_error(Errno::EACCES, "mmap", "file descriptor is not open for reading");
-                            }
-                            //If we want to write our changes back to the file the file needs to be open for reading and writing
-                            if (flags & MAP_SHARED) != 0 && (prot & PROT_WRITE) != 0 && (normalfile_filedesc_obj.flags & O_RDWR) != O_RDWR {
-                                return syscall_error(Errno::EACCES, "mmap", "file descriptor is not open RDWR, but MAP_SHARED and PROT_WRITE are set");
-                            }
-                            let filesize = normalfile_inode_obj.size;
-                            //The offset cannot be negative, and we cannot read past the end of the file
-                            if off < 0 || off > filesize as i64 {
-                                return syscall_error(Errno::ENXIO, "mmap", "Addresses in the range [off,off+len) are invalid for the object specified by fildes.");
-                            }
-                            //Because of NaCl's internal workings we must allow mappings to extend past the end of the file
-                            let fobj = FILEOBJECTTABLE.get(&normalfile_filedesc_obj.inode).unwrap();
-                            //The actual memory mapping is not emulated inside Lind, so the call to the kernel
-                            //is required. To perform this call, the file descriptor of the actual file
-                            //stored on the host machine is needed. Since Lind's emulated filesystem
-                            //does not match the underlying host's filesystem, the file descriptor
-                            //provided to the `mmap_syscall()` cannot be used and must be converted
-                            //to the actual file descriptor stored in the host's filesystem.
-                            let fobjfdno = fobj.as_fd_handle_raw_int();
-                            interface::libc_mmap(addr, len, prot, flags, fobjfdno, off)
-                        }
-                        _ => {syscall_error(Errno::EACCES, "mmap", "the fildes argument refers to a file whose type is not supported by mmap")}
-                    }
+    }
+
+    /// ### Description
+    ///
+    /// The `_regular_file_of()` is a helper function for sendfile and
+    /// copy_file_range, which work on the file objects of regular files
+    /// directly. It looks up the regular file `fd` refers to.
+    ///
+    /// ### Returns
+    ///
+    /// The inode number of the file and the flags of `fd`, None if `fd` is
+    /// open but doesn't refer to a regular file, or the error for the caller
+    /// to return.
+    ///
+    /// ### Errors
+    ///
+    /// * `EBADF` - `fd` is not a valid file descriptor.
+    /// * `EISDIR` - `fd` refers to a directory.
+    fn _regular_file_of(&self, fd: i32, syscallname: &str) -> Result<Option<(usize, i32)>, i32> {
+        let checkedfd = match self.get_filedescriptor(fd) {
+            Ok(checkedfd) => checkedfd,
+            Err(_) => {
+                return Err(syscall_error(
+                    Errno::EBADF,
+                    syscallname,
+                    "invalid file descriptor",
+                ))
+            }
+        };
+        let unlocked_fd = checkedfd.read();
+        match &*unlocked_fd {
+            Some(File(normalfile_filedesc_obj)) => {
+                match *FS_METADATA
+                    .inodetable
+                    .get(&normalfile_filedesc_obj.inode)
+                    .unwrap()
+                {
+                    Inode::File(_) => Ok(Some((
+                        normalfile_filedesc_obj.inode,
+                        normalfile_filedesc_obj.flags,
+                    ))),
+                    Inode::Dir(_) => Err(syscall_error(
+                        Errno::EISDIR,
+                        syscallname,
+                        "fd refers to a directory",
+                    )),
+                    _ => Ok(None),
                 }
-                _ => syscall_error(
-                    Errno::EACCES,
-                    "mmap",
-                    "the fildes argument refers to a file whose type is not supported by mmap",
-                ),
             }
-        } else {
-            syscall_error(Errno::EBADF, "mmap", "invalid file descriptor")
+            Some(_) => Ok(None),
+            None => Err(syscall_error(
+                Errno::EBADF,
+                syscallname,
+                "invalid file descriptor",
+            )),
+        }
+    }
+
+    // Whether fd is open on a file of the lind file system, which devices are
+    // not since they have a device number of their own, and neither are pipes
+    // and sockets
+    fn _on_file_system(&self, fd: i32) -> bool {
+        let checkedfd = match self.get_filedescriptor(fd) {
+            Ok(checkedfd) => checkedfd,
+            Err(_) => return false,
+        };
+        let unlocked_fd = checkedfd.read();
+        match &*unlocked_fd {
+            Some(File(normalfile_filedesc_obj)) => !matches!(
+                FS_METADATA
+                    .inodetable
+                    .get(&normalfile_filedesc_obj.inode)
+                    .as_deref(),
+                Some(Inode::CharDev(_))
+            ),
+            _ => false,
         }
     }
 
     /// ### Description
     ///
-    /// The `munmap_syscall()` function shall remove any mappings
-    /// containing any part of the address space of the process
-    /// starting at `addr` and continuing for `len` bytes.
-    /// Further references to these pages shall result in the
-    /// generation of a `SIGSEGV` signal to the process. If there
-    /// are no mappings in the specified address range, then
-    /// `munmap_syscall()` has no effect.
-    /// The current implementation of the syscall solely relies
-    /// on the inner implementation of NaCl (except for a simple
-    /// `len` argument check) by creating a new mapping in the
-    /// specified memory region by using `MAP_FIXED` with
-    /// `PROT_NONE` flag to deny any access to the unmapped
-    /// memory region.
-    ///
-    /// ### Arguments
-    ///
-    /// The `munmap_syscall()` accepts two arguments:
-    /// * `addr` - the address starting from which the mapping
-    /// shall be removed
-    /// * `len` - specifies the length of the mapping that
-    /// shall be removed.
+    /// The `_read_file_at()` is a helper function that reads from the file
+    /// object of the regular file `inodenum` at `position` into `buf`, without
+    /// going through a file descriptor.
     ///
     /// ### Returns
     ///
-    /// On success, `munmap_syscall()` returns 0.
-    /// In case of a failure, an error is returned, and `errno`
-    /// is set to `EINVAL`.
+    /// The number of bytes read, which is 0 at or past the end of the file.
+    fn _read_file_at(inodenum: usize, buf: &mut [u8], position: usize) -> usize {
+        let inodeobj = match FS_METADATA.inodetable.get(&inodenum) {
+            Some(inodeobj) => inodeobj,
+            None => return 0,
+        };
+        let size = match &*inodeobj {
+            Inode::File(normalfile_inode_obj) => normalfile_inode_obj.size,
+            _ => return 0,
+        };
+        if position >= size {
+            return 0;
+        }
+        let count = interface::rust_min(buf.len(), size - position);
+        let fileobject = FILEOBJECTTABLE.get(&inodenum).unwrap();
+        fileobject
+            .readat(buf.as_mut_ptr(), count, position)
+            .unwrap()
+    }
+
+    /// ### Description
     ///
-    /// ### Errors
+    /// The `_write_file_at()` is a helper function that writes `buf` to the
+    /// file object of the regular file `inodenum` at `position`, without going
+    /// through a file descriptor. The file grows the same way as for
+    /// `pwrite_syscall()`: RLIMIT_FSIZE applies, the growth is reserved with
+    /// `_grow_for_write()`, and a gap before `position` is filled with zeroes.
     ///
-    /// * `EINVAL` - the value of len is 0
-    /// Other `EINVAL` errors are returned directly from the  call to
-    /// `libc_mmap`
+    /// ### Returns
     ///
-    /// ### Panics
+    /// The number of bytes written, or the error for the caller to return.
     ///
-    /// There are no cases where this function panics.
+    /// ### Errors
     ///
-    /// To learn more about the syscall, flags, possible error values, etc., see
-    /// [munmap(2)](https://linux.die.net/man/2/munmap)
+    /// * `EFBIG` - `position` is at or past RLIMIT_FSIZE.
+    /// * `ENOSPC`, `EDQUOT` - as for `_grow_for_write()`.
+    fn _write_file_at(
+        &self,
+        inodenum: usize,
+        buf: &[u8],
+        position: usize,
+        syscallname: &str,
+    ) -> i32 {
+        let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+        let normalfile_inode_obj = match *inodeobj {
+            Inode::File(ref mut normalfile_inode_obj) => normalfile_inode_obj,
+            _ => panic!("{}: regular file inode changed type", syscallname),
+        };
+        let filesize = normalfile_inode_obj.size;
 
-    pub fn munmap_syscall(&self, addr: *mut u8, len: usize) -> i32 {
-        if len == 0 {
-            return syscall_error(Errno::EINVAL, "mmap", "the value of len is 0");
+        let limit = self.get_rlimit(RLIMIT_FSIZE).rlim_cur;
+        if !buf.is_empty() && position as u64 >= limit {
+            return self._file_too_large(syscallname);
         }
-        //NaCl's munmap implementation actually just writes
-        //over the previously mapped data with PROT_NONE.
-        //This frees all of the resources except page table
-        //space, and is put inside safeposix for consistency.
-        //`MAP_FIXED` is used to precisely unmap the specified
-        //memory region, and `MAP_ANONYMOUS` is used to deny
-        //any further access to the unmapped memory region
-        //thereby emulating the unmapping process.
-        interface::libc_mmap(
-            addr,
-            len,
-            PROT_NONE,
-            MAP_PRIVATE | MAP_ANONYMOUS | MAP_FIXED,
-            -1,
-            0,
-        )
-    }
-
-    //------------------------------------FLOCK SYSCALL------------------------------------
+        let count =
+            interface::rust_min(buf.len() as u64, limit.saturating_sub(position as u64)) as usize;
+        let count = match self._grow_for_write(normalfile_inode_obj, position, count, syscallname) {
+            Ok(count) => count,
+            Err(e) => return e,
+        };
 
-    pub fn flock_syscall(&self, fd: i32, operation: i32) -> i32 {
-        let checkedfd = self.get_filedescriptor(fd).unwrap();
-        let unlocked_fd = checkedfd.read();
-        if let Some(filedesc_enum) = &*unlocked_fd {
-            let lock = match filedesc_enum {
-                File(normalfile_filedesc_obj) => &normalfile_filedesc_obj.advlock,
-                Socket(socket_filedesc_obj) => &socket_filedesc_obj.advlock,
-                Stream(stream_filedesc_obj) => &stream_filedesc_obj.advlock,
-                Pipe(pipe_filedesc_obj) => &pipe_filedesc_obj.advlock,
-                Epoll(epoll_filedesc_obj) => &epoll_filedesc_obj.advlock,
-            };
-            match operation & (LOCK_SH | LOCK_EX | LOCK_UN) {
-                LOCK_SH => {
-                    if operation & LOCK_NB == LOCK_NB {
-                        //EAGAIN and EWOULDBLOCK are the same
-                        if !lock.try_lock_sh() {
-                            return syscall_error(
-                                Errno::EAGAIN,
-                                "flock",
-                                "shared lock would block",
-                            );
-                        };
-                    } else {
-                        lock.lock_sh();
-                    }
-                }
-                LOCK_EX => {
-                    if operation & LOCK_NB == LOCK_NB {
-                        if !lock.try_lock_ex() {
-                            return syscall_error(
-                                Errno::EAGAIN,
-                                "flock",
-                                "exclusive lock would block",
-                            );
-                        };
-                    } else {
-                        lock.lock_ex();
-                    }
-                }
-                LOCK_UN => {
-                    if operation & LOCK_NB == LOCK_NB {
-                        lock.unlock();
-                    } else {
-                        lock.unlock();
-                    }
-                }
-                _ => {
-                    return syscall_error(Errno::EINVAL, "flock", "unknown operation");
-                }
+        let mut fileobject = FILEOBJECTTABLE.get_mut(&inodenum).unwrap();
+        if position > filesize {
+            let blankbytecount = position - filesize;
+            if fileobject.zerofill_at(filesize, blankbytecount).unwrap() != blankbytecount {
+                panic!("Write of blank bytes for {} failed!", syscallname);
             }
-            0 //flock has  succeeded!
-        } else {
-            syscall_error(Errno::EBADF, "flock", "invalid file descriptor")
         }
+        let retval = fileobject.writeat(buf.as_ptr(), count, position).unwrap();
+        let newposition = position + retval;
+        let time = interface::timestamp_nanos();
+        normalfile_inode_obj.mtime = time;
+        normalfile_inode_obj.ctime = time;
+        if newposition > filesize {
+            normalfile_inode_obj.size = newposition;
+            drop(fileobject);
+            drop(inodeobj);
+            log_metadata(&FS_METADATA, inodenum);
+        }
+        retval as i32
     }
 
     /// ### Description
     ///
     /// The `remove_from_parent_dir()` is a helper function used by a couple
-    /// of syscalls to remove a file from its parent directory's inode. It
-    /// ensures that the parent directory has the appropriate permissions
-    /// before removing the file entry and updating the parent directory's
-    /// metadata.
+    /// of syscalls to remove a file from its parent directory's inode,
+    /// removing the file entry and updating the parent directory's metadata.
+    /// It does not know who is asking, so the syscalls check that the caller
+    /// may remove the entry beforehand, see `_remove_check()`.
     ///
     /// ### Arguments
     ///
@@ -4648,14 +8810,7 @@ impl Cage {
     ///
     /// ### Returns
     ///
-    /// Upon successful completion, zero is returned. In case of a failure, an
-    /// error is returned, and `errno` is set depending on the error, e.g.,
-    /// EPERM.
-    ///
-    /// ### Errors
-    ///
-    /// Currently, the following error is supported:
-    /// * `EPERM` - the parent directory does not have write permission.
+    /// Zero, as the entry is always removed.
     ///
     /// ### Panics
     ///
@@ -4669,15 +8824,6 @@ impl Cage {
         if let Inode::Dir(ref mut parent_dir) =
             *(FS_METADATA.inodetable.get_mut(&parent_inodenum).unwrap())
         {
-            // check if parent directory has write permissions
-            if parent_dir.mode as u32 & (S_IWOTH | S_IWGRP | S_IWUSR) == 0 {
-                return syscall_error(
-                    Errno::EPERM,
-                    "rmdir",
-                    "Parent directory does not have write permission",
-                );
-            }
-
             // remove entry of corresponding filename from filename-inode dict
             parent_dir
                 .filename_to_inode_dict
@@ -4685,6 +8831,10 @@ impl Cage {
                 .unwrap();
             // Decrement the link count of the parent directory
             parent_dir.linkcount -= 1;
+            // Removing an entry modifies the directory
+            let time = interface::timestamp_nanos();
+            parent_dir.mtime = time;
+            parent_dir.ctime = time;
         } else {
             // Panic if the parent inode is not a directory
             panic!("Non directory file was parent!");
@@ -4715,10 +8865,14 @@ impl Cage {
     /// * `ENOENT` - `path` is an empty string or names a nonexistent directory
     /// * `EBUSY` - `path` names a root directory that cannot be removed
     /// * `ENOEMPTY` - `path` names a non-empty directory,
-    /// * `EPERM` - the directory to be removed or its parent directory
-    /// does not allow write permission
+    /// * `EPERM` - the directory to be removed does not allow write permission,
+    ///   or the parent directory has the sticky bit set and the caller owns
+    ///   neither it nor the directory
     /// * `ENOTDIR` - `path` is not a directory
-    /// Other errors, like `EACCES`, `EINVAL`, etc. are not supported.
+    /// * `EACCES` - search permission is denied on a component of the path
+    ///   prefix, or write or search permission is denied on the parent
+    ///   directory
+    /// Other errors, like `EINVAL`, etc. are not supported.
     ///
     /// ### Panics
     /// A panic occurs when the directory to be removed does not have `S_IFDIR"`
@@ -4735,6 +8889,13 @@ impl Cage {
         //components.
         let truepath = normpath(convpath(path), self);
 
+        //Every directory leading to the one being removed has to be searchable
+        //by the caller
+        let searchcheck = self._search_check("rmdir", &truepath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
+
         //Perfrom a walk down the file tree starting from the root directory to
         //obtain an inode number of the file whose pathname was specified and
         //its parent directory's inode.
@@ -4744,19 +8905,12 @@ impl Cage {
             //which means it is a root directory that cannot be removed
             (Some(_), None) => syscall_error(Errno::EBUSY, "rmdir", "Cannot remove root directory"),
             (Some(inodenum), Some(parent_inodenum)) => {
-                //If the parent directory of the directory that shall be removed
-                //doesn't allow write permission, the removal cannot be performed
-                if let Inode::Dir(ref mut parent_dir) =
-                    *(FS_METADATA.inodetable.get_mut(&parent_inodenum).unwrap())
-                {
-                    // check if parent directory has write permissions
-                    if parent_dir.mode as u32 & (S_IWOTH | S_IWGRP | S_IWUSR) == 0 {
-                        return syscall_error(
-                            Errno::EPERM,
-                            "rmdir",
-                            "Parent directory does not have write permission",
-                        );
-                    }
+                //The caller has to be granted write and search permission on the
+                //parent directory, and to own the directory being removed or its
+                //parent if the parent is sticky
+                let removecheck = self._remove_check("rmdir", parent_inodenum, inodenum);
+                if removecheck != 0 {
+                    return removecheck;
                 }
                 //Getting a mutable reference to an inode struct that corresponds to
                 //the directory that shall be removed
@@ -4848,15 +9002,15 @@ impl Cage {
                         //Remove entry of corresponding inodenum from the filesystem
                         //inodetable
                         if remove_inode {
-                            FS_METADATA.inodetable.remove(&inodenum).unwrap();
+                            FS_METADATA.remove_inode(inodenum).unwrap();
                         }
                         //Log is used to store all the changes made to the filesystem. After
                         //the cage is closed, all the collected changes are serialized and
                         //the state of the underlying filesystem is persisted. This allows us
                         //to avoid serializing and persisting filesystem state after every
-                        //`rmdir_syscall()`.
-                        log_metadata(&FS_METADATA, parent_inodenum);
-                        log_metadata(&FS_METADATA, inodenum);
+                        //`rmdir_syscall()`. The parent and the removed directory are
+                        //logged together so that replay removes both or neither.
+                        log_metadata_group(&FS_METADATA, &[parent_inodenum, inodenum]);
                         0 // success
                     }
                     _ => syscall_error(Errno::ENOTDIR, "rmdir", "Path is not a directory"),
@@ -4865,109 +9019,592 @@ impl Cage {
         }
     }
 
-    //------------------RENAME SYSCALL------------------
+    //------------------RENAME SYSCALL------------------
+    /// ### Description
+    ///
+    /// `rename_syscall` renames a file or directory specified by `oldpath` to
+    /// `newpath`, moving it between directories if required. If `newpath`
+    /// already exists it is atomically replaced, so that there is no point at
+    /// which another cage attempting to access `newpath` will find it missing.
+    /// When a directory is moved to a new parent, its `..` entry is updated and
+    /// the linkcounts of both parents are adjusted accordingly.
+    ///
+    /// ### Arguments
+    ///
+    /// It accepts two parameters:
+    /// * `oldpath` - A string slice that specifies the current name and
+    ///   location of the file or directory.
+    /// * `newpath` - A string slice that specifies the new name and location
+    ///   for the file or directory.
+    ///
+    /// ### Returns
+    ///
+    /// For a successful call, the return value will be 0, indicating that the
+    /// rename operation was successful. On error, a negative errno is returned
+    /// to indicate the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `ENOENT` - The `oldpath` or `newpath` is null, i.e. not provided, the
+    ///   `oldpath` does not exist, or a directory component of `newpath` does
+    ///   not exist.
+    /// * `EBUSY` - Cannot rename the root directory, or replace it.
+    /// * `EINVAL` - An attempt was made to make a directory a subdirectory of
+    ///   itself.
+    /// * `EISDIR` - `newpath` is an existing directory, but `oldpath` is not a
+    ///   directory.
+    /// * `ENOTDIR` - `oldpath` is a directory, and `newpath` exists but is not
+    ///   a directory.
+    /// * `ENOTEMPTY` - `newpath` is a nonempty directory.
+    /// * `EPERM` - One of the parent directories does not allow write
+    ///   permission, or is sticky and the caller owns neither it nor the file
+    ///   being renamed or replaced.
+    /// * `EACCES` - A directory in either path prefix may not be searched, one
+    ///   of the parent directories may not be written to by the caller, or a
+    ///   directory being moved to a new parent may not be written to.
+    ///
+    /// ### Panics
+    ///
+    /// * If either parent inode is not a directory, causing code to panic.
+    ///
+    /// For more detailed description of all the commands and return values,
+    /// refer to the rename syscall man page [here](https://man7.org/linux/man-pages/man2/rename.2.html).
+
+    pub fn rename_syscall(&self, oldpath: &str, newpath: &str) -> i32 {
+        if oldpath.len() == 0 {
+            return syscall_error(Errno::ENOENT, "rename", "Old path is null");
+        }
+        if newpath.len() == 0 {
+            return syscall_error(Errno::ENOENT, "rename", "New path is null");
+        }
+
+        let true_oldpath = normpath(convpath(oldpath), self);
+        let true_newpath = normpath(convpath(newpath), self);
+
+        self._rename_helper("rename", true_oldpath, true_newpath, 0)
+    }
+
+    //------------------RENAMEAT2 SYSCALL------------------
+    /// ### Description
+    ///
+    /// `renameat2_syscall` is like `rename_syscall`, except that relative paths
+    /// are interpreted relative to the directories referred to by `olddirfd`
+    /// and `newdirfd`, see `_at_path_helper()`, and that `flags` can change
+    /// how an existing `newpath` is treated.
+    ///
+    /// ### Arguments
+    ///
+    /// It accepts five parameters:
+    /// * `olddirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `oldpath` - the file to rename.
+    /// * `newdirfd` - `AT_FDCWD` or a file descriptor open on a directory.
+    /// * `newpath` - the new name of the file.
+    /// * `flags` - 0 to behave like `rename_syscall`, or one of:
+    ///   * `RENAME_NOREPLACE` - fail instead of replacing an existing
+    ///     `newpath`.
+    ///   * `RENAME_EXCHANGE` - atomically swap `oldpath` and `newpath`, which
+    ///     both have to exist and may be of different types.
+    ///
+    /// ### Returns
+    ///
+    /// 0 on success, otherwise the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `EINVAL` - `flags` contains an unknown flag or both flags, or with
+    ///   `RENAME_EXCHANGE` either file is a directory containing the other.
+    /// * `EEXIST` - `RENAME_NOREPLACE` is given and `newpath` exists.
+    /// * `ENOENT` - `RENAME_EXCHANGE` is given and `newpath` doesn't exist.
+    /// * `EBADF`, `ENOTDIR` - see `_at_path_helper()`.
+    /// * Any error of `rename_syscall`.
+    ///
+    /// For more detailed description of all the commands and return values,
+    /// refer to the renameat2 syscall man page [here](https://man7.org/linux/man-pages/man2/renameat2.2.html).
+    pub fn renameat2_syscall(
+        &self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: u32,
+    ) -> i32 {
+        if flags & !(RENAME_NOREPLACE | RENAME_EXCHANGE) != 0
+            || flags == RENAME_NOREPLACE | RENAME_EXCHANGE
+        {
+            return syscall_error(Errno::EINVAL, "renameat2", "invalid flags");
+        }
+        let true_oldpath = match self._at_path_helper("renameat2", olddirfd, oldpath) {
+            Ok(true_oldpath) => true_oldpath,
+            Err(e) => return e,
+        };
+        let true_newpath = match self._at_path_helper("renameat2", newdirfd, newpath) {
+            Ok(true_newpath) => true_newpath,
+            Err(e) => return e,
+        };
+        self._rename_helper("renameat2", true_oldpath, true_newpath, flags)
+    }
+
+    /// ### Description
+    ///
+    /// The `_rename_helper()` is the shared implementation of
+    /// `rename_syscall()` and `renameat2_syscall()`, renaming the file at the
+    /// absolute path `true_oldpath` to `true_newpath` as described for
+    /// `rename_syscall()`, unless `flags` says otherwise:
+    /// * `RENAME_NOREPLACE` fails with `EEXIST` instead of replacing an
+    ///   existing `true_newpath`.
+    /// * `RENAME_EXCHANGE` swaps the two files, which both have to exist, see
+    ///   `_rename_exchange()`.
+    fn _rename_helper(
+        &self,
+        syscallname: &str,
+        true_oldpath: interface::RustPathBuf,
+        true_newpath: interface::RustPathBuf,
+        flags: u32,
+    ) -> i32 {
+        // Every directory leading to either path has to be searchable by the caller
+        let searchcheck = self._search_check(syscallname, &true_oldpath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
+        let searchcheck = self._search_check(syscallname, &true_newpath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
+
+        // try to get inodenum of old path and its parent
+        let (inodenum, old_parent_inodenum) = match metawalkandparent(true_oldpath.as_path()) {
+            (None, ..) => {
+                return syscall_error(Errno::ENOENT, syscallname, "Old path does not exist")
+            }
+            (Some(_), None) => {
+                return syscall_error(Errno::EBUSY, syscallname, "Cannot rename root directory")
+            }
+            (Some(inodenum), Some(parent_inodenum)) => (inodenum, parent_inodenum),
+        };
+
+        // try to get inodenum of new path (if it already exists) and its parent
+        let (target_inodenum, new_parent_inodenum) = match metawalkandparent(true_newpath.as_path())
+        {
+            (None, None) => {
+                return syscall_error(
+                    Errno::ENOENT,
+                    syscallname,
+                    "a directory component in new path does not exist",
+                )
+            }
+            (Some(_), None) => {
+                return syscall_error(Errno::EBUSY, syscallname, "Cannot replace root directory")
+            }
+            (target_inodenum, Some(parent_inodenum)) => (target_inodenum, parent_inodenum),
+        };
+
+        if flags & RENAME_NOREPLACE != 0 && target_inodenum.is_some() {
+            return syscall_error(Errno::EEXIST, syscallname, "New path already exists");
+        }
+        if flags & RENAME_EXCHANGE != 0 {
+            return match target_inodenum {
+                Some(target_inodenum) => self._rename_exchange(
+                    syscallname,
+                    (&true_oldpath, inodenum, old_parent_inodenum),
+                    (&true_newpath, target_inodenum, new_parent_inodenum),
+                ),
+                None => syscall_error(Errno::ENOENT, syscallname, "New path does not exist"),
+            };
+        }
+
+        let old_is_dir = matches!(
+            *FS_METADATA.inodetable.get(&inodenum).unwrap(),
+            Inode::Dir(_)
+        );
+
+        // A directory can't be moved beneath itself, as that would disconnect the
+        // whole subtree from the root. Symbolic links can give the new parent a path
+        // that doesn't start with the old path, so the `..` entries are followed up
+        // from the new parent instead of comparing the paths.
+        if old_is_dir && dir_is_ancestor(inodenum, new_parent_inodenum) {
+            return syscall_error(
+                Errno::EINVAL,
+                syscallname,
+                "Cannot make a directory a subdirectory of itself",
+            );
+        }
+
+        // If oldpath and newpath are existing hard links referring to the same
+        // file, rename does nothing and returns success
+        if target_inodenum == Some(inodenum) {
+            return 0;
+        }
+
+        // make sure the existing target (if any) is compatible with what we are
+        // moving over it
+        if let Some(target_inodenum) = target_inodenum {
+            match (
+                &*FS_METADATA.inodetable.get(&target_inodenum).unwrap(),
+                old_is_dir,
+            ) {
+                (Inode::Dir(target_dir), true) => {
+                    // an empty directory has a linkcount of 3, see rmdir_syscall
+                    if target_dir.linkcount > 3 {
+                        return syscall_error(
+                            Errno::ENOTEMPTY,
+                            syscallname,
+                            "New path is a nonempty directory",
+                        );
+                    }
+                }
+                (Inode::Dir(_), false) => {
+                    return syscall_error(
+                        Errno::EISDIR,
+                        syscallname,
+                        "New path is a directory but old path is not",
+                    );
+                }
+                (_, true) => {
+                    return syscall_error(
+                        Errno::ENOTDIR,
+                        syscallname,
+                        "Old path is a directory but new path is not",
+                    );
+                }
+                (_, false) => {}
+            }
+        }
+
+        // The caller has to be allowed to remove the old entry, to add an entry to the
+        // new parent, and to remove the entry being replaced, if any. The sticky bit
+        // applies to both the old name and the replaced one.
+        let removecheck = self._remove_check(syscallname, old_parent_inodenum, inodenum);
+        if removecheck != 0 {
+            return removecheck;
+        }
+        let parentcheck = self._access_check(syscallname, new_parent_inodenum, W_OK | X_OK);
+        if parentcheck != 0 {
+            return parentcheck;
+        }
+        if let Some(target_inodenum) = target_inodenum {
+            let removecheck = self._remove_check(syscallname, new_parent_inodenum, target_inodenum);
+            if removecheck != 0 {
+                return removecheck;
+            }
+        }
+        // Moving a directory to another parent rewrites its `..` entry, which needs
+        // write permission on the directory itself
+        if old_is_dir && old_parent_inodenum != new_parent_inodenum {
+            let dircheck = self._access_check(syscallname, inodenum, W_OK);
+            if dircheck != 0 {
+                return dircheck;
+            }
+        }
+
+        // the domain sockets that move along, found while their paths still resolve
+        let moved_socks = Self::_domsock_paths_beneath(inodenum);
+
+        // remove entry of old path from the old parent's filename-inode dict, this
+        // also decrements the old parent's linkcount
+        let removal_result = Self::remove_from_parent_dir(old_parent_inodenum, &true_oldpath);
+        if removal_result != 0 {
+            return removal_result;
+        }
+
+        let time = interface::timestamp_nanos();
+        let newfilename = true_newpath
+            .file_name()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        // add pair of new path and its inodenum to the new parent's filename-inode
+        // dict. If an entry was replaced the number of entries stays the same, so
+        // the linkcount of the new parent is only incremented for a fresh entry
+        if let Inode::Dir(ref mut new_parent_dir) = *(FS_METADATA
+            .inodetable
+            .get_mut(&new_parent_inodenum)
+            .unwrap())
+        {
+            if new_parent_dir
+                .filename_to_inode_dict
+                .insert(newfilename, inodenum)
+                .is_none()
+            {
+                new_parent_dir.linkcount += 1;
+            }
+            new_parent_dir.ctime = time;
+            new_parent_dir.mtime = time;
+        }
+
+        if old_parent_inodenum != new_parent_inodenum {
+            if let Inode::Dir(ref mut old_parent_dir) = *(FS_METADATA
+                .inodetable
+                .get_mut(&old_parent_inodenum)
+                .unwrap())
+            {
+                old_parent_dir.ctime = time;
+                old_parent_dir.mtime = time;
+            }
+
+            // a directory moved to a new parent needs its .. entry to point there
+            if let Inode::Dir(ref mut moved_dir) =
+                *(FS_METADATA.inodetable.get_mut(&inodenum).unwrap())
+            {
+                moved_dir
+                    .filename_to_inode_dict
+                    .insert("..".to_string(), new_parent_inodenum);
+            }
+        }
+        // as on Linux, being renamed counts as a change of the file's status
+        mark_changed(
+            &mut FS_METADATA.inodetable.get_mut(&inodenum).unwrap(),
+            time,
+        );
+
+        // drop the link of the inode that was replaced by the rename, if any
+        if let Some(target_inodenum) = target_inodenum {
+            Self::_rename_release_target(target_inodenum, &true_newpath);
+        }
+
+        // domain sockets are tracked by their path, so those need to follow the rename
+        // as well, including any that live beneath a renamed directory
+        Self::_domsock_paths_moved(moved_socks, &[(inodenum, &true_newpath)]);
+
+        // log every inode touched by the rename as one transaction, so that
+        // replaying the log never applies half of a rename
+        let mut touched = vec![old_parent_inodenum];
+        if old_parent_inodenum != new_parent_inodenum {
+            touched.push(new_parent_inodenum);
+        }
+        touched.push(inodenum);
+        if let Some(target_inodenum) = target_inodenum {
+            touched.push(target_inodenum);
+        }
+        log_metadata_group(&FS_METADATA, &touched);
+        0 // success
+    }
+
     /// ### Description
     ///
-    /// `rename_syscall` renames a file or directory specified by `oldpath` to
-    /// `newpath`.
+    /// The `_rename_exchange()` is a helper function used by
+    /// `_rename_helper()` for `RENAME_EXCHANGE`, atomically swapping two
+    /// existing files. Each directory entry ends up referring to the inode
+    /// the other one referred to, so both parents keep their number of
+    /// entries. A directory that ends up in another parent has its `..` entry
+    /// pointed there, and domain sockets beneath either path follow the swap.
     ///
     /// ### Arguments
     ///
-    /// It accepts two parameters:
-    /// * `oldpath` - A string slice that specifies the current name and
-    ///   location of the file or directory.
-    /// * `newpath` - A string slice that specifies the new name and location
-    ///   for the file or directory.
+    /// The `_rename_exchange()` accepts three arguments:
+    /// * `syscallname` - the name of the calling syscall, used in errors.
+    /// * `old` - the absolute path, inode number and parent inode number of the
+    ///   first file.
+    /// * `new` - the same for the second file.
     ///
     /// ### Returns
     ///
-    /// For a successful call, the return value will be 0, indicating that the
-    /// rename operation was successful. On error, a negative errno is returned
-    /// to indicate the error.
+    /// Zero if the files were swapped, otherwise the error below.
     ///
     /// ### Errors
     ///
-    /// * `ENOENT` - The `oldpath` or `newpath` is null, i.e. not provided.
-    /// * `EEXIST` - The `oldpath` does not exist.
-    /// * `EBUSY` - Cannot rename the root directory.
-    /// * `EOPNOTSUPP` - Cannot move the file or directory to another directory.
-    ///
-    /// ### Panics
-    ///
-    /// * There are no known panics in this function.
-    ///
-    /// For more detailed description of all the commands and return values,
-    /// refer to the rename syscall man page [here](https://man7.org/linux/man-pages/man2/rename.2.html).
-
-    pub fn rename_syscall(&self, oldpath: &str, newpath: &str) -> i32 {
-        if oldpath.len() == 0 {
-            return syscall_error(Errno::ENOENT, "rename", "Old path is null");
+    /// * `EINVAL` - either file is a directory containing the other.
+    /// * `EACCES`, `EPERM` - the caller may not remove either entry, see
+    ///   `_remove_check()`, or may not write to a directory that is moved to
+    ///   another parent.
+    fn _rename_exchange(
+        &self,
+        syscallname: &str,
+        old: (&interface::RustPathBuf, usize, usize),
+        new: (&interface::RustPathBuf, usize, usize),
+    ) -> i32 {
+        let (true_oldpath, inodenum, old_parent_inodenum) = old;
+        let (true_newpath, target_inodenum, new_parent_inodenum) = new;
+        if inodenum == target_inodenum {
+            return 0;
         }
-        if newpath.len() == 0 {
-            return syscall_error(Errno::ENOENT, "rename", "New path is null");
+        // neither file may be a directory containing the other, which is only seen
+        // by walking up from the parents as paths can go through symbolic links
+        if dir_is_ancestor(inodenum, new_parent_inodenum)
+            || dir_is_ancestor(target_inodenum, old_parent_inodenum)
+        {
+            return syscall_error(
+                Errno::EINVAL,
+                syscallname,
+                "Cannot exchange a directory with a file beneath it",
+            );
         }
 
-        let true_oldpath = normpath(convpath(oldpath), self);
-        let true_newpath = normpath(convpath(newpath), self);
-
-        // try to get inodenum of old path and its parent
-        match metawalkandparent(true_oldpath.as_path()) {
-            (None, ..) => syscall_error(Errno::EEXIST, "rename", "Old path does not exist"),
-            (Some(_), None) => {
-                syscall_error(Errno::EBUSY, "rename", "Cannot rename root directory")
+        for (parent_inodenum, inodenum) in [
+            (old_parent_inodenum, inodenum),
+            (new_parent_inodenum, target_inodenum),
+        ] {
+            let removecheck = self._remove_check(syscallname, parent_inodenum, inodenum);
+            if removecheck != 0 {
+                return removecheck;
             }
-            (Some(inodenum), Some(parent_inodenum)) => {
-                // make sure file is not moved to another dir
-                // get inodenum for parent of new path
-                let (_, new_par_inodenum) = metawalkandparent(true_newpath.as_path());
-
-                // BUG: the rename according to the spec supports moving of files from one
-                // parent to another, so the below behavior is not as per spec
-
-                // check if old and new paths share parent
-                // if they don't, then that is not allowed
-                if new_par_inodenum != Some(parent_inodenum) {
-                    return syscall_error(
-                        Errno::EOPNOTSUPP,
-                        "rename",
-                        "Cannot move file to another directory",
-                    );
+            if old_parent_inodenum != new_parent_inodenum
+                && matches!(
+                    *FS_METADATA.inodetable.get(&inodenum).unwrap(),
+                    Inode::Dir(_)
+                )
+            {
+                let dircheck = self._access_check(syscallname, inodenum, W_OK);
+                if dircheck != 0 {
+                    return dircheck;
                 }
+            }
+        }
+
+        // domain sockets beneath either file swap places with it
+        let mut moved_socks = Self::_domsock_paths_beneath(inodenum);
+        moved_socks.extend(Self::_domsock_paths_beneath(target_inodenum));
+
+        let time = interface::timestamp_nanos();
+        let filename =
+            |path: &interface::RustPathBuf| path.file_name().unwrap().to_str().unwrap().to_string();
+        for (parent_inodenum, entryname, entryinode) in [
+            (old_parent_inodenum, filename(true_oldpath), target_inodenum),
+            (new_parent_inodenum, filename(true_newpath), inodenum),
+        ] {
+            if let Inode::Dir(ref mut parent_dir) =
+                *FS_METADATA.inodetable.get_mut(&parent_inodenum).unwrap()
+            {
+                parent_dir
+                    .filename_to_inode_dict
+                    .insert(entryname, entryinode);
+                parent_dir.ctime = time;
+                parent_dir.mtime = time;
+            } else {
+                panic!("Non directory file was parent!");
+            }
+        }
+        for (moved_inodenum, parent_inodenum) in [
+            (inodenum, new_parent_inodenum),
+            (target_inodenum, old_parent_inodenum),
+        ] {
+            let mut inodeobj = FS_METADATA.inodetable.get_mut(&moved_inodenum).unwrap();
+            if let Inode::Dir(ref mut moved_dir) = *inodeobj {
+                moved_dir
+                    .filename_to_inode_dict
+                    .insert("..".to_string(), parent_inodenum);
+            }
+            mark_changed(&mut inodeobj, time);
+        }
 
-                // get parent directory inode object
-                let pardir_inodeobj = FS_METADATA.inodetable.get_mut(&parent_inodenum).unwrap();
+        Self::_domsock_paths_moved(
+            moved_socks,
+            &[(inodenum, true_newpath), (target_inodenum, true_oldpath)],
+        );
 
-                if let Inode::Dir(parent_dir) = &*pardir_inodeobj {
-                    // add pair of new path and its inodenum to filename-inode dict
-                    parent_dir.filename_to_inode_dict.insert(
-                        true_newpath
-                            .file_name()
-                            .unwrap()
-                            .to_str()
-                            .unwrap()
-                            .to_string(),
-                        inodenum,
-                    );
+        let mut touched = vec![old_parent_inodenum];
+        if old_parent_inodenum != new_parent_inodenum {
+            touched.push(new_parent_inodenum);
+        }
+        touched.extend_from_slice(&[inodenum, target_inodenum]);
+        log_metadata_group(&FS_METADATA, &touched);
+        0 // success
+    }
 
-                    // remove entry of old path from filename-inode dict
-                    parent_dir.filename_to_inode_dict.remove(
-                        &true_oldpath
-                            .file_name()
-                            .unwrap()
-                            .to_str()
-                            .unwrap()
-                            .to_string(),
-                    );
+    // Finds the bound domain sockets that are the file `inodenum` or live beneath
+    // it, going by the inodes their paths resolve to rather than by the paths,
+    // as symbolic links can give a socket a path that shares no prefix with the
+    // path being renamed. Returns each path with the inodes of the socket and
+    // its parent
+    fn _domsock_paths_beneath(inodenum: usize) -> Vec<(interface::RustPathBuf, usize, usize)> {
+        let sockpaths: Vec<interface::RustPathBuf> = NET_METADATA
+            .domsock_paths
+            .iter()
+            .map(|sockpath| sockpath.clone())
+            .collect();
+        sockpaths
+            .into_iter()
+            .filter_map(|sockpath| match metawalkandparent(&sockpath) {
+                (Some(sockinode), Some(parent))
+                    if sockinode == inodenum || dir_is_ancestor(inodenum, parent) =>
+                {
+                    Some((sockpath, sockinode, parent))
+                }
+                _ => None,
+            })
+            .collect()
+    }
 
-                    // drop the ref to the parent dir inode object
-                    drop(pardir_inodeobj);
+    // Replaces the paths of domain sockets found by `_domsock_paths_beneath()` once
+    // the rename is done. A socket that was renamed itself takes its new path from
+    // `renamed`, and one beneath a renamed directory gets the path its parent has
+    // now, so every socket ends up under a path that resolves to it
+    fn _domsock_paths_moved(
+        moved: Vec<(interface::RustPathBuf, usize, usize)>,
+        renamed: &[(usize, &interface::RustPathBuf)],
+    ) {
+        let mut newpaths = vec![];
+        for (sockpath, sockinode, parent) in moved {
+            let newpath = match renamed.iter().find(|(inodenum, _)| *inodenum == sockinode) {
+                Some((_, newpath)) => Some(unanchoredpath(newpath)),
+                None => dirpathfrominodenum(parent)
+                    .and_then(|dirpath| Some(dirpath.join(sockpath.file_name()?))),
+            };
+            NET_METADATA.domsock_paths.remove(&sockpath);
+            newpaths.push(newpath.unwrap_or(sockpath));
+        }
+        for newpath in newpaths {
+            NET_METADATA.domsock_paths.insert(newpath);
+        }
+    }
 
-                    // log and update metadata
-                    log_metadata(&FS_METADATA, parent_inodenum);
+    /// ### Description
+    ///
+    /// The `_rename_release_target()` is a helper function used by
+    /// `rename_syscall()` to drop the directory entry of an inode that was
+    /// replaced by a rename. Its linkcount is decremented, and if this was
+    /// the last link and no file descriptors refer to it, the inode is removed
+    /// from the filesystem along with its backing data file. Directories that
+    /// are still open are marked with a linkcount of 2 so that
+    /// `close_syscall()` removes them once their last descriptor is closed.
+    ///
+    /// ### Arguments
+    ///
+    /// * `target_inodenum` - the inode number of the replaced file
+    /// * `truepath` - the absolute path the replaced file was reachable from
+    fn _rename_release_target(target_inodenum: usize, truepath: &interface::RustPathBuf) {
+        let mut inodeobj = FS_METADATA.inodetable.get_mut(&target_inodenum).unwrap();
+        let (remove_inode, has_fobj) = match *inodeobj {
+            Inode::File(ref mut f) => {
+                f.linkcount -= 1;
+                (f.linkcount == 0 && f.refcount == 0, true)
+            }
+            Inode::CharDev(ref mut f) => {
+                f.linkcount -= 1;
+                (f.linkcount == 0 && f.refcount == 0, false)
+            }
+            Inode::Socket(ref mut f) => {
+                f.linkcount -= 1;
+                NET_METADATA.domsock_paths.remove(&unanchoredpath(truepath));
+                (f.linkcount == 0 && f.refcount == 0, false)
+            }
+            Inode::Symlink(ref mut f) => {
+                f.linkcount -= 1;
+                (f.linkcount == 0, false)
+            }
+            Inode::Fifo(ref mut f) => {
+                f.linkcount -= 1;
+                (f.linkcount == 0 && f.refcount == 0, false)
+            }
+            Inode::Dir(ref mut d) => {
+                // the replaced directory was checked to be empty by the caller
+                if d.refcount != 0 {
+                    d.linkcount = 2;
                 }
-                NET_METADATA.domsock_paths.insert(true_newpath);
-                NET_METADATA.domsock_paths.remove(&true_oldpath);
-                0 // success
+                (d.refcount == 0, false)
+            }
+        };
+        mark_changed(&mut inodeobj, interface::timestamp_nanos());
+        drop(inodeobj);
+
+        if remove_inode {
+            FS_METADATA.remove_inode(target_inodenum);
+            if has_fobj {
+                let sysfilename = format!("{}{}", FILEDATAPREFIX, target_inodenum);
+                interface::storage_backend()
+                    .remove_data(&sysfilename)
+                    .unwrap();
             }
         }
     }
@@ -4984,6 +9621,23 @@ impl Cage {
                 let ulength = length as usize;
                 let filesize = normalfile_inode_obj.size as usize;
 
+                // the file may not be extended beyond RLIMIT_FSIZE
+                if ulength > filesize && ulength as u64 > self.get_rlimit(RLIMIT_FSIZE).rlim_cur {
+                    return self._file_too_large("truncate");
+                }
+
+                // growing the file needs room for all of the new bytes, while
+                // shrinking it frees the bytes that are cut off
+                let owner = normalfile_inode_obj.uid;
+                if ulength > filesize {
+                    let growth = ulength - filesize;
+                    if let Err(e) = self._reserve_bytes(owner, growth, growth, "truncate") {
+                        return e;
+                    }
+                } else {
+                    FS_METADATA.release_space(owner, (filesize - ulength) as u64, 0);
+                }
+
                 // get file object table with write lock
                 let mut maybe_fileobject = FILEOBJECTTABLE.entry(inodenum);
                 let mut tempbind;
@@ -5004,7 +9658,9 @@ impl Cage {
                     panic!("Somehow a normal file with an fd was truncated but there was no file object in rustposix?");
                 } else {
                     let sysfilename = format!("{}{}", FILEDATAPREFIX, inodenum);
-                    tempbind = interface::openfile(sysfilename, filesize).unwrap(); // open file with size given from inode
+                    tempbind = interface::storage_backend()
+                        .open_data(&sysfilename, filesize)
+                        .unwrap(); // open file with size given from inode
                     close_on_exit = true;
                     &mut tempbind
                 };
@@ -5033,6 +9689,10 @@ impl Cage {
                 drop(maybe_fileobject);
 
                 normalfile_inode_obj.size = ulength;
+                // truncating modifies the file, even if its size stays the same
+                let time = interface::timestamp_nanos();
+                normalfile_inode_obj.mtime = time;
+                normalfile_inode_obj.ctime = time;
 
                 drop(inodeobj);
                 log_metadata(&FS_METADATA, inodenum);
@@ -5048,6 +9708,12 @@ impl Cage {
                 "truncate",
                 "The named file is a domain socket",
             ),
+            Inode::Symlink(_) => syscall_error(
+                Errno::EINVAL,
+                "truncate",
+                "The named file is a symbolic link",
+            ),
+            Inode::Fifo(_) => syscall_error(Errno::EINVAL, "truncate", "The named file is a FIFO"),
             Inode::Dir(_) => {
                 syscall_error(Errno::EISDIR, "truncate", "The named file is a directory")
             }
@@ -5218,6 +9884,264 @@ impl Cage {
         }
     }
 
+    /// ## ------------------FALLOCATE SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `fallocate_syscall()` manipulates the space of the range of `len`
+    /// bytes at `offset` in the regular file referred to by `fd`, without
+    /// writing any data. The backing file on the host is manipulated the same
+    /// way, so a preallocated file takes up its space up front and a sparse
+    /// file stays sparse.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `fallocate_syscall()` receives four arguments:
+    /// * `fd` - the regular file, which must be open for writing.
+    /// * `mode` - what to do with the range:
+    ///   * 0 allocates the range, growing the file if it ends past the end of
+    ///     the file. The new part of the file reads as zeroes.
+    ///   * `FALLOC_FL_KEEP_SIZE` allocates the range without changing the size
+    ///     of the file, even if it ends past the end of the file.
+    ///   * `FALLOC_FL_PUNCH_HOLE | FALLOC_FL_KEEP_SIZE` deallocates the range,
+    ///     which then reads as zeroes, leaving the size of the file as it is.
+    ///   * `FALLOC_FL_ZERO_RANGE` zeroes the range, growing the file like mode
+    ///     0 unless it is combined with `FALLOC_FL_KEEP_SIZE`.
+    /// * `offset` - where the range starts.
+    /// * `len` - the length of the range.
+    ///
+    /// ### Returns
+    ///
+    /// 0 on success, otherwise the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `EBADF` - `fd` is not a valid file descriptor, or is not open for
+    ///   writing.
+    /// * `EINVAL` - `offset` is negative, `len` is not positive, or
+    ///   `FALLOC_FL_PUNCH_HOLE` is combined with `FALLOC_FL_ZERO_RANGE`.
+    /// * `EOPNOTSUPP` - `mode` is not one of the above, including
+    ///   `FALLOC_FL_PUNCH_HOLE` without `FALLOC_FL_KEEP_SIZE`.
+    /// * `EISDIR` - `fd` refers to a directory.
+    /// * `ESPIPE` - `fd` refers to a pipe.
+    /// * `ENODEV` - `fd` refers to anything else that isn't a regular file.
+    /// * `EFBIG` - the file would grow past RLIMIT_FSIZE or the largest
+    ///   possible file.
+    /// * `ENOSPC`, `EDQUOT` - the growth of the file doesn't fit in the file
+    ///   system or the quota of its owner.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [fallocate(2)](https://man7.org/linux/man-pages/man2/fallocate.2.html)
+    pub fn fallocate_syscall(&self, fd: i32, mode: i32, offset: isize, len: isize) -> i32 {
+        if offset < 0 || len <= 0 {
+            return syscall_error(
+                Errno::EINVAL,
+                "fallocate",
+                "offset is negative or len is not positive",
+            );
+        }
+        let keep_size = mode & FALLOC_FL_KEEP_SIZE != 0;
+        match mode & !FALLOC_FL_KEEP_SIZE {
+            0 | FALLOC_FL_ZERO_RANGE => {}
+            FALLOC_FL_PUNCH_HOLE if keep_size => {}
+            FALLOC_FL_PUNCH_HOLE => {
+                return syscall_error(
+                    Errno::EOPNOTSUPP,
+                    "fallocate",
+                    "FALLOC_FL_PUNCH_HOLE must be combined with FALLOC_FL_KEEP_SIZE",
+                );
+            }
+            m if m == FALLOC_FL_PUNCH_HOLE | FALLOC_FL_ZERO_RANGE => {
+                return syscall_error(
+                    Errno::EINVAL,
+                    "fallocate",
+                    "FALLOC_FL_PUNCH_HOLE can't be combined with FALLOC_FL_ZERO_RANGE",
+                );
+            }
+            _ => {
+                return syscall_error(Errno::EOPNOTSUPP, "fallocate", "mode is not supported");
+            }
+        }
+        let end = match offset.checked_add(len) {
+            Some(end) => end as usize,
+            None => return self._file_too_large("fallocate"),
+        };
+
+        let checkedfd = match self.get_filedescriptor(fd) {
+            Ok(checkedfd) => checkedfd,
+            Err(_) => return syscall_error(Errno::EBADF, "fallocate", "invalid file descriptor"),
+        };
+        let unlocked_fd = checkedfd.read();
+        let inodenum = match &*unlocked_fd {
+            Some(File(normalfile_filedesc_obj)) => {
+                if is_rdonly(normalfile_filedesc_obj.flags) {
+                    return syscall_error(
+                        Errno::EBADF,
+                        "fallocate",
+                        "specified file not open for writing",
+                    );
+                }
+                normalfile_filedesc_obj.inode
+            }
+            Some(Pipe(_)) => {
+                return syscall_error(Errno::ESPIPE, "fallocate", "fd refers to a pipe");
+            }
+            Some(_) => {
+                return syscall_error(
+                    Errno::ENODEV,
+                    "fallocate",
+                    "fd does not refer to a regular file",
+                );
+            }
+            None => return syscall_error(Errno::EBADF, "fallocate", "invalid file descriptor"),
+        };
+
+        let mut inodeobj = FS_METADATA.inodetable.get_mut(&inodenum).unwrap();
+        let normalfile_inode_obj = match *inodeobj {
+            Inode::File(ref mut normalfile_inode_obj) => normalfile_inode_obj,
+            Inode::Dir(_) => {
+                return syscall_error(Errno::EISDIR, "fallocate", "fd refers to a directory");
+            }
+            _ => {
+                return syscall_error(
+                    Errno::ENODEV,
+                    "fallocate",
+                    "fd does not refer to a regular file",
+                );
+            }
+        };
+        let filesize = normalfile_inode_obj.size;
+
+        // growing the file is limited by RLIMIT_FSIZE and needs room for all of
+        // the new bytes, just like for truncate
+        let growth = if keep_size {
+            0
+        } else {
+            end.saturating_sub(filesize)
+        };
+        if growth > 0 {
+            if end as u64 > self.get_rlimit(RLIMIT_FSIZE).rlim_cur {
+                return self._file_too_large("fallocate");
+            }
+            if let Err(e) =
+                self._reserve_bytes(normalfile_inode_obj.uid, growth, growth, "fallocate")
+            {
+                return e;
+            }
+        }
+
+        let mut fileobject = FILEOBJECTTABLE.get_mut(&inodenum).unwrap();
+        if let Err(err) = fileobject.fallocate(mode, offset as usize, len as usize) {
+            FS_METADATA.release_space(normalfile_inode_obj.uid, growth as u64, 0);
+            let errno = err
+                .raw_os_error()
+                .and_then(|errno| Errno::from_discriminant(errno).ok())
+                .unwrap_or(Errno::EIO);
+            return syscall_error(
+                errno,
+                "fallocate",
+                "the space of the backing file could not be changed",
+            );
+        }
+        drop(fileobject);
+
+        // plain preallocation leaves the contents as they are, unlike
+        // growing the file or zeroing part of it
+        if growth > 0 || mode & !FALLOC_FL_KEEP_SIZE != 0 {
+            let time = interface::timestamp_nanos();
+            normalfile_inode_obj.mtime = time;
+            normalfile_inode_obj.ctime = time;
+        }
+        if growth > 0 {
+            normalfile_inode_obj.size = end;
+            drop(inodeobj);
+            log_metadata(&FS_METADATA, inodenum);
+        }
+        0
+    }
+
+    /// ## ------------------POSIX_FADVISE SYSCALL------------------
+    /// ### Description
+    ///
+    /// The `posix_fadvise_syscall()` announces how the range of `len` bytes
+    /// at `offset` in the file referred to by `fd` is going to be accessed.
+    /// For a regular file the advice is passed on to the backing file on the
+    /// host, whose page cache holds the data of the file. It is ignored for
+    /// anything else that can be advised about.
+    ///
+    /// ### Function Arguments
+    ///
+    /// The `posix_fadvise_syscall()` receives four arguments:
+    /// * `fd` - the file the advice is about.
+    /// * `offset` - where the range starts.
+    /// * `len` - the length of the range, with 0 extending it to the end of the
+    ///   file.
+    /// * `advice` - one of `POSIX_FADV_NORMAL`, `POSIX_FADV_RANDOM`,
+    ///   `POSIX_FADV_SEQUENTIAL`, `POSIX_FADV_WILLNEED`, `POSIX_FADV_DONTNEED`
+    ///   or `POSIX_FADV_NOREUSE`.
+    ///
+    /// ### Returns
+    ///
+    /// 0 on success, otherwise the error.
+    ///
+    /// ### Errors
+    ///
+    /// * `EBADF` - `fd` is not a valid file descriptor.
+    /// * `EINVAL` - `advice` is not valid, `len` is negative, or `fd` refers to
+    ///   something that has no data to advise about, such as a socket.
+    /// * `ESPIPE` - `fd` refers to a pipe.
+    ///
+    /// For more detailed description of all the commands and return values, see
+    /// [posix_fadvise(2)](https://man7.org/linux/man-pages/man2/posix_fadvise.2.html)
+    pub fn posix_fadvise_syscall(&self, fd: i32, offset: isize, len: isize, advice: i32) -> i32 {
+        let checkedfd = match self.get_filedescriptor(fd) {
+            Ok(checkedfd) => checkedfd,
+            Err(_) => {
+                return syscall_error(Errno::EBADF, "posix_fadvise", "invalid file descriptor")
+            }
+        };
+        let unlocked_fd = checkedfd.read();
+        let inodenum = match &*unlocked_fd {
+            Some(File(normalfile_filedesc_obj)) => normalfile_filedesc_obj.inode,
+            Some(Pipe(_)) => {
+                return syscall_error(Errno::ESPIPE, "posix_fadvise", "fd refers to a pipe");
+            }
+            Some(_) => {
+                return syscall_error(
+                    Errno::EINVAL,
+                    "posix_fadvise",
+                    "fd refers to something that has no data to advise about",
+                );
+            }
+            None => return syscall_error(Errno::EBADF, "posix_fadvise", "invalid file descriptor"),
+        };
+        if !(POSIX_FADV_NORMAL..=POSIX_FADV_NOREUSE).contains(&advice) || len < 0 {
+            return syscall_error(
+                Errno::EINVAL,
+                "posix_fadvise",
+                "advice is not valid or len is negative",
+            );
+        }
+
+        let inodeobj = FS_METADATA.inodetable.get(&inodenum).unwrap();
+        if let Inode::File(_) = &*inodeobj {
+            // a negative offset can't hold any data, so there's nothing to
+            // pass the advice on for
+            if offset >= 0 {
+                let fileobject = FILEOBJECTTABLE.get(&inodenum).unwrap();
+                let ret = fileobject.fadvise(offset as usize, len as usize, advice);
+                if ret != 0 {
+                    let errno = Errno::from_discriminant(ret).unwrap_or(Errno::EINVAL);
+                    return syscall_error(
+                        errno,
+                        "posix_fadvise",
+                        "the advice could not be passed on to the backing file",
+                    );
+                }
+            }
+        }
+        0
+    }
+
     //------------------FTRUNCATE SYSCALL------------------
 
     pub fn ftruncate_syscall(&self, fd: i32, length: isize) -> i32 {
@@ -5361,6 +10285,7 @@ impl Cage {
                 // fd
                 flags: accflag | actualflags,
                 advlock: interface::RustRfc::new(interface::AdvisoryLock::new()),
+                fifoinode: None,
             }));
 
             // now lets return the fd numbers in the pipefd array
@@ -5378,6 +10303,140 @@ impl Cage {
         0 // success
     }
 
+    /// ### Description
+    ///
+    /// The `eventfd_syscall()` creates an eventfd, a 64 bit counter that can
+    /// be used as a lightweight wait/notify mechanism between threads and
+    /// cages. Writing 8 bytes to the descriptor adds the value they hold to
+    /// the counter, and reading 8 bytes returns the value of the counter and
+    /// resets it to zero, or returns 1 and takes one from it with
+    /// EFD_SEMAPHORE. A read blocks while the counter is zero and a write
+    /// blocks while it has no room for the value, unless the descriptor is
+    /// nonblocking. The descriptor is readable for select, poll and epoll
+    /// while the counter is nonzero, and writable while at least one can be
+    /// added to it.
+    ///
+    /// ### Arguments
+    ///
+    /// The `eventfd_syscall()` accepts two arguments:
+    /// * `initval` - The value the counter starts out at.
+    /// * `flags` - Any of EFD_CLOEXEC, EFD_NONBLOCK and EFD_SEMAPHORE.
+    ///
+    /// ### Returns
+    ///
+    /// Upon successful completion, the new file descriptor is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * `EINVAL` - `flags` has an unsupported flag set
+    /// * `ENFILE` - no available file descriptors
+    ///
+    /// ### Panics
+    ///
+    /// A panic can occur if there is no lock on the file descriptor index,
+    /// which should not be possible
+    ///
+    /// To learn more about the syscall, flags, and error values, see
+    /// [eventfd(2)](https://man7.org/linux/man-pages/man2/eventfd.2.html)
+    pub fn eventfd_syscall(&self, initval: u32, flags: i32) -> i32 {
+        if flags & !(EFD_CLOEXEC | EFD_NONBLOCK | EFD_SEMAPHORE) != 0 {
+            return syscall_error(Errno::EINVAL, "eventfd", "invalid flags");
+        }
+
+        let eventfd = interface::RustRfc::new(interface::EmulatedEventFd::new(
+            initval as u64,
+            flags & EFD_SEMAPHORE != 0,
+        ));
+        let (fd, guardopt) = self.get_next_fd(None);
+        if fd < 0 {
+            return fd;
+        }
+        let fdoption = &mut *guardopt.unwrap();
+        let _insertval = fdoption.insert(EventFd(EventFdDesc {
+            eventfd,
+            // an eventfd is open for both reading and writing
+            flags: O_RDWR | (flags & (EFD_CLOEXEC | EFD_NONBLOCK)),
+            advlock: interface::RustRfc::new(interface::AdvisoryLock::new()),
+        }));
+
+        fd
+    }
+
+    // Reads the counter of the eventfd into buf, which has to hold 8 bytes. A
+    // blocking read is retried until the counter is nonzero, checking for
+    // cancellation in between
+    fn _read_eventfd(
+        &self,
+        eventfd: &interface::EmulatedEventFd,
+        nonblocking: bool,
+        buf: *mut u8,
+        count: usize,
+    ) -> i32 {
+        if count < 8 {
+            return syscall_error(
+                Errno::EINVAL,
+                "read",
+                "an eventfd has to be read 8 bytes at a time",
+            );
+        }
+        loop {
+            match eventfd.read(nonblocking) {
+                Ok(value) => {
+                    let bytes = value.to_ne_bytes();
+                    unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), buf, 8) };
+                    return 8;
+                }
+                Err(e) if e == -(Errno::EAGAIN as i32) && !nonblocking => {
+                    if self
+                        .cancelstatus
+                        .load(interface::RustAtomicOrdering::Relaxed)
+                    {
+                        loop {
+                            interface::cancelpoint(self.cageid);
+                        }
+                    }
+                }
+                Err(e) => return e,
+            }
+        }
+    }
+
+    // Adds the value held by the 8 bytes in buf to the counter of the eventfd,
+    // retrying a blocking write until there is room for it
+    fn _write_eventfd(
+        &self,
+        eventfd: &interface::EmulatedEventFd,
+        nonblocking: bool,
+        buf: *const u8,
+        count: usize,
+    ) -> i32 {
+        if count < 8 {
+            return syscall_error(
+                Errno::EINVAL,
+                "write",
+                "an eventfd has to be written 8 bytes at a time",
+            );
+        }
+        let mut bytes = [0u8; 8];
+        unsafe { std::ptr::copy_nonoverlapping(buf, bytes.as_mut_ptr(), 8) };
+        let value = u64::from_ne_bytes(bytes);
+        loop {
+            let ret = eventfd.write(value, nonblocking);
+            if ret == -(Errno::EAGAIN as i32) && !nonblocking {
+                if self
+                    .cancelstatus
+                    .load(interface::RustAtomicOrdering::Relaxed)
+                {
+                    loop {
+                        interface::cancelpoint(self.cageid);
+                    }
+                }
+                continue;
+            }
+            return if ret < 0 { ret } else { 8 };
+        }
+    }
+
     //------------------GETDENTS SYSCALL------------------
     /// ## `getdents_syscall`
     ///
@@ -17,8 +17,39 @@ pub const ROOTDIRECTORYINODE: usize = 1;
 pub const STREAMINODE: usize = 2; // Dummy value
 pub const PIPEINODE: usize = 0xfeef0000; // Dummy value
 pub const EPOLLINODE: usize = 0xfeef0000; // Dummy value
+pub const EVENTFDINODE: usize = 0xfeef0000; // Dummy value
+pub const TIMERFDINODE: usize = 0xfeef0000; // Dummy value
+pub const SIGNALFDINODE: usize = 0xfeef0000; // Dummy value
 
 pub const PIPE_CAPACITY: usize = 65536;
+// Pipe sizes are a power of two number of pages
+pub const PAGE_SIZE: usize = 4096;
+// Largest size F_SETPIPE_SZ lets anyone but root give a pipe
+pub const PIPE_MAX_SIZE: usize = 1048576;
+// Largest size F_SETPIPE_SZ gives a pipe at all, so that the rounded up size
+// still fits in what fcntl returns
+pub const PIPE_HARD_MAX_SIZE: usize = 1 << 30;
+
+// Flags for splice/tee/vmsplice
+pub const SPLICE_F_MOVE: u32 = 0x1;
+pub const SPLICE_F_NONBLOCK: u32 = 0x2;
+pub const SPLICE_F_MORE: u32 = 0x4;
+pub const SPLICE_F_GIFT: u32 = 0x8;
+pub const SPLICE_F_ALL: u32 = SPLICE_F_MOVE | SPLICE_F_NONBLOCK | SPLICE_F_MORE | SPLICE_F_GIFT;
+
+// Most bytes sendfile and copy_file_range copy between two files at a time
+pub const COPY_CHUNK_SIZE: usize = 65536;
+
+// Most buffers a single readv/writev family call may be given
+pub const IOV_MAX: i32 = 1024;
+
+// Flags for preadv2/pwritev2
+pub const RWF_HIPRI: i32 = 0x1;
+pub const RWF_DSYNC: i32 = 0x2;
+pub const RWF_SYNC: i32 = 0x4;
+pub const RWF_NOWAIT: i32 = 0x8;
+pub const RWF_APPEND: i32 = 0x10;
+pub const RWF_SUPPORTED: i32 = RWF_HIPRI | RWF_DSYNC | RWF_SYNC | RWF_NOWAIT | RWF_APPEND;
 
 pub const F_OK: u32 = 0;
 pub const X_OK: u32 = 1;
@@ -40,6 +71,21 @@ pub const O_NONBLOCK: i32 = 0o4000;
 pub const O_SYNC: i32 = 0o10000;
 // O_FSYNC=O_SYNC
 pub const O_ASYNC: i32 = 0o20000;
+pub const O_NOFOLLOW: i32 = 0o400000;
+
+// dirfd and flags for the *at family of syscalls
+pub const AT_FDCWD: i32 = -100;
+pub const AT_SYMLINK_NOFOLLOW: i32 = 0x100;
+pub const AT_REMOVEDIR: i32 = 0x200;
+pub const AT_EACCESS: i32 = 0x200;
+pub const AT_SYMLINK_FOLLOW: i32 = 0x400;
+pub const AT_NO_AUTOMOUNT: i32 = 0x800;
+pub const AT_EMPTY_PATH: i32 = 0x1000;
+
+// flags for renameat2
+pub const RENAME_NOREPLACE: u32 = 1;
+pub const RENAME_EXCHANGE: u32 = 2;
+
 pub const O_CLOEXEC: i32 = 0o2000000;
 
 pub const DEFAULTTIME: u64 = 1323630836;
@@ -78,10 +124,14 @@ pub const F_GETSIG: i32 = 11;
 pub const F_SETLEASE: i32 = 1024;
 pub const F_GETLEASE: i32 = 1025;
 pub const F_NOTIFY: i32 = 1026;
+pub const F_SETPIPE_SZ: i32 = 1031;
+pub const F_GETPIPE_SZ: i32 = 1032;
 
 //Commands for IOCTL
 pub const FIONBIO: u32 = 21537;
 pub const FIOASYNC: u32 = 21586;
+// _IOW(0x94, 9, int): clone the file given as the argument into fd
+pub const FICLONE: u32 = 1074041865;
 
 //File types for open/stat etc.
 pub const S_IFBLK: i32 = 0o60000;
@@ -93,6 +143,17 @@ pub const S_IFREG: i32 = 0o100000;
 pub const S_IFSOCK: i32 = 0o140000;
 pub const S_FILETYPEFLAGS: i32 = 0o170000;
 
+pub const S_ISUID: u32 = 0o4000;
+pub const S_ISGID: u32 = 0o2000;
+pub const S_ISVTX: u32 = 0o1000;
+
+// Special tv_nsec values for utimensat and futimens
+pub const UTIME_NOW: i64 = (1 << 30) - 1;
+pub const UTIME_OMIT: i64 = (1 << 30) - 2;
+// Access times are only updated on reads if they are older than this many
+// nanoseconds (one day), or older than the last modification
+pub const RELATIME_INTERVAL: u64 = 24 * 60 * 60 * 1_000_000_000;
+
 //for flock syscall
 pub const LOCK_SH: i32 = 1;
 pub const LOCK_EX: i32 = 2;
@@ -114,6 +175,19 @@ pub const PROT_EXEC: i32 = 4;
 pub const SEEK_SET: i32 = 0;
 pub const SEEK_CUR: i32 = 1;
 pub const SEEK_END: i32 = 2;
+pub const SEEK_DATA: i32 = 3;
+pub const SEEK_HOLE: i32 = 4;
+
+pub const FALLOC_FL_KEEP_SIZE: i32 = 0x01;
+pub const FALLOC_FL_PUNCH_HOLE: i32 = 0x02;
+pub const FALLOC_FL_ZERO_RANGE: i32 = 0x10;
+
+pub const POSIX_FADV_NORMAL: i32 = 0;
+pub const POSIX_FADV_RANDOM: i32 = 1;
+pub const POSIX_FADV_SEQUENTIAL: i32 = 2;
+pub const POSIX_FADV_WILLNEED: i32 = 3;
+pub const POSIX_FADV_DONTNEED: i32 = 4;
+pub const POSIX_FADV_NOREUSE: i32 = 5;
 
 pub const IPC_PRIVATE: i32 = 0o0;
 pub const IPC_CREAT: i32 = 0o1000;
@@ -154,6 +228,21 @@ pub const RANDOMDEVNO: DevNo = DevNo { major: 1, minor: 8 };
 pub const URANDOMDEVNO: DevNo = DevNo { major: 1, minor: 9 };
 
 pub const FILEDATAPREFIX: &str = "linddata.";
+// Snapshots keep the data of files and their metadata under this prefix
+pub const SNAPSHOTPREFIX: &str = "lindsnap.";
+pub const MAXSNAPSHOTNAME: usize = 64;
+
+// Number of symbolic links followed while resolving a path before giving up
+// with ELOOP, matching the Linux limit
+pub const MAXSYMLINKS: usize = 40;
+
+// Capacity given to a newly formatted file system, bounding how much of the
+// host disk the linddata.* files can take up. It can be changed afterwards with
+// lind_fs_utils and is persisted with the rest of the metadata
+pub const DEFAULT_CAPACITY_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+pub const DEFAULT_CAPACITY_INODES: u64 = 1024 * 1024;
+// Block size that statfs reports the capacity and usage in
+pub const FS_BLOCKSIZE: u64 = 4096;
 
 pub fn is_reg(mode: u32) -> bool {
     (mode as i32 & S_FILETYPEFLAGS) == S_IFREG
@@ -167,6 +256,14 @@ pub fn is_dir(mode: u32) -> bool {
     (mode as i32 & S_FILETYPEFLAGS) == S_IFDIR
 }
 
+pub fn is_lnk(mode: u32) -> bool {
+    (mode as i32 & S_FILETYPEFLAGS) == S_IFLNK
+}
+
+pub fn is_fifo(mode: u32) -> bool {
+    (mode as i32 & S_FILETYPEFLAGS) == S_IFIFO
+}
+
 pub fn is_wronly(flags: i32) -> bool {
     (flags & O_RDWRFLAGS) == O_WRONLY
 }
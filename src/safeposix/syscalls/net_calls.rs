@@ -26,6 +26,8 @@
 //! - [recv_common](crate::safeposix::cage::Cage::recv_common)
 //! - [recvfrom_syscall](crate::safeposix::cage::Cage::recvfrom_syscall)
 //! - [recv_syscall](crate::safeposix::cage::Cage::recv_syscall)
+//! - [sendmsg_syscall](crate::safeposix::cage::Cage::sendmsg_syscall)
+//! - [recvmsg_syscall](crate::safeposix::cage::Cage::recvmsg_syscall)
 //! - [listen_syscall](crate::safeposix::cage::Cage::listen_syscall)
 //! - [netshutdown_syscall](crate::safeposix::cage::Cage::netshutdown_syscall)
 //! - [_cleanup_socket_inner_helper](crate::safeposix::cage::Cage::_cleanup_socket_inner_helper)
@@ -59,6 +61,30 @@ use crate::interface::errnos::{syscall_error, Errno};
 use crate::safeposix::cage::{FileDescriptor::*, *};
 use crate::safeposix::filesystem::*;
 use crate::safeposix::net::*;
+use std::os::unix::ffi::OsStringExt;
+
+//The bytes read, peer address, whether SO_PASSCRED is set, the ancillary data
+//received and the ancillary data discarded by one read of a Unix domain socket
+type UnixRecvResult = (
+    usize,
+    Option<interface::GenSockaddr>,
+    bool,
+    Option<UnixControlMsg>,
+    Vec<UnixControlMsg>,
+);
+
+//A message taken off the queue of a Unix datagram or seqpacket socket, and the
+//address it came from
+type UnixMsgRecvResult = (
+    interface::PipeMsg<UnixDatagram>,
+    Option<interface::GenSockaddr>,
+);
+
+//How long epoll_wait blocks at most before checking for signals and
+//cancellation, and how often it looks at file descriptors that can't tell it
+//when they change, in milliseconds
+const EPOLL_WAIT_SLICE_MS: i32 = 100;
+const EPOLL_POLL_INTERVAL_MS: i32 = 1;
 
 impl Cage {
     //Initializes a socket file descriptor and sets the necessary flags
@@ -165,10 +191,11 @@ impl Cage {
     /// * `domain`: The communication domain for the socket. Supported values
     ///   are `PF_INET` (Internet Protocol) and `PF_UNIX` (Unix domain sockets).
     /// * `socktype`: The socket type. Supported values are `SOCK_STREAM`
-    ///   (stream sockets) and `SOCK_DGRAM` (datagram sockets).
+    ///   (stream sockets), `SOCK_DGRAM` (datagram sockets) and, for Unix domain
+    ///   sockets only, `SOCK_SEQPACKET` (sequenced packet sockets).
     /// * `protocol`: The protocol to use for communication. This defaults to
     ///   TCP for stream sockets (`SOCK_STREAM`) and UDP for datagram sockets
-    ///   (`SOCK_DGRAM`).
+    ///   (`SOCK_DGRAM`). `SOCK_SEQPACKET` sockets take no protocol.
     ///
     /// ### Returns
     /// * The new file descriptor representing the socket on success.
@@ -300,6 +327,35 @@ impl Cage {
                 }
             }
 
+            SOCK_SEQPACKET => {
+                //SOCK_SEQPACKET is only implemented for Unix domain sockets, where it has no
+                // protocol. It is connection based, so we store it as TCP to have it connect,
+                // listen and accept like a stream socket does
+                if protocol != 0 {
+                    return syscall_error(
+                        Errno::EOPNOTSUPP,
+                        "socket",
+                        "SOCK_SEQPACKET has no protocol to choose from.",
+                    );
+                }
+                if domain != PF_UNIX {
+                    return syscall_error(
+                        Errno::EOPNOTSUPP,
+                        "socket",
+                        "SOCK_SEQPACKET is only implemented for Unix domain sockets",
+                    );
+                }
+                let sockfdobj = self._socket_initializer(
+                    domain,
+                    socktype,
+                    IPPROTO_TCP,
+                    nonblocking,
+                    cloexec,
+                    ConnState::NOTCONNECTED,
+                );
+                return self._socket_inserter(Socket(sockfdobj));
+            }
+
             _ => {
                 return syscall_error(
                     Errno::EOPNOTSUPP,
@@ -465,6 +521,10 @@ impl Cage {
         newsockaddr: &mut interface::GenSockaddr,
     ) -> i32 {
         // Unix Sockets
+        //Abstract addresses don't exist in the filesystem
+        if let Some(name) = newsockaddr.abstract_name() {
+            return Self::bind_inner_socket_unix_abstract(sockhandle, name);
+        }
         let path = newsockaddr.path();
         //Check that path is not empty
         if path.len() == 0 {
@@ -473,6 +533,13 @@ impl Cage {
         //true path is normalized path of the path to a unix socket
         let truepath = normpath(convpath(path), self);
 
+        //As for any other file created, every directory leading to the socket file
+        //has to be searchable by the caller
+        let searchcheck = self._search_check("bind", &truepath);
+        if searchcheck != 0 {
+            return searchcheck;
+        }
+
         //returns tuple consisting of inode number of file (if it exists), and
         //inode number of parent (if it exists)
         match metawalkandparent(truepath.as_path()) {
@@ -484,6 +551,21 @@ impl Cage {
             (None, Some(pardirinode)) => {
                 let filename = truepath.file_name().unwrap().to_str().unwrap().to_string(); //for now we assume this is sane, but maybe this should be checked later
 
+                //adding the socket file to the parent directory needs write and search
+                //permission on it
+                let parentcheck = self._access_check("bind", pardirinode, W_OK | X_OK);
+                if parentcheck != 0 {
+                    return parentcheck;
+                }
+
+                //the socket file takes up an inode like any other file, and belongs to
+                //the caller
+                let (euid, egid) = self.get_effective_ids();
+                let reserved = self._reserve_inode(euid, "bind");
+                if reserved != 0 {
+                    return reserved;
+                }
+
                 //this may end up skipping an inode number in the case of ENOTDIR, but that's
                 // not catastrophic FS_METADATA contains information about the
                 // file system
@@ -506,12 +588,12 @@ impl Cage {
                     //Add file type constant of a socket
                     let effective_mode = S_IFSOCK as u32 | mode;
 
-                    let time = interface::timestamp(); //We do a real timestamp now
-                                                       //Create a new inode for the file of the socket
+                    let time = interface::timestamp_nanos(); //We do a real timestamp now
+                                                             //Create a new inode for the file of the socket
                     newinode = Inode::Socket(SocketInode {
                         size: 0,
-                        uid: DEFAULT_UID,
-                        gid: DEFAULT_GID,
+                        uid: euid,
+                        gid: egid,
                         mode: effective_mode,
                         linkcount: 1,
                         refcount: 1,
@@ -526,8 +608,12 @@ impl Cage {
                     dir.filename_to_inode_dict
                         .insert(filename.clone(), newinodenum);
                     dir.linkcount += 1;
+                    //Adding the entry modifies the directory
+                    dir.ctime = time;
+                    dir.mtime = time;
                 } else {
                     //Parent dictory inode does not exist in inode table of file system
+                    FS_METADATA.release_space(euid, 0, 1);
                     return syscall_error(
                         Errno::ENOTDIR,
                         "bind",
@@ -544,9 +630,27 @@ impl Cage {
                     mode: S_IFSOCK | 0o666,
                     sendpipe: None,
                     receivepipe: None,
-                    inode: newinodenum,
+                    sendcontrol: None,
+                    receivecontrol: None,
+                    sendmsgpipe: None,
+                    receivemsgpipe: None,
+                    inode: Some(newinodenum),
+                    _abstract_name: None,
                 });
 
+                //A datagram socket can be sent to as soon as it is bound, so this is
+                //where its receive queue is set up. Other sockets find it through
+                //the inode of the socket file, which stays the same across renames
+                if sockhandle.socktype & 0x7 == SOCK_DGRAM {
+                    let receivequeue = interface::RustRfc::new(
+                        interface::EmulatedMsgPipe::new_with_capacity(UDSOCK_CAPACITY),
+                    );
+                    NET_METADATA
+                        .domsock_dgram_table
+                        .insert(newinodenum, receivequeue.clone());
+                    sockhandle.unix_info.as_mut().unwrap().receivemsgpipe = Some(receivequeue);
+                }
+
                 //Insert path to socket file into a set
                 NET_METADATA.domsock_paths.insert(truepath);
                 //Insert the file inode num and inode as key-value pair into
@@ -563,6 +667,42 @@ impl Cage {
         0
     }
 
+    //bind_syscall implementation for an abstract unix domain address. The name is
+    //taken in a table of its own rather than in the filesystem, so no inode is
+    //created, and the socket gives it back once it is gone. A datagram socket's
+    //receive queue is found through the same table
+    fn bind_inner_socket_unix_abstract(sockhandle: &mut SocketHandle, name: &[u8]) -> i32 {
+        let receivequeue = if sockhandle.socktype & 0x7 == SOCK_DGRAM {
+            Some(interface::RustRfc::new(
+                interface::EmulatedMsgPipe::new_with_capacity(UDSOCK_CAPACITY),
+            ))
+        } else {
+            None
+        };
+        match NET_METADATA.domsock_abstract_table.entry(name.to_vec()) {
+            interface::RustHashEntry::Occupied(_) => {
+                return syscall_error(Errno::EADDRINUSE, "bind", "Address already in use");
+            }
+            interface::RustHashEntry::Vacant(v) => {
+                v.insert(receivequeue.clone());
+            }
+        }
+        sockhandle.unix_info = Some(UnixSocketInfo {
+            mode: S_IFSOCK | 0o666,
+            sendpipe: None,
+            receivepipe: None,
+            sendcontrol: None,
+            receivecontrol: None,
+            sendmsgpipe: None,
+            receivemsgpipe: receivequeue,
+            inode: None,
+            _abstract_name: Some(UnixAbstractName {
+                name: name.to_vec(),
+            }),
+        });
+        0
+    }
+
     //bind_syscall implementation in the case that the socket's domain is INET
     //More details at https://man7.org/linux/man-pages/man7/ip.7.html
     fn bind_inner_socket_inet(
@@ -948,6 +1088,11 @@ impl Cage {
         sockfdobj: &mut SocketDesc,
         remoteaddr: &interface::GenSockaddr,
     ) -> i32 {
+        //Unix domain datagram sockets are handled by lind
+        if sockhandle.domain == AF_UNIX {
+            return self.connect_udp_unix(sockhandle, remoteaddr);
+        }
+
         //for UDP, just set the addresses and return
         //we don't need to check connection state for UDP, it's connectionless!
         sockhandle.remoteaddr = Some(remoteaddr.clone());
@@ -983,6 +1128,104 @@ impl Cage {
         };
     }
 
+    //The function sets the peer of a datagram socket with a unix address family.
+    //Datagrams sent without an address go to the socket bound at remoteaddr,
+    //whose receive queue is looked up here. The socket may be connected again
+    //later to change its peer. Like a stream socket connecting, an unbound
+    //socket is bound to a new address first, so that the peer can reply.
+    //Args: sockhandle is a mut reference to the SocketHandle of the local socket
+    //      remoteaddr is a reference to the address of the peer socket
+    //On success, zero is returned. On error, -errno is returned, and errno is
+    // set to indicate the error.
+    fn connect_udp_unix(
+        &self,
+        sockhandle: &mut SocketHandle,
+        remoteaddr: &interface::GenSockaddr,
+    ) -> i32 {
+        let peerqueue = match self._unix_dgram_lookup(remoteaddr, "connect") {
+            Ok(queue) => queue,
+            Err(e) => return e,
+        };
+        if sockhandle.localaddr.is_none() {
+            let localaddr = Self::assign_new_addr_unix(sockhandle);
+            let bindret = self.bind_inner_socket(&mut *sockhandle, &localaddr, false);
+            if bindret < 0 {
+                return bindret;
+            }
+        }
+        sockhandle.remoteaddr = Some(*remoteaddr);
+        sockhandle.unix_info.as_mut().unwrap().sendmsgpipe = Some(peerqueue);
+        0
+    }
+
+    //The key a unix domain address is known by in the domain socket tables: the
+    //normalized path of its socket file, or for an abstract address its name
+    //behind the leading null byte, which no normalized path starts with
+    fn _domsock_key(&self, addr: &interface::GenSockaddr) -> interface::RustPathBuf {
+        match addr.abstract_name() {
+            Some(name) => {
+                let mut key = vec![0];
+                key.extend_from_slice(name);
+                interface::RustPathBuf::from(std::ffi::OsString::from_vec(key))
+            }
+            None => normpath(convpath(addr.path()), self),
+        }
+    }
+
+    //Finds the receive queue of the datagram socket bound at the path of addr
+    //On error, -errno is returned, and errno is set to indicate the error:
+    // ENOENT if nothing exists at the path, ECONNREFUSED if it is not a datagram
+    // socket that can still receive
+    fn _unix_dgram_lookup(
+        &self,
+        addr: &interface::GenSockaddr,
+        syscallname: &str,
+    ) -> Result<UnixMsgPipe, i32> {
+        if let Some(name) = addr.abstract_name() {
+            return match NET_METADATA.domsock_abstract_table.get(name) {
+                Some(entry) => entry.value().clone().ok_or_else(|| {
+                    syscall_error(
+                        Errno::ECONNREFUSED,
+                        syscallname,
+                        "no datagram socket is bound to the address",
+                    )
+                }),
+                None => Err(syscall_error(
+                    Errno::ECONNREFUSED,
+                    syscallname,
+                    "no socket is bound to the abstract address",
+                )),
+            };
+        }
+        let path = addr.path();
+        if path.is_empty() {
+            return Err(syscall_error(
+                Errno::ENOENT,
+                syscallname,
+                "given path was null",
+            ));
+        }
+        let truepath = normpath(convpath(path), self);
+        let inodenum = match metawalk(truepath.as_path()) {
+            Some(inodenum) => inodenum,
+            None => {
+                return Err(syscall_error(
+                    Errno::ENOENT,
+                    syscallname,
+                    "not valid unix domain path",
+                ));
+            }
+        };
+        match NET_METADATA.domsock_dgram_table.get(&inodenum) {
+            Some(queue) => Ok(queue.clone()),
+            None => Err(syscall_error(
+                Errno::ECONNREFUSED,
+                syscallname,
+                "no datagram socket is bound to the path",
+            )),
+        }
+    }
+
     //Transmission Control Protocol (TCP) is a standard protocol on the internet
     //that ensures the reliable transmission of data between devices on a network.
     //Read more at https://www.techtarget.com/searchnetworking/definition/TCP
@@ -1046,32 +1289,31 @@ impl Cage {
             self.bind_inner_socket(&mut *sockhandle, &localaddr, false);
         }
         //Normalize the remote address to a path buffer
-        let remotepathbuf = normpath(convpath(remoteaddr.path()), self);
+        let remotepathbuf = self._domsock_key(remoteaddr);
 
         //NET_METADATA.domsock_paths is the set of all currently bound domain sockets
         //try to get and hold reference to the key-value pair, so other process can't
-        // alter it
-        let path_ref = NET_METADATA.domsock_paths.get(&remotepathbuf);
-        // if the entry doesn't exist, return an error.
-        if path_ref.is_none() {
-            return syscall_error(Errno::ENOENT, "connect", "not valid unix domain path");
-        }
-
-        let (pipe1, pipe2) = create_unix_sockpipes();
+        // alter it. Abstract addresses have no file, so they are looked up by name
+        let _path_ref = if let Some(name) = remoteaddr.abstract_name() {
+            if !NET_METADATA.domsock_abstract_table.contains_key(name) {
+                return syscall_error(
+                    Errno::ECONNREFUSED,
+                    "connect",
+                    "no socket is bound to the abstract address",
+                );
+            }
+            None
+        } else {
+            let path_ref = NET_METADATA.domsock_paths.get(&remotepathbuf);
+            // if the entry doesn't exist, return an error.
+            if path_ref.is_none() {
+                return syscall_error(Errno::ENOENT, "connect", "not valid unix domain path");
+            }
+            path_ref
+        };
 
         //Setup the socket handle with the remote address
         sockhandle.remoteaddr = Some(remoteaddr.clone());
-        sockhandle.unix_info.as_mut().unwrap().sendpipe = Some(pipe1.clone());
-        sockhandle.unix_info.as_mut().unwrap().receivepipe = Some(pipe2.clone());
-
-        //Check if the socket is set to blocking mode
-        //connvar is necessary to synchronize connect and accept
-        //as we are performing it in the user space
-        let connvar = if sockfdobj.flags & O_NONBLOCK == 0 {
-            Some(interface::RustRfc::new(ConnCondVar::new()))
-        } else {
-            None
-        };
 
         //The receive_pipe of the socket that is accepting the connection is assigned
         // the send_pipe of the socket that is requesting the connection.
@@ -1080,18 +1322,54 @@ impl Cage {
         // connection. Swapping the pipes here means that in accept sys call we
         // can use the pipes as placed in the domsock_accept_table without
         // confusion
-        let entry = DomsockTableEntry {
+        let mut entry = DomsockTableEntry {
             sockaddr: sockhandle.localaddr.unwrap().clone(),
-            receive_pipe: Some(pipe1.clone()).unwrap(),
-            send_pipe: Some(pipe2.clone()).unwrap(),
-            cond_var: connvar.clone(),
+            receive_pipe: None,
+            send_pipe: None,
+            receive_control: None,
+            send_control: None,
+            receive_msgpipe: None,
+            send_msgpipe: None,
+            cond_var: None,
+        };
+        let sockinfo = sockhandle.unix_info.as_mut().unwrap();
+        if sockhandle.socktype & 0x7 == SOCK_SEQPACKET {
+            //seqpacket sockets keep message boundaries, so they get message pipes
+            let (msgpipe1, msgpipe2) = create_unix_msgpipes();
+            sockinfo.sendmsgpipe = Some(msgpipe1.clone());
+            sockinfo.receivemsgpipe = Some(msgpipe2.clone());
+            entry.receive_msgpipe = Some(msgpipe1);
+            entry.send_msgpipe = Some(msgpipe2);
+        } else {
+            let (pipe1, pipe2) = create_unix_sockpipes();
+            let (control1, control2) = (new_control_queue(), new_control_queue());
+            sockinfo.sendpipe = Some(pipe1.clone());
+            sockinfo.receivepipe = Some(pipe2.clone());
+            sockinfo.sendcontrol = Some(control1.clone());
+            sockinfo.receivecontrol = Some(control2.clone());
+            entry.receive_pipe = Some(pipe1);
+            entry.send_pipe = Some(pipe2);
+            entry.receive_control = Some(control1);
+            entry.send_control = Some(control2);
+        }
+
+        //Check if the socket is set to blocking mode
+        //connvar is necessary to synchronize connect and accept
+        //as we are performing it in the user space
+        let connvar = if sockfdobj.flags & O_NONBLOCK == 0 {
+            Some(interface::RustRfc::new(ConnCondVar::new()))
+        } else {
+            None
         };
+        entry.cond_var = connvar.clone();
         //Access the domsock_accept_table, which keeps track of socket paths and
         //details pertaining to them: the socket address, receive and send pipes, and
         // cond_var
         NET_METADATA
             .domsock_accept_table
-            .insert(remotepathbuf, entry);
+            .insert(remotepathbuf.clone(), entry);
+        //Let the epoll instances watching the listener know about the connection
+        Self::_wake_domsock_listener(&remotepathbuf);
         // TODO: Add logics to handle nonblocking connects here
         //Update the sock handle state to indicate that it is connected
         sockhandle.state = ConnState::CONNECTED;
@@ -1353,7 +1631,10 @@ impl Cage {
     ) -> i32 {
         //if ip and port are not specified, shunt off to send
         //to check for a possible connection to another socket that may exist
-        if dest_addr.port() == 0 && dest_addr.addr().is_unspecified() {
+        if dest_addr.get_family() != AF_UNIX as u16
+            && dest_addr.port() == 0
+            && dest_addr.addr().is_unspecified()
+        {
             return self.send_syscall(fd, buf, buflen, flags);
         }
         //BUG:
@@ -1370,9 +1651,27 @@ impl Cage {
                     let sock_tmp = sockfdobj.handle.clone();
                     let mut sockhandle = sock_tmp.write();
 
-                    //If the socket's domain is UNIX, return with error as UNIX
-                    //sockets are connection based. Currently, lind
-                    //does not implement UDP sends/recvs over UNIX sockets
+                    //Unix datagram and seqpacket sockets are handled by lind,
+                    //sending the buffer as one message
+                    if sockhandle.is_unix_msg() {
+                        let nonblocking =
+                            (sockfdobj.flags & O_NONBLOCK != 0) || (flags & MSG_DONTWAIT != 0);
+                        let data = Self::_msg_data(buf, buflen);
+                        return match self._send_unix_msg(
+                            &mut sockhandle,
+                            nonblocking,
+                            data,
+                            Some(dest_addr),
+                            flags,
+                            None,
+                        ) {
+                            Ok(sent) => sent,
+                            Err((e, _)) => e,
+                        };
+                    }
+
+                    //If the socket is a UNIX stream socket, return with error as
+                    //it is connection based
                     //TODO: Check whether the socket is connected and return
                     //EISCONN or ENOTCONN accordingly.
                     if sockhandle.domain == AF_UNIX {
@@ -1603,13 +1902,30 @@ impl Cage {
                 Socket(ref mut sockfdobj) => {
                     //Grab a write guard to the socket handle
                     let sock_tmp = sockfdobj.handle.clone();
-                    let sockhandle = sock_tmp.write();
+                    let mut sockhandle = sock_tmp.write();
 
                     //Pattern match based on the domain of the socket
                     //Lind handles UNIX sockets internally,
                     //but will call send from libc for INET sockets
                     let socket_type = sockhandle.domain;
                     match socket_type {
+                        //Datagram and seqpacket sockets send the buffer as one message
+                        AF_UNIX if sockhandle.is_unix_msg() => {
+                            let nonblocking =
+                                (sockfdobj.flags & O_NONBLOCK != 0) || (flags & MSG_DONTWAIT != 0);
+                            let data = Self::_msg_data(buf, buflen);
+                            match self._send_unix_msg(
+                                &mut sockhandle,
+                                nonblocking,
+                                data,
+                                None,
+                                flags,
+                                None,
+                            ) {
+                                Ok(sent) => sent,
+                                Err((e, _)) => e,
+                            }
+                        }
                         AF_UNIX => {
                             //Pattern match based on the socket protocol
                             match sockhandle.protocol {
@@ -1820,6 +2136,140 @@ impl Cage {
         }
     }
 
+    //Views the buffer given to send as a single message. A zero length message
+    //may come with a null buffer
+    fn _msg_data<'a>(buf: *const u8, buflen: usize) -> &'a [u8] {
+        if buflen == 0 {
+            &[][..]
+        } else {
+            unsafe { std::slice::from_raw_parts(buf, buflen) }
+        }
+    }
+
+    //Helper function of the send calls for unix datagram and seqpacket sockets,
+    //which sends data as a single message along with the ancillary data in
+    //control, if any. A datagram goes to the socket bound at destaddr if one is
+    //given, and to the peer the socket is connected to otherwise. On failure the
+    //ancillary data is handed back, as in _sendmsg_unix
+    fn _send_unix_msg(
+        &self,
+        sockhandle: &mut SocketHandle,
+        nonblocking: bool,
+        data: &[u8],
+        destaddr: Option<&interface::GenSockaddr>,
+        flags: i32,
+        control: Option<UnixControlMsg>,
+    ) -> Result<i32, (i32, Option<UnixControlMsg>)> {
+        if sockhandle.socktype & 0x7 == SOCK_SEQPACKET {
+            //seqpacket sockets are connection based, so they never take an address
+            let connected = (sockhandle.state == ConnState::CONNECTED)
+                || (sockhandle.state == ConnState::CONNWRONLY);
+            if destaddr.is_some() {
+                let err = if connected {
+                    syscall_error(Errno::EISCONN, "send", "The descriptor is connected")
+                } else {
+                    syscall_error(Errno::ENOTCONN, "send", "The descriptor is not connected")
+                };
+                return Err((err, control));
+            }
+            let sendmsgpipe = match (connected, &sockhandle.unix_info) {
+                (
+                    true,
+                    Some(UnixSocketInfo {
+                        sendmsgpipe: Some(sendmsgpipe),
+                        ..
+                    }),
+                ) => sendmsgpipe.clone(),
+                _ => {
+                    return Err((
+                        syscall_error(Errno::ENOTCONN, "send", "The descriptor is not connected"),
+                        control,
+                    ));
+                }
+            };
+            let msg = UnixDatagram {
+                source: None,
+                control,
+            };
+            return match sendmsgpipe.write_msg(data, msg, nonblocking) {
+                Ok(sent) => Ok(sent as i32),
+                Err((e, unsent)) => {
+                    //a broken pipe raises SIGPIPE just like it does for stream sockets
+                    if (e == -(Errno::EPIPE as i32)) && ((flags & MSG_NOSIGNAL) == 0) {
+                        interface::lind_kill_from_id(self.cageid, SIGPIPE);
+                    }
+                    Err((e, unsent.control))
+                }
+            };
+        }
+
+        //Datagram sockets
+        let peerqueue = match destaddr {
+            Some(destaddr) => {
+                if destaddr.get_family() != AF_UNIX as u16 {
+                    return Err((
+                        syscall_error(
+                            Errno::EINVAL,
+                            "sendto",
+                            "An address with an invalid family for the given domain was specified",
+                        ),
+                        control,
+                    ));
+                }
+                match self._unix_dgram_lookup(destaddr, "sendto") {
+                    Ok(queue) => queue,
+                    Err(e) => return Err((e, control)),
+                }
+            }
+            None => match sockhandle
+                .unix_info
+                .as_ref()
+                .and_then(|sockinfo| sockinfo.sendmsgpipe.clone())
+            {
+                Some(queue) => queue,
+                None => {
+                    return Err((
+                        syscall_error(Errno::ENOTCONN, "send", "The descriptor is not connected"),
+                        control,
+                    ));
+                }
+            },
+        };
+        //Like the connect of a stream socket, sending from an unbound socket binds it
+        //to a new address, so that the receiver can reply
+        if sockhandle.localaddr.is_none() {
+            let localaddr = Self::assign_new_addr_unix(sockhandle);
+            let bindret = self.bind_inner_socket(&mut *sockhandle, &localaddr, false);
+            if bindret < 0 {
+                return Err((bindret, control));
+            }
+        }
+
+        let msg = UnixDatagram {
+            source: sockhandle.localaddr,
+            control,
+        };
+        match peerqueue.write_msg(data, msg, nonblocking) {
+            Ok(sent) => Ok(sent as i32),
+            Err((e, unsent)) if e == -(Errno::EPIPE as i32) => {
+                //The receiving socket has been closed. If it was our peer, we are no
+                //longer connected to anything
+                if destaddr.is_none() {
+                    sockhandle.unix_info.as_mut().unwrap().sendmsgpipe = None;
+                }
+                Err((
+                    syscall_error(
+                        Errno::ECONNREFUSED,
+                        "send",
+                        "the receiving socket has been closed",
+                    ),
+                    unsent.control,
+                ))
+            }
+            Err((e, unsent)) => Err((e, unsent.control)),
+        }
+    }
+
     //Helper function of recv_common, for recv and recvfrom syscalls
     fn recv_common_inner(
         &self,
@@ -1835,6 +2285,18 @@ impl Cage {
                 //Grab a write guard to the socket handle
                 let sock_tmp = sockfdobj.handle.clone();
                 let mut sockhandle = sock_tmp.write();
+                //Unix datagram and seqpacket sockets keep message boundaries,
+                //which lind handles with message pipes
+                if sockhandle.is_unix_msg() {
+                    return self.recv_common_inner_unix_msg(
+                        &mut sockhandle,
+                        sockfdobj,
+                        buf,
+                        buflen,
+                        flags,
+                        addr,
+                    );
+                }
                 //Pattern match based on the socket protocol
                 //and call the appropriate function to handle each case
                 match sockhandle.protocol {
@@ -1969,11 +2431,8 @@ impl Cage {
             //If no messages are available at the socket, the receive calls
             //wait for a message to arrive, unless the socket is nonblocking
             //(see fcntl(2)), in which case the value -1 is returned and errno
-            //is set to EAGAIN.
-            let mut nonblocking = false;
-            if sockfdobj.flags & O_NONBLOCK != 0 {
-                nonblocking = true;
-            }
+            //is set to EAGAIN. MSG_DONTWAIT makes just this call nonblocking.
+            let nonblocking = (sockfdobj.flags & O_NONBLOCK != 0) || (flags & MSG_DONTWAIT != 0);
             //we loop here so we can cancel blocking recvs, if necessary
             loop {
                 //Grab the receive pipe from the socket to read the data
@@ -1993,7 +2452,7 @@ impl Cage {
                     //a receive timeout has expired before data was received.
                     //Check for cancellation of recv call before looping back to
                     //read again
-                    if sockfdobj.flags & O_NONBLOCK == 0 && retval == -(Errno::EAGAIN as i32) {
+                    if !nonblocking && retval == -(Errno::EAGAIN as i32) {
                         if self
                             .cancelstatus
                             .load(interface::RustAtomicOrdering::Relaxed)
@@ -2203,6 +2662,134 @@ impl Cage {
         }
     }
 
+    //Helper function of recv_common_inner, for recv and recvfrom syscalls
+    //Handles unix datagram and seqpacket sockets. A message is always read whole:
+    //whatever does not fit in buf is discarded, and with MSG_TRUNC the length of
+    //the whole message is returned
+    fn recv_common_inner_unix_msg(
+        &self,
+        sockhandle: &mut interface::RustLockWriteGuard<SocketHandle>,
+        sockfdobj: &mut SocketDesc,
+        buf: *mut u8,
+        buflen: usize,
+        flags: i32,
+        addr: &mut Option<&mut interface::GenSockaddr>,
+    ) -> i32 {
+        let nonblocking = (sockfdobj.flags & O_NONBLOCK != 0) || (flags & MSG_DONTWAIT != 0);
+        let (msg, srcaddr) = match self._recv_unix_msg(sockhandle, nonblocking, flags) {
+            Ok(Some(received)) => received,
+            //the peer of a seqpacket socket is gone
+            Ok(None) => return 0,
+            Err(e) => return e,
+        };
+
+        let count = interface::rust_min(msg.data.len(), buflen);
+        if count > 0 {
+            unsafe {
+                std::ptr::copy_nonoverlapping(msg.data.as_ptr(), buf, count);
+            }
+        }
+        if let (Some(ref mut addr), Some(srcaddr)) = (addr, srcaddr) {
+            **addr = srcaddr;
+        }
+        //Descriptors passed along with the message are closed, as recv can't receive
+        //them. This has to wait until our socket is unlocked, in case one of them
+        //refers to it
+        if let Some(control) = msg.meta.control {
+            interface::RustLockWriteGuard::<SocketHandle>::unlocked(sockhandle, || {
+                self._release_rights(control.rights)
+            });
+        }
+
+        if flags & MSG_TRUNC != 0 {
+            msg.data.len() as i32
+        } else {
+            count as i32
+        }
+    }
+
+    //Takes the next message off the receive queue of a unix datagram or seqpacket
+    //socket, or with MSG_PEEK a copy of it without the ancillary data, along with
+    //the address it came from. A datagram socket that isn't bound yet is bound
+    //first, as it can't be sent to otherwise. Returns None once the peer of a
+    //seqpacket socket has closed
+    fn _recv_unix_msg(
+        &self,
+        sockhandle: &mut interface::RustLockWriteGuard<SocketHandle>,
+        nonblocking: bool,
+        flags: i32,
+    ) -> Result<Option<UnixMsgRecvResult>, i32> {
+        let seqpacket = sockhandle.socktype & 0x7 == SOCK_SEQPACKET;
+        if seqpacket
+            && (sockhandle.state != ConnState::CONNECTED)
+            && (sockhandle.state != ConnState::CONNRDONLY)
+        {
+            return Err(syscall_error(
+                Errno::ENOTCONN,
+                "recvfrom",
+                "The descriptor is not connected",
+            ));
+        }
+        if !seqpacket && sockhandle.localaddr.is_none() {
+            let localaddr = Self::assign_new_addr_unix(sockhandle);
+            let bindret = self.bind_inner_socket(&mut *sockhandle, &localaddr, false);
+            if bindret < 0 {
+                return Err(bindret);
+            }
+        }
+        let receivemsgpipe = match sockhandle
+            .unix_info
+            .as_ref()
+            .and_then(|sockinfo| sockinfo.receivemsgpipe.clone())
+        {
+            Some(receivemsgpipe) => receivemsgpipe,
+            None => return Ok(None),
+        };
+
+        //we loop here so we can cancel blocking recvs, if necessary
+        loop {
+            let received = if flags & MSG_PEEK != 0 {
+                receivemsgpipe.peek_msg(nonblocking, |msg| interface::PipeMsg {
+                    data: msg.data.clone(),
+                    meta: UnixDatagram {
+                        source: msg.meta.source,
+                        control: None,
+                    },
+                })
+            } else {
+                receivemsgpipe.read_msg(nonblocking)
+            };
+            match received {
+                //seqpacket messages come from the peer, datagrams carry their sender
+                Ok(Some(msg)) => {
+                    let srcaddr = if seqpacket {
+                        sockhandle.remoteaddr
+                    } else {
+                        msg.meta.source
+                    };
+                    return Ok(Some((msg, srcaddr)));
+                }
+                Ok(None) => return Ok(None),
+                Err(e) if !nonblocking && e == -(Errno::EAGAIN as i32) => {
+                    if self
+                        .cancelstatus
+                        .load(interface::RustAtomicOrdering::Relaxed)
+                    {
+                        // if the cancel status is set in the cage, we trap around a cancel
+                        // point until the individual thread is signaled to cancel itself
+                        loop {
+                            interface::cancelpoint(self.cageid)
+                        }
+                    }
+                    //in order to prevent deadlock, temporarily yield the lock on the
+                    //socket handle to a waiting thread, if one exists
+                    interface::RustLockWriteGuard::<SocketHandle>::bump(sockhandle);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
     //Helper function of recv_syscall and recvfrom_syscall
     pub fn recv_common(
         &self,
@@ -2259,6 +2846,9 @@ impl Cage {
     /// received, the return value is 0.
     /// * The value 0 may also be returned if the requested number of bytes
     /// to receive from a stream socket was 0.
+    /// * A message from a datagram or seqpacket socket that does not fit in
+    /// buf is cut short, and the rest of it is discarded. With MSG_TRUNC the
+    /// length of the whole message is returned instead.
     ///
     /// ### Errors
     ///
@@ -2338,6 +2928,9 @@ impl Cage {
     /// received, the return value is 0.
     /// * The value 0 may also be returned if the requested number of bytes
     /// to receive from a stream socket was 0.
+    /// * A message from a datagram or seqpacket socket that does not fit in
+    /// buf is cut short, and the rest of it is discarded. With MSG_TRUNC the
+    /// length of the whole message is returned instead.
     ///
     /// ### Errors
     ///
@@ -2383,71 +2976,838 @@ impl Cage {
 
     /// ### Description
     ///
-    /// `listen_syscall` listen for connections on a socket
+    /// `sendmsg_syscall` sends a message on a socket, gathering the data to
+    /// send from the buffers described by `msg.msg_iov`. If `msg.msg_name` is
+    /// set the message is sent to that address as with sendto, otherwise the
+    /// socket has to be connected.
+    ///
+    /// On Unix domain sockets the message may also carry ancillary data in
+    /// `msg.msg_control`, made up of SOL_SOCKET control messages:
+    /// * SCM_RIGHTS - an array of open file descriptors of the sending cage.
+    ///   The receiving cage gets new descriptors referring to the same open
+    ///   files, pipes and sockets, as if they had been dup'd across cages.
+    /// * SCM_CREDENTIALS - a `ucred` holding the pid, uid and gid of the
+    ///   sender. Unless the sender is root, these have to be its own cage id
+    ///   and either its real or effective ids.
+    ///
+    /// The ancillary data travels with the bytes of this message and is
+    /// received by the recvmsg call that reads the first of them. As with
+    /// Linux stream sockets, ancillary data sent with no bytes is dropped.
+    /// Datagram and seqpacket sockets send it as part of the message, even an
+    /// empty one.
     ///
     /// ### Arguments
     ///
-    /// it accepts two parameters:
-    /// * `sockfd` - a file descriptor that refers to a socket of type
-    ///   SOCK_STREAM Note, we do not implement sockets of type SOCK_SEQPACKET
-    /// * `backlog` - defines the maximum length to which the queue of pending
-    ///   connections for sockfd may grow.  If a connection request arrives when
-    ///   the queue is full, the client may receive an error with an indication
-    ///   of ECONNREFUSED or, if the underlying protocol supports
-    ///   retransmission, the request may be ignored so that a later reattempt
-    ///   at connection succeeds.
+    /// it accepts three parameters:
+    /// * `fd` - the file descriptor of the sending socket
+    /// * `msg` - the message header describing the data, destination address
+    ///   and ancillary data to send
+    /// * `flags` - bitwise OR of zero or more flags, as for send
     ///
     /// ### Returns
     ///
-    /// for a successful call, zero is returned. On error, -errno is
-    /// returned and errno is set to indicate the error
+    /// On success, the number of bytes sent. On error, a negative error number
+    /// is returned, with the errorno set to represent the corresponding error
     ///
     /// ### Errors
     ///
-    /// * EADDRINUSE - Another socket is already listening on the same port.
+    /// Besides the errors of send and sendto:
     ///
-    /// * EADDRINUSE - (Internet domain sockets) The socket referred to by
-    ///   sockfd had not previously been bound to an address and, upon
-    ///   attempting to bind it to an ephemeral port, it was determined that all
-    ///   port numbers in the ephemeral port range are currently in use.  See
-    ///   the discussion of /proc/sys/net/ipv4/ip_local_port_range in ip(7).
+    /// * EBADF - A descriptor passed with SCM_RIGHTS is not open.
     ///
-    /// * EBADF - The argument sockfd is not a valid file descriptor.
+    /// * EINVAL - The ancillary data is malformed, uses an unknown level or
+    ///   type, carries more than SCM_MAX_FD descriptors, or was given for a
+    ///   socket that is not a Unix domain socket.
     ///
-    /// * ENOTSOCK - The file descriptor sockfd does not refer to a socket.
+    /// * EMSGSIZE - `msg.msg_iovlen` is greater than IOV_MAX.
     ///
-    /// * EOPNOTSUPP - The socket is not of a type that supports the listen()
-    ///   operation.
+    /// * EPERM - The credentials passed with SCM_CREDENTIALS are not the
+    ///   sender's own.
     ///
     /// ### Panics
     ///
     /// * invalid or out-of-bounds file descriptor, calling unwrap() on it will
     ///   cause a panic.
-    /// * unknown errno value from socket bind sys call from libc in the case
-    ///   that the socket isn't assigned an address
-    /// * unknown errno value from socket listen sys call from libc
     ///
     /// for more detailed description of all the commands and return values, see
-    /// [listen(2)](https://linux.die.net/man/2/listen)
-    pub fn listen_syscall(&self, fd: i32, backlog: i32) -> i32 {
-        //BUG:
-        //If fd is out of range of [0,MAXFD], process will panic
-        //Otherwise, we obtain a write guard to the Option<FileDescriptor> object
-        let checkedfd = self.get_filedescriptor(fd).unwrap();
-        let mut unlocked_fd = checkedfd.write();
-        if let Some(filedesc_enum) = &mut *unlocked_fd {
-            match filedesc_enum {
-                //If the file descriptor refers to a socket
-                Socket(ref mut sockfdobj) => {
-                    //get or create the socket and bind it before listening
-                    //Gain write access to the socket handle
-                    let sock_tmp = sockfdobj.handle.clone();
-                    let mut sockhandle = sock_tmp.write();
+    /// [sendmsg(2)](https://man7.org/linux/man-pages/man2/sendmsg.2.html)
+    /// [unix(7)](https://man7.org/linux/man-pages/man7/unix.7.html)
+    pub fn sendmsg_syscall(&self, fd: i32, msg: &interface::MsgHdr, flags: i32) -> i32 {
+        if msg.msg_iovlen > IOV_MAX as usize {
+            return syscall_error(Errno::EMSGSIZE, "sendmsg", "too many iovecs were given");
+        }
+        let data = if msg.msg_iovlen == 0 {
+            vec![]
+        } else {
+            interface::concat_iovec_to_slice(msg.msg_iov, msg.msg_iovlen as i32)
+        };
 
-                    //If the given socket is already listening, return with
-                    //success
-                    match sockhandle.state {
-                        ConnState::LISTEN => {
+        let (rightsfds, sendcreds) = match self._parse_scm(msg) {
+            Ok(control) => control,
+            Err(e) => return e,
+        };
+
+        let destaddr = if !msg.msg_name.is_null() && msg.msg_namelen > 0 {
+            match interface::get_sockaddr(
+                interface::Arg {
+                    dispatch_constsockaddrstruct: msg.msg_name as *const interface::SockaddrDummy,
+                },
+                msg.msg_namelen,
+            ) {
+                Ok(addr) => Some(addr),
+                Err(e) => return e,
+            }
+        } else {
+            None
+        };
+
+        //Without ancillary data this is just a send or sendto of the gathered data
+        if rightsfds.is_empty() && !sendcreds {
+            if let Some(destaddr) = destaddr {
+                return self.sendto_syscall(fd, data.as_ptr(), data.len(), flags, &destaddr);
+            }
+            return self.send_syscall(fd, data.as_ptr(), data.len(), flags);
+        }
+
+        //Take a reference to everything being passed before locking our own socket,
+        //as one of the descriptors may well be the socket itself
+        let mut rights = Vec::with_capacity(rightsfds.len());
+        for rightsfd in rightsfds {
+            let dupd = match self.get_filedescriptor(rightsfd) {
+                Ok(checkedfd) => match &*checkedfd.read() {
+                    Some(filedesc_enum) => self._dup_filedesc(filedesc_enum),
+                    None => Err(syscall_error(
+                        Errno::EBADF,
+                        "sendmsg",
+                        "SCM_RIGHTS descriptor is not open",
+                    )),
+                },
+                Err(_) => Err(syscall_error(
+                    Errno::EBADF,
+                    "sendmsg",
+                    "SCM_RIGHTS descriptor is out of range",
+                )),
+            };
+            match dupd {
+                Ok(filedesc_enum) => rights.push(filedesc_enum),
+                Err(e) => {
+                    self._release_rights(rights);
+                    return e;
+                }
+            }
+        }
+
+        let (uid, gid) = self.get_real_ids();
+        let control = UnixControlMsg {
+            offset: 0,
+            rights,
+            sendcreds,
+            pid: self.cageid as i32,
+            uid,
+            gid,
+        };
+        //Ancillary data that could not be sent is handed back to be released
+        match self._sendmsg_unix(fd, &data, destaddr.as_ref(), flags, control) {
+            Ok(sent) => sent,
+            Err((e, unsent)) => {
+                if let Some(unsent) = unsent {
+                    self._release_rights(unsent.rights);
+                }
+                e
+            }
+        }
+    }
+
+    //Helper function of sendmsg_syscall, which writes the data and queues the
+    //ancillary data alongside it on a connected Unix domain socket, or sends
+    //both as one message on a datagram or seqpacket socket. On failure the
+    //ancillary data is handed back, so that the descriptors in it can be
+    //released once no socket is locked
+    fn _sendmsg_unix(
+        &self,
+        fd: i32,
+        data: &[u8],
+        destaddr: Option<&interface::GenSockaddr>,
+        flags: i32,
+        mut control: UnixControlMsg,
+    ) -> Result<i32, (i32, Option<UnixControlMsg>)> {
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let unlocked_fd = checkedfd.read();
+        let sockfdobj = match &*unlocked_fd {
+            Some(Socket(sockfdobj)) => sockfdobj,
+            Some(_) => {
+                return Err((
+                    syscall_error(
+                        Errno::ENOTSOCK,
+                        "sendmsg",
+                        "file descriptor refers to something other than a socket",
+                    ),
+                    Some(control),
+                ));
+            }
+            None => {
+                return Err((
+                    syscall_error(Errno::EBADF, "sendmsg", "invalid file descriptor"),
+                    Some(control),
+                ));
+            }
+        };
+        let sock_tmp = sockfdobj.handle.clone();
+        let mut sockhandle = sock_tmp.write();
+
+        if sockhandle.domain != AF_UNIX {
+            return Err((
+                syscall_error(
+                    Errno::EINVAL,
+                    "sendmsg",
+                    "ancillary data can only be sent over Unix domain sockets",
+                ),
+                Some(control),
+            ));
+        }
+        if sockhandle.is_unix_msg() {
+            let nonblocking = (sockfdobj.flags & O_NONBLOCK != 0) || (flags & MSG_DONTWAIT != 0);
+            return self._send_unix_msg(
+                &mut sockhandle,
+                nonblocking,
+                data,
+                destaddr,
+                flags,
+                Some(control),
+            );
+        }
+        if destaddr.is_some() {
+            return Err((
+                syscall_error(
+                    Errno::EISCONN,
+                    "sendmsg",
+                    "The descriptor is connection-oriented",
+                ),
+                Some(control),
+            ));
+        }
+        if (sockhandle.state != ConnState::CONNECTED) && (sockhandle.state != ConnState::CONNWRONLY)
+        {
+            return Err((
+                syscall_error(
+                    Errno::ENOTCONN,
+                    "sendmsg",
+                    "The descriptor is not connected",
+                ),
+                Some(control),
+            ));
+        }
+        let sockinfo = sockhandle.unix_info.as_ref().unwrap();
+        let (sendpipe, sendcontrol) = match (&sockinfo.sendpipe, &sockinfo.sendcontrol) {
+            (Some(sendpipe), Some(sendcontrol)) => (sendpipe.clone(), sendcontrol.clone()),
+            _ => {
+                return Err((
+                    syscall_error(Errno::ENOTCONN, "sendmsg", "sendpipe is not available"),
+                    Some(control),
+                ));
+            }
+        };
+
+        //Stream sockets drop ancillary data that comes without any bytes
+        if data.is_empty() {
+            return Err((0, Some(control)));
+        }
+
+        //The control message is queued before its bytes are written, so a reader
+        //can never get ahead of it. Only this socket handle writes to the pipe, and
+        //we hold its lock, so the offset is exactly where our first byte will land
+        let offset = sendpipe.total_written();
+        control.offset = offset;
+        sendcontrol.lock().push_back(control);
+
+        let nonblocking = (sockfdobj.flags & O_NONBLOCK != 0) || (flags & MSG_DONTWAIT != 0);
+        let retval = sendpipe.write_to_pipe(data.as_ptr(), data.len(), nonblocking);
+        if retval <= 0 {
+            //Nothing was written, so the control message can't have been received
+            let mut queue = sendcontrol.lock();
+            let unsent = match queue.back() {
+                Some(last) if last.offset == offset && sendpipe.total_written() == offset => {
+                    queue.pop_back()
+                }
+                _ => None,
+            };
+            drop(queue);
+            //a broken pipe raises SIGPIPE just like it does for send
+            if (retval == -(Errno::EPIPE as i32)) && ((flags & MSG_NOSIGNAL) == 0) {
+                interface::lind_kill_from_id(self.cageid, SIGPIPE);
+            }
+            return Err((retval, unsent));
+        }
+        Ok(retval)
+    }
+
+    //Helper function of sendmsg_syscall, which walks the control messages of msg
+    // and returns the descriptors to pass with SCM_RIGHTS, and whether
+    // SCM_CREDENTIALS was given. The credentials are checked against those of
+    // the cage here
+    fn _parse_scm(&self, msg: &interface::MsgHdr) -> Result<(Vec<i32>, bool), i32> {
+        let mut rightsfds = vec![];
+        let mut sendcreds = false;
+        if msg.msg_control.is_null() || msg.msg_controllen == 0 {
+            return Ok((rightsfds, sendcreds));
+        }
+
+        let headerlen = unsafe { libc::CMSG_LEN(0) } as usize;
+        let mut cmsgptr = unsafe { libc::CMSG_FIRSTHDR(msg) };
+        while !cmsgptr.is_null() {
+            let cmsg = unsafe { &*cmsgptr };
+            if cmsg.cmsg_len < headerlen
+                || cmsg.cmsg_len > msg.msg_controllen
+                || cmsg.cmsg_level != SOL_SOCKET
+            {
+                return Err(syscall_error(
+                    Errno::EINVAL,
+                    "sendmsg",
+                    "malformed control message",
+                ));
+            }
+            let payloadlen = cmsg.cmsg_len - headerlen;
+            let payload = unsafe { libc::CMSG_DATA(cmsgptr) };
+            match cmsg.cmsg_type {
+                SCM_RIGHTS => {
+                    let count = payloadlen / std::mem::size_of::<i32>();
+                    if rightsfds.len() + count > SCM_MAX_FD {
+                        return Err(syscall_error(
+                            Errno::EINVAL,
+                            "sendmsg",
+                            "too many descriptors passed with SCM_RIGHTS",
+                        ));
+                    }
+                    for i in 0..count {
+                        rightsfds.push(unsafe {
+                            std::ptr::read_unaligned((payload as *const i32).add(i))
+                        });
+                    }
+                }
+                SCM_CREDENTIALS => {
+                    if payloadlen != std::mem::size_of::<libc::ucred>() {
+                        return Err(syscall_error(
+                            Errno::EINVAL,
+                            "sendmsg",
+                            "SCM_CREDENTIALS payload is not a ucred",
+                        ));
+                    }
+                    let creds = unsafe { std::ptr::read_unaligned(payload as *const libc::ucred) };
+                    let (uid, gid) = self.get_real_ids();
+                    let (euid, egid) = self.get_effective_ids();
+                    //only root may claim to be someone else
+                    if euid != ROOT_UID
+                        && (creds.pid != self.cageid as i32
+                            || (creds.uid != uid && creds.uid != euid)
+                            || (creds.gid != gid && creds.gid != egid))
+                    {
+                        return Err(syscall_error(
+                            Errno::EPERM,
+                            "sendmsg",
+                            "SCM_CREDENTIALS are not those of the sender",
+                        ));
+                    }
+                    sendcreds = true;
+                }
+                _ => {
+                    return Err(syscall_error(
+                        Errno::EINVAL,
+                        "sendmsg",
+                        "unknown control message type",
+                    ));
+                }
+            }
+            cmsgptr = unsafe { libc::CMSG_NXTHDR(msg, cmsgptr) };
+        }
+        Ok((rightsfds, sendcreds))
+    }
+
+    //Releases file descriptors that were passed with SCM_RIGHTS but will never be
+    //received, just as closing them would
+    fn _release_rights(&self, rights: Vec<FileDescriptor>) {
+        for mut inflight in rights {
+            self._release_filedesc(&mut inflight);
+        }
+    }
+
+    /// ### Description
+    ///
+    /// `recvmsg_syscall` receives a message from a socket, scattering the data
+    /// into the buffers described by `msg.msg_iov`. If `msg.msg_name` is set,
+    /// the address of the sender is stored there and `msg.msg_namelen` is
+    /// updated, otherwise `msg.msg_namelen` is set to 0.
+    ///
+    /// On Unix domain sockets, a single call never reads past the bytes of
+    /// one sendmsg that carried ancillary data into the next, and the
+    /// ancillary data is stored into `msg.msg_control`:
+    /// * SCM_CREDENTIALS holds the pid, uid and gid of the sender, if the
+    ///   sender passed them or SO_PASSCRED is set on the receiving socket.
+    /// * SCM_RIGHTS holds the descriptors passed by the sender, which are
+    ///   installed in this cage at the lowest free numbers. With
+    ///   MSG_CMSG_CLOEXEC they get the close-on-exec flag.
+    ///
+    /// Datagram and seqpacket sockets receive one whole message, along with
+    /// the ancillary data sent with it. If the message does not fit in the
+    /// buffers, the rest of it is discarded and MSG_TRUNC is set in
+    /// `msg.msg_flags`.
+    ///
+    /// `msg.msg_controllen` is set to the length of the ancillary data stored.
+    /// If it did not all fit, the descriptors that did not fit are closed and
+    /// MSG_CTRUNC is set in `msg.msg_flags`. Descriptors whose data was read
+    /// by plain reads or recvs are closed without being received, and
+    /// MSG_PEEK only peeks at the data, never at the ancillary data.
+    ///
+    /// ### Arguments
+    ///
+    /// it accepts three parameters:
+    /// * `fd` - the file descriptor of the receiving socket
+    /// * `msg` - the message header describing where to store the data, source
+    ///   address and ancillary data
+    /// * `flags` - bitwise OR of zero or more flags, as for recv, and
+    ///   MSG_CMSG_CLOEXEC
+    ///
+    /// ### Returns
+    ///
+    /// On success, the number of bytes received, which is 0 once the peer has
+    /// shut down. On error, a negative error number is returned, with the
+    /// errorno set to represent the corresponding error
+    ///
+    /// ### Errors
+    ///
+    /// Besides the errors of recv and recvfrom:
+    ///
+    /// * EMSGSIZE - `msg.msg_iovlen` is greater than IOV_MAX.
+    ///
+    /// ### Panics
+    ///
+    /// * invalid or out-of-bounds file descriptor, calling unwrap() on it will
+    ///   cause a panic.
+    ///
+    /// for more detailed description of all the commands and return values, see
+    /// [recvmsg(2)](https://man7.org/linux/man-pages/man2/recvmsg.2.html)
+    /// [unix(7)](https://man7.org/linux/man-pages/man7/unix.7.html)
+    pub fn recvmsg_syscall(&self, fd: i32, msg: &mut interface::MsgHdr, flags: i32) -> i32 {
+        if msg.msg_iovlen > IOV_MAX as usize {
+            return syscall_error(Errno::EMSGSIZE, "recvmsg", "too many iovecs were given");
+        }
+        let total: usize = if msg.msg_iovlen == 0 {
+            0
+        } else {
+            interface::iovec_to_ioslice(msg.msg_iov, msg.msg_iovlen as i32)
+                .iter()
+                .map(|iov| iov.len())
+                .sum()
+        };
+        let mut data = vec![0u8; total];
+        msg.msg_flags = 0;
+
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let unlocked_fd = checkedfd.read();
+        let sockfdobj = match &*unlocked_fd {
+            Some(Socket(sockfdobj)) => sockfdobj.clone(),
+            Some(_) => {
+                return syscall_error(
+                    Errno::ENOTSOCK,
+                    "recvmsg",
+                    "file descriptor refers to something other than a socket",
+                );
+            }
+            None => return syscall_error(Errno::EBADF, "recvmsg", "invalid file descriptor"),
+        };
+        drop(unlocked_fd);
+        let sockhandle = sockfdobj.handle.read();
+        let (domain, protocol) = (sockhandle.domain, sockhandle.protocol);
+        let unixmsg = sockhandle.is_unix_msg();
+        drop(sockhandle);
+
+        //Datagram and seqpacket sockets receive a whole message at once, with the
+        //ancillary data sent along with it
+        if unixmsg {
+            let mut sockhandle = sockfdobj.handle.write();
+            let passcred = sockhandle.socket_options & (1 << SO_PASSCRED) != 0;
+            let nonblocking = (sockfdobj.flags & O_NONBLOCK != 0) || (flags & MSG_DONTWAIT != 0);
+            let received = self._recv_unix_msg(&mut sockhandle, nonblocking, flags);
+            drop(sockhandle);
+            let (received, srcaddr) = match received {
+                Ok(Some(received)) => received,
+                Ok(None) => {
+                    self._recvmsg_finish(msg, &[], None);
+                    msg.msg_controllen = 0;
+                    return 0;
+                }
+                Err(e) => return e,
+            };
+            let count = total.min(received.data.len());
+            //the rest of the message is lost
+            if count < received.data.len() {
+                msg.msg_flags |= MSG_TRUNC;
+            }
+            self._recvmsg_finish(msg, &received.data[..count], srcaddr);
+            self._store_scm(
+                msg,
+                received.meta.control,
+                passcred,
+                flags & MSG_CMSG_CLOEXEC != 0,
+            );
+            return if flags & MSG_TRUNC != 0 {
+                received.data.len() as i32
+            } else {
+                count as i32
+            };
+        }
+
+        //Everything other than a Unix domain socket, and peeking at one, is a recvfrom
+        //into our buffer with no ancillary data to go with it
+        if domain != AF_UNIX || flags & MSG_PEEK != 0 {
+            let mut srcaddr = interface::GenSockaddr::V4(interface::SockaddrV4::default());
+            let wantaddr = !msg.msg_name.is_null() && domain != AF_UNIX && protocol == IPPROTO_UDP;
+            let mut addropt = if wantaddr { Some(&mut srcaddr) } else { None };
+            let retval = self.recv_common(
+                fd,
+                data.as_mut_ptr(),
+                total,
+                flags & !MSG_CMSG_CLOEXEC,
+                &mut addropt,
+            );
+            if retval < 0 {
+                return retval;
+            }
+            self._recvmsg_finish(
+                msg,
+                &data[..retval as usize],
+                if wantaddr { Some(srcaddr) } else { None },
+            );
+            msg.msg_controllen = 0;
+            return retval;
+        }
+
+        let (retval, remoteaddr, passcred, received, unreceived) =
+            match self._recvmsg_unix(&sockfdobj, &mut data, flags) {
+                Ok(result) => result,
+                Err(e) => return e,
+            };
+        //With our socket unlocked, close what was passed but never received
+        for msg in unreceived {
+            self._release_rights(msg.rights);
+        }
+        self._recvmsg_finish(msg, &data[..retval], remoteaddr);
+        self._store_scm(msg, received, passcred, flags & MSG_CMSG_CLOEXEC != 0);
+        retval as i32
+    }
+
+    //Helper function of recvmsg_syscall, which reads from a connected Unix domain
+    //socket without crossing into the bytes of another message with ancillary
+    //data. It returns the number of bytes read, the peer address, whether
+    //SO_PASSCRED is set, the ancillary data that goes with the bytes read, and
+    //any ancillary data whose bytes were already read by plain reads
+    fn _recvmsg_unix(
+        &self,
+        sockfdobj: &SocketDesc,
+        data: &mut [u8],
+        flags: i32,
+    ) -> Result<UnixRecvResult, i32> {
+        let mut sockhandle = sockfdobj.handle.write();
+        if (sockhandle.state != ConnState::CONNECTED) && (sockhandle.state != ConnState::CONNRDONLY)
+        {
+            return Err(syscall_error(
+                Errno::ENOTCONN,
+                "recvmsg",
+                "The descriptor is not connected",
+            ));
+        }
+        let remoteaddr = sockhandle.remoteaddr;
+        let passcred = sockhandle.socket_options & (1 << SO_PASSCRED) != 0;
+        let sockinfo = sockhandle.unix_info.as_ref().unwrap();
+        let (receivepipe, receivecontrol) = match (&sockinfo.receivepipe, &sockinfo.receivecontrol)
+        {
+            (Some(receivepipe), Some(receivecontrol)) => {
+                (receivepipe.clone(), receivecontrol.clone())
+            }
+            //both ends are closed, so there is nothing left to read
+            _ => return Ok((0, remoteaddr, passcred, None, vec![])),
+        };
+        let nonblocking = (sockfdobj.flags & O_NONBLOCK != 0) || (flags & MSG_DONTWAIT != 0);
+        let mut unreceived = vec![];
+
+        //we loop here so we can cancel blocking recvs, if necessary
+        loop {
+            let mut queue = receivecontrol.lock();
+            //Bytes that were peeked have left the pipe but are still unread
+            let position = receivepipe.total_read() - sockhandle.last_peek.len() as u64;
+            //Any byte written so far has its control message queued already
+            let written = receivepipe.total_written();
+
+            //Plain reads consumed the first bytes of these, so they are never received
+            while queue.front().is_some_and(|front| front.offset < position) {
+                unreceived.push(queue.pop_front().unwrap());
+            }
+
+            let available = written - position;
+            if available > 0 || data.is_empty() {
+                let received = match queue.front() {
+                    Some(front) if front.offset == position && available > 0 => queue.pop_front(),
+                    _ => None,
+                };
+                //Stop short of the bytes of the next message with ancillary data
+                let boundary = queue
+                    .front()
+                    .map_or(written, |next| next.offset.min(written));
+                let count = data.len().min((boundary - position) as usize);
+
+                let peeked = count.min(sockhandle.last_peek.len());
+                for (byte, peekbyte) in data.iter_mut().zip(sockhandle.last_peek.drain(..peeked)) {
+                    *byte = peekbyte;
+                }
+                let mut read = peeked;
+                if count > peeked {
+                    //These bytes are known to be in the pipe, so this can't block
+                    let retval = receivepipe.read_from_pipe(
+                        data[peeked..].as_mut_ptr(),
+                        count - peeked,
+                        true,
+                    );
+                    if retval > 0 {
+                        read += retval as usize;
+                    }
+                }
+                drop(queue);
+                return Ok((read, remoteaddr, passcred, received, unreceived));
+            }
+            drop(queue);
+
+            //Nothing to read. Checking the write end is closed before checking the
+            //count again makes sure no bytes were written in between
+            if receivepipe.is_write_closed() && receivepipe.total_written() == written {
+                return Ok((0, remoteaddr, passcred, None, unreceived));
+            }
+            if nonblocking {
+                drop(sockhandle);
+                for msg in unreceived {
+                    self._release_rights(msg.rights);
+                }
+                return Err(syscall_error(
+                    Errno::EAGAIN,
+                    "recvmsg",
+                    "there is no data available right now, try again later",
+                ));
+            }
+            if self
+                .cancelstatus
+                .load(interface::RustAtomicOrdering::Relaxed)
+            {
+                // if the cancel status is set in the cage, we trap around a cancel
+                // point until the individual thread is signaled to cancel itself
+                loop {
+                    interface::cancelpoint(self.cageid)
+                }
+            }
+            //in order to prevent deadlock, temporarily yield the lock on the socket
+            //handle to a waiting thread, if one exists
+            interface::RustLockWriteGuard::<SocketHandle>::bump(&mut sockhandle);
+            interface::lind_yield();
+        }
+    }
+
+    //Helper function of recvmsg_syscall, which scatters the data into the buffers
+    //of msg and stores the source address if there is one and msg asks for it
+    fn _recvmsg_finish(
+        &self,
+        msg: &mut interface::MsgHdr,
+        data: &[u8],
+        srcaddr: Option<interface::GenSockaddr>,
+    ) {
+        if msg.msg_iovlen > 0 {
+            interface::scatter_to_iovec(msg.msg_iov, msg.msg_iovlen as i32, data);
+        }
+        match srcaddr {
+            Some(addr) if !msg.msg_name.is_null() => interface::copy_out_sockaddr(
+                interface::Arg {
+                    dispatch_sockaddrstruct: msg.msg_name as *mut interface::SockaddrDummy,
+                },
+                interface::Arg {
+                    dispatch_socklen_t_ptr: &mut msg.msg_namelen as *mut u32,
+                },
+                addr,
+            ),
+            _ => msg.msg_namelen = 0,
+        }
+    }
+
+    //Helper function of recvmsg_syscall, which stores the ancillary data that came
+    //with the bytes received into the control buffer of msg, installing passed
+    //descriptors in this cage. Whatever doesn't fit is dropped with MSG_CTRUNC
+    fn _store_scm(
+        &self,
+        msg: &mut interface::MsgHdr,
+        received: Option<UnixControlMsg>,
+        passcred: bool,
+        cloexec: bool,
+    ) {
+        let controllen = if msg.msg_control.is_null() {
+            0
+        } else {
+            msg.msg_controllen
+        };
+        msg.msg_controllen = 0;
+        let received = match received {
+            Some(received) => received,
+            None => return,
+        };
+        let mut used = 0;
+
+        //Credentials come first, as they do on Linux
+        if received.sendcreds || passcred {
+            let credlen = std::mem::size_of::<libc::ucred>() as u32;
+            let space = unsafe { libc::CMSG_SPACE(credlen) } as usize;
+            if space <= controllen {
+                let creds = libc::ucred {
+                    pid: received.pid,
+                    uid: received.uid,
+                    gid: received.gid,
+                };
+                self._put_cmsg(msg, used, SCM_CREDENTIALS, &[creds]);
+                used += space;
+            } else {
+                msg.msg_flags |= MSG_CTRUNC;
+            }
+        }
+
+        if !received.rights.is_empty() {
+            let headerlen = unsafe { libc::CMSG_LEN(0) } as usize;
+            let room = controllen.saturating_sub(used).saturating_sub(headerlen)
+                / std::mem::size_of::<i32>();
+            let mut newfds = vec![];
+            let mut rights = received.rights.into_iter();
+            for mut inflight in rights.by_ref().take(room) {
+                let (newfd, guardopt) = self.get_next_fd(None);
+                if newfd < 0 {
+                    self._release_filedesc(&mut inflight);
+                    msg.msg_flags |= MSG_CTRUNC;
+                    break;
+                }
+                if cloexec {
+                    match inflight {
+                        File(ref mut desc) => desc.flags |= O_CLOEXEC,
+                        Stream(ref mut desc) => desc.flags |= O_CLOEXEC,
+                        Socket(ref mut desc) => desc.flags |= O_CLOEXEC,
+                        Pipe(ref mut desc) => desc.flags |= O_CLOEXEC,
+                        Epoll(ref mut desc) => desc.flags |= O_CLOEXEC,
+                        EventFd(ref mut desc) => desc.flags |= O_CLOEXEC,
+                        TimerFd(ref mut desc) => desc.flags |= O_CLOEXEC,
+                        SignalFd(ref mut desc) => desc.flags |= O_CLOEXEC,
+                    }
+                }
+                let mut newfdguard = guardopt.unwrap();
+                let _insertval = newfdguard.insert(inflight);
+                newfds.push(newfd);
+            }
+            //Descriptors that don't fit in the control buffer are closed
+            let leftover: Vec<FileDescriptor> = rights.collect();
+            if !leftover.is_empty() {
+                msg.msg_flags |= MSG_CTRUNC;
+                self._release_rights(leftover);
+            }
+            if !newfds.is_empty() {
+                self._put_cmsg(msg, used, SCM_RIGHTS, &newfds);
+                used += unsafe { libc::CMSG_SPACE((newfds.len() * 4) as u32) } as usize;
+            }
+        }
+        msg.msg_controllen = used.min(controllen);
+    }
+
+    //Writes one control message with the given payload at offset bytes into the
+    //control buffer of msg, which the caller has made sure has room for it
+    fn _put_cmsg<T: Copy>(
+        &self,
+        msg: &mut interface::MsgHdr,
+        offset: usize,
+        cmsgtype: i32,
+        payload: &[T],
+    ) {
+        let payloadlen = std::mem::size_of_val(payload);
+        let cmsg = interface::CmsgHdr {
+            cmsg_len: unsafe { libc::CMSG_LEN(payloadlen as u32) } as usize,
+            cmsg_level: SOL_SOCKET,
+            cmsg_type: cmsgtype,
+        };
+        unsafe {
+            let cmsgptr = (msg.msg_control as *mut u8).add(offset) as *mut interface::CmsgHdr;
+            std::ptr::write_unaligned(cmsgptr, cmsg);
+            std::ptr::copy_nonoverlapping(
+                payload.as_ptr() as *const u8,
+                libc::CMSG_DATA(cmsgptr),
+                payloadlen,
+            );
+        }
+    }
+
+    /// ### Description
+    ///
+    /// `listen_syscall` listen for connections on a socket
+    ///
+    /// ### Arguments
+    ///
+    /// it accepts two parameters:
+    /// * `sockfd` - a file descriptor that refers to a socket of type
+    ///   SOCK_STREAM or, in the Unix domain, SOCK_SEQPACKET
+    /// * `backlog` - defines the maximum length to which the queue of pending
+    ///   connections for sockfd may grow.  If a connection request arrives when
+    ///   the queue is full, the client may receive an error with an indication
+    ///   of ECONNREFUSED or, if the underlying protocol supports
+    ///   retransmission, the request may be ignored so that a later reattempt
+    ///   at connection succeeds.
+    ///
+    /// ### Returns
+    ///
+    /// for a successful call, zero is returned. On error, -errno is
+    /// returned and errno is set to indicate the error
+    ///
+    /// ### Errors
+    ///
+    /// * EADDRINUSE - Another socket is already listening on the same port.
+    ///
+    /// * EADDRINUSE - (Internet domain sockets) The socket referred to by
+    ///   sockfd had not previously been bound to an address and, upon
+    ///   attempting to bind it to an ephemeral port, it was determined that all
+    ///   port numbers in the ephemeral port range are currently in use.  See
+    ///   the discussion of /proc/sys/net/ipv4/ip_local_port_range in ip(7).
+    ///
+    /// * EBADF - The argument sockfd is not a valid file descriptor.
+    ///
+    /// * ENOTSOCK - The file descriptor sockfd does not refer to a socket.
+    ///
+    /// * EOPNOTSUPP - The socket is not of a type that supports the listen()
+    ///   operation.
+    ///
+    /// ### Panics
+    ///
+    /// * invalid or out-of-bounds file descriptor, calling unwrap() on it will
+    ///   cause a panic.
+    /// * unknown errno value from socket bind sys call from libc in the case
+    ///   that the socket isn't assigned an address
+    /// * unknown errno value from socket listen sys call from libc
+    ///
+    /// for more detailed description of all the commands and return values, see
+    /// [listen(2)](https://linux.die.net/man/2/listen)
+    pub fn listen_syscall(&self, fd: i32, backlog: i32) -> i32 {
+        //BUG:
+        //If fd is out of range of [0,MAXFD], process will panic
+        //Otherwise, we obtain a write guard to the Option<FileDescriptor> object
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let mut unlocked_fd = checkedfd.write();
+        if let Some(filedesc_enum) = &mut *unlocked_fd {
+            match filedesc_enum {
+                //If the file descriptor refers to a socket
+                Socket(ref mut sockfdobj) => {
+                    //get or create the socket and bind it before listening
+                    //Gain write access to the socket handle
+                    let sock_tmp = sockfdobj.handle.clone();
+                    let mut sockhandle = sock_tmp.write();
+
+                    //If the given socket is already listening, return with
+                    //success
+                    match sockhandle.state {
+                        ConnState::LISTEN => {
                             return 0;
                         }
 
@@ -3037,6 +4397,10 @@ impl Cage {
                 let remote_addr: interface::GenSockaddr;
                 let sendpipenumber;
                 let receivepipenumber;
+                let sendcontrol;
+                let receivecontrol;
+                let sendmsgpipe;
+                let receivemsgpipe;
                 // We loop here to accept the connection.
                 // If we get a connection object from the accept table,
                 // we complete the connection and set up the address and pipes.
@@ -3044,8 +4408,7 @@ impl Cage {
                 // non-blocking accept where we return EAGAIN
                 loop {
                     //Normalize the path to the listening socket
-                    let localpathbuf =
-                        normpath(convpath(sockhandle.localaddr.unwrap().path()), self);
+                    let localpathbuf = self._domsock_key(&sockhandle.localaddr.unwrap());
                     //Note, NET_METADATA.domsock_accept_table stores pending
                     //connections (from client calling `connect`)
                     //Retrieve one of the pending connections if it exists
@@ -3066,8 +4429,12 @@ impl Cage {
                         //and then remove the incoming connection's socket from pending connections
                         let addr = ds.get_sockaddr().clone();
                         remote_addr = addr.clone();
-                        receivepipenumber = ds.get_receive_pipe().clone();
-                        sendpipenumber = ds.get_send_pipe().clone();
+                        receivepipenumber = ds.get_receive_pipe().cloned();
+                        sendpipenumber = ds.get_send_pipe().cloned();
+                        receivecontrol = ds.get_receive_control().cloned();
+                        sendcontrol = ds.get_send_control().cloned();
+                        receivemsgpipe = ds.get_receive_msgpipe().cloned();
+                        sendmsgpipe = ds.get_send_msgpipe().cloned();
                         drop(ds);
                         NET_METADATA.domsock_accept_table.remove(&localpathbuf);
                         break;
@@ -3089,26 +4456,36 @@ impl Cage {
                 let newsock_tmp = newsockfd.handle.clone();
                 let mut newsockhandle = newsock_tmp.write();
 
-                //Retrieve the inodenum of the incoming connection's socket
-                let pathclone = normpath(convpath(remote_addr.path()), self);
-                if let Some(inodenum) = metawalk(pathclone.as_path()) {
-                    //Insert necessary info about the socket communication
-                    newsockhandle.unix_info = Some(UnixSocketInfo {
-                        inode: inodenum.clone(),
-                        mode: sockhandle.unix_info.as_ref().unwrap().mode,
-                        sendpipe: Some(sendpipenumber.clone()),
-                        receivepipe: Some(receivepipenumber.clone()),
-                    });
-                    //Grab the incoming connection's socket inode from the inodetable
-                    //and increase the refcount by 1, as the socket is accepting
-                    //a connection. Thus, we do not want the socket to be closed
-                    //before the connection ends.
+                //Retrieve the inodenum of the incoming connection's socket, of which
+                //there is none if it is bound to an abstract address
+                let inodenum = if remote_addr.abstract_name().is_some() {
+                    None
+                } else {
+                    metawalk(normpath(convpath(remote_addr.path()), self).as_path())
+                };
+                //Insert necessary info about the socket communication
+                newsockhandle.unix_info = Some(UnixSocketInfo {
+                    inode: inodenum,
+                    mode: sockhandle.unix_info.as_ref().unwrap().mode,
+                    sendpipe: sendpipenumber,
+                    receivepipe: receivepipenumber,
+                    sendcontrol,
+                    receivecontrol,
+                    sendmsgpipe,
+                    receivemsgpipe,
+                    _abstract_name: None,
+                });
+                //Grab the incoming connection's socket inode from the inodetable
+                //and increase the refcount by 1, as the socket is accepting
+                //a connection. Thus, we do not want the socket to be closed
+                //before the connection ends.
+                if let Some(inodenum) = inodenum {
                     if let Inode::Socket(ref mut sock) =
                         *(FS_METADATA.inodetable.get_mut(&inodenum).unwrap())
                     {
                         sock.refcount += 1;
                     }
-                };
+                }
 
                 //Finalize values for the new "server" socket handle that was
                 //created to connect with the incoming connection's socket
@@ -3493,10 +4870,8 @@ impl Cage {
                                     // hasn't changed the state to INPROGRESS, so this piece of code
                                     // inside the if statement
                                     // is a dead code that would never be executed currently
-                                    let remotepathbuf = normpath(
-                                        convpath(sockhandle.remoteaddr.unwrap().path()),
-                                        self,
-                                    );
+                                    let remotepathbuf =
+                                        self._domsock_key(&sockhandle.remoteaddr.unwrap());
                                     let dsconnobj =
                                         NET_METADATA.domsock_accept_table.get(&remotepathbuf);
                                     if dsconnobj.is_none() {
@@ -3509,10 +4884,8 @@ impl Cage {
                                     // then check if there are any pending connections
 
                                     // get the path of the socket
-                                    let localpathbuf = normpath(
-                                        convpath(sockhandle.localaddr.unwrap().path()),
-                                        self,
-                                    );
+                                    let localpathbuf =
+                                        self._domsock_key(&sockhandle.localaddr.unwrap());
                                     // check if there is any connections associated with the path
                                     let dsconnobj =
                                         NET_METADATA.domsock_accept_table.get(&localpathbuf);
@@ -3522,6 +4895,17 @@ impl Cage {
                                         new_readfds.set(fd);
                                         *retval += 1;
                                     }
+                                } else if sockhandle.is_unix_msg() {
+                                    // datagram and seqpacket sockets are readable once a message
+                                    // is queued, or the peer of a seqpacket socket is gone
+                                    let receivemsgpipe = sockhandle
+                                        .unix_info
+                                        .as_ref()
+                                        .and_then(|sockinfo| sockinfo.receivemsgpipe.as_ref());
+                                    if receivemsgpipe.is_some_and(|pipe| pipe.check_select_read()) {
+                                        new_readfds.set(fd);
+                                        *retval += 1;
+                                    }
                                 } else if sockhandle.state == ConnState::CONNECTED || newconnection
                                 {
                                     // otherwise, the connection is already established
@@ -3581,6 +4965,15 @@ impl Cage {
                         }
                     }
 
+                    // check if the counter is nonzero, the timer has expired or a signal
+                    // is pending
+                    EventFd(_) | TimerFd(_) | SignalFd(_) => {
+                        if self._fd_readiness(filedesc_enum) & EPOLLIN as u32 != 0 {
+                            new_readfds.set(fd);
+                            *retval += 1;
+                        }
+                    }
+
                     // these file reads never block
                     _ => {
                         new_readfds.set(fd);
@@ -3707,6 +5100,14 @@ impl Cage {
                         }
                     }
 
+                    // only an eventfd with room left in its counter can be written to
+                    EventFd(_) | TimerFd(_) | SignalFd(_) => {
+                        if self._fd_readiness(filedesc_enum) & EPOLLOUT as u32 != 0 {
+                            new_writefds.set(fd);
+                            *retval += 1;
+                        }
+                    }
+
                     // these file writes never block
                     _ => {
                         new_writefds.set(fd);
@@ -3883,8 +5284,11 @@ impl Cage {
 
                             // SO_REUSEADDR: Indicates that the rules used in validating addresses
                             // supplied in a bind call should allow reuse of local addresses.
+
+                            // SO_PASSCRED: Enables receiving the credentials of the sending
+                            // process in an SCM_CREDENTIALS message with recvmsg.
                             SO_LINGER | SO_KEEPALIVE | SO_SNDLOWAT | SO_RCVLOWAT | SO_REUSEPORT
-                            | SO_REUSEADDR => {
+                            | SO_REUSEADDR | SO_PASSCRED => {
                                 if sockhandle.socket_options & optbit == optbit {
                                     // if the bit is set, set optval to 1
                                     *optval = 1;
@@ -4096,7 +5500,7 @@ impl Cage {
                                     &error_string,
                                 );
                             }
-                            SO_LINGER | SO_KEEPALIVE => {
+                            SO_LINGER | SO_KEEPALIVE | SO_PASSCRED => {
                                 // these socket options are stored inside socket_options
                                 // so we just modify it in socket_options
                                 // optval should always be 1 or 0.
@@ -4455,14 +5859,19 @@ impl Cage {
     ///   revents field returns zero. The field events is an input parameter, a
     ///   bit mask specifying the events the application is interested in for
     ///   the file descriptor fd. The bits returned in revents can include any
-    ///   of those specified in events, or POLLNVAL. The bits that may be
-    ///   set/returned in events and revents are: 1. POLLIN: There is data to
-    ///   read. 2. POLLPRI: There is some exceptional condition on the file
-    ///   descriptor, currently not supported 3. POLLOUT: Writing is now
-    ///   possible, though a write larger than the available space in a socket
-    ///   or pipe will still block
-    ///   4. POLLNVAL: Invalid request: fd not open (only returned in revents;
-    ///   ignored in events).
+    ///   of those specified in events, or POLLERR, POLLHUP or POLLNVAL. The
+    ///   bits that may be set/returned in events and revents are: 1. POLLIN:
+    ///   There is data to read. 2. POLLPRI: There is some exceptional condition
+    ///   on the file descriptor, currently only reported for inet sockets 3.
+    ///   POLLOUT: Writing is now possible, though a write larger than the
+    ///   available space in a socket or pipe will still block
+    ///   4. POLLRDHUP: The peer of a stream socket closed its writing half.
+    ///   5. POLLERR: Error condition, such as the read end of a pipe having
+    ///   been closed (only returned in revents; ignored in events).
+    ///   6. POLLHUP: Hang up, such as every write end of a pipe or the peer
+    ///   of a connected socket having been closed (only returned in revents;
+    ///   ignored in events). 7. POLLNVAL: Invalid request: fd not open (only
+    ///   returned in revents; ignored in events).
     /// * `timeout` - The timeout argument is a RustDuration structure that
     ///   specifies the interval that poll() should block waiting for a file
     ///   descriptor to become ready. The call will block until either: 1.  a
@@ -4478,7 +5887,6 @@ impl Cage {
     ///
     /// ### Errors
     /// * EINTR - A signal was caught.
-    /// * EINVAL - fd exceeds the FD_SET_MAX_FD.
     ///
     /// ### Panics
     /// No panic is expected from this syscall
@@ -4488,153 +5896,489 @@ impl Cage {
         timeout: Option<interface::RustDuration>,
     ) -> i32 {
         // timeout is supposed to be in milliseconds
-
-        // current implementation of poll_syscall is based on select_syscall
-        // which gives several issues:
-        // 1. according to standards, select_syscall should only support file descriptor
-        //    that is smaller than 1024, while poll_syscall should not have such
-        //    limitation but our implementation of poll_syscall is actually calling
-        //    select_syscall directly which would mean poll_syscall would also have the
-        //    1024 maximum size limitation However, rustposix itself only support file
-        //    descriptor that is smaller than 1024 which solves this issue automatically
-        //    in an interesting way
-        // 2. current implementation of poll_syscall is very inefficient, that it passes
-        //    each of the file descriptor into select_syscall one by one. A better
-        //    solution might be transforming pollstruct into fdsets and pass into
-        //    select_syscall once (TODO). A even more efficienct way would be completely
-        //    rewriting poll_syscall so it does not depend on select_syscall anymore.
-        //    This is also how Linux does for poll_syscall since Linux claims that poll
-        //    have a better performance than select.
-        // 3. several revent value such as POLLERR (which should be set when pipe is
-        //    broken), or POLLHUP (when peer closed its channel) are not possible to
-        //    monitor. Since select_syscall does not have these features, so our
-        //    poll_syscall, which derived from select_syscall, would subsequently not be
-        //    able to support these features.
-
-        let mut return_code: i32 = 0;
+        let mut return_code: i32;
         let start_time = interface::starttimer();
 
-        let end_time = match timeout {
-            Some(time) => time,
-            None => interface::RustDuration::MAX,
+        let end_time = match timeout {
+            Some(time) => time,
+            None => interface::RustDuration::MAX,
+        };
+
+        // we loop until either timeout
+        // or any of the file descriptor is ready
+        loop {
+            return_code = 0;
+            // look at every file descriptor in a single pass, computing its
+            // revents directly from the state of the object it refers to
+            for structpoll in &mut *fds {
+                // according to standard, we should clear all revents
+                structpoll.revents = 0;
+
+                // get the file descriptor
+                let fd = structpoll.fd;
+
+                // according to standard, we should ignore all file descriptor
+                // that is smaller than 0
+                if fd < 0 {
+                    continue;
+                }
+
+                // a file descriptor that is out of range or not open is
+                // reported as POLLNVAL rather than failing the whole call
+                let mask = match self.get_filedescriptor(fd) {
+                    Ok(checkedfd) => {
+                        let unlocked_fd = checkedfd.read();
+                        match &*unlocked_fd {
+                            Some(filedesc) => {
+                                // POLLERR and POLLHUP are always reported,
+                                // whether or not they were requested
+                                let wanted =
+                                    structpoll.events as u16 as u32 | (POLLERR | POLLHUP) as u32;
+                                (self._fd_readiness(filedesc) & wanted) as i16
+                            }
+                            None => POLLNVAL,
+                        }
+                    }
+                    Err(_) => POLLNVAL,
+                };
+
+                // according to standard, return value is the number of fds
+                // with non-zero revent, which may indicate an error as well
+                if mask != 0 {
+                    return_code += 1;
+                }
+                // set the revents
+                structpoll.revents = mask;
+            }
+
+            // we break if there is any file descriptor ready
+            // or timeout is reached
+            if return_code != 0 || interface::readtimer(start_time) > end_time {
+                break;
+            } else {
+                // otherwise, check for signal and loop again
+                if interface::sigcheck() {
+                    return syscall_error(Errno::EINTR, "poll", "interrupted function call");
+                }
+                // We yield to let other threads continue if we've found no ready descriptors
+                interface::lind_yield();
+            }
+        }
+        return return_code;
+    }
+
+    pub fn _epoll_object_allocator(&self) -> i32 {
+        // create the epoll instance, which is shared by every file descriptor
+        // referring to it
+        let instance = match interface::EpollInstance::new() {
+            Ok(instance) => instance,
+            Err(e) => return e,
+        };
+        let epollobjfd = Epoll(EpollDesc {
+            mode: 0000,
+            instance,
+            advlock: interface::RustRfc::new(interface::AdvisoryLock::new()),
+            errno: 0,
+            flags: 0,
+        });
+        // get a file descriptor
+        let (fd, guardopt) = self.get_next_fd(None);
+        if fd < 0 {
+            return fd;
+        }
+        let fdoption = &mut *guardopt.unwrap();
+        let _insertval = fdoption.insert(epollobjfd);
+
+        return fd;
+    }
+
+    // Tells apart what a file descriptor refers to, so that an epoll item can be
+    // dropped once its file descriptor has been closed and the number reused
+    fn _epoll_source(filedesc: &FileDescriptor) -> usize {
+        match filedesc {
+            Socket(sockfdobj) => interface::RustRfc::as_ptr(&sockfdobj.handle) as usize,
+            Pipe(pipefdobj) => interface::RustRfc::as_ptr(&pipefdobj.pipe) as usize,
+            Epoll(epollfdobj) => interface::RustRfc::as_ptr(&epollfdobj.instance) as usize,
+            EventFd(eventfdobj) => interface::RustRfc::as_ptr(&eventfdobj.eventfd) as usize,
+            TimerFd(timerfdobj) => interface::RustRfc::as_ptr(&timerfdobj.timer) as usize,
+            SignalFd(signalfdobj) => interface::RustRfc::as_ptr(&signalfdobj.mask) as usize,
+            Stream(streamfdobj) => streamfdobj.stream as usize,
+            File(normalfdobj) => normalfdobj.inode,
+        }
+    }
+
+    // Hooks the epoll item of fd up to the wait queues of whatever the file
+    // descriptor is built on, or adds its kernel socket to the host epoll
+    // instance. Returns false if that isn't possible yet, like for a socket that
+    // isn't connected, along with the kernel socket now watched if any
+    fn _epoll_subscribe(
+        &self,
+        instance: &interface::EpollInstance,
+        fd: i32,
+        filedesc: &FileDescriptor,
+        watch: &interface::RustRfc<interface::EpollWatch>,
+    ) -> (bool, i32) {
+        match filedesc {
+            Pipe(pipefdobj) => {
+                pipefdobj.pipe.waiters().register(watch);
+                (true, -1)
+            }
+            EventFd(eventfdobj) => {
+                eventfdobj.eventfd.waiters().register(watch);
+                (true, -1)
+            }
+            // nothing wakes anyone when a timer expires or a signal arrives, so these
+            // are looked at every time
+            TimerFd(_) | SignalFd(_) => (false, -1),
+            Socket(sockfdobj) if sockfdobj.domain == AF_UNIX => {
+                let sock_tmp = sockfdobj.handle.clone();
+                let sockhandle = sock_tmp.read();
+                if sockhandle.state == ConnState::LISTEN {
+                    // a listener is woken by connect
+                    let localpathbuf = self._domsock_key(&sockhandle.localaddr.unwrap());
+                    NET_METADATA
+                        .domsock_accept_waiters
+                        .entry(localpathbuf)
+                        .or_insert_with(
+                            || interface::RustRfc::new(interface::EpollWaitQueue::new()),
+                        )
+                        .register(watch);
+                    return (true, -1);
+                }
+                let sockinfo = match sockhandle.unix_info.as_ref() {
+                    Some(sockinfo) => sockinfo,
+                    None => return (false, -1),
+                };
+                if sockhandle.is_unix_msg() {
+                    if let Some(receivemsgpipe) = sockinfo.receivemsgpipe.as_ref() {
+                        receivemsgpipe.waiters().register(watch);
+                    }
+                    if let Some(sendmsgpipe) = sockinfo.sendmsgpipe.as_ref() {
+                        sendmsgpipe.waiters().register(watch);
+                    }
+                    // an unconnected datagram socket can always be written to
+                    let subscribed = sockinfo.receivemsgpipe.is_some()
+                        && (sockinfo.sendmsgpipe.is_some()
+                            || sockhandle.socktype & 0x7 == SOCK_DGRAM);
+                    (subscribed, -1)
+                } else if let (Some(receivepipe), Some(sendpipe)) =
+                    (sockinfo.receivepipe.as_ref(), sockinfo.sendpipe.as_ref())
+                {
+                    receivepipe.waiters().register(watch);
+                    sendpipe.waiters().register(watch);
+                    (true, -1)
+                } else {
+                    (false, -1)
+                }
+            }
+            Socket(sockfdobj) => {
+                // the kernel socket is only created once it is bound or connected
+                if sockfdobj.rawfd < 0 || instance.host_add(sockfdobj.rawfd, fd) < 0 {
+                    return (false, -1);
+                }
+                (true, sockfdobj.rawfd)
+            }
+            // nothing else changes what it is ready for
+            _ => (true, -1),
+        }
+    }
+
+    // Computes what a file descriptor is ready for, as EPOLL* flags, which have
+    // the same values as their POLL* counterparts
+    fn _fd_readiness(&self, filedesc: &FileDescriptor) -> u32 {
+        let mask = match filedesc {
+            Pipe(pipefdobj) => {
+                let pipe = &pipefdobj.pipe;
+                let mut mask = 0;
+                // a FIFO opened for reading and writing is both ends at once
+                if !is_wronly(pipefdobj.flags) {
+                    // the read end hangs up once every writer is gone
+                    if pipe.has_data() {
+                        mask |= EPOLLIN;
+                    }
+                    if pipe.is_write_closed() {
+                        mask |= EPOLLHUP;
+                    }
+                }
+                if !is_rdonly(pipefdobj.flags) {
+                    // writing fails once every reader is gone
+                    if pipe.check_select_write() {
+                        mask |= EPOLLOUT;
+                    }
+                    if pipe.is_read_closed() {
+                        mask |= EPOLLERR;
+                    }
+                }
+                mask
+            }
+            Socket(sockfdobj) if sockfdobj.domain == AF_UNIX => {
+                let sock_tmp = sockfdobj.handle.clone();
+                let sockhandle = sock_tmp.read();
+                self._unix_readiness(&sockhandle)
+            }
+            Socket(sockfdobj) => {
+                if sockfdobj.rawfd < 0 {
+                    0
+                } else {
+                    let events = (EPOLLIN | EPOLLOUT | EPOLLPRI | EPOLLRDHUP) as i16;
+                    interface::kernel_poll(sockfdobj.rawfd, events) as u16 as i32
+                }
+            }
+            EventFd(eventfdobj) => {
+                let mut mask = 0;
+                if eventfdobj.eventfd.is_readable() {
+                    mask |= EPOLLIN;
+                }
+                if eventfdobj.eventfd.is_writable() {
+                    mask |= EPOLLOUT;
+                }
+                mask
+            }
+            TimerFd(timerfdobj) => {
+                if timerfdobj.timer.expirations() > 0 {
+                    EPOLLIN
+                } else {
+                    0
+                }
+            }
+            SignalFd(signalfdobj) => {
+                let mask = signalfdobj
+                    .mask
+                    .load(interface::RustAtomicOrdering::Relaxed);
+                if self.pending_signals() & mask != 0 {
+                    EPOLLIN
+                } else {
+                    0
+                }
+            }
+            // like select, we don't wait on streams, so stdin is always reported
+            // readable and stdout and stderr writable
+            Stream(streamfdobj) => {
+                if streamfdobj.stream == 0 {
+                    EPOLLIN
+                } else {
+                    EPOLLOUT
+                }
+            }
+            // these never block
+            _ => EPOLLIN | EPOLLOUT,
+        };
+        mask as u32
+    }
+
+    // Computes what a unix domain socket is ready for, from its pending
+    // connections if it is listening and from its pipes otherwise
+    fn _unix_readiness(&self, sockhandle: &SocketHandle) -> i32 {
+        if sockhandle.state == ConnState::LISTEN {
+            let localpathbuf = self._domsock_key(&sockhandle.localaddr.unwrap());
+            if NET_METADATA
+                .domsock_accept_table
+                .contains_key(&localpathbuf)
+            {
+                return EPOLLIN;
+            }
+            return 0;
+        }
+        let sockinfo = match sockhandle.unix_info.as_ref() {
+            Some(sockinfo) => sockinfo,
+            None => return 0,
+        };
+
+        // whether there is anything to read, whether the peer stopped writing and
+        // reading, and whether there is room to write
+        let (readable, peer_wr_closed, peer_rd_closed, writable) = if sockhandle.is_unix_msg() {
+            if sockhandle.socktype & 0x7 == SOCK_DGRAM {
+                // a datagram socket has no peer to hang up on
+                let mut mask = 0;
+                if sockinfo
+                    .receivemsgpipe
+                    .as_ref()
+                    .is_some_and(|pipe| pipe.has_data())
+                {
+                    mask |= EPOLLIN;
+                }
+                if sockinfo
+                    .sendmsgpipe
+                    .as_ref()
+                    .is_none_or(|pipe| pipe.check_select_write())
+                {
+                    mask |= EPOLLOUT;
+                }
+                return mask;
+            }
+            match (
+                sockinfo.receivemsgpipe.as_ref(),
+                sockinfo.sendmsgpipe.as_ref(),
+            ) {
+                (Some(receivepipe), Some(sendpipe)) => (
+                    receivepipe.has_data(),
+                    receivepipe.is_write_closed(),
+                    sendpipe.is_read_closed(),
+                    sendpipe.check_select_write(),
+                ),
+                _ => return 0,
+            }
+        } else {
+            match (sockinfo.receivepipe.as_ref(), sockinfo.sendpipe.as_ref()) {
+                (Some(receivepipe), Some(sendpipe)) => (
+                    receivepipe.has_data(),
+                    receivepipe.is_write_closed(),
+                    sendpipe.is_read_closed(),
+                    sendpipe.check_select_write(),
+                ),
+                _ => return 0,
+            }
         };
 
-        // according to standard, we should clear all revents
-        for structpoll in &mut *fds {
-            structpoll.revents = 0;
+        let mut mask = 0;
+        if readable {
+            mask |= EPOLLIN;
+        }
+        // reading no longer blocks once either side has shut it down
+        if peer_wr_closed
+            || sockhandle.state == ConnState::CONNWRONLY
+            || sockhandle.state == ConnState::NOTCONNECTED
+        {
+            mask |= EPOLLIN | EPOLLRDHUP;
+        }
+        if (peer_wr_closed && peer_rd_closed) || sockhandle.state == ConnState::NOTCONNECTED {
+            mask |= EPOLLHUP;
         }
+        if writable {
+            mask |= EPOLLOUT;
+        }
+        mask
+    }
 
-        // we loop until either timeout
-        // or any of the file descriptor is ready
-        loop {
-            // iterate through each file descriptor
-            for structpoll in &mut *fds {
-                // get the file descriptor
-                let fd = structpoll.fd;
+    // Lets the epoll instances watching the unix domain socket listening on
+    // pathbuf know that a connection is pending, and forgets about the wait queue
+    // once nobody is watching anymore
+    fn _wake_domsock_listener(pathbuf: &interface::RustPathBuf) {
+        let waiters = match NET_METADATA.domsock_accept_waiters.get(pathbuf) {
+            Some(waiters) => waiters.clone(),
+            None => return,
+        };
+        if !waiters.wake() {
+            NET_METADATA
+                .domsock_accept_waiters
+                .remove_if(pathbuf, |_, w| interface::RustRfc::ptr_eq(w, &waiters));
+        }
+    }
 
-                // according to standard, we should ignore all file descriptor
-                // that is smaller than 0
-                if fd < 0 {
-                    continue;
-                }
+    // Removes the epoll item of fd, if it is still the one watched through watch
+    fn _epoll_remove_item(
+        instance: &interface::EpollInstance,
+        fd: i32,
+        watch: &interface::RustRfc<interface::EpollWatch>,
+    ) {
+        let mut interest = instance.interest.lock();
+        if interest
+            .get(&fd)
+            .is_some_and(|item| interface::RustRfc::ptr_eq(&item.watch, watch))
+        {
+            let item = interest.remove(&fd).unwrap();
+            if item.rawfd >= 0 {
+                instance.host_del(item.rawfd);
+            }
+            instance.set_polled(fd, false);
+        }
+    }
 
-                // get the associated events to monitor
-                let events = structpoll.events;
+    // Looks at the file descriptors on the ready list of an epoll instance, along
+    // with the ones that have to be looked at every time, and fills in events for
+    // those that are ready. Level-triggered items that were reported go back on the
+    // ready list, to be looked at again by the next epoll_wait, while
+    // edge-triggered ones wait to be put back there by their next change
+    fn _epoll_collect(
+        &self,
+        instance: &interface::RustRfc<interface::EpollInstance>,
+        events: &mut [EpollEvent],
+        maxevents: usize,
+    ) -> usize {
+        let mut candidates = instance.take_ready();
+        for fd in instance.polled() {
+            if !candidates.contains(&fd) {
+                candidates.push(fd);
+            }
+        }
 
-                // init FdSet structures
-                let reads = &mut interface::FdSet::new();
-                let writes = &mut interface::FdSet::new();
-                let errors = &mut interface::FdSet::new();
+        let mut requeue = vec![];
+        let mut count = 0;
+        for (index, &fd) in candidates.iter().enumerate() {
+            if count >= maxevents {
+                // whatever we didn't get to stays on the ready list
+                requeue.extend_from_slice(&candidates[index..]);
+                break;
+            }
 
-                // POLLIN for readable fd
-                if events & POLLIN > 0 {
-                    reads.set(fd)
-                }
-                // POLLOUT for writable fd
-                if events & POLLOUT > 0 {
-                    writes.set(fd)
+            // take what we need from the item, so that the interest list isn't locked
+            // while we look at the file descriptor
+            let (wanted, data, source, watch) = match instance.interest.lock().get(&fd) {
+                Some(item) if !item.disabled => {
+                    (item.events, item.data, item.source, item.watch.clone())
                 }
-                // POLLPRI for except fd
-                if events & POLLPRI > 0 {
-                    errors.set(fd)
+                _ => continue,
+            };
+
+            let checkedfd = self.get_filedescriptor(fd).unwrap();
+            let unlocked_fd = checkedfd.read();
+            let filedesc = match &*unlocked_fd {
+                Some(filedesc) if Self::_epoll_source(filedesc) == source => filedesc,
+                _ => {
+                    // the file descriptor has been closed, so its item goes away
+                    drop(unlocked_fd);
+                    Self::_epoll_remove_item(instance, fd, &watch);
+                    continue;
                 }
+            };
+            // an item that couldn't be hooked up to a wait queue tries again, before
+            // looking at the file descriptor so that no change is missed
+            let subscription = if instance.is_polled(fd) {
+                Some(self._epoll_subscribe(instance, fd, filedesc, &watch))
+            } else {
+                None
+            };
+            let revents =
+                self._fd_readiness(filedesc) & (wanted | EPOLLERR as u32 | EPOLLHUP as u32);
+            drop(unlocked_fd);
 
-                // this mask is used for storing final revent result
-                let mut mask: i16 = 0;
-
-                // here we just call select_syscall with timeout of zero,
-                // which essentially just check each fd set once then return
-                // NOTE that the nfds argument is highest fd + 1
-                let selectret = Self::select_syscall(
-                    &self,
-                    fd + 1,
-                    Some(reads),
-                    Some(writes),
-                    Some(errors),
-                    Some(interface::RustDuration::ZERO),
-                );
-                // if there is any file descriptor ready
-                if selectret > 0 {
-                    // is the file descriptor ready to read?
-                    mask |= if !reads.is_empty() { POLLIN } else { 0 };
-                    // is the file descriptor ready to write?
-                    mask |= if !writes.is_empty() { POLLOUT } else { 0 };
-                    // is there any exception conditions on the file descriptor?
-                    mask |= if !errors.is_empty() { POLLPRI } else { 0 };
-                    // this file descriptor is ready for something,
-                    // increment the return value
-                    return_code += 1;
-                } else if selectret < 0 {
-                    // if there is any error, first check if the error
-                    // is EBADF, which refers to invalid file descriptor error
-                    // in this case, we should set POLLNVAL to revent
-                    if selectret == -(Errno::EBADF as i32) {
-                        mask |= POLLNVAL;
-                        // according to standard, return value is the number of fds
-                        // with non-zero revent, which may indicate an error as well
-                        return_code += 1;
-                    } else {
-                        return selectret;
+            let mut interest = instance.interest.lock();
+            let item = match interest.get_mut(&fd) {
+                Some(item) if interface::RustRfc::ptr_eq(&item.watch, &watch) => item,
+                _ => {
+                    // the item was removed meanwhile
+                    if let Some((_, rawfd)) = subscription.filter(|&(_, rawfd)| rawfd >= 0) {
+                        instance.host_del(rawfd);
                     }
+                    continue;
                 }
-                // set the revents
-                structpoll.revents = mask;
-            }
-
-            // we break if there is any file descriptor ready
-            // or timeout is reached
-            if return_code != 0 || interface::readtimer(start_time) > end_time {
-                break;
-            } else {
-                // otherwise, check for signal and loop again
-                if interface::sigcheck() {
-                    return syscall_error(Errno::EINTR, "poll", "interrupted function call");
+            };
+            if let Some((subscribed, rawfd)) = subscription {
+                if subscribed {
+                    instance.set_polled(fd, false);
+                }
+                if rawfd >= 0 {
+                    item.rawfd = rawfd;
                 }
-                // We yield to let other threads continue if we've found no ready descriptors
-                interface::lind_yield();
             }
-        }
-        return return_code;
-    }
+            if revents == 0 || item.disabled {
+                continue;
+            }
 
-    pub fn _epoll_object_allocator(&self) -> i32 {
-        // create a Epoll file descriptor
-        let epollobjfd = Epoll(EpollDesc {
-            mode: 0000,
-            registered_fds: interface::RustHashMap::<i32, EpollEvent>::new(),
-            advlock: interface::RustRfc::new(interface::AdvisoryLock::new()),
-            errno: 0,
-            flags: 0,
-        });
-        // get a file descriptor
-        let (fd, guardopt) = self.get_next_fd(None);
-        if fd < 0 {
-            return fd;
+            events[count] = EpollEvent {
+                events: revents,
+                data: interface::EpollData { u64: data },
+            };
+            count += 1;
+            if wanted & EPOLLONESHOT as u32 != 0 {
+                // a one-shot item is done until it is modified
+                item.disabled = true;
+            } else if wanted & EPOLLET as u32 == 0 {
+                requeue.push(fd);
+            }
         }
-        let fdoption = &mut *guardopt.unwrap();
-        let _insertval = fdoption.insert(epollobjfd);
-
-        return fd;
+        instance.requeue(&requeue);
+        count
     }
 
     /// ## ------------------EPOLL_CREATE SYSCALL------------------
@@ -4734,114 +6478,10 @@ impl Cage {
 
         // making sure that the epfd is really an epoll fd
         let checkedfd = self.get_filedescriptor(epfd).unwrap();
-        let mut unlocked_fd = checkedfd.write();
-        if let Some(filedesc_enum_epollfd) = &mut *unlocked_fd {
-            if let Epoll(epollfdobj) = filedesc_enum_epollfd {
-                // first check if fd equals to epfd
-                // standard says EINVAL should be returned when fd equals to epfd
-                // must check before trying to get the read lock of fd
-                // otherwise deadlock would occur (trying to get read lock while the
-                // same fd is already hold with write lock)
-                if fd == epfd {
-                    return syscall_error(
-                        Errno::EINVAL,
-                        "epoll ctl",
-                        "provided fd is the same as epfd",
-                    );
-                }
-
-                // check if the other fd is an epoll or not...
-                let checkedfd = self.get_filedescriptor(fd).unwrap();
-                let unlocked_fd = checkedfd.read();
-                if let Some(filedesc_enum) = &*unlocked_fd {
-                    match filedesc_enum {
-                        Epoll(_) => {
-                            // nested Epoll (i.e. Epoll monitoring on Epoll file descriptor)
-                            // is allowed on Linux with some restrictions, though we currently do
-                            // not support this
-
-                            return syscall_error(
-                                Errno::EBADF,
-                                "epoll ctl",
-                                "provided fd is not a valid file descriptor",
-                            );
-                        }
-                        File(_) => {
-                            // according to standard, EPERM should be returned when
-                            // fd refers to a file or directory
-                            return syscall_error(
-                                Errno::EPERM,
-                                "epoll ctl",
-                                "The target file fd does not support epoll.",
-                            );
-                        }
-                        // other file descriptors are valid
-                        _ => {}
-                    }
-                } else {
-                    // fd is not a valid file descriptor
-                    return syscall_error(
-                        Errno::EBADF,
-                        "epoll ctl",
-                        "provided fd is not a valid file descriptor",
-                    );
-                }
-
-                // now that we know that the types are all good...
-                match op {
-                    EPOLL_CTL_DEL => {
-                        // check if the fd that we are modifying exists or not
-                        if !epollfdobj.registered_fds.contains_key(&fd) {
-                            return syscall_error(
-                                Errno::ENOENT,
-                                "epoll ctl",
-                                "fd is not registered with this epfd",
-                            );
-                        }
-                        // if the fd already exists, remove the entry
-                        epollfdobj.registered_fds.remove(&fd);
-                    }
-                    EPOLL_CTL_MOD => {
-                        // check if the fd that we are modifying exists or not
-                        if !epollfdobj.registered_fds.contains_key(&fd) {
-                            return syscall_error(
-                                Errno::ENOENT,
-                                "epoll ctl",
-                                "fd is not registered with this epfd",
-                            );
-                        }
-                        // if the fd already exists, insert overwrites the prev entry
-                        epollfdobj.registered_fds.insert(
-                            fd,
-                            EpollEvent {
-                                events: event.events,
-                                fd: event.fd,
-                            },
-                        );
-                    }
-                    EPOLL_CTL_ADD => {
-                        //check if the fd that we are modifying exists or not
-                        if epollfdobj.registered_fds.contains_key(&fd) {
-                            return syscall_error(
-                                Errno::EEXIST,
-                                "epoll ctl",
-                                "fd is already registered",
-                            );
-                        }
-                        // add the fd and events
-                        epollfdobj.registered_fds.insert(
-                            fd,
-                            EpollEvent {
-                                events: event.events,
-                                fd: event.fd,
-                            },
-                        );
-                    }
-                    _ => {
-                        return syscall_error(Errno::EINVAL, "epoll ctl", "provided op is invalid");
-                    }
-                }
-            } else {
+        let unlocked_fd = checkedfd.read();
+        let instance = match &*unlocked_fd {
+            Some(Epoll(epollfdobj)) => epollfdobj.instance.clone(),
+            Some(_) => {
                 // epfd is not epoll object
                 return syscall_error(
                     Errno::EINVAL,
@@ -4849,14 +6489,142 @@ impl Cage {
                     "provided epoll fd is not a valid epoll file descriptor",
                 );
             }
-        } else {
-            // epfd is not a valid file descriptor
+            None => {
+                // epfd is not a valid file descriptor
+                return syscall_error(
+                    Errno::EBADF,
+                    "epoll ctl",
+                    "provided fd is not a valid file descriptor",
+                );
+            }
+        };
+        drop(unlocked_fd);
+
+        // standard says EINVAL should be returned when fd equals to epfd
+        if fd == epfd {
             return syscall_error(
-                Errno::EBADF,
+                Errno::EINVAL,
                 "epoll ctl",
-                "provided fd is not a valid file descriptor",
+                "provided fd is the same as epfd",
             );
         }
+
+        // check if the other fd is an epoll or not...
+        let checkedfd = self.get_filedescriptor(fd).unwrap();
+        let unlocked_fd = checkedfd.read();
+        let filedesc_enum = match &*unlocked_fd {
+            Some(Epoll(_)) => {
+                // nested Epoll (i.e. Epoll monitoring on Epoll file descriptor)
+                // is allowed on Linux with some restrictions, though we currently do
+                // not support this
+                return syscall_error(
+                    Errno::EBADF,
+                    "epoll ctl",
+                    "provided fd is not a valid file descriptor",
+                );
+            }
+            Some(File(_)) => {
+                // according to standard, EPERM should be returned when
+                // fd refers to a file or directory
+                return syscall_error(
+                    Errno::EPERM,
+                    "epoll ctl",
+                    "The target file fd does not support epoll.",
+                );
+            }
+            // other file descriptors are valid
+            Some(filedesc_enum) => filedesc_enum,
+            None => {
+                // fd is not a valid file descriptor
+                return syscall_error(
+                    Errno::EBADF,
+                    "epoll ctl",
+                    "provided fd is not a valid file descriptor",
+                );
+            }
+        };
+        let source = Self::_epoll_source(filedesc_enum);
+
+        // now that we know that the types are all good...
+        let mut interest = instance.interest.lock();
+        // an item left behind by a file descriptor that has been closed since and
+        // whose number is now reused is gone, as it would be on Linux
+        if interest.get(&fd).is_some_and(|item| item.source != source) {
+            let item = interest.remove(&fd).unwrap();
+            if item.rawfd >= 0 {
+                instance.host_del(item.rawfd);
+            }
+            instance.set_polled(fd, false);
+        }
+        match op {
+            EPOLL_CTL_DEL => {
+                // check if the fd that we are modifying exists or not
+                let item = match interest.remove(&fd) {
+                    Some(item) => item,
+                    None => {
+                        return syscall_error(
+                            Errno::ENOENT,
+                            "epoll ctl",
+                            "fd is not registered with this epfd",
+                        );
+                    }
+                };
+                // dropping the item also drops its watch, which the wait queues then forget
+                if item.rawfd >= 0 {
+                    instance.host_del(item.rawfd);
+                }
+                instance.set_polled(fd, false);
+            }
+            EPOLL_CTL_MOD => {
+                // check if the fd that we are modifying exists or not
+                let item = match interest.get_mut(&fd) {
+                    Some(item) => item,
+                    None => {
+                        return syscall_error(
+                            Errno::ENOENT,
+                            "epoll ctl",
+                            "fd is not registered with this epfd",
+                        );
+                    }
+                };
+                // the new settings also rearm a one-shot item
+                item.events = event.events;
+                item.data = unsafe { event.data.u64 };
+                item.disabled = false;
+                drop(interest);
+                // the file descriptor may already be ready for the new events
+                instance.mark_ready(fd);
+            }
+            EPOLL_CTL_ADD => {
+                //check if the fd that we are modifying exists or not
+                if interest.contains_key(&fd) {
+                    return syscall_error(Errno::EEXIST, "epoll ctl", "fd is already registered");
+                }
+                // add the fd and events, and hook it up to whatever tells us that it
+                // changed
+                let watch = instance.new_watch(fd);
+                let (subscribed, rawfd) =
+                    self._epoll_subscribe(&instance, fd, filedesc_enum, &watch);
+                interest.insert(
+                    fd,
+                    interface::EpollItem {
+                        events: event.events,
+                        data: unsafe { event.data.u64 },
+                        source,
+                        disabled: false,
+                        rawfd,
+                        watch,
+                    },
+                );
+                instance.set_polled(fd, !subscribed);
+                drop(interest);
+                // the file descriptor may already be ready
+                instance.mark_ready(fd);
+            }
+            _ => {
+                return syscall_error(Errno::EINVAL, "epoll ctl", "provided op is invalid");
+            }
+        }
         return 0;
     }
 
@@ -4881,6 +6649,13 @@ impl Cage {
     /// * `timeout` - The timeout argument is a RustDuration structure that
     ///   specifies the interval that epoll_wait_syscall should block waiting
     ///   for a file descriptor to become ready.
+    ///
+    /// The returned events are EPOLLIN, EPOLLOUT, EPOLLPRI and EPOLLRDHUP as
+    /// requested, along with EPOLLERR and EPOLLHUP which are always reported.
+    /// Each comes with the data it was registered with. An item added with
+    /// EPOLLET is only reported again once the file descriptor changes, and
+    /// one added with EPOLLONESHOT not until it is modified with
+    /// EPOLL_CTL_MOD.
 
     /// ### Returns
     /// On success, epoll_wait_syscall returns the number of file descriptors
@@ -4906,17 +6681,6 @@ impl Cage {
         maxevents: i32,
         timeout: Option<interface::RustDuration>,
     ) -> i32 {
-        // current implementation of epoll is still based on poll_syscall,
-        // we are essentially transforming the epoll input to poll input then
-        // feeding into poll_syscall, and transforming the poll_syscall output
-        // back to epoll result. Such method gives several issues:
-        // 1. epoll is supposed to support a brand new mode called edge-triggered
-        // mode, which only considers a fd to be ready only when new changes are made
-        // to the fd. Currently, we do not support this feature
-        // 2. several flags, such as EPOLLRDHUP, EPOLLERR, etc. are not supported
-        // since poll_syscall currently does not support these flags, so epoll_syscall
-        // that relies on poll_syscall, as a consequence, does not support them
-
         // first check the fds are within the valid range
         if epfd < 0 || epfd >= MAXFD {
             return syscall_error(
@@ -4926,113 +6690,13 @@ impl Cage {
             );
         }
 
-        // get the file descriptor object
+        // get the epoll instance, we don't keep the file descriptor locked while
+        // waiting so that it can still be used by other threads
         let checkedfd = self.get_filedescriptor(epfd).unwrap();
-        let mut unlocked_fd = checkedfd.write();
-        if let Some(filedesc_enum) = &mut *unlocked_fd {
-            // check if epfd is a valid Epoll object
-            if let Epoll(epollfdobj) = filedesc_enum {
-                // maxevents should be larger than 0
-                if maxevents <= 0 {
-                    return syscall_error(
-                        Errno::EINVAL,
-                        "epoll wait",
-                        "max events argument is not a positive number",
-                    );
-                }
-                // transform epoll instance into poll instance
-                let mut poll_fds_vec: Vec<PollStruct> = vec![];
-                let mut rm_fds_vec: Vec<i32> = vec![];
-                // iterate through each registered fds
-                for set in epollfdobj.registered_fds.iter() {
-                    let (&key, &value) = set.pair();
-
-                    // check if any of the registered fds were closed, add them to remove list
-                    let checkedregfd = self.get_filedescriptor(key).unwrap();
-                    let unlocked_regfd = checkedregfd.read();
-                    if unlocked_regfd.is_none() {
-                        rm_fds_vec.push(key);
-                        continue;
-                    }
-
-                    // get the events to monitor
-                    let events = value.events;
-                    let mut structpoll = PollStruct {
-                        fd: key,
-                        events: 0,
-                        revents: 0,
-                    };
-                    // check for each supported event
-                    // EPOLLIN: if the fd is ready to read
-                    if events & EPOLLIN as u32 > 0 {
-                        structpoll.events |= POLLIN;
-                    }
-                    // EPOLLOUT: if the fd is ready to write
-                    if events & EPOLLOUT as u32 > 0 {
-                        structpoll.events |= POLLOUT;
-                    }
-                    // EPOLLPRI: if the fd has any exception?
-                    if events & EPOLLPRI as u32 > 0 {
-                        structpoll.events |= POLLPRI;
-                    }
-                    // now PollStruct is constructed, push it to the vector
-                    poll_fds_vec.push(structpoll);
-                }
-
-                for fd in rm_fds_vec.iter() {
-                    epollfdobj.registered_fds.remove(fd);
-                } // remove closed fds
-
-                // call poll_syscall
-                let poll_fds_slice = &mut poll_fds_vec[..];
-                let pollret = Self::poll_syscall(&self, poll_fds_slice, timeout);
-                if pollret < 0 {
-                    // in case of error, return the error
-                    return pollret;
-                }
-                // the counter is used for making sure the number of returned ready fds
-                // is smaller than or equal to maxevents
-                let mut count = 0;
-                for result in poll_fds_slice.iter() {
-                    // transform the poll result into epoll result
-                    // poll_event is used for marking if the fd is ready for something
-
-                    // events are requested events for poll
-                    // revents are the returned events and all results are stored here
-                    let mut poll_event = false;
-                    let mut event = EpollEvent {
-                        events: 0,
-                        fd: epollfdobj.registered_fds.get(&result.fd).unwrap().fd,
-                    };
-                    // check for POLLIN
-                    if result.revents & POLLIN > 0 {
-                        event.events |= EPOLLIN as u32;
-                        poll_event = true;
-                    }
-                    // check for POLLOUT
-                    if result.revents & POLLOUT > 0 {
-                        event.events |= EPOLLOUT as u32;
-                        poll_event = true;
-                    }
-                    // check for POLLPRI
-                    if result.revents & POLLPRI > 0 {
-                        event.events |= EPOLLPRI as u32;
-                        poll_event = true;
-                    }
-
-                    // if the fd is ready for something
-                    // add it to the return array
-                    if poll_event {
-                        events[count] = event;
-                        count += 1;
-                        // if already reached maxevents, break
-                        if count >= maxevents as usize {
-                            break;
-                        }
-                    }
-                }
-                return count as i32;
-            } else {
+        let unlocked_fd = checkedfd.read();
+        let instance = match &*unlocked_fd {
+            Some(Epoll(epollfdobj)) => epollfdobj.instance.clone(),
+            Some(_) => {
                 // the fd is not an epoll object
                 return syscall_error(
                     Errno::EINVAL,
@@ -5040,14 +6704,69 @@ impl Cage {
                     "provided fd is not an epoll file descriptor",
                 );
             }
-        } else {
-            // epfd is not a valid file descriptor
+            None => {
+                // epfd is not a valid file descriptor
+                return syscall_error(
+                    Errno::EBADF,
+                    "epoll wait",
+                    "provided fd is not a valid file descriptor",
+                );
+            }
+        };
+        drop(unlocked_fd);
+
+        // maxevents should be larger than 0
+        if maxevents <= 0 {
             return syscall_error(
-                Errno::EBADF,
+                Errno::EINVAL,
                 "epoll wait",
-                "provided fd is not a valid file descriptor",
+                "max events argument is not a positive number",
             );
         }
+        let maxevents = interface::rust_min(maxevents as usize, events.len());
+
+        let start_time = interface::starttimer();
+        loop {
+            let count = self._epoll_collect(&instance, events, maxevents);
+            if count > 0 {
+                return count as i32;
+            }
+
+            // nothing is ready, so we block until something might be, the timeout
+            // expires or the wait slice is over
+            let mut wait_ms = EPOLL_WAIT_SLICE_MS;
+            if let Some(timeout) = timeout {
+                let elapsed = interface::readtimer(start_time);
+                if elapsed >= timeout {
+                    return 0;
+                }
+                let remaining = (timeout - elapsed).as_micros().div_ceil(1000);
+                wait_ms = interface::rust_min(wait_ms, remaining as i32);
+            }
+            // file descriptors that can't tell us when they change are looked at often
+            if !instance.polled().is_empty() {
+                wait_ms = interface::rust_min(wait_ms, EPOLL_POLL_INTERVAL_MS);
+            }
+            let waitret = instance.wait(wait_ms);
+            if waitret < 0 && waitret != -(Errno::EINTR as i32) {
+                return waitret;
+            }
+
+            // a signal interrupts the wait
+            if interface::sigcheck() {
+                return syscall_error(Errno::EINTR, "epoll wait", "interrupted function call");
+            }
+            if self
+                .cancelstatus
+                .load(interface::RustAtomicOrdering::Relaxed)
+            {
+                // if the cancel status is set in the cage, we trap around a cancel
+                // point until the individual thread is signaled to cancel itself
+                loop {
+                    interface::cancelpoint(self.cageid);
+                }
+            }
+        }
     }
 
     /// ## ------------------SOCKETPAIR SYSCALL------------------
@@ -5068,11 +6787,13 @@ impl Cage {
     ///                   connection-based byte streams.  An out-of-band data
     ///                   transmission mechanism may be supported.
     ///                2. SOCK_DGRAM Supports datagrams (connectionless,
-    ///                   unreliable messages of a fixed maximum length). The
-    ///                   type argument serves a second purpose: in addition to
-    ///                   specifying a socket type, it may include the bitwise
-    ///                   OR of any of the following values, to modify the
-    ///                   behavior of the socket:
+    ///                   unreliable messages of a fixed maximum length).
+    ///                3. SOCK_SEQPACKET Provides sequenced, reliable, two-way,
+    ///                   connection-based messages of a fixed maximum length.
+    ///                   The type argument serves a second purpose: in addition
+    ///                   to specifying a socket type, it may include the
+    ///                   bitwise OR of any of the following values, to modify
+    ///                   the behavior of the socket:
     ///                1. SOCK_NONBLOCK Set the O_NONBLOCK file status flag on
     ///                   the open file description referred to by the new file
     ///                   descriptor.
@@ -5080,7 +6801,7 @@ impl Cage {
     ///                   file descriptor.
     /// * `protocol` - The protocol specifies a particular protocol to be used
     ///   with the socket. Currently only support the default protocol
-    ///   (IPPROTO_TCP).
+    ///   (IPPROTO_UDP for SOCK_DGRAM, IPPROTO_TCP otherwise).
     /// * `sv` -  The file descriptors used in referencing the new sockets are
     ///   returned in sv.sock1 and sv.sock2. The two sockets are
     ///   indistinguishable.
@@ -5114,7 +6835,16 @@ impl Cage {
         protocol: i32,
         sv: &mut interface::SockPair,
     ) -> i32 {
-        let newprotocol = if protocol == 0 { IPPROTO_TCP } else { protocol }; // only support protocol of 0 currently
+        let realtype = socktype & 0x7;
+        // only support protocol of 0 currently, datagram socketpairs are UDP while the
+        // rest are TCP, as the sockets socket_syscall creates are
+        let newprotocol = if protocol != 0 {
+            protocol
+        } else if realtype == SOCK_DGRAM {
+            IPPROTO_UDP
+        } else {
+            IPPROTO_TCP
+        };
                                                                               // BUG: current implementation of socketpair creates two sockets and bind to an
                                                                               // unique address.
                                                                               // But according to standard, the sockets created from socketpair should not
@@ -5130,11 +6860,14 @@ impl Cage {
             );
         // socket type is stored at the lowest 3 bits
         // so we and it with 0x7 to retrieve it
-        } else if socktype & 0x7 != SOCK_STREAM || newprotocol != IPPROTO_TCP {
+        } else if !matches!(
+            (realtype, newprotocol),
+            (SOCK_STREAM, IPPROTO_TCP) | (SOCK_SEQPACKET, IPPROTO_TCP) | (SOCK_DGRAM, IPPROTO_UDP)
+        ) {
             return syscall_error(
                 Errno::EOPNOTSUPP,
                 "socketpair",
-                "Socketpair currently only supports SOCK_STREAM TCP.",
+                "Socketpair currently only supports SOCK_STREAM, SOCK_SEQPACKET and SOCK_DGRAM.",
             );
         }
 
@@ -5178,20 +6911,48 @@ impl Cage {
         this.bind_inner_socket(&mut *sock1handle, &localaddr1, false);
         this.bind_inner_socket(&mut *sock2handle, &localaddr2, false);
 
-        // setup the pipes
-        let (pipe1, pipe2) = create_unix_sockpipes();
         // one handle's remote address is the other's local address
         sock1handle.remoteaddr = Some(localaddr2.clone());
         sock2handle.remoteaddr = Some(localaddr1.clone());
-        // one handle's sendpipe is the other's receivepipe
-        sock1handle.unix_info.as_mut().unwrap().sendpipe = Some(pipe1.clone());
-        sock1handle.unix_info.as_mut().unwrap().receivepipe = Some(pipe2.clone());
-        sock2handle.unix_info.as_mut().unwrap().sendpipe = Some(pipe2.clone());
-        sock2handle.unix_info.as_mut().unwrap().receivepipe = Some(pipe1.clone());
+        let sock1info = sock1handle.unix_info.as_mut().unwrap();
+        let sock2info = sock2handle.unix_info.as_mut().unwrap();
+        match realtype {
+            SOCK_DGRAM => {
+                // datagram sockets got their receive queues when they were bound,
+                // each one sends to the other's
+                sock1info.sendmsgpipe = sock2info.receivemsgpipe.clone();
+                sock2info.sendmsgpipe = sock1info.receivemsgpipe.clone();
+            }
+            SOCK_SEQPACKET => {
+                // one handle's sendmsgpipe is the other's receivemsgpipe
+                let (msgpipe1, msgpipe2) = create_unix_msgpipes();
+                sock1info.sendmsgpipe = Some(msgpipe1.clone());
+                sock1info.receivemsgpipe = Some(msgpipe2.clone());
+                sock2info.sendmsgpipe = Some(msgpipe2);
+                sock2info.receivemsgpipe = Some(msgpipe1);
+            }
+            _ => {
+                // setup the pipes
+                let (pipe1, pipe2) = create_unix_sockpipes();
+                let (control1, control2) = (new_control_queue(), new_control_queue());
+                // one handle's sendpipe is the other's receivepipe
+                sock1info.sendpipe = Some(pipe1.clone());
+                sock1info.receivepipe = Some(pipe2.clone());
+                sock2info.sendpipe = Some(pipe2.clone());
+                sock2info.receivepipe = Some(pipe1.clone());
+                // and the control queues travel along with the pipes
+                sock1info.sendcontrol = Some(control1.clone());
+                sock1info.receivecontrol = Some(control2.clone());
+                sock2info.sendcontrol = Some(control2);
+                sock2info.receivecontrol = Some(control1);
+            }
+        }
 
-        // now they are connected
-        sock1handle.state = ConnState::CONNECTED;
-        sock2handle.state = ConnState::CONNECTED;
+        // now they are connected, datagram sockets only have a default peer
+        if realtype != SOCK_DGRAM {
+            sock1handle.state = ConnState::CONNECTED;
+            sock2handle.state = ConnState::CONNECTED;
+        }
 
         sv.sock1 = sock1fd;
         sv.sock2 = sock2fd;
@@ -5201,12 +6962,12 @@ impl Cage {
 
         // we need to increment the refcount of the sockets we created
         // reason: in bind_inner_socket, we added entries to the inode table
-        let inode1num = sock1handle.unix_info.as_mut().unwrap().inode;
+        let inode1num = sock1handle.unix_info.as_mut().unwrap().inode.unwrap();
         if let Inode::Socket(ref mut sock) = *(FS_METADATA.inodetable.get_mut(&inode1num).unwrap())
         {
             sock.refcount += 1;
         }
-        let inode2num = sock2handle.unix_info.as_mut().unwrap().inode;
+        let inode2num = sock2handle.unix_info.as_mut().unwrap().inode.unwrap();
         if let Inode::Socket(ref mut sock) = *(FS_METADATA.inodetable.get_mut(&inode2num).unwrap())
         {
             sock.refcount += 1;
@@ -266,6 +266,8 @@ pub const MSG_NO_SHARED_FRAGS: i32 = 0x80000; /* sendpage() internal : page frag
 pub const MSG_SENDPAGE_DECRYPTED: i32 = 0x100000; /* sendpage() internal : page may carry
                                                    * plain text and require encryption
                                                    */
+pub const MSG_CMSG_CLOEXEC: i32 = 0x40000000; /* Set close_on_exec for file descriptor
+                                              * received through SCM_RIGHTS */
 
 //shutdown
 pub const SHUT_RD: i32 = 0;
@@ -329,6 +331,12 @@ pub const SO_ACCEPTCONN: i32 = 30;
 
 // pub const SO_RXQ_OVFL: i32 = 40;
 
+// Ancillary data types for sendmsg/recvmsg on unix domain sockets
+pub const SCM_RIGHTS: i32 = 1;
+pub const SCM_CREDENTIALS: i32 = 2;
+// Most file descriptors a single SCM_RIGHTS message may carry
+pub const SCM_MAX_FD: usize = 253;
+
 // Use this to specify options on a socket. Use the protocol with setsockopt
 // to specify something for all sockets with a protocol
 pub const SOL_TCP: i32 = IPPROTO_TCP;
@@ -364,6 +372,7 @@ pub const POLLOUT: i16 = 0o4; // Writing now will not block.
 pub const POLLERR: i16 = 0o10; // Error condition.
 pub const POLLHUP: i16 = 0o20; // Hung up.
 pub const POLLNVAL: i16 = 0o40; // Invalid polling request.
+pub const POLLRDHUP: i16 = 0x2000; // Peer closed its writing half.
 
 //EPOLL CONSTANTS
 pub const EPOLLIN: i32 = 0x001;
@@ -18,18 +18,31 @@
 //! - [fork_syscall](crate::safeposix::cage::Cage::fork_syscall)
 //! - [exec_syscall](crate::safeposix::cage::Cage::exec_syscall)
 //! - [exit_syscall](crate::safeposix::cage::Cage::exit_syscall)
+//! - [wait_syscall](crate::safeposix::cage::Cage::wait_syscall)
+//! - [waitpid_syscall](crate::safeposix::cage::Cage::waitpid_syscall)
+//! - [wait4_syscall](crate::safeposix::cage::Cage::wait4_syscall)
 //! - [getpid_syscall](crate::safeposix::cage::Cage::getpid_syscall)
 //! - [getppid_syscall](crate::safeposix::cage::Cage::getppid_syscall)
+//! - [setpgid_syscall](crate::safeposix::cage::Cage::setpgid_syscall)
+//! - [getpgid_syscall](crate::safeposix::cage::Cage::getpgid_syscall)
+//! - [getpgrp_syscall](crate::safeposix::cage::Cage::getpgrp_syscall)
+//! - [setsid_syscall](crate::safeposix::cage::Cage::setsid_syscall)
+//! - [getsid_syscall](crate::safeposix::cage::Cage::getsid_syscall)
 //! - [getgid_syscall](crate::safeposix::cage::Cage::getgid_syscall)
 //! - [getegid_syscall](crate::safeposix::cage::Cage::getegid_syscall)
 //! - [getuid_syscall](crate::safeposix::cage::Cage::getuid_syscall)
 //! - [geteuid_syscall](crate::safeposix::cage::Cage::geteuid_syscall)
+//! - [setuid_syscall](crate::safeposix::cage::Cage::setuid_syscall)
+//! - [seteuid_syscall](crate::safeposix::cage::Cage::seteuid_syscall)
+//! - [setgid_syscall](crate::safeposix::cage::Cage::setgid_syscall)
+//! - [setegid_syscall](crate::safeposix::cage::Cage::setegid_syscall)
 //! - [sigaction_syscall](crate::safeposix::cage::Cage::sigaction_syscall)
 //! - [kill_syscall](crate::safeposix::cage::Cage::kill_syscall)
 //! - [sigprocmask_syscall](crate::safeposix::cage::Cage::sigprocmask_syscall)
 //! - [setitimer_syscall](crate::safeposix::cage::Cage::setitimer_syscall)
-//! - [getrlimit](crate::safeposix::cage::Cage::getrlimit)
-//! - [setrlimit](crate::safeposix::cage::Cage::setrlimit)
+//! - [getrlimit_syscall](crate::safeposix::cage::Cage::getrlimit_syscall)
+//! - [setrlimit_syscall](crate::safeposix::cage::Cage::setrlimit_syscall)
+//! - [prlimit64_syscall](crate::safeposix::cage::Cage::prlimit64_syscall)
 
 #![allow(dead_code)]
 
@@ -43,6 +56,10 @@ use crate::safeposix::filesystem::{metawalk, Inode, FS_METADATA};
 use crate::safeposix::net::NET_METADATA;
 use crate::safeposix::shm::SHM_METADATA;
 
+// how long a blocking read from a timerfd or signalfd sleeps at a time before
+// checking whether it can go on
+const BLOCKING_READ_SLICE: interface::RustDuration = interface::RustDuration::from_millis(10);
+
 impl Cage {
     fn unmap_shm_mappings(&self) {
         //unmap shm mappings on exit or exec
@@ -90,12 +107,14 @@ impl Cage {
     ///
     /// ### Errors
     ///    
-    /// There are 2 scenarios where the call to `fork_syscall` might return an
+    /// There are 3 scenarios where the call to `fork_syscall` might return an
     /// error
     ///
     /// * When the RawMutex::create() call fails to create a new Mutex object
     /// * When the RawCondvar::create() call fails to create a new Condition
     ///   Variable object
+    /// * EAGAIN - the real user of the calling cage already runs as many cages
+    ///   as its soft RLIMIT_NPROC allows, and the caller isn't the superuser
     ///
     /// ### Returns
     ///
@@ -110,6 +129,23 @@ impl Cage {
     /// [fork(2)](https://man7.org/linux/man-pages/man2/fork.2.html)
 
     pub fn fork_syscall(&self, child_cageid: u64) -> i32 {
+        // RLIMIT_NPROC caps how many cages a user may run at once, though it doesn't
+        // apply to the superuser
+        let (ruid, _) = self.get_real_ids();
+        if self.get_effective_ids().0 != ROOT_UID {
+            let nproc = Self::live_cages()
+                .iter()
+                .filter(|cage| cage.get_real_ids().0 == ruid)
+                .count() as u64;
+            if nproc >= self.get_rlimit(RLIMIT_NPROC).rlim_cur {
+                return syscall_error(
+                    Errno::EAGAIN,
+                    "fork",
+                    "the user has reached its limit on the number of cages",
+                );
+            }
+        }
+
         //Create a new mutex table that replicates the mutex table of the parent
         // (calling) Cage object Since the child process inherits all the locks
         // that the parent process holds,
@@ -237,12 +273,28 @@ impl Cage {
                                 Inode::Dir(ref mut f) => {
                                     f.refcount += 1;
                                 }
+                                Inode::Symlink(_) => {
+                                    panic!("fork: symlink inode found on a filedesc fd")
+                                }
+                                // FIFOs are always opened as pipes, so a "File" type fd can never
+                                // refer to one.
+                                Inode::Fifo(_) => {
+                                    panic!("fork: FIFO inode found on a filedesc fd")
+                                }
                             }
                         }
                     }
                     // If the fd is linked to a pipe increment the ref count of the pipe
                     Pipe(pipe_filedesc_obj) => {
-                        pipe_filedesc_obj.pipe.incr_ref(pipe_filedesc_obj.flags)
+                        pipe_filedesc_obj.pipe.incr_ref(pipe_filedesc_obj.flags);
+                        // the child also refers to the inode of a FIFO
+                        if let Some(inodenum) = pipe_filedesc_obj.fifoinode {
+                            if let Inode::Fifo(ref mut f) =
+                                *FS_METADATA.inodetable.get_mut(&inodenum).unwrap()
+                            {
+                                f.refcount += 1;
+                            }
+                        }
                     }
                     // If the fd is linked to a socket increment the ref count of the socket
                     Socket(socket_filedesc_obj) => {
@@ -265,10 +317,21 @@ impl Cage {
                                 if let Some(receivepipe) = sockinfo.receivepipe.as_ref() {
                                     receivepipe.incr_ref(O_RDONLY);
                                 }
-                                if let Inode::Socket(ref mut sock) =
-                                    *(FS_METADATA.inodetable.get_mut(&sockinfo.inode).unwrap())
-                                {
-                                    sock.refcount += 1;
+                                if let Some(receivemsgpipe) = sockinfo.receivemsgpipe.as_ref() {
+                                    receivemsgpipe.incr_ref(O_RDONLY);
+                                }
+                                // a datagram socket doesn't hold the queue it sends to open
+                                if sockhandle.socktype & 0x7 == SOCK_SEQPACKET {
+                                    if let Some(sendmsgpipe) = sockinfo.sendmsgpipe.as_ref() {
+                                        sendmsgpipe.incr_ref(O_WRONLY);
+                                    }
+                                }
+                                if let Some(inodenum) = sockinfo.inode {
+                                    if let Inode::Socket(ref mut sock) =
+                                        *(FS_METADATA.inodetable.get_mut(&inodenum).unwrap())
+                                    {
+                                        sock.refcount += 1;
+                                    }
                                 }
                             }
                         }
@@ -348,7 +411,14 @@ impl Cage {
             cageid: child_cageid,
             cwd: interface::RustLock::new(self.cwd.read().clone()),
             // Setting the parent to be the current Cage object
-            parent: self.cageid,
+            parent: interface::RustAtomicU64::new(self.cageid),
+            // The child starts out in the process group and session of its parent
+            pgid: interface::RustAtomicU64::new(
+                self.pgid.load(interface::RustAtomicOrdering::Relaxed),
+            ),
+            sid: interface::RustAtomicU64::new(
+                self.sid.load(interface::RustAtomicOrdering::Relaxed),
+            ),
             // Setting the fd table with our cloned fd table
             filedescriptortable: newfdtable,
             cancelstatus: interface::RustAtomicBool::new(false),
@@ -387,6 +457,14 @@ impl Cage {
             main_threadid: interface::RustAtomicU64::new(0),
             // Creating a new timer for the process with id = child_cageid
             interval_timer: interface::IntervalTimer::new(child_cageid),
+            // The child has no children of its own yet
+            child_events: interface::Mutex::new(vec![]),
+            child_event_cv: interface::Condvar::new(),
+            // The child has not used any resources yet
+            rusage: interface::Mutex::new(interface::Rusage::default()),
+            adopted: interface::RustAtomicBool::new(false),
+            // Resource limits are inherited
+            rlimits: interface::RustLock::new(*self.rlimits.read()),
         };
 
         let shmtable = &SHM_METADATA.shmtable;
@@ -438,8 +516,6 @@ impl Cage {
     ///
     /// For more information please refer to - [https://man7.org/linux/man-pages/man3/exec.3.html]
     pub fn exec_syscall(&self, child_cageid: u64) -> i32 {
-        // We remove the current running process from the cagetable
-        interface::cagetable_remove(self.cageid);
         // Function call to unmap shared memory mappings of the current process
         self.unmap_shm_mappings();
 
@@ -459,6 +535,9 @@ impl Cage {
                     Socket(s) => s.flags & O_CLOEXEC,
                     Pipe(p) => p.flags & O_CLOEXEC,
                     Epoll(p) => p.flags & O_CLOEXEC,
+                    EventFd(e) => e.flags & O_CLOEXEC,
+                    TimerFd(t) => t.flags & O_CLOEXEC,
+                    SignalFd(s) => s.flags & O_CLOEXEC,
                 } != 0
                 {
                     // If the flag is set - we add the fd to our vector
@@ -500,19 +579,35 @@ impl Cage {
             newsigset.insert(0, mainsigset);
         }
 
+        // The credentials of a cage survive exec. Ids that still hold their default
+        // value are reset to -1 to indicate the loading phase, while ids changed by
+        // one of the set*id syscalls are kept, as otherwise a cage that dropped its
+        // privileges could regain them by exec'ing.
+        let exec_id = |id: &interface::RustAtomicI32, default: u32| {
+            let id = id.load(interface::RustAtomicOrdering::Relaxed);
+            interface::RustAtomicI32::new(if id == default as i32 { -1 } else { id })
+        };
+
         // Initialize a new cage object to replace the current running image
-        // We set all the ids to -1 to indicate the loading phase
         // We clone the fd table with the memories unmapped
         let newcage = Cage {
             cageid: child_cageid,
             cwd: interface::RustLock::new(self.cwd.read().clone()),
-            parent: self.parent,
+            parent: interface::RustAtomicU64::new(
+                self.parent.load(interface::RustAtomicOrdering::Relaxed),
+            ),
+            pgid: interface::RustAtomicU64::new(
+                self.pgid.load(interface::RustAtomicOrdering::Relaxed),
+            ),
+            sid: interface::RustAtomicU64::new(
+                self.sid.load(interface::RustAtomicOrdering::Relaxed),
+            ),
             filedescriptortable: self.filedescriptortable.clone(),
             cancelstatus: interface::RustAtomicBool::new(false),
-            getgid: interface::RustAtomicI32::new(-1),
-            getuid: interface::RustAtomicI32::new(-1),
-            getegid: interface::RustAtomicI32::new(-1),
-            geteuid: interface::RustAtomicI32::new(-1),
+            getgid: exec_id(&self.getgid, DEFAULT_GID),
+            getuid: exec_id(&self.getuid, DEFAULT_UID),
+            getegid: exec_id(&self.getegid, DEFAULT_GID),
+            geteuid: exec_id(&self.geteuid, DEFAULT_UID),
             rev_shm: interface::Mutex::new(vec![]),
             mutex_table: interface::RustLock::new(vec![]),
             cv_table: interface::RustLock::new(vec![]),
@@ -523,10 +618,33 @@ impl Cage {
             pendingsigset: interface::RustHashMap::new(),
             main_threadid: interface::RustAtomicU64::new(0),
             interval_timer: self.interval_timer.clone_with_new_cageid(child_cageid),
+            child_events: interface::Mutex::new(vec![]),
+            child_event_cv: interface::Condvar::new(),
+            // the new image is the same process, so its usage so far carries over
+            rusage: interface::Mutex::new(*self.rusage.lock()),
+            adopted: interface::RustAtomicBool::new(
+                self.adopted.load(interface::RustAtomicOrdering::Relaxed),
+            ),
+            rlimits: interface::RustLock::new(*self.rlimits.read()),
         };
 
+        // The new image keeps the children of the old one along with their
+        // uncollected events. We hold our event lock until the new cage is in the
+        // cagetable, so that a child exiting meanwhile either queues its zombie
+        // record before we move the events over, or finds its new parent
+        let mut events = self.child_events.lock();
+        for child in self.live_children() {
+            child
+                .parent
+                .store(child_cageid, interface::RustAtomicOrdering::Relaxed);
+        }
+        *newcage.child_events.lock() = std::mem::take(&mut *events);
+
+        // We remove the current running process from the cagetable
+        interface::cagetable_remove(self.cageid);
         // Insert new cage with updated fd tables to be inserted in the cagetable
         interface::cagetable_insert(child_cageid, newcage);
+        drop(events);
         0
     }
 
@@ -536,13 +654,19 @@ impl Cage {
     /// The termination entails unmapping all memory references
     /// Removing the cage object from the cage table, closing all open files
     /// And decrement all references to files and directories
+    /// A zombie record holding the exit status and resource usage is left
+    /// with the parent cage until the parent reaps it with one of the wait
+    /// calls. Children of the exiting cage are reparented to the init cage
+    /// (cage 1), which reaps them as soon as they exit, and zombies it has not
+    /// reaped are discarded.
     /// For more information please refer [https://man7.org/linux/man-pages/man3/exit.3.html]
     ///
     /// ### Arguments
     ///
     /// The exit function takes only one argument which is `status`
     /// `status` : This is a 32 bit integer value that the function returns back
-    /// upon sucessfully terminating the process
+    /// upon sucessfully terminating the process. Its low 8 bits are reported
+    /// to the parent as the exit status
     ///
     /// ### Returns
     ///
@@ -561,6 +685,55 @@ impl Cage {
     ///
     /// This function has no scenario where it returns an error
     pub fn exit_syscall(&self, status: i32) -> i32 {
+        // The parent learns the low 8 bits of the status through WEXITSTATUS
+        self.exit_helper((status & 0xff) << 8);
+
+        // Return the status integer back to the calling function
+        status
+    }
+
+    /// ### Description
+    ///
+    /// `terminate_by_signal` ends the cage the way `exit_syscall` does, but
+    /// leaves a zombie record saying that the cage was killed by signal `sig`
+    /// (WIFSIGNALED) instead of exiting normally. The runtime calls this when
+    /// a cage receives a signal whose default action is to terminate it.
+    ///
+    /// ### Arguments
+    ///
+    /// * `sig` : the signal that terminated the cage
+    ///
+    /// ### Returns
+    ///
+    /// This function does not return a value
+    ///
+    /// ### Panics
+    ///
+    /// Panics under the same conditions as `exit_syscall`
+    pub fn terminate_by_signal(&self, sig: i32) {
+        self.exit_helper(sig & 0x7f);
+    }
+
+    /// ### Description
+    ///
+    /// `notify_stopped` queues a stop notification for the parent of the cage,
+    /// which a parent waiting with WUNTRACED collects (WIFSTOPPED). The runtime
+    /// calls this when the cage is stopped by signal `sig`.
+    ///
+    /// ### Arguments
+    ///
+    /// * `sig` : the signal that stopped the cage
+    ///
+    /// ### Returns
+    ///
+    /// This function does not return a value
+    pub fn notify_stopped(&self, sig: i32) {
+        self.notify_parent((sig & 0xff) << 8 | 0x7f);
+    }
+
+    // Tears down the cage and leaves a zombie record with the given wait status
+    // for its parent, which is shared by a normal exit and death by a signal
+    fn exit_helper(&self, waitstatus: i32) {
         //Clear all values in stdout stream
         interface::flush_stdout();
         //Unmap all memory mappings for the current cage object
@@ -572,6 +745,30 @@ impl Cage {
             self._close_helper(fd);
         }
 
+        // Our remaining children are orphaned and get adopted by the init cage,
+        // which reaps them once they exit, while the zombies we never reaped are
+        // reaped right away. This is done
+        // while holding our event lock so that a child exiting at the same time
+        // either queues its record here before it is dropped, or sees its new
+        // parent
+        let mut events = self.child_events.lock();
+        if self.cageid != INIT_CAGEID {
+            for child in self.live_children() {
+                child
+                    .adopted
+                    .store(true, interface::RustAtomicOrdering::Relaxed);
+                child
+                    .parent
+                    .store(INIT_CAGEID, interface::RustAtomicOrdering::Relaxed);
+            }
+        }
+        events.clear();
+        drop(events);
+
+        // Leave our zombie record with the parent before leaving the cagetable, so
+        // that a parent waiting for us always finds either the record or the cage
+        self.notify_parent(waitstatus);
+
         //Remove the current cage object from the cage table
         interface::cagetable_remove(self.cageid);
 
@@ -581,13 +778,250 @@ impl Cage {
             // Trigger SIGCHILD if LIND is not run as a test suite
             // SIGCHILD is simply a response that the parent recieves when it's child
             // process terminates
-            if self.cageid != self.parent {
-                interface::lind_kill_from_id(self.parent, SIGCHLD);
+            let parent = self.parent.load(interface::RustAtomicOrdering::Relaxed);
+            if self.cageid != parent {
+                interface::lind_kill_from_id(parent, SIGCHLD);
             }
         }
+    }
 
-        // Return the status integer back to the calling function
-        status
+    // Queues a child event with the given wait status in the parent cage and
+    // wakes up the parent if it is waiting for one
+    fn notify_parent(&self, status: i32) {
+        // The usage of the cage is that of its finished threads and reaped
+        // children plus that of the thread reporting the change
+        let mut rusage = *self.rusage.lock();
+        rusage.accumulate(&interface::lind_getrusage());
+        let event = ChildEvent {
+            cageid: self.cageid,
+            pgid: self.pgid.load(interface::RustAtomicOrdering::Relaxed),
+            status,
+            rusage,
+        };
+        loop {
+            let parentid = self.parent.load(interface::RustAtomicOrdering::Relaxed);
+            // the init cage is its own parent and has nobody to report to
+            if parentid == self.cageid {
+                return;
+            }
+            let parent = match interface::cagetable_getref_opt(parentid) {
+                Some(parent) => parent,
+                None => {
+                    // The parent may have just handed us over to a new one
+                    if self.parent.load(interface::RustAtomicOrdering::Relaxed) != parentid {
+                        continue;
+                    }
+                    return;
+                }
+            };
+            let mut events = parent.child_events.lock();
+            // we could have been reparented while waiting for the lock
+            if self.parent.load(interface::RustAtomicOrdering::Relaxed) != parentid {
+                continue;
+            }
+            // a stop that was never collected is outdated once we exit
+            if !event.is_stop() {
+                events.retain(|queued| queued.cageid != self.cageid);
+            }
+            // the init cage reaps the orphans it adopted on its own, so nothing is
+            // queued for them, though it still has to notice they are gone
+            if !self.adopted.load(interface::RustAtomicOrdering::Relaxed) {
+                events.push(event);
+            }
+            parent.child_event_cv.notify_all();
+            return;
+        }
+    }
+
+    // returns the cages whose parent is the calling cage
+    fn live_children(&self) -> Vec<interface::RustRfc<Cage>> {
+        Self::live_cages()
+            .into_iter()
+            .filter(|cage| {
+                cage.cageid != self.cageid
+                    && cage.parent.load(interface::RustAtomicOrdering::Relaxed) == self.cageid
+            })
+            .collect()
+    }
+
+    // returns all cages currently in the cagetable
+    fn live_cages() -> Vec<interface::RustRfc<Cage>> {
+        (0..interface::MAXCAGEID as u64)
+            .filter_map(interface::cagetable_getref_opt)
+            .collect()
+    }
+
+    /// ### Description
+    ///
+    /// `wait_syscall` suspends the calling cage until one of its children
+    /// exits, and reaps it. It is equivalent to `waitpid(-1, status, 0)`.
+    ///
+    /// ### Arguments
+    ///
+    /// * `status` : if given, receives the wait status of the reaped child
+    ///
+    /// ### Returns
+    ///
+    /// On success, the cage id of the reaped child is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * ECHILD - the calling cage has no children
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/wait.2.html]
+    pub fn wait_syscall(&self, status: Option<&mut i32>) -> i32 {
+        self.wait4_syscall(-1, status, 0, None)
+    }
+
+    /// ### Description
+    ///
+    /// `waitpid_syscall` waits for a state change in a child of the calling
+    /// cage, selected by `pid`, and reaps it if it has exited. It is
+    /// equivalent to `wait4` without the resource usage.
+    ///
+    /// ### Arguments
+    ///
+    /// * `pid` : which children to wait for, as described for `wait4_syscall`
+    /// * `status` : if given, receives the wait status of the child
+    /// * `options` : a bitwise OR of zero or more of WNOHANG and WUNTRACED
+    ///
+    /// ### Returns
+    ///
+    /// On success, the cage id of the child whose state changed is returned.
+    /// If WNOHANG was given and no selected child has changed state yet, 0 is
+    /// returned.
+    ///
+    /// ### Errors
+    ///
+    /// * ECHILD - the calling cage has no children selected by `pid`
+    /// * EINVAL - `options` contains an unsupported flag
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/wait.2.html]
+    pub fn waitpid_syscall(&self, pid: i32, status: Option<&mut i32>, options: i32) -> i32 {
+        self.wait4_syscall(pid, status, options, None)
+    }
+
+    /// ### Description
+    ///
+    /// `wait4_syscall` waits for a state change in a child of the calling
+    /// cage. A child that exited is reaped, which discards its zombie record,
+    /// while a stopped child is only reported if WUNTRACED is given. Events
+    /// are collected in the order in which they happened. Without WNOHANG the
+    /// call blocks until a selected child changes state.
+    ///
+    /// ### Arguments
+    ///
+    /// * `pid` : which children to wait for:
+    ///   - `pid > 0` waits for the child with that cage id
+    ///   - `pid == -1` waits for any child
+    ///   - `pid == 0` waits for any child in the process group of the caller
+    ///   - `pid < -1` waits for any child in the process group `-pid`
+    /// * `status` : if given, receives the wait status of the child, which can
+    ///   be inspected with WIFEXITED, WEXITSTATUS, WIFSIGNALED, WTERMSIG,
+    ///   WIFSTOPPED and WSTOPSIG
+    /// * `options` : a bitwise OR of zero or more of WNOHANG and WUNTRACED
+    /// * `rusage` : if given, receives the resource usage of the child, added
+    ///   up over all of its threads and the children it reaped itself
+    ///
+    /// ### Returns
+    ///
+    /// On success, the cage id of the child whose state changed is returned.
+    /// If WNOHANG was given and no selected child has changed state yet, 0 is
+    /// returned.
+    ///
+    /// ### Errors
+    ///
+    /// * ECHILD - the calling cage has no children selected by `pid`
+    /// * EINVAL - `options` contains an unsupported flag
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/wait4.2.html]
+    pub fn wait4_syscall(
+        &self,
+        pid: i32,
+        status: Option<&mut i32>,
+        options: i32,
+        rusage: Option<&mut interface::Rusage>,
+    ) -> i32 {
+        if options & !(WNOHANG | WUNTRACED) != 0 {
+            return syscall_error(Errno::EINVAL, "wait4", "Invalid options");
+        }
+
+        let ourpgid = self.pgid.load(interface::RustAtomicOrdering::Relaxed);
+        let selected = |cageid: u64, pgid: u64| match pid {
+            -1 => true,
+            0 => pgid == ourpgid,
+            pid if pid < -1 => pgid == (pid as i64).unsigned_abs(),
+            pid => cageid == pid as u64,
+        };
+
+        let mut events = self.child_events.lock();
+        loop {
+            if let Some(index) = events.iter().position(|event| {
+                selected(event.cageid, event.pgid) && (!event.is_stop() || options & WUNTRACED != 0)
+            }) {
+                // collecting the event reaps the child if it has exited
+                let event = events.remove(index);
+                drop(events);
+                // the usage of a reaped child counts towards our own
+                if !event.is_stop() {
+                    self.rusage.lock().accumulate(&event.rusage);
+                }
+                if let Some(status) = status {
+                    *status = event.status;
+                }
+                if let Some(rusage) = rusage {
+                    *rusage = event.rusage;
+                }
+                return event.cageid as i32;
+            }
+
+            // there is nothing to collect yet, so there has to be a child to wait on
+            if !self.live_children().iter().any(|child| {
+                selected(
+                    child.cageid,
+                    child.pgid.load(interface::RustAtomicOrdering::Relaxed),
+                )
+            }) {
+                return syscall_error(
+                    Errno::ECHILD,
+                    "wait4",
+                    "The calling cage has no children to wait for",
+                );
+            }
+
+            if options & WNOHANG != 0 {
+                return 0;
+            }
+
+            // children notify us when their state changes, but we wake up now and then
+            // to check whether the cage is being cancelled
+            self.child_event_cv
+                .wait_for(&mut events, interface::RustDuration::from_millis(100));
+            if self
+                .cancelstatus
+                .load(interface::RustAtomicOrdering::Relaxed)
+            {
+                // our children must still be able to exit while we are trapped here
+                drop(events);
+                // if the cancel status is set in the cage, we trap around a cancel point
+                // until the individual thread is signaled to cancel itself
+                loop {
+                    interface::cancelpoint(self.cageid);
+                }
+            }
+        }
     }
 
     /// ### Description
@@ -621,138 +1055,543 @@ impl Cage {
     /// Returns a 32 bit integer value that represents the unique id of the
     /// parent process.
     pub fn getppid_syscall(&self) -> i32 {
-        self.parent as i32 // mimicing the call above -- easy to change later if
-                           // necessary
+        // the parent may change when the cage is orphaned
+        self.parent.load(interface::RustAtomicOrdering::Relaxed) as i32
     }
 
     /// ### Description
     ///
-    /// This function returns the real group id of the calling process. The real
-    /// group id is specified at login time. The group id is the group of
-    /// the user who invoked the program. Lind is only run in one group -
-    /// and hence a default value is expected from this function.
-    /// Initially we check if the call takes place during the loading stage, and
-    /// return -1 if yes and set the gid to be the default value.
+    /// `setpgid_syscall` moves the cage `pid` into the process group `pgid`,
+    /// which shells use to put the cages of a pipeline into a job of their
+    /// own. A cage may only move itself or one of its children, and only into
+    /// a new group named after the moved cage or an existing group in the same
+    /// session.
     ///
     /// ### Arguments
     ///
-    /// The `getgid_syscall` does not take any argument.
+    /// * `pid` : the cage to move, or 0 for the calling cage
+    /// * `pgid` : the process group to move it into, or 0 to use the id of the
+    ///   moved cage, making it a process group leader
     ///
     /// ### Returns
     ///
-    /// Depending on whether the gid has been initialized or not this function
-    /// returns either -1 or the default gid as a 32 bit integer.
-    pub fn getgid_syscall(&self) -> i32 {
-        // We return -1 for the first call for compatibility with the dynamic loader.
-        // For subsequent calls we return our default value.
-        if self.getgid.load(interface::RustAtomicOrdering::Relaxed) == -1 {
-            self.getgid
-                .store(DEFAULT_GID as i32, interface::RustAtomicOrdering::Relaxed);
-            return -1;
+    /// On success, 0 is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * EINVAL - `pid` or `pgid` is negative
+    /// * ESRCH - `pid` is neither the calling cage nor one of its children
+    /// * EPERM - the cage is a session leader, is in a different session than
+    ///   the caller, or `pgid` names no process group in the caller's session
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/setpgid.2.html]
+    pub fn setpgid_syscall(&self, pid: i32, pgid: i32) -> i32 {
+        if pid < 0 || pgid < 0 {
+            return syscall_error(Errno::EINVAL, "setpgid", "pid or pgid is negative");
+        }
+
+        let child;
+        let target: &Cage = if pid == 0 || pid as u64 == self.cageid {
+            self
+        } else {
+            child = match interface::cagetable_getref_opt(pid as u64) {
+                Some(cage)
+                    if cage.parent.load(interface::RustAtomicOrdering::Relaxed) == self.cageid =>
+                {
+                    cage
+                }
+                _ => {
+                    return syscall_error(
+                        Errno::ESRCH,
+                        "setpgid",
+                        "pid is neither the calling cage nor one of its children",
+                    );
+                }
+            };
+            &child
+        };
+
+        let newpgid = if pgid == 0 {
+            target.cageid
+        } else {
+            pgid as u64
+        };
+        let oursid = self.sid.load(interface::RustAtomicOrdering::Relaxed);
+        let targetsid = target.sid.load(interface::RustAtomicOrdering::Relaxed);
+        if targetsid == target.cageid {
+            return syscall_error(
+                Errno::EPERM,
+                "setpgid",
+                "cannot change the process group of a session leader",
+            );
+        }
+        if targetsid != oursid {
+            return syscall_error(
+                Errno::EPERM,
+                "setpgid",
+                "the child is in a different session than the caller",
+            );
         }
-        DEFAULT_GID as i32 //Lind is only run in one group so a default value
-                           // is returned
+        // joining a group other than its own requires that group to exist in our
+        // session
+        if newpgid != target.cageid
+            && !Self::live_cages().iter().any(|cage| {
+                cage.pgid.load(interface::RustAtomicOrdering::Relaxed) == newpgid
+                    && cage.sid.load(interface::RustAtomicOrdering::Relaxed) == oursid
+            })
+        {
+            return syscall_error(
+                Errno::EPERM,
+                "setpgid",
+                "no process group with this id exists in the caller's session",
+            );
+        }
+
+        target
+            .pgid
+            .store(newpgid, interface::RustAtomicOrdering::Relaxed);
+        0
     }
 
     /// ### Description
     ///
-    /// The `getegid_syscall` returns the effective group id of the user who
-    /// invoked the process. Since Lind is only run in one group a default
-    /// value (or -1) is returned. Initially we check if the call takes
-    /// place during the loading stage, and return -1 if yes and set the
-    /// egid to be the default value.
+    /// `getpgid_syscall` returns the process group id of the cage `pid`.
     ///
     /// ### Arguments
     ///
-    /// This syscall does not take any arguments
+    /// * `pid` : the cage to look up, or 0 for the calling cage
     ///
     /// ### Returns
     ///
-    /// Returns a 32 bit integer value (or -1) which represents the effective
-    /// group
-    pub fn getegid_syscall(&self) -> i32 {
-        // We return -1 for the first call for compatibility with the dynamic loader.
-        // For subsequent calls we return our default value.
-        if self.getegid.load(interface::RustAtomicOrdering::Relaxed) == -1 {
-            self.getegid
-                .store(DEFAULT_GID as i32, interface::RustAtomicOrdering::Relaxed);
-            return -1;
+    /// On success, the process group id is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * ESRCH - no cage with id `pid` exists
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/getpgid.2.html]
+    pub fn getpgid_syscall(&self, pid: i32) -> i32 {
+        if pid == 0 || pid as u64 == self.cageid {
+            return self.pgid.load(interface::RustAtomicOrdering::Relaxed) as i32;
+        }
+        match Self::cage_by_pid(pid) {
+            Some(cage) => cage.pgid.load(interface::RustAtomicOrdering::Relaxed) as i32,
+            None => syscall_error(Errno::ESRCH, "getpgid", "No cage with this id exists"),
         }
-        DEFAULT_GID as i32 //Lind is only run in one group so a default value
-                           // is returned
     }
 
     /// ### Description
     ///
-    /// The `getuid_syscall` returns the real user id of the calling process.
-    /// The real user id is the user who invoked the calling process.
-    /// As Lind only allows one user, a default value is returned.
-    /// Initially we check if the call takes place during the loading stage, and
-    /// return -1 if yes and set the uid to be the default value.
+    /// `getpgrp_syscall` returns the process group id of the calling cage,
+    /// and is equivalent to `getpgid(0)`. This call is always successful.
     ///
     /// ### Arguments
-    ///  
-    /// The `getuid_syscall` does not take any arguments
+    ///
+    /// This system call does not take any arguments
     ///
     /// ### Returns
     ///
-    /// Returns a 32 bit default integer (or -1) representing the user
-    pub fn getuid_syscall(&self) -> i32 {
-        // We return -1 for the first call for compatibility with the dynamic loader.
-        // For subsequent calls we return our default value.
-        if self.getuid.load(interface::RustAtomicOrdering::Relaxed) == -1 {
-            self.getuid
-                .store(DEFAULT_UID as i32, interface::RustAtomicOrdering::Relaxed);
-            return -1;
-        }
-        DEFAULT_UID as i32 //Lind is only run as one user so a default value is
-                           // returned
+    /// Returns the process group id of the calling cage.
+    pub fn getpgrp_syscall(&self) -> i32 {
+        self.pgid.load(interface::RustAtomicOrdering::Relaxed) as i32
     }
 
     /// ### Description
     ///
-    /// The `geteuid_syscall` returns the effective user id of the calling
-    /// process. As Lind only allows one user, a default value (or -1) is
-    /// returned. Initially we check if the call takes place during the
-    /// loading stage, and return -1 if yes and set the euid to be the
-    /// default value.
+    /// `setsid_syscall` starts a new session with the calling cage as its
+    /// leader. The cage also becomes the leader of a new process group in
+    /// that session, and both ids equal the id of the cage.
     ///
-    /// ### Function Arguments
-    /// The geteuid syscall does not take any arguments
+    /// ### Arguments
+    ///
+    /// This system call does not take any arguments
     ///
     /// ### Returns
     ///
-    /// Returns a 32 bit default integer value (or -1) representing the
-    /// effective user
-    pub fn geteuid_syscall(&self) -> i32 {
-        // We return -1 for the first call for compatibility with the dynamic loader.
-        // For subsequent calls we return our default value.
-        if self.geteuid.load(interface::RustAtomicOrdering::Relaxed) == -1 {
-            self.geteuid
-                .store(DEFAULT_UID as i32, interface::RustAtomicOrdering::Relaxed);
-            return -1;
+    /// On success, the id of the new session is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * EPERM - a process group with the id of the calling cage already
+    ///   exists, e.g. because the caller is a process group leader
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/setsid.2.html]
+    pub fn setsid_syscall(&self) -> i32 {
+        if self.pgid.load(interface::RustAtomicOrdering::Relaxed) == self.cageid
+            || Self::live_cages()
+                .iter()
+                .any(|cage| cage.pgid.load(interface::RustAtomicOrdering::Relaxed) == self.cageid)
+        {
+            return syscall_error(
+                Errno::EPERM,
+                "setsid",
+                "a process group with the id of the caller already exists",
+            );
         }
-        DEFAULT_UID as i32 //Lind is only run as one user so a default value is
-                           // returned
+        self.pgid
+            .store(self.cageid, interface::RustAtomicOrdering::Relaxed);
+        self.sid
+            .store(self.cageid, interface::RustAtomicOrdering::Relaxed);
+        self.cageid as i32
     }
 
-    pub fn sigaction_syscall(
-        &self,
-        sig: i32,
-        act: Option<&interface::SigactionStruct>,
-        oact: Option<&mut interface::SigactionStruct>,
-    ) -> i32 {
-        if let Some(some_oact) = oact {
-            let old_sigactionstruct = self.signalhandler.get(&sig);
-
-            if let Some(entry) = old_sigactionstruct {
-                some_oact.clone_from(entry.value());
-            } else {
-                some_oact.clone_from(&interface::SigactionStruct::default()); // leave handler field as NULL
-            }
-        }
-
-        if let Some(some_act) = act {
+    /// ### Description
+    ///
+    /// `getsid_syscall` returns the session id of the cage `pid`.
+    ///
+    /// ### Arguments
+    ///
+    /// * `pid` : the cage to look up, or 0 for the calling cage
+    ///
+    /// ### Returns
+    ///
+    /// On success, the session id is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * ESRCH - no cage with id `pid` exists
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/getsid.2.html]
+    pub fn getsid_syscall(&self, pid: i32) -> i32 {
+        if pid == 0 || pid as u64 == self.cageid {
+            return self.sid.load(interface::RustAtomicOrdering::Relaxed) as i32;
+        }
+        match Self::cage_by_pid(pid) {
+            Some(cage) => cage.sid.load(interface::RustAtomicOrdering::Relaxed) as i32,
+            None => syscall_error(Errno::ESRCH, "getsid", "No cage with this id exists"),
+        }
+    }
+
+    // looks up the cage with the given id, if the id is a valid one
+    fn cage_by_pid(pid: i32) -> Option<interface::RustRfc<Cage>> {
+        if pid <= 0 || pid >= interface::MAXCAGEID {
+            return None;
+        }
+        interface::cagetable_getref_opt(pid as u64)
+    }
+
+    /// ### Description
+    ///
+    /// This function returns the real group id of the calling process. The real
+    /// group id is specified at login time. The group id is the group of
+    /// the user who invoked the program. Cages start out in a default group,
+    /// which a privileged cage may change with `setgid_syscall`.
+    /// Initially we check if the call takes place during the loading stage, and
+    /// return -1 if yes and set the gid to be the default value.
+    ///
+    /// ### Arguments
+    ///
+    /// The `getgid_syscall` does not take any argument.
+    ///
+    /// ### Returns
+    ///
+    /// Depending on whether the gid has been initialized or not this function
+    /// returns either -1 or the current gid as a 32 bit integer.
+    pub fn getgid_syscall(&self) -> i32 {
+        // We return -1 for the first call for compatibility with the dynamic loader.
+        // For subsequent calls we return the stored value, which starts out as our
+        // default and may since have been changed by one of the set*id syscalls.
+        let id = self.getgid.load(interface::RustAtomicOrdering::Relaxed);
+        if id == -1 {
+            self.getgid
+                .store(DEFAULT_GID as i32, interface::RustAtomicOrdering::Relaxed);
+            return -1;
+        }
+        id
+    }
+
+    /// ### Description
+    ///
+    /// The `getegid_syscall` returns the effective group id of the user who
+    /// invoked the process, which starts out as the default group and may be
+    /// changed with `setegid_syscall` or `setgid_syscall`. Initially we check
+    /// if the call takes place during the loading stage, and return -1 if
+    /// yes and set the egid to be the default value.
+    ///
+    /// ### Arguments
+    ///
+    /// This syscall does not take any arguments
+    ///
+    /// ### Returns
+    ///
+    /// Returns a 32 bit integer value (or -1) which represents the effective
+    /// group
+    pub fn getegid_syscall(&self) -> i32 {
+        // We return -1 for the first call for compatibility with the dynamic loader.
+        // For subsequent calls we return the stored value, which starts out as our
+        // default and may since have been changed by one of the set*id syscalls.
+        let id = self.getegid.load(interface::RustAtomicOrdering::Relaxed);
+        if id == -1 {
+            self.getegid
+                .store(DEFAULT_GID as i32, interface::RustAtomicOrdering::Relaxed);
+            return -1;
+        }
+        id
+    }
+
+    /// ### Description
+    ///
+    /// The `getuid_syscall` returns the real user id of the calling process.
+    /// The real user id is the user who invoked the calling process.
+    /// Cages start out as a default user, which a privileged cage may change
+    /// with `setuid_syscall`.
+    /// Initially we check if the call takes place during the loading stage, and
+    /// return -1 if yes and set the uid to be the default value.
+    ///
+    /// ### Arguments
+    ///  
+    /// The `getuid_syscall` does not take any arguments
+    ///
+    /// ### Returns
+    ///
+    /// Returns a 32 bit integer (or -1) representing the user
+    pub fn getuid_syscall(&self) -> i32 {
+        // We return -1 for the first call for compatibility with the dynamic loader.
+        // For subsequent calls we return the stored value, which starts out as our
+        // default and may since have been changed by one of the set*id syscalls.
+        let id = self.getuid.load(interface::RustAtomicOrdering::Relaxed);
+        if id == -1 {
+            self.getuid
+                .store(DEFAULT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+            return -1;
+        }
+        id
+    }
+
+    /// ### Description
+    ///
+    /// The `geteuid_syscall` returns the effective user id of the calling
+    /// process, which filesystem permission checks are made against. It
+    /// starts out as the default user and may be changed with
+    /// `seteuid_syscall` or `setuid_syscall`. Initially we check if the call
+    /// takes place during the loading stage, and return -1 if yes and set
+    /// the euid to be the default value.
+    ///
+    /// ### Function Arguments
+    /// The geteuid syscall does not take any arguments
+    ///
+    /// ### Returns
+    ///
+    /// Returns a 32 bit integer value (or -1) representing the effective
+    /// user
+    pub fn geteuid_syscall(&self) -> i32 {
+        // We return -1 for the first call for compatibility with the dynamic loader.
+        // For subsequent calls we return the stored value, which starts out as our
+        // default and may since have been changed by one of the set*id syscalls.
+        let id = self.geteuid.load(interface::RustAtomicOrdering::Relaxed);
+        if id == -1 {
+            self.geteuid
+                .store(DEFAULT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+            return -1;
+        }
+        id
+    }
+
+    /// ### Description
+    ///
+    /// The `setuid_syscall` sets the user id of the calling process. If the
+    /// caller is the superuser, both the real and the effective user id are
+    /// set, which permanently drops (or changes) its privileges. Otherwise
+    /// only the effective user id may be set, and only back to the real user
+    /// id.
+    ///
+    /// ### Arguments
+    ///
+    /// * `uid` - the user id to switch to
+    ///
+    /// ### Returns
+    ///
+    /// Returns 0 on success.
+    ///
+    /// ### Errors
+    ///
+    /// * `EINVAL` - `uid` is -1, which is not a valid user id
+    /// * `EPERM` - the caller is not the superuser and `uid` is not its real
+    ///   user id
+    ///
+    /// To learn more about the syscall, see
+    /// [setuid(2)](https://man7.org/linux/man-pages/man2/setuid.2.html)
+    pub fn setuid_syscall(&self, uid: u32) -> i32 {
+        if uid == NOCHANGE_ID {
+            return syscall_error(Errno::EINVAL, "setuid", "the user id is not valid");
+        }
+        let (ruid, _) = self.get_real_ids();
+        let (euid, _) = self.get_effective_ids();
+        if euid == ROOT_UID {
+            self.getuid
+                .store(uid as i32, interface::RustAtomicOrdering::Relaxed);
+        } else if uid != ruid {
+            return syscall_error(
+                Errno::EPERM,
+                "setuid",
+                "the caller is not privileged to switch to this user id",
+            );
+        }
+        self.geteuid
+            .store(uid as i32, interface::RustAtomicOrdering::Relaxed);
+        0
+    }
+
+    /// ### Description
+    ///
+    /// The `seteuid_syscall` sets the effective user id of the calling
+    /// process, which is the id filesystem permission checks are made
+    /// against. The superuser may set any effective user id, while other
+    /// callers may only switch between their real and current effective
+    /// user id.
+    ///
+    /// ### Arguments
+    ///
+    /// * `euid` - the effective user id to switch to
+    ///
+    /// ### Returns
+    ///
+    /// Returns 0 on success.
+    ///
+    /// ### Errors
+    ///
+    /// * `EINVAL` - `euid` is -1, which is not a valid user id
+    /// * `EPERM` - the caller is not the superuser and `euid` is neither its
+    ///   real nor its current effective user id
+    ///
+    /// To learn more about the syscall, see
+    /// [seteuid(2)](https://man7.org/linux/man-pages/man2/seteuid.2.html)
+    pub fn seteuid_syscall(&self, euid: u32) -> i32 {
+        if euid == NOCHANGE_ID {
+            return syscall_error(Errno::EINVAL, "seteuid", "the user id is not valid");
+        }
+        let (ruid, _) = self.get_real_ids();
+        let (cureuid, _) = self.get_effective_ids();
+        if cureuid != ROOT_UID && euid != ruid && euid != cureuid {
+            return syscall_error(
+                Errno::EPERM,
+                "seteuid",
+                "the caller is not privileged to switch to this user id",
+            );
+        }
+        self.geteuid
+            .store(euid as i32, interface::RustAtomicOrdering::Relaxed);
+        0
+    }
+
+    /// ### Description
+    ///
+    /// The `setgid_syscall` sets the group id of the calling process. If the
+    /// caller is the superuser, both the real and the effective group id are
+    /// set. Otherwise only the effective group id may be set, and only back
+    /// to the real group id.
+    ///
+    /// ### Arguments
+    ///
+    /// * `gid` - the group id to switch to
+    ///
+    /// ### Returns
+    ///
+    /// Returns 0 on success.
+    ///
+    /// ### Errors
+    ///
+    /// * `EINVAL` - `gid` is -1, which is not a valid group id
+    /// * `EPERM` - the caller is not the superuser and `gid` is not its real
+    ///   group id
+    ///
+    /// To learn more about the syscall, see
+    /// [setgid(2)](https://man7.org/linux/man-pages/man2/setgid.2.html)
+    pub fn setgid_syscall(&self, gid: u32) -> i32 {
+        if gid == NOCHANGE_ID {
+            return syscall_error(Errno::EINVAL, "setgid", "the group id is not valid");
+        }
+        let (_, rgid) = self.get_real_ids();
+        let (euid, _) = self.get_effective_ids();
+        if euid == ROOT_UID {
+            self.getgid
+                .store(gid as i32, interface::RustAtomicOrdering::Relaxed);
+        } else if gid != rgid {
+            return syscall_error(
+                Errno::EPERM,
+                "setgid",
+                "the caller is not privileged to switch to this group id",
+            );
+        }
+        self.getegid
+            .store(gid as i32, interface::RustAtomicOrdering::Relaxed);
+        0
+    }
+
+    /// ### Description
+    ///
+    /// The `setegid_syscall` sets the effective group id of the calling
+    /// process. The superuser may set any effective group id, while other
+    /// callers may only switch between their real and current effective
+    /// group id.
+    ///
+    /// ### Arguments
+    ///
+    /// * `egid` - the effective group id to switch to
+    ///
+    /// ### Returns
+    ///
+    /// Returns 0 on success.
+    ///
+    /// ### Errors
+    ///
+    /// * `EINVAL` - `egid` is -1, which is not a valid group id
+    /// * `EPERM` - the caller is not the superuser and `egid` is neither its
+    ///   real nor its current effective group id
+    ///
+    /// To learn more about the syscall, see
+    /// [setegid(2)](https://man7.org/linux/man-pages/man2/setegid.2.html)
+    pub fn setegid_syscall(&self, egid: u32) -> i32 {
+        if egid == NOCHANGE_ID {
+            return syscall_error(Errno::EINVAL, "setegid", "the group id is not valid");
+        }
+        let (_, rgid) = self.get_real_ids();
+        let (euid, curegid) = self.get_effective_ids();
+        if euid != ROOT_UID && egid != rgid && egid != curegid {
+            return syscall_error(
+                Errno::EPERM,
+                "setegid",
+                "the caller is not privileged to switch to this group id",
+            );
+        }
+        self.getegid
+            .store(egid as i32, interface::RustAtomicOrdering::Relaxed);
+        0
+    }
+
+    pub fn sigaction_syscall(
+        &self,
+        sig: i32,
+        act: Option<&interface::SigactionStruct>,
+        oact: Option<&mut interface::SigactionStruct>,
+    ) -> i32 {
+        if let Some(some_oact) = oact {
+            let old_sigactionstruct = self.signalhandler.get(&sig);
+
+            if let Some(entry) = old_sigactionstruct {
+                some_oact.clone_from(entry.value());
+            } else {
+                some_oact.clone_from(&interface::SigactionStruct::default()); // leave handler field as NULL
+            }
+        }
+
+        if let Some(some_act) = act {
             if sig == 9 || sig == 19 {
                 // Disallow changing the action for SIGKILL and SIGSTOP
                 return syscall_error(
@@ -768,21 +1607,92 @@ impl Cage {
         0
     }
 
+    /// ### Description
+    ///
+    /// `kill_syscall` sends the signal `sig` to one or more cages, selected
+    /// by `cage_id`. Only cages the caller is permitted to signal receive it:
+    /// the superuser may signal any cage, and other users only cages whose
+    /// real user id matches the caller's real or effective user id. A cage
+    /// whose main thread has the signal blocked is left with it pending, from
+    /// where it is delivered once unblocked or can be read from a signalfd.
+    ///
+    /// ### Arguments
+    ///
+    /// * `cage_id` : which cages to signal:
+    ///   - `cage_id > 0` signals the cage with that id
+    ///   - `cage_id == 0` signals every cage in the caller's process group
+    ///   - `cage_id == -1` signals every cage except the utility cage, the init
+    ///     cage and the caller
+    ///   - `cage_id < -1` signals every cage in the process group `-cage_id`
+    /// * `sig` : the signal to send, or 0 to only check that the cages exist
+    ///   and may be signaled
+    ///
+    /// ### Returns
+    ///
+    /// On success, i.e. if at least one cage was signaled, 0 is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * EINVAL - `sig` or `cage_id` is out of range
+    /// * ESRCH - no cage is selected by `cage_id`
+    /// * EPERM - the caller may not signal any of the selected cages
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/kill.2.html]
     pub fn kill_syscall(&self, cage_id: i32, sig: i32) -> i32 {
-        if (cage_id < 0) || (cage_id >= interface::MAXCAGEID) {
-            return syscall_error(Errno::EINVAL, "sigkill", "Invalid cage id.");
+        if !(0..=SIGNAL_MAX).contains(&sig) {
+            return syscall_error(Errno::EINVAL, "kill", "Invalid signal number");
+        }
+        if cage_id >= interface::MAXCAGEID {
+            return syscall_error(Errno::EINVAL, "kill", "Invalid cage id.");
         }
 
-        if let Some(cage) = interface::cagetable_getref_opt(cage_id as u64) {
-            interface::lind_threadkill(
-                cage.main_threadid
-                    .load(interface::RustAtomicOrdering::Relaxed),
-                sig,
-            );
-            return 0;
+        let targets: Vec<interface::RustRfc<Cage>> = if cage_id > 0 {
+            interface::cagetable_getref_opt(cage_id as u64)
+                .into_iter()
+                .collect()
         } else {
+            let ourpgid = self.pgid.load(interface::RustAtomicOrdering::Relaxed);
+            Self::live_cages()
+                .into_iter()
+                .filter(|cage| {
+                    let pgid = cage.pgid.load(interface::RustAtomicOrdering::Relaxed);
+                    match cage_id {
+                        0 => pgid == ourpgid,
+                        -1 => cage.cageid > INIT_CAGEID && cage.cageid != self.cageid,
+                        _ => pgid == (cage_id as i64).unsigned_abs(),
+                    }
+                })
+                .collect()
+        };
+        if targets.is_empty() {
             return syscall_error(Errno::ESRCH, "kill", "Target cage does not exist");
         }
+
+        let (ouruid, oureuid) = (self.get_real_ids().0, self.get_effective_ids().0);
+        let mut signaled = false;
+        for cage in targets {
+            let theiruid = cage.get_real_ids().0;
+            if oureuid != ROOT_UID && ouruid != theiruid && oureuid != theiruid {
+                continue;
+            }
+            signaled = true;
+            // the null signal only checks that the cage could be signaled
+            if sig != 0 {
+                cage.send_signal(sig);
+            }
+        }
+        if !signaled {
+            return syscall_error(
+                Errno::EPERM,
+                "kill",
+                "The caller is not permitted to signal the target cage",
+            );
+        }
+        0
     }
 
     pub fn sigprocmask_syscall(
@@ -814,7 +1724,15 @@ impl Cage {
                 SIG_UNBLOCK => {
                     // Unblock signals in set
                     let newset = curr_sigset & !*some_set;
-                    let pendingsignals = curr_sigset & some_set;
+                    // the signals that were left pending while they were blocked are
+                    // delivered now
+                    let pendingsignals = match self.pendingsigset.get(&pthreadid) {
+                        Some(pending) => {
+                            pending.fetch_and(!*some_set, interface::RustAtomicOrdering::Relaxed)
+                                & some_set
+                        }
+                        None => 0,
+                    };
                     sigset.store(newset, interface::RustAtomicOrdering::Relaxed);
                     self.send_pending_signals(pendingsignals, pthreadid);
                     0
@@ -865,32 +1783,587 @@ impl Cage {
         0
     }
 
-    pub fn getrlimit(&self, res_type: u64, rlimit: &mut Rlimit) -> i32 {
-        match res_type {
-            RLIMIT_NOFILE => {
-                rlimit.rlim_cur = NOFILE_CUR;
-                rlimit.rlim_max = NOFILE_MAX;
+    /// ### Description
+    ///
+    /// `timerfd_create_syscall` creates a timer that notifies of its
+    /// expirations through a file descriptor rather than with a signal. The
+    /// timer starts out disarmed and is set with `timerfd_settime_syscall`.
+    /// Reading 8 bytes from the descriptor returns the number of times the
+    /// timer has expired since it was set or last read, blocking until it
+    /// expires at least once unless the descriptor is nonblocking. The
+    /// descriptor is readable for select, poll and epoll while there are
+    /// expirations to read.
+    ///
+    /// ### Arguments
+    ///
+    /// * `clockid` : the clock the timer measures time with, CLOCK_REALTIME or
+    ///   CLOCK_MONOTONIC
+    /// * `flags` : any of TFD_NONBLOCK and TFD_CLOEXEC
+    ///
+    /// ### Returns
+    ///
+    /// On success, the new file descriptor is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * EINVAL - `clockid` is not supported, or `flags` has an unsupported
+    ///   flag set
+    /// * ENFILE - no available file descriptors
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/timerfd_create.2.html]
+    pub fn timerfd_create_syscall(&self, clockid: i32, flags: i32) -> i32 {
+        if clockid != CLOCK_REALTIME && clockid != CLOCK_MONOTONIC {
+            return syscall_error(Errno::EINVAL, "timerfd_create", "unsupported clock");
+        }
+        if flags & !(TFD_NONBLOCK | TFD_CLOEXEC) != 0 {
+            return syscall_error(Errno::EINVAL, "timerfd_create", "invalid flags");
+        }
+
+        let (fd, guardopt) = self.get_next_fd(None);
+        if fd < 0 {
+            return fd;
+        }
+        let fdoption = &mut *guardopt.unwrap();
+        let _insertval = fdoption.insert(TimerFd(TimerFdDesc {
+            timer: interface::RustRfc::new(interface::ExpirationTimer::new()),
+            clockid,
+            flags: O_RDONLY | flags,
+            advlock: interface::RustRfc::new(interface::AdvisoryLock::new()),
+        }));
+        fd
+    }
+
+    /// ### Description
+    ///
+    /// `timerfd_settime_syscall` arms or disarms the timer of a timerfd. The
+    /// timer first expires once `new_value.it_value` has passed, or at that
+    /// time of its clock with TFD_TIMER_ABSTIME, and from then on every
+    /// `new_value.it_interval` if that is nonzero. A zero `it_value` disarms
+    /// the timer. Any expirations that haven't been read are dropped.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fd` : the timerfd
+    /// * `flags` : 0 or TFD_TIMER_ABSTIME
+    /// * `new_value` : the new setting of the timer
+    /// * `old_value` : if not null, receives the previous setting of the timer
+    ///   the way `timerfd_gettime_syscall` returns it
+    ///
+    /// ### Returns
+    ///
+    /// On success, 0 is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * EBADF - `fd` is not a valid file descriptor
+    /// * EFAULT - `new_value` is null
+    /// * EINVAL - `fd` is not a timerfd, `flags` has an unsupported flag set,
+    ///   or a time in `new_value` is negative or has more than 999,999,999
+    ///   nanoseconds
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/timerfd_settime.2.html]
+    pub fn timerfd_settime_syscall(
+        &self,
+        fd: i32,
+        flags: i32,
+        new_value: Option<&interface::ITimerSpec>,
+        old_value: Option<&mut interface::ITimerSpec>,
+    ) -> i32 {
+        let (timer, clockid) = match self._get_timerfd(fd, "timerfd_settime") {
+            Ok(timerfd) => timerfd,
+            Err(e) => return e,
+        };
+        let new_value = match new_value {
+            Some(new_value) => new_value,
+            None => return syscall_error(Errno::EFAULT, "timerfd_settime", "new_value is null"),
+        };
+        if flags & !TFD_TIMER_ABSTIME != 0 {
+            return syscall_error(Errno::EINVAL, "timerfd_settime", "invalid flags");
+        }
+        let (mut value, interval) = match (
+            Self::_timespec_to_duration(&new_value.it_value),
+            Self::_timespec_to_duration(&new_value.it_interval),
+        ) {
+            (Some(value), Some(interval)) => (value, interval),
+            _ => return syscall_error(Errno::EINVAL, "timerfd_settime", "time is out of range"),
+        };
+        if flags & TFD_TIMER_ABSTIME != 0 && !value.is_zero() {
+            // a time that has already passed makes the timer expire right away
+            let now = interface::clock_now(clockid == CLOCK_MONOTONIC);
+            value = interface::rust_max(
+                value.saturating_sub(now),
+                interface::RustDuration::from_nanos(1),
+            );
+        }
+
+        let (old_remaining, old_interval) = timer.set_time(value, interval);
+        if let Some(old_value) = old_value {
+            old_value.it_value = Self::_duration_to_timespec(old_remaining);
+            old_value.it_interval = Self::_duration_to_timespec(old_interval);
+        }
+        0
+    }
+
+    /// ### Description
+    ///
+    /// `timerfd_gettime_syscall` returns the setting of the timer of a
+    /// timerfd: the time left until it next expires, which is zero if it is
+    /// disarmed, and the interval it expires at.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fd` : the timerfd
+    /// * `curr_value` : receives the setting of the timer
+    ///
+    /// ### Returns
+    ///
+    /// On success, 0 is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * EBADF - `fd` is not a valid file descriptor
+    /// * EINVAL - `fd` is not a timerfd
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/timerfd_gettime.2.html]
+    pub fn timerfd_gettime_syscall(&self, fd: i32, curr_value: &mut interface::ITimerSpec) -> i32 {
+        let (timer, _) = match self._get_timerfd(fd, "timerfd_gettime") {
+            Ok(timerfd) => timerfd,
+            Err(e) => return e,
+        };
+        let (remaining, interval) = timer.get_time();
+        curr_value.it_value = Self::_duration_to_timespec(remaining);
+        curr_value.it_interval = Self::_duration_to_timespec(interval);
+        0
+    }
+
+    // Looks up the timer and clock of a timerfd
+    fn _get_timerfd(
+        &self,
+        fd: i32,
+        syscallname: &str,
+    ) -> Result<(interface::RustRfc<interface::ExpirationTimer>, i32), i32> {
+        let checkedfd = match self.get_filedescriptor(fd) {
+            Ok(checkedfd) => checkedfd,
+            Err(_) => {
+                return Err(syscall_error(
+                    Errno::EBADF,
+                    syscallname,
+                    "invalid file descriptor",
+                ))
             }
-            RLIMIT_STACK => {
-                rlimit.rlim_cur = STACK_CUR;
-                rlimit.rlim_max = STACK_MAX;
+        };
+        let unlocked_fd = checkedfd.read();
+        match &*unlocked_fd {
+            Some(TimerFd(timerfd_filedesc_obj)) => Ok((
+                timerfd_filedesc_obj.timer.clone(),
+                timerfd_filedesc_obj.clockid,
+            )),
+            Some(_) => Err(syscall_error(
+                Errno::EINVAL,
+                syscallname,
+                "file descriptor is not a timerfd",
+            )),
+            None => Err(syscall_error(
+                Errno::EBADF,
+                syscallname,
+                "invalid file descriptor",
+            )),
+        }
+    }
+
+    fn _timespec_to_duration(ts: &interface::TimeSpec) -> Option<interface::RustDuration> {
+        if ts.tv_sec < 0 || !(0..1_000_000_000).contains(&ts.tv_nsec) {
+            return None;
+        }
+        Some(interface::RustDuration::new(
+            ts.tv_sec as u64,
+            ts.tv_nsec as u32,
+        ))
+    }
+
+    fn _duration_to_timespec(duration: interface::RustDuration) -> interface::TimeSpec {
+        interface::TimeSpec {
+            tv_sec: duration.as_secs() as i64,
+            tv_nsec: duration.subsec_nanos() as i64,
+        }
+    }
+
+    // Reads the number of expirations of a timer into buf, which has to hold 8
+    // bytes. A blocking read sleeps until the timer expires, checking for
+    // cancellation in between
+    pub(crate) fn _read_timerfd(
+        &self,
+        timer: &interface::ExpirationTimer,
+        nonblocking: bool,
+        buf: *mut u8,
+        count: usize,
+    ) -> i32 {
+        if count < 8 {
+            return syscall_error(
+                Errno::EINVAL,
+                "read",
+                "a timerfd has to be read 8 bytes at a time",
+            );
+        }
+        loop {
+            let expirations = timer.take_expirations();
+            if expirations > 0 {
+                let bytes = expirations.to_ne_bytes();
+                unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), buf, 8) };
+                return 8;
+            }
+            if nonblocking {
+                return syscall_error(
+                    Errno::EAGAIN,
+                    "read",
+                    "the timer hasn't expired since it was last read",
+                );
             }
-            _ => return -1,
+            if self
+                .cancelstatus
+                .load(interface::RustAtomicOrdering::Relaxed)
+            {
+                loop {
+                    interface::cancelpoint(self.cageid);
+                }
+            }
+            if interface::sigcheck() {
+                return syscall_error(Errno::EINTR, "read", "interrupted function call");
+            }
+            let nap = match timer.until_next_expiration() {
+                Some(remaining) => interface::rust_min(remaining, BLOCKING_READ_SLICE),
+                None => BLOCKING_READ_SLICE,
+            };
+            interface::sleep(nap);
         }
-        0
     }
 
-    pub fn setrlimit(&self, res_type: u64, _limit_value: u64) -> i32 {
-        match res_type {
-            RLIMIT_NOFILE => {
-                if NOFILE_CUR > NOFILE_MAX {
-                    -1
-                } else {
-                    0
+    /// ### Description
+    ///
+    /// `signalfd_syscall` creates a file descriptor that signals can be read
+    /// from, or changes which signals an existing one accepts. Reading from
+    /// the descriptor takes signals in its mask that are pending for the
+    /// calling cage, so that they are no longer delivered, and returns a
+    /// `SignalfdSiginfo` for each one. A signal is only left pending for a
+    /// cage that has it blocked, so the signals in the mask should be blocked
+    /// with `sigprocmask_syscall`. A read blocks until such a signal is
+    /// pending unless the descriptor is nonblocking. The descriptor is
+    /// readable for select, poll and epoll while one is.
+    ///
+    /// ### Arguments
+    ///
+    /// * `fd` : -1 to create a new signalfd, or an existing signalfd whose mask
+    ///   is replaced
+    /// * `mask` : the signals to accept. SIGKILL and SIGSTOP are silently left
+    ///   out
+    /// * `flags` : any of SFD_NONBLOCK and SFD_CLOEXEC, which only apply to a
+    ///   new signalfd
+    ///
+    /// ### Returns
+    ///
+    /// On success, the signalfd is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * EBADF - `fd` is neither -1 nor a valid file descriptor
+    /// * EFAULT - `mask` is null
+    /// * EINVAL - `fd` is not a signalfd, or `flags` has an unsupported flag
+    ///   set
+    /// * ENFILE - no available file descriptors
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/signalfd.2.html]
+    pub fn signalfd_syscall(
+        &self,
+        fd: i32,
+        mask: Option<&interface::SigsetType>,
+        flags: i32,
+    ) -> i32 {
+        let mask = match mask {
+            Some(mask) => *mask,
+            None => return syscall_error(Errno::EFAULT, "signalfd", "mask is null"),
+        };
+        if flags & !(SFD_NONBLOCK | SFD_CLOEXEC) != 0 {
+            return syscall_error(Errno::EINVAL, "signalfd", "invalid flags");
+        }
+        let mask = interface::lind_sigdelset(interface::lind_sigdelset(mask, SIGKILL), SIGSTOP);
+
+        if fd != -1 {
+            let checkedfd = match self.get_filedescriptor(fd) {
+                Ok(checkedfd) => checkedfd,
+                Err(_) => {
+                    return syscall_error(Errno::EBADF, "signalfd", "invalid file descriptor")
+                }
+            };
+            let unlocked_fd = checkedfd.read();
+            return match &*unlocked_fd {
+                Some(SignalFd(signalfd_filedesc_obj)) => {
+                    signalfd_filedesc_obj
+                        .mask
+                        .store(mask, interface::RustAtomicOrdering::Relaxed);
+                    fd
+                }
+                Some(_) => syscall_error(
+                    Errno::EINVAL,
+                    "signalfd",
+                    "file descriptor is not a signalfd",
+                ),
+                None => syscall_error(Errno::EBADF, "signalfd", "invalid file descriptor"),
+            };
+        }
+
+        let (newfd, guardopt) = self.get_next_fd(None);
+        if newfd < 0 {
+            return newfd;
+        }
+        let fdoption = &mut *guardopt.unwrap();
+        let _insertval = fdoption.insert(SignalFd(SignalFdDesc {
+            mask: interface::RustRfc::new(interface::RustAtomicU64::new(mask)),
+            flags: O_RDONLY | flags,
+            advlock: interface::RustRfc::new(interface::AdvisoryLock::new()),
+        }));
+        newfd
+    }
+
+    // Reads as many of the pending signals in mask as fit in buf, which has to
+    // hold at least one SignalfdSiginfo. A blocking read waits until one of
+    // them is pending, checking for cancellation in between
+    pub(crate) fn _read_signalfd(
+        &self,
+        mask: interface::SigsetType,
+        nonblocking: bool,
+        buf: *mut u8,
+        count: usize,
+    ) -> i32 {
+        let infosize = std::mem::size_of::<interface::SignalfdSiginfo>();
+        if count < infosize {
+            return syscall_error(
+                Errno::EINVAL,
+                "read",
+                "buffer is too small for a signalfd_siginfo",
+            );
+        }
+        loop {
+            let mut infos = vec![];
+            while infos.len() < count / infosize {
+                match self.take_pending_signal(mask) {
+                    Some(signo) => infos.push(interface::SignalfdSiginfo {
+                        ssi_signo: signo as u32,
+                        ssi_code: SI_USER,
+                        ..Default::default()
+                    }),
+                    None => break,
                 }
-                //FIXME: not implemented yet to update value in program
             }
-            _ => -1,
+            if !infos.is_empty() {
+                let bytecount = infos.len() * infosize;
+                unsafe {
+                    std::ptr::copy_nonoverlapping(infos.as_ptr() as *const u8, buf, bytecount)
+                };
+                return bytecount as i32;
+            }
+            if nonblocking {
+                return syscall_error(
+                    Errno::EAGAIN,
+                    "read",
+                    "none of the signals of the signalfd are pending",
+                );
+            }
+            if self
+                .cancelstatus
+                .load(interface::RustAtomicOrdering::Relaxed)
+            {
+                loop {
+                    interface::cancelpoint(self.cageid);
+                }
+            }
+            if interface::sigcheck() {
+                return syscall_error(Errno::EINTR, "read", "interrupted function call");
+            }
+            interface::sleep(BLOCKING_READ_SLICE);
+        }
+    }
+
+    /// ### Description
+    ///
+    /// `getrlimit_syscall` returns the soft and hard limit the calling cage
+    /// has on a resource.
+    ///
+    /// ### Arguments
+    ///
+    /// * `resource` : the resource, one of the RLIMIT_* constants
+    /// * `rlimit` : receives the soft and hard limit
+    ///
+    /// ### Returns
+    ///
+    /// On success, 0 is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * EINVAL - `resource` is not valid
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/getrlimit.2.html]
+    pub fn getrlimit_syscall(&self, resource: u64, rlimit: &mut Rlimit) -> i32 {
+        self._prlimit_helper("getrlimit", self, resource, None, Some(rlimit))
+    }
+
+    /// ### Description
+    ///
+    /// `setrlimit_syscall` sets the soft and hard limit the calling cage has
+    /// on a resource. Any cage may lower its limits, or raise its soft limit
+    /// up to the hard one, while only the superuser may raise a hard limit.
+    /// The limits are inherited by children of the cage and kept across
+    /// exec. RLIMIT_NOFILE limits the fd numbers the cage may allocate,
+    /// RLIMIT_FSIZE the size files may be grown to, and RLIMIT_NPROC the
+    /// number of cages of the same user that may run when the cage forks.
+    ///
+    /// ### Arguments
+    ///
+    /// * `resource` : the resource, one of the RLIMIT_* constants
+    /// * `rlimit` : the new soft and hard limit
+    ///
+    /// ### Returns
+    ///
+    /// On success, 0 is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * EINVAL - `resource` is not valid, or the soft limit exceeds the hard
+    ///   limit
+    /// * EPERM - an unprivileged caller tried to raise the hard limit, or the
+    ///   hard RLIMIT_NOFILE was raised above NOFILE_MAX
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/setrlimit.2.html]
+    pub fn setrlimit_syscall(&self, resource: u64, rlimit: &Rlimit) -> i32 {
+        self._prlimit_helper("setrlimit", self, resource, Some(rlimit), None)
+    }
+
+    /// ### Description
+    ///
+    /// `prlimit64_syscall` combines `getrlimit_syscall` and
+    /// `setrlimit_syscall`, and may act on another cage. The old limits are
+    /// returned before the new ones are set. Acting on another cage requires
+    /// the caller to be the superuser or for the real and effective user id
+    /// of the target to match the real user id of the caller.
+    ///
+    /// ### Arguments
+    ///
+    /// * `pid` : the cage whose limits to get or set, or 0 for the caller
+    /// * `resource` : the resource, one of the RLIMIT_* constants
+    /// * `new_limit` : if given, the new soft and hard limit
+    /// * `old_limit` : if given, receives the previous soft and hard limit
+    ///
+    /// ### Returns
+    ///
+    /// On success, 0 is returned.
+    ///
+    /// ### Errors
+    ///
+    /// * EINVAL - `resource` is not valid, or the new soft limit exceeds the
+    ///   new hard limit
+    /// * ESRCH - no cage with id `pid` exists
+    /// * EPERM - the caller may not act on the target cage, an unprivileged
+    ///   caller tried to raise the hard limit, or the hard RLIMIT_NOFILE was
+    ///   raised above NOFILE_MAX
+    ///
+    /// ### Panics
+    ///
+    /// This system call has no scenarios where it panics
+    ///
+    /// For more information please refer to - [https://man7.org/linux/man-pages/man2/prlimit.2.html]
+    pub fn prlimit64_syscall(
+        &self,
+        pid: i32,
+        resource: u64,
+        new_limit: Option<&Rlimit>,
+        old_limit: Option<&mut Rlimit>,
+    ) -> i32 {
+        if pid == 0 || pid as u64 == self.cageid {
+            return self._prlimit_helper("prlimit64", self, resource, new_limit, old_limit);
+        }
+        let target = match Self::cage_by_pid(pid) {
+            Some(cage) => cage,
+            None => return syscall_error(Errno::ESRCH, "prlimit64", "No cage with this id exists"),
+        };
+        let (ruid, _) = self.get_real_ids();
+        if self.get_effective_ids().0 != ROOT_UID
+            && (target.get_real_ids().0 != ruid || target.get_effective_ids().0 != ruid)
+        {
+            return syscall_error(
+                Errno::EPERM,
+                "prlimit64",
+                "The caller may not change the limits of the target cage",
+            );
+        }
+        self._prlimit_helper("prlimit64", &target, resource, new_limit, old_limit)
+    }
+
+    // gets and sets the limits of the target cage on behalf of the calling cage
+    fn _prlimit_helper(
+        &self,
+        syscallname: &str,
+        target: &Cage,
+        resource: u64,
+        new_limit: Option<&Rlimit>,
+        old_limit: Option<&mut Rlimit>,
+    ) -> i32 {
+        if resource >= RLIM_NLIMITS as u64 {
+            return syscall_error(Errno::EINVAL, syscallname, "Invalid resource");
+        }
+
+        let mut rlimits = target.rlimits.write();
+        let current = rlimits[resource as usize];
+        if let Some(new_limit) = new_limit {
+            if new_limit.rlim_cur > new_limit.rlim_max {
+                return syscall_error(
+                    Errno::EINVAL,
+                    syscallname,
+                    "The soft limit exceeds the hard limit",
+                );
+            }
+            if new_limit.rlim_max > current.rlim_max && self.get_effective_ids().0 != ROOT_UID {
+                return syscall_error(
+                    Errno::EPERM,
+                    syscallname,
+                    "Only the superuser may raise a hard limit",
+                );
+            }
+            // the fd table of a cage can't grow beyond NOFILE_MAX
+            if resource == RLIMIT_NOFILE && new_limit.rlim_max > NOFILE_MAX {
+                return syscall_error(
+                    Errno::EPERM,
+                    syscallname,
+                    "The hard limit on open files may not exceed NOFILE_MAX",
+                );
+            }
+            rlimits[resource as usize] = *new_limit;
+        }
+        if let Some(old_limit) = old_limit {
+            *old_limit = current;
         }
+        0
     }
 }
@@ -12,6 +12,11 @@ use crate::interface;
 pub const DEFAULT_UID: u32 = 1000;
 pub const DEFAULT_GID: u32 = 1000;
 
+// The superuser, which bypasses permission checks and may change ownership
+pub const ROOT_UID: u32 = 0;
+// (uid_t)-1 and (gid_t)-1, which leave an id unchanged in chown and the set*id calls
+pub const NOCHANGE_ID: u32 = u32::MAX;
+
 // RESOURCE LIMITS
 
 pub const SIGNAL_MAX: i32 = 64;
@@ -22,14 +27,43 @@ pub const NOFILE_MAX: u64 = 4 * 1024;
 pub const STACK_CUR: u64 = 8192 * 1024;
 pub const STACK_MAX: u64 = 1 << 32;
 
-pub const RLIMIT_STACK: u64 = 0;
-pub const RLIMIT_NOFILE: u64 = 1;
+pub const CORE_CUR: u64 = 0;
+
+// Resources whose use getrlimit/setrlimit limit, numbered as on Linux. Each
+// cage stores a soft and hard limit for all of them, but only those for
+// FSIZE, NPROC and NOFILE are enforced
+pub const RLIMIT_CPU: u64 = 0;
+pub const RLIMIT_FSIZE: u64 = 1;
+pub const RLIMIT_DATA: u64 = 2;
+pub const RLIMIT_STACK: u64 = 3;
+pub const RLIMIT_CORE: u64 = 4;
+pub const RLIMIT_RSS: u64 = 5;
+pub const RLIMIT_NPROC: u64 = 6;
+pub const RLIMIT_NOFILE: u64 = 7;
+pub const RLIMIT_MEMLOCK: u64 = 8;
+pub const RLIMIT_AS: u64 = 9;
+pub const RLIMIT_LOCKS: u64 = 10;
+pub const RLIMIT_SIGPENDING: u64 = 11;
+pub const RLIMIT_MSGQUEUE: u64 = 12;
+pub const RLIMIT_NICE: u64 = 13;
+pub const RLIMIT_RTPRIO: u64 = 14;
+pub const RLIMIT_RTTIME: u64 = 15;
+pub const RLIM_NLIMITS: usize = 16;
+
+pub const RLIM_INFINITY: u64 = u64::MAX;
 
 // Constants for exit_syscall status
 
 pub const EXIT_SUCCESS: i32 = 0;
 pub const EXIT_FAILURE: i32 = 1;
 
+// The init cage, which adopts the children of exiting cages
+pub const INIT_CAGEID: u64 = 1;
+
+// Options for the wait calls
+pub const WNOHANG: i32 = 1; // return right away if no child has changed state
+pub const WUNTRACED: i32 = 2; // also report children that have stopped
+
 // Signal Table (x86/ARM)
 // Based on https://man7.org/linux/man-pages/man7/signal.7.html
 pub const SIGHUP: i32 = 1;
@@ -75,3 +109,21 @@ pub const SIG_BLOCK: i32 = 0;
 pub const SIG_UNBLOCK: i32 = 1;
 pub const SIG_SETMASK: i32 = 2;
 pub const ITIMER_REAL: i32 = 0;
+
+// Flags for eventfd, timerfd_create and signalfd. Asking for a nonblocking
+// descriptor or for close-on-exec uses the values of O_NONBLOCK and O_CLOEXEC
+pub const EFD_SEMAPHORE: i32 = 1;
+pub const EFD_NONBLOCK: i32 = 0o4000;
+pub const EFD_CLOEXEC: i32 = 0o2000000;
+pub const TFD_NONBLOCK: i32 = 0o4000;
+pub const TFD_CLOEXEC: i32 = 0o2000000;
+pub const TFD_TIMER_ABSTIME: i32 = 1; // flag for timerfd_settime
+pub const SFD_NONBLOCK: i32 = 0o4000;
+pub const SFD_CLOEXEC: i32 = 0o2000000;
+
+// Clocks a timerfd can measure time with
+pub const CLOCK_REALTIME: i32 = 0;
+pub const CLOCK_MONOTONIC: i32 = 1;
+
+// The si_code of a signal sent by kill
+pub const SI_USER: i32 = 0;
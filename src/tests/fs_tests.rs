@@ -6,10 +6,10 @@ pub mod fs_tests {
     use crate::interface;
     use crate::safeposix::syscalls::fs_calls::*;
     use crate::safeposix::{cage::*, dispatcher::*, filesystem};
-    use interface::IovecStruct;
+    use interface::{IovecStruct, StorageBackend};
     use libc::{c_void, O_DIRECTORY};
     use std::fs::OpenOptions;
-    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
 
     #[test]
     pub fn ut_lind_fs_simple() {
@@ -1344,6 +1344,265 @@ pub mod fs_tests {
         lindrustfinalize();
     }
 
+    #[test]
+    pub fn ut_lind_fs_ioctl_ficlone() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        let srcfd = cage.open_syscall("/clonesrc", O_CREAT | O_RDWR, S_IRWXA);
+        let destfd = cage.open_syscall("/clonedest", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(srcfd >= 0 && destfd >= 0);
+        assert_eq!(cage.write_syscall(srcfd, str2cbuf("original data"), 13), 13);
+        assert_eq!(
+            cage.write_syscall(destfd, str2cbuf("to be replaced by the clone"), 27),
+            27
+        );
+
+        //FICLONE takes the descriptor of the source by value
+        let src: IoctlPtrUnion = IoctlPtrUnion {
+            int_ptr: srcfd as usize as *mut i32,
+        };
+        assert_eq!(cage.ioctl_syscall(destfd, FICLONE, src), 0);
+        let mut statdata = StatData::default();
+        assert_eq!(cage.fstat_syscall(destfd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 13);
+        let mut read_buf = sizecbuf(13);
+        assert_eq!(cage.pread_syscall(destfd, read_buf.as_mut_ptr(), 13, 0), 13);
+        assert_eq!(cbuf2str(&read_buf), "original data");
+
+        //the host directory backend shares the clone's data with the source
+        let mut srcstat = StatData::default();
+        assert_eq!(cage.fstat_syscall(srcfd, &mut srcstat), 0);
+        let srcdata = format!("{}{}", FILEDATAPREFIX, srcstat.st_ino);
+        assert_eq!(std::fs::metadata(&srcdata).unwrap().nlink(), 2);
+
+        //and writing to either one leaves the other as it was
+        assert_eq!(cage.pwrite_syscall(destfd, str2cbuf("CLONED"), 6, 0), 6);
+        assert_eq!(cage.pwrite_syscall(srcfd, str2cbuf("source"), 6, 7), 6);
+        assert_eq!(cage.pread_syscall(destfd, read_buf.as_mut_ptr(), 13, 0), 13);
+        assert_eq!(cbuf2str(&read_buf), "CLONEDal data");
+        assert_eq!(cage.pread_syscall(srcfd, read_buf.as_mut_ptr(), 13, 0), 13);
+        assert_eq!(cbuf2str(&read_buf), "originasource");
+        assert_eq!(std::fs::metadata(&srcdata).unwrap().nlink(), 1);
+
+        //a file can't be cloned into itself, nor into a directory or from one
+        //that is only open for writing
+        assert_eq!(
+            cage.ioctl_syscall(srcfd, FICLONE, src),
+            -(Errno::EINVAL as i32)
+        );
+        let dirfd = cage.open_syscall("/", O_RDONLY, S_IRWXA);
+        assert_eq!(
+            cage.ioctl_syscall(dirfd, FICLONE, src),
+            -(Errno::EISDIR as i32)
+        );
+        let wronlyfd = cage.open_syscall("/clonesrc", O_WRONLY, S_IRWXA);
+        let wronly: IoctlPtrUnion = IoctlPtrUnion {
+            int_ptr: wronlyfd as usize as *mut i32,
+        };
+        assert_eq!(
+            cage.ioctl_syscall(destfd, FICLONE, wronly),
+            -(Errno::EBADF as i32)
+        );
+
+        //devices and pipes are not on the file system, so nothing is shared
+        //with them
+        let devfd = cage.open_syscall("/dev/zero", O_RDONLY, S_IRWXA);
+        let mut pipefds = PipeArray::default();
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+        for fd in [devfd, pipefds.readfd] {
+            let other: IoctlPtrUnion = IoctlPtrUnion {
+                int_ptr: fd as usize as *mut i32,
+            };
+            assert_eq!(
+                cage.ioctl_syscall(destfd, FICLONE, other),
+                -(Errno::EXDEV as i32)
+            );
+        }
+
+        for fd in [
+            srcfd,
+            destfd,
+            dirfd,
+            wronlyfd,
+            devfd,
+            pipefds.readfd,
+            pipefds.writefd,
+        ] {
+            assert_eq!(cage.close_syscall(fd), 0);
+        }
+        assert_eq!(cage.unlink_syscall("/clonesrc"), 0);
+        assert_eq!(cage.unlink_syscall("/clonedest"), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_ioctl_ficlone_backends() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+
+        //clone on each kind of backend, which all share the data but the one
+        //keeping it in memory
+        let dir = tempfile::tempdir().unwrap();
+        let hostbackend = interface::storage_backend();
+        let backends: Vec<(interface::RustRfc<dyn interface::StorageBackend>, bool)> = vec![
+            (
+                interface::RustRfc::new(
+                    interface::HostDirBackend::new(dir.path().join("host")).unwrap(),
+                ),
+                true,
+            ),
+            (
+                interface::RustRfc::new(
+                    interface::ImageBackend::open(dir.path().join("fs.img")).unwrap(),
+                ),
+                true,
+            ),
+            (
+                interface::RustRfc::new(interface::MemoryBackend::new()),
+                false,
+            ),
+        ];
+        for (backend, shares) in backends {
+            interface::set_storage_backend(backend);
+            lindrustinit(0);
+            let cage = interface::cagetable_getref(1);
+            let read_file = |path: &str| {
+                let fd = cage.open_syscall(path, O_RDONLY, S_IRWXA);
+                let mut read_buf = sizecbuf(11);
+                assert_eq!(cage.pread_syscall(fd, read_buf.as_mut_ptr(), 11, 0), 11);
+                assert_eq!(cage.close_syscall(fd), 0);
+                cbuf2str(&read_buf).to_string()
+            };
+
+            let srcfd = cage.open_syscall("/clonesrc", O_CREAT | O_RDWR, S_IRWXA);
+            let mut destfd = cage.open_syscall("/clonedest", O_CREAT | O_RDWR, S_IRWXA);
+            assert!(srcfd >= 0 && destfd >= 0);
+            assert_eq!(cage.write_syscall(srcfd, str2cbuf("shared data"), 11), 11);
+            let src: IoctlPtrUnion = IoctlPtrUnion {
+                int_ptr: srcfd as usize as *mut i32,
+            };
+            if shares {
+                //writing to the clone leaves the source as it was, also once
+                //both were closed and opened again
+                assert_eq!(cage.ioctl_syscall(destfd, FICLONE, src), 0);
+                assert_eq!(cage.pwrite_syscall(destfd, str2cbuf("SHARED"), 6, 0), 6);
+                assert_eq!(cage.close_syscall(destfd), 0);
+                assert_eq!(read_file("/clonesrc"), "shared data");
+                assert_eq!(read_file("/clonedest"), "SHARED data");
+                destfd = cage.open_syscall("/clonedest", O_RDWR, S_IRWXA);
+                assert_eq!(cage.ioctl_syscall(destfd, FICLONE, src), 0);
+            } else {
+                //rather than copying, the clone is refused and nothing changes
+                assert_eq!(
+                    cage.ioctl_syscall(destfd, FICLONE, src),
+                    -(Errno::EOPNOTSUPP as i32)
+                );
+                let mut statdata = StatData::default();
+                assert_eq!(cage.fstat_syscall(destfd, &mut statdata), 0);
+                assert_eq!(statdata.st_size, 0);
+            }
+            assert_eq!(cage.close_syscall(destfd), 0);
+            assert_eq!(cage.close_syscall(srcfd), 0);
+
+            //removing one of two files sharing data leaves the other with it
+            assert_eq!(cage.unlink_syscall("/clonesrc"), 0);
+            if shares {
+                assert_eq!(read_file("/clonedest"), "shared data");
+            }
+            assert_eq!(cage.unlink_syscall("/clonedest"), 0);
+            assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+            lindrustfinalize();
+        }
+        interface::set_storage_backend(hostbackend);
+
+        //and no data is left behind once both files are gone
+        let leftover = std::fs::read_dir(dir.path().join("host"))
+            .unwrap()
+            .filter(|entry| {
+                let name = entry.as_ref().unwrap().file_name();
+                name.to_string_lossy().starts_with(FILEDATAPREFIX)
+            })
+            .count();
+        assert_eq!(leftover, 0);
+    }
+
+    #[test]
+    pub fn ut_lind_fs_ioctl_ficlone_failed() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+
+        let dir = tempfile::tempdir().unwrap();
+        let hostbackend = interface::storage_backend();
+        interface::set_storage_backend(interface::RustRfc::new(
+            interface::HostDirBackend::new(dir.path()).unwrap(),
+        ));
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
+
+        let srcfd = cage.open_syscall("/clonesrc", O_CREAT | O_RDWR, S_IRWXA);
+        let destfd = cage.open_syscall("/clonedest", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(srcfd >= 0 && destfd >= 0);
+        let buf = vec![b'a'; 2 * FS_BLOCKSIZE as usize];
+        assert_eq!(
+            cage.write_syscall(srcfd, buf.as_ptr(), buf.len()),
+            buf.len() as i32
+        );
+        assert_eq!(cage.write_syscall(destfd, str2cbuf("kept"), 4), 4);
+
+        //the data of the source goes missing underneath lind, so the clone fails
+        let mut statdata = StatData::default();
+        assert_eq!(cage.fstat_syscall(srcfd, &mut statdata), 0);
+        let srcpath = dir
+            .path()
+            .join(format!("{}{}", FILEDATAPREFIX, statdata.st_ino));
+        std::fs::remove_file(&srcpath).unwrap();
+        let mut before = FSData::default();
+        assert_eq!(cage.fstatfs_syscall(destfd, &mut before), 0);
+        let src: IoctlPtrUnion = IoctlPtrUnion {
+            int_ptr: srcfd as usize as *mut i32,
+        };
+        assert_eq!(
+            cage.ioctl_syscall(destfd, FICLONE, src),
+            -(Errno::EIO as i32)
+        );
+
+        //which gives back the space charged for the growth, and leaves the
+        //destination open with its data and size
+        let mut after = FSData::default();
+        assert_eq!(cage.fstatfs_syscall(destfd, &mut after), 0);
+        assert_eq!(after.f_bfree, before.f_bfree);
+        assert_eq!(cage.fstat_syscall(destfd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 4);
+        assert_eq!(cage.write_syscall(destfd, str2cbuf("!"), 1), 1);
+        let mut read_buf = sizecbuf(5);
+        assert_eq!(cage.pread_syscall(destfd, read_buf.as_mut_ptr(), 5, 0), 5);
+        assert_eq!(cbuf2str(&read_buf), "kept!");
+
+        assert_eq!(cage.close_syscall(destfd), 0);
+        assert_eq!(cage.close_syscall(srcfd), 0);
+        std::fs::write(&srcpath, b"").unwrap();
+        assert_eq!(cage.unlink_syscall("/clonesrc"), 0);
+        assert_eq!(cage.unlink_syscall("/clonedest"), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+        interface::set_storage_backend(hostbackend);
+    }
+
     #[test]
     pub fn ut_lind_fs_fdflags() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
@@ -1663,15 +1922,18 @@ pub mod fs_tests {
         let oldpath = "/testdir/olddir";
         let newpath = "/newpath";
 
-        // Create the directory for the oldpath with the parent not having read
-        // permission. Currently assigning "Write only" permissions
-        assert_eq!(cage.mkdir_syscall("/testdir", S_IWUSR), 0);
+        // Create the file for the oldpath, then take search permission away from
+        // its parent directory by leaving it "Write only"
+        assert_eq!(cage.mkdir_syscall("/testdir", S_IRWXU), 0);
         let fd = cage.open_syscall(oldpath, O_CREAT | O_EXCL | O_WRONLY, S_IWUSR);
         assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
+        assert_eq!(cage.chmod_syscall("/testdir", S_IWUSR), 0);
 
-        // Expect the linking to be successful, but this is a bug which must be fixed
-        // as the parent directory doesn't have read permissions due to which it should
-        // not be able to link the files.
+        // Linking fails as the parent directory of oldpath can't be searched
+        assert_eq!(cage.link_syscall(oldpath, newpath), -(Errno::EACCES as i32));
+
+        // Once search permission is restored, the link can be created
+        assert_eq!(cage.chmod_syscall("/testdir", S_IRWXU), 0);
         assert_eq!(cage.link_syscall(oldpath, newpath), 0);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
@@ -1780,1060 +2042,2009 @@ pub mod fs_tests {
     }
 
     #[test]
-    pub fn ut_lind_fs_mknod_empty_path() {
+    pub fn ut_lind_fs_metadata_log_framing() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let dev = makedev(&DevNo { major: 1, minor: 3 });
-        let path = "";
-        // Check for error when directory is empty
+
+        //start from an empty log, then rename a file within a directory
+        let fd = cage.open_syscall("/logframed", O_CREAT | O_WRONLY, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.close_syscall(fd), 0);
+        filesystem::checkpoint_metadata(&filesystem::FS_METADATA);
+        assert_eq!(cage.rename_syscall("/logframed", "/logframed2"), 0);
+
+        //the rename is logged as one transaction holding the parent and the file
+        let logread = interface::storage_backend().read_log().unwrap().unwrap();
+        let mut report = filesystem::ReplayReport::default();
+        let transactions = filesystem::parse_log(&logread, &mut report);
+        assert!(report.is_clean());
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].len(), 2);
+        let entrylen = interface::convert_bytes_to_size(&logread[0..interface::COUNTMAPSIZE]);
+
+        //a torn tail loses only the entry it cuts into
+        let mut torn = logread[..interface::COUNTMAPSIZE + entrylen - 3].to_vec();
+        let mut report = filesystem::ReplayReport::default();
+        assert_eq!(filesystem::parse_log(&torn, &mut report).len(), 0);
         assert_eq!(
-            cage.mknod_syscall(path, S_IRWXA | S_IFCHR as u32, dev),
-            -(Errno::ENOENT as i32)
+            report.damage,
+            Some(filesystem::LogDamage::TruncatedEntry { offset: 0 })
         );
+        assert_eq!(report.bytes_discarded, entrylen - 3);
+
+        //so does a log too short to hold its count
+        torn.truncate(3);
+        let mut report = filesystem::ReplayReport::default();
+        assert_eq!(filesystem::parse_log(&torn, &mut report).len(), 0);
+        assert_eq!(report.bytes_discarded, 3);
+
+        //a flipped byte in the payload is caught by the checksum
+        let mut flipped = logread.clone();
+        flipped[interface::COUNTMAPSIZE + filesystem::LOGENTRYHEADERSIZE] ^= 0xFF;
+        let mut report = filesystem::ReplayReport::default();
+        assert_eq!(filesystem::parse_log(&flipped, &mut report).len(), 0);
+        assert_eq!(
+            report.damage,
+            Some(filesystem::LogDamage::ChecksumMismatch { offset: 0 })
+        );
+        assert!(!report.is_clean());
+
+        assert_eq!(cage.unlink_syscall("/logframed2"), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_mknod_nonexisting_parent_directory() {
+    pub fn ut_lind_fs_metadata_log_replay_damaged() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let dev = makedev(&DevNo { major: 1, minor: 3 });
-        let path = "/parentdir/file";
-        // Check for error when both parent and file don't exist
-        assert_eq!(
-            cage.mknod_syscall(path, S_IRWXA | S_IFCHR as u32, dev),
-            -(Errno::ENOENT as i32)
+
+        let fd = cage.open_syscall("/replayed", O_CREAT | O_WRONLY, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.close_syscall(fd), 0);
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/replayed", &mut statdata), 0);
+        let inodenum = statdata.st_ino as usize;
+
+        //stop as if lind crashed, leaving the log behind, and lose the inode from
+        //memory so that only the log can bring it back
+        let log = filesystem::LOGMAP.write().take().unwrap();
+        log.close().unwrap();
+        filesystem::FS_METADATA.inodetable.remove(&inodenum);
+
+        //then append garbage to the log as if the next entry had been torn
+        let mut logread = interface::storage_backend().read_log().unwrap().unwrap();
+        let logsize = interface::convert_bytes_to_size(&logread[0..interface::COUNTMAPSIZE]);
+        logread.truncate(interface::COUNTMAPSIZE + logsize);
+        logread.extend_from_slice(&[0, 0, 0, 42, 1, 2, 3, 4, 5]);
+        logread[0..interface::COUNTMAPSIZE].copy_from_slice(&(logsize + 9).to_be_bytes());
+        std::fs::write(interface::LOGFILENAME, &logread).unwrap();
+
+        //replay applies everything before the damage and reports the rest
+        let report = filesystem::load_fs();
+        assert!(!report.formatted);
+        assert!(report.transactions_applied > 0);
+        assert_eq!(report.bytes_discarded, 9);
+        assert_eq!(
+            report.damage,
+            Some(filesystem::LogDamage::TruncatedEntry { offset: logsize })
+        );
+        assert_eq!(cage.stat_syscall("/replayed", &mut statdata), 0);
+        assert_eq!(statdata.st_ino as usize, inodenum);
+
+        //and the replayed state was checkpointed, leaving a fresh log
+        assert_eq!(
+            filesystem::LOGMAP.read().as_ref().unwrap().bytes_written(),
+            0
         );
+        let metadatabytes = interface::storage_backend()
+            .read_metadata()
+            .unwrap()
+            .unwrap();
+        let saved: filesystem::FilesystemMetadata =
+            interface::serde_deserialize_from_bytes(&metadatabytes).unwrap();
+        assert!(saved.inodetable.contains_key(&inodenum));
+
+        assert_eq!(cage.unlink_syscall("/replayed"), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_mknod_existing_file() {
+    pub fn ut_lind_fs_metadata_log_torn_transaction() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let dev = makedev(&DevNo { major: 1, minor: 3 });
-        let path = "/charfile";
-        // Create a special character file for the first time
-        assert_eq!(cage.mknod_syscall(path, S_IRWXA | S_IFCHR as u32, dev), 0);
 
-        // Check for error when the same file is created again
+        //the saved metadata predates the file, so only the log holds it
+        filesystem::checkpoint_metadata(&filesystem::FS_METADATA);
+        let fd = cage.open_syscall("/tornkept", O_CREAT | O_WRONLY, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.close_syscall(fd), 0);
+        let keptsize = filesystem::LOGMAP.read().as_ref().unwrap().bytes_written();
+
+        //then crash partway through writing the rename's entry
+        assert_eq!(cage.rename_syscall("/tornkept", "/tornmoved"), 0);
+        let log = filesystem::LOGMAP.write().take().unwrap();
+        log.close().unwrap();
+        let mut logread = interface::storage_backend().read_log().unwrap().unwrap();
+        let logsize = interface::convert_bytes_to_size(&logread[0..interface::COUNTMAPSIZE]);
+        assert!(logsize > keptsize);
+        logread.truncate(interface::COUNTMAPSIZE + logsize - 3);
+        std::fs::write(interface::LOGFILENAME, &logread).unwrap();
+
+        //the torn rename is skipped as a whole, the transactions before it are not
+        let report = filesystem::load_fs();
         assert_eq!(
-            cage.mknod_syscall(path, S_IRWXA | S_IFCHR as u32, dev),
-            -(Errno::EEXIST as i32)
+            report.damage,
+            Some(filesystem::LogDamage::TruncatedEntry { offset: keptsize })
+        );
+        assert_eq!(report.bytes_discarded, logsize - 3 - keptsize);
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/tornkept", &mut statdata), 0);
+        assert_eq!(statdata.st_nlink, 1);
+        assert_eq!(
+            cage.stat_syscall("/tornmoved", &mut statdata),
+            -(Errno::ENOENT as i32)
+        );
+
+        //the checkpoint keeps what was replayed and nothing of the torn entry, so
+        //the next start has nothing left to replay
+        assert_eq!(
+            filesystem::LOGMAP.read().as_ref().unwrap().bytes_written(),
+            0
+        );
+        let log = filesystem::LOGMAP.write().take().unwrap();
+        log.close().unwrap();
+        let report = filesystem::load_fs();
+        assert!(report.is_clean());
+        assert_eq!(report.transactions_applied, 0);
+        assert_eq!(cage.stat_syscall("/tornkept", &mut statdata), 0);
+        assert_eq!(
+            cage.stat_syscall("/tornmoved", &mut statdata),
+            -(Errno::ENOENT as i32)
         );
+
+        assert_eq!(cage.unlink_syscall("/tornkept"), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_mknod_invalid_modebits() {
+    pub fn ut_lind_fs_metadata_corrupt() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let dev = makedev(&DevNo { major: 1, minor: 3 });
-        let path = "/testfile";
-        let invalid_mode = 0o77777; // Invalid mode bits for testing
-                                    // Check for error when the file is being created with invalid mode
+
+        //an undecodable metadata file is set aside and a new file system made,
+        //rather than panicking
+        let log = filesystem::LOGMAP.write().take().unwrap();
+        log.close().unwrap();
+        interface::storage_backend()
+            .write_metadata(b"not metadata")
+            .unwrap();
+        let report = filesystem::load_fs();
+        assert!(report.metadata_corrupt);
+        assert!(report.formatted);
+        assert!(!report.is_clean());
         assert_eq!(
-            cage.mknod_syscall(path, invalid_mode, dev),
-            -(Errno::EPERM as i32)
+            std::fs::read(interface::METADATACORRUPTFILENAME).unwrap(),
+            b"not metadata"
+        );
+        let metadatabytes = interface::storage_backend()
+            .read_metadata()
+            .unwrap()
+            .unwrap();
+        assert!(
+            interface::serde_deserialize_from_bytes::<filesystem::FilesystemMetadata>(
+                &metadatabytes
+            )
+            .is_ok()
         );
+        std::fs::remove_file(interface::METADATACORRUPTFILENAME).unwrap();
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_mknod_invalid_filetypes() {
+    pub fn ut_lind_fs_metadata_log_checkpoint() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
-        // Check for error when file types other than S_IFCHR are passed in the input
         let cage = interface::cagetable_getref(1);
-        let dev = makedev(&DevNo { major: 1, minor: 3 });
-        let path = "/invalidfile";
 
-        // When file type is S_IFDIR (Directory), error is expected
-        assert_eq!(
-            cage.mknod_syscall(path, S_IRWXA | S_IFDIR as u32, dev),
-            -(Errno::EINVAL as i32)
-        );
-
-        // When file type is S_IFIFO (FIFO), error is expected
-        assert_eq!(
-            cage.mknod_syscall(path, S_IRWXA | S_IFIFO as u32, dev),
-            -(Errno::EINVAL as i32)
-        );
+        //with a tiny threshold every entry checkpoints the log into lind.metadata
+        let threshold =
+            filesystem::LOG_CHECKPOINT_BYTES.swap(1, interface::RustAtomicOrdering::Relaxed);
+        assert_eq!(cage.mkdir_syscall("/checkpointed", S_IRWXA), 0);
+        filesystem::LOG_CHECKPOINT_BYTES.store(threshold, interface::RustAtomicOrdering::Relaxed);
 
-        // When file type is S_IFREG (Regular File), error is expected
         assert_eq!(
-            cage.mknod_syscall(path, S_IRWXA | S_IFREG as u32, dev),
-            -(Errno::EINVAL as i32)
+            filesystem::LOGMAP.read().as_ref().unwrap().bytes_written(),
+            0
         );
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/checkpointed", &mut statdata), 0);
+        let metadatabytes = interface::storage_backend()
+            .read_metadata()
+            .unwrap()
+            .unwrap();
+        let saved: filesystem::FilesystemMetadata =
+            interface::serde_deserialize_from_bytes(&metadatabytes).unwrap();
+        assert!(saved.inodetable.contains_key(&(statdata.st_ino as usize)));
 
-        // When file type is S_IFSOCK (Socket), error is expected
-        assert_eq!(
-            cage.mknod_syscall(path, S_IRWXA | S_IFSOCK as u32, dev),
-            -(Errno::EINVAL as i32)
-        );
+        //below the threshold entries accumulate in the log
+        assert_eq!(cage.rmdir_syscall("/checkpointed"), 0);
+        assert!(filesystem::LOGMAP.read().as_ref().unwrap().bytes_written() > 0);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_mknod_success() {
+    pub fn ut_lind_fs_memory_storage_backend() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
-        // let's create /dev/null
         let cage = interface::cagetable_getref(1);
-        let dev = makedev(&DevNo { major: 1, minor: 3 });
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
 
-        //making the node with read only permission (S_IRUSR) and check if it gets
-        // created successfully
-        assert_eq!(
-            cage.mknod_syscall("/readOnlyFile", S_IRUSR | S_IFCHR as u32, dev),
-            0
-        );
+        //bring lind up again on a backend that keeps everything in memory
+        let hostbackend = interface::storage_backend();
+        let memory = interface::RustRfc::new(interface::MemoryBackend::new());
+        interface::set_storage_backend(memory.clone());
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
 
-        //making the node with write only permission (S_IWUSR) and check if it gets
-        // created successfully
-        assert_eq!(
-            cage.mknod_syscall("/writeOnlyFile", S_IWUSR | S_IFCHR as u32, dev),
-            0
-        );
+        let fd = cage.open_syscall("/inmemory", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
+        assert_eq!(cage.close_syscall(fd), 0);
 
-        //making the node with execute only permission (S_IXUSR) and check if it gets
-        // created successfully
-        assert_eq!(
-            cage.mknod_syscall("/executeOnlyFile", S_IXUSR | S_IFCHR as u32, dev),
-            0
-        );
+        //the data outlives the file object, but never reaches the host
+        let fd = cage.open_syscall("/inmemory", O_RDONLY, S_IRWXA);
+        let mut read_buf = sizecbuf(12);
+        assert_eq!(cage.read_syscall(fd, read_buf.as_mut_ptr(), 12), 12);
+        assert_eq!(cbuf2str(&read_buf), "hello there!");
+        assert_eq!(cage.close_syscall(fd), 0);
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/inmemory", &mut statdata), 0);
+        let sysfilename = format!("{}{}", FILEDATAPREFIX, statdata.st_ino);
+        assert!(!std::path::Path::new(&sysfilename).exists());
 
-        //now we are going to mknod /dev/null with read, write, and execute flags and
-        // permissions and then make sure that it exists
-        let path = "/null";
-        assert_eq!(cage.mknod_syscall(path, S_IRWXA | S_IFCHR as u32, dev), 0);
-        let fd = cage.open_syscall(path, O_RDWR, S_IRWXA);
+        assert_eq!(cage.unlink_syscall("/inmemory"), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
 
-        //checking the metadata of the file:
-        let mut statdata = StatData::default();
+        //the metadata was saved to memory as well
+        assert!(memory.read_metadata().unwrap().is_some());
+        assert_eq!(memory.read_log().unwrap(), None);
+        interface::set_storage_backend(hostbackend);
+    }
 
-        //should be a chr file, so let's check this
-        let mut buf = sizecbuf(4);
-        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
-        assert_eq!(statdata.st_mode & S_FILETYPEFLAGS as u32, S_IFCHR as u32);
-        assert_eq!(statdata.st_rdev, dev);
-        assert_eq!(cage.write_syscall(fd, str2cbuf("test"), 4), 4);
-        assert_eq!(cage.read_syscall(fd, buf.as_mut_ptr(), 4), 0);
-        assert_eq!(cbuf2str(&buf), "\0\0\0\0");
-        assert_eq!(cage.close_syscall(fd), 0);
+    #[test]
+    pub fn ut_lind_fs_image_storage_backend() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
 
-        let mut statdata2 = StatData::default();
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
 
-        //try it again with /dev/random
-        let dev2 = makedev(&DevNo { major: 1, minor: 8 });
-        let path2 = "/random";
+        //bring lind up again on a single image file
+        let dir = tempfile::tempdir().unwrap();
+        let imagepath = dir.path().join("fs.img");
+        let hostbackend = interface::storage_backend();
+        interface::set_storage_backend(interface::RustRfc::new(
+            interface::ImageBackend::open(&imagepath).unwrap(),
+        ));
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
 
-        //making the node and then making sure that it exists
-        assert_eq!(cage.mknod_syscall(path2, S_IRWXA | S_IFCHR as u32, dev2), 0);
-        let fd2 = cage.open_syscall(path2, O_RDWR, S_IRWXA);
+        let fd = cage.open_syscall("/inimage", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
+        assert_eq!(cage.close_syscall(fd), 0);
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/inimage", &mut statdata), 0);
+        let sysfilename = format!("{}{}", FILEDATAPREFIX, statdata.st_ino);
+        assert!(!std::path::Path::new(&sysfilename).exists());
 
-        let mut buf2 = sizecbuf(4);
-        assert_eq!(cage.fstat_syscall(fd2, &mut statdata2), 0);
-        assert_eq!(statdata2.st_mode & S_FILETYPEFLAGS as u32, S_IFCHR as u32);
-        assert_eq!(statdata2.st_rdev, dev2);
-        assert_eq!(cage.write_syscall(fd2, str2cbuf("testing"), 7), 7);
-        assert_ne!(cage.read_syscall(fd2, buf2.as_mut_ptr(), 7), 0);
-        assert_eq!(cage.close_syscall(fd2), 0);
+        //closing the file wrote its data into the image
+        let image = interface::ImageBackend::open(&imagepath).unwrap();
+        let data = image.open_data(&sysfilename, 12).unwrap();
+        let mut read_buf = sizecbuf(12);
+        data.readat(read_buf.as_mut_ptr(), 12, 0).unwrap();
+        assert_eq!(cbuf2str(&read_buf), "hello there!");
+        drop(image);
 
+        assert_eq!(cage.unlink_syscall("/inimage"), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
+        interface::set_storage_backend(hostbackend);
+
+        //the metadata is in the image too, and the log beside it is gone
+        let image = interface::ImageBackend::open(&imagepath).unwrap();
+        assert!(image.read_metadata().unwrap().is_some());
+        assert_eq!(image.read_log().unwrap(), None);
+        assert!(image.remove_data(&sysfilename).is_err());
     }
 
     #[test]
-    pub fn ut_lind_fs_multiple_open() {
+    pub fn ut_lind_fs_export_and_mount_image() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        //try to open several files at once -- the fd's should not be overwritten
-        let fd1 = cage.open_syscall("/foo", O_CREAT | O_EXCL | O_RDWR, S_IRWXA);
-        let fd2 = cage.open_syscall("/foo", O_RDWR, S_IRWXA);
-        assert_ne!(fd1, fd2);
+        //a sparse file, with data at the start and at 2MB
+        let fd = cage.open_syscall("/exported", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello"), 5), 5);
+        assert_eq!(
+            cage.lseek_syscall(fd, 2 * 1024 * 1024, SEEK_SET),
+            2 * 1024 * 1024
+        );
+        assert_eq!(cage.write_syscall(fd, str2cbuf("end"), 3), 3);
+        assert_eq!(cage.close_syscall(fd), 0);
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/exported", &mut statdata), 0);
+        let sysfilename = format!("{}{}", FILEDATAPREFIX, statdata.st_ino);
 
-        let flags: i32 = O_TRUNC | O_CREAT | O_RDWR;
-        let mode: u32 = 0o666; // 0666
-        let name = "double_open_file";
+        //export the running file system, whose changes are still only in the log
+        let dir = tempfile::tempdir().unwrap();
+        let imagepath = dir.path().join("fs.img");
+        let hostbackend = interface::storage_backend();
+        let image = interface::ImageBackend::open(&imagepath).unwrap();
+        assert!(copy_fs(&*hostbackend, &image).is_ok());
+        drop(image);
 
-        let mut read_buf = sizecbuf(2);
-        let fd3 = cage.open_syscall(name, flags, mode);
-        assert_eq!(cage.write_syscall(fd3, str2cbuf("hi"), 2), 2);
-        assert_eq!(cage.lseek_syscall(fd3, 0, SEEK_SET), 0);
-        assert_eq!(cage.read_syscall(fd3, read_buf.as_mut_ptr(), 2), 2);
-        assert_eq!(cbuf2str(&read_buf), "hi");
+        assert_eq!(cage.unlink_syscall("/exported"), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
 
-        let _fd4 = cage.open_syscall(name, flags, mode);
-        let mut buf = sizecbuf(5);
-        assert_eq!(cage.lseek_syscall(fd3, 2, SEEK_SET), 2);
-        assert_eq!(cage.write_syscall(fd3, str2cbuf("boo"), 3), 3);
-        assert_eq!(cage.lseek_syscall(fd3, 0, SEEK_SET), 0);
-        assert_eq!(cage.read_syscall(fd3, buf.as_mut_ptr(), 5), 5);
-        assert_eq!(cbuf2str(&buf), "\0\0boo");
+        //mount the image, which holds the file the host no longer does
+        interface::set_storage_backend(interface::RustRfc::new(
+            interface::ImageBackend::open(&imagepath).unwrap(),
+        ));
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
 
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/exported", &mut statdata), 0);
+        assert_eq!(statdata.st_size, 2 * 1024 * 1024 + 3);
+        let fd = cage.open_syscall("/exported", O_RDONLY, S_IRWXA);
+        let mut read_buf = sizecbuf(5);
+        assert_eq!(cage.read_syscall(fd, read_buf.as_mut_ptr(), 5), 5);
+        assert_eq!(cbuf2str(&read_buf), "hello");
+        assert_eq!(cage.lseek_syscall(fd, 1024 * 1024, SEEK_SET), 1024 * 1024);
+        let mut read_buf = sizecbuf(4);
+        assert_eq!(cage.read_syscall(fd, read_buf.as_mut_ptr(), 4), 4);
+        assert_eq!(*read_buf, [0u8; 4]);
+        assert_eq!(
+            cage.lseek_syscall(fd, 2 * 1024 * 1024, SEEK_SET),
+            2 * 1024 * 1024
+        );
+        let mut read_buf = sizecbuf(3);
+        assert_eq!(cage.read_syscall(fd, read_buf.as_mut_ptr(), 3), 3);
+        assert_eq!(cbuf2str(&read_buf), "end");
+        assert_eq!(cage.close_syscall(fd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
+        interface::set_storage_backend(hostbackend);
+
+        //and import it somewhere else
+        let image = interface::ImageBackend::open(&imagepath).unwrap();
+        let memory = interface::MemoryBackend::new();
+        assert!(copy_fs(&image, &memory).is_ok());
+        assert!(memory.read_metadata().unwrap().is_some());
+        let data = memory.open_data(&sysfilename, 2 * 1024 * 1024 + 3).unwrap();
+        let mut read_buf = sizecbuf(3);
+        data.readat(read_buf.as_mut_ptr(), 3, 2 * 1024 * 1024)
+            .unwrap();
+        assert_eq!(cbuf2str(&read_buf), "end");
+
+        //copying needs metadata to copy
+        assert!(copy_fs(&interface::MemoryBackend::new(), &memory).is_err());
     }
 
     #[test]
-    pub fn ut_lind_fs_rmdir_normal() {
+    pub fn ut_lind_fs_snapshot_restore() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
-        //and also performs clean env setup
+        // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        //We create a new parent directory `/parent_dir`
-        //and its child directory '/parent_dir/dir` both
-        //with the required write permission flags, thus
-        //calling `rmdir_syscall()`on the child directory
-        //should result in a normal behavior
-        let path = "/parent_dir/dir";
-        assert_eq!(cage.mkdir_syscall("/parent_dir", S_IRWXA), 0);
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
-        assert_eq!(cage.rmdir_syscall(path), 0);
-        //To check if the child directory was successfully
-        //removed, we call `open_syscall()` on it, and see
-        //if it correctly returns `Path does not exist` error
+        //snapshots cannot be used while lind is running
         assert_eq!(
-            cage.open_syscall(path, O_TRUNC, S_IRWXA),
-            -(Errno::ENOENT as i32)
+            filesystem::snapshot_create("busy").unwrap_err().kind(),
+            std::io::ErrorKind::ResourceBusy
         );
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
 
+        //work on a file system of its own, so that no snapshot is left behind
+        let dir = tempfile::tempdir().unwrap();
+        let hostbackend = interface::storage_backend();
+        interface::set_storage_backend(interface::RustRfc::new(
+            interface::HostDirBackend::new(dir.path()).unwrap(),
+        ));
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
+        let fd = cage.open_syscall("/golden", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("golden"), 6), 6);
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.mkdir_syscall("/keep", S_IRWXA), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
+
+        let info = filesystem::snapshot_create("golden").unwrap();
+        assert_eq!(info.name, "golden");
+        assert_eq!(info.files, 1);
+        assert_eq!(info.bytes, 6);
+        assert_eq!(
+            filesystem::snapshot_create("golden").unwrap_err().kind(),
+            std::io::ErrorKind::AlreadyExists
+        );
+        assert_eq!(
+            filesystem::snapshot_create("../golden").unwrap_err().kind(),
+            std::io::ErrorKind::InvalidInput
+        );
+
+        //change everything the snapshot holds
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
+        let fd = cage.open_syscall("/golden", O_RDWR, S_IRWXA);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("tarnished"), 9), 9);
+        assert_eq!(cage.close_syscall(fd), 0);
+        let fd = cage.open_syscall("/extra", O_CREAT | O_RDWR, S_IRWXA);
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.rmdir_syscall("/keep"), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+
+        //restoring brings back the file system as it was, twice over
+        for _ in 0..2 {
+            assert!(filesystem::snapshot_restore("golden").is_ok());
+            lindrustinit(0);
+            let cage = interface::cagetable_getref(1);
+            let mut statdata = StatData::default();
+            assert_eq!(cage.stat_syscall("/golden", &mut statdata), 0);
+            assert_eq!(statdata.st_size, 6);
+            let fd = cage.open_syscall("/golden", O_RDWR, S_IRWXA);
+            let mut read_buf = sizecbuf(6);
+            assert_eq!(cage.read_syscall(fd, read_buf.as_mut_ptr(), 6), 6);
+            assert_eq!(cbuf2str(&read_buf), "golden");
+            assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
+            assert_eq!(cage.write_syscall(fd, str2cbuf("GOLDEN"), 6), 6);
+            assert_eq!(cage.close_syscall(fd), 0);
+            assert_eq!(
+                cage.stat_syscall("/extra", &mut statdata),
+                -(Errno::ENOENT as i32)
+            );
+            assert_eq!(cage.stat_syscall("/keep", &mut statdata), 0);
+            assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+            lindrustfinalize();
+        }
+
+        let snapshots = filesystem::snapshot_list().unwrap();
+        assert_eq!(snapshots, vec![info]);
+        assert!(filesystem::snapshot_delete("golden").is_ok());
+        assert!(filesystem::snapshot_list().unwrap().is_empty());
+        assert_eq!(
+            filesystem::snapshot_restore("golden").unwrap_err().kind(),
+            std::io::ErrorKind::NotFound
+        );
+        //and none of its data is left
+        assert!(std::fs::read_dir(dir.path()).unwrap().all(|entry| !entry
+            .unwrap()
+            .file_name()
+            .to_string_lossy()
+            .starts_with(SNAPSHOTPREFIX)));
+        interface::set_storage_backend(hostbackend);
+    }
+
+    // Replaces the contents of a file, creating it if needed
+    fn snapshot_write_file(cage: &Cage, path: &str, data: &str) {
+        let fd = cage.open_syscall(path, O_CREAT | O_TRUNC | O_WRONLY, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(
+            cage.write_syscall(fd, str2cbuf(data), data.len()),
+            data.len() as i32
+        );
+        assert_eq!(cage.close_syscall(fd), 0);
+    }
+
+    // Reads the whole of a file
+    fn snapshot_read_file(cage: &Cage, path: &str) -> String {
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
+        let fd = cage.open_syscall(path, O_RDONLY, S_IRWXA);
+        assert!(fd >= 0);
+        let mut read_buf = sizecbuf(statdata.st_size);
+        assert_eq!(
+            cage.read_syscall(fd, read_buf.as_mut_ptr(), statdata.st_size),
+            statdata.st_size as i32
+        );
+        assert_eq!(cage.close_syscall(fd), 0);
+        cbuf2str(&read_buf).to_string()
     }
 
     #[test]
-    pub fn ut_lind_fs_rmdir_empty_path() {
+    pub fn ut_lind_fs_snapshot_restore_changes() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
 
-        //Trying to remove a directory by providing an empty string
-        //should return `Given path is an empty string` error
-        assert_eq!(cage.rmdir_syscall(""), -(Errno::ENOENT as i32));
+        let dir = tempfile::tempdir().unwrap();
+        let hostbackend = interface::storage_backend();
+        interface::set_storage_backend(interface::RustRfc::new(
+            interface::HostDirBackend::new(dir.path()).unwrap(),
+        ));
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.mkdir_syscall("/snapdir", S_IRWXA), 0);
+        snapshot_write_file(&cage, "/alpha", "alpha");
+        snapshot_write_file(&cage, "/bravo", "bravo");
+        snapshot_write_file(&cage, "/snapdir/charlie", "charlie");
+        assert_eq!(cage.link_syscall("/bravo", "/bravolink"), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+        assert!(filesystem::snapshot_create("before").is_ok());
+
+        //grow, shrink and remove what the snapshot holds, and add to it
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
+        snapshot_write_file(&cage, "/alpha", "alpha, and then some");
+        assert_eq!(cage.truncate_syscall("/bravo", 2), 0);
+        assert_eq!(cage.unlink_syscall("/snapdir/charlie"), 0);
+        assert_eq!(cage.rmdir_syscall("/snapdir"), 0);
+        assert_eq!(cage.unlink_syscall("/bravolink"), 0);
+        snapshot_write_file(&cage, "/new", "new");
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+
+        //restoring brings back the removed files with their links, and the
+        //changed files with their sizes
+        assert!(filesystem::snapshot_restore("before").is_ok());
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(snapshot_read_file(&cage, "/alpha"), "alpha");
+        assert_eq!(snapshot_read_file(&cage, "/bravo"), "bravo");
+        assert_eq!(snapshot_read_file(&cage, "/bravolink"), "bravo");
+        assert_eq!(snapshot_read_file(&cage, "/snapdir/charlie"), "charlie");
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/bravo", &mut statdata), 0);
+        assert_eq!(statdata.st_nlink, 2);
+        assert_eq!(
+            cage.stat_syscall("/new", &mut statdata),
+            -(Errno::ENOENT as i32)
+        );
 
+        //files made afterwards don't take the place of a restored one
+        snapshot_write_file(&cage, "/after", "after");
+        assert_eq!(snapshot_read_file(&cage, "/after"), "after");
+        assert_eq!(snapshot_read_file(&cage, "/alpha"), "alpha");
+        assert_eq!(snapshot_read_file(&cage, "/snapdir/charlie"), "charlie");
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
+        interface::set_storage_backend(hostbackend);
     }
 
     #[test]
-    pub fn ut_lind_fs_rmdir_nonexist_dir() {
+    pub fn ut_lind_fs_snapshot_delete_shared() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-
-        //We create a new parent directory `/parent_dir`
-        //However, we never create its child directory
-        //'/parent_dir/dir`, thus calling `rmdir_syscall()`
-        //on this child directory should return
-        //`Path does not exist` error
-        let path = "/parent_dir/dir";
-        assert_eq!(cage.mkdir_syscall("/parent_dir", S_IRWXA), 0);
-        assert_eq!(cage.rmdir_syscall(path), -(Errno::ENOENT as i32));
-
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
+
+        //on both backends that share the data of snapshots with the files
+        let dir = tempfile::tempdir().unwrap();
+        let hostbackend = interface::storage_backend();
+        let hostdir = dir.path().join("host");
+        let backends: Vec<interface::RustRfc<dyn interface::StorageBackend>> = vec![
+            interface::RustRfc::new(interface::HostDirBackend::new(&hostdir).unwrap()),
+            interface::RustRfc::new(
+                interface::ImageBackend::open(dir.path().join("fs.img")).unwrap(),
+            ),
+        ];
+        for (i, backend) in backends.into_iter().enumerate() {
+            interface::set_storage_backend(backend.clone());
+            lindrustinit(0);
+            let cage = interface::cagetable_getref(1);
+            snapshot_write_file(&cage, "/shared", "shared data");
+            let mut statdata = StatData::default();
+            assert_eq!(cage.stat_syscall("/shared", &mut statdata), 0);
+            assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+            lindrustfinalize();
+            let datapath = hostdir.join(format!("{}{}", FILEDATAPREFIX, statdata.st_ino));
+            let hostlinks = |links: u64| {
+                if i == 0 {
+                    assert_eq!(std::fs::metadata(&datapath).unwrap().nlink(), links);
+                }
+            };
+
+            //deleting a snapshot that was just taken leaves the file it shares
+            //data with as it was
+            assert!(filesystem::snapshot_create("taken").is_ok());
+            hostlinks(2);
+            assert!(filesystem::snapshot_delete("taken").is_ok());
+            hostlinks(1);
+
+            //and so does deleting one that was restored, which shares its data
+            //with the files again
+            assert!(filesystem::snapshot_create("restored").is_ok());
+            assert!(filesystem::snapshot_restore("restored").is_ok());
+            hostlinks(2);
+            assert!(filesystem::snapshot_delete("restored").is_ok());
+            hostlinks(1);
+            assert!(filesystem::snapshot_list().unwrap().is_empty());
+            assert!(backend
+                .remove_data(&format!("{}restored.metadata", SNAPSHOTPREFIX))
+                .is_err());
+
+            lindrustinit(0);
+            let cage = interface::cagetable_getref(1);
+            assert_eq!(snapshot_read_file(&cage, "/shared"), "shared data");
+            snapshot_write_file(&cage, "/shared", "changed");
+            assert_eq!(snapshot_read_file(&cage, "/shared"), "changed");
+            assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+            lindrustfinalize();
+        }
+        interface::set_storage_backend(hostbackend);
     }
 
     #[test]
-    pub fn ut_lind_fs_rmdir_root() {
+    pub fn ut_lind_fs_snapshot_names_and_busy() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        //Trying to remove the root directory should return
-        //`Cannot remove root directory` error
-        assert_eq!(cage.rmdir_syscall("/"), -(Errno::EBUSY as i32));
+        //nothing can be done with snapshots while files are open, and the open
+        //file is left alone
+        let fd = cage.open_syscall("/busy", O_CREAT | O_RDWR, S_IRWXA);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("busy"), 4), 4);
+        for result in [
+            filesystem::snapshot_create("busy").map(|_| ()),
+            filesystem::snapshot_restore("busy"),
+            filesystem::snapshot_delete("busy"),
+            filesystem::snapshot_list().map(|_| ()),
+        ] {
+            assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::ResourceBusy);
+        }
+        assert_eq!(cage.pwrite_syscall(fd, str2cbuf("BUSY"), 4, 0), 4);
+        let mut read_buf = sizecbuf(4);
+        assert_eq!(cage.pread_syscall(fd, read_buf.as_mut_ptr(), 4, 0), 4);
+        assert_eq!(cbuf2str(&read_buf), "BUSY");
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.unlink_syscall("/busy"), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
 
+        let dir = tempfile::tempdir().unwrap();
+        let hostbackend = interface::storage_backend();
+        interface::set_storage_backend(interface::RustRfc::new(
+            interface::HostDirBackend::new(dir.path()).unwrap(),
+        ));
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
+        snapshot_write_file(&cage, "/named", "named");
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+
+        //names are letters, digits, '-' and '_', so no name can pass for the
+        //data of another snapshot or reach outside of the backend
+        let longest = "n".repeat(MAXSNAPSHOTNAME);
+        let toolong = "n".repeat(MAXSNAPSHOTNAME + 1);
+        for name in [
+            "",
+            "lindsnap.x",
+            "x.1",
+            "x.metadata",
+            ".",
+            "..",
+            "../x",
+            "a/b",
+            "a b",
+            &toolong,
+        ] {
+            for result in [
+                filesystem::snapshot_create(name).map(|_| ()),
+                filesystem::snapshot_restore(name),
+                filesystem::snapshot_delete(name),
+            ] {
+                assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+            }
+        }
+        assert!(filesystem::snapshot_create(&longest).is_ok());
+        assert!(filesystem::snapshot_create("lindsnap").is_ok());
+
+        //a name is only taken once, and the snapshot already under it is kept
+        let first = filesystem::snapshot_create("dup").unwrap();
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
+        snapshot_write_file(&cage, "/named", "renamed");
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+        assert_eq!(
+            filesystem::snapshot_create("dup").unwrap_err().kind(),
+            std::io::ErrorKind::AlreadyExists
+        );
+        let snapshots = filesystem::snapshot_list().unwrap();
+        assert_eq!(snapshots.len(), 3);
+        assert!(snapshots.contains(&first));
+        assert!(filesystem::snapshot_restore("dup").is_ok());
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(snapshot_read_file(&cage, "/named"), "named");
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
+
+        //once deleted, the name can be used again
+        assert!(filesystem::snapshot_delete("dup").is_ok());
+        assert_eq!(
+            filesystem::snapshot_delete("dup").unwrap_err().kind(),
+            std::io::ErrorKind::NotFound
+        );
+        assert!(filesystem::snapshot_create("dup").is_ok());
+        interface::set_storage_backend(hostbackend);
     }
 
     #[test]
-    pub fn ut_lind_fs_rmdir_nonempty_dir() {
+    pub fn ut_lind_fs_mknod_empty_path() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
-        //and also performs clean env setup
+        // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-
-        //We create a new parent directory `/parent_dir` and
-        //its child directory '/parent_dir/dir`, thus calling `rmdir_syscall()`
-        //on the parent directory should return `Directory is not empty` error
-        let path = "/parent_dir/dir";
-        assert_eq!(cage.mkdir_syscall("/parent_dir", S_IRWXA), 0);
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
+        let dev = makedev(&DevNo { major: 1, minor: 3 });
+        let path = "";
+        // Check for error when directory is empty
         assert_eq!(
-            cage.rmdir_syscall("/parent_dir"),
-            -(Errno::ENOTEMPTY as i32)
+            cage.mknod_syscall(path, S_IRWXA | S_IFCHR as u32, dev),
+            -(Errno::ENOENT as i32)
         );
-
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_rmdir_nowriteperm_child_dir() {
+    pub fn ut_lind_fs_mknod_nonexisting_parent_directory() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-
-        //We create a new parent directory `/parent_dir` with all write permission
-        //flags and its child directory '/parent_dir/dir` without any write
-        //permision flags, thus calling `rmdir_syscall()`on the child directory
-        //should return `Directory does not allow write permission` error
-        //because the directory cannot be removed if it does not allow
-        //write permission
-        let path = "/parent_dir/dir";
-        assert_eq!(cage.mkdir_syscall("/parent_dir", S_IRWXA), 0);
-        assert_eq!(cage.mkdir_syscall(path, 0), 0);
-        assert_eq!(cage.rmdir_syscall(path), -(Errno::EPERM as i32));
-
+        let dev = makedev(&DevNo { major: 1, minor: 3 });
+        let path = "/parentdir/file";
+        // Check for error when both parent and file don't exist
+        assert_eq!(
+            cage.mknod_syscall(path, S_IRWXA | S_IFCHR as u32, dev),
+            -(Errno::ENOENT as i32)
+        );
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_rmdir_nowriteperm_parent_dir() {
+    pub fn ut_lind_fs_mknod_existing_file() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
+        let dev = makedev(&DevNo { major: 1, minor: 3 });
+        let path = "/charfile";
+        // Create a special character file for the first time
+        assert_eq!(cage.mknod_syscall(path, S_IRWXA | S_IFCHR as u32, dev), 0);
 
-        //We create a new parent directory `/parent_dir` with all write permission
-        //flags (to be able to create its child directory) and its child directory
-        //'/parent_dir/dir` with all write permision flags.
-        let path = "/parent_dir/dir";
-        assert_eq!(cage.mkdir_syscall("/parent_dir", S_IRWXA), 0);
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
-        //Now, we change the parent directories write permission flags to 0,
-        //thus calling `rmdir_syscall()`on the child directory
-        //should return `Directory does not allow write permission` error
-        //because the directory cannot be removed if its parent directory
-        //does not allow write permission
+        // Check for error when the same file is created again
         assert_eq!(
-            cage.chmod_syscall("/parent_dir", S_IRUSR | S_IRGRP | S_IROTH),
-            0
+            cage.mknod_syscall(path, S_IRWXA | S_IFCHR as u32, dev),
+            -(Errno::EEXIST as i32)
         );
-        assert_eq!(cage.rmdir_syscall(path), -(Errno::EPERM as i32));
-
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    //BUG:
-    //The correct behavior of the `rmdir_syscall()` when called on a directory
-    //whose path includes a component that does not allow search permission
-    //(the read flag) is to return with `EACCES` error.
-    //However, the `metawalkandparent())` helper function used
-    //to retrieve the inodes of the directory to be removed and its parent
-    //directory does not check for search permission. Thus, the following test
-    //will not return any errors and run normally even though the parent
-    //directory does not grand search permission.
-    pub fn ut_lind_fs_search_permission_bug_with_rmdir() {
+    pub fn ut_lind_fs_mknod_invalid_modebits() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
-        //and also performs clean env setup
+        // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-
-        //Creating the parent directory that does not allow search permission
-        //by excluding any read flags and specifying only write flags
-        //to be able to delete the child directory.
-        let path = "/parent_dir/dir";
+        let dev = makedev(&DevNo { major: 1, minor: 3 });
+        let path = "/testfile";
+        let invalid_mode = 0o77777; // Invalid mode bits for testing
+                                    // Check for error when the file is being created with invalid mode
         assert_eq!(
-            cage.mkdir_syscall("/parent_dir", S_IWUSR | S_IWGRP | S_IWOTH),
-            0
+            cage.mknod_syscall(path, invalid_mode, dev),
+            -(Errno::EPERM as i32)
         );
-        //Creating the child directory with all the required flags
-        //and then deleting it. Because of the bug described above,
-        //removing the directory will not return any errors.
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
-        assert_eq!(cage.rmdir_syscall(path), 0);
-
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_stat_file_complex() {
+    pub fn ut_lind_fs_mknod_invalid_filetypes() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
+        // Check for error when file types other than S_IFCHR and S_IFIFO are passed in
+        // the input
         let cage = interface::cagetable_getref(1);
-        let fd = cage.open_syscall("/fooComplex", O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
-
-        assert_eq!(cage.write_syscall(fd, str2cbuf("hi"), 2), 2);
-
-        let mut statdata = StatData::default();
-        let mut statdata2 = StatData::default();
+        let dev = makedev(&DevNo { major: 1, minor: 3 });
+        let path = "/invalidfile";
 
-        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
-        assert_eq!(statdata.st_size, 2);
-        assert_eq!(statdata.st_nlink, 1);
+        // When file type is S_IFDIR (Directory), error is expected
+        assert_eq!(
+            cage.mknod_syscall(path, S_IRWXA | S_IFDIR as u32, dev),
+            -(Errno::EINVAL as i32)
+        );
 
-        assert_eq!(cage.link_syscall("/fooComplex", "/barComplex"), 0);
-        assert_eq!(cage.stat_syscall("/fooComplex", &mut statdata), 0);
-        assert_eq!(cage.stat_syscall("/barComplex", &mut statdata2), 0);
+        // When file type is S_IFREG (Regular File), error is expected
+        assert_eq!(
+            cage.mknod_syscall(path, S_IRWXA | S_IFREG as u32, dev),
+            -(Errno::EINVAL as i32)
+        );
 
-        //check that they are the same and that the link count is 0
-        assert!(statdata == statdata2);
-        assert_eq!(statdata.st_nlink, 2);
+        // When file type is S_IFSOCK (Socket), error is expected
+        assert_eq!(
+            cage.mknod_syscall(path, S_IRWXA | S_IFSOCK as u32, dev),
+            -(Errno::EINVAL as i32)
+        );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_stat_file_mode() {
+    pub fn ut_lind_fs_mknod_success() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
+        // let's create /dev/null
         let cage = interface::cagetable_getref(1);
-        let path = "/fooFileMode";
-        let _fd = cage.open_syscall(path, O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
+        let dev = makedev(&DevNo { major: 1, minor: 3 });
 
+        //making the node with read only permission (S_IRUSR) and check if it gets
+        // created successfully
+        assert_eq!(
+            cage.mknod_syscall("/readOnlyFile", S_IRUSR | S_IFCHR as u32, dev),
+            0
+        );
+
+        //making the node with write only permission (S_IWUSR) and check if it gets
+        // created successfully
+        assert_eq!(
+            cage.mknod_syscall("/writeOnlyFile", S_IWUSR | S_IFCHR as u32, dev),
+            0
+        );
+
+        //making the node with execute only permission (S_IXUSR) and check if it gets
+        // created successfully
+        assert_eq!(
+            cage.mknod_syscall("/executeOnlyFile", S_IXUSR | S_IFCHR as u32, dev),
+            0
+        );
+
+        //now we are going to mknod /dev/null with read, write, and execute flags and
+        // permissions and then make sure that it exists
+        let path = "/null";
+        assert_eq!(cage.mknod_syscall(path, S_IRWXA | S_IFCHR as u32, dev), 0);
+        let fd = cage.open_syscall(path, O_RDWR, S_IRWXA);
+
+        //checking the metadata of the file:
         let mut statdata = StatData::default();
-        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
-        assert_eq!(statdata.st_mode, S_IRWXA | S_IFREG as u32);
 
-        //make a file without permissions and check that it is a reg file without
-        // permissions
-        let path2 = "/fooFileMode2";
-        let _fd2 = cage.open_syscall(path2, O_CREAT | O_EXCL | O_WRONLY, 0);
-        assert_eq!(cage.stat_syscall(path2, &mut statdata), 0);
-        assert_eq!(statdata.st_mode, S_IFREG as u32);
+        //should be a chr file, so let's check this
+        let mut buf = sizecbuf(4);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_mode & S_FILETYPEFLAGS as u32, S_IFCHR as u32);
+        assert_eq!(statdata.st_rdev, dev);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("test"), 4), 4);
+        assert_eq!(cage.read_syscall(fd, buf.as_mut_ptr(), 4), 0);
+        assert_eq!(cbuf2str(&buf), "\0\0\0\0");
+        assert_eq!(cage.close_syscall(fd), 0);
 
-        //check that stat can be done on the current (root) dir
-        assert_eq!(cage.stat_syscall(".", &mut statdata), 0);
+        let mut statdata2 = StatData::default();
+
+        //try it again with /dev/random
+        let dev2 = makedev(&DevNo { major: 1, minor: 8 });
+        let path2 = "/random";
+
+        //making the node and then making sure that it exists
+        assert_eq!(cage.mknod_syscall(path2, S_IRWXA | S_IFCHR as u32, dev2), 0);
+        let fd2 = cage.open_syscall(path2, O_RDWR, S_IRWXA);
+
+        let mut buf2 = sizecbuf(4);
+        assert_eq!(cage.fstat_syscall(fd2, &mut statdata2), 0);
+        assert_eq!(statdata2.st_mode & S_FILETYPEFLAGS as u32, S_IFCHR as u32);
+        assert_eq!(statdata2.st_rdev, dev2);
+        assert_eq!(cage.write_syscall(fd2, str2cbuf("testing"), 7), 7);
+        assert_ne!(cage.read_syscall(fd2, buf2.as_mut_ptr(), 7), 0);
+        assert_eq!(cage.close_syscall(fd2), 0);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_statfs() {
+    pub fn ut_lind_fs_mkfifo() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let mut fsdata = FSData::default();
+        let path = "/fifo";
 
-        assert_eq!(cage.statfs_syscall("/", &mut fsdata), 0);
-        assert_eq!(fsdata.f_type, 0xBEEFC0DE);
-        assert_eq!(fsdata.f_bsize, 4096);
+        // a FIFO can be made with mkfifo as well as with mknod
+        assert_eq!(cage.mkfifo_syscall(path, S_IRWXA), 0);
+        assert_eq!(cage.mkfifo_syscall(path, S_IRWXA), -(Errno::EEXIST as i32));
+        assert_eq!(cage.mknod_syscall("/fifo2", S_IRWXA | S_IFIFO as u32, 0), 0);
+
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
+        assert_eq!(statdata.st_mode, S_IRWXA | S_IFIFO as u32);
+        assert_eq!(statdata.st_size, 0);
+        let fifoinode = statdata.st_ino;
+        assert_eq!(cage.stat_syscall("/fifo2", &mut statdata), 0);
+        assert_eq!(statdata.st_mode & S_FILETYPEFLAGS as u32, S_IFIFO as u32);
+
+        // without a reader, opening for writing without blocking fails
+        assert_eq!(
+            cage.open_syscall(path, O_WRONLY | O_NONBLOCK, 0),
+            -(Errno::ENXIO as i32)
+        );
+
+        // opening for reading without blocking doesn't wait for a writer, after
+        // which the FIFO can be opened for writing
+        let readfd = cage.open_syscall(path, O_RDONLY | O_NONBLOCK, 0);
+        assert!(readfd > 0);
+        let writefd = cage.open_syscall(path, O_WRONLY | O_NONBLOCK, 0);
+        assert!(writefd > 0);
+
+        // the descriptors refer to the FIFO, and data flows between them
+        assert_eq!(cage.fstat_syscall(writefd, &mut statdata), 0);
+        assert_eq!(statdata.st_ino, fifoinode);
+        assert_eq!(statdata.st_mode & S_FILETYPEFLAGS as u32, S_IFIFO as u32);
+        assert_eq!(cage.write_syscall(writefd, str2cbuf("fifo"), 4), 4);
+        let mut buf = sizecbuf(4);
+        assert_eq!(cage.read_syscall(readfd, buf.as_mut_ptr(), 4), 4);
+        assert_eq!(cbuf2str(&buf), "fifo");
+        assert_eq!(
+            cage.write_syscall(readfd, str2cbuf("fifo"), 4),
+            -(Errno::EBADF as i32)
+        );
+
+        // opening for reading and writing never waits, and the descriptor is both
+        // ends of the pipe
+        let rdwrfd = cage.open_syscall("/fifo2", O_RDWR, 0);
+        assert!(rdwrfd > 0);
+        assert_eq!(cage.write_syscall(rdwrfd, str2cbuf("both"), 4), 4);
+        let mut buf = sizecbuf(4);
+        assert_eq!(cage.read_syscall(rdwrfd, buf.as_mut_ptr(), 4), 4);
+        assert_eq!(cbuf2str(&buf), "both");
+        assert_eq!(cage.close_syscall(rdwrfd), 0);
+
+        // once the writer is gone the reader sees the end of the file, and once
+        // both are gone the pipe is let go of along with anything left in it
+        assert_eq!(cage.write_syscall(writefd, str2cbuf("left"), 4), 4);
+        assert_eq!(cage.close_syscall(writefd), 0);
+        assert_eq!(cage.read_syscall(readfd, buf.as_mut_ptr(), 4), 4);
+        assert_eq!(cage.read_syscall(readfd, buf.as_mut_ptr(), 4), 0);
+        assert_eq!(cage.close_syscall(readfd), 0);
+        if let filesystem::Inode::Fifo(ref f) =
+            *filesystem::FS_METADATA.inodetable.get(&fifoinode).unwrap()
+        {
+            assert!(f.pipe.is_none());
+            assert_eq!(f.refcount, 0);
+        } else {
+            panic!("the inode of a FIFO isn't a FIFO");
+        }
+
+        // the FIFO is kept in the metadata, without its pipe
+        let serialized = interface::serde_serialize_to_bytes(&**filesystem::FS_METADATA).unwrap();
+        let metadata: filesystem::FilesystemMetadata =
+            interface::serde_deserialize_from_bytes(&serialized).unwrap();
+        match *metadata.inodetable.get(&fifoinode).unwrap() {
+            filesystem::Inode::Fifo(ref f) => {
+                assert_eq!(f.mode, S_IRWXA | S_IFIFO as u32);
+                assert_eq!(f.linkcount, 1);
+                assert!(f.pipe.is_none());
+            }
+            _ => panic!("the FIFO wasn't persisted as a FIFO"),
+        }
+
+        // a FIFO can't be truncated, and is removed by unlinking it
+        assert_eq!(cage.truncate_syscall(path, 0), -(Errno::EINVAL as i32));
+        assert_eq!(cage.unlink_syscall(path), 0);
+        assert_eq!(cage.unlink_syscall("/fifo2"), 0);
+        assert_eq!(
+            cage.stat_syscall(path, &mut statdata),
+            -(Errno::ENOENT as i32)
+        );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_fstatfs() {
+    pub fn ut_lind_fs_fifo_blocking_open() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let mut fsdata = FSData::default();
+        let path = "/stagefifo";
+        assert_eq!(cage.mkfifo_syscall(path, S_IRWXA), 0);
 
-        // Get fd
-        let fd = cage.open_syscall("/", O_RDONLY, 0);
-        assert!(fd >= 0);
-        // fstatfs
-        assert_eq!(cage.fstatfs_syscall(fd, &mut fsdata), 0);
-        // Check the output
-        assert_eq!(fsdata.f_type, 0xBEEFC0DE);
-        assert_eq!(fsdata.f_bsize, 4096);
-        // Close the file
-        assert_eq!(cage.close_syscall(fd), 0);
+        // the writing stage runs in a cage of its own
+        assert_eq!(cage.fork_syscall(2), 0);
+        let writer = interface::helper_thread(move || {
+            let cage2 = interface::cagetable_getref(2);
+            // opening for writing waits for the reader to show up
+            let writefd = cage2.open_syscall(path, O_WRONLY, 0);
+            assert!(writefd > 0);
+            assert_eq!(cage2.write_syscall(writefd, str2cbuf("stage"), 5), 5);
+            assert_eq!(cage2.close_syscall(writefd), 0);
+            assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        });
+
+        // opening for reading waits for the writer, whose output is then read up to
+        // the end of the file
+        interface::sleep(interface::RustDuration::from_millis(50));
+        let readfd = cage.open_syscall(path, O_RDONLY, 0);
+        assert!(readfd > 0);
+        let mut buf = sizecbuf(5);
+        assert_eq!(cage.read_syscall(readfd, buf.as_mut_ptr(), 5), 5);
+        assert_eq!(cbuf2str(&buf), "stage");
+        assert_eq!(cage.read_syscall(readfd, buf.as_mut_ptr(), 5), 0);
+        writer.join().unwrap();
+
+        // a reader arriving first waits for the writer in the same way
+        assert_eq!(cage.fork_syscall(3), 0);
+        let writer = interface::helper_thread(move || {
+            let cage3 = interface::cagetable_getref(3);
+            interface::sleep(interface::RustDuration::from_millis(50));
+            let writefd = cage3.open_syscall(path, O_WRONLY, 0);
+            assert!(writefd > 0);
+            assert_eq!(cage3.close_syscall(writefd), 0);
+            assert_eq!(cage3.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        });
+        let readfd2 = cage.open_syscall(path, O_RDONLY, 0);
+        assert!(readfd2 > 0);
+        writer.join().unwrap();
+        assert_eq!(cage.close_syscall(readfd2), 0);
+        assert_eq!(cage.close_syscall(readfd), 0);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_rename() {
+    pub fn ut_lind_fs_multiple_open() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        let old_path = "/test_dir";
-        assert_eq!(cage.mkdir_syscall(old_path, S_IRWXA), 0);
-        assert_eq!(cage.rename_syscall(old_path, "/test_dir_renamed"), 0);
+        //try to open several files at once -- the fd's should not be overwritten
+        let fd1 = cage.open_syscall("/foo", O_CREAT | O_EXCL | O_RDWR, S_IRWXA);
+        let fd2 = cage.open_syscall("/foo", O_RDWR, S_IRWXA);
+        assert_ne!(fd1, fd2);
+
+        let flags: i32 = O_TRUNC | O_CREAT | O_RDWR;
+        let mode: u32 = 0o666; // 0666
+        let name = "double_open_file";
+
+        let mut read_buf = sizecbuf(2);
+        let fd3 = cage.open_syscall(name, flags, mode);
+        assert_eq!(cage.write_syscall(fd3, str2cbuf("hi"), 2), 2);
+        assert_eq!(cage.lseek_syscall(fd3, 0, SEEK_SET), 0);
+        assert_eq!(cage.read_syscall(fd3, read_buf.as_mut_ptr(), 2), 2);
+        assert_eq!(cbuf2str(&read_buf), "hi");
+
+        let _fd4 = cage.open_syscall(name, flags, mode);
+        let mut buf = sizecbuf(5);
+        assert_eq!(cage.lseek_syscall(fd3, 2, SEEK_SET), 2);
+        assert_eq!(cage.write_syscall(fd3, str2cbuf("boo"), 3), 3);
+        assert_eq!(cage.lseek_syscall(fd3, 0, SEEK_SET), 0);
+        assert_eq!(cage.read_syscall(fd3, buf.as_mut_ptr(), 5), 5);
+        assert_eq!(cbuf2str(&buf), "\0\0boo");
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_ftruncate() {
+    pub fn ut_lind_fs_rmdir_normal() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
-        // and also performs clean env setup
+        //and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        let fd = cage.open_syscall("/ftruncate", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
-        assert!(fd >= 0);
-
-        // check if ftruncate() works for extending file with null bytes
-        assert_eq!(cage.write_syscall(fd, str2cbuf("Hello there!"), 12), 12);
-        assert_eq!(cage.ftruncate_syscall(fd, 15), 0);
-        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
-        let mut buf = sizecbuf(15);
-        assert_eq!(cage.read_syscall(fd, buf.as_mut_ptr(), 15), 15);
-        assert_eq!(cbuf2str(&buf), "Hello there!\0\0\0");
-
-        // check if ftruncate() works for cutting off extra bytes
-        assert_eq!(cage.ftruncate_syscall(fd, 5), 0);
-        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
-        let mut buf1 = sizecbuf(7);
-        assert_eq!(cage.read_syscall(fd, buf1.as_mut_ptr(), 7), 5);
-        assert_eq!(cbuf2str(&buf1), "Hello\0\0");
+        //We create a new parent directory `/parent_dir`
+        //and its child directory '/parent_dir/dir` both
+        //with the required write permission flags, thus
+        //calling `rmdir_syscall()`on the child directory
+        //should result in a normal behavior
+        let path = "/parent_dir/dir";
+        assert_eq!(cage.mkdir_syscall("/parent_dir", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
+        assert_eq!(cage.rmdir_syscall(path), 0);
+        //To check if the child directory was successfully
+        //removed, we call `open_syscall()` on it, and see
+        //if it correctly returns `Path does not exist` error
+        assert_eq!(
+            cage.open_syscall(path, O_TRUNC, S_IRWXA),
+            -(Errno::ENOENT as i32)
+        );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_truncate() {
+    pub fn ut_lind_fs_rmdir_empty_path() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        let path = String::from("/truncate");
-        let fd = cage.open_syscall(&path, O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
-        assert!(fd >= 0);
-
-        // check if truncate() works for extending file with null bytes
-        assert_eq!(cage.write_syscall(fd, str2cbuf("Hello there!"), 12), 12);
-        assert_eq!(cage.truncate_syscall(&path, 15), 0);
-        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
-        let mut buf = sizecbuf(15);
-        assert_eq!(cage.read_syscall(fd, buf.as_mut_ptr(), 15), 15);
-        assert_eq!(cbuf2str(&buf), "Hello there!\0\0\0");
-
-        // check if truncate() works for cutting off extra bytes
-        assert_eq!(cage.truncate_syscall(&path, 5), 0);
-        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
-        let mut buf1 = sizecbuf(7);
-        assert_eq!(cage.read_syscall(fd, buf1.as_mut_ptr(), 7), 5);
-        assert_eq!(cbuf2str(&buf1), "Hello\0\0");
+        //Trying to remove a directory by providing an empty string
+        //should return `Given path is an empty string` error
+        assert_eq!(cage.rmdir_syscall(""), -(Errno::ENOENT as i32));
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
-    #[cfg(target_os = "macos")]
-    type CharPtr = *const u8;
-
-    #[cfg(not(target_os = "macos"))]
-    type CharPtr = *const i8;
-
     #[test]
-    pub fn ut_lind_fs_getdents() {
+    pub fn ut_lind_fs_rmdir_nonexist_dir() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        let bufsize = 50;
-        let mut vec = vec![0u8; bufsize as usize];
-        let baseptr: *mut u8 = &mut vec[0];
+        //We create a new parent directory `/parent_dir`
+        //However, we never create its child directory
+        //'/parent_dir/dir`, thus calling `rmdir_syscall()`
+        //on this child directory should return
+        //`Path does not exist` error
+        let path = "/parent_dir/dir";
+        assert_eq!(cage.mkdir_syscall("/parent_dir", S_IRWXA), 0);
+        assert_eq!(cage.rmdir_syscall(path), -(Errno::ENOENT as i32));
 
-        assert_eq!(cage.mkdir_syscall("/getdents", S_IRWXA), 0);
-        let fd = cage.open_syscall("/getdents", O_RDWR, S_IRWXA);
-        assert_eq!(cage.getdents_syscall(fd, baseptr, bufsize as u32), 48);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
 
-        unsafe {
-            let first_dirent = baseptr as *mut interface::ClippedDirent;
-            assert!((*first_dirent).d_off == 24);
-            let reclen_matched: bool = ((*first_dirent).d_reclen == 24);
-            assert_eq!(reclen_matched, true);
+    #[test]
+    pub fn ut_lind_fs_rmdir_root() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
 
-            let nameoffset = baseptr.wrapping_offset(interface::CLIPPED_DIRENT_SIZE as isize);
-            let returnedname = interface::RustCStr::from_ptr(nameoffset as *const _);
-            let name_matched: bool = (returnedname
-                == interface::RustCStr::from_bytes_with_nul(b".\0").unwrap())
-                | (returnedname == interface::RustCStr::from_bytes_with_nul(b"..\0").unwrap());
-            assert_eq!(name_matched, true);
+        let cage = interface::cagetable_getref(1);
 
-            let second_dirent = baseptr.wrapping_offset(24) as *mut interface::ClippedDirent;
-            assert!((*second_dirent).d_off >= 48);
-        }
+        //Trying to remove the root directory should return
+        //`Cannot remove root directory` error
+        assert_eq!(cage.rmdir_syscall("/"), -(Errno::EBUSY as i32));
 
-        assert_eq!(cage.close_syscall(fd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
+
     #[test]
-    fn ut_lind_fs_getdents_invalid_fd() {
+    pub fn ut_lind_fs_rmdir_nonempty_dir() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        //and also performs clean env setup
         let _thelock = setup::lock_and_init();
-        let cage = interface::cagetable_getref(1);
-
-        let bufsize = 50;
-        let mut vec = vec![0u8; bufsize as usize];
-        let baseptr: *mut u8 = &mut vec[0];
-
-        // Create a directory
-        assert_eq!(cage.mkdir_syscall("/getdents", S_IRWXA), 0);
 
-        // Open the directory
-        let fd = cage.open_syscall("/getdents", O_RDWR, S_IRWXA);
-
-        // Attempt to call `getdents_syscall` with an invalid file descriptor
-        let result = cage.getdents_syscall(-1, baseptr, bufsize as u32);
-
-        // Assert that the return value is EBADF (errno for "Bad file descriptor")
-        assert_eq!(result, -(Errno::EBADF as i32));
+        let cage = interface::cagetable_getref(1);
 
-        // Close the directory
-        assert_eq!(cage.close_syscall(fd), 0);
+        //We create a new parent directory `/parent_dir` and
+        //its child directory '/parent_dir/dir`, thus calling `rmdir_syscall()`
+        //on the parent directory should return `Directory is not empty` error
+        let path = "/parent_dir/dir";
+        assert_eq!(cage.mkdir_syscall("/parent_dir", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
+        assert_eq!(
+            cage.rmdir_syscall("/parent_dir"),
+            -(Errno::ENOTEMPTY as i32)
+        );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    fn ut_lind_fs_getdents_out_of_range_fd() {
-        // Acquire a lock on TESTMUTEX to prevent other tests from running concurrently,
-        // and also perform clean environment setup.
+    pub fn ut_lind_fs_rmdir_nowriteperm_child_dir() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Allocate a buffer to store directory entries
-        let bufsize = 1024;
-        let mut vec = vec![0u8; bufsize as usize];
-        let baseptr: *mut u8 = &mut vec[0];
-
-        // Attempt to call getdents_syscall with a file descriptor out of range
-        let result = cage.getdents_syscall(MAXFD + 1, baseptr, bufsize as u32);
-
-        // Verify that it returns EBADF (errno for "Bad file descriptor")
-        assert_eq!(result, -(Errno::EBADF as i32));
+        //We create a new parent directory `/parent_dir` with all write permission
+        //flags and its child directory '/parent_dir/dir` without any write
+        //permision flags, thus calling `rmdir_syscall()`on the child directory
+        //should return `Directory does not allow write permission` error
+        //because the directory cannot be removed if it does not allow
+        //write permission
+        let path = "/parent_dir/dir";
+        assert_eq!(cage.mkdir_syscall("/parent_dir", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall(path, 0), 0);
+        assert_eq!(cage.rmdir_syscall(path), -(Errno::EPERM as i32));
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    fn ut_lind_fs_getdents_non_existing_fd() {
-        // Acquire a lock on TESTMUTEX to prevent other tests from running concurrently,
-        // and also perform clean environment setup.
+    pub fn ut_lind_fs_rmdir_nowriteperm_parent_dir() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Allocate a buffer to store directory entries
-        let bufsize = 1024;
-        let mut vec = vec![0u8; bufsize as usize];
-        let baseptr: *mut u8 = &mut vec[0];
-
-        // Attempt to call getdents_syscall with a non-existing file descriptor
-        let result = cage.getdents_syscall(100, baseptr, bufsize as u32);
-
-        // Verify that it returns EBADF (errno for "Bad file descriptor")
-        assert_eq!(result, -(Errno::EBADF as i32));
+        //We create a new parent directory `/parent_dir` with all write permission
+        //flags (to be able to create its child directory) and its child directory
+        //'/parent_dir/dir` with all write permision flags.
+        let path = "/parent_dir/dir";
+        assert_eq!(cage.mkdir_syscall("/parent_dir", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
+        //Now, we change the parent directories write permission flags to 0,
+        //keeping it searchable, thus calling `rmdir_syscall()`on the child
+        //directory should return `EACCES` because the directory cannot be
+        //removed if its parent directory does not allow write permission
+        assert_eq!(
+            cage.chmod_syscall(
+                "/parent_dir",
+                S_IRUSR | S_IXUSR | S_IRGRP | S_IXGRP | S_IROTH | S_IXOTH
+            ),
+            0
+        );
+        assert_eq!(cage.rmdir_syscall(path), -(Errno::EACCES as i32));
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    fn ut_lind_fs_getdents_bufsize_too_small() {
+    //The `rmdir_syscall()` called on a directory whose path includes a
+    //component that does not allow search permission (the execute flag)
+    //returns with `EACCES` error, as does creating anything beneath it.
+    pub fn ut_lind_fs_search_permission_bug_with_rmdir() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        //and also performs clean env setup
         let _thelock = setup::lock_and_init();
+
         let cage = interface::cagetable_getref(1);
 
-        let bufsize = interface::CLIPPED_DIRENT_SIZE - 1; // Buffer size smaller than CLIPPED_DIRENT_SIZE
-        let mut vec = vec![0u8; bufsize as usize];
-        let baseptr: *mut u8 = &mut vec[0];
+        //Creating the parent directory that does not allow search permission
+        //by excluding any read flags and specifying only write flags
+        //to be able to delete the child directory.
+        let path = "/parent_dir/dir";
+        assert_eq!(
+            cage.mkdir_syscall("/parent_dir", S_IWUSR | S_IWGRP | S_IWOTH),
+            0
+        );
+        //Neither creating the child directory nor removing it gets past the
+        //parent directory.
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), -(Errno::EACCES as i32));
+        assert_eq!(cage.rmdir_syscall(path), -(Errno::EACCES as i32));
 
-        // Create a directory
-        assert_eq!(cage.mkdir_syscall("/getdents", S_IRWXA), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
 
-        // Open the directory
-        let fd = cage.open_syscall("/getdents", O_RDWR, S_IRWXA);
+    #[test]
+    pub fn ut_lind_fs_search_permission_with_rmdir() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        //and also performs clean env setup
+        let _thelock = setup::lock_and_init();
 
-        // Attempt to call `getdents_syscall` with a buffer size smaller than
-        // CLIPPED_DIRENT_SIZE
-        let result = cage.getdents_syscall(fd, baseptr, bufsize as u32);
+        let cage = interface::cagetable_getref(1);
 
-        // Assert that the return value is EINVAL (errno for "Invalid argument")
-        assert_eq!(result, -(Errno::EINVAL as i32));
+        //Creating the parent directory and the child directory with all the
+        //required flags, then taking away search permission from the parent
+        //by excluding any execute flags and keeping only write flags.
+        let path = "/parent_dir/dir";
+        assert_eq!(cage.mkdir_syscall("/parent_dir", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
+        assert_eq!(
+            cage.chmod_syscall("/parent_dir", S_IWUSR | S_IWGRP | S_IWOTH),
+            0
+        );
+        //A directory whose path includes a component that does not allow
+        //search permission cannot be removed.
+        assert_eq!(cage.rmdir_syscall(path), -(Errno::EACCES as i32));
 
-        // Close the directory
-        assert_eq!(cage.close_syscall(fd), 0);
+        //Once search permission is restored, the directory can be removed
+        assert_eq!(cage.chmod_syscall("/parent_dir", S_IRWXA), 0);
+        assert_eq!(cage.rmdir_syscall(path), 0);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    fn ut_lind_fs_getdents_non_directory_fd() {
-        // Acquire a lock on TESTMUTEX to prevent other tests from running concurrently,
-        // and also perform clean environment setup.
+    pub fn ut_lind_fs_stat_file_complex() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
+        let fd = cage.open_syscall("/fooComplex", O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
 
-        // Create a regular file
-        let filepath = "/regularfile";
-        let fd = cage.open_syscall(filepath, O_CREAT | O_WRONLY, S_IRWXA);
-        assert!(fd >= 0);
-        // Allocate a buffer to store directory entries
-        let bufsize = 1024;
-        let mut vec = vec![0u8; bufsize as usize];
-        let baseptr: *mut u8 = &mut vec[0];
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hi"), 2), 2);
 
-        // Attempt to call getdents_syscall on the regular file descriptor
-        let result = cage.getdents_syscall(fd, baseptr, bufsize as u32);
-        // Verify that it returns ENOTDIR
-        assert_eq!(result, -(Errno::ENOTDIR as i32));
+        let mut statdata = StatData::default();
+        let mut statdata2 = StatData::default();
+
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 2);
+        assert_eq!(statdata.st_nlink, 1);
+
+        assert_eq!(cage.link_syscall("/fooComplex", "/barComplex"), 0);
+        assert_eq!(cage.stat_syscall("/fooComplex", &mut statdata), 0);
+        assert_eq!(cage.stat_syscall("/barComplex", &mut statdata2), 0);
+
+        //check that they are the same and that the link count is 0
+        assert!(statdata == statdata2);
+        assert_eq!(statdata.st_nlink, 2);
 
-        // Clean up: Close the file descriptor and finalize the test environment
-        assert_eq!(cage.close_syscall(fd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_dir_chdir_getcwd() {
+    pub fn ut_lind_fs_stat_file_mode() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
-        //and also performs clean env setup
+        // and also performs clean env setup
         let _thelock = setup::lock_and_init();
+
         let cage = interface::cagetable_getref(1);
+        let path = "/fooFileMode";
+        let _fd = cage.open_syscall(path, O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
 
-        //Checking if retrieving the current working directory
-        //`/newcwd1` by passing a string long enough to store
-        //the directory succeeds
-        //`newcwdsize` is the size of the string needed to store the new
-        //current woring directory `/newcwd1`
-        let newcwdsize = "/newcwd1\0".as_bytes().to_vec().len();
-        let newcwdsize_u32: u32 = newcwdsize as u32;
-        //Creating the new current working directory and checking if it exists
-        cage.mkdir_syscall("/newcwd1", S_IRWXA);
-        assert_eq!(cage.access_syscall("newcwd1", F_OK), 0);
-        //Creating a string large enough to store the new current
-        //working directory and filling it with 0's
-        let mut buf = vec![0u8; newcwdsize];
-        let bufptr: *mut u8 = &mut buf[0];
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
+        assert_eq!(statdata.st_mode, S_IRWXA | S_IFREG as u32);
 
-        //First, we change to the root directory and call `getcwd_syscall()`
-        //to check if the root current working directory is successfully
-        //retrieved
-        assert_eq!(cage.chdir_syscall("/"), 0);
-        assert_eq!(cage.getcwd_syscall(bufptr, 2), 0);
-        assert_eq!(std::str::from_utf8(&buf).unwrap(), "/\0\0\0\0\0\0\0\0");
+        //make a file without permissions and check that it is a reg file without
+        // permissions
+        let path2 = "/fooFileMode2";
+        let _fd2 = cage.open_syscall(path2, O_CREAT | O_EXCL | O_WRONLY, 0);
+        assert_eq!(cage.stat_syscall(path2, &mut statdata), 0);
+        assert_eq!(statdata.st_mode, S_IFREG as u32);
 
-        //Now, we change to the newly created directory and check if it is
-        //successfully retrieved as a new current working directory
-        assert_eq!(cage.chdir_syscall("/newcwd1"), 0);
-        assert_eq!(cage.getcwd_syscall(bufptr, newcwdsize_u32), 0);
-        assert_eq!(std::str::from_utf8(&buf).unwrap(), "/newcwd1\0");
+        //check that stat can be done on the current (root) dir
+        assert_eq!(cage.stat_syscall(".", &mut statdata), 0);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_getcwd_invalid_args() {
-        //Acquiring a lock on TESTMUTEX prevents other tests from running
-        //concurrently, and also performs clean env setup
+    pub fn ut_lind_fs_statfs() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-        let cage = interface::cagetable_getref(1);
-
-        //`/newcwd2` is a valid directory that we will create to check
-        //if calling `getcwd_syscall()` with invalid arguments
-        //correctly returns the required errors.
-        //`newcwdsize` is the size of the string needed to store the new
-        //current working directory `/newcwd1`
-        let newcwdsize = "/newcwd2\0".as_bytes().to_vec().len();
-        let newcwdsize_u32: u32 = newcwdsize as u32;
-        //Creating the new current working directory, checking if it exists
-        //and changing to it
-        cage.mkdir_syscall("/newcwd2", S_IRWXA);
-        assert_eq!(cage.access_syscall("newcwd2", F_OK), 0);
-        assert_eq!(cage.chdir_syscall("newcwd2"), 0);
 
-        //Checking if passing a valid string pointer and a size of 0 to
-        //`getcwd_syscall()` correctly results in `The size argument is zero and
-        //buf is not a null pointer` error
-        //Creating a string large enough to store the new current
-        //working directory and filling it with 0's
-        let mut buf = vec![0u8; newcwdsize];
-        let bufptr: *mut u8 = &mut buf[0];
-        assert_eq!(cage.getcwd_syscall(bufptr, 0), -(Errno::EINVAL as i32));
+        let cage = interface::cagetable_getref(1);
+        let mut fsdata = FSData::default();
 
-        //Checking if passing a valid string pointer and a non-zero size smaller
-        //than the size of the current working directory plus the terminating null
-        //character to `getcwd_syscall()` correctly results in `The bufsize argument
-        //is less than the length of the absolute pathname of the working directory,
-        //including the terminating null byte` error
-        assert_eq!(
-            cage.getcwd_syscall(bufptr, newcwdsize_u32 - 1),
-            -(Errno::ERANGE as i32)
-        );
+        assert_eq!(cage.statfs_syscall("/", &mut fsdata), 0);
+        assert_eq!(fsdata.f_type, 0xBEEFC0DE);
+        assert_eq!(fsdata.f_bsize, 4096);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_exec_cloexec() {
+    pub fn ut_lind_fs_fstatfs() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let mut uselessstatdata = StatData::default();
+        let mut fsdata = FSData::default();
 
-        let fd1 = cage.open_syscall(
-            "/cloexecuted",
-            O_CREAT | O_TRUNC | O_RDWR | O_CLOEXEC,
-            S_IRWXA,
-        );
-        let fd2 = cage.open_syscall("/cloexekept", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
-        assert!(fd1 > 0);
-        assert!(fd2 > 0);
-        assert_eq!(cage.fstat_syscall(fd1, &mut uselessstatdata), 0);
-        assert_eq!(cage.fstat_syscall(fd2, &mut uselessstatdata), 0);
+        // Get fd
+        let fd = cage.open_syscall("/", O_RDONLY, 0);
+        assert!(fd >= 0);
+        // fstatfs
+        assert_eq!(cage.fstatfs_syscall(fd, &mut fsdata), 0);
+        // Check the output
+        assert_eq!(fsdata.f_type, 0xBEEFC0DE);
+        assert_eq!(fsdata.f_bsize, 4096);
+        // Close the file
+        assert_eq!(cage.close_syscall(fd), 0);
 
-        assert_eq!(cage.exec_syscall(2), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
 
-        let execcage = interface::cagetable_getref(2);
-        assert_eq!(
-            execcage.fstat_syscall(fd1, &mut uselessstatdata),
-            -(Errno::EBADF as i32)
-        );
-        assert_eq!(execcage.fstat_syscall(fd2, &mut uselessstatdata), 0);
+    #[test]
+    pub fn ut_lind_fs_rename() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
 
-        assert_eq!(execcage.close_syscall(fd2), 0);
-        assert_eq!(cage.unlink_syscall("/cloexecuted"), 0);
-        assert_eq!(cage.unlink_syscall("/cloexekept"), 0);
+        let cage = interface::cagetable_getref(1);
 
-        assert_eq!(execcage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        let old_path = "/test_dir";
+        assert_eq!(cage.mkdir_syscall(old_path, S_IRWXA), 0);
+        assert_eq!(cage.rename_syscall(old_path, "/test_dir_renamed"), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_shm() {
+    pub fn ut_lind_fs_ftruncate() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let key = 31337;
-        let mut shmidstruct = ShmidsStruct::default();
-
-        // shmget returns an identifier in shmid
-        let shmid = cage.shmget_syscall(key, 1024, 0666 | IPC_CREAT);
 
-        // shmat to attach to shared memory
-        let shmatret = cage.shmat_syscall(shmid, 0xfffff000 as *mut u8, 0);
+        let fd = cage.open_syscall("/ftruncate", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
 
-        assert_ne!(shmatret, -1);
+        // check if ftruncate() works for extending file with null bytes
+        assert_eq!(cage.write_syscall(fd, str2cbuf("Hello there!"), 12), 12);
+        assert_eq!(cage.ftruncate_syscall(fd, 15), 0);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
+        let mut buf = sizecbuf(15);
+        assert_eq!(cage.read_syscall(fd, buf.as_mut_ptr(), 15), 15);
+        assert_eq!(cbuf2str(&buf), "Hello there!\0\0\0");
 
-        // get struct info
-        let shmctlret1 = cage.shmctl_syscall(shmid, IPC_STAT, Some(&mut shmidstruct));
+        // check if ftruncate() works for cutting off extra bytes
+        assert_eq!(cage.ftruncate_syscall(fd, 5), 0);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
+        let mut buf1 = sizecbuf(7);
+        assert_eq!(cage.read_syscall(fd, buf1.as_mut_ptr(), 7), 5);
+        assert_eq!(cbuf2str(&buf1), "Hello\0\0");
 
-        assert_eq!(shmctlret1, 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
 
-        assert_eq!(shmidstruct.shm_nattch, 1);
+    #[test]
+    pub fn ut_lind_fs_truncate() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
 
-        // mark the shared memory to be rmoved
-        let shmctlret2 = cage.shmctl_syscall(shmid, IPC_RMID, None);
+        let cage = interface::cagetable_getref(1);
 
-        assert_eq!(shmctlret2, 0);
+        let path = String::from("/truncate");
+        let fd = cage.open_syscall(&path, O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
 
-        //detach from shared memory
-        let shmdtret = cage.shmdt_syscall(0xfffff000 as *mut u8);
+        // check if truncate() works for extending file with null bytes
+        assert_eq!(cage.write_syscall(fd, str2cbuf("Hello there!"), 12), 12);
+        assert_eq!(cage.truncate_syscall(&path, 15), 0);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
+        let mut buf = sizecbuf(15);
+        assert_eq!(cage.read_syscall(fd, buf.as_mut_ptr(), 15), 15);
+        assert_eq!(cbuf2str(&buf), "Hello there!\0\0\0");
 
-        assert_eq!(shmdtret, shmid); //NaCl requires shmdt to return the shmid, so this is non-posixy
+        // check if truncate() works for cutting off extra bytes
+        assert_eq!(cage.truncate_syscall(&path, 5), 0);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
+        let mut buf1 = sizecbuf(7);
+        assert_eq!(cage.read_syscall(fd, buf1.as_mut_ptr(), 7), 5);
+        assert_eq!(cbuf2str(&buf1), "Hello\0\0");
 
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
+    #[cfg(target_os = "macos")]
+    type CharPtr = *const u8;
+
+    #[cfg(not(target_os = "macos"))]
+    type CharPtr = *const i8;
+
     #[test]
-    pub fn ut_lind_fs_getpid_getppid() {
+    pub fn ut_lind_fs_getdents() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
-        let cage1 = interface::cagetable_getref(1);
-        let pid1 = cage1.getpid_syscall();
+        let cage = interface::cagetable_getref(1);
 
-        assert_eq!(cage1.fork_syscall(2), 0);
+        let bufsize = 50;
+        let mut vec = vec![0u8; bufsize as usize];
+        let baseptr: *mut u8 = &mut vec[0];
 
-        let child = std::thread::spawn(move || {
-            let cage2 = interface::cagetable_getref(2);
-            let pid2 = cage2.getpid_syscall();
-            let ppid2 = cage2.getppid_syscall();
+        assert_eq!(cage.mkdir_syscall("/getdents", S_IRWXA), 0);
+        let fd = cage.open_syscall("/getdents", O_RDWR, S_IRWXA);
+        assert_eq!(cage.getdents_syscall(fd, baseptr, bufsize as u32), 48);
 
-            assert_ne!(pid2, pid1); // make sure the child and the parent have different pids
-            assert_eq!(ppid2, pid1); // make sure the child's getppid is correct
+        unsafe {
+            let first_dirent = baseptr as *mut interface::ClippedDirent;
+            assert!((*first_dirent).d_off == 24);
+            let reclen_matched: bool = ((*first_dirent).d_reclen == 24);
+            assert_eq!(reclen_matched, true);
 
-            assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
-        });
+            let nameoffset = baseptr.wrapping_offset(interface::CLIPPED_DIRENT_SIZE as isize);
+            let returnedname = interface::RustCStr::from_ptr(nameoffset as *const _);
+            let name_matched: bool = (returnedname
+                == interface::RustCStr::from_bytes_with_nul(b".\0").unwrap())
+                | (returnedname == interface::RustCStr::from_bytes_with_nul(b"..\0").unwrap());
+            assert_eq!(name_matched, true);
 
-        child.join().unwrap();
-        assert_eq!(cage1.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+            let second_dirent = baseptr.wrapping_offset(24) as *mut interface::ClippedDirent;
+            assert!((*second_dirent).d_off >= 48);
+        }
+
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
-
-    // This test verifies the functionality of semaphores in a fork scenario.
-    // The test involves a parent process and a child process that synchronize
-    //their execution using a shared semaphore. The test aims to ensure:
-    //   1. The semaphore is initialized correctly.
-    //   2. The child process can acquire and release the semaphore.
-    //   3. The parent process can acquire and release the semaphore after the child
-    //      process exits.
-    //   4. The semaphore can be destroyed safely.
     #[test]
-    pub fn ut_lind_fs_sem_fork() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
-        // and also performs clean env setup
+    fn ut_lind_fs_getdents_invalid_fd() {
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
-        let key = 31337;
 
-        // Create a shared memory region of 1024 bytes. This region will be
-        // shared between the parent and child process.
-        // IPC_CREAT tells the system to create a new memory segment for the shared
-        // memory and 0666 sets the access permissions of the memory segment.
-        let shmid = cage.shmget_syscall(key, 1024, 0666 | IPC_CREAT);
+        let bufsize = 50;
+        let mut vec = vec![0u8; bufsize as usize];
+        let baseptr: *mut u8 = &mut vec[0];
 
-        // Attach shared memory for semaphore access.
-        let shmatret = cage.shmat_syscall(shmid, 0xfffff000 as *mut u8, 0);
-        assert_ne!(shmatret, -1);
-        // Initialize semaphore in shared memory (initial value: 1, available).
-        let ret_init = cage.sem_init_syscall(shmatret as u32, 1, 1);
-        assert_eq!(ret_init, 0);
-        assert_eq!(cage.sem_getvalue_syscall(shmatret as u32), 1);
-        // Fork process to create child (new cagetable ID 2) for semaphore testing.
-        assert_eq!(cage.fork_syscall(2), 0);
-        // Create thread to simulate child process behavior after forking.
-        let thread_child = interface::helper_thread(move || {
-            // Set reference to child process's cagetable (ID 2) for independent operation.
-            let cage1 = interface::cagetable_getref(2);
-            // Child process blocks on semaphore wait (decrementing it from 1 to 0).
-            assert_eq!(cage1.sem_wait_syscall(shmatret as u32), 0);
-            // Simulate processing time with 40ms delay.
-            interface::sleep(interface::RustDuration::from_millis(40));
-            // Child process releases semaphore, signaling its availability to parent
-            //(value increases from 0 to 1).
-            assert_eq!(cage1.sem_post_syscall(shmatret as u32), 0);
-            cage1.exit_syscall(EXIT_SUCCESS);
-        });
+        // Create a directory
+        assert_eq!(cage.mkdir_syscall("/getdents", S_IRWXA), 0);
 
-        // Parent waits on semaphore (blocks until released by child, decrementing to
-        // 0).
-        assert_eq!(cage.sem_wait_syscall(shmatret as u32), 0);
-        assert_eq!(cage.sem_getvalue_syscall(shmatret as u32), 0);
-        // Simulate parent process processing time with 100ms delay to ensure
-        // synchronization.
-        interface::sleep(interface::RustDuration::from_millis(100));
-        // Wait for child process to finish to prevent race conditions before destroying
-        // semaphore. Release semaphore, making it available again (value
-        // increases to 1).
-        assert_eq!(cage.sem_post_syscall(shmatret as u32), 0);
-        thread_child.join().unwrap();
+        // Open the directory
+        let fd = cage.open_syscall("/getdents", O_RDWR, S_IRWXA);
 
-        // Destroy the semaphore
-        assert_eq!(cage.sem_destroy_syscall(shmatret as u32), 0);
-        // Mark the shared memory segment to be removed.
-        let shmctlret2 = cage.shmctl_syscall(shmid, IPC_RMID, None);
-        assert_eq!(shmctlret2, 0);
-        //detach from shared memory
-        let shmdtret = cage.shmdt_syscall(0xfffff000 as *mut u8);
-        assert_eq!(shmdtret, shmid);
-        cage.exit_syscall(EXIT_SUCCESS);
+        // Attempt to call `getdents_syscall` with an invalid file descriptor
+        let result = cage.getdents_syscall(-1, baseptr, bufsize as u32);
 
+        // Assert that the return value is EBADF (errno for "Bad file descriptor")
+        assert_eq!(result, -(Errno::EBADF as i32));
+
+        // Close the directory
+        assert_eq!(cage.close_syscall(fd), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
-    // This test verifies the functionality of timed semaphores in a fork scenario.
-    // It involves a parent process and a child process that synchronize their
-    // execution using a shared semaphore with a timeout. The test aims to
-    // ensure:
-    //  1. The semaphore is initialized correctly.
-    //  2. The child process can acquire and release the semaphore.
-    //  3. The parent process can acquire the semaphore using a timed wait operation
-    //     with a
-    //  timeout, and the semaphore is acquired successfully.
-    //  4. The parent process can release the semaphore.
-    //  5. The semaphore can be destroyed safely.
     #[test]
-    pub fn ut_lind_fs_sem_trytimed() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
-        // and also performs clean env setup
+    fn ut_lind_fs_getdents_out_of_range_fd() {
+        // Acquire a lock on TESTMUTEX to prevent other tests from running concurrently,
+        // and also perform clean environment setup.
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let key = 31337;
-        // Create a shared memory region of 1024 bytes.
-        //This region will be shared between the parent and child process.
-        // IPC_CREAT tells the system to create a new memory segment for the shared
-        // memory and 0666 sets the access permissions of the memory segment.
-        let shmid = cage.shmget_syscall(key, 1024, 0666 | IPC_CREAT);
-        // Attach the shared memory region to the address space of the process
-        // to make sure for both processes to access the shared semaphore.
-        let shmatret = cage.shmat_syscall(shmid, 0xfffff000 as *mut u8, 0);
-        assert_ne!(shmatret, -1);
-        // Initialize semaphore in shared memory (initial value: 1, available).
-        let ret_init = cage.sem_init_syscall(shmatret as u32, 1, 1);
-        assert_eq!(ret_init, 0);
-        assert_eq!(cage.sem_getvalue_syscall(shmatret as u32), 1);
-        // Fork process, creating a child process with its own independent cagetable (ID
-        // 2).
+
+        // Allocate a buffer to store directory entries
+        let bufsize = 1024;
+        let mut vec = vec![0u8; bufsize as usize];
+        let baseptr: *mut u8 = &mut vec[0];
+
+        // Attempt to call getdents_syscall with a file descriptor out of range
+        let result = cage.getdents_syscall(MAXFD + 1, baseptr, bufsize as u32);
+
+        // Verify that it returns EBADF (errno for "Bad file descriptor")
+        assert_eq!(result, -(Errno::EBADF as i32));
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    fn ut_lind_fs_getdents_non_existing_fd() {
+        // Acquire a lock on TESTMUTEX to prevent other tests from running concurrently,
+        // and also perform clean environment setup.
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Allocate a buffer to store directory entries
+        let bufsize = 1024;
+        let mut vec = vec![0u8; bufsize as usize];
+        let baseptr: *mut u8 = &mut vec[0];
+
+        // Attempt to call getdents_syscall with a non-existing file descriptor
+        let result = cage.getdents_syscall(100, baseptr, bufsize as u32);
+
+        // Verify that it returns EBADF (errno for "Bad file descriptor")
+        assert_eq!(result, -(Errno::EBADF as i32));
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    fn ut_lind_fs_getdents_bufsize_too_small() {
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        let bufsize = interface::CLIPPED_DIRENT_SIZE - 1; // Buffer size smaller than CLIPPED_DIRENT_SIZE
+        let mut vec = vec![0u8; bufsize as usize];
+        let baseptr: *mut u8 = &mut vec[0];
+
+        // Create a directory
+        assert_eq!(cage.mkdir_syscall("/getdents", S_IRWXA), 0);
+
+        // Open the directory
+        let fd = cage.open_syscall("/getdents", O_RDWR, S_IRWXA);
+
+        // Attempt to call `getdents_syscall` with a buffer size smaller than
+        // CLIPPED_DIRENT_SIZE
+        let result = cage.getdents_syscall(fd, baseptr, bufsize as u32);
+
+        // Assert that the return value is EINVAL (errno for "Invalid argument")
+        assert_eq!(result, -(Errno::EINVAL as i32));
+
+        // Close the directory
+        assert_eq!(cage.close_syscall(fd), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    fn ut_lind_fs_getdents_non_directory_fd() {
+        // Acquire a lock on TESTMUTEX to prevent other tests from running concurrently,
+        // and also perform clean environment setup.
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Create a regular file
+        let filepath = "/regularfile";
+        let fd = cage.open_syscall(filepath, O_CREAT | O_WRONLY, S_IRWXA);
+        assert!(fd >= 0);
+        // Allocate a buffer to store directory entries
+        let bufsize = 1024;
+        let mut vec = vec![0u8; bufsize as usize];
+        let baseptr: *mut u8 = &mut vec[0];
+
+        // Attempt to call getdents_syscall on the regular file descriptor
+        let result = cage.getdents_syscall(fd, baseptr, bufsize as u32);
+        // Verify that it returns ENOTDIR
+        assert_eq!(result, -(Errno::ENOTDIR as i32));
+
+        // Clean up: Close the file descriptor and finalize the test environment
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_dir_chdir_getcwd() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        //and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        //Checking if retrieving the current working directory
+        //`/newcwd1` by passing a string long enough to store
+        //the directory succeeds
+        //`newcwdsize` is the size of the string needed to store the new
+        //current woring directory `/newcwd1`
+        let newcwdsize = "/newcwd1\0".as_bytes().to_vec().len();
+        let newcwdsize_u32: u32 = newcwdsize as u32;
+        //Creating the new current working directory and checking if it exists
+        cage.mkdir_syscall("/newcwd1", S_IRWXA);
+        assert_eq!(cage.access_syscall("newcwd1", F_OK), 0);
+        //Creating a string large enough to store the new current
+        //working directory and filling it with 0's
+        let mut buf = vec![0u8; newcwdsize];
+        let bufptr: *mut u8 = &mut buf[0];
+
+        //First, we change to the root directory and call `getcwd_syscall()`
+        //to check if the root current working directory is successfully
+        //retrieved
+        assert_eq!(cage.chdir_syscall("/"), 0);
+        assert_eq!(cage.getcwd_syscall(bufptr, 2), 0);
+        assert_eq!(std::str::from_utf8(&buf).unwrap(), "/\0\0\0\0\0\0\0\0");
+
+        //Now, we change to the newly created directory and check if it is
+        //successfully retrieved as a new current working directory
+        assert_eq!(cage.chdir_syscall("/newcwd1"), 0);
+        assert_eq!(cage.getcwd_syscall(bufptr, newcwdsize_u32), 0);
+        assert_eq!(std::str::from_utf8(&buf).unwrap(), "/newcwd1\0");
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_getcwd_invalid_args() {
+        //Acquiring a lock on TESTMUTEX prevents other tests from running
+        //concurrently, and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        //`/newcwd2` is a valid directory that we will create to check
+        //if calling `getcwd_syscall()` with invalid arguments
+        //correctly returns the required errors.
+        //`newcwdsize` is the size of the string needed to store the new
+        //current working directory `/newcwd1`
+        let newcwdsize = "/newcwd2\0".as_bytes().to_vec().len();
+        let newcwdsize_u32: u32 = newcwdsize as u32;
+        //Creating the new current working directory, checking if it exists
+        //and changing to it
+        cage.mkdir_syscall("/newcwd2", S_IRWXA);
+        assert_eq!(cage.access_syscall("newcwd2", F_OK), 0);
+        assert_eq!(cage.chdir_syscall("newcwd2"), 0);
+
+        //Checking if passing a valid string pointer and a size of 0 to
+        //`getcwd_syscall()` correctly results in `The size argument is zero and
+        //buf is not a null pointer` error
+        //Creating a string large enough to store the new current
+        //working directory and filling it with 0's
+        let mut buf = vec![0u8; newcwdsize];
+        let bufptr: *mut u8 = &mut buf[0];
+        assert_eq!(cage.getcwd_syscall(bufptr, 0), -(Errno::EINVAL as i32));
+
+        //Checking if passing a valid string pointer and a non-zero size smaller
+        //than the size of the current working directory plus the terminating null
+        //character to `getcwd_syscall()` correctly results in `The bufsize argument
+        //is less than the length of the absolute pathname of the working directory,
+        //including the terminating null byte` error
+        assert_eq!(
+            cage.getcwd_syscall(bufptr, newcwdsize_u32 - 1),
+            -(Errno::ERANGE as i32)
+        );
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_exec_cloexec() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let mut uselessstatdata = StatData::default();
+
+        let fd1 = cage.open_syscall(
+            "/cloexecuted",
+            O_CREAT | O_TRUNC | O_RDWR | O_CLOEXEC,
+            S_IRWXA,
+        );
+        let fd2 = cage.open_syscall("/cloexekept", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        assert!(fd1 > 0);
+        assert!(fd2 > 0);
+        assert_eq!(cage.fstat_syscall(fd1, &mut uselessstatdata), 0);
+        assert_eq!(cage.fstat_syscall(fd2, &mut uselessstatdata), 0);
+
+        assert_eq!(cage.exec_syscall(2), 0);
+
+        let execcage = interface::cagetable_getref(2);
+        assert_eq!(
+            execcage.fstat_syscall(fd1, &mut uselessstatdata),
+            -(Errno::EBADF as i32)
+        );
+        assert_eq!(execcage.fstat_syscall(fd2, &mut uselessstatdata), 0);
+
+        assert_eq!(execcage.close_syscall(fd2), 0);
+        assert_eq!(cage.unlink_syscall("/cloexecuted"), 0);
+        assert_eq!(cage.unlink_syscall("/cloexekept"), 0);
+
+        assert_eq!(execcage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_shm() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let key = 31337;
+        let mut shmidstruct = ShmidsStruct::default();
+
+        // shmget returns an identifier in shmid
+        let shmid = cage.shmget_syscall(key, 1024, 0666 | IPC_CREAT);
+
+        // shmat to attach to shared memory
+        let shmatret = cage.shmat_syscall(shmid, 0xfffff000 as *mut u8, 0);
+
+        assert_ne!(shmatret, -1);
+
+        // get struct info
+        let shmctlret1 = cage.shmctl_syscall(shmid, IPC_STAT, Some(&mut shmidstruct));
+
+        assert_eq!(shmctlret1, 0);
+
+        assert_eq!(shmidstruct.shm_nattch, 1);
+
+        // mark the shared memory to be rmoved
+        let shmctlret2 = cage.shmctl_syscall(shmid, IPC_RMID, None);
+
+        assert_eq!(shmctlret2, 0);
+
+        //detach from shared memory
+        let shmdtret = cage.shmdt_syscall(0xfffff000 as *mut u8);
+
+        assert_eq!(shmdtret, shmid); //NaCl requires shmdt to return the shmid, so this is non-posixy
+
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_getpid_getppid() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage1 = interface::cagetable_getref(1);
+        let pid1 = cage1.getpid_syscall();
+
+        assert_eq!(cage1.fork_syscall(2), 0);
+
+        let child = std::thread::spawn(move || {
+            let cage2 = interface::cagetable_getref(2);
+            let pid2 = cage2.getpid_syscall();
+            let ppid2 = cage2.getppid_syscall();
+
+            assert_ne!(pid2, pid1); // make sure the child and the parent have different pids
+            assert_eq!(ppid2, pid1); // make sure the child's getppid is correct
+
+            assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        });
+
+        child.join().unwrap();
+        assert_eq!(cage1.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    // This test verifies the functionality of semaphores in a fork scenario.
+    // The test involves a parent process and a child process that synchronize
+    //their execution using a shared semaphore. The test aims to ensure:
+    //   1. The semaphore is initialized correctly.
+    //   2. The child process can acquire and release the semaphore.
+    //   3. The parent process can acquire and release the semaphore after the child
+    //      process exits.
+    //   4. The semaphore can be destroyed safely.
+    #[test]
+    pub fn ut_lind_fs_sem_fork() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let key = 31337;
+
+        // Create a shared memory region of 1024 bytes. This region will be
+        // shared between the parent and child process.
+        // IPC_CREAT tells the system to create a new memory segment for the shared
+        // memory and 0666 sets the access permissions of the memory segment.
+        let shmid = cage.shmget_syscall(key, 1024, 0666 | IPC_CREAT);
+
+        // Attach shared memory for semaphore access.
+        let shmatret = cage.shmat_syscall(shmid, 0xfffff000 as *mut u8, 0);
+        assert_ne!(shmatret, -1);
+        // Initialize semaphore in shared memory (initial value: 1, available).
+        let ret_init = cage.sem_init_syscall(shmatret as u32, 1, 1);
+        assert_eq!(ret_init, 0);
+        assert_eq!(cage.sem_getvalue_syscall(shmatret as u32), 1);
+        // Fork process to create child (new cagetable ID 2) for semaphore testing.
+        assert_eq!(cage.fork_syscall(2), 0);
+        // Create thread to simulate child process behavior after forking.
+        let thread_child = interface::helper_thread(move || {
+            // Set reference to child process's cagetable (ID 2) for independent operation.
+            let cage1 = interface::cagetable_getref(2);
+            // Child process blocks on semaphore wait (decrementing it from 1 to 0).
+            assert_eq!(cage1.sem_wait_syscall(shmatret as u32), 0);
+            // Simulate processing time with 40ms delay.
+            interface::sleep(interface::RustDuration::from_millis(40));
+            // Child process releases semaphore, signaling its availability to parent
+            //(value increases from 0 to 1).
+            assert_eq!(cage1.sem_post_syscall(shmatret as u32), 0);
+            cage1.exit_syscall(EXIT_SUCCESS);
+        });
+
+        // Parent waits on semaphore (blocks until released by child, decrementing to
+        // 0).
+        assert_eq!(cage.sem_wait_syscall(shmatret as u32), 0);
+        assert_eq!(cage.sem_getvalue_syscall(shmatret as u32), 0);
+        // Simulate parent process processing time with 100ms delay to ensure
+        // synchronization.
+        interface::sleep(interface::RustDuration::from_millis(100));
+        // Wait for child process to finish to prevent race conditions before destroying
+        // semaphore. Release semaphore, making it available again (value
+        // increases to 1).
+        assert_eq!(cage.sem_post_syscall(shmatret as u32), 0);
+        thread_child.join().unwrap();
+
+        // Destroy the semaphore
+        assert_eq!(cage.sem_destroy_syscall(shmatret as u32), 0);
+        // Mark the shared memory segment to be removed.
+        let shmctlret2 = cage.shmctl_syscall(shmid, IPC_RMID, None);
+        assert_eq!(shmctlret2, 0);
+        //detach from shared memory
+        let shmdtret = cage.shmdt_syscall(0xfffff000 as *mut u8);
+        assert_eq!(shmdtret, shmid);
+        cage.exit_syscall(EXIT_SUCCESS);
+
+        lindrustfinalize();
+    }
+
+    // This test verifies the functionality of timed semaphores in a fork scenario.
+    // It involves a parent process and a child process that synchronize their
+    // execution using a shared semaphore with a timeout. The test aims to
+    // ensure:
+    //  1. The semaphore is initialized correctly.
+    //  2. The child process can acquire and release the semaphore.
+    //  3. The parent process can acquire the semaphore using a timed wait operation
+    //     with a
+    //  timeout, and the semaphore is acquired successfully.
+    //  4. The parent process can release the semaphore.
+    //  5. The semaphore can be destroyed safely.
+    #[test]
+    pub fn ut_lind_fs_sem_trytimed() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let key = 31337;
+        // Create a shared memory region of 1024 bytes.
+        //This region will be shared between the parent and child process.
+        // IPC_CREAT tells the system to create a new memory segment for the shared
+        // memory and 0666 sets the access permissions of the memory segment.
+        let shmid = cage.shmget_syscall(key, 1024, 0666 | IPC_CREAT);
+        // Attach the shared memory region to the address space of the process
+        // to make sure for both processes to access the shared semaphore.
+        let shmatret = cage.shmat_syscall(shmid, 0xfffff000 as *mut u8, 0);
+        assert_ne!(shmatret, -1);
+        // Initialize semaphore in shared memory (initial value: 1, available).
+        let ret_init = cage.sem_init_syscall(shmatret as u32, 1, 1);
+        assert_eq!(ret_init, 0);
+        assert_eq!(cage.sem_getvalue_syscall(shmatret as u32), 1);
+        // Fork process, creating a child process with its own independent cagetable (ID
+        // 2).
         assert_eq!(cage.fork_syscall(2), 0);
         // Define the child process behavior in a separate thread
         let thread_child = interface::helper_thread(move || {
@@ -2853,256 +4064,1142 @@ pub mod fs_tests {
         // Parent process waits (with 100ms timeout) for semaphore release by child
         //returns 0 if acquired successfully before timeout.
         assert_eq!(
-            cage.sem_timedwait_syscall(shmatret as u32, interface::RustDuration::from_millis(100)),
+            cage.sem_timedwait_syscall(shmatret as u32, interface::RustDuration::from_millis(100)),
+            0
+        );
+        assert_eq!(cage.sem_getvalue_syscall(shmatret as u32), 0);
+        // Simulate some work by sleeping for 10 milliseconds.
+        interface::sleep(interface::RustDuration::from_millis(10));
+        // Release semaphore, signaling its availability for parent
+        //(value increases from 0 to 1).
+        assert_eq!(cage.sem_post_syscall(shmatret as u32), 0);
+
+        // wait for the child process to exit before destroying the semaphore.
+        thread_child.join().unwrap();
+
+        // Destroy the semaphore
+        assert_eq!(cage.sem_destroy_syscall(shmatret as u32), 0);
+        // Mark the shared memory segment to be removed.
+        let shmctlret2 = cage.shmctl_syscall(shmid, IPC_RMID, None);
+        assert_eq!(shmctlret2, 0);
+        // Detach from the shared memory region.
+        let shmdtret = cage.shmdt_syscall(0xfffff000 as *mut u8);
+        assert_eq!(shmdtret, shmid);
+
+        cage.exit_syscall(EXIT_SUCCESS);
+
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_sem_test() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let key = 31337;
+        // Create a shared memory region
+        let shmid = cage.shmget_syscall(key, 1024, 0666 | IPC_CREAT);
+        // Attach the shared memory region
+        let shmatret = cage.shmat_syscall(shmid, 0xfffff000 as *mut u8, 0);
+        assert_ne!(shmatret, -1);
+        assert_eq!(cage.sem_destroy_syscall(shmatret as u32), -22);
+        assert_eq!(cage.sem_getvalue_syscall(shmatret as u32), -22);
+        assert_eq!(cage.sem_post_syscall(shmatret as u32), -22);
+        // Initialize the semaphore with shared between process
+        let ret_init = cage.sem_init_syscall(shmatret as u32, 1, 0);
+        assert_eq!(ret_init, 0);
+        // Should return errno
+        assert_eq!(
+            cage.sem_timedwait_syscall(shmatret as u32, interface::RustDuration::from_millis(100)),
+            -110
+        );
+        assert_eq!(cage.sem_trywait_syscall(shmatret as u32), -11);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_tmp_file_test() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Check if /tmp is there
+        assert_eq!(cage.access_syscall("/tmp", F_OK), 0);
+
+        // Open  file in /tmp
+        let file_path = "/tmp/testfile";
+        let fd = cage.open_syscall(file_path, O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+
+        assert_eq!(cage.write_syscall(fd, str2cbuf("Hello world"), 6), 6);
+        assert_eq!(cage.close_syscall(fd), 0);
+
+        lindrustfinalize();
+
+        // Init again
+        lindrustinit(0);
+        let cage = interface::cagetable_getref(1);
+
+        // Check if /tmp is there
+        assert_eq!(cage.access_syscall("/tmp", F_OK), 0);
+        // Check if file is still there (it shouldn't be, assert no)
+        assert_eq!(cage.access_syscall(file_path, F_OK), -2);
+
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_mkdir_empty_directory() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let path = "";
+        // Check for error when directory is empty
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), -(Errno::ENOENT as i32));
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_mkdir_nonexisting_directory() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let path = "/parentdir/dir";
+        // Check for error when both parent and child directories don't exist
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), -(Errno::ENOENT as i32));
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_mkdir_existing_directory() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let path = "/parentdir";
+        // Create a parent directory
+        cage.mkdir_syscall(path, S_IRWXA);
+        // Check for error when the same directory is created again
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), -(Errno::EEXIST as i32));
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_mkdir_invalid_modebits() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let path = "/parentdir";
+        let invalid_mode = 0o77777; // Invalid mode bits
+                                    // Create a parent directory
+        cage.mkdir_syscall(path, S_IRWXA);
+        // Check for error when a directory is being created with invalid mode
+        assert_eq!(
+            cage.mkdir_syscall("/parentdir/dir", invalid_mode),
+            -(Errno::EPERM as i32)
+        );
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_mkdir_success() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let path = "/parentdir";
+        // Create a parent directory
+        cage.mkdir_syscall(path, S_IRWXA);
+
+        // Get the stat data for the parent directory and check for inode link count to
+        // be 3 initially
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
+        assert_eq!(statdata.st_nlink, 3);
+
+        // Create a child directory inside parent directory with valid mode bits
+        assert_eq!(cage.mkdir_syscall("/parentdir/dir", S_IRWXA), 0);
+
+        // Get the stat data for the child directory and check for inode link count to
+        // be 3 initially
+        let mut statdata2 = StatData::default();
+        assert_eq!(cage.stat_syscall("/parentdir/dir", &mut statdata2), 0);
+        assert_eq!(statdata2.st_nlink, 3);
+
+        // Get the stat data for the parent directory and check for inode link count to
+        // be 4 now as a new child directory has been created.
+        let mut statdata3 = StatData::default();
+        assert_eq!(cage.stat_syscall(path, &mut statdata3), 0);
+        assert_eq!(statdata3.st_nlink, 4);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_mkdir_using_symlink() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Create a file which will be referred to as originalFile
+        let fd = cage.open_syscall("/originalFile", O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hi"), 2), 2);
+
+        // Create a link between two files where the symlinkFile is originally not
+        // present But while linking, symlinkFile will get created
+        assert_eq!(cage.link_syscall("/originalFile", "/symlinkFile"), 0);
+
+        // Check for error while creating the symlinkFile again as it would already be
+        // created while linking the two files above.
+        assert_eq!(
+            cage.mkdir_syscall("/symlinkFile", S_IRWXA),
+            -(Errno::EEXIST as i32)
+        );
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_open_empty_directory() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let path = "";
+        // Check for error when directory is empty
+        assert_eq!(
+            cage.open_syscall(path, O_CREAT | O_TRUNC | O_RDWR, S_IRWXA),
+            -(Errno::ENOENT as i32)
+        );
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_open_nonexisting_parentdirectory_and_file() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let path = "/dir/file";
+        // Check for error when neither file nor parent exists and O_CREAT flag is not
+        // present
+        assert_eq!(
+            cage.open_syscall(path, F_GETFD, S_IRWXA),
+            -(Errno::ENOENT as i32)
+        );
+
+        // Check for error when neither file nor parent exists and O_CREAT flag is
+        // present
+        assert_eq!(
+            cage.open_syscall(path, O_CREAT, S_IRWXA),
+            -(Errno::ENOENT as i32)
+        );
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_open_existing_parentdirectory_and_nonexisting_file() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        // Create a parent directory
+        assert_eq!(cage.mkdir_syscall("/dir", S_IRWXA), 0);
+        let path = "/dir/file";
+
+        // Check for error when parent directory exists but file doesn't exist and
+        // O_CREAT is not present
+        assert_eq!(
+            cage.open_syscall(path, O_TRUNC, S_IRWXA),
+            -(Errno::ENOENT as i32)
+        );
+
+        // Check for error when parent directory exists but file doesn't exist and
+        // Filetype Flags contain S_IFCHR flag
+        assert_eq!(
+            cage.open_syscall(path, S_IFCHR | O_CREAT, S_IRWXA),
+            -(Errno::EINVAL as i32)
+        );
+
+        // Check for error when parent directory exists but file doesn't exist and mode
+        // bits are invalid
+        let invalid_mode = 0o77777;
+        assert_eq!(
+            cage.open_syscall(path, O_CREAT, invalid_mode),
+            -(Errno::EPERM as i32)
+        );
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_open_existing_file_without_flags() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        // This test is used for validating two scenarios:
+        // 1. When the non-existing file is opened using O_CREAT flag, it should open
+        //    successfully.
+        // 2. When the same existing file is being opened without O_CREAT flag, it
+        //    should open successfully.
+        let cage = interface::cagetable_getref(1);
+
+        // Open a non-existing file with O_CREAT flag
+        // This should create a new file with a valid file descriptor
+        let path = "/test";
+        let fd = cage.open_syscall(path, O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd > 0);
+
+        // Open the existing file without O_CREAT and O_EXCL
+        // The file should open successfully as the two flags are not set while
+        // re-opening the file
+        let fd2 = cage.open_syscall(path, O_RDONLY, 0);
+        assert!(fd2 > 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_open_existing_file_with_flags() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        // This test is used for validating two scenarios:
+        // 1. When the non-existing file is opened using O_CREAT flag, it should open
+        //    successfully.
+        // 2. When the same existing file is opened using O_CREAT and O_EXCL flags, it
+        //    should return an error for file already existing.
+        let cage = interface::cagetable_getref(1);
+
+        // Open a non-existing file with O_CREAT flag
+        // This should create a new file with a valid file descriptor
+        let path = "/test";
+        let fd = cage.open_syscall(path, O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd > 0);
+
+        // Open the existing file with O_CREAT and O_EXCL flags
+        // The file should not open successfully as the two flags are set while
+        // re-opening the file It should return an error for "File already
+        // exists"
+        assert_eq!(
+            cage.open_syscall(path, O_CREAT | O_EXCL | O_RDONLY, S_IRWXA),
+            -(Errno::EEXIST as i32)
+        );
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_open_create_new_file_and_check_link_count() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Create a new file
+        let path = "/newfile.txt";
+        let fd = cage.open_syscall(path, O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd > 0);
+
+        // Write a string to the newly opened file of size 12
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
+
+        // Get the stat data for the file and check for file attributes
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
+
+        // Validate the link count for the new file to be 1
+        assert_eq!(statdata.st_nlink, 1);
+
+        // Validate the size of the file to be 12
+        assert_eq!(statdata.st_size, 12);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_open_existing_file_with_o_trunc_flag() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Create a new file
+        let path = "/file.txt";
+        let fd = cage.open_syscall(path, O_CREAT | O_WRONLY, S_IRWXA);
+        assert!(fd > 0);
+        // Write a string to the newly opened file of size 12
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
+        // Get the stat data for the file and check for file attributes
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
+        // Validate the size of the file to be 12
+        assert_eq!(statdata.st_size, 12);
+
+        // Open the same file with O_TRUNC flag
+        // Since the file is truncated, the size of the file should be truncated to 0.
+        let fd2 = cage.open_syscall(path, O_WRONLY | O_TRUNC, S_IRWXA);
+        assert!(fd2 > 0);
+        // Get the stat data for the same file and check for file attributes
+        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
+        // Validate the size of the file to be 0 as the file is truncated now
+        assert_eq!(statdata.st_size, 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_open_new_file_with_s_ifchar_flag() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Create a parent directory
+        assert_eq!(cage.mkdir_syscall("/testdir", S_IRWXA), 0);
+        let path = "/testdir/file";
+
+        // Attempt to open a file with S_IFCHR flag, which should be invalid for regular
+        // files
+        assert_eq!(
+            cage.open_syscall(path, O_CREAT | S_IFCHR, S_IRWXA),
+            -(Errno::EINVAL as i32)
+        );
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_creat_new_file() {
+        // Since this call is almost similar to open_syscall, and we have
+        // covered all the possible test scenarios for open_syscall above. So,
+        // just testing the basic working flow for the creat_sycall.
+
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Create a file and validate the size of it.
+        let path = "/creatFile";
+        let fd = cage.creat_syscall(path, S_IRWXA);
+        assert!(fd > 0);
+
+        let mut statdata = StatData::default();
+
+        // The size of the file should be 0
+        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_creat_truncate_existing_file() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        let path = "/creatFile";
+        // Create a new file
+        let fd = cage.creat_syscall(path, S_IRWXA);
+
+        // Write a string to the newly opened file of size 12
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
+
+        // Get the stat data for the file and check for file attributes
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
+
+        // Validate the size of the file to be 12
+        assert_eq!(statdata.st_size, 12);
+
+        // Call the function on the existing file, which should truncate
+        // the file size to 0.
+        let _fd2 = cage.creat_syscall(path, S_IRWXA);
+        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
+
+        // Validate the size of the file to be 0 now as should be truncated
+        assert_eq!(statdata.st_size, 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_read_write_only_fd() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Test to create a file with write only permissions, and check if
+        // a valid error is returned when the file is used for reading.
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_WRONLY, S_IRWXA);
+        let mut read_buf = sizecbuf(5);
+        assert_eq!(
+            cage.read_syscall(fd, read_buf.as_mut_ptr(), 5),
+            -(Errno::EBADF as i32)
+        );
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_read_from_directory() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Create a directory and try to read from it.
+        // We should expect an error (EISDIR) as reading from a directory is not
+        // supported.
+        let path = "/test_dir";
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
+        let fd = cage.open_syscall(path, O_RDONLY, S_IRWXA);
+
+        let mut read_buf = sizecbuf(5);
+        assert_eq!(
+            cage.read_syscall(fd, read_buf.as_mut_ptr(), 5),
+            -(Errno::EISDIR as i32)
+        );
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_read_from_epoll() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Create an Epoll and try to read from it.
+        // We should expect an error (EINVAL) as reading from an Epoll is not supported.
+        let epfd = cage.epoll_create_syscall(1);
+        assert!(epfd > 0);
+        let mut read_buf = sizecbuf(5);
+        assert_eq!(
+            cage.read_syscall(epfd, read_buf.as_mut_ptr(), 5),
+            -(Errno::EINVAL as i32)
+        );
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_read_from_regular_file() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // This test mainly tests two scenarios for reading from a regular file:
+        // * Reading from a file should initially start from 0 position.
+        // * Once read, the position of the seek pointer in the file descriptor should
+        // increment by the count of bytes read. If the read is performed again, then
+        // the position should continue from the point it was previously left.
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+
+        // Write sample data to the file.
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
+
+        // Set the initial position to 0 in the file descriptor.
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
+
+        // Read first 5 bytes from the file, and assert the result.
+        let mut read_buf1 = sizecbuf(5);
+        assert_eq!(cage.read_syscall(fd, read_buf1.as_mut_ptr(), 5), 5);
+        assert_eq!(cbuf2str(&read_buf1), "hello");
+
+        // Read next 7 bytes which should start from the previous position.
+        let mut read_buf2 = sizecbuf(7);
+        assert_eq!(cage.read_syscall(fd, read_buf2.as_mut_ptr(), 7), 7);
+        assert_eq!(cbuf2str(&read_buf2), " there!");
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_read_from_chardev_file() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // This test mainly tests the case for reading from a character device type
+        // file. In this case, we are trying to read 100 bytes from the
+        // "/dev/zero" file, which should return 100 bytes of "0" filled
+        // characters.
+        let path = "/dev/zero";
+        let fd = cage.open_syscall(path, O_RDWR, S_IRWXA);
+
+        // Verify if the returned count of bytes is 100.
+        let mut read_bufzero = sizecbuf(100);
+        assert_eq!(cage.read_syscall(fd, read_bufzero.as_mut_ptr(), 100), 100);
+        // Verify if the characters present in the buffer are all "0".
+        assert_eq!(
+            cbuf2str(&read_bufzero),
+            std::iter::repeat("\0")
+                .take(100)
+                .collect::<String>()
+                .as_str()
+        );
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_read_from_sockets() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // This test mainly tests the case for reading data from a pair of Sockets.
+        // In this case, we create a socket pair of two sockets, and send data through
+        // one socket, and try to read it from the other one using `read_syscall()`.
+        let mut socketpair = interface::SockPair::default();
+
+        // Verify if the socketpair is formed successfully.
+        assert_eq!(
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
             0
         );
-        assert_eq!(cage.sem_getvalue_syscall(shmatret as u32), 0);
-        // Simulate some work by sleeping for 10 milliseconds.
-        interface::sleep(interface::RustDuration::from_millis(10));
-        // Release semaphore, signaling its availability for parent
-        //(value increases from 0 to 1).
-        assert_eq!(cage.sem_post_syscall(shmatret as u32), 0);
+        // Verify if the number of bytes sent to socket1 is correct.
+        assert_eq!(
+            cage.send_syscall(socketpair.sock1, str2cbuf("test"), 4, 0),
+            4
+        );
+        // Verify if the number of bytes received by socket2 is correct.
+        let mut buf2 = sizecbuf(4);
+        assert_eq!(cage.read_syscall(socketpair.sock2, buf2.as_mut_ptr(), 4), 4);
+        // Verify if the data received inside the buffer is correct.
+        assert_eq!(cbuf2str(&buf2), "test");
+        // Close the sockets
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
 
-        // wait for the child process to exit before destroying the semaphore.
-        thread_child.join().unwrap();
+    #[test]
+    pub fn ut_lind_fs_read_from_pipe_blocking_mode() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
 
-        // Destroy the semaphore
-        assert_eq!(cage.sem_destroy_syscall(shmatret as u32), 0);
-        // Mark the shared memory segment to be removed.
-        let shmctlret2 = cage.shmctl_syscall(shmid, IPC_RMID, None);
-        assert_eq!(shmctlret2, 0);
-        // Detach from the shared memory region.
-        let shmdtret = cage.shmdt_syscall(0xfffff000 as *mut u8);
-        assert_eq!(shmdtret, shmid);
+        let cage = interface::cagetable_getref(1);
 
-        cage.exit_syscall(EXIT_SUCCESS);
+        // This test mainly tests the case of reading data from the pipe.
+        // We create two pipes, i.e., Read and Write and validate if the data
+        // received is correct or not.
+
+        // Create a pipe of read and write file descriptors.
+        let mut pipe_fds = PipeArray::default();
+        assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
+        let read_fd = pipe_fds.readfd;
+        let write_fd = pipe_fds.writefd;
+
+        let write_data = "Testing";
+        let mut buf = sizecbuf(7);
+
+        // Write data to the pipe
+        assert_eq!(
+            cage.write_syscall(write_fd, write_data.as_ptr(), write_data.len()),
+            write_data.len() as i32
+        );
+
+        // Read the data from the pipe and verify its count.
+        assert_eq!(
+            cage.read_syscall(read_fd, buf.as_mut_ptr(), buf.len()),
+            write_data.len() as i32
+        );
+        // Verify if the data returned in the pipe buffer is correct.
+        assert_eq!(cbuf2str(&buf), write_data);
+
+        // Close the file descriptors
+        assert_eq!(cage.close_syscall(read_fd), 0);
+        assert_eq!(cage.close_syscall(write_fd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_read_from_pipe_nonblocking_mode() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // This test mainly tests the case of reading data from the pipe, but in
+        // non-blocking mode. We create two pipes, i.e., Read and Write and
+        // validate if the data received is correct or not.
+
+        // Create a pipe of read and write file descriptors.
+        let mut pipe_fds = PipeArray::default();
+        assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
+        let read_fd = pipe_fds.readfd;
+        let write_fd = pipe_fds.writefd;
+
+        let write_data = "Testing";
+        let mut buf = sizecbuf(7);
+
+        // Set pipe to non-blocking mode
+        assert_eq!(cage.fcntl_syscall(read_fd, F_SETFL, O_NONBLOCK), 0);
+
+        // Read from the pipe (should return EAGAIN as there's no data yet)
+        assert_eq!(
+            cage.read_syscall(read_fd, buf.as_mut_ptr(), buf.len()),
+            -(Errno::EAGAIN as i32)
+        );
+
+        // Write data to the pipe
+        assert_eq!(
+            cage.write_syscall(write_fd, write_data.as_ptr(), write_data.len()),
+            write_data.len() as i32
+        );
+
+        // Read the data from the pipe and verify its count.
+        assert_eq!(
+            cage.read_syscall(read_fd, buf.as_mut_ptr(), buf.len()),
+            write_data.len() as i32
+        );
+        // Verify if the data returned in the pipe buffer is correct.
+        assert_eq!(cbuf2str(&buf), write_data);
 
+        // Close the file descriptors
+        assert_eq!(cage.close_syscall(read_fd), 0);
+        assert_eq!(cage.close_syscall(write_fd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_sem_test() {
+    pub fn ut_lind_fs_pread_write_only_fd() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let key = 31337;
-        // Create a shared memory region
-        let shmid = cage.shmget_syscall(key, 1024, 0666 | IPC_CREAT);
-        // Attach the shared memory region
-        let shmatret = cage.shmat_syscall(shmid, 0xfffff000 as *mut u8, 0);
-        assert_ne!(shmatret, -1);
-        assert_eq!(cage.sem_destroy_syscall(shmatret as u32), -22);
-        assert_eq!(cage.sem_getvalue_syscall(shmatret as u32), -22);
-        assert_eq!(cage.sem_post_syscall(shmatret as u32), -22);
-        // Initialize the semaphore with shared between process
-        let ret_init = cage.sem_init_syscall(shmatret as u32, 1, 0);
-        assert_eq!(ret_init, 0);
-        // Should return errno
+
+        // Test to create a file with write only permissions, and check if
+        // a valid error is returned when the file is used for reading.
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_WRONLY, S_IRWXA);
+        let mut read_buf = sizecbuf(5);
         assert_eq!(
-            cage.sem_timedwait_syscall(shmatret as u32, interface::RustDuration::from_millis(100)),
-            -110
+            cage.pread_syscall(fd, read_buf.as_mut_ptr(), 5, 0),
+            -(Errno::EBADF as i32)
         );
-        assert_eq!(cage.sem_trywait_syscall(shmatret as u32), -11);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_tmp_file_test() {
+    pub fn ut_lind_fs_pread_from_file() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Check if /tmp is there
-        assert_eq!(cage.access_syscall("/tmp", F_OK), 0);
+        // This test mainly tests two scenarios for reading from a file using
+        // `pread_syscall()`.
+        // * Reading from a file from the starting position offset(0).
+        // * Reading from a file from a random position offset.
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
 
-        // Open  file in /tmp
-        let file_path = "/tmp/testfile";
-        let fd = cage.open_syscall(file_path, O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        // Write sample data to the file.
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
 
-        assert_eq!(cage.write_syscall(fd, str2cbuf("Hello world"), 6), 6);
-        assert_eq!(cage.close_syscall(fd), 0);
+        // Set the initial position to 0 in the file descriptor.
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
+
+        // Read first 5 bytes from the file, and assert the result.
+        let mut read_buf1 = sizecbuf(5);
+        assert_eq!(cage.pread_syscall(fd, read_buf1.as_mut_ptr(), 5, 0), 5);
+        assert_eq!(cbuf2str(&read_buf1), "hello");
+
+        // Read 5 bytes, but from the 6th position offset of the file.
+        let mut read_buf2 = sizecbuf(5);
+        assert_eq!(cage.pread_syscall(fd, read_buf2.as_mut_ptr(), 5, 6), 5);
+        assert_eq!(cbuf2str(&read_buf2), "there");
 
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
+    }
 
-        // Init again
-        lindrustinit(0);
-        let cage = interface::cagetable_getref(1);
+    #[test]
+    pub fn ut_lind_fs_pread_from_directory() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
 
-        // Check if /tmp is there
-        assert_eq!(cage.access_syscall("/tmp", F_OK), 0);
-        // Check if file is still there (it shouldn't be, assert no)
-        assert_eq!(cage.access_syscall(file_path, F_OK), -2);
+        let cage = interface::cagetable_getref(1);
+        let mut buf = sizecbuf(5);
 
+        // Test for invalid directory should fail
+        let path = "/test_dir";
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
+        let fd = cage.open_syscall(path, O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(
+            cage.pread_syscall(fd, buf.as_mut_ptr(), buf.len(), 0),
+            -(Errno::EISDIR as i32)
+        );
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_mkdir_empty_directory() {
+    pub fn ut_lind_fs_pread_invalid_types() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let path = "";
-        // Check for error when directory is empty
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), -(Errno::ENOENT as i32));
+        let mut buf = sizecbuf(5);
+
+        // Test for invalid pipe
+        // Try reading the data from the pipe and check for error.
+        let mut pipe_fds = PipeArray::default();
+        assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
+        let read_fd = pipe_fds.readfd;
+        assert_eq!(
+            cage.pread_syscall(read_fd, buf.as_mut_ptr(), buf.len(), 0),
+            -(Errno::ESPIPE as i32)
+        );
+
+        // Test for invalid sockets
+        // Try reading the data from the socket and check for error.
+        let mut socketpair = interface::SockPair::default();
+        assert_eq!(
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            0
+        );
+        assert_eq!(
+            cage.pread_syscall(socketpair.sock2, buf.as_mut_ptr(), 4, 0),
+            -(Errno::ESPIPE as i32)
+        );
+
+        // Test for invalid epoll
+        // Try reading the data from the epoll and check for error.
+        let epfd = cage.epoll_create_syscall(1);
+        assert_eq!(
+            cage.pread_syscall(epfd, buf.as_mut_ptr(), 5, 0),
+            -(Errno::ESPIPE as i32)
+        );
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_mkdir_nonexisting_directory() {
+    pub fn ut_lind_fs_write_read_only_fd() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let path = "/parentdir/dir";
-        // Check for error when both parent and child directories don't exist
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), -(Errno::ENOENT as i32));
+
+        // Test to write to a file with read only permissions, and check if
+        // a valid error is returned when the file is used for writing.
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDONLY, S_IRWXA);
+        assert!(fd >= 0);
+
+        let write_data = "hello";
+        assert_eq!(
+            cage.write_syscall(fd, write_data.as_ptr(), write_data.len()),
+            -(Errno::EBADF as i32)
+        );
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_mkdir_existing_directory() {
+    pub fn ut_lind_fs_write_to_directory() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let path = "/parentdir";
-        // Create a parent directory
-        cage.mkdir_syscall(path, S_IRWXA);
-        // Check for error when the same directory is created again
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), -(Errno::EEXIST as i32));
+
+        // Create a directory and try to write to it.
+        // We should expect an error (EISDIR) as writing to a directory is not
+        // supported.
+        let path = "/test_dir";
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
+        let fd = cage.open_syscall(path, O_WRONLY, S_IRWXA);
+
+        let write_data = "hello";
+        assert_eq!(
+            cage.write_syscall(fd, write_data.as_ptr(), write_data.len()),
+            -(Errno::EISDIR as i32)
+        );
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_mkdir_invalid_modebits() {
+    pub fn ut_lind_fs_write_to_epoll() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let path = "/parentdir";
-        let invalid_mode = 0o77777; // Invalid mode bits
-                                    // Create a parent directory
-        cage.mkdir_syscall(path, S_IRWXA);
-        // Check for error when a directory is being created with invalid mode
+
+        // Create an Epoll and try to write to it.
+        // We should expect an error (EINVAL) as writing to an Epoll is not supported.
+        let epfd = cage.epoll_create_syscall(1);
+        assert!(epfd > 0);
+        let write_data = "hello";
         assert_eq!(
-            cage.mkdir_syscall("/parentdir/dir", invalid_mode),
-            -(Errno::EPERM as i32)
+            cage.write_syscall(epfd, write_data.as_ptr(), write_data.len()),
+            -(Errno::EINVAL as i32)
         );
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_mkdir_success() {
+    pub fn ut_lind_fs_write_to_regular_file() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let path = "/parentdir";
-        // Create a parent directory
-        cage.mkdir_syscall(path, S_IRWXA);
 
-        // Get the stat data for the parent directory and check for inode link count to
-        // be 3 initially
+        // This test mainly tests writing to a regular file.
+        // * Writing data to a file should start from position 0.
+        // * Once written, the position of the seek pointer in the file descriptor
+        // should increment by the count of bytes written. If write is performed again,
+        // then the position should continue from the point it was previously left.
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+
         let mut statdata = StatData::default();
-        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
-        assert_eq!(statdata.st_nlink, 3);
 
-        // Create a child directory inside parent directory with valid mode bits
-        assert_eq!(cage.mkdir_syscall("/parentdir/dir", S_IRWXA), 0);
+        // Write sample data to the file, and verify the number of bytes returned
+        let write_data1 = "hello";
+        assert_eq!(
+            cage.write_syscall(fd, write_data1.as_ptr(), write_data1.len()),
+            5
+        );
+
+        // Verify the size of the file
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 5);
+
+        // Write additional data to the file.
+        let write_data2 = " there!";
+        assert_eq!(
+            cage.write_syscall(fd, write_data2.as_ptr(), write_data2.len()),
+            7
+        );
+
+        // Verify the updated size of the file
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 12);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_write_to_chardev_file() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // This test mainly tests the case for writing to a character device type
+        // file. In this case, we are trying to write 100 bytes to the
+        // "/dev/null" file, which should succeed without doing anything.
+        let path = "/dev/null";
+        let fd = cage.open_syscall(path, O_RDWR, S_IRWXA);
+
+        // Verify if the returned count of bytes is 100.
+        let write_data = "0".repeat(100);
+        assert_eq!(cage.write_syscall(fd, write_data.as_ptr(), 100), 100);
+
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_write_to_sockets() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
 
-        // Get the stat data for the child directory and check for inode link count to
-        // be 3 initially
-        let mut statdata2 = StatData::default();
-        assert_eq!(cage.stat_syscall("/parentdir/dir", &mut statdata2), 0);
-        assert_eq!(statdata2.st_nlink, 3);
+        // This test mainly tests the case for writing data to a pair of Sockets.
+        // In this case, we create a socket pair of two sockets, and send data through
+        // one socket, and try to read it from the other one.
+        let mut socketpair = interface::SockPair::default();
 
-        // Get the stat data for the parent directory and check for inode link count to
-        // be 4 now as a new child directory has been created.
-        let mut statdata3 = StatData::default();
-        assert_eq!(cage.stat_syscall(path, &mut statdata3), 0);
-        assert_eq!(statdata3.st_nlink, 4);
+        // Verify if the socketpair is formed successfully.
+        assert_eq!(
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            0
+        );
+        // Verify if the number of bytes sent to socket1 is correct.
+        let write_data = "test";
+        assert_eq!(
+            cage.write_syscall(socketpair.sock1, write_data.as_ptr(), 4),
+            4
+        );
+
+        // Verify if the number of bytes received by socket2 is correct.
+        let mut buf2 = sizecbuf(4);
+        assert_eq!(cage.read_syscall(socketpair.sock2, buf2.as_mut_ptr(), 4), 4);
+        // Verify if the data received inside the buffer is correct.
+        assert_eq!(cbuf2str(&buf2), "test");
 
+        // Close the sockets
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_mkdir_using_symlink() {
+    pub fn ut_lind_fs_pwrite_read_only_fd() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Create a file which will be referred to as originalFile
-        let fd = cage.open_syscall("/originalFile", O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
-        assert_eq!(cage.write_syscall(fd, str2cbuf("hi"), 2), 2);
-
-        // Create a link between two files where the symlinkFile is originally not
-        // present But while linking, symlinkFile will get created
-        assert_eq!(cage.link_syscall("/originalFile", "/symlinkFile"), 0);
+        // Test to write to a file with read only permissions, and check if
+        // a valid error is returned when the file is used for writing.
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDONLY, S_IRWXA);
+        assert!(fd >= 0);
 
-        // Check for error while creating the symlinkFile again as it would already be
-        // created while linking the two files above.
+        let write_data = "hello";
         assert_eq!(
-            cage.mkdir_syscall("/symlinkFile", S_IRWXA),
-            -(Errno::EEXIST as i32)
+            cage.pwrite_syscall(fd, write_data.as_ptr(), write_data.len(), 0),
+            -(Errno::EBADF as i32)
         );
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_open_empty_directory() {
+    pub fn ut_lind_fs_pwrite_to_file() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let path = "";
-        // Check for error when directory is empty
-        assert_eq!(
-            cage.open_syscall(path, O_CREAT | O_TRUNC | O_RDWR, S_IRWXA),
-            -(Errno::ENOENT as i32)
-        );
+
+        // This test mainly tests two scenarios for writing to a file using
+        // `pwrite_syscall()`.
+        // * Writing to a file from the starting position offset(0).
+        // * Writing to a file from a random position offset, which should
+        // pad the file with additional "\0" bytes.
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+
+        // Write sample data to the file and verify the number of bytes returned.
+        let write_data1 = "hello";
+        assert_eq!(cage.pwrite_syscall(fd, write_data1.as_ptr(), 5, 0), 5);
+
+        // Write additional data to the file starting from the 6th position offset.
+        let write_data2 = "there!";
+        assert_eq!(cage.pwrite_syscall(fd, write_data2.as_ptr(), 6, 6), 6);
+
+        // Read back the data to verify, but since we are changing the offset to
+        // a larger number than the file size, it should pad the file with "\0" values.
+        // Verify if the file contains the paded bytes as well.
+        let mut read_buf = sizecbuf(12);
+        assert_eq!(cage.pread_syscall(fd, read_buf.as_mut_ptr(), 12, 0), 12);
+        assert_eq!(cbuf2str(&read_buf), "hello\0there!");
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_open_nonexisting_parentdirectory_and_file() {
+    pub fn ut_lind_fs_pwrite_to_directory() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let path = "/dir/file";
-        // Check for error when neither file nor parent exists and O_CREAT flag is not
-        // present
-        assert_eq!(
-            cage.open_syscall(path, F_GETFD, S_IRWXA),
-            -(Errno::ENOENT as i32)
-        );
 
-        // Check for error when neither file nor parent exists and O_CREAT flag is
-        // present
+        // Create a directory and try to write to it.
+        // We should expect an error (EISDIR) as writing to a directory is not
+        // supported.
+        let path = "/test_dir";
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
+        let fd = cage.open_syscall(path, O_WRONLY, S_IRWXA);
+
+        let write_data = "hello";
         assert_eq!(
-            cage.open_syscall(path, O_CREAT, S_IRWXA),
-            -(Errno::ENOENT as i32)
+            cage.pwrite_syscall(fd, write_data.as_ptr(), write_data.len(), 0),
+            -(Errno::EISDIR as i32)
         );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
@@ -3110,36 +5207,42 @@ pub mod fs_tests {
     }
 
     #[test]
-    pub fn ut_lind_fs_open_existing_parentdirectory_and_nonexisting_file() {
+    pub fn ut_lind_fs_pwrite_invalid_types() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        // Create a parent directory
-        assert_eq!(cage.mkdir_syscall("/dir", S_IRWXA), 0);
-        let path = "/dir/file";
 
-        // Check for error when parent directory exists but file doesn't exist and
-        // O_CREAT is not present
+        // Test for invalid pipe
+        // Try writing the data to the pipe and check for error.
+        let mut pipe_fds = PipeArray::default();
+        assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
+        let write_fd = pipe_fds.writefd;
+        let write_data = "hello";
         assert_eq!(
-            cage.open_syscall(path, O_TRUNC, S_IRWXA),
-            -(Errno::ENOENT as i32)
+            cage.pwrite_syscall(write_fd, write_data.as_ptr(), write_data.len(), 0),
+            -(Errno::ESPIPE as i32)
         );
 
-        // Check for error when parent directory exists but file doesn't exist and
-        // Filetype Flags contain S_IFCHR flag
+        // Test for invalid sockets
+        // Try writing the data to the socket and check for error.
+        let mut socketpair = interface::SockPair::default();
         assert_eq!(
-            cage.open_syscall(path, S_IFCHR | O_CREAT, S_IRWXA),
-            -(Errno::EINVAL as i32)
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            0
+        );
+        assert_eq!(
+            cage.pwrite_syscall(socketpair.sock2, write_data.as_ptr(), 4, 0),
+            -(Errno::ESPIPE as i32)
         );
 
-        // Check for error when parent directory exists but file doesn't exist and mode
-        // bits are invalid
-        let invalid_mode = 0o77777;
+        // Test for invalid epoll
+        // Try writing the data to the epoll and check for error.
+        let epfd = cage.epoll_create_syscall(1);
         assert_eq!(
-            cage.open_syscall(path, O_CREAT, invalid_mode),
-            -(Errno::EPERM as i32)
+            cage.pwrite_syscall(epfd, write_data.as_ptr(), 5, 0),
+            -(Errno::ESPIPE as i32)
         );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
@@ -3147,145 +5250,130 @@ pub mod fs_tests {
     }
 
     #[test]
-    pub fn ut_lind_fs_open_existing_file_without_flags() {
+    pub fn ut_lind_fs_pwrite_to_chardev_file() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
-        // This test is used for validating two scenarios:
-        // 1. When the non-existing file is opened using O_CREAT flag, it should open
-        //    successfully.
-        // 2. When the same existing file is being opened without O_CREAT flag, it
-        //    should open successfully.
         let cage = interface::cagetable_getref(1);
 
-        // Open a non-existing file with O_CREAT flag
-        // This should create a new file with a valid file descriptor
-        let path = "/test";
-        let fd = cage.open_syscall(path, O_CREAT | O_RDWR, S_IRWXA);
-        assert!(fd > 0);
+        // This test mainly tests the case for writing to a character device type
+        // file. In this case, we are trying to write 100 bytes to the
+        // "/dev/null" file, which should succeed without doing anything.
+        let path = "/dev/null";
+        let fd = cage.open_syscall(path, O_RDWR, S_IRWXA);
 
-        // Open the existing file without O_CREAT and O_EXCL
-        // The file should open successfully as the two flags are not set while
-        // re-opening the file
-        let fd2 = cage.open_syscall(path, O_RDONLY, 0);
-        assert!(fd2 > 0);
+        // Verify if the returned count of bytes is 100.
+        let write_data = "0".repeat(100);
+        assert_eq!(cage.pwrite_syscall(fd, write_data.as_ptr(), 100, 0), 100);
 
+        assert_eq!(cage.close_syscall(fd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
-    #[test]
-    pub fn ut_lind_fs_open_existing_file_with_flags() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
-        // and also performs clean env setup
+    pub fn ut_lind_fs_shmget_syscall() {
+        // acquire locks and start env cleanup
         let _thelock = setup::lock_and_init();
-
-        // This test is used for validating two scenarios:
-        // 1. When the non-existing file is opened using O_CREAT flag, it should open
-        //    successfully.
-        // 2. When the same existing file is opened using O_CREAT and O_EXCL flags, it
-        //    should return an error for file already existing.
         let cage = interface::cagetable_getref(1);
 
-        // Open a non-existing file with O_CREAT flag
-        // This should create a new file with a valid file descriptor
-        let path = "/test";
-        let fd = cage.open_syscall(path, O_CREAT | O_RDWR, S_IRWXA);
-        assert!(fd > 0);
+        let key = 33123;
+        // Get shmid of a memory segment / create a new one if it doesn't exist
+        let shmid = cage.shmget_syscall(33123, 1024, IPC_CREAT);
+        assert_eq!(shmid, 4);
 
-        // Open the existing file with O_CREAT and O_EXCL flags
-        // The file should not open successfully as the two flags are set while
-        // re-opening the file It should return an error for "File already
-        // exists"
+        // Check error upon asking for a valid key and passing the IPC_CREAT and
+        // IPC_EXCL flag
         assert_eq!(
-            cage.open_syscall(path, O_CREAT | O_EXCL | O_RDONLY, S_IRWXA),
+            cage.shmget_syscall(key, 1024, IPC_CREAT | IPC_EXCL),
             -(Errno::EEXIST as i32)
         );
 
-        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        // Check error when passing IPC_CREAT flag as the key
+        assert_eq!(
+            cage.shmget_syscall(IPC_PRIVATE, 1024, IPC_PRIVATE),
+            -(Errno::ENOENT as i32)
+        );
+
+        // Check if the function returns a correct shmid upon asking with a key that we
+        // know exists
+        assert_eq!(cage.shmget_syscall(key, 1024, 0666), shmid);
+
+        // Check if the function returns the correct error when we don't pass IPC_CREAT
+        // for a key that doesn't exist
+        assert_eq!(
+            cage.shmget_syscall(123456, 1024, 0),
+            -(Errno::ENOENT as i32)
+        );
+
+        // Check if the size error is returned correctly
+        assert_eq!(
+            cage.shmget_syscall(123456, (SHMMAX + 10) as usize, IPC_CREAT),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.shmget_syscall(123456, 0 as usize, IPC_CREAT),
+            -(Errno::EINVAL as i32)
+        );
+
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_open_create_new_file_and_check_link_count() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
-        // and also performs clean env setup
+    pub fn ut_lind_fs_lseek_on_file() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Create a new file
-        let path = "/newfile.txt";
-        let fd = cage.open_syscall(path, O_CREAT | O_RDWR, S_IRWXA);
-        assert!(fd > 0);
-
-        // Write a string to the newly opened file of size 12
-        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
-
-        // Get the stat data for the file and check for file attributes
-        let mut statdata = StatData::default();
-        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
-
-        // Validate the link count for the new file to be 1
-        assert_eq!(statdata.st_nlink, 1);
+        // Test to create a file and check if seeking to a new location is possible.
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_WRONLY, S_IRWXA);
+        assert!(fd >= 0);
 
-        // Validate the size of the file to be 12
-        assert_eq!(statdata.st_size, 12);
+        // Attempt to seek within the file and check if it succeeds
+        assert_eq!(cage.lseek_syscall(fd, 10, SEEK_SET), 10);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_open_existing_file_with_o_trunc_flag() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_lseek_on_directory() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Create a new file
-        let path = "/file.txt";
-        let fd = cage.open_syscall(path, O_CREAT | O_WRONLY, S_IRWXA);
-        assert!(fd > 0);
-        // Write a string to the newly opened file of size 12
-        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
-        // Get the stat data for the file and check for file attributes
-        let mut statdata = StatData::default();
-        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
-        // Validate the size of the file to be 12
-        assert_eq!(statdata.st_size, 12);
+        // Create a directory and try to seek within it.
+        let path = "/test_dir";
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
+        let fd = cage.open_syscall(path, O_RDONLY, S_IRWXA);
+        assert!(fd >= 0);
 
-        // Open the same file with O_TRUNC flag
-        // Since the file is truncated, the size of the file should be truncated to 0.
-        let fd2 = cage.open_syscall(path, O_WRONLY | O_TRUNC, S_IRWXA);
-        assert!(fd2 > 0);
-        // Get the stat data for the same file and check for file attributes
-        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
-        // Validate the size of the file to be 0 as the file is truncated now
-        assert_eq!(statdata.st_size, 0);
+        // Attempt to seek within the directory and check if it succeeds
+        assert_eq!(cage.lseek_syscall(fd, 1, SEEK_SET), 1);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_open_new_file_with_s_ifchar_flag() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_lseek_invalid_whence() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Create a parent directory
-        assert_eq!(cage.mkdir_syscall("/testdir", S_IRWXA), 0);
-        let path = "/testdir/file";
+        // Test to create a file and check for invalid `whence` value
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
 
-        // Attempt to open a file with S_IFCHR flag, which should be invalid for regular
-        // files
+        // Attempt to seek with an invalid `whence` value and check if it returns an
+        // error
         assert_eq!(
-            cage.open_syscall(path, O_CREAT | S_IFCHR, S_IRWXA),
+            cage.lseek_syscall(fd, 10, 999), // Invalid whence value
             -(Errno::EINVAL as i32)
         );
 
@@ -3294,81 +5382,79 @@ pub mod fs_tests {
     }
 
     #[test]
-    pub fn ut_lind_fs_creat_new_file() {
-        // Since this call is almost similar to open_syscall, and we have
-        // covered all the possible test scenarios for open_syscall above. So,
-        // just testing the basic working flow for the creat_sycall.
-
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_lseek_beyond_file_size() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Create a file and validate the size of it.
-        let path = "/creatFile";
-        let fd = cage.creat_syscall(path, S_IRWXA);
-        assert!(fd > 0);
+        // Test to create a file and seek beyond its size
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
 
-        let mut statdata = StatData::default();
+        // Write sample data to the file.
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello"), 5), 5);
 
-        // The size of the file should be 0
-        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
-        assert_eq!(statdata.st_size, 0);
+        // Seek beyond the end of the file and verify if it succeeds
+        assert_eq!(
+            cage.lseek_syscall(fd, 10, SEEK_END),
+            15 // 5 (file size) + 10 (offset)
+        );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_creat_truncate_existing_file() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_lseek_before_start_of_file() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        let path = "/creatFile";
-        // Create a new file
-        let fd = cage.creat_syscall(path, S_IRWXA);
-
-        // Write a string to the newly opened file of size 12
-        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
-
-        // Get the stat data for the file and check for file attributes
-        let mut statdata = StatData::default();
-        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
-
-        // Validate the size of the file to be 12
-        assert_eq!(statdata.st_size, 12);
+        // Test to create a file and attempt to seek before the start of the file
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
 
-        // Call the function on the existing file, which should truncate
-        // the file size to 0.
-        let _fd2 = cage.creat_syscall(path, S_IRWXA);
-        assert_eq!(cage.stat_syscall(path, &mut statdata), 0);
+        // Attempt to seek to a negative offset and check if it returns an error
+        // using "SEEK_SET" whence, where we are explicitly setting the file
+        // offset to -10 value.
+        assert_eq!(
+            cage.lseek_syscall(fd, -10, SEEK_SET),
+            -(Errno::EINVAL as i32)
+        );
 
-        // Validate the size of the file to be 0 now as should be truncated
-        assert_eq!(statdata.st_size, 0);
+        // Attempt to seek to a negative offset and check if it returns an error
+        // using "SEEK_CUR" whence, where current position of the file is 0,
+        // as it's empty initially, and we are adding -10 to the offset.
+        assert_eq!(
+            cage.lseek_syscall(fd, -10, SEEK_CUR),
+            -(Errno::EINVAL as i32)
+        );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_read_write_only_fd() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_lseek_on_pipe() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Test to create a file with write only permissions, and check if
-        // a valid error is returned when the file is used for reading.
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_WRONLY, S_IRWXA);
-        let mut read_buf = sizecbuf(5);
+        // Create a pipe and attempt to seek within it
+        let mut pipe_fds = PipeArray::default();
+        assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
+        let read_fd = pipe_fds.readfd;
+
+        // Attempt to seek within the pipe and check if it returns an error
         assert_eq!(
-            cage.read_syscall(fd, read_buf.as_mut_ptr(), 5),
-            -(Errno::EBADF as i32)
+            cage.lseek_syscall(read_fd, 10, SEEK_SET),
+            -(Errno::ESPIPE as i32)
         );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
@@ -3376,46 +5462,40 @@ pub mod fs_tests {
     }
 
     #[test]
-    pub fn ut_lind_fs_read_from_directory() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_lseek_on_chardev() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Create a directory and try to read from it.
-        // We should expect an error (EISDIR) as reading from a directory is not
-        // supported.
-        let path = "/test_dir";
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
-        let fd = cage.open_syscall(path, O_RDONLY, S_IRWXA);
+        // Attempt to seek within a character device file
+        let path = "/dev/null";
+        let fd = cage.open_syscall(path, O_RDWR, S_IRWXA);
 
-        let mut read_buf = sizecbuf(5);
-        assert_eq!(
-            cage.read_syscall(fd, read_buf.as_mut_ptr(), 5),
-            -(Errno::EISDIR as i32)
-        );
+        // Seek within the character device and check if it returns 0 (no operation)
+        assert_eq!(cage.lseek_syscall(fd, 10, SEEK_SET), 0);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_read_from_epoll() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_lseek_on_epoll() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Create an Epoll and try to read from it.
-        // We should expect an error (EINVAL) as reading from an Epoll is not supported.
+        // Create an Epoll and try to seek from it.
         let epfd = cage.epoll_create_syscall(1);
         assert!(epfd > 0);
-        let mut read_buf = sizecbuf(5);
+
+        // Attempt to seek from the epoll and check if it returns an error
         assert_eq!(
-            cage.read_syscall(epfd, read_buf.as_mut_ptr(), 5),
-            -(Errno::EINVAL as i32)
+            cage.lseek_syscall(epfd, 10, SEEK_SET),
+            -(Errno::ESPIPE as i32)
         );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
@@ -3423,362 +5503,597 @@ pub mod fs_tests {
     }
 
     #[test]
-    pub fn ut_lind_fs_read_from_regular_file() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_close_regular_file() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // This test mainly tests two scenarios for reading from a regular file:
-        // * Reading from a file should initially start from 0 position.
-        // * Once read, the position of the seek pointer in the file descriptor should
-        // increment by the count of bytes read. If the read is performed again, then
-        // the position should continue from the point it was previously left.
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        // Create and open a regular file, then close it.
+        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDWR, S_IRWXA);
         assert!(fd >= 0);
 
         // Write sample data to the file.
-        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
-
-        // Set the initial position to 0 in the file descriptor.
-        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello"), 5), 5);
 
-        // Read first 5 bytes from the file, and assert the result.
-        let mut read_buf1 = sizecbuf(5);
-        assert_eq!(cage.read_syscall(fd, read_buf1.as_mut_ptr(), 5), 5);
-        assert_eq!(cbuf2str(&read_buf1), "hello");
+        // Close the file descriptor, which should succeed.
+        assert_eq!(cage.close_syscall(fd), 0);
 
-        // Read next 7 bytes which should start from the previous position.
-        let mut read_buf2 = sizecbuf(7);
-        assert_eq!(cage.read_syscall(fd, read_buf2.as_mut_ptr(), 7), 7);
-        assert_eq!(cbuf2str(&read_buf2), " there!");
+        // Attempt to close the file descriptor again to ensure it's already closed.
+        // Expect an error for "Invalid File Descriptor".
+        assert_eq!(cage.close_syscall(fd), -(Errno::EBADF as i32));
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_read_from_chardev_file() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_close_directory() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // This test mainly tests the case for reading from a character device type
-        // file. In this case, we are trying to read 100 bytes from the
-        // "/dev/zero" file, which should return 100 bytes of "0" filled
-        // characters.
-        let path = "/dev/zero";
-        let fd = cage.open_syscall(path, O_RDWR, S_IRWXA);
+        // Create a directory and open it.
+        let path = "/test_dir";
+        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
+        let fd = cage.open_syscall(path, O_RDONLY, S_IRWXA);
+        assert!(fd >= 0);
 
-        // Verify if the returned count of bytes is 100.
-        let mut read_bufzero = sizecbuf(100);
-        assert_eq!(cage.read_syscall(fd, read_bufzero.as_mut_ptr(), 100), 100);
-        // Verify if the characters present in the buffer are all "0".
-        assert_eq!(
-            cbuf2str(&read_bufzero),
-            std::iter::repeat("\0")
-                .take(100)
-                .collect::<String>()
-                .as_str()
-        );
+        // Close the directory file descriptor, which should succeed.
         assert_eq!(cage.close_syscall(fd), 0);
+
+        // Attempt to close the file descriptor again to ensure it's already closed.
+        // Expect an error for "Invalid File Descriptor".
+        assert_eq!(cage.close_syscall(fd), -(Errno::EBADF as i32));
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_read_from_sockets() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_close_socket() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // This test mainly tests the case for reading data from a pair of Sockets.
-        // In this case, we create a socket pair of two sockets, and send data through
-        // one socket, and try to read it from the other one using `read_syscall()`.
+        // Create a socket pair.
         let mut socketpair = interface::SockPair::default();
-
-        // Verify if the socketpair is formed successfully.
         assert_eq!(
             Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
             0
         );
-        // Verify if the number of bytes sent to socket1 is correct.
-        assert_eq!(
-            cage.send_syscall(socketpair.sock1, str2cbuf("test"), 4, 0),
-            4
-        );
-        // Verify if the number of bytes received by socket2 is correct.
-        let mut buf2 = sizecbuf(4);
-        assert_eq!(cage.read_syscall(socketpair.sock2, buf2.as_mut_ptr(), 4), 4);
-        // Verify if the data received inside the buffer is correct.
-        assert_eq!(cbuf2str(&buf2), "test");
-        // Close the sockets
+
+        // Close both the socket file descriptors, which should succeed.
         assert_eq!(cage.close_syscall(socketpair.sock1), 0);
         assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+
+        // Attempt to close the file descriptors again to ensure they are already
+        // closed. Expect an error for "Invalid File Descriptor".
+        assert_eq!(cage.close_syscall(socketpair.sock1), -(Errno::EBADF as i32));
+        assert_eq!(cage.close_syscall(socketpair.sock2), -(Errno::EBADF as i32));
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_read_from_pipe_blocking_mode() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_close_pipe() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // This test mainly tests the case of reading data from the pipe.
-        // We create two pipes, i.e., Read and Write and validate if the data
-        // received is correct or not.
-
-        // Create a pipe of read and write file descriptors.
+        // Create a pipe.
         let mut pipe_fds = PipeArray::default();
         assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
         let read_fd = pipe_fds.readfd;
         let write_fd = pipe_fds.writefd;
 
-        let write_data = "Testing";
-        let mut buf = sizecbuf(7);
-
         // Write data to the pipe
+        let write_data = "Testing";
         assert_eq!(
             cage.write_syscall(write_fd, write_data.as_ptr(), write_data.len()),
             write_data.len() as i32
         );
 
-        // Read the data from the pipe and verify its count.
+        // Read the data from the pipe.
+        let mut buf = sizecbuf(7);
         assert_eq!(
             cage.read_syscall(read_fd, buf.as_mut_ptr(), buf.len()),
             write_data.len() as i32
         );
-        // Verify if the data returned in the pipe buffer is correct.
         assert_eq!(cbuf2str(&buf), write_data);
 
-        // Close the file descriptors
+        // Close the pipe file descriptors, which should succeed.
         assert_eq!(cage.close_syscall(read_fd), 0);
         assert_eq!(cage.close_syscall(write_fd), 0);
+
+        // Attempt to close the file descriptor again to ensure they are already closed.
+        // Expect an error for "Invalid File Descriptor".
+        assert_eq!(cage.close_syscall(read_fd), -(Errno::EBADF as i32));
+        assert_eq!(cage.close_syscall(write_fd), -(Errno::EBADF as i32));
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_read_from_pipe_nonblocking_mode() {
+    pub fn ut_lind_fs_close_chardev() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // Open a character device file.
+        let fd = cage.open_syscall("/dev/zero", O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+
+        // Close the character device file descriptor, which should succeed.
+        assert_eq!(cage.close_syscall(fd), 0);
+
+        // Attempt to close the file descriptor again to ensure it's already closed.
+        // Expect an error for "Invalid File Descriptor".
+        assert_eq!(cage.close_syscall(fd), -(Errno::EBADF as i32));
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_fs_stat_syscall_tests() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let mut statdata = StatData::default();
+
+        // test out whether an error is output for a non existent file path
+        // (ENOENT[-2])
+        assert_eq!(
+            cage.stat_syscall("non_existent_file_path", &mut statdata),
+            syscall_error(Errno::ENOENT, "stat", "test_failure")
+        );
+
+        // setting up directory inode object '/tmp' for testing stat_syscall with a
+        // directory
+        let dir_path = "/tmp"; // since setup already initializes tmp, assuming it is there
+        assert_eq!(cage.stat_syscall(dir_path, &mut statdata), 0);
+
+        // setting up generic inode object "/tmp/generic" for testing stat_syscall with
+        // a generic file
+        let generic_path = "/tmp/generic";
+        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
+        assert!(creat_fd > 0);
+        assert_eq!(cage.stat_syscall(generic_path, &mut statdata), 0);
+
+        // setting up character device inode object "/chardev" for testing stat_syscall
+        // with a character device
+        let dev = makedev(&DevNo { major: 1, minor: 3 });
+        let chardev_path = "/chardev";
+        assert_eq!(
+            cage.mknod_syscall(chardev_path, S_IRWXA | S_IFCHR as u32, dev),
+            0
+        );
+        assert_eq!(cage.stat_syscall(chardev_path, &mut statdata), 0);
+
+        // setting up socket inode object with path "/socket.sock"  for testing
+        // stat_syscall with a socket
+        let socketfile_path = "/socket.sock";
+        let socketfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+        assert!(socketfd > 0);
+        let sockaddr = interface::new_sockaddr_unix(AF_UNIX as u16, socketfile_path.as_bytes());
+        let socket = interface::GenSockaddr::Unix(sockaddr);
+        assert_eq!(cage.bind_syscall(socketfd, &socket), 0);
+
+        // stat_syscall test here
+        assert_eq!(cage.stat_syscall(socketfile_path, &mut statdata), 0);
+
+        // socket teardown
+        assert_eq!(cage.close_syscall(socketfd), 0);
+        cage.unlink_syscall(socketfile_path);
+
+        lindrustfinalize();
+        return;
+    }
+
+    #[test]
+    pub fn ut_lind_fs_fstat_syscall_tests() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // This test mainly tests the case of reading data from the pipe, but in
-        // non-blocking mode. We create two pipes, i.e., Read and Write and
-        // validate if the data received is correct or not.
+        let mut statdata = StatData::default();
 
-        // Create a pipe of read and write file descriptors.
-        let mut pipe_fds = PipeArray::default();
-        assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
-        let read_fd = pipe_fds.readfd;
-        let write_fd = pipe_fds.writefd;
+        // test out whether an error is output for a non existent fd (1000)
+        // (EBADF[-9])
+        let non_existent_fd = 1000;
+        assert_eq!(
+            cage.fstat_syscall(non_existent_fd, &mut statdata),
+            syscall_error(Errno::EBADF, "fstat", "test_failure")
+        );
 
-        let write_data = "Testing";
-        let mut buf = sizecbuf(7);
+        // setting up directory inode object '/tmp' for testing fstat_syscall with a
+        // directory
+        let dir_path = "/tmp"; // since setup already initializes tmp, assuming it is there
+        let dir_fd = cage.open_syscall(dir_path, O_RDONLY | O_DIRECTORY, S_IRWXA);
+        assert!(dir_fd > 0);
+        assert_eq!(cage.fstat_syscall(dir_fd, &mut statdata), 0);
+        assert_eq!(cage.close_syscall(dir_fd), 0);
 
-        // Set pipe to non-blocking mode
-        assert_eq!(cage.fcntl_syscall(read_fd, F_SETFL, O_NONBLOCK), 0);
+        // setting up generic inode object "/tmp/generic" for testing fstat_syscall with
+        // a generic file
+        let generic_path = "/tmp/generic";
+        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
+        assert!(creat_fd > 0);
+        assert_eq!(cage.fstat_syscall(creat_fd, &mut statdata), 0);
 
-        // Read from the pipe (should return EAGAIN as there's no data yet)
+        // setting up character device inode object "/chardev" for testing fstat_syscall
+        // with a character device
+        let dev = makedev(&DevNo { major: 1, minor: 3 });
+        let chardev_path = "/chardev";
         assert_eq!(
-            cage.read_syscall(read_fd, buf.as_mut_ptr(), buf.len()),
-            -(Errno::EAGAIN as i32)
+            cage.mknod_syscall(chardev_path, S_IRWXA | S_IFCHR as u32, dev),
+            0
         );
+        let chardev_fd = cage.open_syscall(chardev_path, O_RDONLY, S_IRWXA);
+        assert!(chardev_fd > 0);
+        assert_eq!(cage.fstat_syscall(chardev_fd, &mut statdata), 0);
+        assert_eq!(cage.close_syscall(chardev_fd), 0);
 
-        // Write data to the pipe
+        // setting up socket inode object with path "/socket.sock" for testing
+        // fstat_syscall with a socket
+        let socketfile_path = "/socket.sock";
+
+        let socketfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+        assert!(socketfd > 0);
+
+        let sockaddr = interface::new_sockaddr_unix(AF_UNIX as u16, socketfile_path.as_bytes());
+        let socket = interface::GenSockaddr::Unix(sockaddr);
+        assert_eq!(cage.bind_syscall(socketfd, &socket), 0);
+
+        // Errno::EOPNOTSUPP : -95
         assert_eq!(
-            cage.write_syscall(write_fd, write_data.as_ptr(), write_data.len()),
-            write_data.len() as i32
+            cage.fstat_syscall(socketfd, &mut statdata),
+            syscall_error(Errno::EOPNOTSUPP, "fstat", "test_failure")
         );
 
-        // Read the data from the pipe and verify its count.
+        // Clean up
+        assert_eq!(cage.close_syscall(socketfd), 0);
+
+        cage.unlink_syscall(socketfile_path);
+
+        lindrustfinalize();
+        return;
+    }
+
+    #[test]
+    pub fn ut_lind_fs_statfs_syscall_tests() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let mut fsdata = FSData::default();
+
+        // test out whether an error is output for a non existent file path
+        // (ENOENT[-2])
         assert_eq!(
-            cage.read_syscall(read_fd, buf.as_mut_ptr(), buf.len()),
-            write_data.len() as i32
+            cage.statfs_syscall("non_existent_file_path", &mut fsdata),
+            syscall_error(Errno::ENOENT, "statfs", "test_failure")
         );
-        // Verify if the data returned in the pipe buffer is correct.
-        assert_eq!(cbuf2str(&buf), write_data);
 
-        // Close the file descriptors
-        assert_eq!(cage.close_syscall(read_fd), 0);
-        assert_eq!(cage.close_syscall(write_fd), 0);
-        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        // setting up inode object "/tmp/generic" for testing statfs_syscall
+        let generic_path = "/tmp/generic";
+        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
+        assert!(creat_fd > 0);
+        assert_eq!(cage.statfs_syscall(generic_path, &mut fsdata), 0);
+
         lindrustfinalize();
+        return;
     }
 
     #[test]
-    pub fn ut_lind_fs_pread_write_only_fd() {
+    pub fn ut_lind_fs_fstatfs_syscall_tests() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Test to create a file with write only permissions, and check if
-        // a valid error is returned when the file is used for reading.
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_WRONLY, S_IRWXA);
-        let mut read_buf = sizecbuf(5);
+        let mut fsdata = FSData::default();
+
+        // test out whether an error is output for a non existent fd (1000)
+        // (ENOENT[-2])
+        let non_existent_fd = 1000;
         assert_eq!(
-            cage.pread_syscall(fd, read_buf.as_mut_ptr(), 5, 0),
-            -(Errno::EBADF as i32)
+            cage.fstatfs_syscall(non_existent_fd, &mut fsdata),
+            syscall_error(Errno::EBADF, "fstatfs", "test_failure")
+        );
+
+        // setting up generic inode object "/tmp/generic" for testing fstat_syscall with
+        // a generic file
+        let generic_path = "/tmp/generic";
+        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
+        assert!(creat_fd > 0);
+        assert_eq!(cage.fstatfs_syscall(creat_fd, &mut fsdata), 0);
+
+        // setting up socket inode object with path "/socket.sock" for testing
+        // fstat_syscall with a socket
+        let socketfile_path = "/socket.sock";
+
+        let socketfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+        assert!(socketfd > 0);
+
+        let sockaddr = interface::new_sockaddr_unix(AF_UNIX as u16, socketfile_path.as_bytes());
+        let socket = interface::GenSockaddr::Unix(sockaddr);
+        assert_eq!(cage.bind_syscall(socketfd, &socket), 0);
+
+        // Errno::EBADF : -9
+        assert_eq!(
+            cage.fstatfs_syscall(socketfd, &mut fsdata),
+            syscall_error(Errno::EBADF, "fstatfs", "test_failure")
         );
 
-        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        // Clean up
+        assert_eq!(cage.close_syscall(socketfd), 0);
+
+        cage.unlink_syscall(socketfile_path);
+
         lindrustfinalize();
+        return;
     }
 
     #[test]
-    pub fn ut_lind_fs_pread_from_file() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_access_syscall_tests() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // This test mainly tests two scenarios for reading from a file using
-        // `pread_syscall()`.
-        // * Reading from a file from the starting position offset(0).
-        // * Reading from a file from a random position offset.
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
-        assert!(fd >= 0);
-
-        // Write sample data to the file.
-        assert_eq!(cage.write_syscall(fd, str2cbuf("hello there!"), 12), 12);
-
-        // Set the initial position to 0 in the file descriptor.
-        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
+        // test out whether an error is output for a non existent file path
+        // (ENOENT[-2])
+        assert_eq!(
+            cage.access_syscall("non_existent_file_path", S_IRWXA),
+            syscall_error(Errno::ENOENT, "access", "test_failure")
+        );
 
-        // Read first 5 bytes from the file, and assert the result.
-        let mut read_buf1 = sizecbuf(5);
-        assert_eq!(cage.pread_syscall(fd, read_buf1.as_mut_ptr(), 5, 0), 5);
-        assert_eq!(cbuf2str(&read_buf1), "hello");
+        // setting up inode object "/tmp/generic" for testing statfs_syscall
+        let generic_path = "/tmp/generic";
+        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
+        assert!(creat_fd > 0);
+        // set mode to some value
+        assert_eq!(cage.access_syscall(generic_path, S_IRWXA), 0);
 
-        // Read 5 bytes, but from the 6th position offset of the file.
-        let mut read_buf2 = sizecbuf(5);
-        assert_eq!(cage.pread_syscall(fd, read_buf2.as_mut_ptr(), 5, 6), 5);
-        assert_eq!(cbuf2str(&read_buf2), "there");
+        // change mode
+        assert_eq!(cage.chmod_syscall(generic_path, S_IRGRP), 0);
+        assert_eq!(
+            cage.access_syscall(generic_path, S_IRWXA),
+            syscall_error(Errno::EACCES, "access", "incorrect access")
+        );
 
-        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
+        return;
     }
 
     #[test]
-    pub fn ut_lind_fs_pread_from_directory() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_rename_syscall_tests() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let mut buf = sizecbuf(5);
 
-        // Test for invalid directory should fail
-        let path = "/test_dir";
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
-        let fd = cage.open_syscall(path, O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
-        assert!(fd >= 0);
+        // test out whether an error is output for a non existent file path
+        // (ENOENT[-2])
         assert_eq!(
-            cage.pread_syscall(fd, buf.as_mut_ptr(), buf.len(), 0),
-            -(Errno::EISDIR as i32)
+            cage.rename_syscall("non_existent_file_path", "non-existent-target"),
+            syscall_error(Errno::ENOENT, "rename", "test_failure")
         );
-        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        assert_eq!(
+            cage.rename_syscall("/non_existent_dir/file", "/non-existent-target"),
+            -(Errno::ENOENT as i32)
+        );
+
+        // empty inputs for rename
+        assert_eq!(
+            cage.rename_syscall("", ""),
+            syscall_error(Errno::ENOENT, "rename", "empty inputs")
+        );
+
+        // root path provided
+        assert_eq!(
+            cage.rename_syscall("/", "rename-to-this"),
+            syscall_error(Errno::EBUSY, "rename", "cant deal with root")
+        );
+
+        // setting up inode object "/tmp/generic" for testing statfs_syscall
+        let generic_path = "/tmp/generic";
+        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
+        assert!(creat_fd > 0);
+
+        // try to move a directory beneath itself
+        assert_eq!(
+            cage.rename_syscall("/tmp", generic_path),
+            syscall_error(Errno::EINVAL, "rename", "cant move into own subtree")
+        );
+
+        // normal rename
+        assert_eq!(cage.rename_syscall("/tmp/generic", "/tmp/generic1"), 0);
+
+        // clean up
+        cage.close_syscall(creat_fd);
+
         lindrustfinalize();
+        return;
     }
 
     #[test]
-    pub fn ut_lind_fs_pread_invalid_types() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_rename_across_directories() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
-        let mut buf = sizecbuf(5);
 
-        // Test for invalid pipe
-        // Try reading the data from the pipe and check for error.
-        let mut pipe_fds = PipeArray::default();
-        assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
-        let read_fd = pipe_fds.readfd;
-        assert_eq!(
-            cage.pread_syscall(read_fd, buf.as_mut_ptr(), buf.len(), 0),
-            -(Errno::ESPIPE as i32)
-        );
+        assert_eq!(cage.mkdir_syscall("/renamesrc", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall("/renamedst", S_IRWXA), 0);
+        let fd = cage.open_syscall("/renamesrc/file", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello"), 5), 5);
+        assert_eq!(cage.close_syscall(fd), 0);
 
-        // Test for invalid sockets
-        // Try reading the data from the socket and check for error.
-        let mut socketpair = interface::SockPair::default();
+        // move the file to another parent directory
         assert_eq!(
-            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            cage.rename_syscall("/renamesrc/file", "/renamedst/file2"),
             0
         );
+        let mut statdata = StatData::default();
         assert_eq!(
-            cage.pread_syscall(socketpair.sock2, buf.as_mut_ptr(), 4, 0),
-            -(Errno::ESPIPE as i32)
+            cage.stat_syscall("/renamesrc/file", &mut statdata),
+            -(Errno::ENOENT as i32)
         );
+        assert_eq!(cage.stat_syscall("/renamedst/file2", &mut statdata), 0);
+        assert_eq!(statdata.st_size, 5);
+        assert_eq!(statdata.st_nlink, 1);
 
-        // Test for invalid epoll
-        // Try reading the data from the epoll and check for error.
-        let epfd = cage.epoll_create_syscall(1);
+        // the parents' linkcounts should reflect the moved entry
+        assert_eq!(cage.stat_syscall("/renamesrc", &mut statdata), 0);
+        assert_eq!(statdata.st_nlink, 3);
+        assert_eq!(cage.stat_syscall("/renamedst", &mut statdata), 0);
+        assert_eq!(statdata.st_nlink, 4);
+
+        // move a directory to another parent, its .. entry should follow it
+        assert_eq!(cage.mkdir_syscall("/renamesrc/subdir", S_IRWXA), 0);
         assert_eq!(
-            cage.pread_syscall(epfd, buf.as_mut_ptr(), 5, 0),
-            -(Errno::ESPIPE as i32)
+            cage.rename_syscall("/renamesrc/subdir", "/renamedst/subdir"),
+            0
         );
+        assert_eq!(cage.chdir_syscall("/renamedst/subdir"), 0);
+        let mut parentstat = StatData::default();
+        assert_eq!(cage.stat_syscall("..", &mut statdata), 0);
+        assert_eq!(cage.stat_syscall("/renamedst", &mut parentstat), 0);
+        assert_eq!(statdata.st_ino, parentstat.st_ino);
+        assert_eq!(cage.chdir_syscall("/"), 0);
+
+        // the source directory is now empty and can be removed
+        assert_eq!(cage.rmdir_syscall("/renamesrc"), 0);
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_write_read_only_fd() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_rename_beneath_symlink() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Test to write to a file with read only permissions, and check if
-        // a valid error is returned when the file is used for writing.
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDONLY, S_IRWXA);
-        assert!(fd >= 0);
-
-        let write_data = "hello";
+        // a link outside the directory gives its children paths that don't start
+        // with the path of the directory
+        assert_eq!(cage.mkdir_syscall("/symdir", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall("/symdir/sub", S_IRWXA), 0);
+        assert_eq!(cage.symlink_syscall("/symdir", "/symlnk"), 0);
         assert_eq!(
-            cage.write_syscall(fd, write_data.as_ptr(), write_data.len()),
-            -(Errno::EBADF as i32)
+            cage.rename_syscall("/symdir", "/symlnk/moved"),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.rename_syscall("/symdir", "/symlnk/sub/moved"),
+            -(Errno::EINVAL as i32)
         );
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/symdir/sub", &mut statdata), 0);
+
+        // a socket bound through the link follows a rename of the directory
+        let sockfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+        let sockaddr = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "/symlnk/sub/sock".as_bytes(),
+        ));
+        assert_eq!(cage.bind_syscall(sockfd, &sockaddr), 0);
+        assert_eq!(cage.rename_syscall("/symdir", "/symdir2"), 0);
+        let sockpaths = crate::safeposix::net::NET_METADATA.get_domainsock_paths();
+        assert!(sockpaths.contains(&interface::RustPathBuf::from("/symdir2/sub/sock")));
+        assert!(!sockpaths.contains(&interface::RustPathBuf::from("/symlnk/sub/sock")));
 
+        assert_eq!(cage.close_syscall(sockfd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_write_to_directory() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_rename_replace() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Create a directory and try to write to it.
-        // We should expect an error (EISDIR) as writing to a directory is not
-        // supported.
-        let path = "/test_dir";
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
-        let fd = cage.open_syscall(path, O_WRONLY, S_IRWXA);
+        // replacing an existing file drops the replaced file's link
+        let fd1 = cage.open_syscall("/replace_old", O_CREAT | O_RDWR, S_IRWXA);
+        assert_eq!(cage.write_syscall(fd1, str2cbuf("new"), 3), 3);
+        assert_eq!(cage.close_syscall(fd1), 0);
+        let fd2 = cage.open_syscall("/replace_new", O_CREAT | O_RDWR, S_IRWXA);
+        assert_eq!(cage.write_syscall(fd2, str2cbuf("stale data"), 10), 10);
+        assert_eq!(cage.close_syscall(fd2), 0);
+        assert_eq!(cage.link_syscall("/replace_new", "/replace_link"), 0);
 
-        let write_data = "hello";
+        assert_eq!(cage.rename_syscall("/replace_old", "/replace_new"), 0);
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/replace_new", &mut statdata), 0);
+        assert_eq!(statdata.st_size, 3);
+        assert_eq!(cage.stat_syscall("/replace_link", &mut statdata), 0);
+        assert_eq!(statdata.st_size, 10);
+        assert_eq!(statdata.st_nlink, 1);
+
+        // renaming a hard link onto another link of the same file does nothing
+        assert_eq!(cage.link_syscall("/replace_new", "/replace_new2"), 0);
+        assert_eq!(cage.rename_syscall("/replace_new", "/replace_new2"), 0);
+        assert_eq!(cage.stat_syscall("/replace_new", &mut statdata), 0);
+        assert_eq!(statdata.st_nlink, 2);
+
+        // directory and non-directory can't replace each other
+        assert_eq!(cage.mkdir_syscall("/replace_dir", S_IRWXA), 0);
         assert_eq!(
-            cage.write_syscall(fd, write_data.as_ptr(), write_data.len()),
-            -(Errno::EISDIR as i32)
+            cage.rename_syscall("/replace_new", "/replace_dir"),
+            syscall_error(Errno::EISDIR, "rename", "file over directory")
+        );
+        assert_eq!(
+            cage.rename_syscall("/replace_dir", "/replace_new"),
+            syscall_error(Errno::ENOTDIR, "rename", "directory over file")
+        );
+
+        // a directory can only replace an empty directory
+        assert_eq!(cage.mkdir_syscall("/replace_dir2", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall("/replace_dir2/child", S_IRWXA), 0);
+        assert_eq!(
+            cage.rename_syscall("/replace_dir", "/replace_dir2"),
+            syscall_error(Errno::ENOTEMPTY, "rename", "nonempty directory")
+        );
+        assert_eq!(cage.rename_syscall("/replace_dir2", "/replace_dir"), 0);
+        assert_eq!(cage.stat_syscall("/replace_dir/child", &mut statdata), 0);
+        assert_eq!(
+            cage.stat_syscall("/replace_dir2", &mut statdata),
+            -(Errno::ENOENT as i32)
+        );
+
+        // the parent directory of the new path has to exist
+        assert_eq!(
+            cage.rename_syscall("/replace_new", "/nonexistent/replace_new"),
+            syscall_error(Errno::ENOENT, "rename", "missing parent")
         );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
@@ -3786,409 +6101,1029 @@ pub mod fs_tests {
     }
 
     #[test]
-    pub fn ut_lind_fs_write_to_epoll() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_symlink_readlink_lstat() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // Create an Epoll and try to write to it.
-        // We should expect an error (EINVAL) as writing to an Epoll is not supported.
-        let epfd = cage.epoll_create_syscall(1);
-        assert!(epfd > 0);
-        let write_data = "hello";
+        let fd = cage.open_syscall("/symlink_target", O_CREAT | O_RDWR, S_IRWXA);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello"), 5), 5);
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.symlink_syscall("/symlink_target", "/symlink_link"), 0);
+
+        // creating a link over an existing name fails, even if that name is a link
         assert_eq!(
-            cage.write_syscall(epfd, write_data.as_ptr(), write_data.len()),
+            cage.symlink_syscall("/symlink_target", "/symlink_link"),
+            -(Errno::EEXIST as i32)
+        );
+        assert_eq!(
+            cage.symlink_syscall("/symlink_target", "/nonexistent/link"),
+            -(Errno::ENOENT as i32)
+        );
+
+        // readlink returns the target without a terminating null byte and truncates
+        let mut buf = sizecbuf(64);
+        assert_eq!(
+            cage.readlink_syscall("/symlink_link", buf.as_mut_ptr(), 64),
+            15
+        );
+        assert_eq!(cbuf2str(&buf[..15]), "/symlink_target");
+        assert_eq!(buf[15], 0);
+        let mut smallbuf = sizecbuf(4);
+        assert_eq!(
+            cage.readlink_syscall("/symlink_link", smallbuf.as_mut_ptr(), 4),
+            4
+        );
+        assert_eq!(cbuf2str(&smallbuf), "/sym");
+        assert_eq!(
+            cage.readlink_syscall("/symlink_target", buf.as_mut_ptr(), 64),
             -(Errno::EINVAL as i32)
         );
 
+        // stat follows the link while lstat describes the link itself
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/symlink_link", &mut statdata), 0);
+        assert!(is_reg(statdata.st_mode));
+        assert_eq!(statdata.st_size, 5);
+        assert_eq!(cage.lstat_syscall("/symlink_link", &mut statdata), 0);
+        assert!(is_lnk(statdata.st_mode));
+        assert_eq!(statdata.st_size, 15);
+
+        // opening through the link reaches the target's data
+        let fd = cage.open_syscall("/symlink_link", O_RDONLY, S_IRWXA);
+        let mut readbuf = sizecbuf(5);
+        assert_eq!(cage.read_syscall(fd, readbuf.as_mut_ptr(), 5), 5);
+        assert_eq!(cbuf2str(&readbuf), "hello");
+        assert_eq!(cage.close_syscall(fd), 0);
+
+        // a dangling link can be lstat'ed and unlinked but not stat'ed
+        assert_eq!(cage.symlink_syscall("missing", "/symlink_dangling"), 0);
+        assert_eq!(
+            cage.stat_syscall("/symlink_dangling", &mut statdata),
+            -(Errno::ENOENT as i32)
+        );
+        assert_eq!(cage.lstat_syscall("/symlink_dangling", &mut statdata), 0);
+        assert_eq!(cage.unlink_syscall("/symlink_dangling"), 0);
+        assert_eq!(
+            cage.lstat_syscall("/symlink_dangling", &mut statdata),
+            -(Errno::ENOENT as i32)
+        );
+
+        // unlinking the link leaves its target in place
+        assert_eq!(cage.unlink_syscall("/symlink_link"), 0);
+        assert_eq!(cage.stat_syscall("/symlink_target", &mut statdata), 0);
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_write_to_regular_file() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_symlink_resolution() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
 
-        // This test mainly tests writing to a regular file.
-        // * Writing data to a file should start from position 0.
-        // * Once written, the position of the seek pointer in the file descriptor
-        // should increment by the count of bytes written. If write is performed again,
-        // then the position should continue from the point it was previously left.
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        // a relative link to a directory is followed as a directory component
+        assert_eq!(cage.mkdir_syscall("/symdir", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall("/symdir/real", S_IRWXA), 0);
+        assert_eq!(cage.symlink_syscall("real", "/symdir/alias"), 0);
+        let fd = cage.open_syscall("/symdir/alias/file", O_CREAT | O_RDWR, S_IRWXA);
         assert!(fd >= 0);
-
+        assert_eq!(cage.close_syscall(fd), 0);
         let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/symdir/real/file", &mut statdata), 0);
 
-        // Write sample data to the file, and verify the number of bytes returned
-        let write_data1 = "hello";
+        // chdir through a link stores the resolved directory
+        assert_eq!(cage.chdir_syscall("/symdir/alias"), 0);
+        let mut cwdbuf = sizecbuf(32);
+        assert_eq!(cage.getcwd_syscall(cwdbuf.as_mut_ptr(), 32), 0);
+        assert_eq!(cbuf2str(&cwdbuf[..12]), "/symdir/real");
+        assert_eq!(cage.chdir_syscall("/"), 0);
+
+        // links that point at each other can't be resolved
+        assert_eq!(cage.symlink_syscall("/symdir/loop2", "/symdir/loop1"), 0);
+        assert_eq!(cage.symlink_syscall("/symdir/loop1", "/symdir/loop2"), 0);
         assert_eq!(
-            cage.write_syscall(fd, write_data1.as_ptr(), write_data1.len()),
-            5
+            cage.stat_syscall("/symdir/loop1", &mut statdata),
+            -(Errno::ELOOP as i32)
         );
+        assert_eq!(
+            cage.open_syscall("/symdir/loop1", O_RDONLY, S_IRWXA),
+            -(Errno::ELOOP as i32)
+        );
+        assert_eq!(cage.lstat_syscall("/symdir/loop1", &mut statdata), 0);
 
-        // Verify the size of the file
-        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
-        assert_eq!(statdata.st_size, 5);
-
-        // Write additional data to the file.
-        let write_data2 = " there!";
+        // O_NOFOLLOW refuses to open a link, and O_CREAT|O_EXCL won't create through
+        // one
         assert_eq!(
-            cage.write_syscall(fd, write_data2.as_ptr(), write_data2.len()),
-            7
+            cage.symlink_syscall("/symdir/real/file", "/symdir/filelink"),
+            0
+        );
+        assert_eq!(
+            cage.open_syscall("/symdir/filelink", O_RDONLY | O_NOFOLLOW, S_IRWXA),
+            -(Errno::ELOOP as i32)
+        );
+        assert_eq!(
+            cage.open_syscall("/symdir/filelink", O_CREAT | O_EXCL | O_RDWR, S_IRWXA),
+            -(Errno::EEXIST as i32)
         );
 
-        // Verify the updated size of the file
-        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
-        assert_eq!(statdata.st_size, 12);
+        // hard links to a symbolic link refer to the link itself
+        assert_eq!(
+            cage.link_syscall("/symdir/filelink", "/symdir/filelink2"),
+            0
+        );
+        assert_eq!(cage.lstat_syscall("/symdir/filelink2", &mut statdata), 0);
+        assert!(is_lnk(statdata.st_mode));
+        assert_eq!(statdata.st_nlink, 2);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_write_to_chardev_file() {
+    fn ut_lind_fs_writev_socketpair() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // This test mainly tests the case for writing to a character device type
-        // file. In this case, we are trying to write 100 bytes to the
-        // "/dev/null" file, which should succeed without doing anything.
-        let path = "/dev/null";
-        let fd = cage.open_syscall(path, O_RDWR, S_IRWXA);
+        let mut socketpair = interface::SockPair::default();
+        assert_eq!(
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            0
+        );
 
-        // Verify if the returned count of bytes is 100.
-        let write_data = "0".repeat(100);
-        assert_eq!(cage.write_syscall(fd, write_data.as_ptr(), 100), 100);
+        // Prepare data to write.
+        let data = b"Hello, world!";
+        let iovec = interface::IovecStruct {
+            iov_base: data.as_ptr() as *mut libc::c_void,
+            iov_len: data.len(),
+        };
+        // Write the data to the first socket using writev_syscall.
+        let bytes_written = cage.writev_syscall(socketpair.sock1, &iovec, 1);
+        assert_eq!(bytes_written, data.len() as i32);
+        // Read the data from the second socket.
+        let mut buffer = vec![0u8; data.len()];
+        let bytes_read = cage.recv_syscall(socketpair.sock2, buffer.as_mut_ptr(), buffer.len(), 0);
+        assert_eq!(bytes_read, data.len() as i32);
+        // Verify that the data received from the second socket matches the original
+        // data.
+        assert_eq!(buffer, data);
+        // Close both sockets.
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
 
-        assert_eq!(cage.close_syscall(fd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_write_to_sockets() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    fn ut_lind_fs_writev_pipe() {
+        // Acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
-
-        // This test mainly tests the case for writing data to a pair of Sockets.
-        // In this case, we create a socket pair of two sockets, and send data through
-        // one socket, and try to read it from the other one.
-        let mut socketpair = interface::SockPair::default();
-
-        // Verify if the socketpair is formed successfully.
-        assert_eq!(
-            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
-            0
-        );
-        // Verify if the number of bytes sent to socket1 is correct.
-        let write_data = "test";
+    
+        // Create a pipe
+        let mut pipe_fds = PipeArray::default();
+        assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
+        let read_fd = pipe_fds.readfd;
+        let write_fd = pipe_fds.writefd;
+    
+        // Prepare multiple data segments to be written using an iovec structure
+        let data1 = b"Hello, ";
+        let data2 = b"pipe!";
+        let data3 = b" Testing writev.";
+    
+        let iovec = [
+            interface::IovecStruct {
+                iov_base: data1.as_ptr() as *mut libc::c_void,
+                iov_len: data1.len(),
+            },
+            interface::IovecStruct {
+                iov_base: data2.as_ptr() as *mut libc::c_void,
+                iov_len: data2.len(),
+            },
+            interface::IovecStruct {
+                iov_base: data3.as_ptr() as *mut libc::c_void,
+                iov_len: data3.len(),
+            },
+        ];
+    
+        // Write the data to the pipe using writev_syscall
+        let bytes_written = cage.writev_syscall(write_fd, iovec.as_ptr(), iovec.len() as i32);
         assert_eq!(
-            cage.write_syscall(socketpair.sock1, write_data.as_ptr(), 4),
-            4
+            bytes_written,
+            (data1.len() + data2.len() + data3.len()) as i32
         );
-
-        // Verify if the number of bytes received by socket2 is correct.
-        let mut buf2 = sizecbuf(4);
-        assert_eq!(cage.read_syscall(socketpair.sock2, buf2.as_mut_ptr(), 4), 4);
-        // Verify if the data received inside the buffer is correct.
-        assert_eq!(cbuf2str(&buf2), "test");
-
-        // Close the sockets
-        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
-        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+    
+        // Read the data from the pipe
+        let mut buffer = vec![0u8; data1.len() + data2.len() + data3.len()];
+        let bytes_read = cage.read_syscall(read_fd, buffer.as_mut_ptr(), buffer.len());
+        assert_eq!(bytes_read, buffer.len() as i32);
+    
+        // Verify that the data read is the same as the data written
+        let expected_data = [data1.as_ref(), data2.as_ref(), data3.as_ref()].concat();
+        assert_eq!(buffer, expected_data);
+    
+        // Close both pipe file descriptors
+        assert_eq!(cage.close_syscall(read_fd), 0);
+        assert_eq!(cage.close_syscall(write_fd), 0);
+    
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_pwrite_read_only_fd() {
-        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
-        // and also performs clean env setup
+    fn ut_lind_fs_writev_file() {
+        // Acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean environment setup.
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // Test to write to a file with read only permissions, and check if
-        // a valid error is returned when the file is used for writing.
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDONLY, S_IRWXA);
-        assert!(fd >= 0);
+        // Define the file path
+        let file_path = "/tmp/test_writev_file";
 
-        let write_data = "hello";
-        assert_eq!(
-            cage.pwrite_syscall(fd, write_data.as_ptr(), write_data.len(), 0),
-            -(Errno::EBADF as i32)
-        );
+        // Open or create a file for writing
+        let fd = cage.open_syscall(file_path, O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0, "Failed to open file");
+
+        // Prepare the data to be written using an iovec structure
+        let data1 = b"Hello, ";
+        let data2 = b"world!";
+        let expected_data = [data1.as_slice(), data2.as_slice()].concat(); // Concatenate slices for comparison
+        let iovecs = [
+            interface::IovecStruct {
+                iov_base: data1.as_ptr() as *mut libc::c_void,
+                iov_len: data1.len(),
+            },
+            interface::IovecStruct {
+                iov_base: data2.as_ptr() as *mut libc::c_void,
+                iov_len: data2.len(),
+            },
+        ];
+
+        // Write the data to the file using writev_syscall
+        let total_len = expected_data.len() as i32;
+        let bytes_written = cage.writev_syscall(fd, iovecs.as_ptr(), iovecs.len() as i32);
+        assert_eq!(bytes_written, total_len);
+
+        // Read the data back from the file
+        let mut buffer = vec![0u8; total_len as usize];
+        cage.lseek_syscall(fd, 0, libc::SEEK_SET); // Seek to the beginning of the file
+        let bytes_read = cage.read_syscall(fd, buffer.as_mut_ptr(), buffer.len());
+        assert_eq!(bytes_read, total_len);
+
+        // Verify that the data read is the same as the data written
+        assert_eq!(buffer, expected_data);
+
+        // Close the file
+        assert_eq!(cage.close_syscall(fd), 0);
+
+        // Clean up the file
+        assert_eq!(cage.unlink_syscall(file_path), 0);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_pwrite_to_file() {
+    fn ut_lind_fs_readv_preadv_file() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // This test mainly tests two scenarios for writing to a file using
-        // `pwrite_syscall()`.
-        // * Writing to a file from the starting position offset(0).
-        // * Writing to a file from a random position offset, which should
-        // pad the file with additional "\0" bytes.
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_TRUNC | O_RDWR, S_IRWXA);
+        let fd = cage.open_syscall("/readvfile", O_CREAT | O_RDWR, S_IRWXA);
         assert!(fd >= 0);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("Hello, world!"), 13), 13);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
 
-        // Write sample data to the file and verify the number of bytes returned.
-        let write_data1 = "hello";
-        assert_eq!(cage.pwrite_syscall(fd, write_data1.as_ptr(), 5, 0), 5);
-
-        // Write additional data to the file starting from the 6th position offset.
-        let write_data2 = "there!";
-        assert_eq!(cage.pwrite_syscall(fd, write_data2.as_ptr(), 6, 6), 6);
+        // readv fills the buffers in order and advances the file offset
+        let mut buf1 = [0u8; 5];
+        let mut buf2 = [0u8; 3];
+        let mut buf3 = [0u8; 10];
+        let iovecs = [
+            interface::IovecStruct {
+                iov_base: buf1.as_mut_ptr() as *mut libc::c_void,
+                iov_len: buf1.len(),
+            },
+            interface::IovecStruct {
+                iov_base: buf2.as_mut_ptr() as *mut libc::c_void,
+                iov_len: buf2.len(),
+            },
+            interface::IovecStruct {
+                iov_base: buf3.as_mut_ptr() as *mut libc::c_void,
+                iov_len: buf3.len(),
+            },
+        ];
+        assert_eq!(cage.readv_syscall(fd, iovecs.as_ptr(), 3), 13);
+        assert_eq!(&buf1, b"Hello");
+        assert_eq!(&buf2, b", w");
+        assert_eq!(&buf3[..5], b"orld!");
+        assert_eq!(cage.readv_syscall(fd, iovecs.as_ptr(), 3), 0);
+
+        // preadv reads at the given offset and leaves the file offset alone
+        buf1 = [0u8; 5];
+        assert_eq!(cage.preadv_syscall(fd, iovecs.as_ptr(), 2, 7), 6);
+        assert_eq!(&buf1, b"world");
+        assert_eq!(&buf2[..1], b"!");
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_CUR), 13);
+        assert_eq!(cage.preadv_syscall(fd, iovecs.as_ptr(), 2, 100), 0);
+        assert_eq!(cage.preadv2_syscall(fd, iovecs.as_ptr(), 1, 0, 0), 5);
+        assert_eq!(&buf1, b"Hello");
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_CUR), 13);
+        // with an offset of -1, preadv2 reads from the file offset like readv
+        assert_eq!(cage.lseek_syscall(fd, 7, SEEK_SET), 7);
+        assert_eq!(
+            cage.preadv2_syscall(fd, iovecs.as_ptr(), 1, -1, RWF_NOWAIT),
+            5
+        );
+        assert_eq!(&buf1, b"world");
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_CUR), 12);
 
-        // Read back the data to verify, but since we are changing the offset to
-        // a larger number than the file size, it should pad the file with "\0" values.
-        // Verify if the file contains the paded bytes as well.
-        let mut read_buf = sizecbuf(12);
-        assert_eq!(cage.pread_syscall(fd, read_buf.as_mut_ptr(), 12, 0), 12);
-        assert_eq!(cbuf2str(&read_buf), "hello\0there!");
+        assert_eq!(
+            cage.preadv_syscall(fd, iovecs.as_ptr(), 1, -1),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.preadv2_syscall(fd, iovecs.as_ptr(), 1, -2, 0),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.preadv2_syscall(fd, iovecs.as_ptr(), 1, 0, 0x100),
+            -(Errno::EOPNOTSUPP as i32)
+        );
+        assert_eq!(
+            cage.readv_syscall(fd, iovecs.as_ptr(), -1),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.readv_syscall(fd, iovecs.as_ptr(), IOV_MAX + 1),
+            -(Errno::EINVAL as i32)
+        );
 
+        assert_eq!(cage.close_syscall(fd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_pwrite_to_directory() {
+    fn ut_lind_fs_pwritev_file() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // Create a directory and try to write to it.
-        // We should expect an error (EISDIR) as writing to a directory is not
-        // supported.
-        let path = "/test_dir";
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
-        let fd = cage.open_syscall(path, O_WRONLY, S_IRWXA);
+        let fd = cage.open_syscall("/pwritevfile", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        let data1 = b"abc";
+        let data2 = b"de";
+        let iovecs = [
+            interface::IovecStruct {
+                iov_base: data1.as_ptr() as *mut libc::c_void,
+                iov_len: data1.len(),
+            },
+            interface::IovecStruct {
+                iov_base: data2.as_ptr() as *mut libc::c_void,
+                iov_len: data2.len(),
+            },
+        ];
 
-        let write_data = "hello";
+        // pwritev writes at the offset, filling the gap before it with zeroes,
+        // without moving the file offset
+        assert_eq!(cage.pwritev_syscall(fd, iovecs.as_ptr(), 2, 2), 5);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_CUR), 0);
+        // pwritev2 with an offset of -1 writes at the file offset and advances it
+        assert_eq!(cage.pwritev2_syscall(fd, iovecs.as_ptr(), 1, -1, 0), 3);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_CUR), 3);
+        // RWF_APPEND writes at the end of the file whatever the offset, and only
+        // moves the file offset if no offset was given
         assert_eq!(
-            cage.pwrite_syscall(fd, write_data.as_ptr(), write_data.len(), 0),
-            -(Errno::EISDIR as i32)
+            cage.pwritev2_syscall(fd, iovecs[1..].as_ptr(), 1, 0, RWF_APPEND | RWF_DSYNC),
+            2
+        );
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_CUR), 3);
+        assert_eq!(
+            cage.pwritev2_syscall(fd, iovecs.as_ptr(), 1, -1, RWF_APPEND | RWF_SYNC),
+            3
         );
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_CUR), 12);
 
+        let mut buf = [0u8; 12];
+        assert_eq!(cage.pread_syscall(fd, buf.as_mut_ptr(), 12, 0), 12);
+        assert_eq!(&buf, b"abcbcdedeabc");
+
+        assert_eq!(
+            cage.pwritev_syscall(fd, iovecs.as_ptr(), 2, -1),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.pwritev2_syscall(fd, iovecs.as_ptr(), 2, 0, 0x100),
+            -(Errno::EOPNOTSUPP as i32)
+        );
+        assert_eq!(cage.close_syscall(fd), 0);
+
+        // descriptors outside the table are rejected rather than looked up
+        for badfd in [-1, MAXFD, i32::MAX] {
+            assert_eq!(
+                cage.writev_syscall(badfd, iovecs.as_ptr(), 2),
+                -(Errno::EBADF as i32)
+            );
+            assert_eq!(
+                cage.pwritev_syscall(badfd, iovecs.as_ptr(), 2, 0),
+                -(Errno::EBADF as i32)
+            );
+            assert_eq!(
+                cage.readv_syscall(badfd, iovecs.as_ptr(), 2),
+                -(Errno::EBADF as i32)
+            );
+            assert_eq!(
+                cage.preadv2_syscall(badfd, iovecs.as_ptr(), 2, -1, 0),
+                -(Errno::EBADF as i32)
+            );
+        }
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_pwrite_invalid_types() {
+    fn ut_lind_fs_readv_pipe_socket() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // Test for invalid pipe
-        // Try writing the data to the pipe and check for error.
+        let mut buf1 = [0u8; 4];
+        let mut buf2 = [0u8; 8];
+        let iovecs = [
+            interface::IovecStruct {
+                iov_base: buf1.as_mut_ptr() as *mut libc::c_void,
+                iov_len: buf1.len(),
+            },
+            interface::IovecStruct {
+                iov_base: buf2.as_mut_ptr() as *mut libc::c_void,
+                iov_len: buf2.len(),
+            },
+        ];
+
         let mut pipe_fds = PipeArray::default();
         assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
-        let write_fd = pipe_fds.writefd;
-        let write_data = "hello";
+        // Pipes can't be accessed at an offset, and RWF_NOWAIT doesn't wait for data
         assert_eq!(
-            cage.pwrite_syscall(write_fd, write_data.as_ptr(), write_data.len(), 0),
+            cage.preadv_syscall(pipe_fds.readfd, iovecs.as_ptr(), 2, 0),
             -(Errno::ESPIPE as i32)
         );
+        assert_eq!(
+            cage.pwritev_syscall(pipe_fds.writefd, iovecs.as_ptr(), 2, 0),
+            -(Errno::ESPIPE as i32)
+        );
+        assert_eq!(
+            cage.preadv2_syscall(pipe_fds.readfd, iovecs.as_ptr(), 2, -1, RWF_NOWAIT),
+            -(Errno::EAGAIN as i32)
+        );
+        // A read only takes what is in the pipe rather than waiting to fill every
+        // buffer
+        assert_eq!(
+            cage.write_syscall(pipe_fds.writefd, str2cbuf("pipedata"), 8),
+            8
+        );
+        assert_eq!(cage.readv_syscall(pipe_fds.readfd, iovecs.as_ptr(), 2), 8);
+        assert_eq!(&buf1, b"pipe");
+        assert_eq!(&buf2[..4], b"data");
+        assert_eq!(cage.close_syscall(pipe_fds.writefd), 0);
+        assert_eq!(cage.readv_syscall(pipe_fds.readfd, iovecs.as_ptr(), 2), 0);
+        assert_eq!(cage.close_syscall(pipe_fds.readfd), 0);
 
-        // Test for invalid sockets
-        // Try writing the data to the socket and check for error.
+        // Sockets receive once and spread the data over the buffers
         let mut socketpair = interface::SockPair::default();
         assert_eq!(
             Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
             0
         );
         assert_eq!(
-            cage.pwrite_syscall(socketpair.sock2, write_data.as_ptr(), 4, 0),
-            -(Errno::ESPIPE as i32)
+            cage.send_syscall(socketpair.sock1, str2cbuf("socketdata"), 10, 0),
+            10
         );
-
-        // Test for invalid epoll
-        // Try writing the data to the epoll and check for error.
-        let epfd = cage.epoll_create_syscall(1);
+        assert_eq!(cage.readv_syscall(socketpair.sock2, iovecs.as_ptr(), 2), 10);
+        assert_eq!(&buf1, b"sock");
+        assert_eq!(&buf2[..6], b"etdata");
         assert_eq!(
-            cage.pwrite_syscall(epfd, write_data.as_ptr(), 5, 0),
-            -(Errno::ESPIPE as i32)
+            cage.preadv2_syscall(socketpair.sock2, iovecs.as_ptr(), 2, -1, RWF_NOWAIT),
+            -(Errno::EOPNOTSUPP as i32)
         );
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_pwrite_to_chardev_file() {
+    pub fn ut_lind_fs_permissions_between_users() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
+        //Run the test cage as the superuser, the way the loader would start a
+        //privileged tenant
+        cage.getuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        cage.geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
 
-        // This test mainly tests the case for writing to a character device type
-        // file. In this case, we are trying to write 100 bytes to the
-        // "/dev/null" file, which should succeed without doing anything.
-        let path = "/dev/null";
-        let fd = cage.open_syscall(path, O_RDWR, S_IRWXA);
+        //The superuser sets up a shared directory only it may write to, with a
+        //private file and a world readable file owned by user 2000 in it, and
+        //a private directory owned by user 2000
+        assert_eq!(
+            cage.mkdir_syscall("/shared", S_IRWXU | S_IRGRP | S_IXGRP | S_IROTH | S_IXOTH),
+            0
+        );
+        let fd = cage.open_syscall(
+            "/shared/secret",
+            O_CREAT | O_EXCL | O_WRONLY,
+            S_IRUSR | S_IWUSR,
+        );
+        assert!(fd >= 0);
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.chown_syscall("/shared/secret", 2000, 2000), 0);
+        let fd = cage.open_syscall(
+            "/shared/public",
+            O_CREAT | O_EXCL | O_WRONLY,
+            S_IRUSR | S_IWUSR | S_IRGRP | S_IROTH,
+        );
+        assert!(fd >= 0);
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.chown_syscall("/shared/public", 2000, 2000), 0);
+        assert_eq!(cage.mkdir_syscall("/private", S_IRWXU), 0);
+        let fd = cage.open_syscall("/private/file", O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.chown_syscall("/private", 2000, 2000), 0);
 
-        // Verify if the returned count of bytes is 100.
-        let write_data = "0".repeat(100);
-        assert_eq!(cage.pwrite_syscall(fd, write_data.as_ptr(), 100, 0), 100);
+        assert_eq!(cage.fork_syscall(2), 0);
+        let cage2 = interface::cagetable_getref(2);
+        //The child permanently drops to user 3000; the group has to be changed
+        //first, while it is still privileged
+        assert_eq!(cage2.setgid_syscall(3000), 0);
+        assert_eq!(cage2.setuid_syscall(3000), 0);
+        assert_eq!(cage2.getuid_syscall(), 3000);
+        assert_eq!(cage2.geteuid_syscall(), 3000);
+        assert_eq!(cage2.getegid_syscall(), 3000);
+        //and can't get its privileges back
+        assert_eq!(cage2.setuid_syscall(ROOT_UID), -(Errno::EPERM as i32));
+        assert_eq!(cage2.seteuid_syscall(ROOT_UID), -(Errno::EPERM as i32));
+        assert_eq!(cage2.setgid_syscall(2000), -(Errno::EPERM as i32));
+
+        //Another user's private file can't be read or written
+        assert_eq!(
+            cage2.open_syscall("/shared/secret", O_RDONLY, 0),
+            -(Errno::EACCES as i32)
+        );
+        assert_eq!(
+            cage2.access_syscall("/shared/secret", R_OK),
+            -(Errno::EACCES as i32)
+        );
+        //A world readable file can only be read
+        let fd = cage2.open_syscall("/shared/public", O_RDONLY, 0);
+        assert!(fd >= 0);
+        assert_eq!(cage2.close_syscall(fd), 0);
+        assert_eq!(
+            cage2.open_syscall("/shared/public", O_WRONLY, 0),
+            -(Errno::EACCES as i32)
+        );
+        assert_eq!(
+            cage2.open_syscall("/shared/public", O_RDONLY | O_TRUNC, 0),
+            -(Errno::EACCES as i32)
+        );
+        assert_eq!(cage2.access_syscall("/shared/public", R_OK), 0);
+        assert_eq!(
+            cage2.access_syscall("/shared/public", W_OK),
+            -(Errno::EACCES as i32)
+        );
+        //Files in a directory that can't be searched can't be reached at all,
+        //whatever their own mode is
+        assert_eq!(
+            cage2.open_syscall("/private/file", O_RDONLY, 0),
+            -(Errno::EACCES as i32)
+        );
+        //Neither the shared directory nor the file belongs to the user, so it
+        //can't create, remove or change the mode of anything there
+        assert_eq!(
+            cage2.mkdir_syscall("/shared/newdir", S_IRWXA),
+            -(Errno::EACCES as i32)
+        );
+        assert_eq!(
+            cage2.open_syscall("/shared/newfile", O_CREAT | O_WRONLY, S_IRWXA),
+            -(Errno::EACCES as i32)
+        );
+        assert_eq!(
+            cage2.unlink_syscall("/shared/public"),
+            -(Errno::EACCES as i32)
+        );
+        assert_eq!(
+            cage2.chmod_syscall("/shared/public", S_IRWXA),
+            -(Errno::EPERM as i32)
+        );
+        assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
 
+        //The superuser may temporarily take on the identity of the owner, which
+        //is then granted access, and switch back since its real id is still root
+        assert_eq!(cage.seteuid_syscall(2000), 0);
+        let fd = cage.open_syscall("/private/file", O_RDWR, 0);
+        assert!(fd >= 0);
         assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.seteuid_syscall(ROOT_UID), 0);
+
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
-    pub fn ut_lind_fs_shmget_syscall() {
-        // acquire locks and start env cleanup
+    #[test]
+    pub fn ut_lind_fs_bind_and_rename_permissions() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-        let cage = interface::cagetable_getref(1);
 
-        let key = 33123;
-        // Get shmid of a memory segment / create a new one if it doesn't exist
-        let shmid = cage.shmget_syscall(33123, 1024, IPC_CREAT);
-        assert_eq!(shmid, 4);
+        let cage = interface::cagetable_getref(1);
+        //Run the test cage as the superuser so it can start cages as other users
+        cage.getuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        cage.geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
 
-        // Check error upon asking for a valid key and passing the IPC_CREAT and
-        // IPC_EXCL flag
+        //A directory others may not write to, and a writable one that can only be
+        //reached through a directory others may not search
+        assert_eq!(cage.mkdir_syscall("/sockdir", S_IRWXA), 0);
         assert_eq!(
-            cage.shmget_syscall(key, 1024, IPC_CREAT | IPC_EXCL),
-            -(Errno::EEXIST as i32)
+            cage.mkdir_syscall("/closed", S_IRWXU | S_IRGRP | S_IXGRP | S_IROTH | S_IXOTH),
+            0
         );
+        assert_eq!(cage.mkdir_syscall("/nosearch", S_IRWXU), 0);
+        assert_eq!(cage.mkdir_syscall("/nosearch/open", S_IRWXA), 0);
 
-        // Check error when passing IPC_CREAT flag as the key
+        assert_eq!(cage.fork_syscall(2), 0);
+        let cage2 = interface::cagetable_getref(2);
+        assert_eq!(cage2.setuid_syscall(2000), 0);
+
+        //Binding creates the socket file like any other file, so the caller needs
+        //to be able to reach the parent directory and to write to it
+        for path in ["/closed/sock", "/nosearch/open/sock"] {
+            let sockfd = cage2.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+            let sockaddr = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+                AF_UNIX as u16,
+                path.as_bytes(),
+            ));
+            assert_eq!(
+                cage2.bind_syscall(sockfd, &sockaddr),
+                -(Errno::EACCES as i32)
+            );
+            assert_eq!(cage2.close_syscall(sockfd), 0);
+        }
+        let mut statdata = StatData::default();
         assert_eq!(
-            cage.shmget_syscall(IPC_PRIVATE, 1024, IPC_PRIVATE),
+            cage.stat_syscall("/closed/sock", &mut statdata),
             -(Errno::ENOENT as i32)
         );
 
-        // Check if the function returns a correct shmid upon asking with a key that we
-        // know exists
-        assert_eq!(cage.shmget_syscall(key, 1024, 0666), shmid);
-
-        // Check if the function returns the correct error when we don't pass IPC_CREAT
-        // for a key that doesn't exist
-        assert_eq!(
-            cage.shmget_syscall(123456, 1024, 0),
-            -(Errno::ENOENT as i32)
-        );
+        //and the socket file belongs to the caller
+        let sockfd = cage2.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+        let sockaddr = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "/sockdir/sock".as_bytes(),
+        ));
+        assert_eq!(cage2.bind_syscall(sockfd, &sockaddr), 0);
+        assert_eq!(cage.stat_syscall("/sockdir/sock", &mut statdata), 0);
+        assert_eq!(statdata.st_uid, 2000);
+        assert_eq!(cage2.close_syscall(sockfd), 0);
 
-        // Check if the size error is returned correctly
-        assert_eq!(
-            cage.shmget_syscall(123456, (SHMMAX + 10) as usize, IPC_CREAT),
-            -(Errno::EINVAL as i32)
-        );
+        //Renaming into a directory is adding an entry to it just the same
+        let fd = cage2.open_syscall("/sockdir/file", O_CREAT | O_WRONLY, S_IRWXA);
+        assert_eq!(cage2.close_syscall(fd), 0);
         assert_eq!(
-            cage.shmget_syscall(123456, 0 as usize, IPC_CREAT),
-            -(Errno::EINVAL as i32)
+            cage2.rename_syscall("/sockdir/file", "/closed/file"),
+            -(Errno::EACCES as i32)
         );
+        assert_eq!(cage2.rename_syscall("/sockdir/file", "/sockdir/file2"), 0);
 
+        assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_lseek_on_file() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_sticky_tmp() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
+        //Run the test cage as the superuser so it can start cages as other users
+        cage.getuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        cage.geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
 
-        // Test to create a file and check if seeking to a new location is possible.
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_WRONLY, S_IRWXA);
+        //The /tmp directory is created world writable with the sticky bit set
+        let mut statdata = StatData::default();
+        assert_eq!(cage.stat_syscall("/tmp", &mut statdata), 0);
+        assert_eq!(statdata.st_mode & S_ISVTX, S_ISVTX);
+
+        assert_eq!(cage.fork_syscall(2), 0);
+        assert_eq!(cage.fork_syscall(3), 0);
+        let cage2 = interface::cagetable_getref(2);
+        let cage3 = interface::cagetable_getref(3);
+        assert_eq!(cage2.setuid_syscall(2000), 0);
+        assert_eq!(cage3.setuid_syscall(3000), 0);
+
+        let fd = cage2.open_syscall("/tmp/a", O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
         assert!(fd >= 0);
+        assert_eq!(cage2.close_syscall(fd), 0);
+        assert_eq!(cage2.mkdir_syscall("/tmp/dir", S_IRWXA), 0);
 
-        // Attempt to seek within the file and check if it succeeds
-        assert_eq!(cage.lseek_syscall(fd, 10, SEEK_SET), 10);
+        //Another user may not remove, rename or replace the file, or remove the
+        //directory, even though /tmp is writable by everyone
+        assert_eq!(cage3.unlink_syscall("/tmp/a"), -(Errno::EPERM as i32));
+        assert_eq!(
+            cage3.rename_syscall("/tmp/a", "/tmp/b"),
+            -(Errno::EPERM as i32)
+        );
+        let fd = cage3.open_syscall("/tmp/c", O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage3.close_syscall(fd), 0);
+        assert_eq!(
+            cage3.rename_syscall("/tmp/c", "/tmp/a"),
+            -(Errno::EPERM as i32)
+        );
+        assert_eq!(cage3.rmdir_syscall("/tmp/dir"), -(Errno::EPERM as i32));
+
+        //but their own files can be removed by their owners
+        assert_eq!(cage3.unlink_syscall("/tmp/c"), 0);
+        assert_eq!(cage2.rename_syscall("/tmp/a", "/tmp/b"), 0);
+        assert_eq!(cage2.unlink_syscall("/tmp/b"), 0);
+        assert_eq!(cage2.rmdir_syscall("/tmp/dir"), 0);
 
+        assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        assert_eq!(cage3.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_lseek_on_directory() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_chown_fchown_lchown() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
+        let mut statdata = StatData::default();
 
-        // Create a directory and try to seek within it.
-        let path = "/test_dir";
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
-        let fd = cage.open_syscall(path, O_RDONLY, S_IRWXA);
+        let fd = cage.open_syscall("/file", O_CREAT | O_EXCL | O_RDWR, S_IRWXA);
         assert!(fd >= 0);
+        assert_eq!(cage.symlink_syscall("/file", "/link"), 0);
 
-        // Attempt to seek within the directory and check if it succeeds
-        assert_eq!(cage.lseek_syscall(fd, 1, SEEK_SET), 1);
+        //New files are owned by the effective ids of their creator
+        assert_eq!(cage.stat_syscall("/file", &mut statdata), 0);
+        assert_eq!(statdata.st_uid, DEFAULT_UID);
+        assert_eq!(statdata.st_gid, DEFAULT_GID);
+
+        //An unprivileged owner may only "change" the group to its own
+        assert_eq!(cage.chown_syscall("/file", NOCHANGE_ID, DEFAULT_GID), 0);
+        assert_eq!(
+            cage.chown_syscall("/file", 2000, NOCHANGE_ID),
+            -(Errno::EPERM as i32)
+        );
+        assert_eq!(
+            cage.chown_syscall("/file", NOCHANGE_ID, 2000),
+            -(Errno::EPERM as i32)
+        );
+
+        //The superuser may change both
+        cage.geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+
+        //lchown changes the link itself, not the file it points to
+        assert_eq!(cage.lchown_syscall("/link", 2000, 2000), 0);
+        assert_eq!(cage.lstat_syscall("/link", &mut statdata), 0);
+        assert_eq!(statdata.st_uid, 2000);
+        assert_eq!(statdata.st_gid, 2000);
+        assert_eq!(cage.stat_syscall("/link", &mut statdata), 0);
+        assert_eq!(statdata.st_uid, DEFAULT_UID);
+
+        //while chown follows the link
+        assert_eq!(cage.chown_syscall("/link", 3000, 3000), 0);
+        assert_eq!(cage.stat_syscall("/file", &mut statdata), 0);
+        assert_eq!(statdata.st_uid, 3000);
+        assert_eq!(statdata.st_gid, 3000);
+
+        //fchown changes the file behind the descriptor
+        assert_eq!(cage.fchown_syscall(fd, 4000, NOCHANGE_ID), 0);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_uid, 4000);
+        assert_eq!(statdata.st_gid, 3000);
+
+        assert_eq!(
+            cage.chown_syscall("/nonexistent", 0, 0),
+            -(Errno::ENOENT as i32)
+        );
+        assert_eq!(cage.fchown_syscall(-1, 0, 0), -(Errno::EBADF as i32));
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.fchown_syscall(fd, 0, 0), -(Errno::EBADF as i32));
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_lseek_invalid_whence() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_utimensat_futimens() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
+        let mut statdata = StatData::default();
 
-        // Test to create a file and check for invalid `whence` value
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDWR, S_IRWXA);
+        assert_eq!(cage.mkdir_syscall("/timesdir", S_IRWXA), 0);
+        let fd = cage.open_syscall("/timesdir/file", O_CREAT | O_EXCL | O_RDWR, S_IRWXA);
         assert!(fd >= 0);
+        assert_eq!(cage.symlink_syscall("file", "/timesdir/link"), 0);
+
+        //Explicit times are stored with nanosecond precision, while the status
+        //change time is set to the current time
+        let times = [
+            interface::TimeSpec {
+                tv_sec: 100,
+                tv_nsec: 5,
+            },
+            interface::TimeSpec {
+                tv_sec: 200,
+                tv_nsec: 6,
+            },
+        ];
+        assert_eq!(
+            cage.utimensat_syscall(AT_FDCWD, "/timesdir/file", Some(&times), 0),
+            0
+        );
+        assert_eq!(cage.stat_syscall("/timesdir/file", &mut statdata), 0);
+        assert_eq!(statdata.st_atim, (100, 5));
+        assert_eq!(statdata.st_mtim, (200, 6));
+        assert!(statdata.st_ctim.0 > 200);
+
+        //UTIME_OMIT leaves a time unchanged and UTIME_NOW sets it to the current
+        //time. A relative path is resolved against the directory fd.
+        let dirfd = cage.open_syscall("/timesdir", O_RDONLY, 0);
+        assert!(dirfd >= 0);
+        let times = [
+            interface::TimeSpec {
+                tv_sec: 0,
+                tv_nsec: UTIME_OMIT,
+            },
+            interface::TimeSpec {
+                tv_sec: 0,
+                tv_nsec: UTIME_NOW,
+            },
+        ];
+        assert_eq!(cage.utimensat_syscall(dirfd, "file", Some(&times), 0), 0);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_atim, (100, 5));
+        assert!(statdata.st_mtim.0 > 200);
+
+        //AT_SYMLINK_NOFOLLOW changes the link rather than the file it points to
+        let times = [
+            interface::TimeSpec {
+                tv_sec: 300,
+                tv_nsec: 0,
+            },
+            interface::TimeSpec {
+                tv_sec: 300,
+                tv_nsec: 0,
+            },
+        ];
+        assert_eq!(
+            cage.utimensat_syscall(
+                AT_FDCWD,
+                "/timesdir/link",
+                Some(&times),
+                AT_SYMLINK_NOFOLLOW
+            ),
+            0
+        );
+        assert_eq!(cage.lstat_syscall("/timesdir/link", &mut statdata), 0);
+        assert_eq!(statdata.st_mtim, (300, 0));
+        assert_eq!(cage.stat_syscall("/timesdir/link", &mut statdata), 0);
+        assert_eq!(statdata.st_atim, (100, 5));
 
-        // Attempt to seek with an invalid `whence` value and check if it returns an
-        // error
+        //futimens with no times sets both to the current time
+        assert_eq!(cage.futimens_syscall(fd, None), 0);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert!(statdata.st_atim.0 > 300);
+        assert!(statdata.st_mtim.0 > 300);
+
+        let badtimes = [
+            interface::TimeSpec {
+                tv_sec: 0,
+                tv_nsec: 1_000_000_000,
+            },
+            interface::TimeSpec {
+                tv_sec: 0,
+                tv_nsec: UTIME_OMIT,
+            },
+        ];
         assert_eq!(
-            cage.lseek_syscall(fd, 10, 999), // Invalid whence value
+            cage.futimens_syscall(fd, Some(&badtimes)),
             -(Errno::EINVAL as i32)
         );
+        //times before the epoch, or too far after it to be kept in nanoseconds,
+        //are rejected rather than wrapped around
+        for tv_sec in [-1, i64::MAX, (u64::MAX / 1_000_000_000) as i64 + 1] {
+            let badtimes = [
+                interface::TimeSpec { tv_sec, tv_nsec: 0 },
+                interface::TimeSpec {
+                    tv_sec: 0,
+                    tv_nsec: UTIME_OMIT,
+                },
+            ];
+            assert_eq!(
+                cage.futimens_syscall(fd, Some(&badtimes)),
+                -(Errno::EINVAL as i32)
+            );
+        }
+        assert_eq!(
+            cage.utimensat_syscall(AT_FDCWD, "/timesdir/file", None, 0x4000),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.utimensat_syscall(AT_FDCWD, "/timesdir/nonexistent", None, 0),
+            -(Errno::ENOENT as i32)
+        );
+        assert_eq!(
+            cage.utimensat_syscall(fd, "file", None, 0),
+            -(Errno::ENOTDIR as i32)
+        );
+        assert_eq!(cage.futimens_syscall(-1, None), -(Errno::EBADF as i32));
 
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.close_syscall(dirfd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_lseek_beyond_file_size() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_utimensat_permissions() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
+        //Run the test cage as the superuser so it can start a cage as another user
+        cage.getuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        cage.geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
 
-        // Test to create a file and seek beyond its size
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDWR, S_IRWXA);
+        let fd = cage.open_syscall("/writable", O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.close_syscall(fd), 0);
+        let fd = cage.open_syscall("/readonly", O_CREAT | O_EXCL | O_WRONLY, S_IRUSR | S_IROTH);
         assert!(fd >= 0);
+        assert_eq!(cage.close_syscall(fd), 0);
 
-        // Write sample data to the file.
-        assert_eq!(cage.write_syscall(fd, str2cbuf("hello"), 5), 5);
+        assert_eq!(cage.fork_syscall(2), 0);
+        let cage2 = interface::cagetable_getref(2);
+        assert_eq!(cage2.setuid_syscall(2000), 0);
+
+        //Anyone who may write to a file may set its times to the current time,
+        //but only the owner may set them to arbitrary values
+        let times = [
+            interface::TimeSpec {
+                tv_sec: 1,
+                tv_nsec: 0,
+            },
+            interface::TimeSpec {
+                tv_sec: 1,
+                tv_nsec: 0,
+            },
+        ];
+        assert_eq!(cage2.utimensat_syscall(AT_FDCWD, "/writable", None, 0), 0);
+        assert_eq!(
+            cage2.utimensat_syscall(AT_FDCWD, "/writable", Some(&times), 0),
+            -(Errno::EPERM as i32)
+        );
+        assert_eq!(
+            cage2.utimensat_syscall(AT_FDCWD, "/readonly", None, 0),
+            -(Errno::EACCES as i32)
+        );
+        assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
 
-        // Seek beyond the end of the file and verify if it succeeds
         assert_eq!(
-            cage.lseek_syscall(fd, 10, SEEK_END),
-            15 // 5 (file size) + 10 (offset)
+            cage.utimensat_syscall(AT_FDCWD, "/readonly", Some(&times), 0),
+            0
         );
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
@@ -4196,676 +7131,1327 @@ pub mod fs_tests {
     }
 
     #[test]
-    pub fn ut_lind_fs_lseek_before_start_of_file() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_timestamps_maintained() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
 
         let cage = interface::cagetable_getref(1);
+        let mut statdata = StatData::default();
+        //Times far in the past, so that any update is easy to tell apart
+        let old = [
+            interface::TimeSpec {
+                tv_sec: 1,
+                tv_nsec: 0,
+            },
+            interface::TimeSpec {
+                tv_sec: 2,
+                tv_nsec: 0,
+            },
+        ];
 
-        // Test to create a file and attempt to seek before the start of the file
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDWR, S_IRWXA);
+        assert_eq!(cage.mkdir_syscall("/dir", S_IRWXA), 0);
+        let fd = cage.open_syscall("/dir/file", O_CREAT | O_EXCL | O_RDWR, S_IRWXA);
         assert!(fd >= 0);
 
-        // Attempt to seek to a negative offset and check if it returns an error
-        // using "SEEK_SET" whence, where we are explicitly setting the file
-        // offset to -10 value.
-        assert_eq!(
-            cage.lseek_syscall(fd, -10, SEEK_SET),
-            -(Errno::EINVAL as i32)
-        );
+        //Writing updates the modification and status change times
+        assert_eq!(cage.futimens_syscall(fd, Some(&old)), 0);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello"), 5), 5);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert!(statdata.st_mtim.0 > 2);
+        assert_eq!(statdata.st_mtim, statdata.st_ctim);
+        assert_eq!(statdata.st_atim, (1, 0));
+
+        //as do pwrite and truncating
+        assert_eq!(cage.futimens_syscall(fd, Some(&old)), 0);
+        assert_eq!(cage.pwrite_syscall(fd, str2cbuf("j"), 1, 0), 1);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert!(statdata.st_mtim.0 > 2);
+        assert_eq!(cage.futimens_syscall(fd, Some(&old)), 0);
+        assert_eq!(cage.ftruncate_syscall(fd, 2), 0);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert!(statdata.st_mtim.0 > 2);
 
-        // Attempt to seek to a negative offset and check if it returns an error
-        // using "SEEK_CUR" whence, where current position of the file is 0,
-        // as it's empty initially, and we are adding -10 to the offset.
-        assert_eq!(
-            cage.lseek_syscall(fd, -10, SEEK_CUR),
-            -(Errno::EINVAL as i32)
-        );
+        //Reading updates the access time when it isn't newer than the
+        //modification time
+        let mut buf = sizecbuf(2);
+        assert_eq!(cage.futimens_syscall(fd, Some(&old)), 0);
+        assert_eq!(cage.pread_syscall(fd, buf.as_mut_ptr(), 2, 0), 2);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert!(statdata.st_atim.0 > 2);
+        assert_eq!(statdata.st_mtim, (2, 0));
+
+        //Changing the mode or the number of links only changes the status change
+        //time of the file, while adding or removing an entry modifies the
+        //directory
+        assert_eq!(cage.futimens_syscall(fd, Some(&old)), 0);
+        assert_eq!(cage.utimensat_syscall(AT_FDCWD, "/dir", Some(&old), 0), 0);
+        assert_eq!(cage.chmod_syscall("/dir/file", S_IRUSR | S_IWUSR), 0);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_mtim, (2, 0));
+        assert!(statdata.st_ctim.0 > 2);
+
+        assert_eq!(cage.futimens_syscall(fd, Some(&old)), 0);
+        assert_eq!(cage.link_syscall("/dir/file", "/dir/link"), 0);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_mtim, (2, 0));
+        assert!(statdata.st_ctim.0 > 2);
+        assert_eq!(cage.stat_syscall("/dir", &mut statdata), 0);
+        assert!(statdata.st_mtim.0 > 2);
+        assert_eq!(statdata.st_mtim, statdata.st_ctim);
+
+        assert_eq!(cage.futimens_syscall(fd, Some(&old)), 0);
+        assert_eq!(cage.utimensat_syscall(AT_FDCWD, "/dir", Some(&old), 0), 0);
+        assert_eq!(cage.unlink_syscall("/dir/link"), 0);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert!(statdata.st_ctim.0 > 2);
+        assert_eq!(cage.stat_syscall("/dir", &mut statdata), 0);
+        assert!(statdata.st_mtim.0 > 2);
 
+        assert_eq!(cage.mkdir_syscall("/dir/sub", S_IRWXA), 0);
+        assert_eq!(cage.utimensat_syscall(AT_FDCWD, "/dir", Some(&old), 0), 0);
+        assert_eq!(cage.rmdir_syscall("/dir/sub"), 0);
+        assert_eq!(cage.stat_syscall("/dir", &mut statdata), 0);
+        assert!(statdata.st_mtim.0 > 2);
+
+        assert_eq!(cage.close_syscall(fd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_lseek_on_pipe() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_rlimit_nofile() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // Create a pipe and attempt to seek within it
-        let mut pipe_fds = PipeArray::default();
-        assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
-        let read_fd = pipe_fds.readfd;
-
-        // Attempt to seek within the pipe and check if it returns an error
+        // Allow fds 0 to 4 only, with 0 to 2 taken by the standard streams
+        let limit = Rlimit {
+            rlim_cur: 5,
+            rlim_max: NOFILE_MAX,
+        };
+        assert_eq!(cage.setrlimit_syscall(RLIMIT_NOFILE, &limit), 0);
+        let fd1 = cage.open_syscall("/file1", O_CREAT | O_RDWR, S_IRWXA);
+        let fd2 = cage.open_syscall("/file2", O_CREAT | O_RDWR, S_IRWXA);
+        assert_eq!((fd1, fd2), (3, 4));
         assert_eq!(
-            cage.lseek_syscall(read_fd, 10, SEEK_SET),
-            -(Errno::ESPIPE as i32)
+            cage.open_syscall("/file3", O_CREAT | O_RDWR, S_IRWXA),
+            -(Errno::EMFILE as i32)
         );
+        assert_eq!(cage.dup_syscall(fd1, None), -(Errno::EMFILE as i32));
+        assert_eq!(cage.dup2_syscall(fd1, 5), -(Errno::EBADF as i32));
+        assert_eq!(cage.fcntl_syscall(fd1, F_DUPFD, 5), -(Errno::EINVAL as i32));
+
+        // Freeing an fd below the limit makes room again
+        assert_eq!(cage.close_syscall(fd2), 0);
+        assert_eq!(cage.dup_syscall(fd1, None), 4);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_lseek_on_chardev() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_rlimit_fsize() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // Attempt to seek within a character device file
-        let path = "/dev/null";
-        let fd = cage.open_syscall(path, O_RDWR, S_IRWXA);
+        let limit = Rlimit {
+            rlim_cur: 10,
+            rlim_max: RLIM_INFINITY,
+        };
+        assert_eq!(cage.setrlimit_syscall(RLIMIT_FSIZE, &limit), 0);
+        let fd = cage.open_syscall("/file", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
 
-        // Seek within the character device and check if it returns 0 (no operation)
-        assert_eq!(cage.lseek_syscall(fd, 10, SEEK_SET), 0);
+        // Writes are cut short at the limit, and fail once it is reached
+        assert_eq!(cage.write_syscall(fd, str2cbuf("12345678"), 8), 8);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("abcde"), 5), 2);
+        assert_eq!(
+            cage.write_syscall(fd, str2cbuf("x"), 1),
+            -(Errno::EFBIG as i32)
+        );
+        assert_eq!(cage.write_syscall(fd, str2cbuf(""), 0), 0);
+        assert_eq!(cage.pwrite_syscall(fd, str2cbuf("ABCDE"), 5, 7), 3);
+        assert_eq!(
+            cage.pwrite_syscall(fd, str2cbuf("A"), 1, 10),
+            -(Errno::EFBIG as i32)
+        );
+        let mut statdata = StatData::default();
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 10);
 
+        // Files can't be extended beyond the limit either, but can be shrunk
+        assert_eq!(cage.ftruncate_syscall(fd, 11), -(Errno::EFBIG as i32));
+        assert_eq!(cage.truncate_syscall("/file", 20), -(Errno::EFBIG as i32));
+        assert_eq!(cage.ftruncate_syscall(fd, 4), 0);
+        assert_eq!(cage.truncate_syscall("/file", 10), 0);
+
+        assert_eq!(cage.close_syscall(fd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_lseek_on_epoll() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_statfs_usage() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // Create an Epoll and try to seek from it.
-        let epfd = cage.epoll_create_syscall(1);
-        assert!(epfd > 0);
+        let mut before = FSData::default();
+        assert_eq!(cage.statfs_syscall("/", &mut before), 0);
+        assert_eq!(before.f_blocks, DEFAULT_CAPACITY_BYTES / FS_BLOCKSIZE);
+        assert_eq!(before.f_files, DEFAULT_CAPACITY_INODES);
+        assert!(before.f_bfree <= before.f_blocks);
+        assert!(before.f_ffiles < before.f_files);
 
-        // Attempt to seek from the epoll and check if it returns an error
+        // A new file takes up an inode, and its data takes up blocks
+        let fd = cage.open_syscall("/file", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        let buf = vec![b'a'; 2 * FS_BLOCKSIZE as usize];
         assert_eq!(
-            cage.lseek_syscall(epfd, 10, SEEK_SET),
-            -(Errno::ESPIPE as i32)
+            cage.write_syscall(fd, buf.as_ptr(), buf.len()),
+            buf.len() as i32
         );
+        let mut during = FSData::default();
+        assert_eq!(cage.fstatfs_syscall(fd, &mut during), 0);
+        assert_eq!(during.f_bfree, before.f_bfree - 2);
+        assert_eq!(during.f_bavail, before.f_bavail - 2);
+        assert_eq!(during.f_ffiles, before.f_ffiles - 1);
+
+        // Both are given back once the file is gone
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.unlink_syscall("/file"), 0);
+        let mut after = FSData::default();
+        assert_eq!(cage.statfs_syscall("/", &mut after), 0);
+        assert_eq!(after.f_bfree, before.f_bfree);
+        assert_eq!(after.f_ffiles, before.f_ffiles);
 
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_close_regular_file() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_capacity() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // Create and open a regular file, then close it.
-        let fd = cage.open_syscall("/test_file", O_CREAT | O_RDWR, S_IRWXA);
+        let fd = cage.open_syscall("/file", O_CREAT | O_RDWR, S_IRWXA);
         assert!(fd >= 0);
+        // Leave room for 10 more bytes and no more inodes
+        let (_, used) = filesystem::FS_METADATA.space_in_use();
+        filesystem::FS_METADATA.set_capacity(filesystem::FsSpace {
+            bytes: used.bytes + 10,
+            inodes: used.inodes,
+        });
 
-        // Write sample data to the file.
-        assert_eq!(cage.write_syscall(fd, str2cbuf("hello"), 5), 5);
+        // Writes are cut short when the file system fills up, and fail once it is full
+        assert_eq!(cage.write_syscall(fd, str2cbuf("12345678"), 8), 8);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("abcde"), 5), 2);
+        assert_eq!(
+            cage.write_syscall(fd, str2cbuf("x"), 1),
+            -(Errno::ENOSPC as i32)
+        );
+        // Overwriting data that is already there needs no space
+        assert_eq!(cage.pwrite_syscall(fd, str2cbuf("ABCD"), 4, 0), 4);
+        assert_eq!(
+            cage.pwrite_syscall(fd, str2cbuf("A"), 1, 10),
+            -(Errno::ENOSPC as i32)
+        );
+        assert_eq!(cage.ftruncate_syscall(fd, 11), -(Errno::ENOSPC as i32));
+        // Shrinking the file frees space again
+        assert_eq!(cage.ftruncate_syscall(fd, 5), 0);
+        assert_eq!(cage.ftruncate_syscall(fd, 10), 0);
+        let mut statdata = StatData::default();
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 10);
 
-        // Close the file descriptor, which should succeed.
-        assert_eq!(cage.close_syscall(fd), 0);
+        // No more files can be created either
+        assert_eq!(
+            cage.open_syscall("/file2", O_CREAT | O_RDWR, S_IRWXA),
+            -(Errno::ENOSPC as i32)
+        );
+        assert_eq!(cage.mkdir_syscall("/dir", S_IRWXA), -(Errno::ENOSPC as i32));
+        assert_eq!(
+            cage.symlink_syscall("/file", "/link"),
+            -(Errno::ENOSPC as i32)
+        );
+        assert_eq!(cage.access_syscall("/file2", F_OK), -(Errno::ENOENT as i32));
 
-        // Attempt to close the file descriptor again to ensure it's already closed.
-        // Expect an error for "Invalid File Descriptor".
-        assert_eq!(cage.close_syscall(fd), -(Errno::EBADF as i32));
+        // Removing a file makes room for a new one
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.unlink_syscall("/file"), 0);
+        let fd = cage.open_syscall("/file2", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.close_syscall(fd), 0);
 
+        filesystem::FS_METADATA.set_capacity(filesystem::FsSpace {
+            bytes: DEFAULT_CAPACITY_BYTES,
+            inodes: DEFAULT_CAPACITY_INODES,
+        });
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_close_directory() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_quota() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // Create a directory and open it.
-        let path = "/test_dir";
-        assert_eq!(cage.mkdir_syscall(path, S_IRWXA), 0);
-        let fd = cage.open_syscall(path, O_RDONLY, S_IRWXA);
+        let fd = cage.open_syscall("/file", O_CREAT | O_RDWR, S_IRWXA);
         assert!(fd >= 0);
+        // The files of the default user may take up 10 more bytes and one more inode
+        let (_, used) = filesystem::FS_METADATA.quota_of(DEFAULT_UID);
+        filesystem::FS_METADATA.set_quota(
+            DEFAULT_UID,
+            filesystem::FsSpace {
+                bytes: used.bytes + 10,
+                inodes: used.inodes + 1,
+            },
+        );
+        assert_eq!(
+            filesystem::FS_METADATA.quota_of(DEFAULT_UID).0.bytes,
+            used.bytes + 10
+        );
 
-        // Close the directory file descriptor, which should succeed.
-        assert_eq!(cage.close_syscall(fd), 0);
+        // Filling the gap before the offset has to fit in the quota as well
+        assert_eq!(
+            cage.pwrite_syscall(fd, str2cbuf("x"), 1, 10),
+            -(Errno::EDQUOT as i32)
+        );
+        assert_eq!(cage.pwrite_syscall(fd, str2cbuf("abcdefgh"), 8, 4), 6);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_END), 10);
+        assert_eq!(
+            cage.write_syscall(fd, str2cbuf("x"), 1),
+            -(Errno::EDQUOT as i32)
+        );
+        assert_eq!(cage.ftruncate_syscall(fd, 11), -(Errno::EDQUOT as i32));
 
-        // Attempt to close the file descriptor again to ensure it's already closed.
-        // Expect an error for "Invalid File Descriptor".
-        assert_eq!(cage.close_syscall(fd), -(Errno::EBADF as i32));
+        let fd2 = cage.open_syscall("/file2", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd2 >= 0);
+        assert_eq!(
+            cage.open_syscall("/file3", O_CREAT | O_RDWR, S_IRWXA),
+            -(Errno::EDQUOT as i32)
+        );
+
+        // The superuser is not held to the quota, and the space of a file it
+        // takes over is no longer charged to the default user
+        cage.geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        assert_eq!(cage.ftruncate_syscall(fd, 20), 0);
+        assert_eq!(cage.fchown_syscall(fd2, ROOT_UID, DEFAULT_GID), 0);
+        cage.geteuid
+            .store(DEFAULT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        let fd3 = cage.open_syscall("/file3", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd3 >= 0);
+
+        // Truncating a file on open frees its data
+        assert_eq!(cage.close_syscall(fd), 0);
+        let fd = cage.open_syscall("/file", O_RDWR | O_TRUNC, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("0123456789"), 10), 10);
 
+        for fd in [fd, fd2, fd3] {
+            assert_eq!(cage.close_syscall(fd), 0);
+        }
+        filesystem::FS_METADATA.set_quota(DEFAULT_UID, filesystem::FsSpace::default());
+        assert_eq!(
+            filesystem::FS_METADATA.quota_of(DEFAULT_UID).0,
+            filesystem::FsSpace::default()
+        );
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_close_socket() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_quota_charges_holes() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // Create a socket pair.
-        let mut socketpair = interface::SockPair::default();
+        let fd = cage.open_syscall("/sparse", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        let (_, before) = filesystem::FS_METADATA.quota_of(DEFAULT_UID);
+        let mut fsdata = FSData::default();
+        assert_eq!(cage.fstatfs_syscall(fd, &mut fsdata), 0);
+        let freeblocks = fsdata.f_bfree;
+
+        // Extending a file leaves a hole, which is charged like written data
+        let holesize = 16 * FS_BLOCKSIZE as usize;
+        assert_eq!(cage.ftruncate_syscall(fd, holesize as isize), 0);
+        let (_, used) = filesystem::FS_METADATA.quota_of(DEFAULT_UID);
+        assert_eq!(used.bytes, before.bytes + holesize as u64);
+        assert_eq!(cage.fstatfs_syscall(fd, &mut fsdata), 0);
+        assert_eq!(fsdata.f_bfree, freeblocks - 16);
+
+        // and so is a hole left by writing past the end of the file
         assert_eq!(
-            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
-            0
+            cage.pwrite_syscall(fd, str2cbuf("x"), 1, 2 * holesize as isize),
+            1
         );
+        let (_, used) = filesystem::FS_METADATA.quota_of(DEFAULT_UID);
+        assert_eq!(used.bytes, before.bytes + 2 * holesize as u64 + 1);
 
-        // Close both the socket file descriptors, which should succeed.
-        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
-        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
-
-        // Attempt to close the file descriptors again to ensure they are already
-        // closed. Expect an error for "Invalid File Descriptor".
-        assert_eq!(cage.close_syscall(socketpair.sock1), -(Errno::EBADF as i32));
-        assert_eq!(cage.close_syscall(socketpair.sock2), -(Errno::EBADF as i32));
+        // A quota too small for the hole rejects it, however little is written
+        filesystem::FS_METADATA.set_quota(
+            DEFAULT_UID,
+            filesystem::FsSpace {
+                bytes: used.bytes + 10,
+                inodes: 0,
+            },
+        );
+        assert_eq!(
+            cage.pwrite_syscall(fd, str2cbuf("x"), 1, 3 * holesize as isize),
+            -(Errno::EDQUOT as i32)
+        );
+        assert_eq!(
+            cage.ftruncate_syscall(fd, 3 * holesize as isize),
+            -(Errno::EDQUOT as i32)
+        );
 
+        filesystem::FS_METADATA.set_quota(DEFAULT_UID, filesystem::FsSpace::default());
+        assert_eq!(cage.close_syscall(fd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_close_pipe() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_sendfile() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // Create a pipe.
-        let mut pipe_fds = PipeArray::default();
-        assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
-        let read_fd = pipe_fds.readfd;
-        let write_fd = pipe_fds.writefd;
+        // a source file larger than what is copied at a time
+        let data: Vec<u8> = (0..100000).map(|i| (i % 253) as u8).collect();
+        let infd = cage.open_syscall("/sendfilein", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(infd >= 0);
+        assert_eq!(cage.write_syscall(infd, data.as_ptr(), data.len()), 100000);
+        let outfd = cage.open_syscall("/sendfileout", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(outfd >= 0);
 
-        // Write data to the pipe
-        let write_data = "Testing";
+        // to a file from the file offset, advancing both file offsets
+        assert_eq!(cage.lseek_syscall(infd, 0, SEEK_SET), 0);
+        assert_eq!(cage.sendfile_syscall(outfd, infd, None, 200000), 100000);
+        assert_eq!(cage.lseek_syscall(infd, 0, SEEK_CUR), 100000);
+        assert_eq!(cage.lseek_syscall(outfd, 0, SEEK_CUR), 100000);
+        assert_eq!(cage.sendfile_syscall(outfd, infd, None, 10), 0);
+        let mut buf = vec![0u8; 100000];
         assert_eq!(
-            cage.write_syscall(write_fd, write_data.as_ptr(), write_data.len()),
-            write_data.len() as i32
+            cage.pread_syscall(outfd, buf.as_mut_ptr(), 100000, 0),
+            100000
         );
+        assert_eq!(buf, data);
 
-        // Read the data from the pipe.
-        let mut buf = sizecbuf(7);
+        // from an offset, which is advanced instead of the file offset
+        let mut off: i64 = 99990;
+        assert_eq!(cage.sendfile_syscall(outfd, infd, Some(&mut off), 100), 10);
+        assert_eq!(off, 100000);
+        assert_eq!(cage.lseek_syscall(infd, 0, SEEK_CUR), 100000);
+        let mut statdata = StatData::default();
+        assert_eq!(cage.fstat_syscall(outfd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 100010);
+
+        // to a pipe, which only takes what it has room for
+        let mut pipefds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe2_syscall(&mut pipefds, O_NONBLOCK), 0);
+        let mut off: i64 = 0;
         assert_eq!(
-            cage.read_syscall(read_fd, buf.as_mut_ptr(), buf.len()),
-            write_data.len() as i32
+            cage.sendfile_syscall(pipefds.writefd, infd, Some(&mut off), 100000),
+            PIPE_CAPACITY as i32
         );
-        assert_eq!(cbuf2str(&buf), write_data);
+        assert_eq!(off, PIPE_CAPACITY as i64);
+        assert_eq!(
+            cage.sendfile_syscall(pipefds.writefd, infd, Some(&mut off), 100000),
+            -(Errno::EAGAIN as i32)
+        );
+        assert_eq!(
+            cage.read_syscall(pipefds.readfd, buf.as_mut_ptr(), 100000),
+            PIPE_CAPACITY as i32
+        );
+        assert_eq!(&buf[..PIPE_CAPACITY], &data[..PIPE_CAPACITY]);
 
-        // Close the pipe file descriptors, which should succeed.
-        assert_eq!(cage.close_syscall(read_fd), 0);
-        assert_eq!(cage.close_syscall(write_fd), 0);
+        // to a socket
+        let mut socketpair = interface::SockPair::default();
+        assert_eq!(
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            0
+        );
+        let mut off: i64 = 5;
+        assert_eq!(
+            cage.sendfile_syscall(socketpair.sock1, infd, Some(&mut off), 10),
+            10
+        );
+        assert_eq!(
+            cage.recv_syscall(socketpair.sock2, buf.as_mut_ptr(), 100, 0),
+            10
+        );
+        assert_eq!(&buf[..10], &data[5..15]);
 
-        // Attempt to close the file descriptor again to ensure they are already closed.
-        // Expect an error for "Invalid File Descriptor".
-        assert_eq!(cage.close_syscall(read_fd), -(Errno::EBADF as i32));
-        assert_eq!(cage.close_syscall(write_fd), -(Errno::EBADF as i32));
+        // the source has to be a regular file open for reading, and the destination
+        // open for writing
+        assert_eq!(
+            cage.sendfile_syscall(outfd, pipefds.readfd, None, 10),
+            -(Errno::EINVAL as i32)
+        );
+        let wronlyfd = cage.open_syscall("/sendfilein", O_WRONLY, S_IRWXA);
+        assert!(wronlyfd >= 0);
+        assert_eq!(
+            cage.sendfile_syscall(outfd, wronlyfd, None, 10),
+            -(Errno::EBADF as i32)
+        );
+        let rdonlyfd = cage.open_syscall("/sendfileout", O_RDONLY, S_IRWXA);
+        assert!(rdonlyfd >= 0);
+        assert_eq!(
+            cage.sendfile_syscall(rdonlyfd, infd, Some(&mut 0), 10),
+            -(Errno::EBADF as i32)
+        );
+        // a file can't be appended to, which leaves both offsets alone
+        let appendfd = cage.open_syscall("/sendfileout", O_WRONLY | O_APPEND, S_IRWXA);
+        assert!(appendfd >= 0);
+        let mut off: i64 = 0;
+        assert_eq!(
+            cage.sendfile_syscall(appendfd, infd, Some(&mut off), 10),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(off, 0);
+        assert_eq!(cage.fstat_syscall(outfd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 100010);
+        assert_eq!(
+            cage.sendfile_syscall(outfd, infd, Some(&mut -1), 10),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.sendfile_syscall(outfd, 500, None, 10),
+            -(Errno::EBADF as i32)
+        );
 
+        for fd in [
+            infd,
+            outfd,
+            wronlyfd,
+            rdonlyfd,
+            appendfd,
+            pipefds.readfd,
+            pipefds.writefd,
+            socketpair.sock1,
+            socketpair.sock2,
+        ] {
+            assert_eq!(cage.close_syscall(fd), 0);
+        }
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_close_chardev() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_copy_file_range() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        // Open a character device file.
-        let fd = cage.open_syscall("/dev/zero", O_RDWR, S_IRWXA);
-        assert!(fd >= 0);
+        let data: Vec<u8> = (0..70000).map(|i| (i % 241) as u8).collect();
+        let infd = cage.open_syscall("/copyin", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(infd >= 0);
+        assert_eq!(cage.write_syscall(infd, data.as_ptr(), data.len()), 70000);
+        let outfd = cage.open_syscall("/copyout", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(outfd >= 0);
 
-        // Close the character device file descriptor, which should succeed.
-        assert_eq!(cage.close_syscall(fd), 0);
+        // between the file offsets, which are both advanced
+        assert_eq!(cage.lseek_syscall(infd, 0, SEEK_SET), 0);
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, None, outfd, None, 100000, 0),
+            70000
+        );
+        assert_eq!(cage.lseek_syscall(infd, 0, SEEK_CUR), 70000);
+        assert_eq!(cage.lseek_syscall(outfd, 0, SEEK_CUR), 70000);
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, None, outfd, None, 100, 0),
+            0
+        );
+        let mut buf = vec![0u8; 70000];
+        assert_eq!(cage.pread_syscall(outfd, buf.as_mut_ptr(), 70000, 0), 70000);
+        assert_eq!(buf, data);
 
-        // Attempt to close the file descriptor again to ensure it's already closed.
-        // Expect an error for "Invalid File Descriptor".
-        assert_eq!(cage.close_syscall(fd), -(Errno::EBADF as i32));
+        // between offsets, leaving the file offsets alone, and past the end of the
+        // destination, which leaves a gap of zeroes
+        let mut off_in: i64 = 10;
+        let mut off_out: i64 = 70010;
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, Some(&mut off_in), outfd, Some(&mut off_out), 5, 0),
+            5
+        );
+        assert_eq!((off_in, off_out), (15, 70015));
+        assert_eq!(cage.lseek_syscall(outfd, 0, SEEK_CUR), 70000);
+        let mut buf = [0u8; 15];
+        assert_eq!(cage.pread_syscall(outfd, buf.as_mut_ptr(), 15, 70000), 15);
+        assert_eq!(&buf[..10], &[0u8; 10]);
+        assert_eq!(&buf[10..], &data[10..15]);
+
+        // within the same file, as long as the ranges don't overlap
+        let mut off_in: i64 = 0;
+        let mut off_out: i64 = 100;
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, Some(&mut off_in), infd, Some(&mut off_out), 100, 0),
+            100
+        );
+        let mut off_in: i64 = 0;
+        let mut off_out: i64 = 50;
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, Some(&mut off_in), infd, Some(&mut off_out), 100, 0),
+            -(Errno::EINVAL as i32)
+        );
+        let mut buf = [0u8; 100];
+        assert_eq!(cage.pread_syscall(infd, buf.as_mut_ptr(), 100, 100), 100);
+        assert_eq!(&buf[..], &data[..100]);
+
+        // only regular files open in the right direction, without flags
+        assert_eq!(cage.mkdir_syscall("/copydir", S_IRWXA), 0);
+        let dirfd = cage.open_syscall("/copydir", O_RDONLY, S_IRWXA);
+        assert!(dirfd >= 0);
+        assert_eq!(
+            cage.copy_file_range_syscall(dirfd, None, outfd, None, 10, 0),
+            -(Errno::EISDIR as i32)
+        );
+        let mut pipefds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, None, pipefds.writefd, None, 10, 0),
+            -(Errno::EINVAL as i32)
+        );
+        let rdonlyfd = cage.open_syscall("/copyout", O_RDONLY, S_IRWXA);
+        assert!(rdonlyfd >= 0);
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, None, rdonlyfd, None, 10, 0),
+            -(Errno::EBADF as i32)
+        );
+        let appendfd = cage.open_syscall("/copyout", O_WRONLY | O_APPEND, S_IRWXA);
+        assert!(appendfd >= 0);
+        let mut off_in: i64 = 0;
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, Some(&mut off_in), appendfd, None, 10, 0),
+            -(Errno::EBADF as i32)
+        );
+        assert_eq!(off_in, 0);
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, None, outfd, None, 10, 1),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, Some(&mut -1), outfd, None, 10, 0),
+            -(Errno::EINVAL as i32)
+        );
+
+        // the copy is cut short by the file size limit
+        let limit = Rlimit {
+            rlim_cur: 70020,
+            rlim_max: RLIM_INFINITY,
+        };
+        assert_eq!(cage.setrlimit_syscall(RLIMIT_FSIZE, &limit), 0);
+        let mut off_out: i64 = 70015;
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, Some(&mut 0), outfd, Some(&mut off_out), 100, 0),
+            5
+        );
+        assert_eq!(
+            cage.copy_file_range_syscall(infd, Some(&mut 0), outfd, Some(&mut off_out), 100, 0),
+            -(Errno::EFBIG as i32)
+        );
 
+        for fd in [
+            infd,
+            outfd,
+            dirfd,
+            rdonlyfd,
+            appendfd,
+            pipefds.readfd,
+            pipefds.writefd,
+        ] {
+            assert_eq!(cage.close_syscall(fd), 0);
+        }
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_fs_stat_syscall_tests() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_fallocate() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
+
+        let fd = cage.open_syscall("/fallocatefile", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        let data = [7u8; 8192];
+        assert_eq!(cage.write_syscall(fd, data.as_ptr(), 8192), 8192);
         let mut statdata = StatData::default();
 
-        // test out whether an error is output for a non existent file path
-        // (ENOENT[-2])
+        // preallocating past the end grows the file with zeroes, unless the size
+        // is to be kept
+        assert_eq!(cage.fallocate_syscall(fd, 0, 4096, 12288), 0);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 16384);
+        let mut buf = [1u8; 8192];
+        assert_eq!(cage.pread_syscall(fd, buf.as_mut_ptr(), 8192, 8192), 8192);
+        assert_eq!(buf, [0u8; 8192]);
         assert_eq!(
-            cage.stat_syscall("non_existent_file_path", &mut statdata),
-            syscall_error(Errno::ENOENT, "stat", "test_failure")
+            cage.fallocate_syscall(fd, FALLOC_FL_KEEP_SIZE, 16384, 65536),
+            0
         );
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 16384);
 
-        // setting up directory inode object '/tmp' for testing stat_syscall with a
-        // directory
-        let dir_path = "/tmp"; // since setup already initializes tmp, assuming it is there
-        assert_eq!(cage.stat_syscall(dir_path, &mut statdata), 0);
-
-        // setting up generic inode object "/tmp/generic" for testing stat_syscall with
-        // a generic file
-        let generic_path = "/tmp/generic";
-        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
-        assert!(creat_fd > 0);
-        assert_eq!(cage.stat_syscall(generic_path, &mut statdata), 0);
+        // a punched hole reads as zeroes and can be found by lseek, while the
+        // size stays the same
+        assert_eq!(
+            cage.fallocate_syscall(fd, FALLOC_FL_PUNCH_HOLE | FALLOC_FL_KEEP_SIZE, 0, 4096),
+            0
+        );
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 16384);
+        let mut buf = [1u8; 8192];
+        assert_eq!(cage.pread_syscall(fd, buf.as_mut_ptr(), 8192, 0), 8192);
+        assert_eq!(&buf[..4096], &[0u8; 4096]);
+        assert_eq!(&buf[4096..], &data[4096..]);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_DATA), 4096);
 
-        // setting up character device inode object "/chardev" for testing stat_syscall
-        // with a character device
-        let dev = makedev(&DevNo { major: 1, minor: 3 });
-        let chardev_path = "/chardev";
+        // zeroing a range grows the file like preallocating does
         assert_eq!(
-            cage.mknod_syscall(chardev_path, S_IRWXA | S_IFCHR as u32, dev),
+            cage.fallocate_syscall(fd, FALLOC_FL_ZERO_RANGE, 4096, 16384),
             0
         );
-        assert_eq!(cage.stat_syscall(chardev_path, &mut statdata), 0);
+        assert_eq!(cage.fstat_syscall(fd, &mut statdata), 0);
+        assert_eq!(statdata.st_size, 20480);
+        assert_eq!(cage.pread_syscall(fd, buf.as_mut_ptr(), 8192, 4096), 8192);
+        assert_eq!(buf, [0u8; 8192]);
 
-        // setting up socket inode object with path "/socket.sock"  for testing
-        // stat_syscall with a socket
-        let socketfile_path = "/socket.sock";
-        let socketfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
-        assert!(socketfd > 0);
-        let sockaddr = interface::new_sockaddr_unix(AF_UNIX as u16, socketfile_path.as_bytes());
-        let socket = interface::GenSockaddr::Unix(sockaddr);
-        assert_eq!(cage.bind_syscall(socketfd, &socket), 0);
+        // unsupported modes and invalid ranges
+        assert_eq!(
+            cage.fallocate_syscall(fd, FALLOC_FL_PUNCH_HOLE, 0, 4096),
+            -(Errno::EOPNOTSUPP as i32)
+        );
+        assert_eq!(
+            cage.fallocate_syscall(fd, FALLOC_FL_PUNCH_HOLE | FALLOC_FL_ZERO_RANGE, 0, 4096),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.fallocate_syscall(fd, 0x08, 0, 4096),
+            -(Errno::EOPNOTSUPP as i32)
+        );
+        assert_eq!(cage.fallocate_syscall(fd, 0, 0, 0), -(Errno::EINVAL as i32));
+        assert_eq!(
+            cage.fallocate_syscall(fd, 0, -1, 10),
+            -(Errno::EINVAL as i32)
+        );
 
-        // stat_syscall test here
-        assert_eq!(cage.stat_syscall(socketfile_path, &mut statdata), 0);
+        // the file size limit applies to growing the file
+        let limit = Rlimit {
+            rlim_cur: 32768,
+            rlim_max: RLIM_INFINITY,
+        };
+        assert_eq!(cage.setrlimit_syscall(RLIMIT_FSIZE, &limit), 0);
+        assert_eq!(
+            cage.fallocate_syscall(fd, 0, 0, 32769),
+            -(Errno::EFBIG as i32)
+        );
+        assert_eq!(cage.fallocate_syscall(fd, FALLOC_FL_KEEP_SIZE, 0, 32769), 0);
 
-        // socket teardown
-        assert_eq!(cage.close_syscall(socketfd), 0);
-        cage.unlink_syscall(socketfile_path);
+        // only regular files open for writing
+        let rdonlyfd = cage.open_syscall("/fallocatefile", O_RDONLY, S_IRWXA);
+        assert!(rdonlyfd >= 0);
+        assert_eq!(
+            cage.fallocate_syscall(rdonlyfd, 0, 0, 4096),
+            -(Errno::EBADF as i32)
+        );
+        assert_eq!(cage.mkdir_syscall("/fallocatedir", S_IRWXA), 0);
+        let dirfd = cage.open_syscall("/fallocatedir", O_RDONLY, S_IRWXA);
+        assert!(dirfd >= 0);
+        // directories can't be opened for writing
+        assert_eq!(
+            cage.fallocate_syscall(dirfd, 0, 0, 4096),
+            -(Errno::EBADF as i32)
+        );
+        let mut pipefds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+        assert_eq!(
+            cage.fallocate_syscall(pipefds.writefd, 0, 0, 4096),
+            -(Errno::ESPIPE as i32)
+        );
 
+        for fd in [fd, rdonlyfd, dirfd, pipefds.readfd, pipefds.writefd] {
+            assert_eq!(cage.close_syscall(fd), 0);
+        }
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
-        return;
     }
 
     #[test]
-    pub fn ut_lind_fs_fstat_syscall_tests() {
+    pub fn ut_lind_fs_lseek_data_and_hole() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
 
-        let mut statdata = StatData::default();
+        let fd = cage.open_syscall("/sparsefile", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        let data = [7u8; 4096];
+        assert_eq!(cage.write_syscall(fd, data.as_ptr(), 4096), 4096);
+        // writing past the end leaves a hole instead of writing out the zeroes
+        assert_eq!(cage.pwrite_syscall(fd, data.as_ptr(), 4096, 1 << 20), 4096);
+
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_DATA), 0);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_HOLE), 4096);
+        assert_eq!(cage.lseek_syscall(fd, 4096, SEEK_DATA), 1 << 20);
+        // the end of the file is a hole as well
+        assert_eq!(cage.lseek_syscall(fd, 1 << 20, SEEK_HOLE), (1 << 20) + 4096);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_CUR), (1 << 20) + 4096);
+
+        // truncating the file longer adds a hole at its end
+        assert_eq!(cage.ftruncate_syscall(fd, 2 << 20), 0);
+        assert_eq!(
+            cage.lseek_syscall(fd, (1 << 20) + 4096, SEEK_DATA),
+            -(Errno::ENXIO as i32)
+        );
+        assert_eq!(cage.lseek_syscall(fd, 5000, SEEK_HOLE), 5000);
 
-        // test out whether an error is output for a non existent fd (1000)
-        // (EBADF[-9])
-        let non_existent_fd = 1000;
+        // there is nothing at or past the end of the file
         assert_eq!(
-            cage.fstat_syscall(non_existent_fd, &mut statdata),
-            syscall_error(Errno::EBADF, "fstat", "test_failure")
+            cage.lseek_syscall(fd, 2 << 20, SEEK_DATA),
+            -(Errno::ENXIO as i32)
         );
-
-        // setting up directory inode object '/tmp' for testing fstat_syscall with a
-        // directory
-        let dir_path = "/tmp"; // since setup already initializes tmp, assuming it is there
-        let dir_fd = cage.open_syscall(dir_path, O_RDONLY | O_DIRECTORY, S_IRWXA);
-        assert!(dir_fd > 0);
-        assert_eq!(cage.fstat_syscall(dir_fd, &mut statdata), 0);
-        assert_eq!(cage.close_syscall(dir_fd), 0);
-
-        // setting up generic inode object "/tmp/generic" for testing fstat_syscall with
-        // a generic file
-        let generic_path = "/tmp/generic";
-        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
-        assert!(creat_fd > 0);
-        assert_eq!(cage.fstat_syscall(creat_fd, &mut statdata), 0);
-
-        // setting up character device inode object "/chardev" for testing fstat_syscall
-        // with a character device
-        let dev = makedev(&DevNo { major: 1, minor: 3 });
-        let chardev_path = "/chardev";
         assert_eq!(
-            cage.mknod_syscall(chardev_path, S_IRWXA | S_IFCHR as u32, dev),
-            0
+            cage.lseek_syscall(fd, 2 << 20, SEEK_HOLE),
+            -(Errno::ENXIO as i32)
         );
-        let chardev_fd = cage.open_syscall(chardev_path, O_RDONLY, S_IRWXA);
-        assert!(chardev_fd > 0);
-        assert_eq!(cage.fstat_syscall(chardev_fd, &mut statdata), 0);
-        assert_eq!(cage.close_syscall(chardev_fd), 0);
-
-        // setting up socket inode object with path "/socket.sock" for testing
-        // fstat_syscall with a socket
-        let socketfile_path = "/socket.sock";
-
-        let socketfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
-        assert!(socketfd > 0);
-
-        let sockaddr = interface::new_sockaddr_unix(AF_UNIX as u16, socketfile_path.as_bytes());
-        let socket = interface::GenSockaddr::Unix(sockaddr);
-        assert_eq!(cage.bind_syscall(socketfd, &socket), 0);
-
-        // Errno::EOPNOTSUPP : -95
         assert_eq!(
-            cage.fstat_syscall(socketfd, &mut statdata),
-            syscall_error(Errno::EOPNOTSUPP, "fstat", "test_failure")
+            cage.lseek_syscall(fd, -1, SEEK_HOLE),
+            -(Errno::ENXIO as i32)
         );
 
-        // Clean up
-        assert_eq!(cage.close_syscall(socketfd), 0);
-
-        cage.unlink_syscall(socketfile_path);
+        // the holes read as zeroes
+        let mut buf = [1u8; 4096];
+        assert_eq!(cage.pread_syscall(fd, buf.as_mut_ptr(), 4096, 8192), 4096);
+        assert_eq!(buf, [0u8; 4096]);
 
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
-        return;
     }
 
     #[test]
-    pub fn ut_lind_fs_statfs_syscall_tests() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_posix_fadvise() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
-        let mut fsdata = FSData::default();
 
-        // test out whether an error is output for a non existent file path
-        // (ENOENT[-2])
+        let fd = cage.open_syscall("/fadvisefile", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        let data = [7u8; 4096];
+        assert_eq!(cage.write_syscall(fd, data.as_ptr(), 4096), 4096);
+
+        for advice in [
+            POSIX_FADV_NORMAL,
+            POSIX_FADV_RANDOM,
+            POSIX_FADV_SEQUENTIAL,
+            POSIX_FADV_WILLNEED,
+            POSIX_FADV_DONTNEED,
+            POSIX_FADV_NOREUSE,
+        ] {
+            assert_eq!(cage.posix_fadvise_syscall(fd, 0, 0, advice), 0);
+        }
+        // the advice doesn't change the data
+        let mut buf = [0u8; 4096];
+        assert_eq!(cage.pread_syscall(fd, buf.as_mut_ptr(), 4096, 0), 4096);
+        assert_eq!(buf, data);
+
         assert_eq!(
-            cage.statfs_syscall("non_existent_file_path", &mut fsdata),
-            syscall_error(Errno::ENOENT, "statfs", "test_failure")
+            cage.posix_fadvise_syscall(fd, 0, 0, 6),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.posix_fadvise_syscall(fd, 0, -1, POSIX_FADV_NORMAL),
+            -(Errno::EINVAL as i32)
+        );
+        let mut pipefds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+        assert_eq!(
+            cage.posix_fadvise_syscall(pipefds.readfd, 0, 0, POSIX_FADV_NORMAL),
+            -(Errno::ESPIPE as i32)
         );
 
-        // setting up inode object "/tmp/generic" for testing statfs_syscall
-        let generic_path = "/tmp/generic";
-        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
-        assert!(creat_fd > 0);
-        assert_eq!(cage.statfs_syscall(generic_path, &mut fsdata), 0);
-
+        for fd in [fd, pipefds.readfd, pipefds.writefd] {
+            assert_eq!(cage.close_syscall(fd), 0);
+        }
+        assert_eq!(
+            cage.posix_fadvise_syscall(fd, 0, 0, POSIX_FADV_NORMAL),
+            -(Errno::EBADF as i32)
+        );
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
-        return;
     }
 
     #[test]
-    pub fn ut_lind_fs_fstatfs_syscall_tests() {
+    pub fn ut_lind_fs_openat_mkdirat_readlinkat() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
+        let mut statdata = StatData::default();
 
-        let mut fsdata = FSData::default();
+        assert_eq!(cage.mkdir_syscall("/atdir", S_IRWXA), 0);
+        let dirfd = cage.open_syscall("/atdir", O_RDONLY, 0);
+        assert!(dirfd >= 0);
 
-        // test out whether an error is output for a non existent fd (1000)
-        // (ENOENT[-2])
-        let non_existent_fd = 1000;
+        // relative paths are resolved against the directory fd
+        assert_eq!(cage.mkdirat_syscall(dirfd, "sub", S_IRWXA), 0);
+        let fd = cage.openat_syscall(dirfd, "sub/file", O_CREAT | O_EXCL | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.stat_syscall("/atdir/sub/file", &mut statdata), 0);
+        assert_eq!(cage.symlink_syscall("sub/file", "/atdir/link"), 0);
+        let mut buf = [0u8; 16];
         assert_eq!(
-            cage.fstatfs_syscall(non_existent_fd, &mut fsdata),
-            syscall_error(Errno::EBADF, "fstatfs", "test_failure")
+            cage.readlinkat_syscall(dirfd, "link", buf.as_mut_ptr(), buf.len()),
+            8
         );
+        assert_eq!(&buf[..8], b"sub/file");
 
-        // setting up generic inode object "/tmp/generic" for testing fstat_syscall with
-        // a generic file
-        let generic_path = "/tmp/generic";
-        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
-        assert!(creat_fd > 0);
-        assert_eq!(cage.fstatfs_syscall(creat_fd, &mut fsdata), 0);
-
-        // setting up socket inode object with path "/socket.sock" for testing
-        // fstat_syscall with a socket
-        let socketfile_path = "/socket.sock";
-
-        let socketfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
-        assert!(socketfd > 0);
-
-        let sockaddr = interface::new_sockaddr_unix(AF_UNIX as u16, socketfile_path.as_bytes());
-        let socket = interface::GenSockaddr::Unix(sockaddr);
-        assert_eq!(cage.bind_syscall(socketfd, &socket), 0);
+        // against the current working directory for AT_FDCWD, while absolute
+        // paths ignore the directory fd entirely
+        assert_eq!(cage.chdir_syscall("/atdir/sub"), 0);
+        let fd2 = cage.openat_syscall(AT_FDCWD, "file", O_RDONLY, 0);
+        assert!(fd2 >= 0);
+        let fd3 = cage.openat_syscall(fd, "/atdir/sub/file", O_RDONLY, 0);
+        assert!(fd3 >= 0);
 
-        // Errno::EBADF : -9
+        // a relative path needs a valid directory fd
         assert_eq!(
-            cage.fstatfs_syscall(socketfd, &mut fsdata),
-            syscall_error(Errno::EBADF, "fstatfs", "test_failure")
+            cage.openat_syscall(fd, "file", O_RDONLY, 0),
+            -(Errno::ENOTDIR as i32)
+        );
+        assert_eq!(
+            cage.mkdirat_syscall(100, "dir", S_IRWXA),
+            -(Errno::EBADF as i32)
+        );
+        assert_eq!(
+            cage.openat_syscall(dirfd, "", O_RDONLY, 0),
+            -(Errno::ENOENT as i32)
         );
 
-        // Clean up
-        assert_eq!(cage.close_syscall(socketfd), 0);
-
-        cage.unlink_syscall(socketfile_path);
-
+        for fd in [dirfd, fd, fd2, fd3] {
+            assert_eq!(cage.close_syscall(fd), 0);
+        }
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
-        return;
     }
 
     #[test]
-    pub fn ut_lind_fs_access_syscall_tests() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_unlinkat_linkat() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
+        let mut statdata = StatData::default();
 
-        // test out whether an error is output for a non existent file path
-        // (ENOENT[-2])
+        assert_eq!(cage.mkdir_syscall("/linkatdir", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall("/linkatdir/sub", S_IRWXA), 0);
+        let dirfd = cage.open_syscall("/linkatdir", O_RDONLY, 0);
+        assert!(dirfd >= 0);
+        let fd = cage.open_syscall("/linkatdir/file", O_CREAT | O_EXCL | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.symlink_syscall("file", "/linkatdir/link"), 0);
+
+        // a symbolic link is linked itself, unless AT_SYMLINK_FOLLOW is given
         assert_eq!(
-            cage.access_syscall("non_existent_file_path", S_IRWXA),
-            syscall_error(Errno::ENOENT, "access", "test_failure")
+            cage.linkat_syscall(dirfd, "link", dirfd, "sub/samelink", 0),
+            0
+        );
+        assert_eq!(
+            cage.lstat_syscall("/linkatdir/sub/samelink", &mut statdata),
+            0
+        );
+        assert!(is_lnk(statdata.st_mode));
+        assert_eq!(
+            cage.linkat_syscall(dirfd, "link", dirfd, "sub/samefile", AT_SYMLINK_FOLLOW),
+            0
+        );
+        assert_eq!(
+            cage.lstat_syscall("/linkatdir/sub/samefile", &mut statdata),
+            0
+        );
+        assert!(is_reg(statdata.st_mode));
+        assert_eq!(statdata.st_nlink, 2);
+
+        // AT_EMPTY_PATH links the file of the fd, but only for the superuser
+        assert_eq!(
+            cage.linkat_syscall(fd, "", dirfd, "byfd", AT_EMPTY_PATH),
+            -(Errno::ENOENT as i32)
+        );
+        cage.geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        assert_eq!(cage.linkat_syscall(fd, "", dirfd, "byfd", AT_EMPTY_PATH), 0);
+        assert_eq!(cage.stat_syscall("/linkatdir/byfd", &mut statdata), 0);
+        assert_eq!(statdata.st_nlink, 3);
+        assert_eq!(
+            cage.linkat_syscall(fd, "", dirfd, "byfd2", 0),
+            -(Errno::ENOENT as i32)
+        );
+        assert_eq!(
+            cage.linkat_syscall(dirfd, "file", dirfd, "other", 0x8),
+            -(Errno::EINVAL as i32)
         );
 
-        // setting up inode object "/tmp/generic" for testing statfs_syscall
-        let generic_path = "/tmp/generic";
-        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
-        assert!(creat_fd > 0);
-        // set mode to some value
-        assert_eq!(cage.access_syscall(generic_path, S_IRWXA), 0);
+        // unlinkat removes files, and directories only with AT_REMOVEDIR
+        assert_eq!(cage.unlinkat_syscall(dirfd, "byfd", 0), 0);
+        assert_eq!(cage.unlinkat_syscall(dirfd, "sub/samefile", 0), 0);
+        assert_eq!(cage.unlinkat_syscall(dirfd, "sub/samelink", 0), 0);
+        assert_eq!(cage.stat_syscall("/linkatdir/file", &mut statdata), 0);
+        assert_eq!(statdata.st_nlink, 1);
+        assert_eq!(
+            cage.unlinkat_syscall(dirfd, "sub", 0),
+            -(Errno::EISDIR as i32)
+        );
+        assert_eq!(
+            cage.unlinkat_syscall(dirfd, "file", AT_REMOVEDIR),
+            -(Errno::ENOTDIR as i32)
+        );
+        assert_eq!(cage.unlinkat_syscall(dirfd, "sub", AT_REMOVEDIR), 0);
+        assert_eq!(
+            cage.stat_syscall("/linkatdir/sub", &mut statdata),
+            -(Errno::ENOENT as i32)
+        );
+        assert_eq!(
+            cage.unlinkat_syscall(dirfd, "file", 0x1),
+            -(Errno::EINVAL as i32)
+        );
 
-        // change mode
-        assert_eq!(cage.chmod_syscall(generic_path, S_IRGRP), 0);
+        // a file that was unlinked while open can't be linked back in
+        assert_eq!(cage.unlinkat_syscall(dirfd, "file", 0), 0);
         assert_eq!(
-            cage.access_syscall(generic_path, S_IRWXA),
-            syscall_error(Errno::EACCES, "access", "incorrect access")
+            cage.linkat_syscall(fd, "", dirfd, "revived", AT_EMPTY_PATH),
+            -(Errno::ENOENT as i32)
         );
 
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.close_syscall(dirfd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
-        return;
     }
 
     #[test]
-    pub fn ut_lind_fs_rename_syscall_tests() {
-        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_fstatat_faccessat_fchmodat() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
+        let mut statdata = StatData::default();
+        let mut fdstatdata = StatData::default();
 
-        // test out whether an error is output for a non existent file path
-        // (EEXIST[-17])
+        assert_eq!(cage.mkdir_syscall("/statatdir", S_IRWXA), 0);
+        let dirfd = cage.open_syscall("/statatdir", O_RDONLY, 0);
+        assert!(dirfd >= 0);
+        let fd = cage.open_syscall("/statatdir/file", O_CREAT | O_EXCL | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.symlink_syscall("file", "/statatdir/link"), 0);
+
+        // symbolic links are followed unless AT_SYMLINK_NOFOLLOW is given
+        assert_eq!(cage.fstatat_syscall(dirfd, "link", &mut statdata, 0), 0);
+        assert!(is_reg(statdata.st_mode));
         assert_eq!(
-            cage.rename_syscall("non_existent_file_path", "non-existent-target"),
-            syscall_error(Errno::EEXIST, "rename", "test_failure")
+            cage.fstatat_syscall(dirfd, "link", &mut statdata, AT_SYMLINK_NOFOLLOW),
+            0
         );
+        assert!(is_lnk(statdata.st_mode));
 
-        // empty inputs for rename
+        // an empty path with AT_EMPTY_PATH is the fd itself, or the working
+        // directory for AT_FDCWD
         assert_eq!(
-            cage.rename_syscall("", ""),
-            syscall_error(Errno::ENOENT, "rename", "empty inputs")
+            cage.fstatat_syscall(fd, "", &mut statdata, AT_EMPTY_PATH),
+            0
         );
-
-        // root path provided
+        assert_eq!(cage.fstat_syscall(fd, &mut fdstatdata), 0);
+        assert_eq!(statdata.st_ino, fdstatdata.st_ino);
+        assert_eq!(cage.chdir_syscall("/statatdir"), 0);
         assert_eq!(
-            cage.rename_syscall("/", "rename-to-this"),
-            syscall_error(Errno::EBUSY, "rename", "cant deal with root")
+            cage.fstatat_syscall(AT_FDCWD, "", &mut statdata, AT_EMPTY_PATH),
+            0
         );
-
-        // setting up inode object "/tmp/generic" for testing statfs_syscall
-        let generic_path = "/tmp/generic";
-        let creat_fd = cage.creat_syscall(generic_path, S_IRWXA);
-        assert!(creat_fd > 0);
-
-        // try to rename to different parent
+        assert_eq!(cage.fstat_syscall(dirfd, &mut fdstatdata), 0);
+        assert_eq!(statdata.st_ino, fdstatdata.st_ino);
         assert_eq!(
-            cage.rename_syscall("/tmp", generic_path),
-            syscall_error(Errno::EOPNOTSUPP, "rename", "cant move directories")
+            cage.fstatat_syscall(fd, "", &mut statdata, 0),
+            -(Errno::ENOENT as i32)
+        );
+        assert_eq!(
+            cage.fstatat_syscall(100, "", &mut statdata, AT_EMPTY_PATH),
+            -(Errno::EBADF as i32)
+        );
+        assert_eq!(
+            cage.fstatat_syscall(dirfd, "file", &mut statdata, 0x2),
+            -(Errno::EINVAL as i32)
         );
 
-        // normal rename
-        assert_eq!(cage.rename_syscall("/tmp/generic", "/tmp/generic1"), 0);
+        // fchmodat changes the mode of the file a link refers to, never the link
+        assert_eq!(
+            cage.fchmodat_syscall(dirfd, "link", S_IRUSR | S_IWUSR, 0),
+            0
+        );
+        assert_eq!(cage.fstatat_syscall(dirfd, "file", &mut statdata, 0), 0);
+        assert_eq!(statdata.st_mode & 0o777, S_IRUSR | S_IWUSR);
+        assert_eq!(
+            cage.fchmodat_syscall(dirfd, "link", S_IRWXA, AT_SYMLINK_NOFOLLOW),
+            -(Errno::EOPNOTSUPP as i32)
+        );
 
-        // clean up
-        cage.close_syscall(creat_fd);
+        // faccessat checks the real ids, or the effective ones with AT_EACCESS
+        assert_eq!(cage.faccessat_syscall(dirfd, "file", R_OK | W_OK, 0), 0);
+        assert_eq!(
+            cage.faccessat_syscall(dirfd, "link", X_OK, 0),
+            -(Errno::EACCES as i32)
+        );
+        // the link itself allows everything
+        assert_eq!(
+            cage.faccessat_syscall(dirfd, "link", X_OK, AT_SYMLINK_NOFOLLOW),
+            0
+        );
+        cage.geteuid
+            .store(2000, interface::RustAtomicOrdering::Relaxed);
+        assert_eq!(cage.faccessat_syscall(dirfd, "file", R_OK, 0), 0);
+        assert_eq!(
+            cage.faccessat_syscall(dirfd, "file", R_OK, AT_EACCESS),
+            -(Errno::EACCES as i32)
+        );
+        assert_eq!(
+            cage.faccessat_syscall(dirfd, "file", R_OK, 0x4),
+            -(Errno::EINVAL as i32)
+        );
+        cage.geteuid
+            .store(DEFAULT_UID as i32, interface::RustAtomicOrdering::Relaxed);
 
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.close_syscall(dirfd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
-        return;
     }
 
     #[test]
-    fn ut_lind_fs_writev_socketpair() {
+    pub fn ut_lind_fs_renameat2() {
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
         let cage = interface::cagetable_getref(1);
+        let mut statdata = StatData::default();
 
-        let mut socketpair = interface::SockPair::default();
+        assert_eq!(cage.mkdir_syscall("/renameat", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall("/renameat/a", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall("/renameat/b", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall("/renameat/a/dir", S_IRWXA), 0);
+        for (path, data) in [("/renameat/a/one", b"one"), ("/renameat/b/two", b"two")] {
+            let fd = cage.open_syscall(path, O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
+            assert!(fd >= 0);
+            assert_eq!(cage.write_syscall(fd, data.as_ptr(), 3), 3);
+            assert_eq!(cage.close_syscall(fd), 0);
+        }
+        let adirfd = cage.open_syscall("/renameat/a", O_RDONLY, 0);
+        assert!(adirfd >= 0);
+        let bdirfd = cage.open_syscall("/renameat/b", O_RDONLY, 0);
+        assert!(bdirfd >= 0);
+        let read_file = |path: &str| {
+            let fd = cage.open_syscall(path, O_RDONLY, 0);
+            assert!(fd >= 0);
+            let mut buf = [0u8; 3];
+            assert_eq!(cage.read_syscall(fd, buf.as_mut_ptr(), 3), 3);
+            assert_eq!(cage.close_syscall(fd), 0);
+            buf
+        };
+
+        // RENAME_NOREPLACE refuses to replace an existing file
         assert_eq!(
-            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            cage.renameat2_syscall(adirfd, "one", bdirfd, "two", RENAME_NOREPLACE),
+            -(Errno::EEXIST as i32)
+        );
+        assert_eq!(
+            cage.renameat2_syscall(adirfd, "one", bdirfd, "three", RENAME_NOREPLACE),
             0
         );
+        assert_eq!(&read_file("/renameat/b/three"), b"one");
 
-        // Prepare data to write.
-        let data = b"Hello, world!";
-        let iovec = interface::IovecStruct {
-            iov_base: data.as_ptr() as *mut libc::c_void,
-            iov_len: data.len(),
-        };
-        // Write the data to the first socket using writev_syscall.
-        let bytes_written = cage.writev_syscall(socketpair.sock1, &iovec, 1);
-        assert_eq!(bytes_written, data.len() as i32);
-        // Read the data from the second socket.
-        let mut buffer = vec![0u8; data.len()];
-        let bytes_read = cage.recv_syscall(socketpair.sock2, buffer.as_mut_ptr(), buffer.len(), 0);
-        assert_eq!(bytes_read, data.len() as i32);
-        // Verify that the data received from the second socket matches the original
-        // data.
-        assert_eq!(buffer, data);
-        // Close both sockets.
-        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
-        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+        // RENAME_EXCHANGE swaps two files, which may be of different types
+        assert_eq!(
+            cage.renameat2_syscall(bdirfd, "three", bdirfd, "two", RENAME_EXCHANGE),
+            0
+        );
+        assert_eq!(&read_file("/renameat/b/two"), b"one");
+        assert_eq!(&read_file("/renameat/b/three"), b"two");
+        assert_eq!(
+            cage.renameat2_syscall(adirfd, "dir", bdirfd, "two", RENAME_EXCHANGE),
+            0
+        );
+        assert_eq!(&read_file("/renameat/a/dir"), b"one");
+        assert_eq!(cage.stat_syscall("/renameat/b/two", &mut statdata), 0);
+        assert!(is_dir(statdata.st_mode));
+        // the swapped directory's .. now leads to its new parent
+        assert_eq!(cage.chdir_syscall("/renameat/b/two"), 0);
+        let fd = cage.openat_syscall(AT_FDCWD, "../three", O_RDONLY, 0);
+        assert!(fd >= 0);
+        assert_eq!(cage.close_syscall(fd), 0);
+
+        // both files have to exist, and neither may contain the other
+        assert_eq!(
+            cage.renameat2_syscall(adirfd, "dir", bdirfd, "missing", RENAME_EXCHANGE),
+            -(Errno::ENOENT as i32)
+        );
+        assert_eq!(
+            cage.renameat2_syscall(
+                AT_FDCWD,
+                "/renameat/b",
+                AT_FDCWD,
+                "/renameat/b/two",
+                RENAME_EXCHANGE
+            ),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.renameat2_syscall(
+                adirfd,
+                "dir",
+                bdirfd,
+                "three",
+                RENAME_NOREPLACE | RENAME_EXCHANGE
+            ),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.renameat2_syscall(adirfd, "dir", bdirfd, "three", 0x10),
+            -(Errno::EINVAL as i32)
+        );
+
+        // without flags it is a plain rename, replacing the target
+        assert_eq!(cage.renameat2_syscall(adirfd, "dir", bdirfd, "three", 0), 0);
+        assert_eq!(&read_file("/renameat/b/three"), b"one");
+        assert_eq!(
+            cage.stat_syscall("/renameat/a/dir", &mut statdata),
+            -(Errno::ENOENT as i32)
+        );
 
+        assert_eq!(cage.close_syscall(adirfd), 0);
+        assert_eq!(cage.close_syscall(bdirfd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    fn ut_lind_fs_writev_pipe() {
-        // Acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+    pub fn ut_lind_fs_renameat2_exchange_beneath_symlink() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
         let cage = interface::cagetable_getref(1);
-    
-        // Create a pipe
-        let mut pipe_fds = PipeArray::default();
-        assert_eq!(cage.pipe_syscall(&mut pipe_fds), 0);
-        let read_fd = pipe_fds.readfd;
-        let write_fd = pipe_fds.writefd;
-    
-        // Prepare multiple data segments to be written using an iovec structure
-        let data1 = b"Hello, ";
-        let data2 = b"pipe!";
-        let data3 = b" Testing writev.";
-    
-        let iovec = [
-            interface::IovecStruct {
-                iov_base: data1.as_ptr() as *mut libc::c_void,
-                iov_len: data1.len(),
-            },
-            interface::IovecStruct {
-                iov_base: data2.as_ptr() as *mut libc::c_void,
-                iov_len: data2.len(),
-            },
-            interface::IovecStruct {
-                iov_base: data3.as_ptr() as *mut libc::c_void,
-                iov_len: data3.len(),
-            },
-        ];
-    
-        // Write the data to the pipe using writev_syscall
-        let bytes_written = cage.writev_syscall(write_fd, iovec.as_ptr(), iovec.len() as i32);
+
+        // a link gives the child a path that doesn't start with its parent's path
+        assert_eq!(cage.mkdir_syscall("/xdir", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall("/xdir/sub", S_IRWXA), 0);
+        assert_eq!(cage.symlink_syscall("/xdir", "/xlnk"), 0);
         assert_eq!(
-            bytes_written,
-            (data1.len() + data2.len() + data3.len()) as i32
+            cage.renameat2_syscall(AT_FDCWD, "/xlnk/sub", AT_FDCWD, "/xdir", RENAME_EXCHANGE),
+            -(Errno::EINVAL as i32)
         );
-    
-        // Read the data from the pipe
-        let mut buffer = vec![0u8; data1.len() + data2.len() + data3.len()];
-        let bytes_read = cage.read_syscall(read_fd, buffer.as_mut_ptr(), buffer.len());
-        assert_eq!(bytes_read, buffer.len() as i32);
-    
-        // Verify that the data read is the same as the data written
-        let expected_data = [data1.as_ref(), data2.as_ref(), data3.as_ref()].concat();
-        assert_eq!(buffer, expected_data);
-    
-        // Close both pipe file descriptors
-        assert_eq!(cage.close_syscall(read_fd), 0);
-        assert_eq!(cage.close_syscall(write_fd), 0);
-    
+        assert_eq!(
+            cage.renameat2_syscall(AT_FDCWD, "/xdir", AT_FDCWD, "/xlnk/sub", RENAME_EXCHANGE),
+            -(Errno::EINVAL as i32)
+        );
+
+        // sockets bound beneath either side follow the exchange
+        assert_eq!(cage.mkdir_syscall("/xother", S_IRWXA), 0);
+        let sockfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+        let sockaddr = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "/xlnk/sub/sock".as_bytes(),
+        ));
+        assert_eq!(cage.bind_syscall(sockfd, &sockaddr), 0);
+        assert_eq!(
+            cage.renameat2_syscall(AT_FDCWD, "/xdir", AT_FDCWD, "/xother", RENAME_EXCHANGE),
+            0
+        );
+        let sockpaths = crate::safeposix::net::NET_METADATA.get_domainsock_paths();
+        assert!(sockpaths.contains(&interface::RustPathBuf::from("/xother/sub/sock")));
+        assert!(!sockpaths.contains(&interface::RustPathBuf::from("/xdir/sub/sock")));
+        assert!(!sockpaths.contains(&interface::RustPathBuf::from("/xlnk/sub/sock")));
+
+        assert_eq!(cage.close_syscall(sockfd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    fn ut_lind_fs_writev_file() {
-        // Acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
-        // and also performs clean environment setup.
+    pub fn ut_lind_fs_at_dirfd_moved_or_removed() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
         let _thelock = setup::lock_and_init();
         let cage = interface::cagetable_getref(1);
+        let mut statdata = StatData::default();
 
-        // Define the file path
-        let file_path = "/tmp/test_writev_file";
-
-        // Open or create a file for writing
-        let fd = cage.open_syscall(file_path, O_CREAT | O_RDWR, S_IRWXA);
-        assert!(fd >= 0, "Failed to open file");
-
-        // Prepare the data to be written using an iovec structure
-        let data1 = b"Hello, ";
-        let data2 = b"world!";
-        let expected_data = [data1.as_slice(), data2.as_slice()].concat(); // Concatenate slices for comparison
-        let iovecs = [
-            interface::IovecStruct {
-                iov_base: data1.as_ptr() as *mut libc::c_void,
-                iov_len: data1.len(),
-            },
-            interface::IovecStruct {
-                iov_base: data2.as_ptr() as *mut libc::c_void,
-                iov_len: data2.len(),
-            },
-        ];
-
-        // Write the data to the file using writev_syscall
-        let total_len = expected_data.len() as i32;
-        let bytes_written = cage.writev_syscall(fd, iovecs.as_ptr(), iovecs.len() as i32);
-        assert_eq!(bytes_written, total_len);
-
-        // Read the data back from the file
-        let mut buffer = vec![0u8; total_len as usize];
-        cage.lseek_syscall(fd, 0, libc::SEEK_SET); // Seek to the beginning of the file
-        let bytes_read = cage.read_syscall(fd, buffer.as_mut_ptr(), buffer.len());
-        assert_eq!(bytes_read, total_len);
-
-        // Verify that the data read is the same as the data written
-        assert_eq!(buffer, expected_data);
+        assert_eq!(cage.mkdir_syscall("/atmove", S_IRWXA), 0);
+        assert_eq!(cage.mkdir_syscall("/atmove/dir", S_IRWXA), 0);
+        let dirfd = cage.open_syscall("/atmove/dir", O_RDONLY, 0);
+        assert!(dirfd >= 0);
 
-        // Close the file
+        // lookups follow the directory after it has been renamed
+        assert_eq!(cage.mkdir_syscall("/atmoved", S_IRWXA), 0);
+        assert_eq!(cage.rename_syscall("/atmove/dir", "/atmoved/dir"), 0);
+        let fd = cage.openat_syscall(dirfd, "file", O_CREAT | O_EXCL | O_WRONLY, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.stat_syscall("/atmoved/dir/file", &mut statdata), 0);
+        assert_eq!(
+            cage.stat_syscall("/atmove/dir/file", &mut statdata),
+            -(Errno::ENOENT as i32)
+        );
+        // .. is the directory's current parent
+        assert_eq!(cage.mkdirat_syscall(dirfd, "../sibling", S_IRWXA), 0);
+        assert_eq!(cage.stat_syscall("/atmoved/sibling", &mut statdata), 0);
+        // relative links inside the directory resolve from it
+        assert_eq!(cage.symlink_syscall("file", "/atmoved/dir/link"), 0);
+        let fd = cage.openat_syscall(dirfd, "link", O_RDONLY, 0);
+        assert!(fd >= 0);
         assert_eq!(cage.close_syscall(fd), 0);
 
-        // Clean up the file
-        assert_eq!(cage.unlink_syscall(file_path), 0);
+        // once removed, the directory itself can still be looked at, but nothing
+        // can be found or created in it
+        assert_eq!(cage.unlink_syscall("/atmoved/dir/link"), 0);
+        assert_eq!(cage.unlink_syscall("/atmoved/dir/file"), 0);
+        assert_eq!(cage.rmdir_syscall("/atmoved/dir"), 0);
+        assert_eq!(cage.fstatat_syscall(dirfd, ".", &mut statdata, 0), 0);
+        assert!(is_dir(statdata.st_mode));
+        assert_eq!(
+            cage.openat_syscall(dirfd, "file", O_CREAT | O_WRONLY, S_IRWXA),
+            -(Errno::ENOENT as i32)
+        );
+        assert_eq!(
+            cage.mkdirat_syscall(dirfd, "sub", S_IRWXA),
+            -(Errno::ENOENT as i32)
+        );
 
+        assert_eq!(cage.close_syscall(dirfd), 0);
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
@@ -645,4 +645,467 @@ pub mod ipc_tests {
 
         lindrustfinalize();
     }
+
+    #[test]
+    pub fn ut_lind_ipc_pipe_size() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let mut pipefds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe2_syscall(&mut pipefds, O_NONBLOCK), 0);
+        assert_eq!(
+            cage.fcntl_syscall(pipefds.readfd, F_GETPIPE_SZ, 0),
+            PIPE_CAPACITY as i32
+        );
+
+        // the contents of the pipe have to fit in its new size
+        let data: Vec<u8> = (0..10000).map(|i| (i % 251) as u8).collect();
+        assert_eq!(
+            cage.write_syscall(pipefds.writefd, data.as_ptr(), data.len()),
+            10000
+        );
+        assert_eq!(
+            cage.fcntl_syscall(pipefds.writefd, F_SETPIPE_SZ, 4096),
+            -(Errno::EBUSY as i32)
+        );
+        // sizes are rounded up to a power of two number of pages, and the contents are
+        // kept
+        assert_eq!(
+            cage.fcntl_syscall(pipefds.writefd, F_SETPIPE_SZ, 10001),
+            16384
+        );
+        assert_eq!(cage.fcntl_syscall(pipefds.readfd, F_GETPIPE_SZ, 0), 16384);
+        let mut buf = vec![0u8; 20000];
+        assert_eq!(
+            cage.read_syscall(pipefds.readfd, buf.as_mut_ptr(), buf.len()),
+            10000
+        );
+        assert_eq!(&buf[..10000], &data[..]);
+
+        // a pipe of a single page takes a page and is then full
+        assert_eq!(cage.fcntl_syscall(pipefds.readfd, F_SETPIPE_SZ, 0), 4096);
+        assert_eq!(
+            cage.write_syscall(pipefds.writefd, data.as_ptr(), data.len()),
+            4096
+        );
+        assert_eq!(
+            cage.write_syscall(pipefds.writefd, data.as_ptr(), 1),
+            -(Errno::EAGAIN as i32)
+        );
+        assert_eq!(
+            cage.read_syscall(pipefds.readfd, buf.as_mut_ptr(), buf.len()),
+            4096
+        );
+
+        // only root may go above the size limit
+        assert_eq!(
+            cage.fcntl_syscall(pipefds.writefd, F_SETPIPE_SZ, PIPE_MAX_SIZE as i32),
+            PIPE_MAX_SIZE as i32
+        );
+        assert_eq!(
+            cage.fcntl_syscall(pipefds.writefd, F_SETPIPE_SZ, PIPE_MAX_SIZE as i32 + 1),
+            -(Errno::EPERM as i32)
+        );
+        // sizes no pipe can have are invalid for anyone
+        assert_eq!(
+            cage.fcntl_syscall(pipefds.writefd, F_SETPIPE_SZ, i32::MAX),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.fcntl_syscall(pipefds.writefd, F_SETPIPE_SZ, -1),
+            -(Errno::EINVAL as i32)
+        );
+
+        // root may, up to a hard limit that keeps the rounded size in range
+        let euid = cage.geteuid.load(interface::RustAtomicOrdering::Relaxed);
+        cage.geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        assert_eq!(
+            cage.fcntl_syscall(pipefds.writefd, F_SETPIPE_SZ, PIPE_MAX_SIZE as i32 + 1),
+            2 * PIPE_MAX_SIZE as i32
+        );
+        for size in [PIPE_HARD_MAX_SIZE as i32 + 1, i32::MAX] {
+            assert_eq!(
+                cage.fcntl_syscall(pipefds.writefd, F_SETPIPE_SZ, size),
+                -(Errno::EINVAL as i32)
+            );
+        }
+        assert_eq!(
+            cage.fcntl_syscall(pipefds.readfd, F_GETPIPE_SZ, 0),
+            2 * PIPE_MAX_SIZE as i32
+        );
+        cage.geteuid
+            .store(euid, interface::RustAtomicOrdering::Relaxed);
+
+        // other descriptors don't have a pipe size
+        let fd = cage.open_syscall("/pipesizefile", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(
+            cage.fcntl_syscall(fd, F_GETPIPE_SZ, 0),
+            -(Errno::EBADF as i32)
+        );
+        assert_eq!(
+            cage.fcntl_syscall(fd, F_SETPIPE_SZ, 4096),
+            -(Errno::EBADF as i32)
+        );
+
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.unlink_syscall("/pipesizefile"), 0);
+        assert_eq!(cage.close_syscall(pipefds.readfd), 0);
+        assert_eq!(cage.close_syscall(pipefds.writefd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_ipc_splice_file() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let mut pipefds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+        let fd = cage.open_syscall("/splicefile", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(cage.write_syscall(fd, str2cbuf("hello world"), 11), 11);
+
+        // from a file at an offset, which is advanced instead of the file offset
+        let mut off: i64 = 6;
+        assert_eq!(
+            cage.splice_syscall(fd, Some(&mut off), pipefds.writefd, None, 100, 0),
+            5
+        );
+        assert_eq!(off, 11);
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_CUR), 11);
+        // from a file at the file offset
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_SET), 0);
+        assert_eq!(
+            cage.splice_syscall(fd, None, pipefds.writefd, None, 5, 0),
+            5
+        );
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_CUR), 5);
+        let mut buf = sizecbuf(10);
+        assert_eq!(cage.read_syscall(pipefds.readfd, buf.as_mut_ptr(), 10), 10);
+        assert_eq!(cbuf2str(&buf), "worldhello");
+
+        // into a file at an offset
+        assert_eq!(cage.write_syscall(pipefds.writefd, str2cbuf("HELLO"), 5), 5);
+        let mut off: i64 = 0;
+        assert_eq!(
+            cage.splice_syscall(pipefds.readfd, None, fd, Some(&mut off), 3, 0),
+            3
+        );
+        assert_eq!(off, 3);
+        assert_eq!(
+            cage.splice_syscall(pipefds.readfd, None, fd, Some(&mut off), 100, 0),
+            2
+        );
+        assert_eq!(off, 5);
+        let mut buf = sizecbuf(11);
+        assert_eq!(cage.pread_syscall(fd, buf.as_mut_ptr(), 11, 0), 11);
+        assert_eq!(cbuf2str(&buf), "HELLO world");
+
+        // an empty pipe either fails or waits for something to splice
+        assert_eq!(
+            cage.splice_syscall(pipefds.readfd, None, fd, None, 100, SPLICE_F_NONBLOCK),
+            -(Errno::EAGAIN as i32)
+        );
+        let cage2 = cage.clone();
+        let thread = interface::helper_thread(move || {
+            interface::sleep(interface::RustDuration::from_millis(30));
+            assert_eq!(cage2.write_syscall(pipefds.writefd, str2cbuf("late"), 4), 4);
+        });
+        assert_eq!(cage.lseek_syscall(fd, 0, SEEK_END), 11);
+        assert_eq!(
+            cage.splice_syscall(pipefds.readfd, None, fd, None, 100, 0),
+            4
+        );
+        thread.join().unwrap();
+        let mut buf = sizecbuf(15);
+        assert_eq!(cage.pread_syscall(fd, buf.as_mut_ptr(), 15, 0), 15);
+        assert_eq!(cbuf2str(&buf), "HELLO worldlate");
+
+        // invalid combinations
+        assert_eq!(
+            cage.splice_syscall(fd, None, fd, None, 100, 0),
+            -(Errno::EINVAL as i32)
+        );
+        let mut off: i64 = 0;
+        assert_eq!(
+            cage.splice_syscall(pipefds.readfd, Some(&mut off), fd, None, 100, 0),
+            -(Errno::ESPIPE as i32)
+        );
+        let mut off: i64 = -1;
+        assert_eq!(
+            cage.splice_syscall(fd, Some(&mut off), pipefds.writefd, None, 100, 0),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.splice_syscall(fd, None, pipefds.readfd, None, 100, 0),
+            -(Errno::EBADF as i32)
+        );
+        assert_eq!(
+            cage.splice_syscall(fd, None, pipefds.writefd, None, 100, 0x100),
+            -(Errno::EINVAL as i32)
+        );
+
+        // once every writer is gone the pipe is at EOF
+        assert_eq!(cage.close_syscall(pipefds.writefd), 0);
+        assert_eq!(
+            cage.splice_syscall(pipefds.readfd, None, fd, None, 100, 0),
+            0
+        );
+
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.unlink_syscall("/splicefile"), 0);
+        assert_eq!(cage.close_syscall(pipefds.readfd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_ipc_splice_pipe_and_tee() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let mut pipe1 = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        let mut pipe2 = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut pipe1), 0);
+        assert_eq!(cage.pipe_syscall(&mut pipe2), 0);
+        assert_eq!(cage.write_syscall(pipe1.writefd, str2cbuf("data"), 4), 4);
+
+        // tee copies without taking anything out of the first pipe
+        assert_eq!(cage.tee_syscall(pipe1.readfd, pipe2.writefd, 100, 0), 4);
+        assert_eq!(
+            cage.splice_syscall(pipe1.readfd, None, pipe2.writefd, None, 2, 0),
+            2
+        );
+        let mut buf = sizecbuf(6);
+        assert_eq!(cage.read_syscall(pipe2.readfd, buf.as_mut_ptr(), 6), 6);
+        assert_eq!(cbuf2str(&buf), "datada");
+        let mut buf = sizecbuf(2);
+        assert_eq!(cage.read_syscall(pipe1.readfd, buf.as_mut_ptr(), 2), 2);
+        assert_eq!(cbuf2str(&buf), "ta");
+
+        // the destination pipe only takes what it has room for
+        assert_eq!(cage.fcntl_syscall(pipe2.writefd, F_SETPIPE_SZ, 4096), 4096);
+        let data = vec![b'x'; 5000];
+        assert_eq!(cage.write_syscall(pipe1.writefd, data.as_ptr(), 5000), 5000);
+        assert_eq!(
+            cage.splice_syscall(pipe1.readfd, None, pipe2.writefd, None, 5000, 0),
+            4096
+        );
+        assert_eq!(
+            cage.tee_syscall(pipe1.readfd, pipe2.writefd, 5000, SPLICE_F_NONBLOCK),
+            -(Errno::EAGAIN as i32)
+        );
+        let mut buf = vec![0u8; 5000];
+        assert_eq!(
+            cage.read_syscall(pipe2.readfd, buf.as_mut_ptr(), 5000),
+            4096
+        );
+        assert_eq!(cage.read_syscall(pipe1.readfd, buf.as_mut_ptr(), 5000), 904);
+
+        // tee only works between two different pipes
+        assert_eq!(
+            cage.tee_syscall(pipe1.readfd, pipe2.writefd, 100, SPLICE_F_NONBLOCK),
+            -(Errno::EAGAIN as i32)
+        );
+        assert_eq!(
+            cage.tee_syscall(pipe1.readfd, pipe1.writefd, 100, 0),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.splice_syscall(pipe1.readfd, None, pipe1.writefd, None, 100, 0),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.tee_syscall(pipe1.writefd, pipe2.writefd, 100, 0),
+            -(Errno::EBADF as i32)
+        );
+        let fd = cage.open_syscall("/teefile", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(
+            cage.tee_syscall(pipe1.readfd, fd, 100, 0),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.unlink_syscall("/teefile"), 0);
+
+        // an empty pipe without writers is at EOF
+        assert_eq!(cage.close_syscall(pipe1.writefd), 0);
+        assert_eq!(cage.tee_syscall(pipe1.readfd, pipe2.writefd, 100, 0), 0);
+        assert_eq!(
+            cage.splice_syscall(pipe1.readfd, None, pipe2.writefd, None, 100, 0),
+            0
+        );
+
+        assert_eq!(cage.close_syscall(pipe1.readfd), 0);
+        assert_eq!(cage.close_syscall(pipe2.readfd), 0);
+        assert_eq!(cage.close_syscall(pipe2.writefd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_ipc_splice_socket() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let mut socketpair = interface::SockPair::default();
+        assert_eq!(
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            0
+        );
+        let mut pipefds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+
+        // from a socket into a pipe
+        assert_eq!(
+            cage.send_syscall(socketpair.sock1, str2cbuf("ping"), 4, 0),
+            4
+        );
+        assert_eq!(
+            cage.splice_syscall(socketpair.sock2, None, pipefds.writefd, None, 100, 0),
+            4
+        );
+        assert_eq!(
+            cage.splice_syscall(
+                socketpair.sock2,
+                None,
+                pipefds.writefd,
+                None,
+                100,
+                SPLICE_F_NONBLOCK
+            ),
+            -(Errno::EAGAIN as i32)
+        );
+
+        // and from the pipe back out of the socket
+        assert_eq!(
+            cage.splice_syscall(pipefds.readfd, None, socketpair.sock2, None, 100, 0),
+            4
+        );
+        let mut buf = sizecbuf(4);
+        assert_eq!(
+            cage.recv_syscall(socketpair.sock1, buf.as_mut_ptr(), 4, 0),
+            4
+        );
+        assert_eq!(cbuf2str(&buf), "ping");
+
+        let mut off: i64 = 0;
+        assert_eq!(
+            cage.splice_syscall(
+                socketpair.sock2,
+                Some(&mut off),
+                pipefds.writefd,
+                None,
+                100,
+                0
+            ),
+            -(Errno::ESPIPE as i32)
+        );
+
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+        assert_eq!(cage.close_syscall(pipefds.readfd), 0);
+        assert_eq!(cage.close_syscall(pipefds.writefd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_ipc_vmsplice() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+        let mut pipefds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+
+        // the buffers are written into the write end
+        let iovec: [interface::IovecStruct; 2] = [
+            interface::IovecStruct {
+                iov_base: str2cbuf("vm") as *mut c_void,
+                iov_len: 2,
+            },
+            interface::IovecStruct {
+                iov_base: str2cbuf("splice") as *mut c_void,
+                iov_len: 6,
+            },
+        ];
+        assert_eq!(
+            cage.vmsplice_syscall(pipefds.writefd, iovec.as_ptr(), 2, 0),
+            8
+        );
+
+        // and read into from the read end
+        let mut buf1 = sizecbuf(3);
+        let mut buf2 = sizecbuf(10);
+        let iovec: [interface::IovecStruct; 2] = [
+            interface::IovecStruct {
+                iov_base: buf1.as_mut_ptr() as *mut c_void,
+                iov_len: 3,
+            },
+            interface::IovecStruct {
+                iov_base: buf2.as_mut_ptr() as *mut c_void,
+                iov_len: 10,
+            },
+        ];
+        assert_eq!(
+            cage.vmsplice_syscall(pipefds.readfd, iovec.as_ptr(), 2, 0),
+            8
+        );
+        assert_eq!(cbuf2str(&buf1), "vms");
+        assert_eq!(cbuf2str(&buf2[..5]), "plice");
+        assert_eq!(
+            cage.vmsplice_syscall(pipefds.readfd, iovec.as_ptr(), 2, SPLICE_F_NONBLOCK),
+            -(Errno::EAGAIN as i32)
+        );
+
+        // only pipes can be vmspliced
+        let fd = cage.open_syscall("/vmsplicefile", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(fd >= 0);
+        assert_eq!(
+            cage.vmsplice_syscall(fd, iovec.as_ptr(), 2, 0),
+            -(Errno::EBADF as i32)
+        );
+        assert_eq!(
+            cage.vmsplice_syscall(pipefds.readfd, iovec.as_ptr(), -1, 0),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(cage.close_syscall(fd), 0);
+        assert_eq!(cage.unlink_syscall("/vmsplicefile"), 0);
+
+        assert_eq!(cage.close_syscall(pipefds.readfd), 0);
+        assert_eq!(cage.close_syscall(pipefds.writefd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
 }
@@ -54,6 +54,12 @@ mod setup {
         {
             println!("test_setup()");
             let cage = interface::cagetable_getref(1);
+            //clean up as the superuser, so that files left behind by tests
+            //running as other users can be removed as well
+            cage.getuid
+                .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+            cage.geteuid
+                .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
             crate::lib_fs_utils::lind_deltree(&cage, "/");
             assert_eq!(cage.mkdir_syscall("/dev", S_IRWXA), 0);
             assert_eq!(
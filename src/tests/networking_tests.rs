@@ -1424,6 +1424,155 @@ pub mod net_tests {
         lindrustfinalize();
     }
 
+    #[test]
+    pub fn ut_lind_net_poll_streams() {
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // stdout and stderr can always be written to, and stdin read from
+        let mut polled = vec![
+            interface::PollStruct {
+                fd: 0,
+                events: POLLIN | POLLOUT,
+                revents: 0,
+            },
+            interface::PollStruct {
+                fd: 1,
+                events: POLLOUT,
+                revents: 0,
+            },
+            interface::PollStruct {
+                fd: 2,
+                events: POLLIN | POLLOUT,
+                revents: 0,
+            },
+        ];
+        assert_eq!(
+            cage.poll_syscall(
+                &mut polled.as_mut_slice(),
+                Some(interface::RustDuration::ZERO)
+            ),
+            3
+        );
+        assert_eq!(polled[0].revents, POLLIN);
+        assert_eq!(polled[1].revents, POLLOUT);
+        assert_eq!(polled[2].revents, POLLOUT);
+
+        // nothing is reported for events the stream can't be ready for
+        let mut polled = vec![interface::PollStruct {
+            fd: 1,
+            events: POLLIN,
+            revents: 0,
+        }];
+        assert_eq!(
+            cage.poll_syscall(
+                &mut polled.as_mut_slice(),
+                Some(interface::RustDuration::ZERO)
+            ),
+            0
+        );
+        assert_eq!(polled[0].revents, 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_poll_hangup() {
+        // this test is used for testing the error and hangup conditions poll reports
+        // for each kind of file descriptor
+
+        // acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        // a pipe whose write end is closed, so its read end hangs up
+        let mut hupfds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut hupfds), 0);
+        assert_eq!(cage.close_syscall(hupfds.writefd), 0);
+
+        // a pipe whose read end is closed, so writing to it is an error
+        let mut errfds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut errfds), 0);
+        assert_eq!(cage.close_syscall(errfds.readfd), 0);
+
+        // a connected unix domain socket whose peer is closed
+        let mut socketpair = interface::SockPair::default();
+        assert_eq!(
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            0
+        );
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+
+        // contruct the PollStruct array:
+        // 1. the hung up pipe read end, POLLHUP is reported even though only POLLIN was
+        //    asked for
+        // 2. the broken pipe write end, which is still writable but has POLLERR
+        // 3. the socket whose peer is gone, which is readable (EOF) and has POLLRDHUP
+        //    and POLLHUP
+        // 4. a closed fd and an fd out of range, which are both POLLNVAL
+        let mut polled = vec![
+            interface::PollStruct {
+                fd: hupfds.readfd,
+                events: POLLIN,
+                revents: 0,
+            },
+            interface::PollStruct {
+                fd: errfds.writefd,
+                events: POLLOUT,
+                revents: 0,
+            },
+            interface::PollStruct {
+                fd: socketpair.sock1,
+                events: POLLIN | POLLRDHUP,
+                revents: 0,
+            },
+            interface::PollStruct {
+                fd: socketpair.sock2,
+                events: POLLIN,
+                revents: 0,
+            },
+            interface::PollStruct {
+                fd: MAXFD,
+                events: POLLIN,
+                revents: 0,
+            },
+        ];
+        assert_eq!(cage.poll_syscall(&mut polled.as_mut_slice(), None), 5);
+        assert_eq!(polled[0].revents, POLLHUP);
+        assert_eq!(polled[1].revents, POLLOUT | POLLERR);
+        assert_eq!(polled[2].revents, POLLIN | POLLRDHUP | POLLHUP);
+        assert_eq!(polled[3].revents, POLLNVAL);
+        assert_eq!(polled[4].revents, POLLNVAL);
+
+        // without POLLRDHUP in events, the socket only reports POLLIN and POLLHUP
+        let mut polled = vec![interface::PollStruct {
+            fd: socketpair.sock1,
+            events: POLLIN,
+            revents: 0,
+        }];
+        assert_eq!(cage.poll_syscall(&mut polled.as_mut_slice(), None), 1);
+        assert_eq!(polled[0].revents, POLLIN | POLLHUP);
+
+        assert_eq!(cage.close_syscall(hupfds.readfd), 0);
+        assert_eq!(cage.close_syscall(errfds.writefd), 0);
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
     #[test]
     pub fn ut_lind_net_poll_timeout() {
         // this test is used for testing poll with timeout behaviors specifically
@@ -4199,9 +4348,9 @@ pub mod net_tests {
         );
 
         // test for unsupported socktype
-        // socketpair only works with SOCK_STREAM
+        // socketpair only works with SOCK_STREAM, SOCK_DGRAM and SOCK_SEQPACKET
         assert_eq!(
-            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_DGRAM, 0, &mut socketpair),
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_RAW, 0, &mut socketpair),
             -(Errno::EOPNOTSUPP as i32)
         );
 
@@ -4213,9 +4362,10 @@ pub mod net_tests {
         );
 
         // test for bad structured input
+        // the type in the lowest bits is SOCK_DGRAM, but the rest are not valid flags
         assert_eq!(
             Cage::socketpair_syscall(cage.clone(), AF_UNIX, 472810394, 0, &mut socketpair),
-            -(Errno::EOPNOTSUPP as i32)
+            -(Errno::EINVAL as i32)
         );
 
         // test for invalid flags
@@ -4292,19 +4442,114 @@ pub mod net_tests {
     }
 
     #[test]
-    pub fn ut_lind_net_socketpair_nonblocking() {
-        // this test is used for testing socketpair when nonblocking flag is set
-        // when nonblocking flag is set, the socket should not block on syscalls like
-        // recv_syscall, instead, EAGAIN error should be returned when there is no
-        // data to receive
+    pub fn ut_lind_net_sendmsg_scm_rights() {
+        // this test passes an open file from one cage to another over a
+        // socketpair with SCM_RIGHTS, and checks the receiver can use it after
+        // the sender has closed its own descriptor
 
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
         let cage = interface::cagetable_getref(1);
         let mut socketpair = interface::SockPair::default();
+        assert_eq!(
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            0
+        );
+        assert_eq!(cage.fork_syscall(2), 0);
+        let cage2 = interface::cagetable_getref(2);
+
+        let filefd = cage.open_syscall("/scmrightsfile", O_CREAT | O_RDWR, S_IRWXA);
+        assert!(filefd >= 0);
+        assert_eq!(cage.write_syscall(filefd, str2cbuf("passed along"), 12), 12);
+        assert_eq!(cage.unlink_syscall("/scmrightsfile"), 0);
+
+        // send one byte along with the descriptor
+        let mut data = *b"x";
+        let mut iov = interface::IovecStruct {
+            iov_base: data.as_mut_ptr() as *mut c_void,
+            iov_len: 1,
+        };
+        let mut control = [0u64; 8];
+        let mut msg: interface::MsgHdr = unsafe { std::mem::zeroed() };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut c_void;
+        msg.msg_controllen = unsafe { libc::CMSG_SPACE(size_of::<i32>() as u32) } as usize;
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_level = SOL_SOCKET;
+            (*cmsg).cmsg_type = SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(size_of::<i32>() as u32) as usize;
+            *(libc::CMSG_DATA(cmsg) as *mut i32) = filefd;
+        }
+        assert_eq!(cage.sendmsg_syscall(socketpair.sock1, &msg, 0), 1);
+        // the file stays open while it is in flight
+        assert_eq!(cage.close_syscall(filefd), 0);
+
+        // receive it in the other cage
+        let mut recvdata = [0u8; 4];
+        let mut recviov = interface::IovecStruct {
+            iov_base: recvdata.as_mut_ptr() as *mut c_void,
+            iov_len: 4,
+        };
+        let mut recvcontrol = [0u64; 8];
+        let mut recvmsg: interface::MsgHdr = unsafe { std::mem::zeroed() };
+        recvmsg.msg_iov = &mut recviov;
+        recvmsg.msg_iovlen = 1;
+        recvmsg.msg_control = recvcontrol.as_mut_ptr() as *mut c_void;
+        recvmsg.msg_controllen = size_of::<[u64; 8]>();
+        assert_eq!(
+            cage2.recvmsg_syscall(socketpair.sock2, &mut recvmsg, MSG_CMSG_CLOEXEC),
+            1
+        );
+        assert_eq!(recvdata[0], b'x');
+        assert_eq!(recvmsg.msg_flags, 0);
+        assert_eq!(
+            recvmsg.msg_controllen,
+            unsafe { libc::CMSG_SPACE(size_of::<i32>() as u32) } as usize
+        );
+        let newfd = unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&recvmsg);
+            assert_eq!((*cmsg).cmsg_level, SOL_SOCKET);
+            assert_eq!((*cmsg).cmsg_type, SCM_RIGHTS);
+            *(libc::CMSG_DATA(cmsg) as *const i32)
+        };
+        assert!(newfd >= 0);
+        assert_eq!(cage2.fcntl_syscall(newfd, F_GETFD, 0), O_CLOEXEC);
+        let mut readbuf = sizecbuf(12);
+        assert_eq!(cage2.pread_syscall(newfd, readbuf.as_mut_ptr(), 12, 0), 12);
+        assert_eq!(cbuf2str(&readbuf), "passed along");
+        assert_eq!(cage2.close_syscall(newfd), 0);
+
+        // a descriptor that isn't open can't be passed
+        unsafe {
+            *(libc::CMSG_DATA(libc::CMSG_FIRSTHDR(&msg)) as *mut i32) = filefd;
+        }
+        assert_eq!(
+            cage.sendmsg_syscall(socketpair.sock1, &msg, 0),
+            -(Errno::EBADF as i32)
+        );
 
-        // try with nonblocking flag
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+        assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_sendmsg_boundaries_and_truncation() {
+        // this test checks that a recvmsg never reads past the bytes that came
+        // with ancillary data, that descriptors which don't fit in the control
+        // buffer or whose bytes are read with recv are closed, and that data
+        // without ancillary data still goes through sendmsg/recvmsg
+
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        let mut socketpair = interface::SockPair::default();
         assert_eq!(
             Cage::socketpair_syscall(
                 cage.clone(),
@@ -4315,116 +4560,845 @@ pub mod net_tests {
             ),
             0
         );
+        let mut pipefds = PipeArray::default();
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
 
-        let cage2 = cage.clone();
-
-        let thread = interface::helper_thread(move || {
-            let mut buf = sizecbuf(10);
-            // counter is used for recording how many times do recv_syscall returned
-            // with EAGAIN
-            let mut counter = 0;
-
-            // receive the message
-            // since peer will sleep for 30ms before send the message
-            // some nonblocking returns are expected
-            loop {
-                let result = cage2.recv_syscall(socketpair.sock2, buf.as_mut_ptr(), 10, 0);
-                if result == -(Errno::EAGAIN as i32) {
-                    // return due to nonblocking flag
-                    counter += 1;
-                    continue;
-                }
-                if result != -libc::EINTR {
-                    break; // if the error was EINTR, retry the syscall
-                }
-            }
-            // check if the received message is correct
-            assert_eq!(cbuf2str(&buf), "test\0\0\0\0\0\0");
-            // check if there is any nonblocking return
-            assert_ne!(counter, 0);
-
-            // sleep for 30ms so receiver could have some nonblocking return
-            interface::sleep(interface::RustDuration::from_millis(30));
-            // send the message
-            assert_eq!(
-                cage2.send_syscall(socketpair.sock2, str2cbuf("Socketpair Test"), 15, 0),
-                15
-            );
-        });
-
-        let cage3 = cage.clone();
-
-        let thread_2 = interface::helper_thread(move || {
-            // sleep for 30ms so receiver could have some nonblocking return
-            interface::sleep(interface::RustDuration::from_millis(30));
+        let mut data = *b"abcdef";
+        let mut iov = interface::IovecStruct {
+            iov_base: data.as_mut_ptr() as *mut c_void,
+            iov_len: 2,
+        };
+        let mut control = [0u64; 8];
+        let mut msg: interface::MsgHdr = unsafe { std::mem::zeroed() };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        // plain "ab", then "cd" with both pipe ends, then plain "ef"
+        assert_eq!(cage.sendmsg_syscall(socketpair.sock1, &msg, 0), 2);
+        msg.msg_control = control.as_mut_ptr() as *mut c_void;
+        msg.msg_controllen = unsafe { libc::CMSG_SPACE(2 * size_of::<i32>() as u32) } as usize;
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_level = SOL_SOCKET;
+            (*cmsg).cmsg_type = SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(2 * size_of::<i32>() as u32) as usize;
+            let fds = libc::CMSG_DATA(cmsg) as *mut i32;
+            *fds = pipefds.readfd;
+            *fds.add(1) = pipefds.writefd;
+        }
+        unsafe {
+            (*msg.msg_iov).iov_base = data[2..].as_mut_ptr() as *mut c_void;
+        }
+        assert_eq!(cage.sendmsg_syscall(socketpair.sock1, &msg, 0), 2);
+        assert_eq!(
+            cage.send_syscall(socketpair.sock1, data[4..].as_ptr(), 2, 0),
+            2
+        );
+        // only our copies of the pipe ends are left open after this
+        assert_eq!(cage.close_syscall(pipefds.readfd), 0);
+        assert_eq!(cage.close_syscall(pipefds.writefd), 0);
 
-            // send message
+        let mut recvdata = [0u8; 6];
+        let mut recviov = interface::IovecStruct {
+            iov_base: recvdata.as_mut_ptr() as *mut c_void,
+            iov_len: 6,
+        };
+        let mut recvcontrol = [0u64; 8];
+        let mut recvmsg: interface::MsgHdr = unsafe { std::mem::zeroed() };
+        recvmsg.msg_iov = &mut recviov;
+        recvmsg.msg_iovlen = 1;
+        recvmsg.msg_control = recvcontrol.as_mut_ptr() as *mut c_void;
+        recvmsg.msg_controllen = size_of::<[u64; 8]>();
+        // the first read stops where the ancillary data starts
+        assert_eq!(
+            cage.recvmsg_syscall(socketpair.sock1, &mut recvmsg, 0),
+            -(Errno::EAGAIN as i32)
+        );
+        assert_eq!(cage.recvmsg_syscall(socketpair.sock2, &mut recvmsg, 0), 2);
+        assert_eq!(&recvdata[..2], b"ab");
+        assert_eq!(recvmsg.msg_controllen, 0);
+
+        // there is only room for one of the two descriptors
+        recvmsg.msg_controllen = unsafe { libc::CMSG_LEN(size_of::<i32>() as u32) } as usize;
+        assert_eq!(cage.recvmsg_syscall(socketpair.sock2, &mut recvmsg, 0), 4);
+        assert_eq!(&recvdata[..4], b"cdef");
+        assert_eq!(recvmsg.msg_flags, MSG_CTRUNC);
+        let readfd = unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&recvmsg);
+            assert_eq!((*cmsg).cmsg_type, SCM_RIGHTS);
             assert_eq!(
-                cage3.send_syscall(socketpair.sock1, str2cbuf("test"), 4, 0),
-                4
+                (*cmsg).cmsg_len,
+                libc::CMSG_LEN(size_of::<i32>() as u32) as usize
             );
+            *(libc::CMSG_DATA(cmsg) as *const i32)
+        };
+        // the write end that didn't fit was closed, so the pipe is at EOF
+        let mut buf = sizecbuf(4);
+        assert_eq!(cage.read_syscall(readfd, buf.as_mut_ptr(), 4), 0);
+        assert_eq!(cage.close_syscall(readfd), 0);
 
-            // receive the message
-            // since peer will sleep for 30ms before send the message
-            // some nonblocking returns are expected
-            let mut buf2 = sizecbuf(15);
-            let mut counter = 0;
-            loop {
-                let result = cage3.recv_syscall(socketpair.sock1, buf2.as_mut_ptr(), 15, 0);
-                if result == -(Errno::EAGAIN as i32) {
-                    // return due to nonblocking flag
-                    counter += 1;
-                    continue;
-                }
-                if result != -libc::EINTR {
-                    break; // if the error was EINTR, retry the syscall
-                }
-            }
-            // check if the received message is correct
-            let str2 = cbuf2str(&buf2);
-            assert_eq!(str2, "Socketpair Test");
-            // check if there is any nonblocking return
-            assert_ne!(counter, 0);
-        });
+        // descriptors whose bytes are read with recv are closed as well
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+        unsafe {
+            let fds = libc::CMSG_DATA(libc::CMSG_FIRSTHDR(&msg)) as *mut i32;
+            *fds = pipefds.writefd;
+            *fds.add(1) = pipefds.writefd;
+        }
+        assert_eq!(cage.sendmsg_syscall(socketpair.sock1, &msg, 0), 2);
+        assert_eq!(cage.close_syscall(pipefds.writefd), 0);
+        assert_eq!(
+            cage.recv_syscall(socketpair.sock2, buf.as_mut_ptr(), 4, 0),
+            2
+        );
+        recvmsg.msg_controllen = size_of::<[u64; 8]>();
+        assert_eq!(
+            cage.recvmsg_syscall(socketpair.sock2, &mut recvmsg, 0),
+            -(Errno::EAGAIN as i32)
+        );
+        assert_eq!(cage.read_syscall(pipefds.readfd, buf.as_mut_ptr(), 4), 0);
+        assert_eq!(cage.close_syscall(pipefds.readfd), 0);
 
-        thread.join().unwrap();
-        thread_2.join().unwrap();
+        // unknown control message types are rejected
+        unsafe {
+            (*libc::CMSG_FIRSTHDR(&msg)).cmsg_type = 99;
+        }
+        assert_eq!(
+            cage.sendmsg_syscall(socketpair.sock1, &msg, 0),
+            -(Errno::EINVAL as i32)
+        );
 
         assert_eq!(cage.close_syscall(socketpair.sock1), 0);
         assert_eq!(cage.close_syscall(socketpair.sock2), 0);
-
         assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
         lindrustfinalize();
     }
 
     #[test]
-    pub fn ut_lind_net_udp_bad_bind() {
+    pub fn ut_lind_net_sendmsg_scm_credentials() {
+        // this test checks that SCM_CREDENTIALS carries the sender's ids, that
+        // they can't be forged, and that SO_PASSCRED asks for them
+
         //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
         // and also performs clean env setup
         let _thelock = setup::lock_and_init();
-
         let cage = interface::cagetable_getref(1);
+        let mut socketpair = interface::SockPair::default();
+        assert_eq!(
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            0
+        );
 
-        let sockfd = cage.socket_syscall(AF_INET, SOCK_DGRAM, 0);
-        assert!(sockfd > 0); //checking that the sockfd is valid
-        let port: u16 = generate_random_port();
-
-        let sockaddr = interface::SockaddrV4 {
-            sin_family: AF_INET as u16,
-            sin_port: port.to_be(),
-            sin_addr: interface::V4Addr {
-                s_addr: u32::from_ne_bytes([127, 0, 0, 1]),
-            },
-            padding: 0,
+        let mut data = *b"hi";
+        let mut iov = interface::IovecStruct {
+            iov_base: data.as_mut_ptr() as *mut c_void,
+            iov_len: 2,
+        };
+        let credspace = unsafe { libc::CMSG_SPACE(size_of::<libc::ucred>() as u32) } as usize;
+        let mut control = [0u64; 8];
+        let mut msg: interface::MsgHdr = unsafe { std::mem::zeroed() };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut c_void;
+        msg.msg_controllen = credspace;
+        let setcreds = |msg: &interface::MsgHdr, uid: u32| unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(msg);
+            (*cmsg).cmsg_level = SOL_SOCKET;
+            (*cmsg).cmsg_type = SCM_CREDENTIALS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(size_of::<libc::ucred>() as u32) as usize;
+            *(libc::CMSG_DATA(cmsg) as *mut libc::ucred) = libc::ucred {
+                pid: 1,
+                uid: uid,
+                gid: DEFAULT_GID,
+            };
         };
-        let socket = interface::GenSockaddr::V4(sockaddr); //127.0.0.1
-        let port: u16 = generate_random_port();
 
-        let _sockaddr2 = interface::SockaddrV4 {
-            sin_family: AF_INET as u16,
-            sin_port: port.to_be(),
-            sin_addr: interface::V4Addr {
-                s_addr: u32::from_ne_bytes([127, 0, 0, 1]),
+        // a user can't claim to be someone else
+        setcreds(&msg, ROOT_UID);
+        assert_eq!(
+            cage.sendmsg_syscall(socketpair.sock1, &msg, 0),
+            -(Errno::EPERM as i32)
+        );
+        setcreds(&msg, DEFAULT_UID);
+        assert_eq!(cage.sendmsg_syscall(socketpair.sock1, &msg, 0), 2);
+
+        let mut recvdata = [0u8; 8];
+        let mut recviov = interface::IovecStruct {
+            iov_base: recvdata.as_mut_ptr() as *mut c_void,
+            iov_len: 8,
+        };
+        let mut recvcontrol = [0u64; 8];
+        let mut recvmsg: interface::MsgHdr = unsafe { std::mem::zeroed() };
+        recvmsg.msg_iov = &mut recviov;
+        recvmsg.msg_iovlen = 1;
+        recvmsg.msg_control = recvcontrol.as_mut_ptr() as *mut c_void;
+        recvmsg.msg_controllen = size_of::<[u64; 8]>();
+        let getcreds = |recvmsg: &interface::MsgHdr| unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(recvmsg);
+            assert_eq!((*cmsg).cmsg_type, SCM_CREDENTIALS);
+            *(libc::CMSG_DATA(cmsg) as *const libc::ucred)
+        };
+        assert_eq!(cage.recvmsg_syscall(socketpair.sock2, &mut recvmsg, 0), 2);
+        assert_eq!(recvmsg.msg_controllen, credspace);
+        let creds = getcreds(&recvmsg);
+        assert_eq!(
+            (creds.pid, creds.uid, creds.gid),
+            (1, DEFAULT_UID, DEFAULT_GID)
+        );
+
+        // with SO_PASSCRED the receiver gets the credentials of the sender even
+        // when they only come along with descriptors
+        assert_eq!(
+            cage.setsockopt_syscall(socketpair.sock2, SOL_SOCKET, SO_PASSCRED, 1),
+            0
+        );
+        let mut optval = 0;
+        assert_eq!(
+            cage.getsockopt_syscall(socketpair.sock2, SOL_SOCKET, SO_PASSCRED, &mut optval),
+            0
+        );
+        assert_eq!(optval, 1);
+        msg.msg_controllen = unsafe { libc::CMSG_SPACE(size_of::<i32>() as u32) } as usize;
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_type = SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(size_of::<i32>() as u32) as usize;
+            *(libc::CMSG_DATA(cmsg) as *mut i32) = 1;
+        }
+        assert_eq!(cage.sendmsg_syscall(socketpair.sock1, &msg, 0), 2);
+        recvmsg.msg_controllen = size_of::<[u64; 8]>();
+        assert_eq!(cage.recvmsg_syscall(socketpair.sock2, &mut recvmsg, 0), 2);
+        let creds = getcreds(&recvmsg);
+        assert_eq!((creds.pid, creds.uid), (1, DEFAULT_UID));
+        let stdoutfd = unsafe {
+            let cmsg = libc::CMSG_NXTHDR(&recvmsg, libc::CMSG_FIRSTHDR(&recvmsg));
+            assert_eq!((*cmsg).cmsg_type, SCM_RIGHTS);
+            *(libc::CMSG_DATA(cmsg) as *const i32)
+        };
+        assert!(stdoutfd > 2);
+        assert_eq!(cage.close_syscall(stdoutfd), 0);
+
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_unix_dgram() {
+        // this test checks that unix datagram sockets keep message boundaries, that
+        // an unbound sender is bound automatically so it can be replied to, and
+        // that a connected socket learns when its peer is gone
+
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        let serversockfd = cage.socket_syscall(AF_UNIX, SOCK_DGRAM, 0);
+        let clientsockfd = cage.socket_syscall(AF_UNIX, SOCK_DGRAM, 0);
+        assert!(serversockfd > 0);
+        assert!(clientsockfd > 0);
+
+        let serversocket = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "/dgram.sock".as_bytes(),
+        ));
+        let missingsocket = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "/missing.sock".as_bytes(),
+        ));
+        // nothing can be sent to a path that doesn't exist, or to a file that no
+        // datagram socket is bound to
+        assert_eq!(
+            cage.sendto_syscall(clientsockfd, str2cbuf("lost"), 4, 0, &missingsocket),
+            -(Errno::ENOENT as i32)
+        );
+        let fd = cage.open_syscall("/notasocket", O_CREAT | O_WRONLY, S_IRWXA);
+        assert!(fd > 0);
+        assert_eq!(cage.close_syscall(fd), 0);
+        let filesocket = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "/notasocket".as_bytes(),
+        ));
+        assert_eq!(
+            cage.sendto_syscall(clientsockfd, str2cbuf("lost"), 4, 0, &filesocket),
+            -(Errno::ECONNREFUSED as i32)
+        );
+
+        assert_eq!(cage.bind_syscall(serversockfd, &serversocket), 0);
+
+        // every message is received on its own, however short the buffer
+        assert_eq!(
+            cage.sendto_syscall(clientsockfd, str2cbuf("first"), 5, 0, &serversocket),
+            5
+        );
+        assert_eq!(
+            cage.sendto_syscall(clientsockfd, str2cbuf("second!"), 7, 0, &serversocket),
+            7
+        );
+        let mut from = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "".as_bytes(),
+        ));
+        let mut buf = sizecbuf(3);
+        assert_eq!(
+            cage.recvfrom_syscall(
+                serversockfd,
+                buf.as_mut_ptr(),
+                3,
+                MSG_TRUNC,
+                &mut Some(&mut from)
+            ),
+            5
+        );
+        assert_eq!(cbuf2str(&buf), "fir");
+        // the sender was given an address of its own when it first sent
+        let clientpath = from.path().to_string();
+        assert!(clientpath.starts_with("/sock"));
+        let mut buf = sizecbuf(10);
+        assert_eq!(
+            cage.recvfrom_syscall(serversockfd, buf.as_mut_ptr(), 10, 0, &mut None),
+            7
+        );
+        assert_eq!(cbuf2str(&buf), "second!\0\0\0");
+        assert_eq!(
+            cage.recv_syscall(serversockfd, buf.as_mut_ptr(), 10, MSG_DONTWAIT),
+            -(Errno::EAGAIN as i32)
+        );
+
+        // so it can be answered
+        assert_eq!(
+            cage.sendto_syscall(serversockfd, str2cbuf("reply"), 5, 0, &from),
+            5
+        );
+        let mut buf = sizecbuf(5);
+        assert_eq!(cage.recv_syscall(clientsockfd, buf.as_mut_ptr(), 5, 0), 5);
+        assert_eq!(cbuf2str(&buf), "reply");
+
+        // a connected socket sends to its peer without naming it, and finds out
+        // once the peer is closed
+        assert_eq!(cage.connect_syscall(clientsockfd, &serversocket), 0);
+        assert_eq!(cage.send_syscall(clientsockfd, str2cbuf("hello"), 5, 0), 5);
+        assert_eq!(cage.recv_syscall(serversockfd, buf.as_mut_ptr(), 5, 0), 5);
+        assert_eq!(cbuf2str(&buf), "hello");
+        assert_eq!(cage.close_syscall(serversockfd), 0);
+        assert_eq!(
+            cage.send_syscall(clientsockfd, str2cbuf("hello"), 5, 0),
+            -(Errno::ECONNREFUSED as i32)
+        );
+        assert_eq!(
+            cage.send_syscall(clientsockfd, str2cbuf("hello"), 5, 0),
+            -(Errno::ENOTCONN as i32)
+        );
+
+        assert_eq!(cage.close_syscall(clientsockfd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_unix_dgram_socketpair() {
+        // this test checks that a datagram socketpair passes messages both ways
+        // without joining them
+
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        let mut socketpair = interface::SockPair::default();
+        assert_eq!(
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_DGRAM, 0, &mut socketpair),
+            0
+        );
+
+        assert_eq!(cage.send_syscall(socketpair.sock1, str2cbuf("ab"), 2, 0), 2);
+        assert_eq!(cage.send_syscall(socketpair.sock1, str2cbuf("cd"), 2, 0), 2);
+        assert_eq!(cage.send_syscall(socketpair.sock2, str2cbuf("ef"), 2, 0), 2);
+        let mut buf = sizecbuf(4);
+        assert_eq!(
+            cage.recv_syscall(socketpair.sock2, buf.as_mut_ptr(), 4, 0),
+            2
+        );
+        assert_eq!(cbuf2str(&buf), "ab\0\0");
+        let mut buf = sizecbuf(4);
+        assert_eq!(
+            cage.recv_syscall(socketpair.sock2, buf.as_mut_ptr(), 4, 0),
+            2
+        );
+        assert_eq!(cbuf2str(&buf), "cd\0\0");
+        let mut buf = sizecbuf(4);
+        assert_eq!(
+            cage.recv_syscall(socketpair.sock1, buf.as_mut_ptr(), 4, 0),
+            2
+        );
+        assert_eq!(cbuf2str(&buf), "ef\0\0");
+
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_unix_seqpacket() {
+        // this test checks that a seqpacket socket can be listened on and
+        // connected to, and that its messages keep their boundaries
+
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        let serversockfd = cage.socket_syscall(AF_UNIX, SOCK_SEQPACKET, 0);
+        let clientsockfd = cage.socket_syscall(AF_UNIX, SOCK_SEQPACKET, 0);
+        assert!(serversockfd > 0);
+        assert!(clientsockfd > 0);
+        // there is no seqpacket socket outside of the unix domain
+        assert_eq!(
+            cage.socket_syscall(AF_INET, SOCK_SEQPACKET, 0),
+            -(Errno::EOPNOTSUPP as i32)
+        );
+
+        let serversocket = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "/seqpacket.sock".as_bytes(),
+        ));
+        assert_eq!(cage.bind_syscall(serversockfd, &serversocket), 0);
+        assert_eq!(cage.listen_syscall(serversockfd, 1), 0);
+
+        let cage2 = cage.clone();
+        let thread = interface::helper_thread(move || {
+            let mut socket2 = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+                AF_UNIX as u16,
+                "".as_bytes(),
+            ));
+            let sockfd = cage2.accept_syscall(serversockfd, &mut socket2);
+            assert!(sockfd > 0);
+
+            let mut buf = sizecbuf(10);
+            assert_eq!(cage2.recv_syscall(sockfd, buf.as_mut_ptr(), 10, 0), 3);
+            assert_eq!(cbuf2str(&buf), "one\0\0\0\0\0\0\0");
+            let mut buf = sizecbuf(10);
+            assert_eq!(cage2.recv_syscall(sockfd, buf.as_mut_ptr(), 10, 0), 3);
+            assert_eq!(cbuf2str(&buf), "two\0\0\0\0\0\0\0");
+            assert_eq!(cage2.send_syscall(sockfd, str2cbuf("three"), 5, 0), 5);
+            assert_eq!(cage2.close_syscall(sockfd), 0);
+        });
+
+        assert_eq!(cage.connect_syscall(clientsockfd, &serversocket), 0);
+        assert_eq!(cage.send_syscall(clientsockfd, str2cbuf("one"), 3, 0), 3);
+        assert_eq!(cage.send_syscall(clientsockfd, str2cbuf("two"), 3, 0), 3);
+        // a connected seqpacket socket can't be given another destination
+        assert_eq!(
+            cage.sendto_syscall(clientsockfd, str2cbuf("two"), 3, 0, &serversocket),
+            -(Errno::EISCONN as i32)
+        );
+        let mut buf = sizecbuf(5);
+        assert_eq!(cage.recv_syscall(clientsockfd, buf.as_mut_ptr(), 5, 0), 5);
+        assert_eq!(cbuf2str(&buf), "three");
+        thread.join().unwrap();
+        // the peer is gone, so there is nothing more to read
+        assert_eq!(cage.recv_syscall(clientsockfd, buf.as_mut_ptr(), 5, 0), 0);
+
+        assert_eq!(cage.close_syscall(clientsockfd), 0);
+        assert_eq!(cage.close_syscall(serversockfd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_unix_seqpacket_socketpair() {
+        // this test checks truncation, descriptor passing and closing on a
+        // seqpacket socketpair
+
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        let mut socketpair = interface::SockPair::default();
+        assert_eq!(
+            Cage::socketpair_syscall(
+                cage.clone(),
+                AF_UNIX,
+                SOCK_SEQPACKET | SOCK_NONBLOCK,
+                0,
+                &mut socketpair
+            ),
+            0
+        );
+
+        let mut buf = sizecbuf(4);
+        assert_eq!(
+            cage.recv_syscall(socketpair.sock2, buf.as_mut_ptr(), 4, 0),
+            -(Errno::EAGAIN as i32)
+        );
+
+        // the part of a message that doesn't fit is thrown away, and recvmsg says so
+        assert_eq!(
+            cage.send_syscall(socketpair.sock1, str2cbuf("abcdef"), 6, 0),
+            6
+        );
+        assert_eq!(cage.send_syscall(socketpair.sock1, str2cbuf("gh"), 2, 0), 2);
+        let mut recvdata = [0u8; 4];
+        let mut recviov = interface::IovecStruct {
+            iov_base: recvdata.as_mut_ptr() as *mut c_void,
+            iov_len: 4,
+        };
+        let mut recvmsg: interface::MsgHdr = unsafe { std::mem::zeroed() };
+        recvmsg.msg_iov = &mut recviov;
+        recvmsg.msg_iovlen = 1;
+        assert_eq!(cage.recvmsg_syscall(socketpair.sock2, &mut recvmsg, 0), 4);
+        assert_eq!(&recvdata, b"abcd");
+        assert_ne!(recvmsg.msg_flags & MSG_TRUNC, 0);
+        assert_eq!(cage.recvmsg_syscall(socketpair.sock2, &mut recvmsg, 0), 2);
+        assert_eq!(&recvdata[..2], b"gh");
+        assert_eq!(recvmsg.msg_flags & MSG_TRUNC, 0);
+
+        // descriptors travel with the message they were sent with
+        let mut data = *b"fd";
+        let mut iov = interface::IovecStruct {
+            iov_base: data.as_mut_ptr() as *mut c_void,
+            iov_len: 2,
+        };
+        let mut control = [0u64; 4];
+        let mut msg: interface::MsgHdr = unsafe { std::mem::zeroed() };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut c_void;
+        msg.msg_controllen = unsafe { libc::CMSG_SPACE(size_of::<i32>() as u32) } as usize;
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_level = SOL_SOCKET;
+            (*cmsg).cmsg_type = SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(size_of::<i32>() as u32) as usize;
+            *(libc::CMSG_DATA(cmsg) as *mut i32) = 1;
+        }
+        assert_eq!(cage.sendmsg_syscall(socketpair.sock1, &msg, 0), 2);
+        let mut recvcontrol = [0u64; 4];
+        recvmsg.msg_control = recvcontrol.as_mut_ptr() as *mut c_void;
+        recvmsg.msg_controllen = size_of::<[u64; 4]>();
+        assert_eq!(cage.recvmsg_syscall(socketpair.sock2, &mut recvmsg, 0), 2);
+        assert_eq!(&recvdata[..2], b"fd");
+        let stdoutfd = unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&recvmsg);
+            assert_eq!((*cmsg).cmsg_type, SCM_RIGHTS);
+            *(libc::CMSG_DATA(cmsg) as *const i32)
+        };
+        assert!(stdoutfd > 2);
+        assert_eq!(cage.close_syscall(stdoutfd), 0);
+
+        // once one end is closed the other reads end of file and can't send
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+        assert_eq!(
+            cage.recv_syscall(socketpair.sock2, buf.as_mut_ptr(), 4, 0),
+            0
+        );
+        assert_eq!(
+            cage.send_syscall(socketpair.sock2, str2cbuf("ab"), 2, MSG_NOSIGNAL),
+            -(Errno::EPIPE as i32)
+        );
+
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_unix_abstract() {
+        // this test checks that sockets bound to abstract addresses can be connected
+        // to from another cage, and that the name is free again once the socket
+        // is closed everywhere
+
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        let serversockfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+        assert!(serversockfd > 0);
+        let serversocket = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "\0lind.abstract".as_bytes(),
+        ));
+        assert_eq!(
+            serversocket.abstract_name(),
+            Some("lind.abstract".as_bytes())
+        );
+        assert_eq!(cage.bind_syscall(serversockfd, &serversocket), 0);
+        assert_eq!(cage.listen_syscall(serversockfd, 1), 0);
+
+        // the name is taken, but no file was created for it
+        let othersockfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+        assert_eq!(
+            cage.bind_syscall(othersockfd, &serversocket),
+            -(Errno::EADDRINUSE as i32)
+        );
+        // names are as long as the address, so a trailing null byte makes another one
+        let padsocket = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "\0lind.abstract\0".as_bytes(),
+        ));
+        assert_eq!(
+            padsocket.abstract_name(),
+            Some("lind.abstract\0".as_bytes())
+        );
+        assert_eq!(cage.close_syscall(othersockfd), 0);
+        let othersockfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+        assert_eq!(cage.bind_syscall(othersockfd, &padsocket), 0);
+        assert_eq!(cage.close_syscall(othersockfd), 0);
+        let mut statdata = StatData::default();
+        assert_eq!(
+            cage.stat_syscall("/lind.abstract", &mut statdata),
+            -(Errno::ENOENT as i32)
+        );
+
+        assert_eq!(cage.fork_syscall(2), 0);
+        let thread = interface::helper_thread(move || {
+            let cage2 = interface::cagetable_getref(2);
+            let clientsockfd = cage2.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+            assert!(clientsockfd > 0);
+            assert_eq!(cage2.connect_syscall(clientsockfd, &serversocket), 0);
+            assert_eq!(cage2.send_syscall(clientsockfd, str2cbuf("ping"), 4, 0), 4);
+            let mut buf = sizecbuf(4);
+            assert_eq!(cage2.recv_syscall(clientsockfd, buf.as_mut_ptr(), 4, 0), 4);
+            assert_eq!(cbuf2str(&buf), "pong");
+            assert_eq!(cage2.close_syscall(clientsockfd), 0);
+            // the child holds on to the listening socket it inherited until now
+            assert_eq!(cage2.close_syscall(serversockfd), 0);
+            assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        });
+
+        let mut clientaddr = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "".as_bytes(),
+        ));
+        let sockfd = cage.accept_syscall(serversockfd, &mut clientaddr);
+        assert!(sockfd > 0);
+        let mut buf = sizecbuf(4);
+        assert_eq!(cage.recv_syscall(sockfd, buf.as_mut_ptr(), 4, 0), 4);
+        assert_eq!(cbuf2str(&buf), "ping");
+        assert_eq!(cage.send_syscall(sockfd, str2cbuf("pong"), 4, 0), 4);
+        thread.join().unwrap();
+
+        // the accepted socket shares the address, but doesn't hold on to the name
+        let mut localaddr = clientaddr;
+        assert_eq!(cage.getsockname_syscall(sockfd, &mut localaddr), 0);
+        assert_eq!(localaddr, serversocket);
+        assert_eq!(cage.close_syscall(serversockfd), 0);
+        let newsockfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+        assert_eq!(cage.bind_syscall(newsockfd, &serversocket), 0);
+        // and now nothing listens there
+        let clientsockfd = cage.socket_syscall(AF_UNIX, SOCK_STREAM, 0);
+        let missingsocket = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "\0lind.missing".as_bytes(),
+        ));
+        assert_eq!(
+            cage.connect_syscall(clientsockfd, &missingsocket),
+            -(Errno::ECONNREFUSED as i32)
+        );
+
+        assert_eq!(cage.close_syscall(sockfd), 0);
+        assert_eq!(cage.close_syscall(newsockfd), 0);
+        assert_eq!(cage.close_syscall(clientsockfd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_unix_abstract_dgram() {
+        // this test checks sending datagrams to an abstract address and replying to
+        // a sender bound to one
+
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        let serversockfd = cage.socket_syscall(AF_UNIX, SOCK_DGRAM, 0);
+        let clientsockfd = cage.socket_syscall(AF_UNIX, SOCK_DGRAM, 0);
+        let serversocket = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "\0lind.server".as_bytes(),
+        ));
+        let clientsocket = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "\0lind.client".as_bytes(),
+        ));
+        assert_eq!(
+            cage.sendto_syscall(clientsockfd, str2cbuf("lost"), 4, 0, &serversocket),
+            -(Errno::ECONNREFUSED as i32)
+        );
+        assert_eq!(cage.bind_syscall(serversockfd, &serversocket), 0);
+        assert_eq!(cage.bind_syscall(clientsockfd, &clientsocket), 0);
+
+        assert_eq!(
+            cage.sendto_syscall(clientsockfd, str2cbuf("hello"), 5, 0, &serversocket),
+            5
+        );
+        let mut from = interface::GenSockaddr::Unix(interface::new_sockaddr_unix(
+            AF_UNIX as u16,
+            "".as_bytes(),
+        ));
+        let mut buf = sizecbuf(5);
+        assert_eq!(
+            cage.recvfrom_syscall(serversockfd, buf.as_mut_ptr(), 5, 0, &mut Some(&mut from)),
+            5
+        );
+        assert_eq!(cbuf2str(&buf), "hello");
+        assert_eq!(from, clientsocket);
+        assert_eq!(
+            cage.sendto_syscall(serversockfd, str2cbuf("world"), 5, 0, &from),
+            5
+        );
+        assert_eq!(cage.recv_syscall(clientsockfd, buf.as_mut_ptr(), 5, 0), 5);
+        assert_eq!(cbuf2str(&buf), "world");
+
+        // once the server is closed its name is gone
+        assert_eq!(cage.close_syscall(serversockfd), 0);
+        assert_eq!(
+            cage.sendto_syscall(clientsockfd, str2cbuf("lost"), 4, 0, &serversocket),
+            -(Errno::ECONNREFUSED as i32)
+        );
+
+        assert_eq!(cage.close_syscall(clientsockfd), 0);
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_socketpair_nonblocking() {
+        // this test is used for testing socketpair when nonblocking flag is set
+        // when nonblocking flag is set, the socket should not block on syscalls like
+        // recv_syscall, instead, EAGAIN error should be returned when there is no
+        // data to receive
+
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        let mut socketpair = interface::SockPair::default();
+
+        // try with nonblocking flag
+        assert_eq!(
+            Cage::socketpair_syscall(
+                cage.clone(),
+                AF_UNIX,
+                SOCK_STREAM | SOCK_NONBLOCK,
+                0,
+                &mut socketpair
+            ),
+            0
+        );
+
+        let cage2 = cage.clone();
+
+        let thread = interface::helper_thread(move || {
+            let mut buf = sizecbuf(10);
+            // counter is used for recording how many times do recv_syscall returned
+            // with EAGAIN
+            let mut counter = 0;
+
+            // receive the message
+            // since peer will sleep for 30ms before send the message
+            // some nonblocking returns are expected
+            loop {
+                let result = cage2.recv_syscall(socketpair.sock2, buf.as_mut_ptr(), 10, 0);
+                if result == -(Errno::EAGAIN as i32) {
+                    // return due to nonblocking flag
+                    counter += 1;
+                    continue;
+                }
+                if result != -libc::EINTR {
+                    break; // if the error was EINTR, retry the syscall
+                }
+            }
+            // check if the received message is correct
+            assert_eq!(cbuf2str(&buf), "test\0\0\0\0\0\0");
+            // check if there is any nonblocking return
+            assert_ne!(counter, 0);
+
+            // sleep for 30ms so receiver could have some nonblocking return
+            interface::sleep(interface::RustDuration::from_millis(30));
+            // send the message
+            assert_eq!(
+                cage2.send_syscall(socketpair.sock2, str2cbuf("Socketpair Test"), 15, 0),
+                15
+            );
+        });
+
+        let cage3 = cage.clone();
+
+        let thread_2 = interface::helper_thread(move || {
+            // sleep for 30ms so receiver could have some nonblocking return
+            interface::sleep(interface::RustDuration::from_millis(30));
+
+            // send message
+            assert_eq!(
+                cage3.send_syscall(socketpair.sock1, str2cbuf("test"), 4, 0),
+                4
+            );
+
+            // receive the message
+            // since peer will sleep for 30ms before send the message
+            // some nonblocking returns are expected
+            let mut buf2 = sizecbuf(15);
+            let mut counter = 0;
+            loop {
+                let result = cage3.recv_syscall(socketpair.sock1, buf2.as_mut_ptr(), 15, 0);
+                if result == -(Errno::EAGAIN as i32) {
+                    // return due to nonblocking flag
+                    counter += 1;
+                    continue;
+                }
+                if result != -libc::EINTR {
+                    break; // if the error was EINTR, retry the syscall
+                }
+            }
+            // check if the received message is correct
+            let str2 = cbuf2str(&buf2);
+            assert_eq!(str2, "Socketpair Test");
+            // check if there is any nonblocking return
+            assert_ne!(counter, 0);
+        });
+
+        thread.join().unwrap();
+        thread_2.join().unwrap();
+
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_udp_bad_bind() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+
+        let cage = interface::cagetable_getref(1);
+
+        let sockfd = cage.socket_syscall(AF_INET, SOCK_DGRAM, 0);
+        assert!(sockfd > 0); //checking that the sockfd is valid
+        let port: u16 = generate_random_port();
+
+        let sockaddr = interface::SockaddrV4 {
+            sin_family: AF_INET as u16,
+            sin_port: port.to_be(),
+            sin_addr: interface::V4Addr {
+                s_addr: u32::from_ne_bytes([127, 0, 0, 1]),
+            },
+            padding: 0,
+        };
+        let socket = interface::GenSockaddr::V4(sockaddr); //127.0.0.1
+        let port: u16 = generate_random_port();
+
+        let _sockaddr2 = interface::SockaddrV4 {
+            sin_family: AF_INET as u16,
+            sin_port: port.to_be(),
+            sin_addr: interface::V4Addr {
+                s_addr: u32::from_ne_bytes([127, 0, 0, 1]),
             },
             padding: 0,
         };
@@ -5085,7 +6059,7 @@ pub mod net_tests {
                 10,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: 10,
+                    data: EpollData { fd: 10 },
                 }
             ),
             -(Errno::EBADF as i32)
@@ -5098,7 +6072,7 @@ pub mod net_tests {
                 pipefds.readfd,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: pipefds.readfd,
+                    data: EpollData { fd: pipefds.readfd },
                 }
             ),
             -(Errno::EBADF as i32)
@@ -5112,7 +6086,7 @@ pub mod net_tests {
                 -1,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: -1,
+                    data: EpollData { fd: -1 },
                 }
             ),
             -(Errno::EBADF as i32)
@@ -5124,7 +6098,7 @@ pub mod net_tests {
                 pipefds.readfd,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: pipefds.readfd,
+                    data: EpollData { fd: pipefds.readfd },
                 }
             ),
             -(Errno::EBADF as i32)
@@ -5138,7 +6112,7 @@ pub mod net_tests {
                 pipefds.readfd,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: pipefds.readfd,
+                    data: EpollData { fd: pipefds.readfd },
                 }
             ),
             -(Errno::EINVAL as i32)
@@ -5152,7 +6126,7 @@ pub mod net_tests {
                 epfd,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: epfd,
+                    data: EpollData { fd: epfd },
                 }
             ),
             -(Errno::EINVAL as i32)
@@ -5166,7 +6140,7 @@ pub mod net_tests {
                 filefd,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: filefd,
+                    data: EpollData { fd: filefd },
                 }
             ),
             -(Errno::EPERM as i32)
@@ -5180,7 +6154,7 @@ pub mod net_tests {
                 pipefds.readfd,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: pipefds.readfd,
+                    data: EpollData { fd: pipefds.readfd },
                 }
             ),
             -(Errno::ENOENT as i32)
@@ -5194,7 +6168,7 @@ pub mod net_tests {
                 pipefds.readfd,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: pipefds.readfd,
+                    data: EpollData { fd: pipefds.readfd },
                 }
             ),
             -(Errno::ENOENT as i32)
@@ -5208,7 +6182,7 @@ pub mod net_tests {
                 pipefds.readfd,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: pipefds.readfd,
+                    data: EpollData { fd: pipefds.readfd },
                 }
             ),
             0
@@ -5222,7 +6196,7 @@ pub mod net_tests {
                 pipefds.readfd,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: pipefds.readfd,
+                    data: EpollData { fd: pipefds.readfd },
                 }
             ),
             -(Errno::EEXIST as i32)
@@ -5236,7 +6210,7 @@ pub mod net_tests {
                 pipefds.readfd,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: pipefds.readfd,
+                    data: EpollData { fd: pipefds.readfd },
                 }
             ),
             -(Errno::EINVAL as i32)
@@ -5279,7 +6253,7 @@ pub mod net_tests {
         let mut event_list: Vec<EpollEvent> = vec![
             EpollEvent {
                 events: EPOLLIN as u32,
-                fd: 0,
+                data: EpollData { fd: 0 },
             };
             2
         ];
@@ -5347,7 +6321,7 @@ pub mod net_tests {
                     pipefd.readfd,
                     &mut EpollEvent {
                         events: EPOLLIN as u32,
-                        fd: pipefd.readfd,
+                        data: EpollData { fd: pipefd.readfd },
                     }
                 ),
                 0
@@ -5360,7 +6334,13 @@ pub mod net_tests {
         // at this point, all pipes are added to epoll, and they should all be readable
 
         // prepare the event_list to store the return value
-        let mut event_list: Vec<EpollEvent> = vec![EpollEvent { events: 0, fd: 0 }; pipe_num];
+        let mut event_list: Vec<EpollEvent> = vec![
+            EpollEvent {
+                events: 0,
+                data: EpollData { fd: 0 },
+            };
+            pipe_num
+        ];
 
         // test #1: all the fds should be ready
         assert_eq!(
@@ -5378,7 +6358,7 @@ pub mod net_tests {
         let mut event_list: Vec<EpollEvent> = vec![
             EpollEvent {
                 events: 0xdeadbeef,
-                fd: 0,
+                data: EpollData { fd: 0 },
             };
             pipe_num
         ];
@@ -5390,7 +6370,7 @@ pub mod net_tests {
             if i < 5 {
                 assert_ne!(event_list[i].events & (EPOLLIN as u32), 0);
             } else {
-                assert_eq!(event_list[i].events, 0xdeadbeef);
+                assert_eq!({ event_list[i].events }, 0xdeadbeef);
             }
         }
 
@@ -5400,7 +6380,7 @@ pub mod net_tests {
         let mut event_list: Vec<EpollEvent> = vec![
             EpollEvent {
                 events: 0xdeadbeef,
-                fd: 0,
+                data: EpollData { fd: 0 },
             };
             pipe_num
         ];
@@ -5421,7 +6401,7 @@ pub mod net_tests {
             if i < 8 {
                 assert_ne!(event_list[i].events & (EPOLLIN as u32), 0);
             } else {
-                assert_eq!(event_list[i].events, 0xdeadbeef);
+                assert_eq!({ event_list[i].events }, 0xdeadbeef);
             }
         }
 
@@ -5430,7 +6410,7 @@ pub mod net_tests {
         let mut event_list: Vec<EpollEvent> = vec![
             EpollEvent {
                 events: 0xdeadbeef,
-                fd: 0,
+                data: EpollData { fd: 0 },
             };
             pipe_num
         ];
@@ -5443,7 +6423,7 @@ pub mod net_tests {
             if i < 5 {
                 assert_ne!(event_list[i].events & (EPOLLIN as u32), 0);
             } else {
-                assert_eq!(event_list[i].events, 0xdeadbeef);
+                assert_eq!({ event_list[i].events }, 0xdeadbeef);
             }
         }
 
@@ -5535,13 +6515,16 @@ pub mod net_tests {
                 sockfd,
                 &mut EpollEvent {
                     events: EPOLLIN as u32,
-                    fd: 123,
+                    data: EpollData { fd: 123 },
                 }
             ),
             0
         );
         // event_list used for holding return value
-        let mut event_list: Vec<EpollEvent> = vec![EpollEvent { events: 0, fd: 0 }];
+        let mut event_list: Vec<EpollEvent> = vec![EpollEvent {
+            events: 0,
+            data: EpollData { fd: 0 },
+        }];
 
         // this counter is used for recording how many times do select returns due to
         // timeout
@@ -5560,7 +6543,7 @@ pub mod net_tests {
             if epoll_result == 0 {
                 counter += 1;
             } else if event_list[0].events & (EPOLLIN as u32) != 0 {
-                assert_eq!(event_list[0].fd, 123); // fd field should remain touched
+                assert_eq!(unsafe { event_list[0].data.fd }, 123); // fd field should remain touched
                                                    // just received the message, check the message and break
                 let mut buf = sizecbuf(4);
                 assert_eq!(cage.recv_syscall(sockfd, buf.as_mut_ptr(), 4, 0), 4);
@@ -5590,6 +6573,258 @@ pub mod net_tests {
         lindrustfinalize();
     }
 
+    #[test]
+    pub fn ut_lind_net_epoll_edge_triggered() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        let mut pipefds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+        let epfd = cage.epoll_create_syscall(1);
+        assert!(epfd > 0);
+
+        // the whole data union is handed back, not just its fd
+        assert_eq!(
+            cage.epoll_ctl_syscall(
+                epfd,
+                EPOLL_CTL_ADD,
+                pipefds.readfd,
+                &EpollEvent {
+                    events: EPOLLIN as u32 | EPOLLET as u32,
+                    data: EpollData {
+                        u64: 0x1234_5678_9abc_def0
+                    },
+                }
+            ),
+            0
+        );
+        let mut event_list = vec![
+            EpollEvent {
+                events: 0,
+                data: EpollData { u64: 0 },
+            };
+            1
+        ];
+        let nowait = Some(interface::RustDuration::ZERO);
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 1, nowait), 0);
+
+        assert_eq!(cage.write_syscall(pipefds.writefd, str2cbuf("a"), 1), 1);
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 1, nowait), 1);
+        assert_eq!({ event_list[0].events }, EPOLLIN as u32);
+        assert_eq!(unsafe { event_list[0].data.u64 }, 0x1234_5678_9abc_def0);
+
+        // the data is still there, but nothing changed since it was reported
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 1, nowait), 0);
+
+        // a write from another thread wakes up a blocking wait
+        let cage2 = cage.clone();
+        let writer = interface::helper_thread(move || {
+            interface::sleep(interface::RustDuration::from_millis(50));
+            assert_eq!(cage2.write_syscall(pipefds.writefd, str2cbuf("b"), 1), 1);
+        });
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 1, None), 1);
+        assert_eq!({ event_list[0].events }, EPOLLIN as u32);
+        writer.join().unwrap();
+
+        // once level-triggered, the pipe is reported for as long as it has data
+        assert_eq!(
+            cage.epoll_ctl_syscall(
+                epfd,
+                EPOLL_CTL_MOD,
+                pipefds.readfd,
+                &EpollEvent {
+                    events: EPOLLIN as u32,
+                    data: EpollData { u64: 7 },
+                }
+            ),
+            0
+        );
+        for _ in 0..2 {
+            assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 1, nowait), 1);
+            assert_eq!(unsafe { event_list[0].data.u64 }, 7);
+        }
+        let mut buf = sizecbuf(2);
+        assert_eq!(cage.read_syscall(pipefds.readfd, buf.as_mut_ptr(), 2), 2);
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 1, nowait), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_epoll_oneshot() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        let mut pipefds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+        let epfd = cage.epoll_create_syscall(1);
+        let oneshot = EpollEvent {
+            events: EPOLLIN as u32 | EPOLLONESHOT as u32,
+            data: EpollData { fd: pipefds.readfd },
+        };
+        assert_eq!(
+            cage.epoll_ctl_syscall(epfd, EPOLL_CTL_ADD, pipefds.readfd, &oneshot),
+            0
+        );
+        assert_eq!(cage.write_syscall(pipefds.writefd, str2cbuf("a"), 1), 1);
+
+        let mut event_list = vec![
+            EpollEvent {
+                events: 0,
+                data: EpollData { fd: 0 },
+            };
+            1
+        ];
+        let nowait = Some(interface::RustDuration::ZERO);
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 1, nowait), 1);
+        assert_eq!(unsafe { event_list[0].data.fd }, pipefds.readfd);
+
+        // the item is disabled after reporting once, even as more data comes in
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 1, nowait), 0);
+        assert_eq!(cage.write_syscall(pipefds.writefd, str2cbuf("b"), 1), 1);
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 1, nowait), 0);
+
+        // until it is rearmed
+        assert_eq!(
+            cage.epoll_ctl_syscall(epfd, EPOLL_CTL_MOD, pipefds.readfd, &oneshot),
+            0
+        );
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 1, nowait), 1);
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 1, nowait), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_net_epoll_hangup() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        let epfd = cage.epoll_create_syscall(1);
+        let mut event_list = vec![
+            EpollEvent {
+                events: 0,
+                data: EpollData { fd: 0 },
+            };
+            2
+        ];
+        let nowait = Some(interface::RustDuration::ZERO);
+
+        // the read end of a pipe hangs up once the write end is closed, and the write
+        // end reports an error once the read end is closed
+        let mut pipefds = PipeArray {
+            readfd: -1,
+            writefd: -1,
+        };
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+        assert_eq!(
+            cage.epoll_ctl_syscall(
+                epfd,
+                EPOLL_CTL_ADD,
+                pipefds.readfd,
+                &EpollEvent {
+                    events: EPOLLIN as u32,
+                    data: EpollData { fd: pipefds.readfd },
+                }
+            ),
+            0
+        );
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 2, nowait), 0);
+        assert_eq!(cage.close_syscall(pipefds.writefd), 0);
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 2, nowait), 1);
+        assert_eq!({ event_list[0].events }, EPOLLHUP as u32);
+        assert_eq!(cage.close_syscall(pipefds.readfd), 0);
+
+        assert_eq!(cage.pipe_syscall(&mut pipefds), 0);
+        assert_eq!(
+            cage.epoll_ctl_syscall(
+                epfd,
+                EPOLL_CTL_ADD,
+                pipefds.writefd,
+                &EpollEvent {
+                    events: EPOLLOUT as u32 | EPOLLET as u32,
+                    data: EpollData {
+                        fd: pipefds.writefd
+                    },
+                }
+            ),
+            0
+        );
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 2, nowait), 1);
+        assert_eq!({ event_list[0].events }, EPOLLOUT as u32);
+        assert_eq!(cage.close_syscall(pipefds.readfd), 0);
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 2, nowait), 1);
+        assert_eq!({ event_list[0].events }, EPOLLOUT as u32 | EPOLLERR as u32);
+        assert_eq!(cage.close_syscall(pipefds.writefd), 0);
+
+        // a unix domain socket reports EPOLLRDHUP once its peer is gone
+        let mut socketpair = interface::SockPair::default();
+        assert_eq!(
+            Cage::socketpair_syscall(cage.clone(), AF_UNIX, SOCK_STREAM, 0, &mut socketpair),
+            0
+        );
+        assert_eq!(
+            cage.epoll_ctl_syscall(
+                epfd,
+                EPOLL_CTL_ADD,
+                socketpair.sock1,
+                &EpollEvent {
+                    events: EPOLLIN as u32 | EPOLLRDHUP as u32 | EPOLLET as u32,
+                    data: EpollData {
+                        fd: socketpair.sock1
+                    },
+                }
+            ),
+            0
+        );
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 2, nowait), 0);
+        assert_eq!(
+            cage.send_syscall(socketpair.sock2, str2cbuf("test"), 4, 0),
+            4
+        );
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 2, nowait), 1);
+        assert_eq!({ event_list[0].events }, EPOLLIN as u32);
+        assert_eq!(cage.close_syscall(socketpair.sock2), 0);
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 2, nowait), 1);
+        assert_eq!(
+            { event_list[0].events },
+            EPOLLIN as u32 | EPOLLRDHUP as u32 | EPOLLHUP as u32
+        );
+
+        // closing a file descriptor takes it out of the interest list
+        assert_eq!(cage.close_syscall(socketpair.sock1), 0);
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 2, nowait), 0);
+        assert_eq!(
+            cage.epoll_ctl_syscall(
+                epfd,
+                EPOLL_CTL_DEL,
+                socketpair.sock1,
+                &EpollEvent {
+                    events: 0,
+                    data: EpollData { fd: 0 },
+                }
+            ),
+            -(Errno::EBADF as i32)
+        );
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
     #[test]
     #[ignore]
     pub fn ut_lind_net_epoll() {
@@ -5664,7 +6899,7 @@ pub mod net_tests {
             serversockfd,
             &mut EpollEvent {
                 events: EPOLLIN as u32,
-                fd: serversockfd,
+                data: EpollData { fd: serversockfd },
             },
         );
 
@@ -5674,7 +6909,9 @@ pub mod net_tests {
             serversockfd_unix,
             &mut EpollEvent {
                 events: EPOLLIN as u32,
-                fd: serversockfd_unix,
+                data: EpollData {
+                    fd: serversockfd_unix,
+                },
             },
         );
 
@@ -5684,7 +6921,7 @@ pub mod net_tests {
             pipefds.readfd,
             &mut EpollEvent {
                 events: EPOLLIN as u32,
-                fd: pipefds.readfd,
+                data: EpollData { fd: pipefds.readfd },
             },
         );
 
@@ -5830,8 +7067,13 @@ pub mod net_tests {
         barrier.wait();
 
         let event_list_size = 5;
-        let mut event_list: Vec<EpollEvent> =
-            vec![EpollEvent { events: 0, fd: 0 }; event_list_size];
+        let mut event_list: Vec<EpollEvent> = vec![
+            EpollEvent {
+                events: 0,
+                data: EpollData { fd: 0 },
+            };
+            event_list_size
+        ];
         // acting as the server and processing the request
         // Server loop to handle connections and I/O
         // Check for any activity in any of the Input sockets
@@ -5846,18 +7088,20 @@ pub mod net_tests {
             assert!(num_events >= 0); // check for error
 
             for event in &mut event_list[..num_events as usize] {
+                // we registered each file descriptor with its number as the data
+                let eventfd = unsafe { event.data.fd };
                 // Check for any activity in the input socket and if there are events ready for
                 // reading
                 if event.events & (EPOLLIN as u32) != 0 {
                     // If the socket returned was listener socket, then there's a new connection
-                    if event.fd == serversockfd {
+                    if eventfd == serversockfd {
                         let mut sockgarbage =
                             interface::GenSockaddr::V4(interface::SockaddrV4::default());
-                        let sockfd = cage.accept_syscall(event.fd as i32, &mut sockgarbage);
+                        let sockfd = cage.accept_syscall(eventfd as i32, &mut sockgarbage);
                         assert!(sockfd > 0);
                         let event = interface::EpollEvent {
                             events: EPOLLIN as u32 | EPOLLOUT as u32,
-                            fd: sockfd,
+                            data: EpollData { fd: sockfd },
                         };
                         // Error raised to indicate that the socket file descriptor couldn't be
                         // added to the epoll instance
@@ -5865,16 +7109,16 @@ pub mod net_tests {
                             cage.epoll_ctl_syscall(epfd, EPOLL_CTL_ADD, sockfd, &event),
                             0
                         );
-                    } else if event.fd == serversockfd_unix {
+                    } else if eventfd == serversockfd_unix {
                         // unix socket
                         let mut sockgarbage = interface::GenSockaddr::Unix(
                             interface::new_sockaddr_unix(AF_UNIX as u16, "".as_bytes()),
                         );
-                        let sockfd = cage.accept_syscall(event.fd as i32, &mut sockgarbage);
+                        let sockfd = cage.accept_syscall(eventfd as i32, &mut sockgarbage);
                         assert!(sockfd > 0);
                         let event = interface::EpollEvent {
                             events: EPOLLIN as u32 | EPOLLOUT as u32,
-                            fd: sockfd,
+                            data: EpollData { fd: sockfd },
                         };
                         // Error raised to indicate that the socket file descriptor couldn't be
                         // added to the epoll instance
@@ -5882,7 +7126,7 @@ pub mod net_tests {
                             cage.epoll_ctl_syscall(epfd, EPOLL_CTL_ADD, sockfd, &event),
                             0
                         );
-                    } else if event.fd == pipefds.readfd {
+                    } else if eventfd == pipefds.readfd {
                         // pipe
                         let mut buf = sizecbuf(4);
                         // read the message from peer
@@ -5901,8 +7145,11 @@ pub mod net_tests {
                             cage.epoll_ctl_syscall(
                                 epfd,
                                 EPOLL_CTL_DEL,
-                                event.fd,
-                                &EpollEvent { events: 0, fd: 0 }
+                                eventfd,
+                                &EpollEvent {
+                                    events: 0,
+                                    data: EpollData { fd: 0 },
+                                }
                             ),
                             0
                         );
@@ -5913,7 +7160,7 @@ pub mod net_tests {
                         let mut recvresult: i32;
                         loop {
                             // receive message from peer
-                            recvresult = cage.recv_syscall(event.fd as i32, buf.as_mut_ptr(), 4, 0);
+                            recvresult = cage.recv_syscall(eventfd as i32, buf.as_mut_ptr(), 4, 0);
                             if recvresult != -libc::EINTR {
                                 break; // if the error was EINTR, retry the
                                        // syscall
@@ -5925,13 +7172,16 @@ pub mod net_tests {
                             }
                         } else if recvresult == -libc::ECONNRESET {
                             // peer closed the connection
-                            assert_eq!(cage.close_syscall(event.fd as i32), 0);
+                            assert_eq!(cage.close_syscall(eventfd as i32), 0);
                             assert_eq!(
                                 cage.epoll_ctl_syscall(
                                     epfd,
                                     EPOLL_CTL_DEL,
-                                    event.fd,
-                                    &EpollEvent { events: 0, fd: 0 }
+                                    eventfd,
+                                    &EpollEvent {
+                                        events: 0,
+                                        data: EpollData { fd: 0 },
+                                    }
                                 ),
                                 0
                             );
@@ -5940,17 +7190,17 @@ pub mod net_tests {
                 }
                 if event.events & (EPOLLOUT as u32) != 0 {
                     // Data is sent out this socket, it's no longer ready for writing
-                    assert_eq!(
-                        cage.send_syscall(event.fd as i32, str2cbuf("test"), 4, 0),
-                        4
-                    );
+                    assert_eq!(cage.send_syscall(eventfd as i32, str2cbuf("test"), 4, 0), 4);
                     // remove the fd
                     assert_eq!(
                         cage.epoll_ctl_syscall(
                             epfd,
                             EPOLL_CTL_DEL,
-                            event.fd,
-                            &EpollEvent { events: 0, fd: 0 }
+                            eventfd,
+                            &EpollEvent {
+                                events: 0,
+                                data: EpollData { fd: 0 },
+                            }
                         ),
                         0
                     );
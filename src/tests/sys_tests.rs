@@ -136,4 +136,896 @@ pub mod sys_tests {
         assert_eq!(cage1.exec_syscall(2), 0);
         lindrustfinalize();
     }
+
+    #[test]
+    pub fn ut_lind_exec_keeps_credentials() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage1 = interface::cagetable_getref(1);
+        // Run the first cage as the superuser so that its child may switch users
+        cage1
+            .getuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        cage1
+            .geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        assert_eq!(cage1.fork_syscall(2), 0);
+        let child_cage = interface::cagetable_getref(2);
+        assert_eq!(child_cage.setuid_syscall(3000), 0);
+
+        // The ids the child switched to are kept by exec instead of being reset,
+        // so it can't regain its privileges that way
+        assert_eq!(child_cage.exec_syscall(3), 0);
+        let execed_cage = interface::cagetable_getref(3);
+        assert_eq!(execed_cage.getuid_syscall(), 3000);
+        assert_eq!(execed_cage.geteuid_syscall(), 3000);
+        // while ids that still hold their default value are reset for the loader
+        assert_eq!(execed_cage.getgid_syscall(), -1);
+        assert_eq!(execed_cage.getgid_syscall(), DEFAULT_GID as i32);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_wait_exit_status() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.fork_syscall(2), 0);
+        assert_eq!(cage.fork_syscall(3), 0);
+
+        // The child's exit status is kept until the parent reaps it
+        let cage2 = interface::cagetable_getref(2);
+        assert_eq!(cage2.exit_syscall(3), 3);
+        let mut status = 0;
+        assert_eq!(cage.waitpid_syscall(2, Some(&mut status), 0), 2);
+        assert_eq!(status, 3 << 8);
+        // and a child can only be reaped once
+        assert_eq!(
+            cage.waitpid_syscall(2, Some(&mut status), 0),
+            -(Errno::ECHILD as i32)
+        );
+
+        // Only the low 8 bits of the status are reported
+        assert_eq!(cage.waitpid_syscall(3, None, WNOHANG), 0);
+        let cage3 = interface::cagetable_getref(3);
+        assert_eq!(cage3.exit_syscall(0x1ff), 0x1ff);
+        assert_eq!(cage.wait_syscall(Some(&mut status)), 3);
+        assert_eq!(status, 0xff << 8);
+
+        // No children are left to wait for
+        assert_eq!(cage.wait_syscall(None), -(Errno::ECHILD as i32));
+        assert_eq!(
+            cage.waitpid_syscall(-1, None, WNOHANG),
+            -(Errno::ECHILD as i32)
+        );
+        assert_eq!(cage.waitpid_syscall(-1, None, 0x8), -(Errno::EINVAL as i32));
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_wait_blocks_until_exit() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.fork_syscall(2), 0);
+
+        let thread = interface::helper_thread(move || {
+            interface::sleep(interface::RustDuration::from_millis(100));
+            let cage2 = interface::cagetable_getref(2);
+            assert_eq!(cage2.exit_syscall(7), 7);
+        });
+
+        // The parent is woken up once its child exits
+        let mut status = 0;
+        let mut rusage = interface::Rusage::default();
+        assert_eq!(
+            cage.wait4_syscall(-1, Some(&mut status), 0, Some(&mut rusage)),
+            2
+        );
+        assert_eq!(status, 7 << 8);
+        thread.join().unwrap();
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_wait_signaled_and_stopped() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.fork_syscall(2), 0);
+        let cage2 = interface::cagetable_getref(2);
+
+        // A stopped child is only reported with WUNTRACED
+        cage2.notify_stopped(SIGSTOP);
+        let mut status = 0;
+        assert_eq!(cage.waitpid_syscall(2, Some(&mut status), WNOHANG), 0);
+        assert_eq!(
+            cage.waitpid_syscall(2, Some(&mut status), WNOHANG | WUNTRACED),
+            2
+        );
+        assert_eq!(status, (SIGSTOP << 8) | 0x7f);
+
+        // A stop that was never collected is dropped once the child dies, and the
+        // signal that killed it is reported
+        cage2.notify_stopped(SIGTSTP);
+        cage2.terminate_by_signal(SIGKILL);
+        assert_eq!(cage.waitpid_syscall(-1, Some(&mut status), WUNTRACED), 2);
+        assert_eq!(status, SIGKILL);
+        assert_eq!(cage.wait_syscall(None), -(Errno::ECHILD as i32));
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_wait_process_group() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.fork_syscall(2), 0);
+        assert_eq!(cage.fork_syscall(3), 0);
+        // Move the second child into a process group of its own
+        let cage3 = interface::cagetable_getref(3);
+        cage3.pgid.store(3, interface::RustAtomicOrdering::Relaxed);
+        assert_eq!(cage3.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+
+        // Waiting on our own process group doesn't see the exited child
+        assert_eq!(cage.waitpid_syscall(0, None, WNOHANG), 0);
+        assert_eq!(
+            cage.waitpid_syscall(-4, None, WNOHANG),
+            -(Errno::ECHILD as i32)
+        );
+        assert_eq!(cage.waitpid_syscall(-3, None, WNOHANG), 3);
+
+        let cage2 = interface::cagetable_getref(2);
+        assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        assert_eq!(cage.waitpid_syscall(0, None, 0), 2);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_wait_rusage_per_cage() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.fork_syscall(2), 0);
+        let cage2 = interface::cagetable_getref(2);
+        assert_eq!(cage2.fork_syscall(3), 0);
+
+        // Pretend that threads of the grandchild and the child which have already
+        // finished used some time
+        let spent = interface::Rusage {
+            ru_utime: interface::TimeVal {
+                tv_sec: 5,
+                tv_usec: 600_000,
+            },
+            ru_nvcsw: 10,
+            ..Default::default()
+        };
+        interface::cagetable_getref(3)
+            .rusage
+            .lock()
+            .accumulate(&spent);
+        cage2.rusage.lock().accumulate(&spent);
+
+        // The child accounts for its own threads and for the grandchild it reaped
+        let cage3 = interface::cagetable_getref(3);
+        assert_eq!(cage3.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        assert_eq!(cage2.wait_syscall(None), 3);
+        assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+
+        let mut rusage = interface::Rusage::default();
+        assert_eq!(cage.wait4_syscall(2, None, 0, Some(&mut rusage)), 2);
+        assert!(rusage.ru_utime.tv_sec >= 11);
+        assert!(rusage.ru_utime.tv_usec < 1_000_000);
+        assert!(rusage.ru_nvcsw >= 20);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_wait_orphans_reparented() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.fork_syscall(2), 0);
+        let cage2 = interface::cagetable_getref(2);
+        assert_eq!(cage2.fork_syscall(3), 0);
+        assert_eq!(cage2.fork_syscall(4), 0);
+        let cage4 = interface::cagetable_getref(4);
+        assert_eq!(cage4.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+
+        // The init cage adopts the children of an exiting cage, while the zombies
+        // of its children are reaped with it
+        assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        let cage3 = interface::cagetable_getref(3);
+        assert_eq!(cage3.getppid_syscall(), 1);
+        assert_eq!(
+            cage.waitpid_syscall(4, None, WNOHANG),
+            -(Errno::ECHILD as i32)
+        );
+        assert_eq!(cage.waitpid_syscall(2, None, 0), 2);
+
+        // An adopted orphan is reaped by the init cage as soon as it exits
+        assert_eq!(cage.waitpid_syscall(3, None, WNOHANG), 0);
+        assert_eq!(cage3.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        assert_eq!(cage.wait_syscall(None), -(Errno::ECHILD as i32));
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_pgid_sid_inherited() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.getpgrp_syscall(), 1);
+        assert_eq!(cage.getsid_syscall(0), 1);
+
+        // A forked child shares the process group and session of its parent
+        assert_eq!(cage.fork_syscall(2), 0);
+        assert_eq!(cage.getpgid_syscall(2), 1);
+        assert_eq!(cage.getsid_syscall(2), 1);
+
+        // The child becomes a process group leader, and its own children join its group
+        let cage2 = interface::cagetable_getref(2);
+        assert_eq!(cage2.setpgid_syscall(0, 0), 0);
+        assert_eq!(cage2.getpgrp_syscall(), 2);
+        assert_eq!(cage2.fork_syscall(3), 0);
+        let cage3 = interface::cagetable_getref(3);
+        assert_eq!(cage3.getpgid_syscall(0), 2);
+        assert_eq!(cage3.getsid_syscall(0), 1);
+
+        // exec keeps both ids
+        assert_eq!(cage3.exec_syscall(4), 0);
+        assert_eq!(cage.getpgid_syscall(4), 2);
+        assert_eq!(cage.getsid_syscall(4), 1);
+
+        assert_eq!(cage.getpgid_syscall(3), -(Errno::ESRCH as i32));
+        assert_eq!(cage.getsid_syscall(-5), -(Errno::ESRCH as i32));
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_setpgid_setsid_errors() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.fork_syscall(2), 0);
+        assert_eq!(cage.fork_syscall(3), 0);
+        let cage2 = interface::cagetable_getref(2);
+        let cage3 = interface::cagetable_getref(3);
+
+        assert_eq!(cage.setpgid_syscall(-1, 0), -(Errno::EINVAL as i32));
+        assert_eq!(cage.setpgid_syscall(2, -1), -(Errno::EINVAL as i32));
+        // Only the caller and its children can be moved
+        assert_eq!(cage.setpgid_syscall(5, 0), -(Errno::ESRCH as i32));
+        assert_eq!(cage2.setpgid_syscall(3, 0), -(Errno::ESRCH as i32));
+        // and only into an existing group unless it's a new one named after the cage
+        assert_eq!(cage.setpgid_syscall(2, 7), -(Errno::EPERM as i32));
+        assert_eq!(cage.setpgid_syscall(2, 0), 0);
+        assert_eq!(cage.setpgid_syscall(3, 2), 0);
+        assert_eq!(cage3.getpgrp_syscall(), 2);
+
+        // A process group leader can't start a new session
+        assert_eq!(cage2.setsid_syscall(), -(Errno::EPERM as i32));
+        // neither can a cage whose id is in use as a process group
+        assert_eq!(cage3.setpgid_syscall(0, 0), 0);
+        assert_eq!(cage2.setpgid_syscall(0, 3), 0);
+        assert_eq!(cage3.setpgid_syscall(0, 2), -(Errno::EPERM as i32));
+        assert_eq!(cage3.setsid_syscall(), -(Errno::EPERM as i32));
+        assert_eq!(cage2.setsid_syscall(), 2);
+        assert_eq!(cage2.getsid_syscall(0), 2);
+        assert_eq!(cage2.getpgrp_syscall(), 2);
+
+        // A session leader can't be moved, and cages of other sessions can't be joined
+        assert_eq!(cage.setpgid_syscall(2, 1), -(Errno::EPERM as i32));
+        assert_eq!(cage.setpgid_syscall(3, 2), -(Errno::EPERM as i32));
+        assert_eq!(cage2.fork_syscall(4), 0);
+        assert_eq!(cage2.setpgid_syscall(4, 1), -(Errno::EPERM as i32));
+        assert_eq!(cage.setpgid_syscall(0, 0), -(Errno::EPERM as i32));
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_kill_process_groups() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        assert_eq!(cage.fork_syscall(2), 0);
+        assert_eq!(cage.fork_syscall(3), 0);
+        assert_eq!(cage.setpgid_syscall(3, 0), 0);
+        for cageid in 1..4 {
+            interface::cagetable_getref(cageid).main_threadid.store(
+                interface::get_pthreadid(),
+                interface::RustAtomicOrdering::Relaxed,
+            );
+        }
+        let cage2 = interface::cagetable_getref(2);
+        let cage3 = interface::cagetable_getref(3);
+
+        // The null signal checks which cages a broadcast reaches
+        assert_eq!(cage.kill_syscall(0, 0), 0);
+        assert_eq!(cage.kill_syscall(-3, 0), 0);
+        assert_eq!(cage.kill_syscall(-2, 0), -(Errno::ESRCH as i32));
+        assert_eq!(cage.kill_syscall(-1, 0), 0);
+        assert_eq!(cage.kill_syscall(4, 0), -(Errno::ESRCH as i32));
+        assert_eq!(cage.kill_syscall(1, -1), -(Errno::EINVAL as i32));
+        assert_eq!(
+            cage.kill_syscall(1, SIGNAL_MAX + 1),
+            -(Errno::EINVAL as i32)
+        );
+
+        // Broadcasting to everyone else leaves out the init cage
+        assert_eq!(cage3.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        assert_eq!(cage2.kill_syscall(-1, 0), -(Errno::ESRCH as i32));
+
+        // Cages of other users can only be signaled by the superuser
+        cage.getuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        cage.geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        assert_eq!(cage.fork_syscall(4), 0);
+        let cage4 = interface::cagetable_getref(4);
+        cage4.main_threadid.store(
+            interface::get_pthreadid(),
+            interface::RustAtomicOrdering::Relaxed,
+        );
+        assert_eq!(cage4.setuid_syscall(2000), 0);
+        assert_eq!(cage4.kill_syscall(2, 0), -(Errno::EPERM as i32));
+        assert_eq!(cage4.kill_syscall(-1, 0), -(Errno::EPERM as i32));
+        assert_eq!(cage4.kill_syscall(4, 0), 0);
+        assert_eq!(cage2.kill_syscall(4, 0), -(Errno::EPERM as i32));
+        assert_eq!(cage.kill_syscall(4, 0), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_rlimit_get_set() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        let mut rlimit = Rlimit::default();
+        assert_eq!(cage.getrlimit_syscall(RLIMIT_NOFILE, &mut rlimit), 0);
+        assert_eq!((rlimit.rlim_cur, rlimit.rlim_max), (NOFILE_CUR, NOFILE_MAX));
+        assert_eq!(cage.getrlimit_syscall(RLIMIT_AS, &mut rlimit), 0);
+        assert_eq!(
+            (rlimit.rlim_cur, rlimit.rlim_max),
+            (RLIM_INFINITY, RLIM_INFINITY)
+        );
+        assert_eq!(
+            cage.getrlimit_syscall(RLIM_NLIMITS as u64, &mut rlimit),
+            -(Errno::EINVAL as i32)
+        );
+
+        // The soft limit may move up to the hard limit, which may only be lowered
+        let lowered = Rlimit {
+            rlim_cur: 1 << 20,
+            rlim_max: 1 << 30,
+        };
+        assert_eq!(cage.setrlimit_syscall(RLIMIT_AS, &lowered), 0);
+        let raised = Rlimit {
+            rlim_cur: 1 << 30,
+            rlim_max: 1 << 30,
+        };
+        assert_eq!(cage.setrlimit_syscall(RLIMIT_AS, &raised), 0);
+        let invalid = Rlimit {
+            rlim_cur: 1 << 31,
+            rlim_max: 1 << 30,
+        };
+        assert_eq!(
+            cage.setrlimit_syscall(RLIMIT_AS, &invalid),
+            -(Errno::EINVAL as i32)
+        );
+        let unprivileged = Rlimit {
+            rlim_cur: 1 << 30,
+            rlim_max: 1 << 31,
+        };
+        assert_eq!(
+            cage.setrlimit_syscall(RLIMIT_AS, &unprivileged),
+            -(Errno::EPERM as i32)
+        );
+
+        // Limits are inherited across fork and exec
+        assert_eq!(cage.fork_syscall(2), 0);
+        let cage2 = interface::cagetable_getref(2);
+        assert_eq!(cage2.exec_syscall(3), 0);
+        let cage3 = interface::cagetable_getref(3);
+        assert_eq!(cage3.getrlimit_syscall(RLIMIT_AS, &mut rlimit), 0);
+        assert_eq!(rlimit, raised);
+
+        // prlimit sets the limits of another cage and returns the old ones
+        let mut old = Rlimit::default();
+        assert_eq!(
+            cage.prlimit64_syscall(3, RLIMIT_AS, Some(&lowered), Some(&mut old)),
+            0
+        );
+        assert_eq!(old, raised);
+        assert_eq!(cage3.getrlimit_syscall(RLIMIT_AS, &mut rlimit), 0);
+        assert_eq!(rlimit, lowered);
+        assert_eq!(
+            cage.prlimit64_syscall(2, RLIMIT_AS, None, Some(&mut old)),
+            -(Errno::ESRCH as i32)
+        );
+
+        // The superuser may raise hard limits, but not beyond what the cage supports
+        cage.geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        assert_eq!(cage.setrlimit_syscall(RLIMIT_AS, &unprivileged), 0);
+        let toomanyfiles = Rlimit {
+            rlim_cur: NOFILE_CUR,
+            rlim_max: NOFILE_MAX + 1,
+        };
+        assert_eq!(
+            cage.setrlimit_syscall(RLIMIT_NOFILE, &toomanyfiles),
+            -(Errno::EPERM as i32)
+        );
+
+        // Other users' cages are off limits to unprivileged cages
+        assert_eq!(cage3.setuid_syscall(DEFAULT_UID), 0);
+        assert_eq!(cage.fork_syscall(4), 0);
+        let cage4 = interface::cagetable_getref(4);
+        assert_eq!(cage4.setuid_syscall(2000), 0);
+        assert_eq!(
+            cage3.prlimit64_syscall(4, RLIMIT_AS, None, Some(&mut old)),
+            -(Errno::EPERM as i32)
+        );
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_rlimit_nproc() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+
+        // The user may run two cages, so one more can be forked
+        let limit = Rlimit {
+            rlim_cur: 2,
+            rlim_max: 2,
+        };
+        assert_eq!(cage.setrlimit_syscall(RLIMIT_NPROC, &limit), 0);
+        assert_eq!(cage.fork_syscall(2), 0);
+        assert_eq!(cage.fork_syscall(3), -(Errno::EAGAIN as i32));
+        let cage2 = interface::cagetable_getref(2);
+        assert_eq!(cage2.fork_syscall(3), -(Errno::EAGAIN as i32));
+
+        // Exited cages no longer count
+        assert_eq!(cage2.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        assert_eq!(cage.fork_syscall(3), 0);
+
+        // The limit doesn't apply to the superuser
+        cage.geteuid
+            .store(ROOT_UID as i32, interface::RustAtomicOrdering::Relaxed);
+        assert_eq!(cage.fork_syscall(4), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_eventfd() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        let mut value = 0u64;
+        let counter = &mut value as *mut u64;
+        let valueptr = counter as *mut u8;
+
+        assert_eq!(cage.eventfd_syscall(0, 0x100), -(Errno::EINVAL as i32));
+
+        // a read returns the whole counter and resets it
+        let efd = cage.eventfd_syscall(3, EFD_NONBLOCK);
+        assert!(efd > 0);
+        assert_eq!(cage.read_syscall(efd, valueptr, 8), 8);
+        assert_eq!(value, 3);
+        assert_eq!(cage.read_syscall(efd, valueptr, 8), -(Errno::EAGAIN as i32));
+        assert_eq!(cage.read_syscall(efd, valueptr, 4), -(Errno::EINVAL as i32));
+
+        // writes add to the counter, up to 0xfffffffffffffffe
+        unsafe { *counter = 5 };
+        assert_eq!(cage.write_syscall(efd, valueptr, 8), 8);
+        assert_eq!(cage.write_syscall(efd, valueptr, 8), 8);
+        unsafe { *counter = u64::MAX };
+        assert_eq!(
+            cage.write_syscall(efd, valueptr, 8),
+            -(Errno::EINVAL as i32)
+        );
+        unsafe { *counter = u64::MAX - 10 };
+        assert_eq!(
+            cage.write_syscall(efd, valueptr, 8),
+            -(Errno::EAGAIN as i32)
+        );
+        assert_eq!(cage.read_syscall(efd, valueptr, 8), 8);
+        assert_eq!(value, 10);
+        assert_eq!(cage.close_syscall(efd), 0);
+
+        // a semaphore eventfd is taken from one at a time
+        let efd = cage.eventfd_syscall(2, EFD_SEMAPHORE | EFD_NONBLOCK);
+        assert_eq!(cage.read_syscall(efd, valueptr, 8), 8);
+        assert_eq!(value, 1);
+        assert_eq!(cage.read_syscall(efd, valueptr, 8), 8);
+        assert_eq!(value, 1);
+        assert_eq!(cage.read_syscall(efd, valueptr, 8), -(Errno::EAGAIN as i32));
+        assert_eq!(cage.close_syscall(efd), 0);
+
+        // a blocking read waits for another thread to write to the same descriptor
+        let efd = cage.eventfd_syscall(0, 0);
+        let cage2 = cage.clone();
+        let writer = interface::helper_thread(move || {
+            interface::sleep(interface::RustDuration::from_millis(50));
+            let mut value = 7u64;
+            assert_eq!(
+                cage2.write_syscall(efd, &mut value as *mut u64 as *const u8, 8),
+                8
+            );
+        });
+        assert_eq!(cage.read_syscall(efd, valueptr, 8), 8);
+        assert_eq!(value, 7);
+        writer.join().unwrap();
+        assert_eq!(cage.close_syscall(efd), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_timerfd() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        let mut expirations = 0u64;
+        let expirationsptr = &mut expirations as *mut u64 as *mut u8;
+        let millis = |ms: i64| interface::TimeSpec {
+            tv_sec: 0,
+            tv_nsec: ms * 1_000_000,
+        };
+
+        assert_eq!(cage.timerfd_create_syscall(7, 0), -(Errno::EINVAL as i32));
+        let tfd = cage.timerfd_create_syscall(CLOCK_MONOTONIC, TFD_NONBLOCK);
+        assert!(tfd > 0);
+
+        // a disarmed timer never expires
+        let mut curr = interface::ITimerSpec::default();
+        assert_eq!(cage.timerfd_gettime_syscall(tfd, &mut curr), 0);
+        assert_eq!(curr.it_value.tv_sec, 0);
+        assert_eq!(curr.it_value.tv_nsec, 0);
+        assert_eq!(
+            cage.read_syscall(tfd, expirationsptr, 8),
+            -(Errno::EAGAIN as i32)
+        );
+
+        // a one-shot timer expires once
+        let setting = interface::ITimerSpec {
+            it_interval: millis(0),
+            it_value: millis(50),
+        };
+        assert_eq!(
+            cage.timerfd_settime_syscall(tfd, 0, Some(&setting), None),
+            0
+        );
+        assert_eq!(cage.timerfd_gettime_syscall(tfd, &mut curr), 0);
+        assert!(curr.it_value.tv_nsec > 0 && curr.it_value.tv_nsec <= 50_000_000);
+        assert_eq!(
+            cage.read_syscall(tfd, expirationsptr, 8),
+            -(Errno::EAGAIN as i32)
+        );
+        interface::sleep(interface::RustDuration::from_millis(80));
+        assert_eq!(cage.read_syscall(tfd, expirationsptr, 8), 8);
+        assert_eq!(expirations, 1);
+        assert_eq!(
+            cage.read_syscall(tfd, expirationsptr, 8),
+            -(Errno::EAGAIN as i32)
+        );
+
+        // a periodic timer counts every expiration since it was last read, and
+        // replacing its setting returns the previous one
+        let setting = interface::ITimerSpec {
+            it_interval: millis(20),
+            it_value: millis(20),
+        };
+        assert_eq!(
+            cage.timerfd_settime_syscall(tfd, 0, Some(&setting), None),
+            0
+        );
+        interface::sleep(interface::RustDuration::from_millis(110));
+        assert_eq!(cage.read_syscall(tfd, expirationsptr, 8), 8);
+        assert!(expirations >= 4);
+        let mut old = interface::ITimerSpec::default();
+        let disarm = interface::ITimerSpec::default();
+        assert_eq!(
+            cage.timerfd_settime_syscall(tfd, 0, Some(&disarm), Some(&mut old)),
+            0
+        );
+        assert_eq!(old.it_interval.tv_nsec, 20_000_000);
+        assert!(old.it_value.tv_nsec > 0);
+
+        // an absolute time in the past expires right away
+        let setting = interface::ITimerSpec {
+            it_interval: millis(0),
+            it_value: interface::TimeSpec {
+                tv_sec: 1,
+                tv_nsec: 0,
+            },
+        };
+        assert_eq!(
+            cage.timerfd_settime_syscall(tfd, TFD_TIMER_ABSTIME, Some(&setting), None),
+            0
+        );
+        assert_eq!(cage.read_syscall(tfd, expirationsptr, 8), 8);
+        assert_eq!(expirations, 1);
+
+        // bad settings and descriptors
+        let setting = interface::ITimerSpec {
+            it_interval: millis(0),
+            it_value: millis(1000),
+        };
+        assert_eq!(
+            cage.timerfd_settime_syscall(tfd, 0, Some(&setting), None),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.timerfd_settime_syscall(tfd, 0, None, None),
+            -(Errno::EFAULT as i32)
+        );
+        assert_eq!(
+            cage.timerfd_gettime_syscall(0, &mut curr),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.write_syscall(tfd, expirationsptr, 8),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(cage.close_syscall(tfd), 0);
+
+        // a blocking read sleeps until the timer expires
+        let tfd = cage.timerfd_create_syscall(CLOCK_REALTIME, 0);
+        let setting = interface::ITimerSpec {
+            it_interval: millis(0),
+            it_value: millis(30),
+        };
+        assert_eq!(
+            cage.timerfd_settime_syscall(tfd, 0, Some(&setting), None),
+            0
+        );
+        assert_eq!(cage.read_syscall(tfd, expirationsptr, 8), 8);
+        assert_eq!(expirations, 1);
+        assert_eq!(cage.close_syscall(tfd), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_signalfd() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        // make this thread the main thread of the cage, the way the loader does
+        let pthreadid = interface::get_pthreadid();
+        cage.main_threadid
+            .store(pthreadid, interface::RustAtomicOrdering::Relaxed);
+        cage.sigset
+            .insert(pthreadid, interface::RustAtomicU64::new(0));
+        cage.pendingsigset
+            .insert(pthreadid, interface::RustAtomicU64::new(0));
+
+        // signals are only left pending while they are blocked
+        let mask = interface::lind_sigaddset(interface::lind_sigaddset(0, SIGUSR1), SIGUSR2);
+        assert_eq!(cage.sigprocmask_syscall(SIG_BLOCK, Some(&mask), None), 0);
+
+        let sfd = cage.signalfd_syscall(-1, Some(&mask), SFD_NONBLOCK);
+        assert!(sfd > 0);
+        let mut infos = [interface::SignalfdSiginfo::default(); 2];
+        let infosize = std::mem::size_of::<interface::SignalfdSiginfo>();
+        assert_eq!(infosize, 128);
+        let infosptr = infos.as_mut_ptr() as *mut u8;
+        assert_eq!(
+            cage.read_syscall(sfd, infosptr, 2 * infosize),
+            -(Errno::EAGAIN as i32)
+        );
+        assert_eq!(
+            cage.read_syscall(sfd, infosptr, infosize - 1),
+            -(Errno::EINVAL as i32)
+        );
+
+        // both pending signals are read at once, lowest numbered first
+        assert_eq!(cage.kill_syscall(1, SIGUSR2), 0);
+        assert_eq!(cage.kill_syscall(1, SIGUSR1), 0);
+        assert_eq!(
+            cage.read_syscall(sfd, infosptr, 2 * infosize),
+            2 * infosize as i32
+        );
+        assert_eq!(infos[0].ssi_signo, SIGUSR1 as u32);
+        assert_eq!(infos[1].ssi_signo, SIGUSR2 as u32);
+        // reading them means they aren't pending anymore
+        assert_eq!(cage.pending_signals(), 0);
+
+        // only one signal fits
+        assert_eq!(cage.kill_syscall(1, SIGUSR2), 0);
+        assert_eq!(cage.kill_syscall(1, SIGUSR1), 0);
+        assert_eq!(cage.read_syscall(sfd, infosptr, infosize), infosize as i32);
+        assert_eq!(infos[0].ssi_signo, SIGUSR1 as u32);
+
+        // the mask can be changed, leaving SIGUSR2 pending
+        let usr1 = interface::lind_sigaddset(0, SIGUSR1);
+        assert_eq!(cage.signalfd_syscall(sfd, Some(&usr1), 0), sfd);
+        assert_eq!(
+            cage.read_syscall(sfd, infosptr, infosize),
+            -(Errno::EAGAIN as i32)
+        );
+        assert_eq!(
+            cage.pending_signals(),
+            interface::lind_sigaddset(0, SIGUSR2)
+        );
+
+        // bad arguments
+        assert_eq!(cage.signalfd_syscall(-1, None, 0), -(Errno::EFAULT as i32));
+        assert_eq!(
+            cage.signalfd_syscall(-1, Some(&mask), 0x1),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(
+            cage.signalfd_syscall(0, Some(&mask), 0),
+            -(Errno::EINVAL as i32)
+        );
+        assert_eq!(cage.close_syscall(sfd), 0);
+
+        // a blocking read waits for a signal to arrive
+        let sfd = cage.signalfd_syscall(-1, Some(&usr1), 0);
+        let cage2 = cage.clone();
+        let sender = interface::helper_thread(move || {
+            interface::sleep(interface::RustDuration::from_millis(50));
+            assert_eq!(cage2.kill_syscall(1, SIGUSR1), 0);
+        });
+        assert_eq!(cage.read_syscall(sfd, infosptr, infosize), infosize as i32);
+        assert_eq!(infos[0].ssi_signo, SIGUSR1 as u32);
+        sender.join().unwrap();
+        assert_eq!(cage.close_syscall(sfd), 0);
+
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
+
+    #[test]
+    pub fn ut_lind_eventfd_timerfd_signalfd_readiness() {
+        //acquiring a lock on TESTMUTEX prevents other tests from running concurrently,
+        // and also performs clean env setup
+        let _thelock = setup::lock_and_init();
+        let cage = interface::cagetable_getref(1);
+        let pthreadid = interface::get_pthreadid();
+        cage.main_threadid
+            .store(pthreadid, interface::RustAtomicOrdering::Relaxed);
+        cage.sigset
+            .insert(pthreadid, interface::RustAtomicU64::new(0));
+        cage.pendingsigset
+            .insert(pthreadid, interface::RustAtomicU64::new(0));
+        let usr1 = interface::lind_sigaddset(0, SIGUSR1);
+        assert_eq!(cage.sigprocmask_syscall(SIG_BLOCK, Some(&usr1), None), 0);
+
+        let efd = cage.eventfd_syscall(0, EFD_NONBLOCK);
+        let tfd = cage.timerfd_create_syscall(CLOCK_MONOTONIC, TFD_NONBLOCK);
+        let sfd = cage.signalfd_syscall(-1, Some(&usr1), SFD_NONBLOCK);
+        let nowait = Some(interface::RustDuration::ZERO);
+
+        // nothing is readable yet, and only the eventfd is writable
+        let mut polled: Vec<interface::PollStruct> = [efd, tfd, sfd]
+            .iter()
+            .map(|&fd| interface::PollStruct {
+                fd,
+                events: POLLIN | POLLOUT,
+                revents: 0,
+            })
+            .collect();
+        assert_eq!(cage.poll_syscall(&mut polled, nowait), 1);
+        assert_eq!(polled[0].revents, POLLOUT);
+        assert_eq!(polled[1].revents, 0);
+        assert_eq!(polled[2].revents, 0);
+
+        let epfd = cage.epoll_create_syscall(1);
+        for fd in [efd, tfd, sfd] {
+            assert_eq!(
+                cage.epoll_ctl_syscall(
+                    epfd,
+                    EPOLL_CTL_ADD,
+                    fd,
+                    &EpollEvent {
+                        events: EPOLLIN as u32,
+                        data: EpollData { fd },
+                    }
+                ),
+                0
+            );
+        }
+        let mut event_list = vec![
+            EpollEvent {
+                events: 0,
+                data: EpollData { fd: 0 },
+            };
+            3
+        ];
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 3, nowait), 0);
+
+        // each of them becomes readable in its own way, which a blocking epoll_wait
+        // and select notice
+        let cage2 = cage.clone();
+        let writer = interface::helper_thread(move || {
+            interface::sleep(interface::RustDuration::from_millis(50));
+            let mut value = 1u64;
+            assert_eq!(
+                cage2.write_syscall(efd, &mut value as *mut u64 as *const u8, 8),
+                8
+            );
+        });
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 3, None), 1);
+        assert_eq!(unsafe { event_list[0].data.fd }, efd);
+        writer.join().unwrap();
+
+        let setting = interface::ITimerSpec {
+            it_interval: interface::TimeSpec::default(),
+            it_value: interface::TimeSpec {
+                tv_sec: 0,
+                tv_nsec: 30_000_000,
+            },
+        };
+        assert_eq!(
+            cage.timerfd_settime_syscall(tfd, 0, Some(&setting), None),
+            0
+        );
+        assert_eq!(cage.kill_syscall(1, SIGUSR1), 0);
+        interface::sleep(interface::RustDuration::from_millis(50));
+        assert_eq!(cage.epoll_wait_syscall(epfd, &mut event_list, 3, None), 3);
+
+        let readfds = &mut interface::FdSet::new();
+        for fd in [efd, tfd, sfd] {
+            readfds.set(fd);
+        }
+        assert_eq!(
+            cage.select_syscall(sfd + 1, Some(readfds), None, None, nowait),
+            3
+        );
+
+        assert_eq!(cage.poll_syscall(&mut polled, nowait), 3);
+        assert_eq!(polled[0].revents, POLLIN | POLLOUT);
+        assert_eq!(polled[1].revents, POLLIN);
+        assert_eq!(polled[2].revents, POLLIN);
+
+        for fd in [efd, tfd, sfd, epfd] {
+            assert_eq!(cage.close_syscall(fd), 0);
+        }
+        assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+        lindrustfinalize();
+    }
 }
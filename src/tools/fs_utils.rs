@@ -85,26 +85,130 @@ fn lind_ls(cage: &Cage, path: &str) {
     }
 }
 
+// Opens the image at path as a new image, refusing to touch one that already
+// holds anything
+fn new_image(path: &str) -> Option<interface::ImageBackend> {
+    if std::fs::metadata(path).map_or(false, |m| m.len() > 0) {
+        eprintln!("{} already exists!", path);
+        return None;
+    }
+    match interface::ImageBackend::open(path) {
+        Ok(image) => Some(image),
+        Err(e) => {
+            eprintln!("Could not create image {}: {}", path, e);
+            None
+        }
+    }
+}
+
+// Copies the whole file system from one storage backend into another
+fn lind_copy_fs(from: &dyn interface::StorageBackend, to: &dyn interface::StorageBackend) {
+    match copy_fs(from, to) {
+        Ok(report) => {
+            if !report.is_clean() {
+                eprintln!("{}", report);
+            }
+        }
+        Err(e) => eprintln!("Could not copy the file system: {}", e),
+    }
+}
+
+// Copies the file system in use into a new image
+fn lind_export(imagepath: &str) {
+    if let Some(image) = new_image(imagepath) {
+        lind_copy_fs(&*interface::storage_backend(), &image);
+    }
+}
+
+// Copies the file system in an image into the storage in use, which must not
+// hold one yet
+fn lind_import(imagepath: &str) {
+    if !interface::RustPath::new(imagepath).exists() {
+        eprintln!("No such image exists!");
+        return;
+    }
+    let dest = interface::storage_backend();
+    if let Ok(Some(_)) = dest.read_metadata() {
+        eprintln!("A lind file system already exists here!");
+        return;
+    }
+    match interface::ImageBackend::open(imagepath) {
+        Ok(image) => lind_copy_fs(&image, &*dest),
+        Err(e) => eprintln!("Could not open image {}: {}", imagepath, e),
+    }
+}
+
+fn lind_snapshot(action: &str, name: Option<&str>) {
+    let result = match (action, name) {
+        ("create", Some(name)) => snapshot_create(name).map(|info| {
+            println!(
+                "Took snapshot {} of {} files holding {} bytes",
+                info.name, info.files, info.bytes
+            )
+        }),
+        ("list", None) => snapshot_list().map(|snapshots| {
+            for info in snapshots {
+                println!(
+                    "{}\t{} files\t{} bytes\ttaken at {}",
+                    info.name,
+                    info.files,
+                    info.bytes,
+                    info.created / 1_000_000_000
+                );
+            }
+        }),
+        ("restore", Some(name)) => snapshot_restore(name),
+        ("delete", Some(name)) => snapshot_delete(name),
+        ("list", Some(_)) => panic!("snapshot list takes no name"),
+        _ => panic!("snapshot needs create, restore or delete and a name, or list"),
+    };
+    if let Err(e) = result {
+        eprintln!("Could not {} the snapshot: {}", action, e);
+    }
+}
+
 fn print_usage() {
     println!(
         "
-Usage: lind_fs_utils [commandname] [arguments...]
+Usage: lind_fs_utils [--image imagefile] [commandname] [arguments...]
+
+With --image, the commands work on the lind file system kept in imagefile instead of the one in
+the current directory (or in $LIND_FS_ROOT). Setting $LIND_FS_IMAGE to an image does the same,
+both here and for lind itself.
 
 Where commandname is one of the following:
 
+capacity [bytes] [inodes]       : Set how many bytes of file data and how many inodes the lind file
+                                  system can hold. Without arguments, prints the capacity and how
+                                  much of it is in use.
 cp [hostsource] [linddest]      : Copies files from the host file system into the lind filesystem.
                                   For example, cp bar/etc/passwd /etc/passwd will copy the
                                   former file in the host file system to the latter in lind's fs.
                                   Directories are handled recursively, cp bar/etc /etc/ will make a
                                   directory at /etc in the lind fs, and then populate it with all
                                   of the files in the root fs.
+dedup                           : Let files with identical contents share their data. Files copied
+                                  in with cp or update already do.
 deltree [linddir]               : Delete a directory on the lind file system and all it contains
+export [imagefile]              : Copy the whole lind file system into a new single-file image
 format                          : Make a new blank fs, removing the current one
 help                            : Print this message
+import [imagefile]              : Copy the whole lind file system in an image into the current
+                                  location, which must not hold a lind file system yet
 ls [lindpath]                   : List the contents of a lind file system directory
+mkimage [imagefile]             : Make a new single-file image holding a blank lind file system
+quota [uid] [bytes] [inodes]    : Limit the bytes and inodes charged to the files owned by uid,
+                                  where a limit of 0 means unlimited. Without the limits, prints
+                                  the quota of uid and how much of it is in use.
 mkdir [linddir1...]             : Create a lind file system directory (for each arg)
 rm [lindfile1...]               : Delete a file on the lind file system
 rmdir [linddir1...]             : Delete a directory on the lind file system
+snapshot create [name]          : Save the whole lind file system as a read-only snapshot. Data is
+                                  shared with the files rather than copied where possible.
+snapshot list                   : List the snapshots of the lind file system
+snapshot restore [name]         : Put the lind file system back the way it was when the snapshot
+                                  was taken, keeping all snapshots
+snapshot delete [name]          : Delete a snapshot
 tree [startlindpath]            : Print the lindfs file tree starting at the specified directory
                                   Assumes root directory if no starting path is specified.
 update [hostsource] [linddest]  : Copies files from the host file system into the lind filesystem.
@@ -120,12 +224,86 @@ update [hostsource] [linddest]  : Copies files from the host file system into th
 }
 
 fn main() {
+    let mut args = env::args().peekable();
+    args.next(); //first arg is executable, we don't care
+
+    if args.peek().map(String::as_str) == Some("--image") {
+        args.next();
+        let imagepath = args.next().expect("--image needs 1 argument");
+        match interface::ImageBackend::open(&imagepath) {
+            Ok(image) => interface::set_storage_backend(interface::RustRfc::new(image)),
+            Err(e) => {
+                eprintln!("Could not open image {}: {}", imagepath, e);
+                return;
+            }
+        }
+    }
+
+    let command = if let Some(cmd) = args.next() {
+        cmd
+    } else {
+        print_usage();
+        return; //print usage
+    };
+
+    // these work on whole file systems in storage rather than on a loaded one
+    match command.as_str() {
+        "mkimage" => {
+            let imagepath = args.next().expect("mkimage needs 1 argument");
+            args.next()
+                .and_then::<String, fn(String) -> Option<String>>(|_| {
+                    panic!("mkimage cannot take more than 1 argument")
+                });
+            if let Some(image) = new_image(&imagepath) {
+                // loading a file system from an empty image formats it
+                interface::set_storage_backend(interface::RustRfc::new(image));
+                lindrustinit(0);
+                lindrustfinalize();
+            }
+            return;
+        }
+
+        "export" => {
+            let imagepath = args.next().expect("export needs 1 argument");
+            args.next()
+                .and_then::<String, fn(String) -> Option<String>>(|_| {
+                    panic!("export cannot take more than 1 argument")
+                });
+            lind_export(&imagepath);
+            return;
+        }
+
+        "import" => {
+            let imagepath = args.next().expect("import needs 1 argument");
+            args.next()
+                .and_then::<String, fn(String) -> Option<String>>(|_| {
+                    panic!("import cannot take more than 1 argument")
+                });
+            lind_import(&imagepath);
+            return;
+        }
+
+        "snapshot" => {
+            let action = args.next().expect("snapshot needs at least 1 argument");
+            let name = args.next();
+            args.next()
+                .and_then::<String, fn(String) -> Option<String>>(|_| {
+                    panic!("snapshot cannot take more than 2 arguments")
+                });
+            lind_snapshot(&action, name.as_deref());
+            return;
+        }
+
+        _ => {}
+    }
+
     lindrustinit(0); // no verbosity
-    let mut args = env::args();
     let utilcage = Cage {
         cageid: 0,
         cwd: interface::RustLock::new(interface::RustRfc::new(interface::RustPathBuf::from("/"))),
-        parent: 0,
+        parent: interface::RustAtomicU64::new(0),
+        pgid: interface::RustAtomicU64::new(0),
+        sid: interface::RustAtomicU64::new(0),
         filedescriptortable: init_fdtable(),
         cancelstatus: interface::RustAtomicBool::new(false),
         getgid: interface::RustAtomicI32::new(-1),
@@ -142,14 +320,11 @@ fn main() {
         pendingsigset: interface::RustHashMap::new(),
         main_threadid: interface::RustAtomicU64::new(0),
         interval_timer: interface::IntervalTimer::new(0),
-    };
-
-    args.next(); //first arg is executable, we don't care
-    let command = if let Some(cmd) = args.next() {
-        cmd
-    } else {
-        print_usage();
-        return; //print usage
+        child_events: interface::Mutex::new(vec![]),
+        child_event_cv: interface::Condvar::new(),
+        rusage: interface::Mutex::new(interface::Rusage::default()),
+        adopted: interface::RustAtomicBool::new(false),
+        rlimits: interface::RustLock::new(init_rlimits()),
     };
 
     match command.as_str() {
@@ -157,6 +332,24 @@ fn main() {
             print_usage();
         }
 
+        "capacity" => {
+            if let Some(bytes) = args.next() {
+                let inodes = args.next().expect("capacity needs 0 or 2 arguments");
+                args.next()
+                    .and_then::<String, fn(String) -> Option<String>>(|_| {
+                        panic!("capacity cannot take more than 2 arguments")
+                    });
+                FS_METADATA.set_capacity(FsSpace {
+                    bytes: bytes.parse().expect("capacity in bytes must be a number"),
+                    inodes: inodes.parse().expect("capacity in inodes must be a number"),
+                });
+            } else {
+                let (capacity, used) = FS_METADATA.space_in_use();
+                println!("bytes: {} of {} in use", used.bytes, capacity.bytes);
+                println!("inodes: {} of {} in use", used.inodes, capacity.inodes);
+            }
+        }
+
         "cp" => {
             let source = args.next().expect("cp needs 2 arguments");
             let dest = args.next().expect("cp needs 2 arguments");
@@ -208,12 +401,49 @@ fn main() {
             let log = logobj.take().unwrap();
             let _close = log.close().unwrap();
             drop(logobj);
-            let _logremove = interface::removefile(LOGFILENAME.to_string());
+            let _logremove = interface::storage_backend().remove_log();
 
             format_fs();
             return;
         }
 
+        "quota" => {
+            let uid: u32 = args
+                .next()
+                .expect("quota needs 1 or 3 arguments")
+                .parse()
+                .expect("uid must be a number");
+            if let Some(bytes) = args.next() {
+                let inodes = args.next().expect("quota needs 1 or 3 arguments");
+                args.next()
+                    .and_then::<String, fn(String) -> Option<String>>(|_| {
+                        panic!("quota cannot take more than 3 arguments")
+                    });
+                FS_METADATA.set_quota(
+                    uid,
+                    FsSpace {
+                        bytes: bytes.parse().expect("quota in bytes must be a number"),
+                        inodes: inodes.parse().expect("quota in inodes must be a number"),
+                    },
+                );
+            } else {
+                let (quota, used) = FS_METADATA.quota_of(uid);
+                println!("bytes: {} of {} in use", used.bytes, quota.bytes);
+                println!("inodes: {} of {} in use", used.inodes, quota.inodes);
+            }
+        }
+
+        "dedup" => {
+            args.next()
+                .and_then::<String, fn(String) -> Option<String>>(|_| {
+                    panic!("dedup does not take arguments")
+                });
+            println!(
+                "{} files share their data with identical files",
+                lind_dedup()
+            );
+        }
+
         "deltree" => {
             let rootdir = args.next().expect("deltree needs 1 argument");
             args.next()
@@ -249,3 +479,86 @@ fn main() {
     }
     lindrustfinalize();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safeposix::syscalls::fs_constants::*;
+
+    // One backend of each kind, each on storage of its own
+    fn backends(
+        dir: &std::path::Path,
+        tag: &str,
+    ) -> Vec<interface::RustRfc<dyn interface::StorageBackend>> {
+        vec![
+            interface::RustRfc::new(
+                interface::HostDirBackend::new(dir.join(format!("{}.dir", tag))).unwrap(),
+            ),
+            interface::RustRfc::new(interface::MemoryBackend::new()),
+            interface::RustRfc::new(
+                interface::ImageBackend::open(dir.join(format!("{}.img", tag))).unwrap(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        interface::RUSTPOSIX_TESTSUITE.store(true, interface::RustAtomicOrdering::Relaxed);
+        let dir = tempfile::tempdir().unwrap();
+        let far: usize = 1 << 20;
+
+        for (i, source) in backends(dir.path(), "source").into_iter().enumerate() {
+            //a sparse file, and a file in a directory
+            interface::set_storage_backend(source);
+            lindrustinit(0);
+            let cage = interface::cagetable_getref(1);
+            let fd = cage.open_syscall("/sparse", O_CREAT | O_WRONLY, S_IRWXA);
+            assert!(fd >= 0);
+            assert_eq!(cage.write_syscall(fd, b"head".as_ptr(), 4), 4);
+            assert_eq!(cage.lseek_syscall(fd, far as isize, SEEK_SET), far as i32);
+            assert_eq!(cage.write_syscall(fd, b"tail".as_ptr(), 4), 4);
+            assert_eq!(cage.close_syscall(fd), 0);
+            assert_eq!(cage.mkdir_syscall("/dir", S_IRWXA), 0);
+            let fd = cage.open_syscall("/dir/small", O_CREAT | O_WRONLY, S_IRWXA);
+            assert!(fd >= 0);
+            assert_eq!(cage.write_syscall(fd, b"small".as_ptr(), 5), 5);
+            assert_eq!(cage.close_syscall(fd), 0);
+            assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+            lindrustfinalize();
+
+            let imagepath = dir.path().join(format!("export{}.img", i));
+            lind_export(imagepath.to_str().unwrap());
+
+            //whatever it is imported into holds the same files, with the hole kept
+            for dest in backends(dir.path(), &format!("dest{}", i)) {
+                interface::set_storage_backend(dest.clone());
+                lind_import(imagepath.to_str().unwrap());
+                lindrustinit(0);
+                let cage = interface::cagetable_getref(1);
+                let mut statdata = StatData::default();
+                assert_eq!(cage.stat_syscall("/sparse", &mut statdata), 0);
+                assert_eq!(statdata.st_size, far + 4);
+                let fd = cage.open_syscall("/sparse", O_RDONLY, 0);
+                let mut buf = [0u8; 4];
+                assert_eq!(cage.read_syscall(fd, buf.as_mut_ptr(), 4), 4);
+                assert_eq!(&buf, b"head");
+                assert_eq!(cage.lseek_syscall(fd, far as isize, SEEK_SET), far as i32);
+                assert_eq!(cage.read_syscall(fd, buf.as_mut_ptr(), 4), 4);
+                assert_eq!(&buf, b"tail");
+                assert_eq!(cage.close_syscall(fd), 0);
+                let data = dest
+                    .open_data(&format!("{}{}", FILEDATAPREFIX, statdata.st_ino), far + 4)
+                    .unwrap();
+                assert!(data.seek_data_or_hole(0, SEEK_HOLE).unwrap() < far);
+                data.close().unwrap();
+                let fd = cage.open_syscall("/dir/small", O_RDONLY, 0);
+                let mut buf = [0u8; 5];
+                assert_eq!(cage.read_syscall(fd, buf.as_mut_ptr(), 5), 5);
+                assert_eq!(&buf, b"small");
+                assert_eq!(cage.close_syscall(fd), 0);
+                assert_eq!(cage.exit_syscall(EXIT_SUCCESS), EXIT_SUCCESS);
+                lindrustfinalize();
+            }
+        }
+    }
+}
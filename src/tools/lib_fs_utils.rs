@@ -233,9 +233,35 @@ fn cp_into_lind(
 
     assert_eq!(cage.close_syscall(lindfd), 0);
 
+    //share the data with any identical file already in lind
+    let sysfilename = format!("{}{}", FILEDATAPREFIX, inode);
+    let _ = interface::storage_backend().dedup_data(&sysfilename, veclen);
+
     println!("Copied {:?} as {} ({})", hostfilepath, lindfilepath, inode);
 }
 
+//lets every regular file that is not open share the data of identical files,
+// returning how many files share their data with another one now
+pub fn lind_dedup() -> usize {
+    let files: Vec<(usize, usize)> = FS_METADATA
+        .inodetable
+        .iter()
+        .filter_map(|entry| match entry.value() {
+            Inode::File(f) => Some((*entry.key(), f.size)),
+            _ => None,
+        })
+        .collect();
+    let storage = interface::storage_backend();
+    files
+        .into_iter()
+        .filter(|(inodenum, size)| {
+            let sysfilename = format!("{}{}", FILEDATAPREFIX, inodenum);
+            !FILEOBJECTTABLE.contains_key(inodenum)
+                && storage.dedup_data(&sysfilename, *size).unwrap_or(false)
+        })
+        .count()
+}
+
 pub fn visit_children(
     cage: &Cage,
     path: &str,
@@ -283,9 +309,10 @@ pub fn visit_children(
                 [path, "/", filenamestr].join("")
             };
 
-            //stat to tell whether it's a directory
+            //stat to tell whether it's a directory, without following symbolic links so
+            // that a link to a directory is not descended into
             let mut lindstat_res: StatData = StatData::default();
-            let _stat_us = cage.stat_syscall(fullstatpath.as_str(), &mut lindstat_res);
+            let _stat_us = cage.lstat_syscall(fullstatpath.as_str(), &mut lindstat_res);
 
             //call the visitor function on the child path
             visitor(
@@ -301,7 +328,8 @@ pub fn visit_children(
 
 pub fn lind_deltree(cage: &Cage, path: &str) {
     let mut lindstat_res: StatData = StatData::default();
-    let stat_us = cage.stat_syscall(path, &mut lindstat_res);
+    //lstat so that symbolic links, including dangling ones, are removed themselves
+    let stat_us = cage.lstat_syscall(path, &mut lindstat_res);
 
     if stat_us == 0 {
         if !is_dir(lindstat_res.st_mode) {